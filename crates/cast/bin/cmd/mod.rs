@@ -17,4 +17,5 @@ pub mod rpc;
 pub mod run;
 pub mod send;
 pub mod storage;
+pub mod user_op;
 pub mod wallet;
@@ -0,0 +1,169 @@
+use alloy_primitives::{Address, Bytes, U256};
+use clap::Parser;
+use ethers_providers::Middleware;
+use eyre::Result;
+use foundry_cli::{
+    opts::EthereumOpts,
+    utils::{self, parse_ether_value},
+};
+use foundry_common::{
+    abi::{encode_function_args, get_func},
+    user_op::{encode_execute, Bundler, UserOperation, DEFAULT_EXECUTE_SIG, ENTRY_POINT_V06},
+};
+use foundry_config::Config;
+use std::time::Duration;
+
+/// CLI arguments for `cast 4337`.
+#[derive(Debug, Parser)]
+pub enum UserOpSubcommands {
+    /// Build, sign and submit a user operation to a bundler.
+    #[clap(visible_alias = "s")]
+    Send {
+        #[clap(flatten)]
+        op: UserOpArgs,
+
+        /// Only print the user operation hash and exit immediately.
+        #[clap(name = "async", long = "async", alias = "cast-async", env = "CAST_ASYNC")]
+        cast_async: bool,
+
+        /// The number of seconds to wait for the user operation to be included.
+        #[clap(long, default_value = "120", value_name = "SECONDS")]
+        timeout: u64,
+
+        /// Print the user operation receipt as JSON.
+        #[clap(long, short, help_heading = "Display options")]
+        json: bool,
+    },
+
+    /// Estimate the gas limits of a user operation with a bundler.
+    #[clap(visible_alias = "e")]
+    Estimate {
+        #[clap(flatten)]
+        op: UserOpArgs,
+
+        /// Print the estimate as JSON.
+        #[clap(long, short, help_heading = "Display options")]
+        json: bool,
+    },
+}
+
+/// The user operation to build: a call made by a smart account through its `execute` method.
+#[derive(Debug, Parser)]
+pub struct UserOpArgs {
+    /// The smart account sending the user operation.
+    sender: Address,
+
+    /// The destination of the call made by the account.
+    to: Address,
+
+    /// The signature of the function to call.
+    sig: Option<String>,
+
+    /// The arguments of the function to call.
+    args: Vec<String>,
+
+    /// The value sent by the account with the call, in wei or with a unit (e.g. `1ether`).
+    #[clap(long, value_parser = parse_ether_value)]
+    value: Option<U256>,
+
+    /// The signature of the account's method making the call, which takes the destination,
+    /// value and calldata.
+    #[clap(long, default_value = DEFAULT_EXECUTE_SIG, value_name = "SIG")]
+    execute_sig: String,
+
+    /// The code deploying the account, if it doesn't exist yet: the factory address followed
+    /// by the calldata of its deployment function.
+    #[clap(long, value_name = "HEX")]
+    init_code: Option<Bytes>,
+
+    /// The paymaster address followed by the data it expects, to have the operation sponsored.
+    #[clap(long, value_name = "HEX")]
+    paymaster_and_data: Option<Bytes>,
+
+    /// The EntryPoint contract handling the user operation.
+    #[clap(long, default_value = ENTRY_POINT_V06, value_name = "ADDRESS")]
+    entry_point: Address,
+
+    /// The RPC endpoint of the bundler.
+    #[clap(long, env = "BUNDLER_URL", value_name = "URL")]
+    bundler_url: String,
+
+    #[clap(flatten)]
+    eth: EthereumOpts,
+}
+
+impl UserOpSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Self::Send { op, cast_async, timeout, json } => {
+                let bundler = Bundler::new(&op.bundler_url, op.entry_point)?;
+                let (user_op, chain_id) = op.build().await?;
+                let signer = op.eth.wallet.signer(chain_id).await?;
+                let hash = bundler.send(user_op, &signer, chain_id).await?;
+                if cast_async {
+                    println!("{hash}");
+                    return Ok(())
+                }
+
+                let receipt = bundler.wait_for_receipt(hash, Duration::from_secs(timeout)).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&receipt)?);
+                } else {
+                    let field = |name: &str| receipt.get(name).cloned().unwrap_or_default();
+                    let tx_hash =
+                        receipt.pointer("/receipt/transactionHash").cloned().unwrap_or_default();
+                    println!("userOpHash           {hash}");
+                    println!("success              {}", field("success"));
+                    println!("actualGasCost        {}", field("actualGasCost"));
+                    println!("actualGasUsed        {}", field("actualGasUsed"));
+                    println!("transactionHash      {tx_hash}");
+                }
+            }
+            Self::Estimate { op, json } => {
+                let bundler = Bundler::new(&op.bundler_url, op.entry_point)?;
+                let (user_op, _) = op.build().await?;
+                let gas = bundler.estimate_gas(&user_op).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&gas)?);
+                } else {
+                    println!("preVerificationGas   {}", gas.pre_verification_gas);
+                    println!("verificationGasLimit {}", gas.verification_gas_limit);
+                    println!("callGasLimit         {}", gas.call_gas_limit);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl UserOpArgs {
+    /// Builds the unsigned user operation with the account's next nonce and the chain's current
+    /// fees, returning it with the chain ID.
+    async fn build(&self) -> Result<(UserOperation, u64)> {
+        let config = Config::from(&self.eth);
+        let provider = utils::get_provider(&config)?;
+        let chain_id = provider.get_chainid().await?.as_u64();
+
+        let calldata = match &self.sig {
+            Some(sig) => {
+                let func = get_func(sig)?;
+                let mut calldata = func.selector().to_vec();
+                calldata.extend(encode_function_args(&func, &self.args)?);
+                calldata
+            }
+            None => vec![],
+        };
+        let call_data = encode_execute(
+            &self.execute_sig,
+            self.to,
+            self.value.unwrap_or_default(),
+            calldata.into(),
+        )?;
+
+        let mut user_op =
+            UserOperation::new(&provider, self.entry_point, self.sender, call_data).await?;
+        user_op.init_code = self.init_code.clone().unwrap_or_default();
+        user_op.paymaster_and_data = self.paymaster_and_data.clone().unwrap_or_default();
+        Ok((user_op, chain_id))
+    }
+}
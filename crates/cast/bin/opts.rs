@@ -1,7 +1,8 @@
 use crate::cmd::{
     access_list::AccessListArgs, bind::BindArgs, call::CallArgs, create2::Create2Args,
     estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs, logs::LogsArgs,
-    rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs, wallet::WalletSubcommands,
+    rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs, user_op::UserOpSubcommands,
+    wallet::WalletSubcommands,
 };
 use alloy_primitives::{Address, B256, U256};
 use clap::{Parser, Subcommand, ValueHint};
@@ -436,6 +437,13 @@ pub enum CastSubcommand {
     #[clap(name = "send", visible_alias = "s")]
     SendTx(SendTxArgs),
 
+    /// Build, estimate and send ERC-4337 user operations through a bundler.
+    #[clap(name = "4337", visible_alias = "userop")]
+    UserOp {
+        #[clap(subcommand)]
+        command: UserOpSubcommands,
+    },
+
     /// Publish a raw transaction to the network.
     #[clap(name = "publish", visible_alias = "p")]
     PublishTx {
@@ -108,6 +108,18 @@ pub enum Group {
     ///
     /// Safety: safe.
     Json,
+    /// Utility cheatcodes that deal with parsing values from and converting values to TOML.
+    ///
+    /// Examples: `parseToml`, `parseTomlUint`, `writeToml`.
+    ///
+    /// Safety: safe.
+    Toml,
+    /// Utility cheatcodes that deal with parsing values from YAML.
+    ///
+    /// Examples: `parseYaml`, `parseYamlUint`, `parseYamlKeys`.
+    ///
+    /// Safety: safe.
+    Yaml,
     /// Generic, uncategorized utilities.
     ///
     /// Examples: `toString`, `parse*`, `serialize*`.
@@ -130,6 +142,8 @@ impl Group {
             Self::Environment |
             Self::String |
             Self::Json |
+            Self::Toml |
+            Self::Yaml |
             Self::Utilities => Some(Safety::Safe),
         }
     }
@@ -145,6 +159,8 @@ impl Group {
             Self::Environment => "environment",
             Self::String => "string",
             Self::Json => "json",
+            Self::Toml => "toml",
+            Self::Yaml => "yaml",
             Self::Utilities => "utilities",
         }
     }
@@ -63,6 +63,16 @@ interface Vm {
         Extcodecopy,
     }
 
+    /// The layout of a Merkle tree built by the Merkle cheatcodes. Pairs of nodes are always hashed with
+    /// `keccak256` of the sorted pair.
+    enum MerkleTreeKind {
+        /// Leaves are paired level by level in the given order, and a last odd node is paired with zero,
+        /// as in murky.
+        Sorted,
+        /// Leaves are sorted and laid out in a complete binary tree, as in `@openzeppelin/merkle-tree`.
+        OpenZeppelin,
+    }
+
     /// An Ethereum log. Returned by `getRecordedLogs`.
     struct Log {
         /// The topics of the log, including the signature, if any.
@@ -161,6 +171,14 @@ interface Vm {
         bytes stderr;
     }
 
+    /// The result of a `httpGet` or `httpPost` call.
+    struct HttpResponse {
+        /// The HTTP status code of the response.
+        uint256 status;
+        /// The response body.
+        bytes body;
+    }
+
     /// Information on the chain and fork.
     struct ChainInfo {
         /// The fork identifier. Set to zero if no fork is active.
@@ -221,6 +239,51 @@ interface Vm {
         bool reverted;
     }
 
+    /// The outcome of a call executed by `diffCall`.
+    struct DiffCallResult {
+        /// If the call succeeded.
+        bool success;
+        /// The data returned by the call, or its revert data.
+        bytes returnData;
+        /// The logs emitted during the call.
+        Log[] logs;
+        /// The storage slots changed by the call, sorted by account and slot.
+        StorageAccess[] storageChanges;
+    }
+
+    /// An entry of an EIP-2930 access list.
+    struct AccessListItem {
+        /// The account to warm up.
+        address account;
+        /// The storage slots of the account to warm up.
+        bytes32[] storageKeys;
+    }
+
+    /// A storage variable changed during a `startStateDiffRecording` session, decoded with the
+    /// storage layout of its contract.
+    struct DecodedStorageChange {
+        /// The account whose storage was changed.
+        address account;
+        /// The identifier of the contract deployed at the account, i.e. `Counter.sol:Counter`.
+        /// Empty if no artifact matches its code.
+        string contractName;
+        /// The storage slot holding the variable.
+        bytes32 slot;
+        /// The path of the variable, i.e. `owner`, `balances[0x...]` or `config.fee`.
+        /// Empty if the slot could not be decoded.
+        string label;
+        /// The Solidity type of the variable, i.e. `uint256`. Empty if the slot could not be decoded.
+        string typeName;
+        /// The value of the slot before the session.
+        bytes32 previousValue;
+        /// The value of the slot at the end of the session.
+        bytes32 newValue;
+        /// The value of the variable before the session, formatted according to its type.
+        string decodedPreviousValue;
+        /// The value of the variable at the end of the session, formatted according to its type.
+        string decodedNewValue;
+    }
+
     // ======== EVM ========
 
     /// Gets the address for a given private key.
@@ -239,6 +302,10 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Safe)]
     function load(address target, bytes32 slot) external view returns (bytes32 data);
 
+    /// Loads a transient storage slot from an address.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function tload(address account, bytes32 slot) external view returns (bytes32 data);
+
     /// Load a genesis JSON file's `allocs` into the in-memory revm state.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function loadAllocs(string calldata pathToAllocsJson) external;
@@ -251,6 +318,20 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Safe)]
     function signP256(uint256 privateKey, bytes32 digest) external pure returns (bytes32 r, bytes32 s);
 
+    /// Signs `message` with the BLS12-381 `privateKey`, using the proof of possession scheme of the Ethereum
+    /// consensus layer. Returns the signature as a G2 point in the EIP-2537 encoding.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function signBls(uint256 privateKey, bytes calldata message) external pure returns (bytes memory signature);
+
+    /// Derives the BLS12-381 public key of `privateKey`, as a G1 point in the EIP-2537 encoding.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function blsPublicKey(uint256 privateKey) external pure returns (bytes memory publicKey);
+
+    /// Derives a BLS12-381 private key from at least 32 bytes of input keying material, following the
+    /// `KeyGen` procedure of the IETF BLS signature draft.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function deriveBlsKey(bytes calldata ikm) external pure returns (uint256 privateKey);
+
     // -------- Record Storage --------
 
     /// Records all storage reads and writes.
@@ -270,6 +351,13 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Safe)]
     function stopAndReturnStateDiff() external returns (AccountAccess[] memory accountAccesses);
 
+    /// Returns the storage variables changed during a `vm.startStateDiffRecording` session, decoded
+    /// with the storage layouts of the project's artifacts, which requires
+    /// `extra_output = ["storageLayout"]` and read access to the artifacts directory.
+    /// Slots which cannot be decoded are returned with an empty label.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function stopAndReturnDecodedStateDiff() external returns (DecodedStorageChange[] memory changes);
+
     // -------- Recording Map Writes --------
 
     /// Starts recording all map SSTOREs for later retrieval.
@@ -353,6 +441,12 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function deal(address account, uint256 newBalance) external;
 
+    /// Sets the ERC20 `token` balance of `to` to `amount`, without changing the total supply.
+    /// The storage slot of the balance is found by simulating `balanceOf(to)` and probing the storage slots
+    /// it reads, so it supports Solidity and Vyper tokens, balances packed with other values, and proxies.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function dealERC20(address token, address to, uint256 amount) external;
+
     /// Sets an address' code.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function etch(address target, bytes calldata newRuntimeBytecode) external;
@@ -373,14 +467,22 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function store(address target, bytes32 slot, bytes32 value) external;
 
+    /// Stores a value to an address' transient storage slot.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function tstore(address account, bytes32 slot, bytes32 value) external;
+
     /// Marks the slots of an account and the account address as cold.
     #[cheatcode(group = Evm, safety = Unsafe, status = Experimental)]
     function cool(address target) external;
 
+    /// Marks an address and one of its storage slots as warm.
+    #[cheatcode(group = Evm, safety = Unsafe, status = Experimental)]
+    function warmSlot(address target, bytes32 slot) external;
+
     // -------- Call Manipulation --------
     // --- Mocks ---
 
-    /// Clears all mocked calls.
+    /// Clears all mocked calls, mocked functions and mocked precompile outputs.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function clearMockedCalls() external;
 
@@ -396,6 +498,14 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function mockCall(address callee, uint256 msgValue, bytes calldata data, bytes calldata returnData) external;
 
+    /// Mocks a call to an address, returning specified data, where only the calldata bits set in
+    /// `mask` must match. `mask` must have the same length as `data`.
+    /// E.g. to mock `transfer(address,uint256)` to a fixed recipient for any amount, pass a mask with
+    /// the bytes of the selector and the recipient set to `0xff`, and the amount bytes set to `0x00`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockCallMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata returnData)
+        external;
+
     /// Reverts a call to an address with specified revert data.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function mockCallRevert(address callee, bytes calldata data, bytes calldata revertData) external;
@@ -405,6 +515,23 @@ interface Vm {
     function mockCallRevert(address callee, uint256 msgValue, bytes calldata data, bytes calldata revertData)
         external;
 
+    /// Whenever a call is made to `callee` with calldata `data`, this cheatcode instead calls
+    /// `target` with the same calldata, returning (or reverting with) the data it produces.
+    /// Useful to compute mocked return data from state, e.g. with `target` being the test contract.
+    /// Calldata can either be strict or a partial match, like `mockCall`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockFunction(address callee, address target, bytes calldata data) external;
+
+    /// Mocks the output of a precompile for calls with exactly the given input.
+    /// Calls with other input are still executed by the precompile, unless it's disabled with `disablePrecompile`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function mockPrecompile(address precompile, bytes calldata input, bytes calldata output) external;
+
+    /// Disables a precompile, so that calls to it succeed without output like calls to an account without code,
+    /// except for the calls mocked with `mockPrecompile`.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function disablePrecompile(address precompile) external;
+
     // --- Impersonation (pranks) ---
 
     /// Sets the *next* call's `msg.sender` to be the input address.
@@ -423,6 +550,17 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function startPrank(address msgSender, address txOrigin) external;
 
+    /// Sets all subsequent calls' `tx.origin` to be the input address until `stopPrank` is called, leaving
+    /// `msg.sender` untouched.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function startPrankOrigin(address txOrigin) external;
+
+    /// Sets `msg.sender` to be the input address for the calls made `depth` call frames below the *next* call.
+    /// E.g. a `depth` of 0 is equivalent to `prank`, and a `depth` of 1 applies to the calls made by the callee
+    /// of the next call.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function prankDepth(address msgSender, uint64 depth) external;
+
     /// Resets subsequent calls' `msg.sender` to be `address(this)`.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function stopPrank() external;
@@ -469,6 +607,19 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function deleteSnapshots() external;
 
+    /// Executes the same calldata against `a` and `b` from the current state, and returns the outcome of each call,
+    /// e.g. to test an optimized implementation against a reference one.
+    /// The calls are made by the caller of `diffCall` in separate simulated transactions, in which cheatcodes are not
+    /// applied, and their state changes are discarded.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function diffCall(address a, address b, bytes calldata data) external returns (DiffCallResult memory resultA, DiffCallResult memory resultB);
+
+    /// Simulates a call to `to` with `data` by the caller from the current state, and returns the EIP-2930 access list
+    /// of the accounts and storage slots it touches, sorted by account and slot.
+    /// The caller, `to` and precompiles are not included, since they are always warm.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function accessList(address to, bytes calldata data) external returns (AccessListItem[] memory accessList);
+
     // -------- Forking --------
     // --- Creation and Selection ---
 
@@ -476,7 +627,14 @@ interface Vm {
     #[cheatcode(group = Evm, safety = Unsafe)]
     function activeFork() external view returns (uint256 forkId);
 
+    /// Returns the number of the latest L1 block known to the L2 chain that's forked, read from the `L1Block` predeploy
+    /// on OP Stack chains and from `block.number` on Arbitrum chains. Reverts on other chains.
+    #[cheatcode(group = Evm, safety = Safe)]
+    function l1BlockNumber() external view returns (uint256 blockNumber);
+
     /// Creates a new fork with the given endpoint and the _latest_ block and returns the identifier of the fork.
+    /// The endpoint can also be the path to a JSON state dump written by `anvil --dump-state` or `dumpState`,
+    /// which is forked without a network connection.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function createFork(string calldata urlOrAlias) external returns (uint256 forkId);
     /// Creates a new fork with the given endpoint and block and returns the identifier of the fork.
@@ -488,6 +646,8 @@ interface Vm {
     function createFork(string calldata urlOrAlias, bytes32 txHash) external returns (uint256 forkId);
 
     /// Creates and also selects a new fork with the given endpoint and the latest block and returns the identifier of the fork.
+    /// The endpoint can also be the path to a JSON state dump written by `anvil --dump-state` or `dumpState`,
+    /// which is forked without a network connection.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function createSelectFork(string calldata urlOrAlias) external returns (uint256 forkId);
     /// Creates and also selects a new fork with the given endpoint and block and returns the identifier of the fork.
@@ -512,6 +672,10 @@ interface Vm {
     /// Updates the given fork to block number of the given transaction and replays all transaction mined before it in the block.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function rollFork(uint256 forkId, bytes32 txHash) external;
+    /// Updates the currently active fork to the state between two transactions of a block. This will `rollFork` with the
+    /// number of the block's parent and replays the block's transactions before the given index.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function rollFork(bytes32 blockHash, uint256 txIndex) external;
 
     /// Takes a fork identifier created by `createFork` and sets the corresponding forked state as active.
     #[cheatcode(group = Evm, safety = Unsafe)]
@@ -523,6 +687,16 @@ interface Vm {
     /// Fetches the given transaction from the given fork and executes it on the current state.
     #[cheatcode(group = Evm, safety = Unsafe)]
     function transact(uint256 forkId, bytes32 txHash) external;
+    /// Fetches the given transaction from the active fork and executes it on the current state, with the given sender,
+    /// value and calldata instead of the transaction's. The zero address keeps the transaction's sender and empty
+    /// `newData` keeps its calldata.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function transactWithOverrides(bytes32 txHash, address newSender, uint256 newValue, bytes calldata newData) external;
+
+    /// Copies the code, balance, nonce and all storage slots the source fork has touched of `account` into
+    /// the target fork. Storage the source fork has never read stays unset on the target fork.
+    #[cheatcode(group = Evm, safety = Unsafe)]
+    function copyAccount(uint256 fromForkId, uint256 toForkId, address account) external;
 
     /// Performs an Ethereum JSON-RPC request to the current fork URL.
     #[cheatcode(group = Evm, safety = Safe)]
@@ -593,6 +767,68 @@ interface Vm {
     #[cheatcode(group = Testing, safety = Safe)]
     function assume(bool condition) external pure;
 
+    /// Discard this run's fuzz inputs and generate new ones if `key` was already used by a previous
+    /// run of the same test.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assumeUnique(bytes32 key) external;
+
+    /// Adds a value to the dictionary the fuzzer samples inputs from. Meant to be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function addFuzzFixture(bytes32 value) external;
+
+    /// Adds a `uint256` value to the dictionary the fuzzer samples inputs from. Meant to be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function addFuzzFixture(uint256 value) external;
+
+    /// Adds an `int256` value to the dictionary the fuzzer samples inputs from. Meant to be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function addFuzzFixture(int256 value) external;
+
+    /// Adds an `address` value to the dictionary the fuzzer samples inputs from. Meant to be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function addFuzzFixture(address value) external;
+
+    /// Declares an ordered sequence of functions of `target` that the invariant fuzzer calls one after
+    /// the other, from the same sender, as a single unit. Must be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function targetFlow(address target, bytes4[] calldata selectors) external;
+
+    /// Sets the number of runs of the fuzz tests of the current test contract, overriding the config,
+    /// including inline `forge-config` ones. Must be called in `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function setFuzzRuns(uint256 runs) external;
+
+    /// Sets the maximum number of inputs the fuzz tests of the current test contract may reject through
+    /// `vm.assume`, overriding the config, including inline `forge-config` ones. Must be called in
+    /// `setUp()`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function setMaxRejects(uint256 maxRejects) external;
+
+    /// Discard this run's fuzz inputs and generate new ones if next call reverted.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assumeNoRevert() external;
+
+    /// Discard this run's fuzz inputs and generate new ones if next call reverted with the given
+    /// revert data.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assumeNoRevert(bytes calldata revertData) external;
+
+    /// Discard this run's fuzz inputs and generate new ones if next call reverted with an
+    /// `Error(string)` carrying the given reason.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assumeNoRevert(string calldata reason) external;
+
+    /// Discard this run's fuzz inputs and generate new ones if next call reverted, but only if the
+    /// revert originated from a call to `callee`.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function assumeNoRevert(address callee) external;
+
+    /// Records a named value in the snapshot of the current test contract, which `forge snapshot` writes
+    /// to `snapshots/<path>/<contract>.json` and `forge snapshot --check` compares against. The values of
+    /// tests which fail or revert are discarded.
+    #[cheatcode(group = Testing, safety = Safe)]
+    function snapshotValue(string calldata name, bytes calldata value) external;
+
     /// Writes a breakpoint to jump to in the debugger.
     #[cheatcode(group = Testing, safety = Safe)]
     function breakpoint(string calldata char) external;
@@ -651,6 +887,14 @@ interface Vm {
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data, uint64 count)
         external;
 
+    /// Expect a call to an address with the specified calldata, and a *maximum* amount of gas.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectCallMaxGas(address callee, bytes calldata data, uint64 maxGas) external;
+
+    /// Expect given number of calls to an address with the specified calldata, and a *maximum* amount of gas.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectCallMaxGas(address callee, bytes calldata data, uint64 maxGas, uint64 count) external;
+
     /// Prepare an expected log with (bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData.).
     /// Call this function, then emit an event, then call a function. Internally after the call, we check if
     /// logs were emitted in the expected order with the expected topics and data (as specified by the booleans).
@@ -672,6 +916,40 @@ interface Vm {
     #[cheatcode(group = Testing, safety = Unsafe)]
     function expectEmit(address emitter) external;
 
+    /// Prepare an expected anonymous log with (bool checkTopic0, bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData.).
+    /// Call this function, then emit an anonymous event, then call a function. Internally after the call, we check if
+    /// logs were emitted in the expected order with the expected topics and data (as specified by the booleans).
+    /// As anonymous events have no signature topic, topic 0 is checked like any other indexed parameter.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitAnonymous(bool checkTopic0, bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData)
+        external;
+
+    /// Same as the previous method, but also checks supplied address against emitting contract.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitAnonymous(
+        bool checkTopic0,
+        bool checkTopic1,
+        bool checkTopic2,
+        bool checkTopic3,
+        bool checkData,
+        address emitter
+    ) external;
+
+    /// Prepare an expected anonymous log with all topic and data checks enabled.
+    /// Call this function, then emit an anonymous event, then call a function. Internally after the call, we check if
+    /// logs were emitted in the expected order with the expected topics and data.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitAnonymous() external;
+
+    /// Same as the previous method, but also checks supplied address against emitting contract.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitAnonymous(address emitter) external;
+
+    /// Allows the logs expected for the next call to be emitted in any order, rather than in the order
+    /// they were declared in. Each emitted log matches at most one expected log.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectEmitUnordered() external;
+
     /// Expects an error on next call with any revert data.
     #[cheatcode(group = Testing, safety = Unsafe)]
     function expectRevert() external;
@@ -684,6 +962,22 @@ interface Vm {
     #[cheatcode(group = Testing, safety = Unsafe)]
     function expectRevert(bytes calldata revertData) external;
 
+    /// Expects each of the next `count` calls made at the current depth to revert with an error
+    /// starting with the revert data, whatever its arguments. A call reverting because of one of
+    /// its nested calls counts once.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectRevert(bytes4 revertData, uint64 count) external;
+
+    /// Expects an error on next call that starts with the revert data, ignoring any encoded
+    /// error arguments.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectPartialRevert(bytes4 revertData) external;
+
+    /// Expects an error on next call that starts with the revert data, ignoring any encoded
+    /// error arguments, and that was thrown by `reverter`.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectPartialRevert(bytes4 revertData, address reverter) external;
+
     /// Expects an error on next cheatcode call with any revert data.
     #[cheatcode(group = Testing, safety = Unsafe, status = Internal)]
     function _expectCheatcodeRevert() external;
@@ -711,6 +1005,23 @@ interface Vm {
     #[cheatcode(group = Testing, safety = Unsafe)]
     function expectSafeMemoryCall(uint64 min, uint64 max) external;
 
+    /// Same as `expectSafeMemory`, but the allowed ranges also apply to any context delegatecalled from the
+    /// current subcontext, recursively, so that code executed via delegatecall is checked as well.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function expectSafeMemoryDeep(uint64 min, uint64 max) external;
+
+    /// Saves the current cheatcode configuration, separately from the EVM state: mocked calls
+    /// and functions, pranks, expected calls, emits and reverts, and block and gas price overrides.
+    /// Environment variables set with `setEnv` are not saved, since they are shared by the whole
+    /// process. Returns the ID of the saved configuration. To restore it use `restoreCheatState`.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function saveCheatState() external returns (uint256 id);
+
+    /// Restores the cheatcode configuration saved by `saveCheatState` with the given ID,
+    /// replacing the current one.
+    #[cheatcode(group = Testing, safety = Unsafe)]
+    function restoreCheatState(uint256 id) external;
+
     /// Marks a test as skipped. Must be called at the top of the test.
     #[cheatcode(group = Testing, safety = Unsafe)]
     function skip(bool skipTest) external;
@@ -1326,6 +1637,17 @@ interface Vm {
         view
         returns (DirEntry[] memory entries);
 
+    /// Reads the paths of the files in the directory at the given path and its subdirectories, up to `maxDepth`,
+    /// sorted by path. A `maxDepth` of 1 only returns the files directly in the given directory, and 0 means no limit.
+    #[cheatcode(group = Filesystem)]
+    function readDirRecursive(string calldata path, uint64 maxDepth) external view returns (string[] memory paths);
+
+    /// Returns the paths of the files matching a glob pattern, e.g. `test/fixtures/**/*.json`, sorted by path.
+    /// `*` matches within a path component, and `**` across components.
+    /// Relative patterns are resolved against the project root.
+    #[cheatcode(group = Filesystem)]
+    function glob(string calldata pattern) external view returns (string[] memory paths);
+
     /// Reads the entire content of file to string. `path` is relative to the project root.
     #[cheatcode(group = Filesystem)]
     function readFile(string calldata path) external view returns (string memory data);
@@ -1387,6 +1709,51 @@ interface Vm {
     #[cheatcode(group = Filesystem)]
     function getDeployedCode(string calldata artifactPath) external view returns (bytes memory runtimeBytecode);
 
+    /// Gets the deployed bytecode from an artifact file, with its immutable variables set to the given ABI-encoded
+    /// values, in the order they are declared. Takes in the relative path to the json file.
+    #[cheatcode(group = Filesystem)]
+    function getDeployedCodeWithImmutables(string calldata artifactPath, bytes[] calldata values)
+        external
+        view
+        returns (bytes memory runtimeBytecode);
+
+    /// Gets the ABI from an artifact file as JSON. Takes in the relative path to the json file.
+    #[cheatcode(group = Filesystem)]
+    function getAbi(string calldata artifactPath) external view returns (string memory abiJson);
+
+    /// Gets the signatures and selectors of the functions from an artifact file, sorted by signature.
+    /// Takes in the relative path to the json file.
+    #[cheatcode(group = Filesystem)]
+    function getMethodIdentifiers(string calldata artifactPath)
+        external
+        view
+        returns (string[] memory signatures, bytes4[] memory selectors);
+
+    /// Gets the keccak256 hash of the deployed bytecode from an artifact file, which is the code hash of its
+    /// deployments unless it has immutable variables. Takes in the relative path to the json file.
+    #[cheatcode(group = Filesystem)]
+    function getCodeHash(string calldata artifactPath) external view returns (bytes32 codeHash);
+
+    /// Gets the storage layout from an artifact file as JSON. Takes in the relative path to the json file.
+    /// The artifact must be compiled with `extra_output = ["storageLayout"]`.
+    #[cheatcode(group = Filesystem)]
+    function getStorageLayout(string calldata artifactPath) external view returns (string memory layout);
+
+    /// Gets the storage slot of a variable from the storage layout of an artifact file. Takes in the relative path
+    /// to the json file and the path of the variable, resolving struct members, mapping keys and array indices,
+    /// e.g. `balances[0x...]` or `orders[1].amount`.
+    /// The artifact must be compiled with `extra_output = ["storageLayout"]`.
+    #[cheatcode(group = Filesystem)]
+    function slotOf(string calldata artifactPath, string calldata variablePath) external view returns (bytes32 slot);
+
+    /// Gets the address of a contract deployed on a chain, as saved by `forge script --save-deployments` to
+    /// `<deployments>/<chainId>/<contractName>.json`.
+    #[cheatcode(group = Filesystem)]
+    function getDeployment(string calldata contractName, uint256 chainId)
+        external
+        view
+        returns (address deployedAddress);
+
     // -------- Foreign Function Interface --------
 
     /// Performs a foreign function call via the terminal.
@@ -1397,6 +1764,35 @@ interface Vm {
     #[cheatcode(group = Filesystem)]
     function tryFfi(string[] calldata commandInput) external returns (FfiResult memory result);
 
+    // -------- HTTP Requests --------
+
+    /// Performs an HTTP GET request to the given URL and returns the response status and body.
+    /// The URL must be allowed by `http_permissions`.
+    #[cheatcode(group = Filesystem)]
+    function httpGet(string calldata url) external returns (HttpResponse memory response);
+
+    /// Performs an HTTP POST request with the given body and headers to the given URL and returns the
+    /// response status and body. Headers are formatted as `Name: value`.
+    /// The URL must be allowed by `http_permissions`.
+    #[cheatcode(group = Filesystem)]
+    function httpPost(string calldata url, string calldata body, string[] calldata headers)
+        external
+        returns (HttpResponse memory response);
+
+    // -------- Prompts --------
+
+    /// Prompts the user to select one of `options` in the terminal and returns the index of the selected option.
+    /// If the `FOUNDRY_PROMPT_ANSWERS` environment variable is set, the answer is instead read from the JSON file it
+    /// points to, keyed by `label`, as either the selected option or its index.
+    #[cheatcode(group = Filesystem)]
+    function promptSelect(string calldata label, string[] calldata options) external returns (uint256 index);
+
+    /// Prompts the user to confirm with yes or no in the terminal.
+    /// If the `FOUNDRY_PROMPT_ANSWERS` environment variable is set, the answer is instead read from the JSON file it
+    /// points to, keyed by `label`, as a boolean.
+    #[cheatcode(group = Filesystem)]
+    function promptConfirm(string calldata label) external returns (bool confirmed);
+
     // ======== Environment Variables ========
 
     /// Sets environment variables.
@@ -1547,6 +1943,11 @@ interface Vm {
         external view
         returns (bytes[] memory value);
 
+    /// Gets the secret `key` from the backend configured by `secrets`, by default the OS keychain.
+    /// The secret is redacted from traces. Reverts if the secret was not found.
+    #[cheatcode(group = Environment)]
+    function getSecret(string calldata key) external view returns (string memory value);
+
     // ======== Scripts ========
 
     // -------- Broadcasting Transactions --------
@@ -1566,6 +1967,12 @@ interface Vm {
     #[cheatcode(group = Scripting)]
     function broadcast(uint256 privateKey) external;
 
+    /// Has the next call (at this call depth only) create an ERC-4337 user operation of the smart account provided,
+    /// which makes the call through its `execute(address,uint256,bytes)` method and is signed by the address that calls the
+    /// test contract. The call is simulated with the account as `msg.sender`.
+    #[cheatcode(group = Scripting)]
+    function broadcastUserOp(address account) external;
+
     /// Using the address that calls the test contract, has all subsequent calls
     /// (at this call depth only) create transactions that can later be signed and sent onchain.
     #[cheatcode(group = Scripting)]
@@ -1585,6 +1992,16 @@ interface Vm {
     #[cheatcode(group = Scripting)]
     function stopBroadcast() external;
 
+    /// Attaches the given access list to all subsequently broadcasted transactions, e.g. one generated by `accessList`.
+    /// Pass an empty list to stop attaching it.
+    #[cheatcode(group = Scripting)]
+    function applyAccessList(AccessListItem[] calldata accessList) external;
+
+    /// Sets the EIP-2718 type of all subsequently broadcasted transactions, instead of choosing it based on the chain:
+    /// `0` for legacy, `1` for EIP-2930 and `2` for EIP-1559 transactions.
+    #[cheatcode(group = Scripting)]
+    function setTxType(uint8 txType) external;
+
     // ======== Utilities ========
 
     // -------- Strings --------
@@ -1805,6 +2222,17 @@ interface Vm {
     // NOTE: Please read https://book.getfoundry.sh/cheatcodes/write-json to understand how
     // to use the JSON writing cheats.
 
+    /// Merges two JSON documents following JSON Merge Patch (RFC 7396): the objects of `other` are merged into
+    /// those of `json` recursively, its `null` values remove keys, and its other values replace those of `json`.
+    #[cheatcode(group = Json)]
+    function mergeJson(string calldata json, string calldata other) external pure returns (string memory merged);
+
+    /// Applies a JSON Patch (RFC 6902), i.e. an array of `add`, `remove`, `replace`, `move`, `copy` and `test`
+    /// operations on JSON pointers such as `/contracts/0/address`, to a JSON document.
+    /// Reverts if any operation fails, without applying any of them.
+    #[cheatcode(group = Json)]
+    function patchJson(string calldata json, string calldata patch) external pure returns (string memory patched);
+
     /// Write a serialized JSON object to a file. If the file exists, it will be overwritten.
     #[cheatcode(group = Json)]
     function writeJson(string calldata json, string calldata path) external;
@@ -1814,6 +2242,150 @@ interface Vm {
     #[cheatcode(group = Json)]
     function writeJson(string calldata json, string calldata path, string calldata valueKey) external;
 
+    // ======== TOML Parsing and Manipulation ========
+
+    // -------- Reading --------
+
+    // Values are converted to JSON and selected with the same key syntax as the JSON parsing cheats.
+
+    /// Checks if `key` exists in a TOML table.
+    #[cheatcode(group = Toml)]
+    function keyExistsToml(string calldata toml, string calldata key) external view returns (bool);
+
+    /// ABI-encodes a TOML table.
+    #[cheatcode(group = Toml)]
+    function parseToml(string calldata toml) external pure returns (bytes memory abiEncodedData);
+    /// ABI-encodes a TOML table at `key`.
+    #[cheatcode(group = Toml)]
+    function parseToml(string calldata toml, string calldata key) external pure returns (bytes memory abiEncodedData);
+
+    // The following parseToml cheatcodes do the same type coercion as the parseJson cheatcodes.
+
+    /// Parses a string of TOML data at `key` and coerces it to `uint256`.
+    #[cheatcode(group = Toml)]
+    function parseTomlUint(string calldata toml, string calldata key) external pure returns (uint256);
+    /// Parses a string of TOML data at `key` and coerces it to `uint256[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlUintArray(string calldata toml, string calldata key) external pure returns (uint256[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `int256`.
+    #[cheatcode(group = Toml)]
+    function parseTomlInt(string calldata toml, string calldata key) external pure returns (int256);
+    /// Parses a string of TOML data at `key` and coerces it to `int256[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlIntArray(string calldata toml, string calldata key) external pure returns (int256[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `bool`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBool(string calldata toml, string calldata key) external pure returns (bool);
+    /// Parses a string of TOML data at `key` and coerces it to `bool[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBoolArray(string calldata toml, string calldata key) external pure returns (bool[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `address`.
+    #[cheatcode(group = Toml)]
+    function parseTomlAddress(string calldata toml, string calldata key) external pure returns (address);
+    /// Parses a string of TOML data at `key` and coerces it to `address[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlAddressArray(string calldata toml, string calldata key) external pure returns (address[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `string`.
+    #[cheatcode(group = Toml)]
+    function parseTomlString(string calldata toml, string calldata key) external pure returns (string memory);
+    /// Parses a string of TOML data at `key` and coerces it to `string[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlStringArray(string calldata toml, string calldata key) external pure returns (string[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `bytes`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBytes(string calldata toml, string calldata key) external pure returns (bytes memory);
+    /// Parses a string of TOML data at `key` and coerces it to `bytes[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBytesArray(string calldata toml, string calldata key) external pure returns (bytes[] memory);
+    /// Parses a string of TOML data at `key` and coerces it to `bytes32`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBytes32(string calldata toml, string calldata key) external pure returns (bytes32);
+    /// Parses a string of TOML data at `key` and coerces it to `bytes32[]`.
+    #[cheatcode(group = Toml)]
+    function parseTomlBytes32Array(string calldata toml, string calldata key) external pure returns (bytes32[] memory);
+
+    /// Returns an array of all the keys in a TOML table.
+    #[cheatcode(group = Toml)]
+    function parseTomlKeys(string calldata toml, string calldata key) external pure returns (string[] memory keys);
+
+    // -------- Writing --------
+
+    /// Takes a serialized JSON object, e.g. from `serializeJson`, and writes it to a file as TOML.
+    /// If the file exists, it will be overwritten.
+    #[cheatcode(group = Toml)]
+    function writeToml(string calldata json, string calldata path) external;
+
+    /// Takes a serialized JSON value and writes it to an **existing** TOML file, replacing the value with key = <value_key.>
+    /// This is useful to replace a specific value of a TOML file, without having to parse the entire thing.
+    #[cheatcode(group = Toml)]
+    function writeToml(string calldata json, string calldata path, string calldata valueKey) external;
+
+    // ======== YAML Parsing ========
+
+    // -------- Reading --------
+
+    // Values are converted to JSON and selected with the same key syntax as the JSON parsing cheats.
+
+    /// Checks if `key` exists in a YAML table.
+    #[cheatcode(group = Yaml)]
+    function keyExistsYaml(string calldata yaml, string calldata key) external view returns (bool);
+
+    /// ABI-encodes a YAML table.
+    #[cheatcode(group = Yaml)]
+    function parseYaml(string calldata yaml) external pure returns (bytes memory abiEncodedData);
+    /// ABI-encodes a YAML table at `key`.
+    #[cheatcode(group = Yaml)]
+    function parseYaml(string calldata yaml, string calldata key) external pure returns (bytes memory abiEncodedData);
+
+    // The following parseYaml cheatcodes do the same type coercion as the parseJson cheatcodes.
+
+    /// Parses a string of YAML data at `key` and coerces it to `uint256`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlUint(string calldata yaml, string calldata key) external pure returns (uint256);
+    /// Parses a string of YAML data at `key` and coerces it to `uint256[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlUintArray(string calldata yaml, string calldata key) external pure returns (uint256[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `int256`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlInt(string calldata yaml, string calldata key) external pure returns (int256);
+    /// Parses a string of YAML data at `key` and coerces it to `int256[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlIntArray(string calldata yaml, string calldata key) external pure returns (int256[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `bool`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBool(string calldata yaml, string calldata key) external pure returns (bool);
+    /// Parses a string of YAML data at `key` and coerces it to `bool[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBoolArray(string calldata yaml, string calldata key) external pure returns (bool[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `address`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlAddress(string calldata yaml, string calldata key) external pure returns (address);
+    /// Parses a string of YAML data at `key` and coerces it to `address[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlAddressArray(string calldata yaml, string calldata key) external pure returns (address[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `string`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlString(string calldata yaml, string calldata key) external pure returns (string memory);
+    /// Parses a string of YAML data at `key` and coerces it to `string[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlStringArray(string calldata yaml, string calldata key) external pure returns (string[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `bytes`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBytes(string calldata yaml, string calldata key) external pure returns (bytes memory);
+    /// Parses a string of YAML data at `key` and coerces it to `bytes[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBytesArray(string calldata yaml, string calldata key) external pure returns (bytes[] memory);
+    /// Parses a string of YAML data at `key` and coerces it to `bytes32`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBytes32(string calldata yaml, string calldata key) external pure returns (bytes32);
+    /// Parses a string of YAML data at `key` and coerces it to `bytes32[]`.
+    #[cheatcode(group = Yaml)]
+    function parseYamlBytes32Array(string calldata yaml, string calldata key) external pure returns (bytes32[] memory);
+
+    /// Returns an array of all the keys in a YAML table.
+    #[cheatcode(group = Yaml)]
+    function parseYamlKeys(string calldata yaml, string calldata key) external pure returns (string[] memory keys);
+
     // -------- Key Management --------
 
     /// Derives a private key from the name, labels the account with that name, and returns the wallet.
@@ -1884,7 +2456,8 @@ interface Vm {
     #[cheatcode(group = Utilities)]
     function computeCreate2Address(bytes32 salt, bytes32 initCodeHash, address deployer) external pure returns (address);
 
-    /// Compute the address of a contract created with CREATE2 using the default CREATE2 deployer.
+    /// Compute the address of a contract created with CREATE2 using the configured CREATE2 deployer,
+    /// `create2_deployer`.
     #[cheatcode(group = Utilities)]
     function computeCreate2Address(bytes32 salt, bytes32 initCodeHash) external pure returns (address);
 
@@ -1903,5 +2476,116 @@ interface Vm {
     /// Encodes a `string` value to a base64url string.
     #[cheatcode(group = Utilities)]
     function toBase64URL(string calldata data) external pure returns (string memory);
+
+    // -------- Compression --------
+
+    /// Compresses `data` with gzip.
+    #[cheatcode(group = Utilities)]
+    function gzipCompress(bytes calldata data) external pure returns (bytes memory compressed);
+
+    /// Decompresses gzip-compressed `data`. Reverts if the decompressed data is larger than 16 MiB.
+    #[cheatcode(group = Utilities)]
+    function gzipDecompress(bytes calldata data) external pure returns (bytes memory decompressed);
+
+    /// Compresses `data` with brotli.
+    #[cheatcode(group = Utilities)]
+    function brotliCompress(bytes calldata data) external pure returns (bytes memory compressed);
+
+    /// Decompresses brotli-compressed `data`. Reverts if the decompressed data is larger than 16 MiB.
+    #[cheatcode(group = Utilities)]
+    function brotliDecompress(bytes calldata data) external pure returns (bytes memory decompressed);
+
+    // -------- KZG Commitments --------
+
+    /// Computes the KZG commitment of an EIP-4844 blob. The blob must be exactly 131072 bytes.
+    #[cheatcode(group = Utilities)]
+    function blobToKzgCommitment(bytes calldata blob) external pure returns (bytes memory commitment);
+
+    /// Computes the KZG proof of the blob's polynomial evaluated at `z`, returning the proof and the evaluation `y`.
+    #[cheatcode(group = Utilities)]
+    function computeKzgProof(bytes calldata blob, bytes32 z) external pure returns (bytes memory proof, bytes32 y);
+
+    /// Computes the KZG proof of a blob for its commitment, as included in EIP-4844 blob sidecars.
+    #[cheatcode(group = Utilities)]
+    function computeBlobKzgProof(bytes calldata blob, bytes calldata commitment)
+        external
+        pure
+        returns (bytes memory proof);
+
+    /// Verifies that the polynomial committed to by `commitment` evaluates to `y` at `z`, like the point
+    /// evaluation precompile.
+    #[cheatcode(group = Utilities)]
+    function verifyKzgProof(bytes calldata commitment, bytes32 z, bytes32 y, bytes calldata proof)
+        external
+        pure
+        returns (bool valid);
+
+    /// Verifies the KZG proof of a blob for its commitment.
+    #[cheatcode(group = Utilities)]
+    function verifyBlobKzgProof(bytes calldata blob, bytes calldata commitment, bytes calldata proof)
+        external
+        pure
+        returns (bool valid);
+
+    /// Computes the EIP-4844 versioned hash of a KZG commitment, as returned by `blobhash`.
+    #[cheatcode(group = Utilities)]
+    function kzgToVersionedHash(bytes calldata commitment) external pure returns (bytes32 versionedHash);
+
+    // -------- Merkle Trees --------
+
+    /// Computes the root of a `Sorted` Merkle tree of `leaves`.
+    #[cheatcode(group = Utilities)]
+    function merkleRoot(bytes32[] calldata leaves) external pure returns (bytes32 root);
+
+    /// Computes the root of a Merkle tree of `leaves` with the given layout.
+    #[cheatcode(group = Utilities)]
+    function merkleRoot(bytes32[] calldata leaves, MerkleTreeKind kind) external pure returns (bytes32 root);
+
+    /// Computes the proof of the leaf at `index` in a `Sorted` Merkle tree of `leaves`, which can be verified with
+    /// OpenZeppelin's `MerkleProof.verify`.
+    #[cheatcode(group = Utilities)]
+    function merkleProof(bytes32[] calldata leaves, uint256 index) external pure returns (bytes32[] memory proof);
+
+    /// Computes the proof of the leaf at `index` in a Merkle tree of `leaves` with the given layout.
+    #[cheatcode(group = Utilities)]
+    function merkleProof(bytes32[] calldata leaves, uint256 index, MerkleTreeKind kind)
+        external
+        pure
+        returns (bytes32[] memory proof);
+
+    /// Computes the multi-proof of the leaves at `indices` in a `Sorted` Merkle tree of `leaves`, which can be
+    /// verified with OpenZeppelin's `MerkleProof.multiProofVerify` and the returned `proofLeaves`.
+    #[cheatcode(group = Utilities)]
+    function merkleMultiProof(bytes32[] calldata leaves, uint256[] calldata indices)
+        external
+        pure
+        returns (bytes32[] memory proofLeaves, bytes32[] memory proof, bool[] memory proofFlags);
+
+    /// Computes the multi-proof of the leaves at `indices` in a Merkle tree of `leaves` with the given layout.
+    #[cheatcode(group = Utilities)]
+    function merkleMultiProof(bytes32[] calldata leaves, uint256[] calldata indices, MerkleTreeKind kind)
+        external
+        pure
+        returns (bytes32[] memory proofLeaves, bytes32[] memory proof, bool[] memory proofFlags);
+
+    // -------- Randomness --------
+
+    /// Returns a random `uint256` value.
+    /// The random cheatcodes are seeded with the fuzz seed and the calldata of the test, so the values of a
+    /// failing fuzz run are reproduced when it is replayed.
+    #[cheatcode(group = Utilities)]
+    function randomUint() external returns (uint256);
+
+    /// Returns a random `uint256` value between `min` and `max`, inclusive.
+    #[cheatcode(group = Utilities)]
+    function randomUintBetween(uint256 min, uint256 max) external returns (uint256);
+
+    /// Returns a random address derived from `seed`. The same `seed` gives the same address within a test run.
+    #[cheatcode(group = Utilities)]
+    function randomAddress(uint256 seed) external view returns (address);
+
+    /// Returns `len` random bytes.
+    #[cheatcode(group = Utilities)]
+    function randomBytesLen(uint256 len) external returns (bytes memory);
 }
 }
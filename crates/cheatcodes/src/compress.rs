@@ -0,0 +1,85 @@
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_sol_types::SolValue;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::io::{Read, Write};
+
+/// The buffer size used by the brotli encoder and decoder.
+const BROTLI_BUFFER_SIZE: usize = 4096;
+/// The brotli compression level, between 0 and 11.
+const BROTLI_QUALITY: u32 = 11;
+/// The base 2 logarithm of the brotli sliding window size.
+const BROTLI_LG_WINDOW_SIZE: u32 = 22;
+/// The maximum size of decompressed data, so that compression bombs can't exhaust the memory.
+const MAX_DECOMPRESSED_LEN: u64 = 16 * 1024 * 1024;
+
+impl Cheatcode for gzipCompressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { data } = self;
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data)?;
+        Ok(encoder.finish()?.abi_encode())
+    }
+}
+
+impl Cheatcode for gzipDecompressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { data } = self;
+        Ok(decompress(GzDecoder::new(&data[..]), "gzip")?.abi_encode())
+    }
+}
+
+impl Cheatcode for brotliCompressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { data } = self;
+        let mut encoder = brotli::CompressorWriter::new(
+            Vec::new(),
+            BROTLI_BUFFER_SIZE,
+            BROTLI_QUALITY,
+            BROTLI_LG_WINDOW_SIZE,
+        );
+        encoder.write_all(data)?;
+        Ok(encoder.into_inner().abi_encode())
+    }
+}
+
+impl Cheatcode for brotliDecompressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { data } = self;
+        let decoder = brotli::Decompressor::new(&data[..], BROTLI_BUFFER_SIZE);
+        Ok(decompress(decoder, "brotli")?.abi_encode())
+    }
+}
+
+/// Reads the decompressed data of the decoder, failing if it's larger than
+/// [`MAX_DECOMPRESSED_LEN`].
+fn decompress(decoder: impl Read, format: &str) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_LEN + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| fmt_err!("failed to decompress {format} data: {e}"))?;
+    if decompressed.len() as u64 > MAX_DECOMPRESSED_LEN {
+        bail!("decompressed {format} data exceeds the maximum of {MAX_DECOMPRESSED_LEN} bytes");
+    }
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_decompressed_size() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&vec![0; MAX_DECOMPRESSED_LEN as usize]).unwrap();
+        let compressed = encoder.finish().unwrap();
+        let decompressed = decompress(GzDecoder::new(&compressed[..]), "gzip").unwrap();
+        assert_eq!(decompressed.len() as u64, MAX_DECOMPRESSED_LEN);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&vec![0; MAX_DECOMPRESSED_LEN as usize + 1]).unwrap();
+        let bomb = encoder.finish().unwrap();
+        let err = decompress(GzDecoder::new(&bomb[..]), "gzip").unwrap_err();
+        assert!(err.to_string().contains("exceeds the maximum"), "{err}");
+    }
+}
@@ -1,11 +1,11 @@
 use super::Result;
 use crate::Vm::Rpc;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, U256};
 use foundry_common::fs::normalize_path;
 use foundry_compilers::{utils::canonicalize, ProjectPathsConfig};
 use foundry_config::{
     cache::StorageCachingConfig, fs_permissions::FsAccessKind, Config, FsPermissions,
-    ResolvedRpcEndpoints,
+    HttpPermissions, ResolvedRpcEndpoints, SecretsBackend,
 };
 use foundry_evm_core::opts::EvmOpts;
 use std::{
@@ -28,6 +28,10 @@ pub struct CheatsConfig {
     pub paths: ProjectPathsConfig,
     /// Filesystem permissions for cheatcodes like `writeFile`, `readFile`
     pub fs_permissions: FsPermissions,
+    /// URLs that can be requested by cheatcodes like `httpGet`, `httpPost`
+    pub http_permissions: HttpPermissions,
+    /// Where `getSecret` resolves secrets from
+    pub secrets: SecretsBackend,
     /// Project root
     pub root: PathBuf,
     /// Paths (directories) where file reading/writing is allowed
@@ -36,6 +40,12 @@ pub struct CheatsConfig {
     pub evm_opts: EvmOpts,
     /// Address labels from config
     pub labels: HashMap<Address, String>,
+    /// Optional seed for the RNG of the random cheatcodes, taken from the fuzz config
+    pub seed: Option<U256>,
+    /// The CREATE2 factory used when broadcasting contract creations with a salt
+    pub create2_deployer: Address,
+    /// Where deployments saved by `forge script --save-deployments` are read from
+    pub deployments: PathBuf,
 }
 
 impl CheatsConfig {
@@ -54,10 +64,15 @@ impl CheatsConfig {
             rpc_endpoints,
             paths: config.project_paths(),
             fs_permissions: config.fs_permissions.clone().joined(&config.__root),
+            http_permissions: config.http_permissions.clone(),
+            secrets: config.secrets.clone().joined(&config.__root.0),
             root: config.__root.0.clone(),
             allowed_paths,
             evm_opts,
             labels: config.labels.clone(),
+            seed: config.fuzz.seed,
+            create2_deployer: config.create2_deployer,
+            deployments: config.deployments.clone(),
         }
     }
 
@@ -121,6 +136,16 @@ impl CheatsConfig {
         Ok(())
     }
 
+    /// Returns an error if `url` is not allowed to be requested, see
+    /// [`HttpPermissions::is_url_allowed`]
+    pub fn ensure_url_allowed(&self, url: &str) -> Result<()> {
+        ensure!(
+            self.http_permissions.is_url_allowed(url),
+            "the URL {url} is not allowed to be requested; add it to `http_permissions` to allow it"
+        );
+        Ok(())
+    }
+
     /// Returns the RPC to use
     ///
     /// If `url_or_alias` is a known alias in the `ResolvedRpcEndpoints` then it returns the
@@ -168,10 +193,15 @@ impl Default for CheatsConfig {
             rpc_endpoints: Default::default(),
             paths: ProjectPathsConfig::builder().build_with_root("./"),
             fs_permissions: Default::default(),
+            http_permissions: Default::default(),
+            secrets: Default::default(),
             root: Default::default(),
             allowed_paths: vec![],
             evm_opts: Default::default(),
             labels: Default::default(),
+            seed: None,
+            create2_deployer: Config::DEFAULT_CREATE2_DEPLOYER,
+            deployments: "deployments".into(),
         }
     }
 }
@@ -215,4 +245,19 @@ mod tests {
         let f = format!("{root}lib/other/foundry.toml");
         assert!(!config.is_foundry_toml(f));
     }
+
+    #[test]
+    fn test_allowed_urls() {
+        let config = CheatsConfig::new(
+            &Config {
+                http_permissions: HttpPermissions::new(["https://api.example.com/v1"]),
+                ..Default::default()
+            },
+            Default::default(),
+        );
+
+        assert!(config.ensure_url_allowed("https://api.example.com/v1/users").is_ok());
+        assert!(config.ensure_url_allowed("https://api.example.com/v2").is_err());
+        assert!(CheatsConfig::default().ensure_url_allowed("https://api.example.com/v1").is_err());
+    }
 }
@@ -14,9 +14,16 @@ use revm::{
     primitives::{Account, Bytecode, SpecId, KECCAK_EMPTY},
     EVMData,
 };
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
+mod access_list;
+mod diff;
+mod erc20;
 mod fork;
+pub(crate) mod layout;
 pub(crate) mod mapping;
 pub(crate) mod mock;
 pub(crate) mod prank;
@@ -66,6 +73,15 @@ impl Cheatcode for loadCall {
     }
 }
 
+impl Cheatcode for tloadCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { account, slot } = *self;
+        ensure_not_precompile!(&account, ccx);
+        let val = ccx.data.journaled_state.tload(account, slot.into());
+        Ok(val.abi_encode())
+    }
+}
+
 impl Cheatcode for loadAllocsCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { pathToAllocsJson } = self;
@@ -152,6 +168,27 @@ impl Cheatcode for signP256Call {
     }
 }
 
+impl Cheatcode for signBlsCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { privateKey, message } = self;
+        super::utils::sign_bls(privateKey, message)
+    }
+}
+
+impl Cheatcode for blsPublicKeyCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { privateKey } = self;
+        super::utils::bls_public_key(privateKey)
+    }
+}
+
+impl Cheatcode for deriveBlsKeyCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { ikm } = self;
+        super::utils::derive_bls_key(ikm)
+    }
+}
+
 impl Cheatcode for recordCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
@@ -372,6 +409,15 @@ impl Cheatcode for storeCall {
     }
 }
 
+impl Cheatcode for tstoreCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { account, slot, value } = *self;
+        ensure_not_precompile!(&account, ccx);
+        ccx.data.journaled_state.tstore(account, slot.into(), value.into());
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for coolCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { target } = self;
@@ -383,6 +429,17 @@ impl Cheatcode for coolCall {
     }
 }
 
+impl Cheatcode for warmSlotCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { target, slot } = *self;
+        ensure_not_precompile!(&target, ccx);
+        // loading the account and the slot marks them as warm
+        ccx.data.journaled_state.load_account(target, ccx.data.db)?;
+        ccx.data.journaled_state.sload(target, slot.into(), ccx.data.db)?;
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for readCallersCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self {} = self;
@@ -454,6 +511,7 @@ impl Cheatcode for startStateDiffRecordingCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
         state.recorded_account_diffs_stack = Some(Default::default());
+        state.state_diff_mapping_slots = Some(Default::default());
         Ok(Default::default())
     }
 }
@@ -461,7 +519,85 @@ impl Cheatcode for startStateDiffRecordingCall {
 impl Cheatcode for stopAndReturnStateDiffCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
-        get_state_diff(state)
+        Ok(take_state_diff(state).abi_encode())
+    }
+}
+
+impl Cheatcode for stopAndReturnDecodedStateDiffCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self {} = self;
+        let accesses = take_state_diff(ccx.state);
+        let layouts = layout::read_layouts(&ccx.state.config)?;
+
+        // Net changes of the slots written to, `(account, slot) => (previous, new)`
+        let mut slots = BTreeMap::<(Address, B256), (B256, B256)>::new();
+        for access in accesses.iter().flat_map(|access| &access.storageAccesses) {
+            if access.isWrite && !access.reverted {
+                slots
+                    .entry((access.account, access.slot))
+                    .or_insert((access.previousValue, access.newValue))
+                    .1 = access.newValue;
+            }
+        }
+
+        let mut changes = Vec::new();
+        let mut artifacts = HashMap::new();
+        for ((account, slot), (previous, new)) in slots {
+            if previous == new {
+                continue
+            }
+            let artifact = match artifacts.get(&account) {
+                Some(artifact) => *artifact,
+                None => {
+                    let code = account_code(ccx, account)?;
+                    let artifact = layouts.iter().find(|layout| layout.matches(&code));
+                    artifacts.insert(account, artifact);
+                    artifact
+                }
+            };
+            let contract_name = artifact.map(|artifact| artifact.name.clone()).unwrap_or_default();
+            let change = |label, type_name, decoded_previous, decoded_new| DecodedStorageChange {
+                account,
+                contractName: contract_name.clone(),
+                slot,
+                label,
+                typeName: type_name,
+                previousValue: previous,
+                newValue: new,
+                decodedPreviousValue: decoded_previous,
+                decodedNewValue: decoded_new,
+            };
+
+            let preimages = ccx
+                .state
+                .state_diff_mapping_slots
+                .as_ref()
+                .and_then(|mapping_slots| mapping_slots.get(&account))
+                .map(|mapping_slots| &mapping_slots.seen_sha3);
+            let vars = artifact
+                .map(|artifact| {
+                    layout::LayoutDecoder::new(&artifact.layout, preimages)
+                        .variables(U256::from_be_bytes(slot.0))
+                })
+                .unwrap_or_default();
+            let len = changes.len();
+            for var in vars {
+                let (decoded_previous, decoded_new) = (var.format(previous), var.format(new));
+                // Only the variables packed into the slot which changed are reported
+                if decoded_previous != decoded_new {
+                    changes.push(change(var.label, var.type_name, decoded_previous, decoded_new));
+                }
+            }
+            if changes.len() == len {
+                changes.push(change(
+                    String::new(),
+                    String::new(),
+                    previous.to_string(),
+                    new.to_string(),
+                ));
+            }
+        }
+        Ok(changes.abi_encode())
     }
 }
 
@@ -529,21 +665,32 @@ pub(super) fn journaled_account<'a, DB: DatabaseExt>(
     Ok(data.journaled_state.state.get_mut(&addr).expect("account is loaded"))
 }
 
-/// Consumes recorded account accesses and returns them as an abi encoded
-/// array of [AccountAccess]. If there are no accounts were
-/// recorded as accessed, an abi encoded empty array is returned.
+/// Returns the runtime bytecode of an account.
+fn account_code<DB: DatabaseExt>(ccx: &mut CheatsCtxt<DB>, address: Address) -> Result<Bytes> {
+    let (account, _) = ccx.data.journaled_state.load_account(address, ccx.data.db)?;
+    let code = match &account.info.code {
+        Some(code) => code.clone(),
+        None => {
+            let code_hash = account.info.code_hash;
+            ccx.data.db.code_by_hash(code_hash)?
+        }
+    };
+    Ok(code.original_bytes())
+}
+
+/// Consumes recorded account accesses and returns them as an array of [AccountAccess].
+/// If there are no accounts were recorded as accessed, an empty array is returned.
 ///
 /// In the case where `stopAndReturnStateDiff` is called at a lower
 /// depth than `startStateDiffRecording`, multiple `Vec<RecordedAccountAccesses>`
 /// will be flattened, preserving the order of the accesses.
-fn get_state_diff(state: &mut Cheatcodes) -> Result {
-    let res = state
+fn take_state_diff(state: &mut Cheatcodes) -> Vec<AccountAccess> {
+    state
         .recorded_account_diffs_stack
         .replace(Default::default())
         .unwrap_or_default()
         .into_iter()
         .flatten()
         .map(|record| record.access)
-        .collect::<Vec<_>>();
-    Ok(res.abi_encode())
+        .collect()
 }
@@ -0,0 +1,39 @@
+//! Implementations of the access list [`Evm`](crate::Group::Evm) cheatcodes.
+
+use crate::{Cheatcode, CheatsCtxt, Result, Vm::*};
+use alloy_primitives::U256;
+use alloy_sol_types::SolValue;
+use foundry_evm_core::{access_list::AccessListTracer, backend::DatabaseExt};
+use revm::primitives::TransactTo;
+
+impl Cheatcode for accessListCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { to, data } = self;
+        let mut env = ccx.data.env.clone();
+        env.tx.caller = ccx.caller;
+        env.tx.transact_to = TransactTo::Call(*to);
+        env.tx.data = data.clone().into();
+        env.tx.value = U256::ZERO;
+        env.tx.nonce = None;
+        env.tx.gas_price = U256::ZERO;
+        env.tx.gas_priority_fee = None;
+        env.block.basefee = U256::ZERO;
+
+        let precompiles = ccx.data.precompiles.addresses().into_iter().copied().collect();
+        let mut tracer = AccessListTracer::new(Default::default(), ccx.caller, *to, precompiles);
+        ccx.data.db.simulate(&env, &ccx.data.journaled_state, &mut tracer)?;
+
+        let mut access_list = tracer
+            .access_list()
+            .0
+            .into_iter()
+            .map(|item| {
+                let mut storage_keys = item.storage_keys;
+                storage_keys.sort();
+                AccessListItem { account: item.address, storageKeys: storage_keys }
+            })
+            .collect::<Vec<_>>();
+        access_list.sort_by_key(|item| item.account);
+        Ok(access_list.abi_encode())
+    }
+}
@@ -0,0 +1,76 @@
+//! Implementations of the differential testing [`Evm`](crate::Group::Evm) cheatcodes.
+
+use crate::{Cheatcode, CheatsCtxt, Result, Vm::*};
+use alloy_primitives::{Address, B256, U256};
+use alloy_sol_types::SolValue;
+use foundry_evm_core::backend::DatabaseExt;
+use revm::{
+    inspectors::NoOpInspector,
+    primitives::{ExecutionResult, Output, TransactTo},
+};
+
+impl Cheatcode for diffCallCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { a, b, data } = self;
+        let result_a = simulate_call(ccx, *a, data)?;
+        let result_b = simulate_call(ccx, *b, data)?;
+        Ok((result_a, result_b).abi_encode_params())
+    }
+}
+
+/// Simulates a call to `target` with `data` by the caller of the cheatcode, on top of the current
+/// state, and returns its outcome.
+fn simulate_call<DB: DatabaseExt>(
+    ccx: &mut CheatsCtxt<DB>,
+    target: Address,
+    data: &[u8],
+) -> Result<DiffCallResult> {
+    let mut env = ccx.data.env.clone();
+    env.tx.caller = ccx.caller;
+    env.tx.transact_to = TransactTo::Call(target);
+    env.tx.data = data.to_vec().into();
+    env.tx.value = U256::ZERO;
+    env.tx.nonce = None;
+    env.tx.gas_price = U256::ZERO;
+    env.tx.gas_priority_fee = None;
+    env.block.basefee = U256::ZERO;
+
+    let result = ccx.data.db.simulate(&env, &ccx.data.journaled_state, &mut NoOpInspector)?;
+    let (success, return_data, logs) = match result.result {
+        ExecutionResult::Success { output: Output::Call(output), logs, .. } => (true, output, logs),
+        ExecutionResult::Success { logs, .. } => (true, Default::default(), logs),
+        ExecutionResult::Revert { output, .. } => (false, output, Vec::new()),
+        ExecutionResult::Halt { .. } => (false, Default::default(), Vec::new()),
+    };
+    let logs = logs
+        .into_iter()
+        .map(|log| Log { topics: log.topics, data: log.data.to_vec(), emitter: log.address })
+        .collect();
+
+    // The state changes of a failed call are discarded
+    let mut storage_changes = Vec::new();
+    if success {
+        for (account, state) in result.state {
+            for (slot, value) in state.storage {
+                if value.is_changed() {
+                    storage_changes.push(StorageAccess {
+                        account,
+                        slot: B256::from(slot),
+                        isWrite: true,
+                        previousValue: B256::from(value.original_value()),
+                        newValue: B256::from(value.present_value()),
+                        reverted: false,
+                    });
+                }
+            }
+        }
+        storage_changes.sort_by_key(|change| (change.account, change.slot));
+    }
+
+    Ok(DiffCallResult {
+        success,
+        returnData: return_data.to_vec(),
+        logs,
+        storageChanges: storage_changes,
+    })
+}
@@ -0,0 +1,135 @@
+//! Implementations of ERC20 related [`Evm`](crate::Group::Evm) cheatcodes.
+
+use crate::{Cheatcode, CheatsCtxt, Result, Vm::*};
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::{sol, SolCall};
+use foundry_evm_core::{backend::DatabaseExt, constants::CALLER};
+use itertools::Itertools;
+use revm::{
+    interpreter::{opcode, Interpreter},
+    primitives::{ExecutionResult, Output, TransactTo},
+    Database, EVMData, Inspector, JournaledState,
+};
+
+sol! {
+    interface IERC20 {
+        function balanceOf(address account) external view returns (uint256);
+    }
+}
+
+impl Cheatcode for dealERC20Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { token, to, amount } = *self;
+        let journaled_state = ccx.data.journaled_state.clone();
+        let (_, reads) = simulate_balance_of(ccx.data, &journaled_state, token, to)?;
+
+        // The balance is most likely stored in the last slot read by `balanceOf`
+        for (address, slot) in reads.into_iter().rev().unique() {
+            let Some((offset, width)) =
+                find_balance_bits(ccx.data, &journaled_state, token, to, address, slot)
+            else {
+                continue
+            };
+
+            let mask = U256::MAX >> (256 - width);
+            ensure!(amount <= mask, "amount does not fit in the {width}-bit balance of {to}");
+
+            // Only overwrite the bits of the balance, in case it is packed with other values
+            ccx.data.journaled_state.load_account(address, ccx.data.db)?;
+            ccx.data.journaled_state.touch(&address);
+            let (value, _) = ccx.data.journaled_state.sload(address, slot, ccx.data.db)?;
+            let value = (value & !(mask << offset)) | (amount << offset);
+            ccx.data.journaled_state.sstore(address, slot, value, ccx.data.db)?;
+            return Ok(Default::default())
+        }
+
+        bail!("could not find the storage slot of the balance of {to} in token {token}")
+    }
+}
+
+/// Finds the bit offset and width of the balance of `account` in the given storage slot, if the
+/// slot holds it.
+fn find_balance_bits<DB: DatabaseExt>(
+    data: &mut EVMData<'_, DB>,
+    journaled_state: &JournaledState,
+    token: Address,
+    account: Address,
+    address: Address,
+    slot: U256,
+) -> Option<(usize, usize)> {
+    // Setting all the bits of the slot reveals the width of the balance, if it is stored in it
+    let max = simulate_with_slot(data, journaled_state, token, account, address, slot, U256::MAX)?;
+    if max == U256::ZERO || (max != U256::MAX && (max & (max + U256::from(1))) != U256::ZERO) {
+        return None
+    }
+    let width = 256 - max.leading_zeros();
+
+    // Then find the offset of the balance, assuming it is byte-aligned
+    (0..=256 - width).step_by(8).find_map(|offset| {
+        let value = max << offset;
+        let balance =
+            simulate_with_slot(data, journaled_state, token, account, address, slot, value)?;
+        (balance == max).then_some((offset, width))
+    })
+}
+
+/// Simulates `token.balanceOf(account)` with the given storage slot set to `value`.
+fn simulate_with_slot<DB: DatabaseExt>(
+    data: &mut EVMData<'_, DB>,
+    journaled_state: &JournaledState,
+    token: Address,
+    account: Address,
+    address: Address,
+    slot: U256,
+    value: U256,
+) -> Option<U256> {
+    let mut journaled_state = journaled_state.clone();
+    journaled_state.load_account(address, data.db).ok()?;
+    journaled_state.touch(&address);
+    journaled_state.sstore(address, slot, value, data.db).ok()?;
+    simulate_balance_of(data, &journaled_state, token, account).ok().map(|(balance, _)| balance)
+}
+
+/// Simulates `token.balanceOf(account)` on top of the given journaled state, returning the
+/// balance and the storage slots read during the call.
+fn simulate_balance_of<DB: DatabaseExt>(
+    data: &mut EVMData<'_, DB>,
+    journaled_state: &JournaledState,
+    token: Address,
+    account: Address,
+) -> Result<(U256, Vec<(Address, U256)>)> {
+    let mut env = data.env.clone();
+    env.tx.caller = CALLER;
+    env.tx.transact_to = TransactTo::Call(token);
+    env.tx.data = IERC20::balanceOfCall { account }.abi_encode().into();
+    env.tx.value = U256::ZERO;
+    env.tx.nonce = None;
+    env.tx.gas_price = U256::ZERO;
+    env.tx.gas_priority_fee = None;
+    env.block.basefee = U256::ZERO;
+
+    let mut recorder = SloadRecorder::default();
+    let result = data.db.simulate(&env, journaled_state, &mut recorder)?;
+    let ExecutionResult::Success { output: Output::Call(output), .. } = result.result else {
+        bail!("call to `balanceOf` on token {token} failed")
+    };
+    let balance = IERC20::balanceOfCall::abi_decode_returns(&output, false)?._0;
+    Ok((balance, recorder.reads))
+}
+
+/// An inspector that records the storage slots read, in order.
+#[derive(Debug, Default)]
+struct SloadRecorder {
+    reads: Vec<(Address, U256)>,
+}
+
+impl<DB: Database> Inspector<DB> for SloadRecorder {
+    #[inline]
+    fn step(&mut self, interpreter: &mut Interpreter<'_>, _data: &mut EVMData<'_, DB>) {
+        if interpreter.current_opcode() == opcode::SLOAD {
+            if let Ok(slot) = interpreter.stack().peek(0) {
+                self.reads.push((interpreter.contract.address, slot));
+            }
+        }
+    }
+}
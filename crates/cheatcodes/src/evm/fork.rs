@@ -4,9 +4,18 @@ use alloy_providers::provider::TempProvider;
 use alloy_rpc_types::Filter;
 use alloy_sol_types::SolValue;
 use eyre::WrapErr;
-use foundry_common::{provider::alloy::ProviderBuilder, types::ToEthers};
+use foundry_common::{
+    provider::{alloy::ProviderBuilder, state_dump::is_state_dump},
+    types::ToEthers,
+};
 use foundry_compilers::utils::RuntimeOrHandle;
-use foundry_evm_core::fork::CreateFork;
+use foundry_config::fs_permissions::FsAccessKind;
+use foundry_evm_core::{
+    backend::TransactionOverrides,
+    constants::L1_BLOCK_ADDRESS,
+    fork::{CreateFork, L2Chain},
+};
+use std::path::Path;
 
 impl Cheatcode for activeForkCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
@@ -61,6 +70,25 @@ impl Cheatcode for createSelectFork_2Call {
     }
 }
 
+impl Cheatcode for l1BlockNumberCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self {} = self;
+        let chain_id = ccx.data.env.cfg.chain_id;
+        match L2Chain::from_chain_id(chain_id) {
+            // `block.number` is the L1 block number on Arbitrum
+            Some(L2Chain::Arbitrum) => Ok(ccx.data.env.block.number.abi_encode()),
+            Some(L2Chain::OpStack) => {
+                // `L1Block.number` is the `uint64` in the lowest bytes of the first slot
+                ccx.data.journaled_state.load_account(L1_BLOCK_ADDRESS, ccx.data.db)?;
+                let (slot, _) =
+                    ccx.data.journaled_state.sload(L1_BLOCK_ADDRESS, U256::ZERO, ccx.data.db)?;
+                Ok((slot & U256::from(u64::MAX)).abi_encode())
+            }
+            None => bail!("chain {chain_id} is not a known OP Stack or Arbitrum chain"),
+        }
+    }
+}
+
 impl Cheatcode for rollFork_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { blockNumber } = self;
@@ -108,6 +136,22 @@ impl Cheatcode for rollFork_3Call {
     }
 }
 
+impl Cheatcode for rollFork_4Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { blockHash, txIndex } = self;
+        let index = usize::try_from(*txIndex)
+            .map_err(|_| fmt_err!("transaction index {txIndex} is too large"))?;
+        ccx.data.db.roll_fork_to_transaction_index(
+            None,
+            *blockHash,
+            index,
+            ccx.data.env,
+            &mut ccx.data.journaled_state,
+        )?;
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for selectForkCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { forkId } = self;
@@ -150,6 +194,34 @@ impl Cheatcode for transact_1Call {
     }
 }
 
+impl Cheatcode for transactWithOverridesCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { txHash, newSender, newValue, newData } = self;
+        let overrides = TransactionOverrides {
+            from: Some(*newSender).filter(|sender| !sender.is_zero()),
+            value: Some(*newValue),
+            input: Some(newData.clone()).filter(|data| !data.is_empty()),
+        };
+        ccx.data.db.transact_with_overrides(
+            None,
+            *txHash,
+            overrides,
+            ccx.data.env,
+            &mut ccx.data.journaled_state,
+            ccx.state,
+        )?;
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for copyAccountCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { fromForkId, toForkId, account } = *self;
+        ccx.data.db.copy_account(fromForkId, toForkId, account, &mut ccx.data.journaled_state)?;
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for allowCheatcodesCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { account } = self;
@@ -355,15 +427,20 @@ fn create_fork_request<DB: DatabaseExt>(
     url_or_alias: &str,
     block: Option<u64>,
 ) -> Result<CreateFork> {
-    let url = ccx.state.config.rpc_url(url_or_alias)?;
+    let (url, enable_caching) = if is_state_dump(Path::new(url_or_alias)) {
+        // state dumps are served from the file, there is nothing to cache
+        let path = ccx.state.config.ensure_path_allowed(url_or_alias, FsAccessKind::Read)?;
+        ensure!(path.is_file(), "state dump {url_or_alias} does not exist");
+        (path.display().to_string(), false)
+    } else {
+        let url = ccx.state.config.rpc_url(url_or_alias)?;
+        let enable_caching = !ccx.state.config.evm_opts.no_storage_caching &&
+            ccx.state.config.rpc_storage_caching.enable_for_endpoint(&url);
+        (url, enable_caching)
+    };
     let mut evm_opts = ccx.state.config.evm_opts.clone();
     evm_opts.fork_block_number = block;
-    let fork = CreateFork {
-        enable_caching: ccx.state.config.rpc_storage_caching.enable_for_endpoint(&url),
-        url,
-        env: ccx.data.env.clone(),
-        evm_opts,
-    };
+    let fork = CreateFork { enable_caching, url, env: ccx.data.env.clone(), evm_opts };
     Ok(fork)
 }
 
@@ -0,0 +1,592 @@
+//! Decoding and resolution of storage slots with the storage layouts of the project's contracts.
+
+use crate::{CheatsConfig, Result};
+use alloy_dyn_abi::DynSolType;
+use alloy_primitives::{keccak256, Address, Bytes, B256, I256, U256};
+use foundry_common::fs;
+use foundry_compilers::{
+    artifacts::{Storage, StorageLayout, StorageType},
+    ConfigurableContractArtifact,
+};
+use foundry_config::fs_permissions::FsAccessKind;
+use serde_json::Value;
+use std::{collections::HashMap, ops::Range, path::Path};
+use walkdir::WalkDir;
+
+/// The maximum number of slots past the start of a mapping entry or a dynamic array that are
+/// searched for its values.
+const MAX_SPAN: u64 = 1 << 32;
+
+/// The maximum depth of nested mappings which are decoded.
+const MAX_DEPTH: usize = 8;
+
+/// The storage layout of a contract artifact, along with its runtime bytecode to identify its
+/// deployments.
+#[derive(Clone, Debug)]
+pub struct ArtifactLayout {
+    /// The identifier of the contract, i.e. `Counter.sol:Counter`.
+    pub name: String,
+    /// The storage layout of the contract.
+    pub layout: StorageLayout,
+    /// The runtime bytecode of the contract.
+    code: Bytes,
+    /// The ranges of the immutable variables in the runtime bytecode, which differ between
+    /// deployments.
+    immutables: Vec<Range<usize>>,
+}
+
+impl ArtifactLayout {
+    /// Returns whether the given runtime bytecode is a deployment of this contract.
+    pub fn matches(&self, code: &[u8]) -> bool {
+        code.len() == self.code.len() &&
+            code.iter().zip(self.code.iter()).enumerate().all(|(i, (a, b))| {
+                a == b || self.immutables.iter().any(|immutable| immutable.contains(&i))
+            })
+    }
+}
+
+/// Reads the storage layouts of the project's contract artifacts, which are only emitted with
+/// `extra_output = ["storageLayout"]`.
+pub(crate) fn read_layouts(config: &CheatsConfig) -> Result<Vec<ArtifactLayout>> {
+    let root = config.ensure_path_allowed(&config.paths.artifacts, FsAccessKind::Read)?;
+    Ok(read_artifact_layouts(&root))
+}
+
+/// Reads the storage layouts of the contract artifacts in the given directory, skipping the
+/// artifacts without one.
+pub fn read_artifact_layouts(root: &Path) -> Vec<ArtifactLayout> {
+    let build_info = root.join("build-info");
+    let mut layouts = Vec::new();
+    for entry in WalkDir::new(root).into_iter().filter_map(|entry| entry.ok()) {
+        let path = entry.path();
+        if path.starts_with(&build_info) || path.extension().map_or(true, |ext| ext != "json") {
+            continue
+        }
+        let Ok(artifact) = fs::read_json_file::<ConfigurableContractArtifact>(path) else {
+            continue
+        };
+        let Some(layout) = artifact.storage_layout.filter(|layout| !layout.storage.is_empty())
+        else {
+            continue
+        };
+        let Some(deployed) = artifact.deployed_bytecode else { continue };
+        let Some(code) = deployed.bytecode.as_ref().and_then(|code| code.object.as_bytes()) else {
+            continue
+        };
+        let immutables = deployed
+            .immutable_references
+            .values()
+            .flatten()
+            .map(|offsets| offsets.start as usize..(offsets.start + offsets.length) as usize)
+            .collect();
+        let file = path.parent().and_then(|dir| dir.file_name()).unwrap_or_default();
+        let contract = path.file_stem().unwrap_or_default();
+        layouts.push(ArtifactLayout {
+            name: format!("{}:{}", file.to_string_lossy(), contract.to_string_lossy()),
+            layout,
+            code: code.clone(),
+            immutables,
+        });
+    }
+    layouts
+}
+
+/// A variable stored in a storage slot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotVariable {
+    /// The path of the variable, e.g. `owner`, `balances[0x...]` or `config.fee`.
+    pub label: String,
+    /// The Solidity type of the variable, e.g. `uint256`.
+    pub type_name: String,
+    /// The offset of the variable in the slot, in bytes.
+    pub offset: usize,
+    /// The size of the variable, in bytes.
+    pub size: usize,
+}
+
+impl SlotVariable {
+    /// Formats the value of this variable in the given slot value according to its type.
+    pub fn format(&self, slot_value: B256) -> String {
+        let word = U256::from_be_bytes(slot_value.0);
+        let bits = self.size.min(32) * 8;
+        let value = if bits == 256 {
+            word
+        } else {
+            (word >> (self.offset * 8)) & ((U256::from(1) << bits) - U256::from(1))
+        };
+        let ty = self.type_name.as_str();
+        if ty.ends_with("[]") {
+            // The slot of a dynamic array holds its length
+            value.to_string()
+        } else if ty == "bool" {
+            (value != U256::ZERO).to_string()
+        } else if ty.starts_with("address") || ty.starts_with("contract ") {
+            Address::from_word(B256::from(value.to_be_bytes())).to_checksum(None)
+        } else if ty.starts_with("uint") || ty.starts_with("enum ") {
+            value.to_string()
+        } else if ty.starts_with("int") {
+            // Sign-extend the value to 256 bits
+            let value =
+                if bits < 256 && value.bit(bits - 1) { value | (U256::MAX << bits) } else { value };
+            I256::from_raw(value).to_string()
+        } else if ty.strip_prefix("bytes").is_some_and(|n| n.parse::<usize>().is_ok()) {
+            hex::encode_prefixed(&value.to_be_bytes::<32>()[32 - self.size..])
+        } else if ty == "string" || ty == "bytes" {
+            format_short_bytes(ty, slot_value)
+        } else {
+            hex::encode_prefixed(value.to_be_bytes::<32>())
+        }
+    }
+}
+
+/// Formats a `string` or `bytes` value from the slot holding it, which holds its data if it is
+/// shorter than 32 bytes, or its length otherwise.
+fn format_short_bytes(ty: &str, slot_value: B256) -> String {
+    let last = slot_value[31];
+    if last & 1 == 1 {
+        let len = (U256::from_be_bytes(slot_value.0) - U256::from(1)) / U256::from(2);
+        return format!("<{len} bytes>")
+    }
+    let data = &slot_value[..(last / 2) as usize];
+    if ty == "string" {
+        format!("{:?}", String::from_utf8_lossy(data))
+    } else {
+        hex::encode_prefixed(data)
+    }
+}
+
+/// Decodes the storage slots of a contract with its storage layout.
+#[derive(Clone, Copy, Debug)]
+pub struct LayoutDecoder<'a> {
+    layout: &'a StorageLayout,
+    /// The keccak256 preimages of the mapping entry slots, `slot => (key, mapping slot)`, as
+    /// recorded by [`MappingSlots`](super::mapping::MappingSlots).
+    preimages: Option<&'a HashMap<B256, (B256, B256)>>,
+}
+
+impl<'a> LayoutDecoder<'a> {
+    /// Creates a new decoder for the given storage layout. Mapping entries can only be decoded
+    /// if their keys were recorded.
+    pub fn new(
+        layout: &'a StorageLayout,
+        preimages: Option<&'a HashMap<B256, (B256, B256)>>,
+    ) -> Self {
+        Self { layout, preimages }
+    }
+
+    /// Returns the variables stored in the given slot.
+    pub fn variables(&self, slot: U256) -> Vec<SlotVariable> {
+        self.variables_at(slot, 0).into_iter().map(|(var, _)| var).collect()
+    }
+
+    /// Returns the variables stored in the given slot, with their type identifiers.
+    fn variables_at(&self, slot: U256, depth: usize) -> Vec<(SlotVariable, &'a str)> {
+        // Statically allocated variables
+        let mut vars: Vec<_> = self
+            .layout
+            .storage
+            .iter()
+            .flat_map(|var| self.variable_at(var, U256::ZERO, String::new(), slot))
+            .collect();
+        if !vars.is_empty() || depth > MAX_DEPTH {
+            return vars
+        }
+
+        // Mapping entries, stored from the hash of their key and the slot of the mapping
+        for (entry, (key, mapping_slot)) in self.preimages.into_iter().flatten() {
+            let entry = U256::from_be_bytes(entry.0);
+            if !within_span(entry, slot) {
+                continue
+            }
+            for (mapping, type_id) in
+                self.variables_at(U256::from_be_bytes(mapping_slot.0), depth + 1)
+            {
+                let Some(ty) = self.layout.types.get(type_id) else { continue };
+                let (Some(key_type), Some(value_type)) = (&ty.key, &ty.value) else { continue };
+                if ty.encoding != "mapping" {
+                    continue
+                }
+                let label = format!("{}[{}]", mapping.label, self.format_key(key_type, *key));
+                vars.extend(self.value_at(label, value_type, entry, 0, slot));
+            }
+        }
+
+        // Elements of dynamic arrays, stored from the hash of the slot of the array
+        for var in &self.layout.storage {
+            let Some(ty) = self.layout.types.get(&var.storage_type) else { continue };
+            let Some(element_type) = ty.other.get("base").and_then(Value::as_str) else { continue };
+            let Ok(array_slot) = var.slot.parse::<U256>() else { continue };
+            if ty.encoding != "dynamic_array" {
+                continue
+            }
+            let start = U256::from_be_bytes(keccak256(array_slot.to_be_bytes::<32>()).0);
+            if within_span(start, slot) {
+                vars.extend(self.elements_at(&var.label, element_type, start, None, slot));
+            }
+        }
+        vars
+    }
+
+    /// Returns the variables stored in `slot` which are the given statically allocated variable or
+    /// its members, the variable being relative to `base`.
+    fn variable_at(
+        &self,
+        var: &'a Storage,
+        base: U256,
+        prefix: String,
+        slot: U256,
+    ) -> Vec<(SlotVariable, &'a str)> {
+        let Ok(var_slot) = var.slot.parse::<U256>() else { return Vec::new() };
+        let label = format!("{prefix}{}", var.label);
+        self.value_at(label, &var.storage_type, base + var_slot, var.offset as usize, slot)
+    }
+
+    /// Returns the variables stored in `slot` which are the value of the given type stored from
+    /// `start`, or its members or elements.
+    fn value_at(
+        &self,
+        label: String,
+        type_id: &'a str,
+        start: U256,
+        offset: usize,
+        slot: U256,
+    ) -> Vec<(SlotVariable, &'a str)> {
+        let Some(ty) = self.layout.types.get(type_id) else { return Vec::new() };
+        let size = type_size(ty);
+        let Some(delta) = slot.checked_sub(start) else { return Vec::new() };
+        if delta >= U256::from(size.div_ceil(32).max(1)) {
+            return Vec::new()
+        }
+
+        if ty.encoding == "inplace" {
+            if let Some(members) = ty.other.get("members") {
+                let Ok(members) = serde_json::from_value::<Vec<Storage>>(members.clone()) else {
+                    return Vec::new()
+                };
+                // Member types are looked up in the layout so that their identifiers borrow it
+                return members
+                    .iter()
+                    .flat_map(|member| {
+                        let Some((member_type, _)) =
+                            self.layout.types.get_key_value(&member.storage_type)
+                        else {
+                            return Vec::new()
+                        };
+                        let Ok(member_slot) = member.slot.parse::<U256>() else {
+                            return Vec::new()
+                        };
+                        self.value_at(
+                            format!("{label}.{}", member.label),
+                            member_type,
+                            start + member_slot,
+                            member.offset as usize,
+                            slot,
+                        )
+                    })
+                    .collect()
+            }
+            if let Some(element_type) = ty.other.get("base").and_then(Value::as_str) {
+                let Some((element_type, _)) = self.layout.types.get_key_value(element_type) else {
+                    return Vec::new()
+                };
+                let len = static_array_len(ty);
+                return self.elements_at(&label, element_type, start, len, slot)
+            }
+        } else if delta != U256::ZERO {
+            // Only the first slot of a mapping, a dynamic array, or a `bytes` or `string` is
+            // statically allocated, and it holds the length of arrays and long `bytes` or `string`
+            return Vec::new()
+        }
+
+        let var = SlotVariable { label, type_name: ty.label.clone(), offset, size: size.min(32) };
+        vec![(var, type_id)]
+    }
+
+    /// Returns the variables stored in `slot` which are elements of the array of the given element
+    /// type stored from `start`, or their members or elements.
+    fn elements_at(
+        &self,
+        label: &str,
+        element_type: &'a str,
+        start: U256,
+        len: Option<usize>,
+        slot: U256,
+    ) -> Vec<(SlotVariable, &'a str)> {
+        let Some(ty) = self.layout.types.get(element_type) else { return Vec::new() };
+        let Some(delta) = slot.checked_sub(start).and_then(|delta| usize::try_from(delta).ok())
+        else {
+            return Vec::new()
+        };
+        let size = type_size(ty);
+        let in_bounds = |index: usize| len.map_or(true, |len| index < len);
+        if size > 16 {
+            // Each element spans whole slots
+            let slots = size.div_ceil(32);
+            let index = delta / slots;
+            if !in_bounds(index) {
+                return Vec::new()
+            }
+            let element_start = start + U256::from(index * slots);
+            self.value_at(format!("{label}[{index}]"), element_type, element_start, 0, slot)
+        } else {
+            // Elements are packed into slots
+            let per_slot = 32 / size;
+            (0..per_slot)
+                .map(|i| (delta * per_slot + i, i * size))
+                .filter(|(index, _)| in_bounds(*index))
+                .flat_map(|(index, offset)| {
+                    self.value_at(format!("{label}[{index}]"), element_type, slot, offset, slot)
+                })
+                .collect()
+        }
+    }
+
+    /// Formats a mapping key of the given type, as hashed to compute the slot of its entry.
+    fn format_key(&self, key_type: &str, key: B256) -> String {
+        let Some(ty) = self.layout.types.get(key_type) else { return key.to_string() };
+        // Fixed-size byte arrays are left-aligned, and other value types are right-aligned
+        if ty.label.starts_with("bytes") && ty.label != "bytes" {
+            let size = type_size(ty).min(32);
+            return hex::encode_prefixed(&key[..size])
+        }
+        let size = type_size(ty).min(32);
+        SlotVariable { label: String::new(), type_name: ty.label.clone(), offset: 0, size }
+            .format(key)
+    }
+}
+
+/// Resolves the path of a variable, e.g. `owner`, `balances[0x...]`, `config.fee` or
+/// `orders[1].amount`, to the storage slot holding it.
+///
+/// Variables packed into a slot resolve to that slot.
+pub(crate) fn slot_of(layout: &StorageLayout, path: &str) -> Result<U256> {
+    let (name, mut rest) = split_ident(path);
+    let Some(var) = layout.storage.iter().find(|var| var.label == name) else {
+        bail!("variable `{name}` not found in the storage layout")
+    };
+    let mut slot = parse_slot(&var.slot)?;
+    let mut type_id = type_key(layout, &var.storage_type)?;
+    while !rest.is_empty() {
+        let ty = &layout.types[type_id];
+        if let Some(after) = rest.strip_prefix('.') {
+            let (name, after) = split_ident(after);
+            let members = ty
+                .other
+                .get("members")
+                .and_then(|members| serde_json::from_value::<Vec<Storage>>(members.clone()).ok())
+                .ok_or_else(|| fmt_err!("`{}` is not a struct", ty.label))?;
+            let Some(member) = members.iter().find(|member| member.label == name) else {
+                bail!("`{}` has no member `{name}`", ty.label)
+            };
+            slot += parse_slot(&member.slot)?;
+            type_id = type_key(layout, &member.storage_type)?;
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let Some((index, after)) = after.split_once(']') else {
+                bail!("unclosed `[` in `{path}`")
+            };
+            (slot, type_id) = index_slot(layout, ty, slot, index.trim())?;
+            rest = after;
+        } else {
+            bail!("invalid variable path `{path}`")
+        }
+    }
+    Ok(slot)
+}
+
+/// Returns the slot and the type of the value at `index` of the mapping or array of the given type
+/// stored at `slot`.
+fn index_slot<'a>(
+    layout: &'a StorageLayout,
+    ty: &'a StorageType,
+    slot: U256,
+    index: &str,
+) -> Result<(U256, &'a str)> {
+    if ty.encoding == "mapping" {
+        let (Some(key_type), Some(value_type)) = (&ty.key, &ty.value) else {
+            bail!("invalid mapping type `{}`", ty.label)
+        };
+        let key = encode_key(&layout.types[type_key(layout, key_type)?].label, index)?;
+        let slot = keccak256([key.as_slice(), slot.to_be_bytes::<32>().as_slice()].concat());
+        return Ok((U256::from_be_bytes(slot.0), type_key(layout, value_type)?))
+    }
+
+    let Some(element_type) = ty.other.get("base").and_then(Value::as_str) else {
+        bail!("`{}` cannot be indexed", ty.label)
+    };
+    let element_type = type_key(layout, element_type)?;
+    let index: usize =
+        index.parse().map_err(|_| fmt_err!("invalid index `{index}` of `{}`", ty.label))?;
+    let start = if ty.encoding == "dynamic_array" {
+        U256::from_be_bytes(keccak256(slot.to_be_bytes::<32>()).0)
+    } else {
+        if let Some(len) = static_array_len(ty) {
+            ensure!(index < len, "index {index} out of bounds of `{}`", ty.label);
+        }
+        slot
+    };
+    // Elements of up to 16 bytes are packed into slots, and larger ones span whole slots
+    let size = type_size(&layout.types[element_type]);
+    let offset = if size > 16 { index * size.div_ceil(32) } else { index / (32 / size) };
+    Ok((start + U256::from(offset), element_type))
+}
+
+/// Encodes a mapping key of the given type, as hashed to compute the slot of its entry.
+fn encode_key(ty: &str, key: &str) -> Result<Vec<u8>> {
+    match ty {
+        "string" => Ok(key.trim_matches('"').as_bytes().to_vec()),
+        "bytes" => Ok(hex::decode(key)?),
+        _ => {
+            let ty = if ty.starts_with("address") || ty.starts_with("contract ") {
+                "address"
+            } else if ty.starts_with("enum ") {
+                "uint8"
+            } else {
+                ty
+            };
+            let ty = DynSolType::parse(ty)
+                .map_err(|err| fmt_err!("unsupported mapping key type `{ty}`: {err}"))?;
+            crate::string::parse(key, &ty)
+        }
+    }
+}
+
+/// Splits the leading identifier off a variable path.
+fn split_ident(path: &str) -> (&str, &str) {
+    let end = path.find(|c| c == '.' || c == '[').unwrap_or(path.len());
+    (path[..end].trim(), &path[end..])
+}
+
+/// Parses a slot of the storage layout.
+fn parse_slot(slot: &str) -> Result<U256> {
+    slot.parse().map_err(|_| fmt_err!("invalid slot `{slot}` in the storage layout"))
+}
+
+/// Returns the identifier of a type, borrowed from the storage layout.
+fn type_key<'a>(layout: &'a StorageLayout, type_id: &str) -> Result<&'a str> {
+    match layout.types.get_key_value(type_id) {
+        Some((key, _)) => Ok(key),
+        None => bail!("type `{type_id}` not found in the storage layout"),
+    }
+}
+
+/// Returns the size of a type in storage, in bytes.
+fn type_size(ty: &StorageType) -> usize {
+    ty.number_of_bytes.parse().unwrap_or(32).max(1)
+}
+
+/// Returns the length of a static array type, i.e. `3` for `uint256[3]`.
+fn static_array_len(ty: &StorageType) -> Option<usize> {
+    ty.label.rsplit_once('[').and_then(|(_, len)| len.trim_end_matches(']').parse().ok())
+}
+
+/// Returns whether `slot` is within [`MAX_SPAN`] slots past `start`.
+fn within_span(start: U256, slot: U256) -> bool {
+    slot.checked_sub(start).is_some_and(|delta| delta < U256::from(MAX_SPAN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn layout() -> StorageLayout {
+        serde_json::from_value(json!({
+            "storage": [
+                { "astId": 1, "contract": "C", "label": "owner", "offset": 0, "slot": "0", "type": "t_address" },
+                { "astId": 2, "contract": "C", "label": "paused", "offset": 20, "slot": "0", "type": "t_bool" },
+                { "astId": 3, "contract": "C", "label": "balances", "offset": 0, "slot": "1", "type": "t_mapping(t_address,t_uint256)" },
+                { "astId": 4, "contract": "C", "label": "config", "offset": 0, "slot": "2", "type": "t_struct(Config)1_storage" },
+                { "astId": 5, "contract": "C", "label": "deltas", "offset": 0, "slot": "4", "type": "t_array(t_int128)dyn_storage" }
+            ],
+            "types": {
+                "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+                "t_bool": { "encoding": "inplace", "label": "bool", "numberOfBytes": "1" },
+                "t_int128": { "encoding": "inplace", "label": "int128", "numberOfBytes": "16" },
+                "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+                "t_mapping(t_address,t_uint256)": {
+                    "encoding": "mapping", "key": "t_address", "label": "mapping(address => uint256)",
+                    "numberOfBytes": "32", "value": "t_uint256"
+                },
+                "t_array(t_int128)dyn_storage": {
+                    "base": "t_int128", "encoding": "dynamic_array", "label": "int128[]", "numberOfBytes": "32"
+                },
+                "t_struct(Config)1_storage": {
+                    "encoding": "inplace", "label": "struct C.Config", "numberOfBytes": "64",
+                    "members": [
+                        { "astId": 6, "contract": "C", "label": "fee", "offset": 0, "slot": "0", "type": "t_uint256" },
+                        { "astId": 7, "contract": "C", "label": "admin", "offset": 0, "slot": "1", "type": "t_address" }
+                    ]
+                }
+            }
+        }))
+        .unwrap()
+    }
+
+    fn labels(decoder: &LayoutDecoder<'_>, slot: U256) -> Vec<String> {
+        decoder.variables(slot).into_iter().map(|var| var.label).collect()
+    }
+
+    #[test]
+    fn decodes_static_variables() {
+        let layout = layout();
+        let decoder = LayoutDecoder::new(&layout, None);
+        assert_eq!(labels(&decoder, U256::from(0)), ["owner", "paused"]);
+        assert_eq!(labels(&decoder, U256::from(2)), ["config.fee"]);
+        assert_eq!(labels(&decoder, U256::from(3)), ["config.admin"]);
+        assert_eq!(labels(&decoder, U256::from(4)), ["deltas"]);
+        assert!(labels(&decoder, U256::from(5)).is_empty());
+
+        let vars = decoder.variables(U256::from(0));
+        let value = B256::left_padding_from(&[[1].as_slice(), &[0xaa; 20]].concat());
+        assert_eq!(vars[0].format(value), Address::repeat_byte(0xaa).to_checksum(None));
+        assert_eq!(vars[1].format(value), "true");
+    }
+
+    #[test]
+    fn decodes_dynamic_variables() {
+        let layout = layout();
+        let account = Address::repeat_byte(0x11);
+        let key = B256::left_padding_from(account.as_slice());
+        let mapping_slot = B256::from(U256::from(1));
+        let entry = keccak256([key.as_slice(), mapping_slot.as_slice()].concat());
+        let preimages = HashMap::from([(entry, (key, mapping_slot))]);
+        let decoder = LayoutDecoder::new(&layout, Some(&preimages));
+        assert_eq!(
+            labels(&decoder, U256::from_be_bytes(entry.0)),
+            [format!("balances[{}]", account.to_checksum(None))]
+        );
+
+        let start = U256::from_be_bytes(keccak256(U256::from(4).to_be_bytes::<32>()).0);
+        let vars = decoder.variables(start + U256::from(1));
+        assert_eq!(
+            vars.iter().map(|var| var.label.as_str()).collect::<Vec<_>>(),
+            ["deltas[2]", "deltas[3]"]
+        );
+        let value = B256::from(U256::MAX);
+        assert_eq!(vars[1].format(value), "-1");
+    }
+
+    #[test]
+    fn resolves_slots() {
+        let layout = layout();
+        assert_eq!(slot_of(&layout, "paused").unwrap(), U256::from(0));
+        assert_eq!(slot_of(&layout, "config.admin").unwrap(), U256::from(3));
+
+        let account = Address::repeat_byte(0x11);
+        let key = B256::left_padding_from(account.as_slice());
+        let entry = keccak256([key.as_slice(), B256::from(U256::from(1)).as_slice()].concat());
+        assert_eq!(
+            slot_of(&layout, &format!("balances[{account}]")).unwrap(),
+            U256::from_be_bytes(entry.0)
+        );
+
+        let start = U256::from_be_bytes(keccak256(U256::from(4).to_be_bytes::<32>()).0);
+        assert_eq!(slot_of(&layout, "deltas[3]").unwrap(), start + U256::from(1));
+
+        assert!(slot_of(&layout, "missing").is_err());
+        assert!(slot_of(&layout, "config.missing").is_err());
+        assert!(slot_of(&layout, "owner[0]").is_err());
+        assert!(slot_of(&layout, "balances[0x11").is_err());
+    }
+}
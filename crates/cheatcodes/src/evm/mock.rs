@@ -10,6 +10,25 @@ pub struct MockCallDataContext {
     pub calldata: Bytes,
     /// The value to match for mock
     pub value: Option<U256>,
+    /// The mask applied to both the calldata and the call input before matching, if any
+    pub mask: Option<Bytes>,
+}
+
+impl MockCallDataContext {
+    /// Returns `true` if a call with the given input and value matches this mock, either
+    /// partially or fully.
+    pub fn matches(&self, input: &[u8], value: U256) -> bool {
+        let Some(input) = input.get(..self.calldata.len()) else { return false };
+        let calldata_matches = match &self.mask {
+            Some(mask) => input
+                .iter()
+                .zip(self.calldata.iter())
+                .zip(mask.iter())
+                .all(|((input, calldata), mask)| input & mask == calldata & mask),
+            None => input == &self.calldata[..],
+        };
+        calldata_matches && self.value.map_or(true, |v| v == value)
+    }
 }
 
 /// Mocked return data.
@@ -34,7 +53,13 @@ impl Ord for MockCallDataContext {
         // a partial match to calldata that is more specific than
         // a match to a msg.value, then the more specific calldata takes
         // precedence.
-        self.calldata.cmp(&other.calldata).reverse().then(self.value.cmp(&other.value).reverse())
+        // Unmasked calldata is tighter than any masked calldata, so it always takes precedence.
+        self.mask
+            .is_some()
+            .cmp(&other.mask.is_some())
+            .then(self.calldata.cmp(&other.calldata).reverse())
+            .then(self.value.cmp(&other.value).reverse())
+            .then(self.mask.cmp(&other.mask))
     }
 }
 
@@ -42,6 +67,8 @@ impl Cheatcode for clearMockedCallsCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self {} = self;
         state.mocked_calls = Default::default();
+        state.mocked_functions = Default::default();
+        state.mocked_precompiles = Default::default();
         Ok(Default::default())
     }
 }
@@ -49,21 +76,36 @@ impl Cheatcode for clearMockedCallsCall {
 impl Cheatcode for mockCall_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { callee, data, returnData } = self;
-        let (acc, _) = ccx.data.journaled_state.load_account(*callee, ccx.data.db)?;
-
-        // Etches a single byte onto the account if it is empty to circumvent the `extcodesize`
-        // check Solidity might perform.
-        let empty_bytecode = acc.info.code.as_ref().map_or(true, Bytecode::is_empty);
-        if empty_bytecode {
-            let code = Bytecode::new_raw(Bytes::from_static(&[0u8])).to_checked();
-            ccx.data.journaled_state.set_code(*callee, code);
-        }
-
+        make_acc_non_empty(callee, ccx)?;
         mock_call(ccx.state, callee, data, None, returnData, InstructionResult::Return);
         Ok(Default::default())
     }
 }
 
+impl Cheatcode for mockCallMaskedCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { callee, data, mask, returnData } = self;
+        ensure!(
+            data.len() == mask.len(),
+            "calldata and mask must have the same length, got {} and {}",
+            data.len(),
+            mask.len()
+        );
+        make_acc_non_empty(callee, ccx)?;
+        // normalize the calldata, so that mocks differing only in masked out bytes are the same
+        let calldata: Vec<u8> =
+            data.iter().zip(mask.iter()).map(|(data, mask)| data & mask).collect();
+        ccx.state.mocked_calls.entry(*callee).or_default().insert(
+            MockCallDataContext { calldata, value: None, mask: Some(Bytes::copy_from_slice(mask)) },
+            MockCallReturnData {
+                ret_type: InstructionResult::Return,
+                data: Bytes::copy_from_slice(returnData),
+            },
+        );
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for mockCall_1Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { callee, msgValue, data, returnData } = self;
@@ -89,6 +131,41 @@ impl Cheatcode for mockCallRevert_1Call {
     }
 }
 
+impl Cheatcode for mockFunctionCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { callee, target, data } = self;
+        make_acc_non_empty(callee, ccx)?;
+        ccx.state
+            .mocked_functions
+            .entry(*callee)
+            .or_default()
+            .insert(Bytes::copy_from_slice(data), *target);
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for mockPrecompileCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { precompile, input, output } = self;
+        ensure!(ccx.is_precompile(precompile), "{precompile} is not a precompile");
+        ccx.state
+            .mocked_precompiles
+            .entry(*precompile)
+            .or_default()
+            .insert(Bytes::copy_from_slice(input), Bytes::copy_from_slice(output));
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for disablePrecompileCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { precompile } = self;
+        ensure!(ccx.is_precompile(precompile), "{precompile} is not a precompile");
+        ccx.state.disabled_precompiles.insert(*precompile);
+        Ok(Default::default())
+    }
+}
+
 #[allow(clippy::ptr_arg)] // Not public API, doesn't matter
 fn mock_call(
     state: &mut Cheatcodes,
@@ -99,7 +176,23 @@ fn mock_call(
     ret_type: InstructionResult,
 ) {
     state.mocked_calls.entry(*callee).or_default().insert(
-        MockCallDataContext { calldata: Bytes::copy_from_slice(cdata), value: value.copied() },
+        MockCallDataContext {
+            calldata: Bytes::copy_from_slice(cdata),
+            value: value.copied(),
+            mask: None,
+        },
         MockCallReturnData { ret_type, data: Bytes::copy_from_slice(rdata) },
     );
 }
+
+/// Etches a single byte onto the account if it is empty to circumvent the `extcodesize`
+/// check Solidity might perform.
+fn make_acc_non_empty<DB: DatabaseExt>(callee: &Address, ccx: &mut CheatsCtxt<DB>) -> Result<()> {
+    let (acc, _) = ccx.data.journaled_state.load_account(*callee, ccx.data.db)?;
+    let empty_bytecode = acc.info.code.as_ref().map_or(true, Bytecode::is_empty);
+    if empty_bytecode {
+        let code = Bytecode::new_raw(Bytes::from_static(&[0u8])).to_checked();
+        ccx.data.journaled_state.set_code(*callee, code);
+    }
+    Ok(())
+}
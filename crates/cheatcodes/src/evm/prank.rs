@@ -14,6 +14,8 @@ pub struct Prank {
     pub new_origin: Option<Address>,
     /// The depth at which the prank was called
     pub depth: u64,
+    /// The number of call frames below the next call at which `msg.sender` is set
+    pub call_depth: u64,
     /// Whether the prank stops by itself after the next call
     pub single_call: bool,
     /// Whether the prank has been used yet (false if unused)
@@ -28,6 +30,7 @@ impl Prank {
         new_caller: Address,
         new_origin: Option<Address>,
         depth: u64,
+        call_depth: u64,
         single_call: bool,
     ) -> Prank {
         Prank {
@@ -36,11 +39,25 @@ impl Prank {
             new_caller,
             new_origin,
             depth,
+            call_depth,
             single_call,
             used: false,
         }
     }
 
+    /// Returns `true` if the prank applies to a call or create made by `caller` at `depth`.
+    ///
+    /// Calls below the next call are not made by the pranking contract, so they are only
+    /// considered if the prank targets a deeper call.
+    pub fn applies_to(&self, depth: u64, caller: Address) -> bool {
+        depth >= self.depth && (caller == self.prank_caller || self.call_depth > 0)
+    }
+
+    /// Returns `true` if `msg.sender` should be set for a call or create made at `depth`.
+    pub fn is_target_depth(&self, depth: u64) -> bool {
+        depth == self.depth + self.call_depth
+    }
+
     /// Apply the prank by setting `used` to true iff it is false
     /// Only returns self in the case it is updated (first application)
     pub fn first_time_applied(&self) -> Option<Self> {
@@ -55,28 +72,44 @@ impl Prank {
 impl Cheatcode for prank_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { msgSender } = self;
-        prank(ccx, msgSender, None, true)
+        prank(ccx, msgSender, None, 0, true)
     }
 }
 
 impl Cheatcode for startPrank_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { msgSender } = self;
-        prank(ccx, msgSender, None, false)
+        prank(ccx, msgSender, None, 0, false)
     }
 }
 
 impl Cheatcode for prank_1Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { msgSender, txOrigin } = self;
-        prank(ccx, msgSender, Some(txOrigin), true)
+        prank(ccx, msgSender, Some(txOrigin), 0, true)
     }
 }
 
 impl Cheatcode for startPrank_1Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { msgSender, txOrigin } = self;
-        prank(ccx, msgSender, Some(txOrigin), false)
+        prank(ccx, msgSender, Some(txOrigin), 0, false)
+    }
+}
+
+impl Cheatcode for startPrankOriginCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { txOrigin } = self;
+        // Pranking `msg.sender` as the caller itself leaves it untouched
+        let caller = ccx.caller;
+        prank(ccx, &caller, Some(txOrigin), 0, false)
+    }
+}
+
+impl Cheatcode for prankDepthCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { msgSender, depth } = self;
+        prank(ccx, msgSender, None, *depth, true)
     }
 }
 
@@ -92,6 +125,7 @@ fn prank<DB: DatabaseExt>(
     ccx: &mut CheatsCtxt<DB>,
     new_caller: &Address,
     new_origin: Option<&Address>,
+    call_depth: u64,
     single_call: bool,
 ) -> Result {
     let prank = Prank::new(
@@ -100,6 +134,7 @@ fn prank<DB: DatabaseExt>(
         *new_caller,
         new_origin.copied(),
         ccx.data.journaled_state.depth(),
+        call_depth,
         single_call,
     );
 
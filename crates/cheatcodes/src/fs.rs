@@ -1,15 +1,17 @@
 //! Implementations of [`Filesystem`](crate::Group::Filesystem) cheatcodes.
 
-use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
-use alloy_json_abi::ContractObject;
-use alloy_primitives::U256;
+use crate::{evm::layout, Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_json_abi::{ContractObject, JsonAbi};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_sol_types::SolValue;
 use foundry_common::{fs, get_artifact_path};
+use foundry_compilers::{artifacts::StorageLayout, ConfigurableContractArtifact};
 use foundry_config::fs_permissions::FsAccessKind;
+use globset::GlobBuilder;
 use std::{
-    collections::hash_map::Entry,
+    collections::{hash_map::Entry, BTreeMap},
     io::{BufRead, BufReader, Write},
-    path::Path,
+    path::{Component, Path, PathBuf},
     process::Command,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -135,6 +137,45 @@ impl Cheatcode for readDir_2Call {
     }
 }
 
+impl Cheatcode for readDirRecursiveCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { path, maxDepth } = self;
+        let root = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+        let max_depth = if *maxDepth == 0 { u64::MAX } else { *maxDepth };
+        let paths =
+            walk_files(&root, max_depth).map(|path| path.display().to_string()).collect::<Vec<_>>();
+        Ok(paths.abi_encode())
+    }
+}
+
+impl Cheatcode for globCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { pattern } = self;
+        // Only the directory before the first component with wildcards is searched
+        let is_literal = |component: &Component<'_>| {
+            !component.as_os_str().to_string_lossy().contains(&GLOB_CHARS[..])
+        };
+        let base = Path::new(pattern).components().take_while(is_literal).collect::<PathBuf>();
+        let rest = Path::new(pattern).components().skip_while(is_literal).collect::<PathBuf>();
+        let root = state.config.ensure_path_allowed(&base, FsAccessKind::Read)?;
+        if rest.as_os_str().is_empty() {
+            let paths = if root.is_file() { vec![root.display().to_string()] } else { vec![] };
+            return Ok(paths.abi_encode())
+        }
+
+        let matcher = GlobBuilder::new(&rest.to_string_lossy())
+            .literal_separator(true)
+            .build()
+            .map_err(|e| fmt_err!("invalid glob pattern {pattern:?}: {e}"))?
+            .compile_matcher();
+        let paths = walk_files(&root, u64::MAX)
+            .filter(|path| path.strip_prefix(&root).is_ok_and(|path| matcher.is_match(path)))
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        Ok(paths.abi_encode())
+    }
+}
+
 impl Cheatcode for readFileCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { path } = self;
@@ -266,6 +307,75 @@ impl Cheatcode for getDeployedCodeCall {
     }
 }
 
+impl Cheatcode for getAbiCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path } = self;
+        let abi = read_abi(state, path)?;
+        Ok(serde_json::to_string(&abi)?.abi_encode())
+    }
+}
+
+impl Cheatcode for getMethodIdentifiersCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path } = self;
+        let abi = read_abi(state, path)?;
+        let identifiers = abi
+            .functions()
+            .map(|func| (func.signature(), func.selector()))
+            .collect::<BTreeMap<_, _>>();
+        let (signatures, selectors): (Vec<_>, Vec<_>) = identifiers.into_iter().unzip();
+        Ok((signatures, selectors).abi_encode_params())
+    }
+}
+
+impl Cheatcode for getCodeHashCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path } = self;
+        let object = read_bytecode(state, path)?;
+        if let Some(bin) = object.deployed_bytecode {
+            Ok(keccak256(bin).abi_encode())
+        } else {
+            Err(fmt_err!("No deployed bytecode for contract. Is it abstract or unlinked?"))
+        }
+    }
+}
+
+impl Cheatcode for getDeployedCodeWithImmutablesCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path, values } = self;
+        let Some(deployed) = read_artifact(state, path)?.deployed_bytecode else {
+            bail!("No deployed bytecode for contract. Is it abstract or unlinked?")
+        };
+        let Some(code) = deployed.bytecode.and_then(|bytecode| bytecode.object.into_bytes()) else {
+            bail!("No deployed bytecode for contract. Is it abstract or unlinked?")
+        };
+
+        // Immutable variables are referenced by their AST IDs, which follow their declaration order
+        let mut references = deployed.immutable_references.into_iter().collect::<Vec<_>>();
+        references.sort_by_key(|(id, _)| id.parse::<u64>().unwrap_or(u64::MAX));
+        ensure!(
+            values.len() == references.len(),
+            "expected {} immutable values, got {}",
+            references.len(),
+            values.len()
+        );
+
+        let mut code = code.to_vec();
+        for ((_, offsets), value) in references.iter().zip(values) {
+            ensure!(value.len() <= 32, "immutable values must be at most 32 bytes");
+            let word = B256::left_padding_from(value);
+            for offset in offsets {
+                let range = offset.start as usize..(offset.start + offset.length) as usize;
+                let Some(slice) = code.get_mut(range) else {
+                    bail!("immutable reference out of bounds of the deployed bytecode")
+                };
+                slice.copy_from_slice(&word[32 - slice.len()..]);
+            }
+        }
+        Ok(code.abi_encode())
+    }
+}
+
 /// Reads the bytecode object(s) from the matching artifact
 fn read_bytecode(state: &Cheatcodes, path: &str) -> Result<ContractObject> {
     let path = get_artifact_path(&state.config.paths, path);
@@ -274,6 +384,69 @@ fn read_bytecode(state: &Cheatcodes, path: &str) -> Result<ContractObject> {
     serde_json::from_str::<ContractObject>(&data).map_err(Into::into)
 }
 
+impl Cheatcode for getStorageLayoutCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path } = self;
+        let layout = read_storage_layout(state, path)?;
+        Ok(serde_json::to_string(&layout)?.abi_encode())
+    }
+}
+
+impl Cheatcode for slotOfCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { artifactPath: path, variablePath } = self;
+        let layout = read_storage_layout(state, path)?;
+        let slot = layout::slot_of(&layout, variablePath)?;
+        Ok(B256::from(slot).abi_encode())
+    }
+}
+
+impl Cheatcode for getDeploymentCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { contractName, chainId } = self;
+
+        let path = state
+            .config
+            .root
+            .join(&state.config.deployments)
+            .join(chainId.to_string())
+            .join(format!("{contractName}.json"));
+        let path = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+        let data = fs::read_to_string(&path).map_err(|_| {
+            fmt_err!(
+                "no deployment of {contractName} on chain {chainId} found at {}",
+                path.display()
+            )
+        })?;
+        let deployment: serde_json::Value = serde_json::from_str(&data)?;
+        let Some(address) = deployment["address"].as_str().and_then(|a| a.parse::<Address>().ok())
+        else {
+            bail!("invalid deployment file {}: missing address", path.display())
+        };
+        Ok(address.abi_encode())
+    }
+}
+
+/// Reads the ABI from the matching artifact
+fn read_abi(state: &Cheatcodes, path: &str) -> Result<JsonAbi> {
+    read_bytecode(state, path)?.abi.ok_or_else(|| fmt_err!("No ABI for contract."))
+}
+
+/// Reads the matching artifact, including its extra outputs
+fn read_artifact(state: &Cheatcodes, path: &str) -> Result<ConfigurableContractArtifact> {
+    let path = get_artifact_path(&state.config.paths, path);
+    let path = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+    let data = fs::read_to_string(path)?;
+    serde_json::from_str::<ConfigurableContractArtifact>(&data).map_err(Into::into)
+}
+
+/// Reads the storage layout from the matching artifact
+fn read_storage_layout(state: &Cheatcodes, path: &str) -> Result<StorageLayout> {
+    read_artifact(state, path)?.storage_layout.ok_or_else(|| {
+        fmt_err!("No storage layout for contract. Is `storageLayout` in `extra_output`?")
+    })
+}
+
 impl Cheatcode for ffiCall {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { commandInput: input } = self;
@@ -338,6 +511,23 @@ fn read_dir(state: &Cheatcodes, path: &Path, max_depth: u64, follow_links: bool)
     Ok(paths.abi_encode())
 }
 
+/// The characters which make a path component a glob pattern.
+const GLOB_CHARS: [char; 4] = ['*', '?', '[', '{'];
+
+/// Returns the paths of the files in the given directory and its subdirectories, up to
+/// `max_depth`, sorted by path. Entries which cannot be read are skipped.
+fn walk_files(root: &Path, max_depth: u64) -> impl Iterator<Item = PathBuf> {
+    WalkDir::new(root)
+        .min_depth(1)
+        .max_depth(max_depth.try_into().unwrap_or(usize::MAX))
+        .same_file_system(true)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+}
+
 fn ffi(state: &Cheatcodes, input: &[String]) -> Result<FfiResult> {
     ensure!(
         state.config.ffi,
@@ -0,0 +1,146 @@
+//! Implementations of the HTTP request [`Filesystem`](crate::Group::Filesystem) cheatcodes.
+
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_primitives::U256;
+use alloy_sol_types::SolValue;
+use foundry_compilers::utils::RuntimeOrHandle;
+use foundry_config::HttpPermissions;
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    redirect, Client, Method,
+};
+
+/// The maximum number of redirects followed by a request.
+const MAX_REDIRECTS: usize = 10;
+
+impl Cheatcode for httpGetCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { url } = self;
+        http_request(state, Method::GET, url, None, &[]).map(|res| res.abi_encode())
+    }
+}
+
+impl Cheatcode for httpPostCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { url, body, headers } = self;
+        http_request(state, Method::POST, url, Some(body), headers).map(|res| res.abi_encode())
+    }
+}
+
+fn http_request(
+    state: &Cheatcodes,
+    method: Method,
+    url: &str,
+    body: Option<&str>,
+    headers: &[String],
+) -> Result<HttpResponse> {
+    state.config.ensure_url_allowed(url)?;
+
+    let client = client(state.config.http_permissions.clone())
+        .map_err(|err| fmt_err!("failed to build http client: {err}"))?;
+    let mut request = client.request(method.clone(), url).headers(parse_headers(headers)?);
+    if let Some(body) = body {
+        request = request.body(body.to_string());
+    }
+
+    debug!(target: "cheatcodes", %method, %url, "sending http request");
+
+    let (status, body) = RuntimeOrHandle::new()
+        .block_on(async {
+            let response = request.send().await?;
+            let status = response.status().as_u16();
+            let body = response.bytes().await?;
+            Ok::<_, reqwest::Error>((status, body))
+        })
+        .map_err(|err| match std::error::Error::source(&err) {
+            Some(source) => fmt_err!("{method} request to {url} failed: {err}: {source}"),
+            None => fmt_err!("{method} request to {url} failed: {err}"),
+        })?;
+
+    Ok(HttpResponse { status: U256::from(status), body: body.to_vec() })
+}
+
+/// Returns an HTTP client which only follows redirects to URLs allowed by the permissions, so that
+/// an allowed URL can't redirect requests to any other host.
+fn client(permissions: HttpPermissions) -> reqwest::Result<Client> {
+    let policy = redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            attempt.error("too many redirects")
+        } else if permissions.is_url_allowed(attempt.url().as_str()) {
+            attempt.follow()
+        } else {
+            let error = format!(
+                "the redirect to {} is not allowed; add it to `http_permissions` to allow it",
+                attempt.url()
+            );
+            attempt.error(error)
+        }
+    });
+    Client::builder().redirect(policy).build()
+}
+
+/// Parses headers formatted as `Name: value`.
+fn parse_headers(headers: &[String]) -> Result<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for header in headers {
+        let (name, value) =
+            header.split_once(':').ok_or_else(|| fmt_err!("invalid header {header:?}"))?;
+        let name = HeaderName::from_bytes(name.trim().as_bytes())
+            .map_err(|err| fmt_err!("invalid header name {name:?}: {err}"))?;
+        let value = HeaderValue::from_str(value.trim())
+            .map_err(|err| fmt_err!("invalid header value {value:?}: {err}"))?;
+        map.append(name, value);
+    }
+    Ok(map)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+    };
+
+    /// Serves a redirect to `location` for every request, and returns the URL of the server.
+    fn serve_redirect(location: &'static str) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = [0; 1024];
+                let _ = stream.read(&mut buf);
+                let response = format!(
+                    "HTTP/1.1 302 Found\r\nLocation: {location}\r\nContent-Length: 0\r\n\r\n"
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        format!("http://{addr}")
+    }
+
+    #[test]
+    fn denies_redirects_to_disallowed_urls() {
+        let url = serve_redirect("http://localhost:1/secret");
+        let client = client(HttpPermissions::new([url.clone()])).unwrap();
+        let err =
+            RuntimeOrHandle::new().block_on(client.get(format!("{url}/api")).send()).unwrap_err();
+        assert!(err.is_redirect());
+        let err = std::error::Error::source(&err).unwrap().to_string();
+        assert!(err.contains("the redirect to http://localhost:1/secret is not allowed"), "{err}");
+    }
+
+    #[test]
+    fn parses_headers() {
+        let headers = parse_headers(&[
+            "Content-Type: application/json".to_string(),
+            "x-api-key:secret".to_string(),
+        ])
+        .unwrap();
+        assert_eq!(headers["content-type"], "application/json");
+        assert_eq!(headers["x-api-key"], "secret");
+
+        assert!(parse_headers(&["no separator".to_string()]).is_err());
+        assert!(parse_headers(&["bad name: value".to_string()]).is_err());
+    }
+}
@@ -8,22 +8,27 @@ use crate::{
         DealRecord, RecordAccess,
     },
     script::Broadcast,
-    test::expect::{
-        self, ExpectedCallData, ExpectedCallTracker, ExpectedCallType, ExpectedEmit,
-        ExpectedRevert, ExpectedRevertKind,
+    test::{
+        assume::AssumeNoRevert,
+        cheat_state::CheatState,
+        expect::{
+            self, ExpectedCallData, ExpectedCallTracker, ExpectedCallType, ExpectedEmit,
+            ExpectedRevert, ExpectedRevertKind,
+        },
     },
     CheatsConfig, CheatsCtxt, Error, Result, Vm,
 };
-use alloy_primitives::{Address, Bytes, B256, U256, U64};
-use alloy_rpc_types::request::TransactionRequest;
+use alloy_primitives::{keccak256, Address, Bytes, FixedBytes, B256, U256, U64};
+use alloy_rpc_types::{request::TransactionRequest, AccessList};
 use alloy_signer::LocalWallet;
 use alloy_sol_types::{SolInterface, SolValue};
 use foundry_common::{evm::Breakpoints, provider::alloy::RpcUrl};
 use foundry_evm_core::{
     backend::{DatabaseError, DatabaseExt, RevertDiagnostic},
-    constants::{CHEATCODE_ADDRESS, DEFAULT_CREATE2_DEPLOYER, HARDHAT_CONSOLE_ADDRESS},
+    constants::{CHEATCODE_ADDRESS, HARDHAT_CONSOLE_ADDRESS, MAGIC_ASSUME},
 };
 use itertools::Itertools;
+use rand::{rngs::StdRng, SeedableRng};
 use revm::{
     interpreter::{
         opcode, CallInputs, CallScheme, CreateInputs, Gas, InstructionResult, Interpreter,
@@ -33,12 +38,12 @@ use revm::{
 };
 use serde_json::Value;
 use std::{
-    collections::{BTreeMap, HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     fs::File,
     io::BufReader,
     ops::Range,
     path::PathBuf,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 macro_rules! try_or_continue {
@@ -55,6 +60,8 @@ macro_rules! try_or_continue {
 pub struct Context {
     /// Buffered readers for files opened for reading (path => BufReader mapping)
     pub opened_read_files: HashMap<PathBuf, BufReader<File>>,
+    /// The RNG of the random cheatcodes, see [`Cheatcodes::rng`]
+    pub rng: Option<StdRng>,
 }
 
 /// Every time we clone `Context`, we want it to be empty
@@ -69,6 +76,7 @@ impl Context {
     #[inline]
     pub fn clear(&mut self) {
         self.opened_read_files.clear();
+        self.rng = None;
     }
 }
 
@@ -79,6 +87,11 @@ pub struct BroadcastableTransaction {
     pub rpc: Option<RpcUrl>,
     /// The transaction to broadcast.
     pub transaction: TransactionRequest,
+    /// The EIP-2718 type of the transaction, if set by `vm.setTxType`.
+    pub tx_type: Option<u8>,
+    /// The owner signing the transaction as an ERC-4337 user operation of its `from` smart
+    /// account, if it's sent by `vm.broadcastUserOp`.
+    pub user_op_owner: Option<Address>,
 }
 
 /// List of transactions that can be broadcasted.
@@ -135,6 +148,27 @@ pub struct Cheatcodes {
     /// Expected revert information
     pub expected_revert: Option<ExpectedRevert>,
 
+    /// Assume next call can revert and discard fuzz run if it does.
+    pub assume_no_revert: Option<AssumeNoRevert>,
+
+    /// Values added to the fuzz dictionary with `addFuzzFixture`.
+    pub fuzz_fixtures: Vec<B256>,
+    /// Ordered sequences of selectors of a target contract declared with `targetFlow`, which the
+    /// invariant fuzzer calls as a single unit.
+    pub targeted_flows: Vec<(Address, Vec<FixedBytes<4>>)>,
+    /// Number of fuzz runs set with `setFuzzRuns`, overriding the fuzz config.
+    pub fuzz_runs: Option<u32>,
+    /// Maximum number of rejected fuzz inputs set with `setMaxRejects`, overriding the fuzz
+    /// config.
+    pub fuzz_max_rejects: Option<u32>,
+
+    /// Keys passed to `assumeUnique`, per test function selector, with the calldata of the fuzz
+    /// run that first used them. Shared between the clones of the inspector used for each run.
+    pub assumed_unique_keys: Arc<Mutex<HashMap<([u8; 4], B256), Bytes>>>,
+
+    /// Named values recorded with `snapshotValue` by the current test and its `setUp`.
+    pub value_snapshots: BTreeMap<String, Bytes>,
+
     /// Additional diagnostic for reverts
     pub fork_revert_diagnostic: Option<RevertDiagnostic>,
 
@@ -148,6 +182,10 @@ pub struct Cheatcodes {
     /// merged into the previous vector.
     pub recorded_account_diffs_stack: Option<Vec<Vec<AccountAccess>>>,
 
+    /// Mapping slots written during a `startStateDiffRecording` session, used to decode the
+    /// mapping entries of the state diff.
+    pub state_diff_mapping_slots: Option<HashMap<Address, MappingSlots>>,
+
     /// Recorded logs
     pub recorded_logs: Option<Vec<crate::Vm::Log>>,
 
@@ -155,17 +193,42 @@ pub struct Cheatcodes {
     // **Note**: inner must a BTreeMap because of special `Ord` impl for `MockCallDataContext`
     pub mocked_calls: HashMap<Address, BTreeMap<MockCallDataContext, MockCallReturnData>>,
 
+    /// Mocked functions: calls to an address with matching calldata are redirected to a target
+    // **Note**: inner is a BTreeMap so that longer (tighter) calldata matches are tried first
+    pub mocked_functions: HashMap<Address, BTreeMap<Bytes, Address>>,
+
+    /// Mocked precompile outputs, by precompile and exact input
+    pub mocked_precompiles: HashMap<Address, HashMap<Bytes, Bytes>>,
+    /// Precompiles that behave like accounts without code, apart from their mocked outputs
+    pub disabled_precompiles: HashSet<Address>,
+
     /// Expected calls
     pub expected_calls: ExpectedCallTracker,
     /// Expected emits
     pub expected_emits: VecDeque<ExpectedEmit>,
+    /// Whether the expected emits may be matched in any order
+    pub expected_emits_unordered: bool,
+
+    /// Cheatcode configurations saved by `saveCheatState`, indexed by ID
+    pub cheat_states: Vec<CheatState>,
 
     /// Map of context depths to memory offset ranges that may be written to within the call depth.
     pub allowed_mem_writes: HashMap<u64, Vec<Range<u64>>>,
+    /// Context depths whose allowed memory writes also apply to delegatecalled contexts.
+    pub deep_allowed_mem_writes: HashSet<u64>,
+    /// Map of delegatecalled context depths to their allowed memory writes, and whether they were
+    /// deep, before the writes of their caller were propagated into them.
+    pub propagated_mem_writes: HashMap<u64, (Option<Vec<Range<u64>>>, bool)>,
 
     /// Current broadcasting information
     pub broadcast: Option<Broadcast>,
 
+    /// The access list attached to broadcasted transactions, set by `vm.applyAccessList`
+    pub access_list: Option<AccessList>,
+
+    /// The type of broadcasted transactions, set by `vm.setTxType`
+    pub tx_type: Option<u8>,
+
     /// Used to correct the nonce of --sender after the initiating call. For more, check
     /// `docs/scripting`.
     pub corrected_nonce: bool,
@@ -221,6 +284,22 @@ impl Cheatcodes {
         Self { config, fs_commit: true, labels, ..Default::default() }
     }
 
+    /// Returns the RNG used by the random cheatcodes.
+    ///
+    /// It is seeded on first use in every test run with the configured fuzz seed and the calldata
+    /// of the test call, so replaying the calldata of a failing fuzz run reproduces its values.
+    pub fn rng(&mut self, calldata: &[u8]) -> &mut StdRng {
+        let seed = self.rng_seed(calldata);
+        self.context.rng.get_or_insert_with(|| StdRng::from_seed(seed.0))
+    }
+
+    /// Returns the seed of the RNG used by the random cheatcodes, see [`Self::rng`].
+    pub fn rng_seed(&self, calldata: &[u8]) -> B256 {
+        let mut seed = self.config.seed.unwrap_or_default().to_be_bytes_vec();
+        seed.extend_from_slice(calldata);
+        keccak256(seed)
+    }
+
     fn apply_cheatcode<DB: DatabaseExt>(
         &mut self,
         data: &mut EVMData<'_, DB>,
@@ -690,6 +769,9 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
         if let Some(mapping_slots) = &mut self.mapping_slots {
             mapping::step(mapping_slots, interpreter);
         }
+        if let Some(mapping_slots) = &mut self.state_diff_mapping_slots {
+            mapping::step(mapping_slots, interpreter);
+        }
     }
 
     fn log(&mut self, _: &mut EVMData<'_, DB>, address: &Address, topics: &[B256], data: &Bytes) {
@@ -746,38 +828,83 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                     expected.min_gas.map_or(true, |min_gas| min_gas <= call.gas_limit)
                 {
                     *actual_count += 1;
+
+                    // Track the highest gas supplied, to later check it against the maximum gas
+                    if expected.max_gas.is_some() {
+                        expected.highest_gas = expected.highest_gas.max(Some(call.gas_limit));
+                    }
                 }
             }
         }
 
+        // Handle mocked and disabled precompiles
+        if let Some(output) =
+            self.mocked_precompiles.get(&call.contract).and_then(|mocks| mocks.get(&call.input))
+        {
+            return (InstructionResult::Return, gas, output.clone());
+        }
+        if self.disabled_precompiles.contains(&call.contract) {
+            return (InstructionResult::Stop, gas, Bytes::new());
+        }
+
         // Handle mocked calls
         if let Some(mocks) = self.mocked_calls.get(&call.contract) {
             let ctx = MockCallDataContext {
                 calldata: call.input.clone(),
                 value: Some(call.transfer.value),
+                mask: None,
             };
             if let Some(return_data) = mocks.get(&ctx).or_else(|| {
                 mocks
                     .iter()
-                    .find(|(mock, _)| {
-                        call.input.get(..mock.calldata.len()) == Some(&mock.calldata[..]) &&
-                            mock.value.map_or(true, |value| value == call.transfer.value)
-                    })
+                    .find(|(mock, _)| mock.matches(&call.input, call.transfer.value))
                     .map(|(_, v)| v)
             }) {
                 return (return_data.ret_type, gas, return_data.data.clone());
             }
         }
 
+        // Handle mocked functions, replacing the call target with the mock target
+        if matches!(call.context.scheme, CallScheme::Call | CallScheme::StaticCall) {
+            if let Some(target) = self.mocked_functions.get(&call.contract).and_then(|targets| {
+                targets.get(&call.input).or_else(|| {
+                    targets
+                        .iter()
+                        .rev()
+                        .find(|(calldata, _)| call.input.starts_with(calldata))
+                        .map(|(_, target)| target)
+                })
+            }) {
+                call.contract = *target;
+                call.context.code_address = *target;
+                call.context.address = *target;
+                // the value of the call is sent to the mock target as well
+                call.transfer.target = *target;
+            }
+        }
+
+        // Propagate the allowed memory writes into the delegatecalled context, if requested
+        if call.context.scheme == CallScheme::DelegateCall {
+            let depth = data.journaled_state.depth();
+            if self.deep_allowed_mem_writes.contains(&depth) {
+                if let Some(ranges) = self.allowed_mem_writes.get(&depth).cloned() {
+                    // keep the writes already allowed in the context, e.g. by
+                    // `expectSafeMemoryCall`, and restore them once it ends
+                    let previous = self.allowed_mem_writes.get(&(depth + 1)).cloned();
+                    let was_deep = !self.deep_allowed_mem_writes.insert(depth + 1);
+                    self.propagated_mem_writes.insert(depth + 1, (previous, was_deep));
+                    self.allowed_mem_writes.entry(depth + 1).or_default().extend(ranges);
+                }
+            }
+        }
+
         // Apply our prank
         if let Some(prank) = &self.prank {
-            if data.journaled_state.depth() >= prank.depth &&
-                call.context.caller == prank.prank_caller
-            {
+            if prank.applies_to(data.journaled_state.depth(), call.context.caller) {
                 let mut prank_applied = false;
 
                 // At the target depth we set `msg.sender`
-                if data.journaled_state.depth() == prank.depth {
+                if prank.is_target_depth(data.journaled_state.depth()) {
                     call.context.caller = prank.new_caller;
                     call.transfer.source = prank.new_caller;
                     prank_applied = true;
@@ -809,8 +936,11 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
             {
                 // At the target depth we set `msg.sender` & tx.origin.
                 // We are simulating the caller as being an EOA, so *both* must be set to the
-                // broadcast.origin.
-                data.env.tx.caller = broadcast.new_origin;
+                // broadcast.origin. User operations are sent by a bundler instead, so only
+                // `msg.sender` is their smart account.
+                if broadcast.user_op_owner.is_none() {
+                    data.env.tx.caller = broadcast.new_origin;
+                }
 
                 call.context.caller = broadcast.new_origin;
                 call.transfer.source = broadcast.new_origin;
@@ -825,32 +955,52 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                         return (InstructionResult::Revert, gas, Error::encode(err));
                     }
 
-                    let is_fixed_gas_limit = check_if_fixed_gas_limit(data, call.gas_limit);
-
-                    let account =
-                        data.journaled_state.state().get_mut(&broadcast.new_origin).unwrap();
-
-                    self.broadcastable_transactions.push_back(BroadcastableTransaction {
-                        rpc: data.db.active_fork_url(),
-                        transaction: TransactionRequest {
-                            from: Some(broadcast.new_origin),
-                            to: Some(call.contract),
-                            value: Some(call.transfer.value),
-                            data: Some(call.input.clone()),
-                            nonce: Some(U64::from(account.info.nonce)),
-                            gas: if is_fixed_gas_limit {
-                                Some(U256::from(call.gas_limit))
-                            } else {
-                                None
+                    if let Some(owner) = broadcast.user_op_owner {
+                        // The nonce of the account is the EntryPoint's, fetched when it's sent
+                        self.broadcastable_transactions.push_back(BroadcastableTransaction {
+                            rpc: data.db.active_fork_url(),
+                            transaction: TransactionRequest {
+                                from: Some(broadcast.new_origin),
+                                to: Some(call.contract),
+                                value: Some(call.transfer.value),
+                                data: Some(call.input.clone()),
+                                ..Default::default()
                             },
-                            ..Default::default()
-                        },
-                    });
-                    debug!(target: "cheatcodes", tx=?self.broadcastable_transactions.back().unwrap(), "broadcastable call");
-
-                    let prev = account.info.nonce;
-                    account.info.nonce += 1;
-                    debug!(target: "cheatcodes", address=%broadcast.new_origin, nonce=prev+1, prev, "incremented nonce");
+                            tx_type: None,
+                            user_op_owner: Some(owner),
+                        });
+                        debug!(target: "cheatcodes", tx=?self.broadcastable_transactions.back().unwrap(), "broadcastable user operation");
+                    } else {
+                        let is_fixed_gas_limit = check_if_fixed_gas_limit(data, call.gas_limit);
+
+                        let account =
+                            data.journaled_state.state().get_mut(&broadcast.new_origin).unwrap();
+
+                        self.broadcastable_transactions.push_back(BroadcastableTransaction {
+                            rpc: data.db.active_fork_url(),
+                            transaction: TransactionRequest {
+                                from: Some(broadcast.new_origin),
+                                to: Some(call.contract),
+                                value: Some(call.transfer.value),
+                                data: Some(call.input.clone()),
+                                nonce: Some(U64::from(account.info.nonce)),
+                                gas: if is_fixed_gas_limit {
+                                    Some(U256::from(call.gas_limit))
+                                } else {
+                                    None
+                                },
+                                access_list: self.access_list.clone(),
+                                ..Default::default()
+                            },
+                            tx_type: self.tx_type,
+                            user_op_owner: None,
+                        });
+                        debug!(target: "cheatcodes", tx=?self.broadcastable_transactions.back().unwrap(), "broadcastable call");
+
+                        let prev = account.info.nonce;
+                        account.info.nonce += 1;
+                        debug!(target: "cheatcodes", address=%broadcast.new_origin, nonce=prev+1, prev, "incremented nonce");
+                    }
                 } else if broadcast.single_call {
                     let msg = "`staticcall`s are not allowed after `broadcast`; use `startBroadcast` instead";
                     return (InstructionResult::Revert, Gas::new(0), Error::encode(msg));
@@ -934,6 +1084,21 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                 }
             }
 
+            // Clean up allowed memory writes propagated into a delegatecalled context
+            let depth = data.journaled_state.depth();
+            if call.context.scheme == CallScheme::DelegateCall {
+                if let Some((previous, was_deep)) = self.propagated_mem_writes.remove(&(depth + 1))
+                {
+                    match previous {
+                        Some(ranges) => self.allowed_mem_writes.insert(depth + 1, ranges),
+                        None => self.allowed_mem_writes.remove(&(depth + 1)),
+                    };
+                    if !was_deep {
+                        self.deep_allowed_mem_writes.remove(&(depth + 1));
+                    }
+                }
+            }
+
             // Clean up broadcast
             if let Some(broadcast) = &self.broadcast {
                 if data.journaled_state.depth() == broadcast.depth {
@@ -947,8 +1112,26 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
             }
         }
 
+        // Handle assumed non-reverting calls
+        if let Some(assume_no_revert) = &mut self.assume_no_revert {
+            if !cheatcode_call && data.journaled_state.depth() >= assume_no_revert.depth {
+                assume_no_revert.revert_origin.record(call.contract, status, &retdata);
+            }
+            if !cheatcode_call && data.journaled_state.depth() == assume_no_revert.depth {
+                let assume_no_revert = std::mem::take(&mut self.assume_no_revert).unwrap();
+                // Discard the fuzz run if the call reverted as anticipated; any other revert is
+                // handled as usual
+                if assume_no_revert.matches(status, &retdata) {
+                    return (InstructionResult::Revert, remaining_gas, Error::encode(MAGIC_ASSUME));
+                }
+            }
+        }
+
         // Handle expected reverts
-        if let Some(expected_revert) = &self.expected_revert {
+        if let Some(expected_revert) = &mut self.expected_revert {
+            if !cheatcode_call {
+                expected_revert.revert_origin.record(call.contract, status, &retdata);
+            }
             if data.journaled_state.depth() <= expected_revert.depth {
                 let needs_processing: bool = match expected_revert.kind {
                     ExpectedRevertKind::Default => !cheatcode_call,
@@ -960,18 +1143,25 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                 };
 
                 if needs_processing {
-                    let expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
+                    let mut expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
                     return match expect::handle_expect_revert(
                         false,
-                        expected_revert.reason.as_deref(),
+                        &expected_revert,
                         status,
                         retdata,
                     ) {
                         Err(error) => {
+                            let error = expected_revert.count_error(error);
                             trace!(expected=?expected_revert, ?error, ?status, "Expected revert mismatch");
                             (InstructionResult::Revert, remaining_gas, error.abi_encode().into())
                         }
-                        Ok((_, retdata)) => (InstructionResult::Return, remaining_gas, retdata),
+                        Ok((_, retdata)) => {
+                            // Keep expecting reverts until the expected count is reached
+                            if expected_revert.record_match() {
+                                self.expected_revert = Some(expected_revert);
+                            }
+                            (InstructionResult::Return, remaining_gas, retdata)
+                        }
                     };
                 }
 
@@ -1052,6 +1242,8 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
             // Ignore staticcalls
             !call.is_static;
         if should_check_emits {
+            self.expected_emits_unordered = false;
+
             // Not all emits were matched.
             if self.expected_emits.iter().any(|expected| !expected.found) {
                 return (
@@ -1108,7 +1300,15 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                 // Loop over each address, and for each address, loop over each calldata it expects.
                 for (calldata, (expected, actual_count)) in calldatas {
                     // Grab the values we expect to see
-                    let ExpectedCallData { gas, min_gas, value, count, call_type } = expected;
+                    let ExpectedCallData {
+                        gas,
+                        min_gas,
+                        max_gas,
+                        highest_gas,
+                        value,
+                        count,
+                        call_type,
+                    } = expected;
 
                     let failed = match call_type {
                         // If the cheatcode was called with a `count` argument,
@@ -1127,6 +1327,7 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                             value.as_ref().map(|v| format!("value {v}")),
                             gas.map(|g| format!("gas {g}")),
                             min_gas.map(|g| format!("minimum gas {g}")),
+                            max_gas.map(|g| format!("maximum gas {g}")),
                         ]
                         .into_iter()
                         .flatten()
@@ -1146,12 +1347,25 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                         );
                         return (InstructionResult::Revert, remaining_gas, Error::encode(msg));
                     }
+
+                    // Check that no matching call was supplied more than the maximum gas
+                    if let (Some(max_gas), Some(highest_gas)) = (max_gas, highest_gas) {
+                        if highest_gas > max_gas {
+                            let msg = format!(
+                                "expected call to {address} with data {} to be supplied \
+                                 at most {max_gas} gas, but was supplied {highest_gas} gas",
+                                hex::encode_prefixed(calldata),
+                            );
+                            return (InstructionResult::Revert, remaining_gas, Error::encode(msg));
+                        }
+                    }
                 }
             }
 
             // Check if we have any leftover expected emits
             // First, if any emits were found at the root call, then we its ok and we remove them.
             self.expected_emits.retain(|expected| !expected.found);
+            self.expected_emits_unordered = false;
             // If not empty, we got mismatched emits
             if !self.expected_emits.is_empty() {
                 let msg = if status.is_ok() {
@@ -1177,9 +1391,9 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
 
         // Apply our prank
         if let Some(prank) = &self.prank {
-            if data.journaled_state.depth() >= prank.depth && call.caller == prank.prank_caller {
+            if prank.applies_to(data.journaled_state.depth(), call.caller) {
                 // At the target depth we set `msg.sender`
-                if data.journaled_state.depth() == prank.depth {
+                if prank.is_target_depth(data.journaled_state.depth()) {
                     call.caller = prank.new_caller;
                 }
 
@@ -1195,6 +1409,11 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
             if data.journaled_state.depth() >= broadcast.depth &&
                 call.caller == broadcast.original_caller
             {
+                if broadcast.user_op_owner.is_some() {
+                    let msg = "contract creations can't be sent as user operations";
+                    return (InstructionResult::Revert, None, gas, Error::encode(msg));
+                }
+
                 if let Err(err) = data.journaled_state.load_account(broadcast.new_origin, data.db) {
                     return (InstructionResult::Revert, None, gas, Error::encode(err));
                 }
@@ -1204,6 +1423,7 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                 if data.journaled_state.depth() == broadcast.depth {
                     let (bytecode, to, nonce) = match process_create(
                         broadcast.new_origin,
+                        self.config.create2_deployer,
                         call.init_code.clone(),
                         data,
                         call,
@@ -1229,8 +1449,11 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
                             } else {
                                 None
                             },
+                            access_list: self.access_list.clone(),
                             ..Default::default()
                         },
+                        tx_type: self.tx_type,
+                        user_op_owner: None,
                     });
                     let kind = match call.scheme {
                         CreateScheme::Create => "create",
@@ -1307,22 +1530,43 @@ impl<DB: DatabaseExt> Inspector<DB> for Cheatcodes {
             }
         }
 
+        // Handle assumed non-reverting creations
+        if let Some(assume_no_revert) = &mut self.assume_no_revert {
+            if data.journaled_state.depth() >= assume_no_revert.depth {
+                if let Some(address) = address {
+                    assume_no_revert.revert_origin.record(address, status, &retdata);
+                }
+            }
+            if data.journaled_state.depth() == assume_no_revert.depth {
+                let assume_no_revert = std::mem::take(&mut self.assume_no_revert).unwrap();
+                // Discard the fuzz run if the creation reverted as anticipated; any other revert
+                // is handled as usual
+                if assume_no_revert.matches(status, &retdata) {
+                    return (
+                        InstructionResult::Revert,
+                        None,
+                        remaining_gas,
+                        Error::encode(MAGIC_ASSUME),
+                    );
+                }
+            }
+        }
+
         // Handle expected reverts
         if let Some(expected_revert) = &self.expected_revert {
             if data.journaled_state.depth() <= expected_revert.depth &&
                 matches!(expected_revert.kind, ExpectedRevertKind::Default)
             {
-                let expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
-                return match expect::handle_expect_revert(
-                    true,
-                    expected_revert.reason.as_deref(),
-                    status,
-                    retdata,
-                ) {
+                let mut expected_revert = std::mem::take(&mut self.expected_revert).unwrap();
+                return match expect::handle_expect_revert(true, &expected_revert, status, retdata) {
                     Ok((address, retdata)) => {
+                        if expected_revert.record_match() {
+                            self.expected_revert = Some(expected_revert);
+                        }
                         (InstructionResult::Return, address, remaining_gas, retdata)
                     }
                     Err(err) => {
+                        let err = expected_revert.count_error(err);
                         (InstructionResult::Revert, None, remaining_gas, err.abi_encode().into())
                     }
                 };
@@ -1418,6 +1662,7 @@ fn mstore_revert_string(interpreter: &mut Interpreter<'_>, bytes: &[u8]) {
 
 fn process_create<DB: DatabaseExt>(
     broadcast_sender: Address,
+    create2_deployer: Address,
     bytecode: Bytes,
     data: &mut EVMData<'_, DB>,
     call: &mut CreateInputs,
@@ -1429,17 +1674,18 @@ fn process_create<DB: DatabaseExt>(
         }
         CreateScheme::Create2 { salt } => {
             // Sanity checks for our CREATE2 deployer
-            let info =
-                &data.journaled_state.load_account(DEFAULT_CREATE2_DEPLOYER, data.db)?.0.info;
+            let info = &data.journaled_state.load_account(create2_deployer, data.db)?.0.info;
             match &info.code {
-                Some(code) if code.is_empty() => return Err(DatabaseError::MissingCreate2Deployer),
+                Some(code) if code.is_empty() => {
+                    return Err(DatabaseError::MissingCreate2Deployer(create2_deployer))
+                }
                 None if data.db.code_by_hash(info.code_hash)?.is_empty() => {
-                    return Err(DatabaseError::MissingCreate2Deployer)
+                    return Err(DatabaseError::MissingCreate2Deployer(create2_deployer))
                 }
                 _ => {}
             }
 
-            call.caller = DEFAULT_CREATE2_DEPLOYER;
+            call.caller = create2_deployer;
 
             // We have to increment the nonce of the user address, since this create2 will be done
             // by the create2_deployer
@@ -1450,7 +1696,7 @@ fn process_create<DB: DatabaseExt>(
 
             // Proxy deployer requires the data to be `salt ++ init_code`
             let calldata = [&salt.to_be_bytes::<32>()[..], &bytecode[..]].concat();
-            Ok((calldata.into(), Some(DEFAULT_CREATE2_DEPLOYER), prev))
+            Ok((calldata.into(), Some(create2_deployer), prev))
         }
     }
 }
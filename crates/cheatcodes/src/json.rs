@@ -12,10 +12,7 @@ use std::{borrow::Cow, collections::BTreeMap, fmt::Write};
 impl Cheatcode for keyExistsCall {
     fn apply(&self, _state: &mut Cheatcodes) -> Result {
         let Self { json, key } = self;
-        let json = parse_json_str(json)?;
-        let values = select(&json, key)?;
-        let exists = !values.is_empty();
-        Ok(exists.abi_encode())
+        key_exists(&parse_json_str(json)?, key)
     }
 }
 
@@ -134,16 +131,7 @@ impl Cheatcode for parseJsonBytes32ArrayCall {
 impl Cheatcode for parseJsonKeysCall {
     fn apply(&self, _state: &mut Cheatcodes) -> Result {
         let Self { json, key } = self;
-        let json = parse_json_str(json)?;
-        let values = select(&json, key)?;
-        let [value] = values[..] else {
-            bail!("key {key:?} must return exactly one JSON object");
-        };
-        let Value::Object(object) = value else {
-            bail!("JSON value at {key:?} is not an object");
-        };
-        let keys = object.keys().collect::<Vec<_>>();
-        Ok(keys.abi_encode())
+        parse_keys(&parse_json_str(json)?, key)
     }
 }
 
@@ -253,6 +241,30 @@ impl Cheatcode for serializeBytes_1Call {
     }
 }
 
+impl Cheatcode for mergeJsonCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { json, other } = self;
+        let mut value = parse_json_str(json)?;
+        merge_json(&mut value, parse_json_str(other)?);
+        Ok(serde_json::to_string(&value)?.abi_encode())
+    }
+}
+
+impl Cheatcode for patchJsonCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { json, patch } = self;
+        let mut value = parse_json_str(json)?;
+        let Value::Array(operations) = parse_json_str(patch)? else {
+            bail!("JSON patch must be an array of operations");
+        };
+        for (i, operation) in operations.iter().enumerate() {
+            apply_json_patch(&mut value, operation)
+                .map_err(|e| fmt_err!("failed applying JSON patch operation {i}: {e}"))?;
+        }
+        Ok(serde_json::to_string(&value)?.abi_encode())
+    }
+}
+
 impl Cheatcode for writeJson_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { json, path } = self;
@@ -281,15 +293,33 @@ impl Cheatcode for writeJson_1Call {
 }
 
 fn parse_json(json: &str, path: &str) -> Result {
-    let value = parse_json_str(json)?;
-    let selected = select(&value, path)?;
+    parse_value(&parse_json_str(json)?, path)
+}
+
+fn parse_json_coerce(json: &str, path: &str, ty: &DynSolType) -> Result {
+    parse_value_coerce(&parse_json_str(json)?, path, ty)
+}
+
+/// Returns whether `key` exists in a JSON value, ABI-encoded.
+///
+/// This and the other functions on JSON values are shared with the TOML and YAML cheatcodes,
+/// which convert their documents to JSON.
+pub(super) fn key_exists(value: &Value, key: &str) -> Result {
+    let values = select(value, key)?;
+    let exists = !values.is_empty();
+    Ok(exists.abi_encode())
+}
+
+/// ABI-encodes the JSON values at `path`.
+pub(super) fn parse_value(value: &Value, path: &str) -> Result {
+    let selected = select(value, path)?;
     let sol = json_to_sol(&selected)?;
     Ok(encode(sol))
 }
 
-fn parse_json_coerce(json: &str, path: &str, ty: &DynSolType) -> Result {
-    let value = parse_json_str(json)?;
-    let values = select(&value, path)?;
+/// Coerces the JSON value or array at `path` to the given type, ABI-encoded.
+pub(super) fn parse_value_coerce(value: &Value, path: &str, ty: &DynSolType) -> Result {
+    let values = select(value, path)?;
     ensure!(!values.is_empty(), "no matching value found at {path:?}");
 
     ensure!(
@@ -311,6 +341,137 @@ fn parse_json_coerce(json: &str, path: &str, ty: &DynSolType) -> Result {
     }
 }
 
+/// Returns the keys of the JSON object at `key`, ABI-encoded.
+pub(super) fn parse_keys(value: &Value, key: &str) -> Result {
+    let values = select(value, key)?;
+    let [value] = values[..] else {
+        bail!("key {key:?} must return exactly one JSON object");
+    };
+    let Value::Object(object) = value else {
+        bail!("JSON value at {key:?} is not an object");
+    };
+    let keys = object.keys().collect::<Vec<_>>();
+    Ok(keys.abi_encode())
+}
+
+/// Merges `patch` into `target` following JSON Merge Patch (RFC 7396).
+fn merge_json(target: &mut Value, patch: Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch;
+        return
+    };
+    if !target.is_object() {
+        *target = Value::Object(Default::default());
+    }
+    let Value::Object(target) = target else { unreachable!() };
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(&key);
+        } else {
+            merge_json(target.entry(key).or_insert(Value::Null), value);
+        }
+    }
+}
+
+/// Applies a JSON Patch (RFC 6902) operation to `value`.
+fn apply_json_patch(value: &mut Value, operation: &Value) -> Result<()> {
+    let field =
+        |name: &str| operation.get(name).ok_or_else(|| fmt_err!("missing `{name}` in {operation}"));
+    let str_field = |name: &str| {
+        field(name)?.as_str().ok_or_else(|| fmt_err!("`{name}` must be a string in {operation}"))
+    };
+    let path = str_field("path")?;
+    match str_field("op")? {
+        "add" => json_pointer_add(value, path, field("value")?.clone()),
+        "remove" => json_pointer_remove(value, path).map(drop),
+        "replace" => {
+            let target =
+                value.pointer_mut(path).ok_or_else(|| fmt_err!("path {path:?} does not exist"))?;
+            *target = field("value")?.clone();
+            Ok(())
+        }
+        "move" => {
+            let from = str_field("from")?;
+            ensure!(
+                !path.starts_with(&format!("{from}/")),
+                "cannot move {from:?} into one of its children"
+            );
+            let moved = json_pointer_remove(value, from)?;
+            json_pointer_add(value, path, moved)
+        }
+        "copy" => {
+            let from = str_field("from")?;
+            let copied = value
+                .pointer(from)
+                .ok_or_else(|| fmt_err!("path {from:?} does not exist"))?
+                .clone();
+            json_pointer_add(value, path, copied)
+        }
+        "test" => {
+            let expected = field("value")?;
+            let actual = value.pointer(path);
+            ensure!(actual == Some(expected), "value at {path:?} is not {expected}");
+            Ok(())
+        }
+        op => bail!("unknown operation {op:?}"),
+    }
+}
+
+/// Adds a value at a JSON pointer, inserting it into arrays and replacing the values of objects.
+fn json_pointer_add(value: &mut Value, path: &str, new: Value) -> Result<()> {
+    let Some((parent, key)) = split_json_pointer(path)? else {
+        *value = new;
+        return Ok(())
+    };
+    match value.pointer_mut(parent) {
+        Some(Value::Object(object)) => {
+            object.insert(key, new);
+        }
+        Some(Value::Array(array)) => {
+            let index = if key == "-" { array.len() } else { json_pointer_index(&key)? };
+            ensure!(index <= array.len(), "index {index} out of bounds at {path:?}");
+            array.insert(index, new);
+        }
+        Some(_) => bail!("cannot add a value to a scalar at {path:?}"),
+        None => bail!("path {parent:?} does not exist"),
+    }
+    Ok(())
+}
+
+/// Removes and returns the value at a JSON pointer.
+fn json_pointer_remove(value: &mut Value, path: &str) -> Result<Value> {
+    let Some((parent, key)) = split_json_pointer(path)? else {
+        bail!("cannot remove the root of the document");
+    };
+    let removed = match value.pointer_mut(parent) {
+        Some(Value::Object(object)) => object.remove(&key),
+        Some(Value::Array(array)) => {
+            let index = json_pointer_index(&key)?;
+            (index < array.len()).then(|| array.remove(index))
+        }
+        _ => None,
+    };
+    removed.ok_or_else(|| fmt_err!("path {path:?} does not exist"))
+}
+
+/// Splits a JSON pointer into the pointer of its parent and its unescaped last token, or returns
+/// `None` for the root pointer.
+fn split_json_pointer(path: &str) -> Result<Option<(&str, String)>> {
+    if path.is_empty() {
+        return Ok(None)
+    }
+    let Some((parent, key)) = path.rsplit_once('/') else {
+        bail!("JSON pointer {path:?} must start with `/`");
+    };
+    Ok(Some((parent, key.replace("~1", "/").replace("~0", "~"))))
+}
+
+/// Parses an array index of a JSON pointer.
+fn json_pointer_index(key: &str) -> Result<usize> {
+    ensure!(key == "0" || !key.starts_with('0'), "invalid array index {key:?}");
+    key.parse().map_err(|_| fmt_err!("invalid array index {key:?}"))
+}
+
 fn parse_json_str(json: &str) -> Result<Value> {
     serde_json::from_str(json).map_err(|e| fmt_err!("failed parsing JSON: {e}"))
 }
@@ -345,7 +506,7 @@ fn encode(values: Vec<DynSolValue>) -> Vec<u8> {
 
 /// Canonicalize a json path key to always start from the root of the document.
 /// Read more about json path syntax: <https://goessner.net/articles/JsonPath/>
-fn canonicalize_json_path(path: &str) -> Cow<'_, str> {
+pub(super) fn canonicalize_json_path(path: &str) -> Cow<'_, str> {
     if !path.starts_with('$') {
         format!("${path}").into()
     } else {
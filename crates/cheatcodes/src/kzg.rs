@@ -0,0 +1,128 @@
+//! Implementations of the EIP-4844 KZG commitment [`Utilities`](crate::Group::Utilities)
+//! cheatcodes.
+
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_primitives::B256;
+use alloy_sol_types::SolValue;
+use c_kzg::{Blob, Bytes32, Bytes48, KzgCommitment, KzgProof, KzgSettings};
+use revm::primitives::kzg::EnvKzgSettings;
+use sha2::{Digest, Sha256};
+
+/// The version byte of KZG commitment versioned hashes.
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+impl Cheatcode for blobToKzgCommitmentCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { blob } = self;
+        let commitment =
+            KzgCommitment::blob_to_kzg_commitment(&to_blob(blob)?, settings()).map_err(kzg_err)?;
+        Ok(commitment.to_bytes().to_vec().abi_encode())
+    }
+}
+
+impl Cheatcode for computeKzgProofCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { blob, z } = self;
+        let (proof, y) = KzgProof::compute_kzg_proof(&to_blob(blob)?, &to_bytes32(z)?, settings())
+            .map_err(kzg_err)?;
+        Ok((proof.to_bytes().to_vec(), B256::from_slice(&y[..])).abi_encode_params())
+    }
+}
+
+impl Cheatcode for computeBlobKzgProofCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { blob, commitment } = self;
+        let proof =
+            KzgProof::compute_blob_kzg_proof(&to_blob(blob)?, &to_bytes48(commitment)?, settings())
+                .map_err(kzg_err)?;
+        Ok(proof.to_bytes().to_vec().abi_encode())
+    }
+}
+
+impl Cheatcode for verifyKzgProofCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { commitment, z, y, proof } = self;
+        let valid = KzgProof::verify_kzg_proof(
+            &to_bytes48(commitment)?,
+            &to_bytes32(z)?,
+            &to_bytes32(y)?,
+            &to_bytes48(proof)?,
+            settings(),
+        )
+        .map_err(kzg_err)?;
+        Ok(valid.abi_encode())
+    }
+}
+
+impl Cheatcode for verifyBlobKzgProofCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { blob, commitment, proof } = self;
+        let valid = KzgProof::verify_blob_kzg_proof(
+            &to_blob(blob)?,
+            &to_bytes48(commitment)?,
+            &to_bytes48(proof)?,
+            settings(),
+        )
+        .map_err(kzg_err)?;
+        Ok(valid.abi_encode())
+    }
+}
+
+impl Cheatcode for kzgToVersionedHashCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { commitment } = self;
+        Ok(versioned_hash(&to_bytes48(commitment)?).abi_encode())
+    }
+}
+
+/// Returns the mainnet trusted setup, which is also used by the point evaluation precompile.
+fn settings() -> &'static KzgSettings {
+    EnvKzgSettings::Default.get()
+}
+
+fn versioned_hash(commitment: &[u8]) -> B256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    B256::from(hash)
+}
+
+fn to_blob(blob: &[u8]) -> Result<Blob> {
+    Blob::from_bytes(blob).map_err(|_| {
+        fmt_err!(
+            "invalid blob length: expected {} bytes, got {}",
+            c_kzg::BYTES_PER_BLOB,
+            blob.len()
+        )
+    })
+}
+
+fn to_bytes48(bytes: &[u8]) -> Result<Bytes48> {
+    Bytes48::from_bytes(bytes)
+        .map_err(|_| fmt_err!("invalid length: expected 48 bytes, got {}", bytes.len()))
+}
+
+fn to_bytes32(bytes: &B256) -> Result<Bytes32> {
+    Bytes32::from_bytes(bytes.as_slice()).map_err(kzg_err)
+}
+
+fn kzg_err(err: c_kzg::Error) -> crate::Error {
+    fmt_err!("KZG error: {err:?}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commits_to_zero_blob() {
+        let blob = Blob::from_bytes(&vec![0; c_kzg::BYTES_PER_BLOB]).unwrap();
+        let commitment = KzgCommitment::blob_to_kzg_commitment(&blob, settings()).unwrap();
+        let mut infinity = [0; 48];
+        infinity[0] = 0xc0;
+        assert_eq!(commitment.to_bytes().to_vec(), infinity);
+        assert_eq!(
+            versioned_hash(&infinity).to_string(),
+            "0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014"
+        );
+    }
+}
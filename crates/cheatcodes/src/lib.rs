@@ -27,15 +27,24 @@ mod inspector;
 pub use inspector::{BroadcastableTransaction, BroadcastableTransactions, Cheatcodes, Context};
 
 mod base64;
+mod compress;
 mod env;
 mod evm;
 mod fs;
+mod http;
 mod json;
+mod kzg;
+mod merkle;
+mod prompt;
 mod script;
+mod secrets;
 mod string;
 mod test;
+mod toml;
 mod utils;
+mod yaml;
 
+pub use evm::layout::{read_artifact_layouts, ArtifactLayout, LayoutDecoder, SlotVariable};
 pub use test::expect::ExpectedCallTracker;
 
 /// Cheatcode implementation.
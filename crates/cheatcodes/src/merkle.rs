@@ -0,0 +1,305 @@
+//! Implementations of the Merkle tree [`Utilities`](crate::Group::Utilities) cheatcodes.
+
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_primitives::{keccak256, B256, U256};
+use alloy_sol_types::SolValue;
+use std::collections::VecDeque;
+
+impl Cheatcode for merkleRoot_0Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves } = self;
+        Ok(MerkleTree::new(leaves, MerkleTreeKind::Sorted)?.root().abi_encode())
+    }
+}
+
+impl Cheatcode for merkleRoot_1Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves, kind } = self;
+        Ok(MerkleTree::new(leaves, *kind)?.root().abi_encode())
+    }
+}
+
+impl Cheatcode for merkleProof_0Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves, index } = self;
+        merkle_proof(leaves, index, MerkleTreeKind::Sorted)
+    }
+}
+
+impl Cheatcode for merkleProof_1Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves, index, kind } = self;
+        merkle_proof(leaves, index, *kind)
+    }
+}
+
+impl Cheatcode for merkleMultiProof_0Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves, indices } = self;
+        merkle_multi_proof(leaves, indices, MerkleTreeKind::Sorted)
+    }
+}
+
+impl Cheatcode for merkleMultiProof_1Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { leaves, indices, kind } = self;
+        merkle_multi_proof(leaves, indices, *kind)
+    }
+}
+
+fn merkle_proof(leaves: &[B256], index: &U256, kind: MerkleTreeKind) -> Result {
+    let tree = MerkleTree::new(leaves, kind)?;
+    Ok(tree.proof(tree.leaf_index(index)?).abi_encode())
+}
+
+fn merkle_multi_proof(leaves: &[B256], indices: &[U256], kind: MerkleTreeKind) -> Result {
+    let tree = MerkleTree::new(leaves, kind)?;
+    let indices = indices.iter().map(|index| tree.leaf_index(index)).collect::<Result<Vec<_>>>()?;
+    Ok(tree.multi_proof(&indices)?.abi_encode_params())
+}
+
+/// A Merkle tree whose nodes are stored from the root down, so that parents come before their
+/// children and siblings are adjacent.
+///
+/// Pairs of nodes are hashed with [`hash_pair`].
+struct MerkleTree {
+    nodes: Vec<B256>,
+    /// The parent of every node. The root is its own parent.
+    parents: Vec<usize>,
+    /// The sibling of every node, `None` for the root and for nodes that are paired with zero.
+    siblings: Vec<Option<usize>>,
+    /// The node of every leaf, in the order the leaves were given.
+    leaves: Vec<usize>,
+}
+
+impl MerkleTree {
+    fn new(leaves: &[B256], kind: MerkleTreeKind) -> Result<Self> {
+        ensure!(!leaves.is_empty(), "cannot build a Merkle tree without leaves");
+        if matches!(kind, MerkleTreeKind::OpenZeppelin) {
+            Ok(Self::complete(leaves))
+        } else {
+            Ok(Self::sorted(leaves))
+        }
+    }
+
+    /// Builds the tree level by level, pairing a last odd node with zero.
+    fn sorted(leaves: &[B256]) -> Self {
+        let mut levels = vec![leaves.to_vec()];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next: Vec<B256> = level
+                .chunks(2)
+                .map(|pair| hash_pair(pair[0], pair.get(1).copied().unwrap_or_default()))
+                .collect();
+            levels.push(next);
+        }
+        levels.reverse();
+
+        let mut offsets = Vec::with_capacity(levels.len());
+        let mut len = 0;
+        for level in &levels {
+            offsets.push(len);
+            len += level.len();
+        }
+
+        let mut tree = Self::with_capacity(len);
+        for (depth, level) in levels.iter().enumerate() {
+            for (position, node) in level.iter().enumerate() {
+                tree.nodes.push(*node);
+                if depth == 0 {
+                    tree.parents.push(0);
+                    tree.siblings.push(None);
+                } else {
+                    tree.parents.push(offsets[depth - 1] + position / 2);
+                    let sibling = position ^ 1;
+                    tree.siblings.push((sibling < level.len()).then(|| offsets[depth] + sibling));
+                }
+            }
+        }
+        tree.leaves = (offsets[offsets.len() - 1]..len).collect();
+        tree
+    }
+
+    /// Builds a complete binary tree of the sorted leaves, like `@openzeppelin/merkle-tree`.
+    fn complete(leaves: &[B256]) -> Self {
+        let len = 2 * leaves.len() - 1;
+        let mut sorted = (0..leaves.len()).collect::<Vec<_>>();
+        sorted.sort_by_key(|&i| leaves[i]);
+
+        let mut tree = Self::with_capacity(len);
+        tree.nodes = vec![B256::ZERO; len];
+        tree.leaves = vec![0; leaves.len()];
+        for (position, &i) in sorted.iter().enumerate() {
+            tree.nodes[len - 1 - position] = leaves[i];
+            tree.leaves[i] = len - 1 - position;
+        }
+        for i in (0..len - leaves.len()).rev() {
+            tree.nodes[i] = hash_pair(tree.nodes[2 * i + 1], tree.nodes[2 * i + 2]);
+        }
+        tree.parents = (0..len).map(|i| i.saturating_sub(1) / 2).collect();
+        tree.siblings =
+            (0..len).map(|i| (i > 0).then(|| if i % 2 == 1 { i + 1 } else { i - 1 })).collect();
+        tree
+    }
+
+    fn with_capacity(len: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(len),
+            parents: Vec::with_capacity(len),
+            siblings: Vec::with_capacity(len),
+            leaves: Vec::new(),
+        }
+    }
+
+    fn root(&self) -> B256 {
+        self.nodes[0]
+    }
+
+    fn leaf_index(&self, index: &U256) -> Result<usize> {
+        usize::try_from(*index).ok().filter(|&i| i < self.leaves.len()).ok_or_else(|| {
+            fmt_err!("leaf index {index} out of bounds of {} leaves", self.leaves.len())
+        })
+    }
+
+    fn sibling(&self, node: usize) -> B256 {
+        self.siblings[node].map_or(B256::ZERO, |sibling| self.nodes[sibling])
+    }
+
+    /// Returns the proof of the leaf at `index`, ordered from the leaf up.
+    fn proof(&self, index: usize) -> Vec<B256> {
+        let mut proof = Vec::new();
+        let mut node = self.leaves[index];
+        while node != 0 {
+            proof.push(self.sibling(node));
+            node = self.parents[node];
+        }
+        proof
+    }
+
+    /// Returns the leaves, proof and proof flags of the multi-proof of the leaves at `indices`, as
+    /// expected by OpenZeppelin's `MerkleProof.multiProofVerify`.
+    ///
+    /// See `getMultiProof` in `@openzeppelin/merkle-tree`.
+    fn multi_proof(&self, indices: &[usize]) -> Result<(Vec<B256>, Vec<B256>, Vec<bool>)> {
+        let mut nodes = indices.iter().map(|&i| self.leaves[i]).collect::<Vec<_>>();
+        nodes.sort_unstable_by(|a, b| b.cmp(a));
+        ensure!(nodes.windows(2).all(|pair| pair[0] != pair[1]), "duplicate leaf indices");
+
+        let leaves = nodes.iter().map(|&node| self.nodes[node]).collect();
+        let mut queue = VecDeque::from(nodes);
+        let mut proof = Vec::new();
+        let mut flags = Vec::new();
+        while let Some(node) = queue.front().copied().filter(|&node| node != 0) {
+            queue.pop_front();
+            let sibling = self.siblings[node];
+            if sibling.is_some() && queue.front() == sibling.as_ref() {
+                flags.push(true);
+                queue.pop_front();
+            } else {
+                flags.push(false);
+                proof.push(self.sibling(node));
+            }
+            queue.push_back(self.parents[node]);
+        }
+        if indices.is_empty() {
+            proof.push(self.root());
+        }
+        Ok((leaves, proof, flags))
+    }
+}
+
+/// Hashes a pair of nodes in ascending order.
+fn hash_pair(a: B256, b: B256) -> B256 {
+    let (a, b) = if a <= b { (a, b) } else { (b, a) };
+    keccak256([a.as_slice(), b.as_slice()].concat())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(n: u64) -> Vec<B256> {
+        (1..=n).map(|i| B256::from(U256::from(i))).collect()
+    }
+
+    fn process_proof(leaf: B256, proof: &[B256]) -> B256 {
+        proof.iter().fold(leaf, |node, sibling| hash_pair(node, *sibling))
+    }
+
+    /// A port of OpenZeppelin's `MerkleProof.processMultiProof`.
+    fn process_multi_proof(leaves: &[B256], proof: &[B256], flags: &[bool]) -> B256 {
+        let mut hashes = Vec::new();
+        let (mut leaf_pos, mut hash_pos, mut proof_pos) = (0, 0, 0);
+        let mut next = |hashes: &Vec<B256>| {
+            if leaf_pos < leaves.len() {
+                leaf_pos += 1;
+                leaves[leaf_pos - 1]
+            } else {
+                hash_pos += 1;
+                hashes[hash_pos - 1]
+            }
+        };
+        for &flag in flags {
+            let a = next(&hashes);
+            let b = if flag {
+                next(&hashes)
+            } else {
+                proof_pos += 1;
+                proof[proof_pos - 1]
+            };
+            hashes.push(hash_pair(a, b));
+        }
+        match hashes.last() {
+            Some(root) => *root,
+            None if !leaves.is_empty() => leaves[0],
+            None => proof[0],
+        }
+    }
+
+    #[test]
+    fn sorted_tree() {
+        let l = leaves(3);
+        let tree = MerkleTree::new(&l, MerkleTreeKind::Sorted).unwrap();
+        let root = hash_pair(hash_pair(l[0], l[1]), hash_pair(l[2], B256::ZERO));
+        assert_eq!(tree.root(), root);
+        assert_eq!(tree.proof(2), vec![B256::ZERO, hash_pair(l[0], l[1])]);
+    }
+
+    #[test]
+    fn complete_tree() {
+        let l = leaves(3);
+        let shuffled = [l[2], l[0], l[1]];
+        let tree = MerkleTree::new(&shuffled, MerkleTreeKind::OpenZeppelin).unwrap();
+        assert_eq!(tree.root(), hash_pair(hash_pair(l[1], l[0]), l[2]));
+        assert_eq!(tree.proof(0), vec![hash_pair(l[0], l[1])]);
+    }
+
+    #[test]
+    fn single_leaf() {
+        for kind in [MerkleTreeKind::Sorted, MerkleTreeKind::OpenZeppelin] {
+            let tree = MerkleTree::new(&leaves(1), kind).unwrap();
+            assert_eq!(tree.root(), leaves(1)[0]);
+            assert!(tree.proof(0).is_empty());
+        }
+    }
+
+    #[test]
+    fn proofs_verify() {
+        for kind in [MerkleTreeKind::Sorted, MerkleTreeKind::OpenZeppelin] {
+            for n in 1..=9 {
+                let l = leaves(n);
+                let tree = MerkleTree::new(&l, kind).unwrap();
+                for (i, leaf) in l.iter().enumerate() {
+                    assert_eq!(process_proof(*leaf, &tree.proof(i)), tree.root());
+                }
+
+                let all = (0..l.len()).collect::<Vec<_>>();
+                for indices in
+                    [vec![], vec![0], all.clone(), all.iter().step_by(2).copied().collect()]
+                {
+                    let (proof_leaves, proof, flags) = tree.multi_proof(&indices).unwrap();
+                    assert_eq!(process_multi_proof(&proof_leaves, &proof, &flags), tree.root());
+                }
+            }
+        }
+    }
+}
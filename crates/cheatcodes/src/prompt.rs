@@ -0,0 +1,73 @@
+//! Implementations of the prompt [`Filesystem`](crate::Group::Filesystem) cheatcodes.
+
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_primitives::U256;
+use alloy_sol_types::SolValue;
+use dialoguer::{Confirm, Select};
+use foundry_common::fs;
+use foundry_config::fs_permissions::FsAccessKind;
+use serde_json::Value;
+
+/// The environment variable pointing to a JSON file of prompt answers, keyed by prompt label, which
+/// is used instead of prompting in the terminal, e.g. in CI.
+const PROMPT_ANSWERS_ENV: &str = "FOUNDRY_PROMPT_ANSWERS";
+
+impl Cheatcode for promptSelectCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { label, options } = self;
+        ensure!(!options.is_empty(), "prompt {label:?} has no options");
+
+        let index = match answer(state, label)? {
+            Some(Value::String(answer)) => {
+                options.iter().position(|option| *option == answer).ok_or_else(|| {
+                    fmt_err!("answer {answer:?} to prompt {label:?} is not one of its options")
+                })?
+            }
+            Some(Value::Number(answer)) => answer
+                .as_u64()
+                .and_then(|index| usize::try_from(index).ok())
+                .filter(|&index| index < options.len())
+                .ok_or_else(|| {
+                    fmt_err!("answer {answer} to prompt {label:?} is not a valid option index")
+                })?,
+            Some(answer) => {
+                bail!("answer {answer} to prompt {label:?} must be an option or its index")
+            }
+            None => Select::new()
+                .with_prompt(label)
+                .items(options.as_slice())
+                .default(0)
+                .interact()
+                .map_err(|e| fmt_err!("failed to prompt {label:?}: {e}"))?,
+        };
+        Ok(U256::from(index).abi_encode())
+    }
+}
+
+impl Cheatcode for promptConfirmCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { label } = self;
+        let confirmed = match answer(state, label)? {
+            Some(Value::Bool(answer)) => answer,
+            Some(answer) => bail!("answer {answer} to prompt {label:?} must be a boolean"),
+            None => Confirm::new()
+                .with_prompt(label)
+                .interact()
+                .map_err(|e| fmt_err!("failed to prompt {label:?}: {e}"))?,
+        };
+        Ok(confirmed.abi_encode())
+    }
+}
+
+/// Returns the answer to the prompt with the given `label` from the [`PROMPT_ANSWERS_ENV`] file, or
+/// `None` if the variable is not set and the user should be prompted instead.
+fn answer(state: &Cheatcodes, label: &str) -> Result<Option<Value>> {
+    let Ok(path) = std::env::var(PROMPT_ANSWERS_ENV) else { return Ok(None) };
+    let path = state.config.ensure_path_allowed(&path, FsAccessKind::Read)?;
+    let mut answers: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+    answers
+        .get_mut(label)
+        .map(Value::take)
+        .map(Some)
+        .ok_or_else(|| fmt_err!("no answer to prompt {label:?} in {}", path.display()))
+}
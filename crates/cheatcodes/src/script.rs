@@ -1,7 +1,8 @@
 //! Implementations of [`Scripting`](crate::Group::Scripting) cheatcodes.
 
-use crate::{Cheatcode, CheatsCtxt, DatabaseExt, Result, Vm::*};
+use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Result, Vm::*};
 use alloy_primitives::{Address, U256};
+use alloy_rpc_types::AccessList;
 use alloy_signer::Signer;
 use foundry_config::Config;
 
@@ -26,6 +27,18 @@ impl Cheatcode for broadcast_2Call {
     }
 }
 
+impl Cheatcode for broadcastUserOpCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { account } = self;
+        let owner = ccx.data.env.tx.caller;
+        broadcast(ccx, Some(account), true)?;
+        if let Some(broadcast) = &mut ccx.state.broadcast {
+            broadcast.user_op_owner = Some(owner);
+        }
+        Ok(Default::default())
+    }
+}
+
 impl Cheatcode for startBroadcast_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self {} = self;
@@ -58,6 +71,36 @@ impl Cheatcode for stopBroadcastCall {
     }
 }
 
+impl Cheatcode for applyAccessListCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { accessList } = self;
+        state.access_list = (!accessList.is_empty()).then(|| {
+            AccessList(
+                accessList
+                    .iter()
+                    .map(|item| alloy_rpc_types::AccessListItem {
+                        address: item.account,
+                        storage_keys: item.storageKeys.clone(),
+                    })
+                    .collect(),
+            )
+        });
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for setTxTypeCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { txType } = self;
+        ensure!(
+            *txType <= 2,
+            "transactions of type {txType} can't be broadcasted, expected 0, 1 or 2"
+        );
+        state.tx_type = Some(*txType);
+        Ok(Default::default())
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct Broadcast {
     /// Address of the transaction origin
@@ -70,6 +113,9 @@ pub struct Broadcast {
     pub depth: u64,
     /// Whether the prank stops by itself after the next call
     pub single_call: bool,
+    /// The owner signing the call as a user operation of the `new_origin` smart account, set by
+    /// `vm.broadcastUserOp`
+    pub user_op_owner: Option<Address>,
 }
 
 /// Sets up broadcasting from a script using `new_origin` as the sender.
@@ -92,6 +138,7 @@ fn broadcast<DB: DatabaseExt>(
         original_origin: ccx.data.env.tx.caller,
         depth: ccx.data.journaled_state.depth(),
         single_call,
+        user_op_owner: None,
     };
     debug!(target: "cheatcodes", ?broadcast, "started");
     ccx.state.broadcast = Some(broadcast);
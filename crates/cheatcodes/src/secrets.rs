@@ -0,0 +1,71 @@
+//! Implementation of the secret [`Environment`](crate::Group::Environment) cheatcodes.
+
+use crate::{Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_sol_types::SolValue;
+use foundry_config::SecretsBackend;
+use foundry_evm_core::secrets::register_secret;
+use std::{
+    env,
+    path::{Component, Path},
+};
+
+/// The OS keychain service secrets are stored under.
+const KEYCHAIN_SERVICE: &str = "foundry";
+
+/// The environment variable holding the password of the keystore secrets.
+const SECRETS_PASSWORD_ENV: &str = "FOUNDRY_SECRETS_PASSWORD";
+
+impl Cheatcode for getSecretCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { key } = self;
+        ensure!(!key.is_empty(), "secret key can't be empty");
+        let secret = match &state.config.secrets {
+            SecretsBackend::Keychain => keychain_secret(key)?,
+            SecretsBackend::Keystore { dir } => {
+                let password = env::var(SECRETS_PASSWORD_ENV).map_err(|_| {
+                    fmt_err!("`{SECRETS_PASSWORD_ENV}` must be set to decrypt keystore secrets")
+                })?;
+                keystore_secret(dir, key, &password)?
+            }
+        };
+        // redact the secret from traces, logs and broadcast files wherever it ends up
+        register_secret(&secret);
+        Ok(secret.abi_encode())
+    }
+}
+
+fn keychain_secret(key: &str) -> Result<String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, key)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| fmt_err!("failed to get secret {key:?} from the OS keychain: {e}"))
+}
+
+/// Decrypts the secret `key` from the keystore file of the same name in `dir`.
+fn keystore_secret(dir: &Path, key: &str, password: &str) -> Result<String> {
+    ensure!(
+        matches!(Path::new(key).components().collect::<Vec<_>>()[..], [Component::Normal(_)]),
+        "invalid secret key {key:?}"
+    );
+    let path = dir.join(key);
+    let secret = eth_keystore::decrypt_key(&path, password)
+        .map_err(|e| fmt_err!("failed to decrypt secret {key:?} from {}: {e}", path.display()))?;
+    String::from_utf8(secret).map_err(|_| fmt_err!("secret {key:?} is not valid UTF-8"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decrypts_keystore_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut rng = rand::thread_rng();
+        eth_keystore::encrypt_key(dir.path(), &mut rng, "api-token", "hunter2", Some("ETHERSCAN"))
+            .unwrap();
+
+        assert_eq!(keystore_secret(dir.path(), "ETHERSCAN", "hunter2").unwrap(), "api-token");
+        assert!(keystore_secret(dir.path(), "ETHERSCAN", "wrong").is_err());
+        assert!(keystore_secret(dir.path(), "MISSING", "hunter2").is_err());
+        assert!(keystore_secret(dir.path(), "../ETHERSCAN", "hunter2").is_err());
+    }
+}
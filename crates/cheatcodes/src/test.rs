@@ -1,13 +1,44 @@
 //! Implementations of [`Testing`](crate::Group::Testing) cheatcodes.
 
 use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Error, Result, Vm::*};
-use alloy_primitives::Address;
+use alloy_primitives::{Address, Bytes};
 use alloy_sol_types::SolValue;
 use foundry_evm_core::constants::{MAGIC_ASSUME, MAGIC_SKIP};
+use revm::interpreter::InstructionResult;
 
 pub(crate) mod assert;
+pub(crate) mod assume;
+pub(crate) mod cheat_state;
 pub(crate) mod expect;
 
+/// Tracks the contract a revert originated from while it bubbles up the call stack.
+#[derive(Clone, Debug, Default)]
+pub struct RevertOrigin {
+    /// The reverting contract and its revert data.
+    origin: Option<(Address, Bytes)>,
+}
+
+impl RevertOrigin {
+    /// Records the outcome of a call to `address`.
+    ///
+    /// Successful calls clear the origin, since any revert that happened inside them was caught.
+    /// Reverts keep the recorded origin only if they bubbled up the same revert data.
+    pub fn record(&mut self, address: Address, status: InstructionResult, retdata: &Bytes) {
+        if !status.is_revert() {
+            self.origin = None;
+            return;
+        }
+        if !matches!(&self.origin, Some((_, data)) if data == retdata) {
+            self.origin = Some((address, retdata.clone()));
+        }
+    }
+
+    /// Returns the address of the contract the last revert originated from.
+    pub fn address(&self) -> Option<Address> {
+        self.origin.as_ref().map(|(address, _)| *address)
+    }
+}
+
 impl Cheatcode for assumeCall {
     fn apply(&self, _state: &mut Cheatcodes) -> Result {
         let Self { condition } = self;
@@ -19,6 +50,92 @@ impl Cheatcode for assumeCall {
     }
 }
 
+impl Cheatcode for addFuzzFixture_0Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { value } = *self;
+        state.fuzz_fixtures.push(value);
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for addFuzzFixture_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { value } = *self;
+        state.fuzz_fixtures.push(value.into());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for addFuzzFixture_2Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { value } = *self;
+        state.fuzz_fixtures.push(value.into_raw().into());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for addFuzzFixture_3Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { value } = *self;
+        state.fuzz_fixtures.push(value.into_word());
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for targetFlowCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { target, selectors } = self;
+        ensure!(!selectors.is_empty(), "a flow must have at least one selector");
+        state.targeted_flows.push((*target, selectors.clone()));
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for setFuzzRunsCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { runs } = *self;
+        let runs =
+            u32::try_from(runs).map_err(|_| fmt_err!("number of runs {runs} is too large"))?;
+        ensure!(runs > 0, "number of runs must be greater than 0");
+        state.fuzz_runs = Some(runs);
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for setMaxRejectsCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { maxRejects } = *self;
+        let max_rejects = u32::try_from(maxRejects)
+            .map_err(|_| fmt_err!("maximum number of rejects {maxRejects} is too large"))?;
+        state.fuzz_max_rejects = Some(max_rejects);
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for snapshotValueCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { name, value } = self;
+        state.value_snapshots.insert(name.clone(), Bytes::copy_from_slice(value));
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for assumeUniqueCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { key } = *self;
+        let calldata = &ccx.data.env.tx.data;
+        let selector = calldata.get(..4).and_then(|s| s.try_into().ok()).unwrap_or_default();
+        let mut keys = ccx.state.assumed_unique_keys.lock().unwrap();
+        // replaying the run that first used the key, e.g. to collect its traces, is not a repeat
+        let first = keys.entry((selector, key)).or_insert_with(|| calldata.clone());
+        if *first == *calldata {
+            Ok(Default::default())
+        } else {
+            Err(Error::from(MAGIC_ASSUME))
+        }
+    }
+}
+
 impl Cheatcode for breakpoint_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { char } = self;
@@ -0,0 +1,77 @@
+use super::RevertOrigin;
+use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Result, Vm::*};
+use alloy_primitives::{Address, Bytes};
+use alloy_sol_types::{Revert, SolError};
+use revm::interpreter::InstructionResult;
+
+/// Tracks an `assumeNoRevert` cheatcode call.
+#[derive(Clone, Debug)]
+pub struct AssumeNoRevert {
+    /// The call depth at which the cheatcode was called.
+    pub depth: u64,
+    /// The revert data that will discard the fuzz run, `None` being any.
+    pub reason: Option<Vec<u8>>,
+    /// The contract a revert must originate from to discard the fuzz run, `None` being any.
+    pub reverter: Option<Address>,
+    /// The contract the revert of the next call originated from.
+    pub revert_origin: RevertOrigin,
+}
+
+impl AssumeNoRevert {
+    /// Returns `true` if a call that exited with the given status and return data should discard
+    /// the current fuzz run.
+    pub fn matches(&self, status: InstructionResult, retdata: &Bytes) -> bool {
+        status.is_revert() &&
+            self.reason.as_ref().map_or(true, |reason| reason[..] == retdata[..]) &&
+            self.reverter.map_or(true, |reverter| self.revert_origin.address() == Some(reverter))
+    }
+}
+
+impl Cheatcode for assumeNoRevert_0Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self {} = self;
+        assume_no_revert(ccx.state, None, None, ccx.data.journaled_state.depth())
+    }
+}
+
+impl Cheatcode for assumeNoRevert_1Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { revertData } = self;
+        assume_no_revert(
+            ccx.state,
+            Some(revertData.clone()),
+            None,
+            ccx.data.journaled_state.depth(),
+        )
+    }
+}
+
+impl Cheatcode for assumeNoRevert_2Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { reason } = self;
+        let revert_data = Revert { reason: reason.clone() }.abi_encode();
+        assume_no_revert(ccx.state, Some(revert_data), None, ccx.data.journaled_state.depth())
+    }
+}
+
+impl Cheatcode for assumeNoRevert_3Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { callee } = *self;
+        assume_no_revert(ccx.state, None, Some(callee), ccx.data.journaled_state.depth())
+    }
+}
+
+fn assume_no_revert(
+    state: &mut Cheatcodes,
+    reason: Option<Vec<u8>>,
+    reverter: Option<Address>,
+    depth: u64,
+) -> Result {
+    ensure!(
+        state.assume_no_revert.is_none(),
+        "you must make another external call prior to calling assumeNoRevert again"
+    );
+    state.assume_no_revert =
+        Some(AssumeNoRevert { depth, reason, reverter, revert_origin: Default::default() });
+    Ok(Default::default())
+}
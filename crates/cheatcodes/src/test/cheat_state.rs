@@ -0,0 +1,112 @@
+use crate::{
+    evm::{
+        mock::{MockCallDataContext, MockCallReturnData},
+        prank::Prank,
+    },
+    test::expect::{ExpectedCallTracker, ExpectedEmit, ExpectedRevert},
+    Cheatcode, Cheatcodes, Result,
+    Vm::*,
+};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolValue;
+use revm::primitives::BlockEnv;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// A saved cheatcode configuration. Created by `saveCheatState`.
+///
+/// Environment variables set with `setEnv` are excluded: they are set in the environment of the
+/// process, which is shared by the tests running concurrently, so restoring them could overwrite
+/// the values set by other tests.
+#[derive(Clone, Debug)]
+pub struct CheatState {
+    /// The block environment override.
+    pub block: Option<BlockEnv>,
+    /// The gas price override.
+    pub gas_price: Option<U256>,
+    /// Prank information.
+    pub prank: Option<Prank>,
+    /// Expected revert information.
+    pub expected_revert: Option<ExpectedRevert>,
+    /// Expected calls.
+    pub expected_calls: ExpectedCallTracker,
+    /// Expected emits.
+    pub expected_emits: VecDeque<ExpectedEmit>,
+    /// Whether the expected emits may be matched in any order.
+    pub expected_emits_unordered: bool,
+    /// Mocked calls.
+    pub mocked_calls: HashMap<Address, BTreeMap<MockCallDataContext, MockCallReturnData>>,
+    /// Mocked functions.
+    pub mocked_functions: HashMap<Address, BTreeMap<Bytes, Address>>,
+    /// Mocked precompile outputs.
+    pub mocked_precompiles: HashMap<Address, HashMap<Bytes, Bytes>>,
+    /// Disabled precompiles.
+    pub disabled_precompiles: HashSet<Address>,
+}
+
+impl CheatState {
+    /// Captures the current cheatcode configuration.
+    fn capture(state: &Cheatcodes) -> Self {
+        Self {
+            block: state.block.clone(),
+            gas_price: state.gas_price,
+            prank: state.prank.clone(),
+            expected_revert: state.expected_revert.clone(),
+            expected_calls: state.expected_calls.clone(),
+            expected_emits: state.expected_emits.clone(),
+            expected_emits_unordered: state.expected_emits_unordered,
+            mocked_calls: state.mocked_calls.clone(),
+            mocked_functions: state.mocked_functions.clone(),
+            mocked_precompiles: state.mocked_precompiles.clone(),
+            disabled_precompiles: state.disabled_precompiles.clone(),
+        }
+    }
+
+    /// Replaces the current cheatcode configuration with this one.
+    fn restore(self, state: &mut Cheatcodes) {
+        let Self {
+            block,
+            gas_price,
+            prank,
+            expected_revert,
+            expected_calls,
+            expected_emits,
+            expected_emits_unordered,
+            mocked_calls,
+            mocked_functions,
+            mocked_precompiles,
+            disabled_precompiles,
+        } = self;
+        state.block = block;
+        state.gas_price = gas_price;
+        state.prank = prank;
+        state.expected_revert = expected_revert;
+        state.expected_calls = expected_calls;
+        state.expected_emits = expected_emits;
+        state.expected_emits_unordered = expected_emits_unordered;
+        state.mocked_calls = mocked_calls;
+        state.mocked_functions = mocked_functions;
+        state.mocked_precompiles = mocked_precompiles;
+        state.disabled_precompiles = disabled_precompiles;
+    }
+}
+
+impl Cheatcode for saveCheatStateCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self {} = self;
+        let id = U256::from(state.cheat_states.len());
+        state.cheat_states.push(CheatState::capture(state));
+        Ok(id.abi_encode())
+    }
+}
+
+impl Cheatcode for restoreCheatStateCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { id } = self;
+        let saved = usize::try_from(*id)
+            .ok()
+            .and_then(|id| state.cheat_states.get(id))
+            .ok_or_else(|| fmt_err!("no cheatcode state saved with ID {id}"))?;
+        saved.clone().restore(state);
+        Ok(Default::default())
+    }
+}
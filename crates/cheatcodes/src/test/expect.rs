@@ -1,4 +1,5 @@
-use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Result, Vm::*};
+use super::RevertOrigin;
+use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Error, Result, Vm::*};
 use alloy_primitives::{address, Address, Bytes, LogData as RawLog, B256, U256};
 use alloy_sol_types::{SolError, SolValue};
 use revm::interpreter::{return_ok, InstructionResult};
@@ -35,6 +36,10 @@ pub struct ExpectedCallData {
     pub gas: Option<u64>,
     /// The expected *minimum* gas supplied to the call
     pub min_gas: Option<u64>,
+    /// The expected *maximum* gas supplied to the call
+    pub max_gas: Option<u64>,
+    /// The highest gas supplied to a matching call, tracked if a maximum gas is expected
+    pub highest_gas: Option<u64>,
     /// The number of times the call is expected to be made.
     /// If the type of call is `NonCount`, this is the lower bound for the number of calls
     /// that must be seen.
@@ -74,6 +79,34 @@ pub struct ExpectedRevert {
     pub depth: u64,
     /// The type of expected revert.
     pub kind: ExpectedRevertKind,
+    /// The number of times the revert is expected to happen.
+    pub count: u64,
+    /// The number of matching reverts seen so far.
+    pub actual_count: u64,
+    /// Whether only the selector of the revert data has to match.
+    pub partial_match: bool,
+    /// The contract that is expected to throw the revert, `None` being any.
+    pub reverter: Option<Address>,
+    /// The contract the revert of the next call originated from.
+    pub revert_origin: RevertOrigin,
+}
+
+impl ExpectedRevert {
+    /// Records a matching revert, returning `true` if more reverts are still expected.
+    pub fn record_match(&mut self) -> bool {
+        self.actual_count += 1;
+        self.revert_origin = Default::default();
+        self.actual_count < self.count
+    }
+
+    /// Adds the revert count to the given mismatch error, if more than one revert is expected.
+    pub fn count_error(&self, error: Error) -> Error {
+        if self.count > 1 {
+            fmt_err!("revert {} of {} did not match: {error}", self.actual_count + 1, self.count)
+        } else {
+            error
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -84,35 +117,73 @@ pub struct ExpectedEmit {
     pub log: Option<RawLog>,
     /// The checks to perform:
     /// ```text
-    /// ┌───────┬───────┬───────┬────┐
-    /// │topic 1│topic 2│topic 3│data│
-    /// └───────┴───────┴───────┴────┘
+    /// ┌───────┬───────┬───────┬───────┬────┐
+    /// │topic 0│topic 1│topic 2│topic 3│data│
+    /// └───────┴───────┴───────┴───────┴────┘
     /// ```
-    pub checks: [bool; 4],
+    pub checks: [bool; 5],
     /// If present, check originating address against this
     pub address: Option<Address>,
+    /// Whether the log is anonymous, in which case topic 0 is not the event signature and is
+    /// only checked if requested
+    pub anonymous: bool,
     /// Whether the log was actually found in the subcalls
     pub found: bool,
 }
 
+impl ExpectedEmit {
+    /// Returns `true` if the given log matches this filled expected emit, as per its checks.
+    fn matches(&self, address: &Address, topics: &[B256], data: &Bytes) -> bool {
+        let Some(expected) = &self.log else { return false };
+
+        // Anonymous events have no signature topic, so topic 0 is matched like any other topic.
+        let signature_matches = self.anonymous ||
+            expected.topics().first().zip(topics.first()).map_or(false, |(a, b)| a == b);
+        if !signature_matches || expected.topics().len() != topics.len() {
+            return false
+        }
+
+        // Match topics
+        topics
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.checks[*i])
+            .all(|(i, topic)| topic == &expected.topics()[i]) &&
+            // Maybe match source address
+            self.address.map_or(true, |addr| addr == *address) &&
+            // Maybe match data
+            (!self.checks[4] || expected.data == *data)
+    }
+}
+
 impl Cheatcode for expectCall_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { callee, data } = self;
-        expect_call(state, callee, data, None, None, None, 1, ExpectedCallType::NonCount)
+        expect_call(state, callee, data, None, None, None, None, 1, ExpectedCallType::NonCount)
     }
 }
 
 impl Cheatcode for expectCall_1Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { callee, data, count } = self;
-        expect_call(state, callee, data, None, None, None, *count, ExpectedCallType::Count)
+        expect_call(state, callee, data, None, None, None, None, *count, ExpectedCallType::Count)
     }
 }
 
 impl Cheatcode for expectCall_2Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { callee, msgValue, data } = self;
-        expect_call(state, callee, data, Some(msgValue), None, None, 1, ExpectedCallType::NonCount)
+        expect_call(
+            state,
+            callee,
+            data,
+            Some(msgValue),
+            None,
+            None,
+            None,
+            1,
+            ExpectedCallType::NonCount,
+        )
     }
 }
 
@@ -126,6 +197,7 @@ impl Cheatcode for expectCall_3Call {
             Some(msgValue),
             None,
             None,
+            None,
             *count,
             ExpectedCallType::Count,
         )
@@ -142,6 +214,7 @@ impl Cheatcode for expectCall_4Call {
             Some(msgValue),
             Some(*gas),
             None,
+            None,
             1,
             ExpectedCallType::NonCount,
         )
@@ -158,6 +231,7 @@ impl Cheatcode for expectCall_5Call {
             Some(msgValue),
             Some(*gas),
             None,
+            None,
             *count,
             ExpectedCallType::Count,
         )
@@ -174,6 +248,7 @@ impl Cheatcode for expectCallMinGas_0Call {
             Some(msgValue),
             None,
             Some(*minGas),
+            None,
             1,
             ExpectedCallType::NonCount,
         )
@@ -190,6 +265,41 @@ impl Cheatcode for expectCallMinGas_1Call {
             Some(msgValue),
             None,
             Some(*minGas),
+            None,
+            *count,
+            ExpectedCallType::Count,
+        )
+    }
+}
+
+impl Cheatcode for expectCallMaxGas_0Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { callee, data, maxGas } = self;
+        expect_call(
+            state,
+            callee,
+            data,
+            None,
+            None,
+            None,
+            Some(*maxGas),
+            1,
+            ExpectedCallType::NonCount,
+        )
+    }
+}
+
+impl Cheatcode for expectCallMaxGas_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { callee, data, maxGas, count } = self;
+        expect_call(
+            state,
+            callee,
+            data,
+            None,
+            None,
+            None,
+            Some(*maxGas),
             *count,
             ExpectedCallType::Count,
         )
@@ -202,8 +312,9 @@ impl Cheatcode for expectEmit_0Call {
         expect_emit(
             ccx.state,
             ccx.data.journaled_state.depth(),
-            [checkTopic1, checkTopic2, checkTopic3, checkData],
+            [true, checkTopic1, checkTopic2, checkTopic3, checkData],
             None,
+            false,
         )
     }
 }
@@ -214,8 +325,9 @@ impl Cheatcode for expectEmit_1Call {
         expect_emit(
             ccx.state,
             ccx.data.journaled_state.depth(),
-            [checkTopic1, checkTopic2, checkTopic3, checkData],
+            [true, checkTopic1, checkTopic2, checkTopic3, checkData],
             Some(emitter),
+            false,
         )
     }
 }
@@ -223,55 +335,155 @@ impl Cheatcode for expectEmit_1Call {
 impl Cheatcode for expectEmit_2Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self {} = self;
-        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 4], None)
+        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 5], None, false)
     }
 }
 
 impl Cheatcode for expectEmit_3Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { emitter } = *self;
-        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 4], Some(emitter))
+        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 5], Some(emitter), false)
+    }
+}
+
+impl Cheatcode for expectEmitAnonymous_0Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData } = *self;
+        expect_emit(
+            ccx.state,
+            ccx.data.journaled_state.depth(),
+            [checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData],
+            None,
+            true,
+        )
+    }
+}
+
+impl Cheatcode for expectEmitAnonymous_1Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData, emitter } = *self;
+        expect_emit(
+            ccx.state,
+            ccx.data.journaled_state.depth(),
+            [checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData],
+            Some(emitter),
+            true,
+        )
+    }
+}
+
+impl Cheatcode for expectEmitAnonymous_2Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self {} = self;
+        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 5], None, true)
+    }
+}
+
+impl Cheatcode for expectEmitAnonymous_3Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { emitter } = *self;
+        expect_emit(ccx.state, ccx.data.journaled_state.depth(), [true; 5], Some(emitter), true)
+    }
+}
+
+impl Cheatcode for expectEmitUnorderedCall {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self {} = self;
+        state.expected_emits_unordered = true;
+        Ok(Default::default())
     }
 }
 
 impl Cheatcode for expectRevert_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self {} = self;
-        expect_revert(ccx.state, None, ccx.data.journaled_state.depth(), false)
+        expect_revert(ccx.state, None, ccx.data.journaled_state.depth(), false, 1)
     }
 }
 
 impl Cheatcode for expectRevert_1Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { revertData } = self;
-        expect_revert(ccx.state, Some(revertData.as_ref()), ccx.data.journaled_state.depth(), false)
+        expect_revert(
+            ccx.state,
+            Some(revertData.as_ref()),
+            ccx.data.journaled_state.depth(),
+            false,
+            1,
+        )
     }
 }
 
 impl Cheatcode for expectRevert_2Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { revertData } = self;
-        expect_revert(ccx.state, Some(revertData), ccx.data.journaled_state.depth(), false)
+        expect_revert(ccx.state, Some(revertData), ccx.data.journaled_state.depth(), false, 1)
+    }
+}
+
+impl Cheatcode for expectRevert_3Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { revertData, count } = self;
+        expect_revert(
+            ccx.state,
+            Some(revertData.as_ref()),
+            ccx.data.journaled_state.depth(),
+            false,
+            *count,
+        )?;
+        // Custom errors are matched by their selector, whatever their arguments
+        ccx.state.expected_revert.as_mut().unwrap().partial_match = true;
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for expectPartialRevert_0Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { revertData } = self;
+        expect_partial_revert(
+            ccx.state,
+            revertData.as_ref(),
+            ccx.data.journaled_state.depth(),
+            None,
+        )
+    }
+}
+
+impl Cheatcode for expectPartialRevert_1Call {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { revertData, reverter } = self;
+        expect_partial_revert(
+            ccx.state,
+            revertData.as_ref(),
+            ccx.data.journaled_state.depth(),
+            Some(*reverter),
+        )
     }
 }
 
 impl Cheatcode for _expectCheatcodeRevert_0Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
-        expect_revert(ccx.state, None, ccx.data.journaled_state.depth(), true)
+        expect_revert(ccx.state, None, ccx.data.journaled_state.depth(), true, 1)
     }
 }
 
 impl Cheatcode for _expectCheatcodeRevert_1Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { revertData } = self;
-        expect_revert(ccx.state, Some(revertData.as_ref()), ccx.data.journaled_state.depth(), true)
+        expect_revert(
+            ccx.state,
+            Some(revertData.as_ref()),
+            ccx.data.journaled_state.depth(),
+            true,
+            1,
+        )
     }
 }
 
 impl Cheatcode for _expectCheatcodeRevert_2Call {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self { revertData } = self;
-        expect_revert(ccx.state, Some(revertData), ccx.data.journaled_state.depth(), true)
+        expect_revert(ccx.state, Some(revertData), ccx.data.journaled_state.depth(), true, 1)
     }
 }
 
@@ -285,7 +497,19 @@ impl Cheatcode for expectSafeMemoryCall {
 impl Cheatcode for stopExpectSafeMemoryCall {
     fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
         let Self {} = self;
-        ccx.state.allowed_mem_writes.remove(&ccx.data.journaled_state.depth());
+        let depth = ccx.data.journaled_state.depth();
+        ccx.state.allowed_mem_writes.remove(&depth);
+        ccx.state.deep_allowed_mem_writes.remove(&depth);
+        Ok(Default::default())
+    }
+}
+
+impl Cheatcode for expectSafeMemoryDeepCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { min, max } = *self;
+        let depth = ccx.data.journaled_state.depth();
+        expect_safe_memory(ccx.state, min, max, depth)?;
+        ccx.state.deep_allowed_mem_writes.insert(depth);
         Ok(Default::default())
     }
 }
@@ -322,6 +546,7 @@ fn expect_call(
     value: Option<&U256>,
     mut gas: Option<u64>,
     mut min_gas: Option<u64>,
+    max_gas: Option<u64>,
     count: u64,
     call_type: ExpectedCallType,
 ) -> Result {
@@ -352,7 +577,18 @@ fn expect_call(
             );
             expecteds.insert(
                 calldata.to_vec(),
-                (ExpectedCallData { value: value.copied(), gas, min_gas, count, call_type }, 0),
+                (
+                    ExpectedCallData {
+                        value: value.copied(),
+                        gas,
+                        min_gas,
+                        max_gas,
+                        highest_gas: None,
+                        count,
+                        call_type,
+                    },
+                    0,
+                ),
             );
         }
         ExpectedCallType::NonCount => {
@@ -371,7 +607,15 @@ fn expect_call(
                 // If it does not exist, then create it.
                 Entry::Vacant(entry) => {
                     entry.insert((
-                        ExpectedCallData { value: value.copied(), gas, min_gas, count, call_type },
+                        ExpectedCallData {
+                            value: value.copied(),
+                            gas,
+                            min_gas,
+                            max_gas,
+                            highest_gas: None,
+                            count,
+                            call_type,
+                        },
                         0,
                     ));
                 }
@@ -385,13 +629,15 @@ fn expect_call(
 fn expect_emit(
     state: &mut Cheatcodes,
     depth: u64,
-    checks: [bool; 4],
+    checks: [bool; 5],
     address: Option<Address>,
+    anonymous: bool,
 ) -> Result {
     state.expected_emits.push_back(ExpectedEmit {
         depth,
         checks,
         address,
+        anonymous,
         found: false,
         log: None,
     });
@@ -418,6 +664,21 @@ pub(crate) fn handle_expect_emit(
         return
     }
 
+    // In unordered mode, once all the events are filled, the log can match any of the expected
+    // events that have not been matched yet, regardless of the order they were declared in.
+    if state.expected_emits_unordered &&
+        state.expected_emits.iter().all(|expected| expected.log.is_some())
+    {
+        if let Some(expected) = state
+            .expected_emits
+            .iter_mut()
+            .find(|expected| !expected.found && expected.matches(address, topics, data))
+        {
+            expected.found = true;
+        }
+        return
+    }
+
     // if there's anything to fill, we need to pop back.
     // Otherwise, if there are any events that are unmatched, we try to match to match them
     // in the order declared, so we start popping from the front (like a queue).
@@ -429,39 +690,15 @@ pub(crate) fn handle_expect_emit(
         }
         .expect("we should have an emit to fill or check");
 
-    let Some(expected) = &event_to_fill_or_check.log else {
+    if event_to_fill_or_check.log.is_none() {
         // Fill the event.
         event_to_fill_or_check.log = Some(RawLog::new_unchecked(topics.to_vec(), data.clone()));
         state.expected_emits.push_back(event_to_fill_or_check);
         return
-    };
-
-    let expected_topic_0 = expected.topics().first();
-    let log_topic_0 = topics.first();
-
-    if expected_topic_0
-        .zip(log_topic_0)
-        .map_or(false, |(a, b)| a == b && expected.topics().len() == topics.len())
-    {
-        // Match topics
-        event_to_fill_or_check.found = topics
-            .iter()
-            .skip(1)
-            .enumerate()
-            .filter(|(i, _)| event_to_fill_or_check.checks[*i])
-            .all(|(i, topic)| topic == &expected.topics()[i + 1]);
-
-        // Maybe match source address
-        if let Some(addr) = event_to_fill_or_check.address {
-            event_to_fill_or_check.found &= addr == *address;
-        }
-
-        // Maybe match data
-        if event_to_fill_or_check.checks[3] {
-            event_to_fill_or_check.found &= expected.data == *data;
-        }
     }
 
+    event_to_fill_or_check.found = event_to_fill_or_check.matches(address, topics, data);
+
     // If we found the event, we can push it to the back of the queue
     // and begin expecting the next event.
     if event_to_fill_or_check.found {
@@ -478,11 +715,13 @@ fn expect_revert(
     reason: Option<&[u8]>,
     depth: u64,
     cheatcode: bool,
+    count: u64,
 ) -> Result {
     ensure!(
         state.expected_revert.is_none(),
         "you must call another function prior to expecting a second revert"
     );
+    ensure!(count > 0, "expected revert count must be greater than zero");
     state.expected_revert = Some(ExpectedRevert {
         reason: reason.map(<[_]>::to_vec),
         depth,
@@ -491,13 +730,31 @@ fn expect_revert(
         } else {
             ExpectedRevertKind::Default
         },
+        count,
+        actual_count: 0,
+        partial_match: false,
+        reverter: None,
+        revert_origin: Default::default(),
     });
     Ok(Default::default())
 }
 
+fn expect_partial_revert(
+    state: &mut Cheatcodes,
+    selector: &[u8],
+    depth: u64,
+    reverter: Option<Address>,
+) -> Result {
+    expect_revert(state, Some(selector), depth, false, 1)?;
+    let expected_revert = state.expected_revert.as_mut().unwrap();
+    expected_revert.partial_match = true;
+    expected_revert.reverter = reverter;
+    Ok(Default::default())
+}
+
 pub(crate) fn handle_expect_revert(
     is_create: bool,
-    expected_revert: Option<&[u8]>,
+    expected: &ExpectedRevert,
     status: InstructionResult,
     retdata: Bytes,
 ) -> Result<(Option<Address>, Bytes)> {
@@ -511,8 +768,17 @@ pub(crate) fn handle_expect_revert(
 
     ensure!(!matches!(status, return_ok!()), "call did not revert as expected");
 
+    // Check the contract that threw the revert, if one is expected
+    if let Some(reverter) = expected.reverter {
+        match expected.revert_origin.address() {
+            Some(actual) if actual == reverter => {}
+            Some(actual) => bail!("Reverter != expected reverter: {actual} != {reverter}"),
+            None => bail!("call reverted, but the reverter could not be determined"),
+        }
+    }
+
     // If None, accept any revert
-    let Some(expected_revert) = expected_revert else {
+    let Some(expected_revert) = expected.reason.as_deref() else {
         return Ok(success_return());
     };
 
@@ -520,6 +786,11 @@ pub(crate) fn handle_expect_revert(
         bail!("call reverted as expected, but without data");
     }
 
+    // Partial matches only compare the selector of the raw revert data
+    if expected.partial_match && retdata.get(..expected_revert.len()) == Some(expected_revert) {
+        return Ok(success_return());
+    }
+
     let mut actual_revert: Vec<u8> = retdata.into();
 
     // Try decoding as known errors
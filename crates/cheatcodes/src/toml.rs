@@ -0,0 +1,256 @@
+//! Implementations of [`Toml`](crate::Group::Toml) cheatcodes.
+
+use crate::{json, Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_dyn_abi::DynSolType;
+use foundry_common::fs;
+use foundry_config::fs_permissions::FsAccessKind;
+use serde_json::{Number, Value as JsonValue};
+use toml::Value as TomlValue;
+
+impl Cheatcode for keyExistsTomlCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::key_exists(&parse_toml_str(toml)?, key)
+    }
+}
+
+impl Cheatcode for parseToml_0Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml } = self;
+        json::parse_value(&parse_toml_str(toml)?, "$")
+    }
+}
+
+impl Cheatcode for parseToml_1Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value(&parse_toml_str(toml)?, key)
+    }
+}
+
+impl Cheatcode for parseTomlUintCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Uint(256))
+    }
+}
+
+impl Cheatcode for parseTomlUintArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Uint(256))
+    }
+}
+
+impl Cheatcode for parseTomlIntCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Int(256))
+    }
+}
+
+impl Cheatcode for parseTomlIntArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Int(256))
+    }
+}
+
+impl Cheatcode for parseTomlBoolCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Bool)
+    }
+}
+
+impl Cheatcode for parseTomlBoolArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Bool)
+    }
+}
+
+impl Cheatcode for parseTomlAddressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Address)
+    }
+}
+
+impl Cheatcode for parseTomlAddressArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Address)
+    }
+}
+
+impl Cheatcode for parseTomlStringCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::String)
+    }
+}
+
+impl Cheatcode for parseTomlStringArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::String)
+    }
+}
+
+impl Cheatcode for parseTomlBytesCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Bytes)
+    }
+}
+
+impl Cheatcode for parseTomlBytesArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::Bytes)
+    }
+}
+
+impl Cheatcode for parseTomlBytes32Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::FixedBytes(32))
+    }
+}
+
+impl Cheatcode for parseTomlBytes32ArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_value_coerce(&parse_toml_str(toml)?, key, &DynSolType::FixedBytes(32))
+    }
+}
+
+impl Cheatcode for parseTomlKeysCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { toml, key } = self;
+        json::parse_keys(&parse_toml_str(toml)?, key)
+    }
+}
+
+impl Cheatcode for writeToml_0Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { json, path } = self;
+        let value =
+            serde_json::from_str(json).unwrap_or_else(|_| JsonValue::String(json.to_owned()));
+        write_toml(state, path, value)
+    }
+}
+
+impl Cheatcode for writeToml_1Call {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
+        let Self { json, path, valueKey } = self;
+        let json =
+            serde_json::from_str(json).unwrap_or_else(|_| JsonValue::String(json.to_owned()));
+
+        let data_path = state.config.ensure_path_allowed(path, FsAccessKind::Read)?;
+        let data = parse_toml_str(&fs::read_to_string(data_path)?)?;
+        let value =
+            jsonpath_lib::replace_with(data, &json::canonicalize_json_path(valueKey), &mut |_| {
+                Some(json.clone())
+            })?;
+        write_toml(state, path, value)
+    }
+}
+
+/// Parses a TOML document into a JSON value.
+fn parse_toml_str(toml: &str) -> Result<JsonValue> {
+    let value =
+        toml::from_str::<TomlValue>(toml).map_err(|e| fmt_err!("failed parsing TOML: {e}"))?;
+    Ok(toml_to_json_value(value))
+}
+
+/// Writes a JSON object to a file as a TOML document.
+fn write_toml(state: &Cheatcodes, path: &str, value: JsonValue) -> Result {
+    let value = json_to_toml_value(value)?;
+    ensure!(value.is_table(), "only JSON objects can be written as TOML");
+    let toml_string = toml::to_string_pretty(&value).map_err(|e| fmt_err!("{e}"))?;
+    super::fs::write_file(state, path.as_ref(), toml_string.as_bytes())
+}
+
+/// Converts a TOML value to a JSON value. Datetimes are converted to strings.
+fn toml_to_json_value(toml: TomlValue) -> JsonValue {
+    match toml {
+        TomlValue::String(s) => JsonValue::String(s),
+        TomlValue::Integer(i) => JsonValue::Number(i.into()),
+        TomlValue::Float(f) => Number::from_f64(f).map_or(JsonValue::Null, JsonValue::Number),
+        TomlValue::Boolean(b) => JsonValue::Bool(b),
+        TomlValue::Datetime(d) => JsonValue::String(d.to_string()),
+        TomlValue::Array(a) => JsonValue::Array(a.into_iter().map(toml_to_json_value).collect()),
+        TomlValue::Table(t) => {
+            JsonValue::Object(t.into_iter().map(|(k, v)| (k, toml_to_json_value(v))).collect())
+        }
+    }
+}
+
+/// Converts a JSON value to a TOML value. Integers which don't fit in an `i64` are converted to
+/// strings, since TOML only supports 64-bit signed integers.
+fn json_to_toml_value(json: JsonValue) -> Result<TomlValue> {
+    Ok(match json {
+        JsonValue::Null => bail!("TOML does not support null values"),
+        JsonValue::Bool(b) => TomlValue::Boolean(b),
+        JsonValue::Number(n) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => TomlValue::Integer(i),
+            (None, Some(f)) if n.is_f64() => TomlValue::Float(f),
+            _ => TomlValue::String(n.to_string()),
+        },
+        JsonValue::String(s) => TomlValue::String(s),
+        JsonValue::Array(a) => {
+            TomlValue::Array(a.into_iter().map(json_to_toml_value).collect::<Result<_>>()?)
+        }
+        JsonValue::Object(o) => TomlValue::Table(
+            o.into_iter().map(|(k, v)| Ok((k, json_to_toml_value(v)?))).collect::<Result<_>>()?,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_between_toml_and_json() {
+        let toml = r#"
+name = "deployment"
+chain_id = 1
+ratio = 0.5
+enabled = true
+owners = ["0x0000000000000000000000000000000000000001"]
+
+[contracts.token]
+address = "0x0000000000000000000000000000000000000002"
+decimals = 18
+"#;
+        let value = parse_toml_str(toml).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "name": "deployment",
+                "chain_id": 1,
+                "ratio": 0.5,
+                "enabled": true,
+                "owners": ["0x0000000000000000000000000000000000000001"],
+                "contracts": {
+                    "token": {
+                        "address": "0x0000000000000000000000000000000000000002",
+                        "decimals": 18
+                    }
+                }
+            })
+        );
+        assert_eq!(toml_to_json_value(json_to_toml_value(value.clone()).unwrap()), value);
+
+        let big = json!({ "supply": u64::MAX });
+        assert_eq!(
+            json_to_toml_value(big).unwrap()["supply"],
+            TomlValue::String(u64::MAX.to_string())
+        );
+        assert!(json_to_toml_value(json!({ "missing": null })).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! Implementations of [`Utils`](crate::Group::Utils) cheatcodes.
 
 use crate::{Cheatcode, Cheatcodes, CheatsCtxt, DatabaseExt, Result, Vm::*};
-use alloy_primitives::{keccak256, B256, U256};
+use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_signer::{
     coins_bip39::{
         ChineseSimplified, ChineseTraditional, Czech, English, French, Italian, Japanese, Korean,
@@ -10,17 +10,21 @@ use alloy_signer::{
     LocalWallet, MnemonicBuilder, Signer, SignerSync,
 };
 use alloy_sol_types::SolValue;
-use foundry_evm_core::constants::DEFAULT_CREATE2_DEPLOYER;
+use blst::min_pk::SecretKey as BlsSecretKey;
 use k256::{
     ecdsa::SigningKey,
     elliptic_curve::{sec1::ToEncodedPoint, Curve},
     Secp256k1,
 };
 use p256::ecdsa::{signature::hazmat::PrehashSigner, Signature, SigningKey as P256SigningKey};
+use rand::{Rng, RngCore};
 
 /// The BIP32 default derivation path prefix.
 const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
 
+/// The domain separation tag of the BLS signature scheme used by the Ethereum consensus layer.
+const BLS_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
 impl Cheatcode for createWallet_0Call {
     fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { walletLabel } = self;
@@ -128,9 +132,52 @@ impl Cheatcode for computeCreate2Address_0Call {
 }
 
 impl Cheatcode for computeCreate2Address_1Call {
-    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+    fn apply(&self, state: &mut Cheatcodes) -> Result {
         let Self { salt, initCodeHash } = self;
-        Ok(DEFAULT_CREATE2_DEPLOYER.create2(salt, initCodeHash).abi_encode())
+        Ok(state.config.create2_deployer.create2(salt, initCodeHash).abi_encode())
+    }
+}
+
+impl Cheatcode for randomUintCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self {} = self;
+        Ok(random_uint(ccx).abi_encode())
+    }
+}
+
+impl Cheatcode for randomUintBetweenCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { min, max } = *self;
+        ensure!(min <= max, "min ({min}) must be less than or equal to max ({max})");
+        let range = max - min;
+        let random = random_uint(ccx);
+        let value =
+            if range == U256::MAX { random } else { min + random % (range + U256::from(1)) };
+        Ok(value.abi_encode())
+    }
+}
+
+impl Cheatcode for randomAddressCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { seed } = self;
+        let rng_seed = ccx.state.rng_seed(&ccx.data.env.tx.data);
+        Ok(Address::from_word(keccak256((rng_seed, seed).abi_encode())).abi_encode())
+    }
+}
+
+/// The maximum length of `randomBytesLen`, so that a large length reverts instead of aborting on
+/// allocation failure.
+const MAX_RANDOM_BYTES_LEN: usize = 4 * 1024 * 1024;
+
+impl Cheatcode for randomBytesLenCall {
+    fn apply_full<DB: DatabaseExt>(&self, ccx: &mut CheatsCtxt<DB>) -> Result {
+        let Self { len } = *self;
+        let len = usize::try_from(len).ok().filter(|len| *len <= MAX_RANDOM_BYTES_LEN).ok_or_else(
+            || fmt_err!("length {len} exceeds the maximum of {MAX_RANDOM_BYTES_LEN}"),
+        )?;
+        let mut bytes = vec![0; len];
+        ccx.state.rng(&ccx.data.env.tx.data).fill_bytes(&mut bytes);
+        Ok(bytes.abi_encode())
     }
 }
 
@@ -154,6 +201,10 @@ fn create_wallet(private_key: &U256, label: Option<&str>, state: &mut Cheatcodes
         .abi_encode())
 }
 
+fn random_uint<DB: DatabaseExt>(ccx: &mut CheatsCtxt<DB>) -> U256 {
+    U256::from_be_bytes(ccx.state.rng(&ccx.data.env.tx.data).gen::<[u8; 32]>())
+}
+
 pub(super) fn sign(private_key: &U256, digest: &B256) -> Result {
     // The `ecrecover` precompile does not use EIP-155. No chain ID is needed.
     let wallet = parse_wallet(private_key)?;
@@ -186,6 +237,52 @@ pub(super) fn sign_p256(private_key: &U256, digest: &B256, _state: &mut Cheatcod
     Ok((r_bytes, s_bytes).abi_encode())
 }
 
+pub(super) fn sign_bls(private_key: &U256, message: &[u8]) -> Result {
+    let secret_key = parse_bls_private_key(private_key)?;
+    let signature = secret_key.sign(message, BLS_DST, &[]).serialize();
+    Ok(encode_bls_g2(&signature).abi_encode())
+}
+
+pub(super) fn bls_public_key(private_key: &U256) -> Result {
+    let secret_key = parse_bls_private_key(private_key)?;
+    let public_key = secret_key.sk_to_pk().serialize();
+    Ok(encode_bls_g1(&public_key).abi_encode())
+}
+
+pub(super) fn derive_bls_key(ikm: &[u8]) -> Result {
+    ensure!(ikm.len() >= 32, "input keying material must be at least 32 bytes long");
+    let secret_key = BlsSecretKey::key_gen(ikm, &[])
+        .map_err(|e| fmt_err!("failed to derive BLS private key: {e:?}"))?;
+    Ok(U256::from_be_bytes(secret_key.to_bytes()).abi_encode())
+}
+
+fn parse_bls_private_key(private_key: &U256) -> Result<BlsSecretKey> {
+    ensure!(*private_key != U256::ZERO, "private key cannot be 0");
+    BlsSecretKey::from_bytes(&private_key.to_be_bytes::<32>()).map_err(|_| {
+        fmt_err!(
+            "private key must be less than the BLS12-381 curve order \
+             (52435875175126190479447740508185965837690552500527637822603658699938581184513)"
+        )
+    })
+}
+
+/// Encodes an uncompressed G1 point in the EIP-2537 format, where each 48-byte field element is
+/// left-padded to 64 bytes.
+fn encode_bls_g1(point: &[u8; 96]) -> Vec<u8> {
+    point.chunks(48).flat_map(pad_bls_fp).collect()
+}
+
+/// Encodes an uncompressed G2 point in the EIP-2537 format. Unlike the serialization format of
+/// `blst`, EIP-2537 encodes the `c0` component of each `Fp2` element before `c1`.
+fn encode_bls_g2(point: &[u8; 192]) -> Vec<u8> {
+    let fp2s = point.chunks(96).flat_map(|fp2| [&fp2[48..], &fp2[..48]]);
+    fp2s.flat_map(pad_bls_fp).collect()
+}
+
+fn pad_bls_fp(fp: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    std::iter::repeat(0).take(16).chain(fp.iter().copied())
+}
+
 pub(super) fn parse_private_key(private_key: &U256) -> Result<SigningKey> {
     ensure!(*private_key != U256::ZERO, "private key cannot be 0");
     ensure!(
@@ -0,0 +1,200 @@
+//! Implementations of [`Yaml`](crate::Group::Yaml) cheatcodes.
+
+use crate::{json, Cheatcode, Cheatcodes, Result, Vm::*};
+use alloy_dyn_abi::DynSolType;
+use serde_json::Value;
+
+impl Cheatcode for keyExistsYamlCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::key_exists(&parse_yaml_str(yaml)?, key)
+    }
+}
+
+impl Cheatcode for parseYaml_0Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml } = self;
+        json::parse_value(&parse_yaml_str(yaml)?, "$")
+    }
+}
+
+impl Cheatcode for parseYaml_1Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value(&parse_yaml_str(yaml)?, key)
+    }
+}
+
+impl Cheatcode for parseYamlUintCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Uint(256))
+    }
+}
+
+impl Cheatcode for parseYamlUintArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Uint(256))
+    }
+}
+
+impl Cheatcode for parseYamlIntCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Int(256))
+    }
+}
+
+impl Cheatcode for parseYamlIntArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Int(256))
+    }
+}
+
+impl Cheatcode for parseYamlBoolCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Bool)
+    }
+}
+
+impl Cheatcode for parseYamlBoolArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Bool)
+    }
+}
+
+impl Cheatcode for parseYamlAddressCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Address)
+    }
+}
+
+impl Cheatcode for parseYamlAddressArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Address)
+    }
+}
+
+impl Cheatcode for parseYamlStringCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::String)
+    }
+}
+
+impl Cheatcode for parseYamlStringArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::String)
+    }
+}
+
+impl Cheatcode for parseYamlBytesCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Bytes)
+    }
+}
+
+impl Cheatcode for parseYamlBytesArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::Bytes)
+    }
+}
+
+impl Cheatcode for parseYamlBytes32Call {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::FixedBytes(32))
+    }
+}
+
+impl Cheatcode for parseYamlBytes32ArrayCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_value_coerce(&parse_yaml_str(yaml)?, key, &DynSolType::FixedBytes(32))
+    }
+}
+
+impl Cheatcode for parseYamlKeysCall {
+    fn apply(&self, _state: &mut Cheatcodes) -> Result {
+        let Self { yaml, key } = self;
+        json::parse_keys(&parse_yaml_str(yaml)?, key)
+    }
+}
+
+/// Parses a YAML document into a JSON value.
+///
+/// Keys of mappings which are not strings, such as numbers, are converted to strings.
+fn parse_yaml_str(yaml: &str) -> Result<Value> {
+    let value = serde_yaml::from_str::<serde_yaml::Value>(yaml)
+        .map_err(|e| fmt_err!("failed parsing YAML: {e}"))?;
+    yaml_to_json_value(value)
+}
+
+/// Converts a YAML value to a JSON value.
+fn yaml_to_json_value(yaml: serde_yaml::Value) -> Result<Value> {
+    use serde_yaml::Value as Yaml;
+    Ok(match yaml {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(b),
+        Yaml::Number(n) => serde_json::to_value(n)?,
+        Yaml::String(s) => Value::String(s),
+        Yaml::Sequence(s) => {
+            Value::Array(s.into_iter().map(yaml_to_json_value).collect::<Result<_>>()?)
+        }
+        Yaml::Mapping(m) => Value::Object(
+            m.into_iter()
+                .map(|(k, v)| {
+                    let key = match k {
+                        Yaml::String(s) => s,
+                        Yaml::Bool(b) => b.to_string(),
+                        Yaml::Number(n) => n.to_string(),
+                        _ => bail!("YAML mapping keys must be strings, numbers or booleans"),
+                    };
+                    Ok((key, yaml_to_json_value(v)?))
+                })
+                .collect::<Result<_>>()?,
+        ),
+        Yaml::Tagged(tagged) => yaml_to_json_value(tagged.value)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn converts_yaml_to_json() {
+        let yaml = r#"
+name: deployment
+chain_id: 1
+enabled: true
+owners:
+  - "0x0000000000000000000000000000000000000001"
+contracts:
+  token:
+    decimals: 18
+  1: mainnet
+"#;
+        assert_eq!(
+            parse_yaml_str(yaml).unwrap(),
+            json!({
+                "name": "deployment",
+                "chain_id": 1,
+                "enabled": true,
+                "owners": ["0x0000000000000000000000000000000000000001"],
+                "contracts": { "token": { "decimals": 18 }, "1": "mainnet" }
+            })
+        );
+        assert!(parse_yaml_str("key: [unclosed").is_err());
+    }
+}
@@ -0,0 +1,195 @@
+//! A signer backed by a secp256k1 key of Google Cloud KMS.
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use ethers_core::{
+    k256::ecdsa::{self, RecoveryId, VerifyingKey},
+    types::{
+        transaction::{eip2718::TypedTransaction, eip712::Eip712},
+        Address, Signature, H256, U256,
+    },
+    utils::{hash_message, public_key_to_address},
+};
+use ethers_signers::Signer;
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+/// The endpoint of the Cloud KMS API.
+const GCP_KMS_URL: &str = "https://cloudkms.googleapis.com/v1";
+
+/// The environment variable of the OAuth access token used to call Cloud KMS.
+///
+/// If it's not set, the token of the active `gcloud` account is used.
+pub const GCP_ACCESS_TOKEN_ENV: &str = "GOOGLE_OAUTH_ACCESS_TOKEN";
+
+#[derive(Debug, thiserror::Error)]
+pub enum GcpSignerError {
+    #[error(transparent)]
+    Request(#[from] reqwest::Error),
+    #[error(transparent)]
+    Base64(#[from] base64::DecodeError),
+    #[error(transparent)]
+    Ecdsa(#[from] ecdsa::Error),
+    #[error("failed to get a GCP access token: {0}")]
+    AccessToken(String),
+    #[error("failed to encode the EIP-712 payload: {0}")]
+    Eip712(String),
+    #[error("the signature of the GCP KMS key doesn't recover its public key")]
+    Recovery,
+}
+
+/// A signer of the `EC_SIGN_SECP256K1_SHA256` key version of Google Cloud KMS, identified by its
+/// resource name: `projects/<project>/locations/<location>/keyRings/<key ring>/cryptoKeys/<key>/
+/// cryptoKeyVersions/<version>`.
+///
+/// KMS signs the digests it is given as is, so the keccak256 digests of Ethereum are passed in
+/// place of SHA-256 ones.
+#[derive(Clone, Debug)]
+pub struct GcpSigner {
+    client: reqwest::Client,
+    key: String,
+    token: String,
+    public_key: VerifyingKey,
+    address: Address,
+    chain_id: u64,
+}
+
+#[derive(Deserialize)]
+struct PublicKeyResponse {
+    pem: String,
+}
+
+#[derive(Serialize)]
+struct SignRequest {
+    digest: Digest,
+}
+
+#[derive(Serialize)]
+struct Digest {
+    sha256: String,
+}
+
+#[derive(Deserialize)]
+struct SignResponse {
+    signature: String,
+}
+
+impl GcpSigner {
+    /// Creates a signer of the key version, fetching its public key.
+    pub async fn new(key: String, chain_id: u64) -> Result<Self, GcpSignerError> {
+        let client = reqwest::Client::new();
+        let token = access_token()?;
+        let response: PublicKeyResponse = client
+            .get(format!("{GCP_KMS_URL}/{key}/publicKey"))
+            .bearer_auth(&token)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        // the uncompressed point ends the DER encoded `SubjectPublicKeyInfo`
+        let der = BASE64.decode(
+            response.pem.lines().filter(|line| !line.starts_with("-----")).collect::<String>(),
+        )?;
+        let point = &der[der.len().saturating_sub(65)..];
+        let public_key = VerifyingKey::from_sec1_bytes(point)?;
+        let address = public_key_to_address(&public_key);
+
+        Ok(Self { client, key, token, public_key, address, chain_id })
+    }
+
+    /// Signs the digest with the key, and returns the signature with a `v` of 27 or 28.
+    pub async fn sign_digest(&self, digest: H256) -> Result<Signature, GcpSignerError> {
+        let body = SignRequest { digest: Digest { sha256: BASE64.encode(digest) } };
+        let response: SignResponse = self
+            .client
+            .post(format!("{GCP_KMS_URL}/{}:asymmetricSign", self.key))
+            .bearer_auth(&self.token)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        let signature = ecdsa::Signature::from_der(&BASE64.decode(response.signature)?)?;
+        let signature = signature.normalize_s().unwrap_or(signature);
+        let recovery_id = (0..=1)
+            .filter_map(RecoveryId::from_byte)
+            .find(|id| {
+                VerifyingKey::recover_from_prehash(digest.as_bytes(), &signature, *id)
+                    .map_or(false, |key| key == self.public_key)
+            })
+            .ok_or(GcpSignerError::Recovery)?;
+
+        let bytes = signature.to_bytes();
+        Ok(Signature {
+            r: U256::from_big_endian(&bytes[..32]),
+            s: U256::from_big_endian(&bytes[32..]),
+            v: recovery_id.to_byte() as u64 + 27,
+        })
+    }
+}
+
+#[async_trait]
+impl Signer for GcpSigner {
+    type Error = GcpSignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        self.sign_digest(hash_message(message)).await
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let chain_id = tx.chain_id().map(|id| id.as_u64()).unwrap_or(self.chain_id);
+        let mut tx = tx.clone();
+        tx.set_chain_id(chain_id);
+
+        let mut signature = self.sign_digest(tx.sighash()).await?;
+        // EIP-155 replay protection
+        signature.v = signature.v - 27 + 35 + chain_id * 2;
+        Ok(signature)
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        let digest =
+            payload.encode_eip712().map_err(|err| GcpSignerError::Eip712(err.to_string()))?;
+        self.sign_digest(digest.into()).await
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// Returns the OAuth access token of [GCP_ACCESS_TOKEN_ENV], or of the active `gcloud` account.
+fn access_token() -> Result<String, GcpSignerError> {
+    if let Ok(token) = std::env::var(GCP_ACCESS_TOKEN_ENV) {
+        return Ok(token)
+    }
+    let output = Command::new("gcloud")
+        .args(["auth", "print-access-token"])
+        .output()
+        .map_err(|err| GcpSignerError::AccessToken(format!("could not run gcloud: {err}")))?;
+    if !output.status.success() {
+        return Err(GcpSignerError::AccessToken(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ))
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
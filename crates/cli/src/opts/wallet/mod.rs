@@ -29,6 +29,15 @@ pub use multi_wallet::*;
 
 pub mod error;
 
+#[cfg(feature = "gcp-kms")]
+pub mod gcp;
+#[cfg(feature = "gcp-kms")]
+pub use gcp::{GcpSigner, GcpSignerError};
+
+/// The number of accounts of a hardware wallet, derived with the Ledger Live or Trezor Live
+/// derivation paths, which are scanned for the `--from` address.
+const HW_SCAN_DEPTH: u32 = 10;
+
 /// A wrapper for the raw data options for `Wallet`, extracted to also be used standalone.
 /// The raw wallet options can either be:
 /// 1. Private Key (cleartext in CLI)
@@ -141,6 +150,22 @@ pub struct Wallet {
     /// Use AWS Key Management Service.
     #[clap(long, help_heading = "Wallet options - AWS KMS")]
     pub aws: bool,
+
+    /// The ID of the AWS KMS key to sign with, instead of the `AWS_KMS_KEY_ID` environment
+    /// variable.
+    ///
+    /// Implies --aws.
+    #[clap(long, help_heading = "Wallet options - AWS KMS", value_name = "KEY_ID")]
+    pub aws_kms_key_id: Option<String>,
+
+    /// The resource name of the Google Cloud KMS key version to sign with, e.g.
+    /// `projects/<project>/locations/<location>/keyRings/<key
+    /// ring>/cryptoKeys/<key>/cryptoKeyVersions/1`.
+    ///
+    /// Requests are authenticated with the `GOOGLE_OAUTH_ACCESS_TOKEN` environment variable, or
+    /// the active `gcloud` account. Requires the `gcp-kms` feature.
+    #[clap(long, help_heading = "Wallet options - GCP KMS", value_name = "KEY")]
+    pub gcp_kms_key: Option<String>,
 }
 
 impl From<RawWallet> for Wallet {
@@ -221,11 +246,30 @@ impl Wallet {
                 Some(hd_path) => LedgerHDPath::Other(hd_path.clone()),
                 None => LedgerHDPath::LedgerLive(self.raw.mnemonic_index as usize),
             };
-            let ledger = Ledger::new(derivation, chain_id).await.wrap_err_with(|| {
-                "\
+            let connect = |derivation| async move {
+                Ledger::new(derivation, chain_id).await.wrap_err_with(|| {
+                    "\
 Could not connect to Ledger device.
 Make sure it's connected and unlocked, with no other desktop wallet apps open."
-            })?;
+                })
+            };
+            let mut ledger = connect(derivation).await?;
+
+            // Look for the `--from` account in the first accounts of the device
+            if let (Some(from), None) = (self.from, &self.raw.hd_path) {
+                if ledger.address().to_alloy() != from {
+                    let indexes = (0..HW_SCAN_DEPTH).collect::<Vec<_>>();
+                    let Some((index, _)) = scan_ledger(&ledger, &indexes)
+                        .await?
+                        .into_iter()
+                        .find(|(_, address)| *address == from)
+                    else {
+                        bail!("No account of the first {HW_SCAN_DEPTH} of the Ledger device matches {from}. Pass its derivation path with --mnemonic-derivation-path.")
+                    };
+                    drop(ledger);
+                    ledger = connect(LedgerHDPath::LedgerLive(index as usize)).await?;
+                }
+            }
 
             Ok(WalletSigner::Ledger(ledger))
         } else if self.trezor {
@@ -234,25 +278,51 @@ Make sure it's connected and unlocked, with no other desktop wallet apps open."
                 None => TrezorHDPath::TrezorLive(self.raw.mnemonic_index as usize),
             };
 
-            // cached to ~/.ethers-rs/trezor/cache/trezor.session
-            let trezor = Trezor::new(derivation, chain_id, None).await.wrap_err_with(|| {
-                "\
+            let connect = |derivation| async move {
+                // cached to ~/.ethers-rs/trezor/cache/trezor.session
+                Trezor::new(derivation, chain_id, None).await.wrap_err_with(|| {
+                    "\
 Could not connect to Trezor device.
 Make sure it's connected and unlocked, with no other conflicting desktop wallet apps open."
-            })?;
+                })
+            };
+            let mut trezor = connect(derivation).await?;
+
+            // Look for the `--from` account in the first accounts of the device
+            if let (Some(from), None) = (self.from, &self.raw.hd_path) {
+                if trezor.address().to_alloy() != from {
+                    let indexes = (0..HW_SCAN_DEPTH).collect::<Vec<_>>();
+                    let Some((index, _)) = scan_trezor(&trezor, &indexes)
+                        .await?
+                        .into_iter()
+                        .find(|(_, address)| *address == from)
+                    else {
+                        bail!("No account of the first {HW_SCAN_DEPTH} of the Trezor device matches {from}. Pass its derivation path with --mnemonic-derivation-path.")
+                    };
+                    drop(trezor);
+                    trezor = connect(TrezorHDPath::TrezorLive(index as usize)).await?;
+                }
+            }
 
             Ok(WalletSigner::Trezor(trezor))
-        } else if self.aws {
+        } else if self.aws || self.aws_kms_key_id.is_some() {
             let client =
                 AwsClient::new_with(AwsChainProvider::default(), AwsHttpClient::new().unwrap());
 
             let kms = KmsClient::new_with_client(client, AwsRegion::default());
 
-            let key_id = std::env::var("AWS_KMS_KEY_ID")?;
+            let key_id = match &self.aws_kms_key_id {
+                Some(key_id) => key_id.clone(),
+                None => std::env::var("AWS_KMS_KEY_ID").wrap_err(
+                    "No AWS KMS key ID. Pass --aws-kms-key-id or set `AWS_KMS_KEY_ID`.",
+                )?,
+            };
 
             let aws_signer = AwsSigner::new(kms, key_id, chain_id).await?;
 
             Ok(WalletSigner::Aws(aws_signer))
+        } else if let Some(key) = &self.gcp_kms_key {
+            gcp_signer(key, chain_id).await
         } else {
             trace!("finding local key");
 
@@ -264,7 +334,7 @@ Make sure it's connected and unlocked, with no other conflicting desktop wallet
 Error accessing local wallet. Did you set a private key, mnemonic or keystore?
 Run `cast send --help` or `forge create --help` and use the corresponding CLI
 flag to set your key via:
---private-key, --mnemonic-path, --aws, --interactive, --trezor or --ledger.
+--private-key, --mnemonic-path, --aws, --aws-kms-key-id, --gcp-kms-key, --interactive, --trezor or --ledger.
 Alternatively, if you're using a local node with unlocked accounts,
 use the --unlocked flag and either set the `ETH_FROM` environment variable to the address
 of the unlocked account you want to use, or provide the --from flag with the address directly."
@@ -276,6 +346,28 @@ of the unlocked account you want to use, or provide the --from flag with the add
     }
 }
 
+/// Returns the addresses of the Ledger's accounts at the given indexes of the Ledger Live
+/// derivation path.
+pub async fn scan_ledger(ledger: &Ledger, indexes: &[u32]) -> Result<Vec<(u32, Address)>> {
+    let mut accounts = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        let derivation = LedgerHDPath::LedgerLive(*index as usize);
+        accounts.push((*index, ledger.get_address_with_path(&derivation).await?.to_alloy()));
+    }
+    Ok(accounts)
+}
+
+/// Returns the addresses of the Trezor's accounts at the given indexes of the Trezor Live
+/// derivation path.
+pub async fn scan_trezor(trezor: &Trezor, indexes: &[u32]) -> Result<Vec<(u32, Address)>> {
+    let mut accounts = Vec::with_capacity(indexes.len());
+    for index in indexes {
+        let derivation = TrezorHDPath::TrezorLive(*index as usize);
+        accounts.push((*index, trezor.get_address_with_path(&derivation).await?.to_alloy()));
+    }
+    Ok(accounts)
+}
+
 pub trait WalletTrait {
     /// Returns the configured sender.
     fn sender(&self) -> Option<Address>;
@@ -423,6 +515,9 @@ pub enum WalletSignerError {
     Trezor(#[from] TrezorError),
     #[error(transparent)]
     Aws(#[from] AwsSignerError),
+    #[cfg(feature = "gcp-kms")]
+    #[error(transparent)]
+    Gcp(#[from] GcpSignerError),
 }
 
 #[derive(Debug)]
@@ -431,6 +526,8 @@ pub enum WalletSigner {
     Ledger(Ledger),
     Trezor(Trezor),
     Aws(AwsSigner),
+    #[cfg(feature = "gcp-kms")]
+    Gcp(GcpSigner),
 }
 
 impl From<LocalWallet> for WalletSigner {
@@ -457,6 +554,29 @@ impl From<AwsSigner> for WalletSigner {
     }
 }
 
+#[cfg(feature = "gcp-kms")]
+impl From<GcpSigner> for WalletSigner {
+    fn from(wallet: GcpSigner) -> Self {
+        Self::Gcp(wallet)
+    }
+}
+
+/// Creates the signer of the Google Cloud KMS key version.
+pub async fn gcp_signer(key: &str, chain_id: u64) -> Result<WalletSigner> {
+    #[cfg(feature = "gcp-kms")]
+    {
+        let signer = GcpSigner::new(key.to_string(), chain_id)
+            .await
+            .wrap_err_with(|| format!("Failed to load GCP KMS key {key}"))?;
+        Ok(WalletSigner::Gcp(signer))
+    }
+    #[cfg(not(feature = "gcp-kms"))]
+    {
+        let _ = chain_id;
+        bail!("Can't sign with GCP KMS key {key}: Foundry was built without the `gcp-kms` feature")
+    }
+}
+
 macro_rules! delegate {
     ($s:ident, $inner:ident => $e:expr) => {
         match $s {
@@ -464,6 +584,8 @@ macro_rules! delegate {
             Self::Ledger($inner) => $e,
             Self::Trezor($inner) => $e,
             Self::Aws($inner) => $e,
+            #[cfg(feature = "gcp-kms")]
+            Self::Gcp($inner) => $e,
         }
     };
 }
@@ -504,6 +626,8 @@ impl Signer for WalletSigner {
             Self::Ledger(inner) => Self::Ledger(inner.with_chain_id(chain_id)),
             Self::Trezor(inner) => Self::Trezor(inner.with_chain_id(chain_id)),
             Self::Aws(inner) => Self::Aws(inner.with_chain_id(chain_id)),
+            #[cfg(feature = "gcp-kms")]
+            Self::Gcp(inner) => Self::Gcp(inner.with_chain_id(chain_id)),
         }
     }
 }
@@ -588,6 +712,8 @@ mod tests {
             ledger: false,
             trezor: false,
             aws: false,
+            aws_kms_key_id: None,
+            gcp_kms_key: None,
         };
         match wallet.private_key() {
             Ok(_) => {
@@ -1,4 +1,4 @@
-use super::{WalletSigner, WalletTrait};
+use super::{gcp_signer, scan_ledger, scan_trezor, WalletSigner, WalletTrait};
 use alloy_primitives::Address;
 use clap::Parser;
 use ethers_providers::Middleware;
@@ -18,6 +18,8 @@ use serde::Serialize;
 use std::{
     collections::{HashMap, HashSet},
     iter::repeat,
+    ops::Range,
+    str::FromStr,
     sync::Arc,
 };
 
@@ -46,7 +48,7 @@ macro_rules! create_hw_wallets {
             }
         }
 
-        if let Some(mnemonic_indexes) = &$self.mnemonic_indexes {
+        if let Some(mnemonic_indexes) = &$self.mnemonic_indexes() {
             for index in mnemonic_indexes {
                 if let Some(hw) = $self.$get_wallet($chain_id, None, Some(*index as usize)).await? {
                     $wallets.push(hw);
@@ -70,6 +72,7 @@ macro_rules! create_hw_wallets {
 /// 5. Private Keys (cleartext in CLI)
 /// 6. Private Keys (interactively via secure prompt)
 /// 7. AWS KMS
+/// 8. GCP KMS
 #[derive(Clone, Debug, Default, Serialize, Parser)]
 #[clap(next_help_heading = "Wallet options", about = None, long_about = None)]
 pub struct MultiWallet {
@@ -128,9 +131,10 @@ pub struct MultiWallet {
     )]
     pub hd_paths: Option<Vec<String>>,
 
-    /// Use the private key from the given mnemonic index.
+    /// Use the private key from the given mnemonic index, or range of indexes (e.g. `0..10`).
     ///
-    /// Can be used with --mnemonics, --ledger, --aws and --trezor.
+    /// Can be used with --mnemonics, --ledger, --aws and --trezor. With a hardware wallet, the
+    /// accounts of a range of indexes are scanned for the ones of the senders.
     #[clap(
         long,
         conflicts_with = "hd_paths",
@@ -138,7 +142,7 @@ pub struct MultiWallet {
         default_value = "0",
         value_name = "INDEXES"
     )]
-    pub mnemonic_indexes: Option<Vec<u32>>,
+    pub mnemonic_indexes: Option<Vec<MnemonicIndexes>>,
 
     /// Use the keystore in the given folder or file.
     #[clap(
@@ -195,6 +199,53 @@ pub struct MultiWallet {
     /// Use AWS Key Management Service.
     #[clap(long, help_heading = "Wallet options - remote")]
     pub aws: bool,
+
+    /// The IDs of the AWS KMS keys to sign with, instead of the `AWS_KMS_KEY_IDS` or
+    /// `AWS_KMS_KEY_ID` environment variables.
+    ///
+    /// Implies --aws.
+    #[clap(
+        long,
+        help_heading = "Wallet options - remote",
+        value_name = "KEY_IDS",
+        value_delimiter = ','
+    )]
+    pub aws_kms_key_ids: Option<Vec<String>>,
+
+    /// The resource names of the Google Cloud KMS key versions to sign with.
+    ///
+    /// Requests are authenticated with the `GOOGLE_OAUTH_ACCESS_TOKEN` environment variable, or
+    /// the active `gcloud` account. Requires the `gcp-kms` feature.
+    #[clap(
+        long,
+        help_heading = "Wallet options - remote",
+        value_name = "KEYS",
+        value_delimiter = ','
+    )]
+    pub gcp_kms_keys: Option<Vec<String>>,
+}
+
+/// A mnemonic index, or a range of them, e.g. `3` or `0..10`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MnemonicIndexes(pub Range<u32>);
+
+impl FromStr for MnemonicIndexes {
+    type Err = eyre::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let range = match s.split_once("..") {
+            Some((start, end)) => match end.strip_prefix('=') {
+                Some(end) => start.parse()?..end.parse::<u32>()? + 1,
+                None => start.parse()?..end.parse()?,
+            },
+            None => {
+                let index = s.parse()?;
+                index..index + 1
+            }
+        };
+        eyre::ensure!(!range.is_empty(), "Empty range of mnemonic indexes: {s}");
+        Ok(Self(range))
+    }
 }
 
 impl WalletTrait for MultiWallet {
@@ -204,6 +255,13 @@ impl WalletTrait for MultiWallet {
 }
 
 impl MultiWallet {
+    /// Returns the mnemonic indexes, with the ranges expanded.
+    pub fn mnemonic_indexes(&self) -> Option<Vec<u32>> {
+        self.mnemonic_indexes
+            .as_ref()
+            .map(|indexes| indexes.iter().flat_map(|indexes| indexes.0.clone()).collect())
+    }
+
     /// Given a list of addresses, it finds all the associated wallets if they exist. Throws an
     /// error, if it can't find all.
     pub async fn find_all(
@@ -218,16 +276,18 @@ impl MultiWallet {
         let mut local_wallets = HashMap::new();
         let mut unused_wallets = vec![];
 
+        let hw = self.scan_hardware_wallets(chain, &addresses).await?;
         get_wallets!(
             wallets,
             [
-                self.trezors(chain).await?,
-                self.ledgers(chain).await?,
+                hw.trezors(chain).await?,
+                hw.ledgers(chain).await?,
                 self.private_keys()?,
                 self.interactives()?,
                 self.mnemonics()?,
                 self.keystores()?,
                 self.aws_signers(chain).await?,
+                self.gcp_signers(chain).await?,
                 (!script_wallets.is_empty()).then(|| script_wallets.to_vec())
             ],
             for wallet in wallets.into_iter() {
@@ -336,9 +396,8 @@ impl MultiWallet {
                 } else {
                     repeat(None).take(mnemonics.len()).collect()
                 };
-            let mnemonic_indexes: Vec<_> = if let Some(ref mnemonic_indexes) = self.mnemonic_indexes
-            {
-                mnemonic_indexes.to_vec()
+            let mnemonic_indexes: Vec<_> = if let Some(mnemonic_indexes) = self.mnemonic_indexes() {
+                mnemonic_indexes
             } else {
                 repeat(0).take(mnemonics.len()).collect()
             };
@@ -382,19 +441,71 @@ impl MultiWallet {
         Ok(None)
     }
 
+    /// Narrows the mnemonic indexes of the hardware wallets down to the ones of the given
+    /// addresses, so that a range of indexes can be scanned for the senders without connecting a
+    /// signer for each of them.
+    async fn scan_hardware_wallets(
+        &self,
+        chain_id: u64,
+        addresses: &HashSet<Address>,
+    ) -> Result<Self> {
+        let indexes = self.mnemonic_indexes().unwrap_or_default();
+        if indexes.len() <= 1 || !(self.ledger || self.trezor) {
+            return Ok(self.clone())
+        }
+
+        let mut found = vec![];
+        if self.ledger {
+            let ledger = Ledger::new(LedgerHDPath::LedgerLive(indexes[0] as usize), chain_id)
+                .await
+                .wrap_err("Ledger device not available.")?;
+            for (index, address) in scan_ledger(&ledger, &indexes).await? {
+                if addresses.contains(&address) {
+                    found.push(index);
+                }
+            }
+        }
+        if self.trezor {
+            let trezor = Trezor::new(TrezorHDPath::TrezorLive(indexes[0] as usize), chain_id, None)
+                .await
+                .wrap_err("Trezor device not available.")?;
+            for (index, address) in scan_trezor(&trezor, &indexes).await? {
+                if addresses.contains(&address) {
+                    found.push(index);
+                }
+            }
+        }
+
+        let mut wallet = self.clone();
+        wallet.mnemonic_indexes =
+            Some(found.into_iter().map(|index| MnemonicIndexes(index..index + 1)).collect());
+        Ok(wallet)
+    }
+
     pub async fn aws_signers(&self, chain_id: u64) -> Result<Option<Vec<AwsSigner>>> {
-        if self.aws {
+        if self.aws || self.aws_kms_key_ids.is_some() {
             let mut wallets = vec![];
             let client =
                 AwsClient::new_with(AwsChainProvider::default(), AwsHttpClient::new().unwrap());
 
             let kms = KmsClient::new_with_client(client, AwsRegion::default());
 
-            let env_key_ids = std::env::var("AWS_KMS_KEY_IDS");
-            let key_ids =
-                if env_key_ids.is_ok() { env_key_ids? } else { std::env::var("AWS_KMS_KEY_ID")? };
+            let key_ids = match &self.aws_kms_key_ids {
+                Some(key_ids) => key_ids.clone(),
+                None => {
+                    let env_key_ids = std::env::var("AWS_KMS_KEY_IDS");
+                    let key_ids = if env_key_ids.is_ok() {
+                        env_key_ids?
+                    } else {
+                        std::env::var("AWS_KMS_KEY_ID").wrap_err(
+                            "No AWS KMS key IDs. Pass --aws-kms-key-ids or set `AWS_KMS_KEY_IDS`.",
+                        )?
+                    };
+                    key_ids.split(',').map(str::to_string).collect()
+                }
+            };
 
-            for key in key_ids.split(',') {
+            for key in key_ids {
                 let aws_signer = AwsSigner::new(kms.clone(), key, chain_id).await?;
                 wallets.push(aws_signer)
             }
@@ -404,6 +515,15 @@ impl MultiWallet {
         Ok(None)
     }
 
+    pub async fn gcp_signers(&self, chain_id: u64) -> Result<Option<Vec<WalletSigner>>> {
+        let Some(keys) = &self.gcp_kms_keys else { return Ok(None) };
+        let mut wallets = vec![];
+        for key in keys {
+            wallets.push(gcp_signer(key, chain_id).await?);
+        }
+        Ok(Some(wallets))
+    }
+
     async fn get_from_trezor(
         &self,
         chain_id: u64,
@@ -506,9 +626,47 @@ mod tests {
             }
 
             assert_eq!(
-                args.mnemonic_indexes.expect("--mnemonic-indexes should have been set")[0],
+                args.mnemonic_indexes().expect("--mnemonic-indexes should have been set")[0],
                 test_case.2
             )
         }
     }
+
+    #[test]
+    fn parse_mnemonic_index_ranges() {
+        let args: MultiWallet = MultiWallet::parse_from([
+            "foundry-cli",
+            "--ledger",
+            "--mnemonic-indexes",
+            "0..3",
+            "--mnemonic-indexes",
+            "7",
+        ]);
+        assert_eq!(args.mnemonic_indexes(), Some(vec![0, 1, 2, 7]));
+
+        assert_eq!("2..=4".parse::<MnemonicIndexes>().unwrap(), MnemonicIndexes(2..5));
+        assert!("3..3".parse::<MnemonicIndexes>().is_err());
+        assert!("a..3".parse::<MnemonicIndexes>().is_err());
+    }
+
+    #[test]
+    fn parse_comma_separated_kms_keys() {
+        let args: MultiWallet = MultiWallet::parse_from([
+            "foundry-cli",
+            "--aws-kms-key-ids",
+            "key1,key2",
+            "--aws-kms-key-ids",
+            "key3",
+            "--gcp-kms-keys",
+            "projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1",
+        ]);
+        assert_eq!(
+            args.aws_kms_key_ids,
+            Some(vec!["key1".to_string(), "key2".to_string(), "key3".to_string()])
+        );
+        assert_eq!(
+            args.gcp_kms_keys,
+            Some(vec!["projects/p/locations/l/keyRings/r/cryptoKeys/k/cryptoKeyVersions/1".into()])
+        );
+    }
 }
@@ -526,6 +526,28 @@ https://github.com/foundry-rs/foundry/issues/new/choose"
             .get_stdout_lossy()
     }
 
+    /// Returns the contents of the file at `path`, relative to the current directory, at the given
+    /// revision.
+    pub fn show_file(self, revision: &str, path: &Path) -> Result<String> {
+        self.cmd().arg("show").arg(format!("{revision}:./{}", path.display())).get_stdout_lossy()
+    }
+
+    /// Checks out the given revision in a new detached worktree at `path`.
+    pub fn worktree_add(self, path: &Path, revision: &str) -> Result<()> {
+        self.cmd()
+            .stderr(self.stderr())
+            .args(["worktree", "add", "--detach"])
+            .arg(path)
+            .arg(revision)
+            .exec()
+            .map(drop)
+    }
+
+    /// Removes the worktree at `path`, discarding any changes in it.
+    pub fn worktree_remove(self, path: &Path) -> Result<()> {
+        self.cmd().args(["worktree", "remove", "--force"]).arg(path).exec().map(drop)
+    }
+
     pub fn tag(self) -> Result<String> {
         self.cmd().arg("tag").get_stdout_lossy()
     }
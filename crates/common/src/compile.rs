@@ -276,7 +276,10 @@ impl ProjectCompiler {
 pub struct ContractSources(pub HashMap<String, HashMap<u32, (String, ContractBytecodeSome)>>);
 
 // https://eips.ethereum.org/EIPS/eip-170
-const CONTRACT_SIZE_LIMIT: usize = 24576;
+pub const CONTRACT_SIZE_LIMIT: usize = 24576;
+
+// https://eips.ethereum.org/EIPS/eip-3860
+pub const CONTRACT_INITCODE_SIZE_LIMIT: usize = 2 * CONTRACT_SIZE_LIMIT;
 
 /// Contracts with info about their size
 pub struct SizeReport {
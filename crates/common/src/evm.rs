@@ -80,6 +80,15 @@ pub struct EvmArgs {
     #[serde(skip)]
     pub no_storage_caching: bool,
 
+    /// Prefetches the storage of forked contracts when they are first accessed.
+    ///
+    /// This requires the endpoint to support `debug_storageRangeAt`.
+    ///
+    /// See --fork-url.
+    #[clap(long)]
+    #[serde(skip)]
+    pub fork_prefetch_storage: bool,
+
     /// The initial balance of deployed test contracts.
     #[clap(long, value_name = "BALANCE")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -165,6 +174,10 @@ impl Provider for EvmArgs {
             dict.insert("no_storage_caching".to_string(), self.no_storage_caching.into());
         }
 
+        if self.fork_prefetch_storage {
+            dict.insert("fork_prefetch_storage".to_string(), self.fork_prefetch_storage.into());
+        }
+
         if self.no_rpc_rate_limit {
             dict.insert("no_rpc_rate_limit".to_string(), self.no_rpc_rate_limit.into());
         }
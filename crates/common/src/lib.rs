@@ -18,6 +18,7 @@ pub mod fmt;
 pub mod fs;
 pub mod glob;
 pub mod provider;
+pub mod remote_cache;
 pub mod retry;
 pub mod rpc;
 pub mod runtime_client;
@@ -28,6 +29,7 @@ pub mod term;
 pub mod traits;
 pub mod transactions;
 pub mod types;
+pub mod user_op;
 
 pub use constants::*;
 pub use contracts::*;
@@ -1,7 +1,11 @@
 //! Commonly used helpers to construct `Provider`s
 
 use crate::{
-    provider::runtime_transport::RuntimeTransportBuilder, ALCHEMY_FREE_TIER_CUPS, REQUEST_TIMEOUT,
+    provider::{
+        cassette::{Cassette, RpcCassetteConfig},
+        runtime_transport::RuntimeTransportBuilder,
+    },
+    ALCHEMY_FREE_TIER_CUPS, REQUEST_TIMEOUT,
 };
 use alloy_primitives::U256;
 use alloy_providers::provider::{Provider, TempProvider};
@@ -70,6 +74,8 @@ pub struct ProviderBuilder {
     /// JWT Secret
     jwt: Option<String>,
     headers: Vec<String>,
+    /// Cassette to record responses to or replay them from
+    cassette: Option<RpcCassetteConfig>,
 }
 
 // === impl ProviderBuilder ===
@@ -114,6 +120,7 @@ impl ProviderBuilder {
             compute_units_per_second: ALCHEMY_FREE_TIER_CUPS,
             jwt: None,
             headers: vec![],
+            cassette: None,
         }
     }
 
@@ -203,6 +210,12 @@ impl ProviderBuilder {
         self
     }
 
+    /// Records responses to, or replays them from, the endpoint's cassette in the given directory
+    pub fn cassette(mut self, cassette: Option<RpcCassetteConfig>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
     /// Same as [`Self:build()`] but also retrieves the `chainId` in order to derive an appropriate
     /// interval.
     pub async fn connect(self) -> Result<RetryProvider> {
@@ -227,8 +240,13 @@ impl ProviderBuilder {
             compute_units_per_second,
             jwt,
             headers,
+            cassette,
         } = self;
         let url = url?;
+        let cassette = cassette
+            .map(|cassette| Cassette::open(&cassette, &url))
+            .transpose()
+            .wrap_err("failed to open RPC cassette")?;
 
         let retry_layer = RetryBackoffLayer::new(
             max_retry,
@@ -240,6 +258,7 @@ impl ProviderBuilder {
             .with_timeout(timeout)
             .with_headers(headers)
             .with_jwt(jwt)
+            .with_cassette(cassette)
             .build();
         let client = ClientBuilder::default().layer(retry_layer).transport(transport, false);
 
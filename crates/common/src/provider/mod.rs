@@ -1,7 +1,9 @@
 //! Provider-related instantiation and usage utilities.
 
 pub mod alloy;
+pub mod cassette;
 pub mod ethers;
 pub mod retry;
 pub mod runtime_transport;
+pub mod state_dump;
 pub mod tower;
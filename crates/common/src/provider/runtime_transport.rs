@@ -1,5 +1,9 @@
 //! Runtime transport that connects on first request, which can take either of an HTTP,
-//! WebSocket, or IPC transport and supports retries based on CUPS logic.
+//! WebSocket, or IPC transport, or a state dump file, and supports retries based on CUPS logic.
+use super::{
+    cassette::Cassette,
+    state_dump::{is_state_dump, StateDump, StateDumpTransport},
+};
 use crate::REQUEST_TIMEOUT;
 use alloy_json_rpc::{RequestPacket, ResponsePacket};
 use alloy_pubsub::{PubSubConnect, PubSubFrontend};
@@ -11,7 +15,11 @@ use alloy_transport_ipc::IpcConnect;
 use alloy_transport_ws::WsConnect;
 use ethers_providers::{JwtAuth, JwtKey};
 use reqwest::header::{HeaderName, HeaderValue};
-use std::{path::PathBuf, str::FromStr, sync::Arc};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 use thiserror::Error;
 use tokio::sync::RwLock;
 use tower::Service;
@@ -28,6 +36,8 @@ pub enum InnerTransport {
     // TODO: IPC
     /// IPC transport
     Ipc(PubSubFrontend),
+    /// State dump file
+    StateDump(StateDumpTransport),
 }
 
 /// Error type for the runtime transport.
@@ -60,6 +70,10 @@ pub enum RuntimeTransportError {
     /// Invalid JWT
     #[error("Invalid JWT: {0}")]
     InvalidJwt(String),
+
+    /// Invalid state dump file
+    #[error("Invalid state dump: {0}")]
+    BadStateDump(String),
 }
 
 /// A runtime transport is a custom [alloy_transport::Transport] that only connects when the *first*
@@ -78,6 +92,8 @@ pub struct RuntimeTransport {
     jwt: Option<String>,
     /// The timeout for requests.
     timeout: std::time::Duration,
+    /// The cassette to record responses to or replay them from.
+    cassette: Option<Arc<Cassette>>,
 }
 
 /// A builder for [RuntimeTransport].
@@ -87,12 +103,13 @@ pub struct RuntimeTransportBuilder {
     headers: Vec<String>,
     jwt: Option<String>,
     timeout: std::time::Duration,
+    cassette: Option<Arc<Cassette>>,
 }
 
 impl RuntimeTransportBuilder {
     /// Create a new builder with the given URL.
     pub fn new(url: Url) -> Self {
-        Self { url, headers: vec![], jwt: None, timeout: REQUEST_TIMEOUT }
+        Self { url, headers: vec![], jwt: None, timeout: REQUEST_TIMEOUT, cassette: None }
     }
 
     /// Set the URL for the transport.
@@ -113,6 +130,12 @@ impl RuntimeTransportBuilder {
         self
     }
 
+    /// Set the cassette to record responses to or replay them from.
+    pub fn with_cassette(mut self, cassette: Option<Arc<Cassette>>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
     /// Builds the [RuntimeTransport] and returns it in a disconnected state.
     /// The runtime transport will then connect when the first request happens.
     pub fn build(self) -> RuntimeTransport {
@@ -122,6 +145,7 @@ impl RuntimeTransportBuilder {
             headers: self.headers,
             jwt: self.jwt,
             timeout: self.timeout,
+            cassette: self.cassette,
         }
     }
 }
@@ -138,7 +162,15 @@ impl RuntimeTransport {
         match self.url.scheme() {
             "http" | "https" => self.connect_http().await,
             "ws" | "wss" => self.connect_ws().await,
-            "file" => self.connect_ipc().await,
+            "file" => {
+                let path = url_to_file_path(&self.url)
+                    .map_err(|_| RuntimeTransportError::BadPath(self.url.to_string()))?;
+                if is_state_dump(&path) {
+                    self.connect_state_dump(&path)
+                } else {
+                    self.connect_ipc().await
+                }
+            }
             _ => Err(RuntimeTransportError::BadScheme(self.url.scheme().to_string())),
         }
     }
@@ -201,6 +233,13 @@ impl RuntimeTransport {
         Ok(InnerTransport::Ipc(ipc))
     }
 
+    /// Loads a state dump file to serve requests from.
+    fn connect_state_dump(&self, path: &Path) -> Result<InnerTransport, RuntimeTransportError> {
+        let state = StateDump::load(path)
+            .map_err(|e| RuntimeTransportError::BadStateDump(e.to_string()))?;
+        Ok(InnerTransport::StateDump(StateDumpTransport::new(state)))
+    }
+
     /// Sends a request using the underlying transport.
     /// If this is the first request, it will connect to the appropriate transport depending on the
     /// URL scheme. When sending the request, retries will be automatically handled depending
@@ -208,9 +247,18 @@ impl RuntimeTransport {
     /// For sending the actual request, this action is delegated down to the
     /// underlying transport through Tower's [tower::Service::call]. See tower's [tower::Service]
     /// trait for more information.
+    ///
+    /// With a cassette in replay mode the request is answered from it without connecting, and in
+    /// record mode the successful responses are recorded to it.
     pub fn request(&self, req: RequestPacket) -> TransportFut<'static> {
         let this = self.clone();
         Box::pin(async move {
+            let cassette = match this.cassette {
+                Some(ref cassette) if cassette.is_replay() => return cassette.replay(&req),
+                Some(ref cassette) => Some((Arc::clone(cassette), req.clone())),
+                None => None,
+            };
+
             if this.inner.read().await.is_none() {
                 let mut inner = this.inner.write().await;
                 *inner = Some(this.connect().await.map_err(TransportErrorKind::custom)?)
@@ -220,12 +268,18 @@ impl RuntimeTransport {
             // SAFETY: We just checked that the inner transport exists.
             let inner_mut = inner.as_mut().expect("We should have an inner transport.");
 
-            match inner_mut {
+            let resp = match inner_mut {
                 InnerTransport::Http(http) => http.call(req),
                 InnerTransport::Ws(ws) => ws.call(req),
                 InnerTransport::Ipc(ipc) => ipc.call(req),
+                InnerTransport::StateDump(dump) => dump.call(req),
             }
-            .await
+            .await?;
+
+            if let Some((cassette, req)) = cassette {
+                cassette.record(&req, &resp);
+            }
+            Ok(resp)
         })
     }
 
@@ -0,0 +1,333 @@
+//! An in-process transport that serves a state dump file as if it were a node, so that it can be
+//! forked without a network connection.
+
+use crate::{errors::FsPathError, fs::read_json_file, DEV_CHAIN_ID};
+use alloy_json_rpc::{RequestPacket, ResponsePacket};
+use alloy_primitives::{keccak256, Address, Bloom, Bytes, B256, B64, U256, U64};
+use alloy_transport::{TransportErrorKind, TransportFut};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+/// The gas limit of the block of state dumps that don't include one.
+const DEFAULT_GAS_LIMIT: u64 = 30_000_000;
+
+/// Returns true if the file at `path` is a JSON state dump, as opposed to e.g. an IPC socket.
+pub fn is_state_dump(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "json")
+}
+
+/// A transport that answers the JSON-RPC requests needed for forking from a [`StateDump`].
+///
+/// Accounts and storage slots missing from the dump are empty, and every block up to the dumped
+/// one exists, with its hash derived from its number.
+#[derive(Clone, Debug)]
+pub struct StateDumpTransport {
+    state: Arc<StateDump>,
+}
+
+impl StateDumpTransport {
+    /// Creates a new transport serving the given state.
+    pub fn new(state: StateDump) -> Self {
+        Self { state: Arc::new(state) }
+    }
+
+    /// Answers the request from the state dump.
+    pub fn request(&self, req: RequestPacket) -> TransportFut<'static> {
+        let state = Arc::clone(&self.state);
+        Box::pin(async move {
+            let response = match serde_json::to_value(&req).map_err(TransportErrorKind::custom)? {
+                Value::Array(requests) => {
+                    Value::Array(requests.iter().map(|request| state.respond(request)).collect())
+                }
+                request => state.respond(&request),
+            };
+            serde_json::from_value::<ResponsePacket>(response).map_err(TransportErrorKind::custom)
+        })
+    }
+}
+
+impl tower::Service<RequestPacket> for StateDumpTransport {
+    type Response = ResponsePacket;
+    type Error = alloy_transport::TransportError;
+    type Future = TransportFut<'static>;
+
+    #[inline]
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    #[inline]
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        self.request(req)
+    }
+}
+
+/// The state of a chain at a single block, as written by `anvil --dump-state` or `vm.dumpState`.
+#[derive(Debug, Default)]
+pub struct StateDump {
+    block: DumpBlock,
+    accounts: BTreeMap<Address, DumpAccount>,
+}
+
+impl StateDump {
+    /// Loads the state dump at `path`.
+    pub fn load(path: &Path) -> Result<Self, FsPathError> {
+        Ok(match read_json_file::<StateDumpFile>(path)? {
+            StateDumpFile::Anvil { block, accounts } => {
+                Self { block: block.unwrap_or_default(), accounts }
+            }
+            StateDumpFile::Forge(accounts) => Self { block: Default::default(), accounts },
+        })
+    }
+
+    /// Returns the JSON-RPC response to the given request.
+    fn respond(&self, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or(Value::Null);
+        let method = request.get("method").and_then(Value::as_str).unwrap_or_default();
+        let params = request.get("params").and_then(Value::as_array).map_or(&[][..], Vec::as_slice);
+        match self.call(method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(message) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32601, "message": message },
+            }),
+        }
+    }
+
+    fn call(&self, method: &str, params: &[Value]) -> Result<Value, String> {
+        let account = |index| -> Result<Option<&DumpAccount>, String> {
+            Ok(self.accounts.get(&param::<Address>(params, index)?))
+        };
+        let result = match method {
+            "eth_chainId" => json!(U64::from(DEV_CHAIN_ID)),
+            "eth_blockNumber" => json!(U64::from(self.block_number())),
+            "eth_gasPrice" => json!(self.block.basefee),
+            "eth_getBalance" => {
+                json!(account(0)?.map(|account| account.balance).unwrap_or_default())
+            }
+            "eth_getTransactionCount" => {
+                json!(U64::from(account(0)?.map(DumpAccount::nonce).unwrap_or_default()))
+            }
+            "eth_getCode" => {
+                json!(account(0)?.and_then(|account| account.code.clone()).unwrap_or_default())
+            }
+            "eth_getStorageAt" => {
+                let slot = param::<U256>(params, 1)?;
+                let value = account(0)?
+                    .and_then(|account| account.storage.as_ref()?.get(&slot).copied())
+                    .unwrap_or_default();
+                json!(value)
+            }
+            "eth_getBlockByNumber" => {
+                let number = match params.first().and_then(Value::as_str) {
+                    Some("earliest") => 0,
+                    Some("latest" | "pending" | "safe" | "finalized") | None => self.block_number(),
+                    Some(_) => param::<U64>(params, 0)?.to::<u64>(),
+                };
+                if number <= self.block_number() {
+                    self.block_json(number)
+                } else {
+                    Value::Null
+                }
+            }
+            _ => return Err(format!("method `{method}` is not supported by state dumps")),
+        };
+        Ok(result)
+    }
+
+    fn block_number(&self) -> u64 {
+        self.block.number.saturating_to()
+    }
+
+    fn block_json(&self, number: u64) -> Value {
+        let block = &self.block;
+        json!({
+            "hash": block_hash(number),
+            "parentHash": number.checked_sub(1).map(block_hash).unwrap_or_default(),
+            "sha3Uncles": B256::ZERO,
+            "miner": block.coinbase,
+            "stateRoot": B256::ZERO,
+            "transactionsRoot": B256::ZERO,
+            "receiptsRoot": B256::ZERO,
+            "logsBloom": Bloom::ZERO,
+            "difficulty": block.difficulty,
+            "number": U64::from(number),
+            "gasLimit": block.gas_limit,
+            "gasUsed": U256::ZERO,
+            "timestamp": block.timestamp,
+            "extraData": Bytes::new(),
+            "mixHash": block.prevrandao.unwrap_or_default(),
+            "nonce": B64::ZERO,
+            "baseFeePerGas": block.basefee,
+            "totalDifficulty": U256::ZERO,
+            "uncles": [],
+            "transactions": [],
+            "size": U256::ZERO,
+        })
+    }
+}
+
+/// Returns the hash of the block with the given number, like revm's `EmptyDB`.
+fn block_hash(number: u64) -> B256 {
+    keccak256(number.to_string())
+}
+
+fn param<T: serde::de::DeserializeOwned>(params: &[Value], index: usize) -> Result<T, String> {
+    let param = params.get(index).ok_or_else(|| format!("missing parameter {index}"))?;
+    serde_json::from_value(param.clone()).map_err(|err| format!("invalid parameter {index}: {err}"))
+}
+
+/// The formats of state dump files.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StateDumpFile {
+    /// Written by `anvil --dump-state`.
+    Anvil { block: Option<DumpBlock>, accounts: BTreeMap<Address, DumpAccount> },
+    /// Written by `vm.dumpState`.
+    Forge(BTreeMap<Address, DumpAccount>),
+}
+
+/// The block environment of an anvil state dump.
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct DumpBlock {
+    number: U256,
+    coinbase: Address,
+    timestamp: U256,
+    gas_limit: U256,
+    basefee: U256,
+    difficulty: U256,
+    prevrandao: Option<B256>,
+}
+
+impl Default for DumpBlock {
+    fn default() -> Self {
+        Self {
+            number: U256::ZERO,
+            coinbase: Address::ZERO,
+            timestamp: U256::ZERO,
+            gas_limit: U256::from(DEFAULT_GAS_LIMIT),
+            basefee: U256::ZERO,
+            difficulty: U256::ZERO,
+            prevrandao: None,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct DumpAccount {
+    nonce: Option<DumpNonce>,
+    balance: U256,
+    code: Option<Bytes>,
+    storage: Option<BTreeMap<U256, U256>>,
+}
+
+impl DumpAccount {
+    fn nonce(&self) -> u64 {
+        match self.nonce {
+            Some(DumpNonce::Number(nonce)) => nonce,
+            Some(DumpNonce::Quantity(nonce)) => nonce.to(),
+            None => 0,
+        }
+    }
+}
+
+/// Anvil writes nonces as numbers, `vm.dumpState` as hex quantities.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DumpNonce {
+    Number(u64),
+    Quantity(U64),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(json: Value) -> StateDump {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("state.json");
+        std::fs::write(&path, json.to_string()).unwrap();
+        StateDump::load(&path).unwrap()
+    }
+
+    fn call(state: &StateDump, method: &str, params: Value) -> Value {
+        state.call(method, params.as_array().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn serves_anvil_dump() {
+        let state = dump(json!({
+            "block": { "number": "0x10", "timestamp": "0x64", "gas_limit": "0x1c9c380" },
+            "accounts": {
+                "0x0000000000000000000000000000000000001234": {
+                    "nonce": 2,
+                    "balance": "0xde0b6b3a7640000",
+                    "code": "0x6000",
+                    "storage": { "0x1": "0x2a" }
+                }
+            }
+        }));
+        let account = "0x0000000000000000000000000000000000001234";
+
+        assert_eq!(call(&state, "eth_blockNumber", json!([])), json!("0x10"));
+        assert_eq!(
+            call(&state, "eth_getTransactionCount", json!([account, "latest"])),
+            json!("0x2")
+        );
+        assert_eq!(
+            call(&state, "eth_getBalance", json!([account, "latest"])),
+            json!("0xde0b6b3a7640000")
+        );
+        assert_eq!(call(&state, "eth_getCode", json!([account, "latest"])), json!("0x6000"));
+        assert_eq!(
+            call(&state, "eth_getStorageAt", json!([account, "0x1", "latest"])),
+            json!("0x2a")
+        );
+        assert_eq!(
+            call(&state, "eth_getStorageAt", json!([account, "0x2", "latest"])),
+            json!("0x0")
+        );
+
+        let block = call(&state, "eth_getBlockByNumber", json!(["latest", false]));
+        assert_eq!(block["number"], json!("0x10"));
+        assert_eq!(block["timestamp"], json!("0x64"));
+        assert_eq!(call(&state, "eth_getBlockByNumber", json!(["0x11", false])), Value::Null);
+        assert!(state.call("eth_sendRawTransaction", &[]).is_err());
+    }
+
+    #[test]
+    fn serves_forge_dump() {
+        let state = dump(json!({
+            "0x0000000000000000000000000000000000001234": {
+                "nonce": "0x1",
+                "balance": "0x1",
+                "code": null,
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x000000000000000000000000000000000000000000000000000000000000002a"
+                }
+            }
+        }));
+        let account = "0x0000000000000000000000000000000000001234";
+
+        assert_eq!(call(&state, "eth_blockNumber", json!([])), json!("0x0"));
+        assert_eq!(
+            call(&state, "eth_getTransactionCount", json!([account, "latest"])),
+            json!("0x1")
+        );
+        assert_eq!(call(&state, "eth_getCode", json!([account, "latest"])), json!("0x"));
+        assert_eq!(
+            call(&state, "eth_getStorageAt", json!([account, "0x1", "latest"])),
+            json!("0x2a")
+        );
+        let block = call(&state, "eth_getBlockByNumber", json!(["0x0", false]));
+        assert_eq!(block["gasLimit"], json!("0x1c9c380"));
+    }
+}
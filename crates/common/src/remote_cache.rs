@@ -0,0 +1,335 @@
+//! A remote cache of compiled artifacts, shared between machines.
+
+use crate::fs;
+use alloy_primitives::{keccak256, B256};
+use eyre::{Context, Result};
+use foundry_compilers::{utils::source_files_iter, Graph, Project};
+use foundry_config::RemoteCacheConfig;
+use reqwest::{
+    header::{HeaderMap, HeaderValue, AUTHORIZATION},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+/// The environment variable of the bearer token sent to the remote cache, if any.
+pub const REMOTE_CACHE_TOKEN_ENV: &str = "FOUNDRY_REMOTE_CACHE_TOKEN";
+
+/// The timeout of requests to the remote cache, which can transfer large bundles.
+const REQ_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A client of a remote cache storing the compiled artifacts of projects, addressed by the hash
+/// of their compiler input.
+///
+/// The cache is a plain HTTP backend accessed with `GET`, `HEAD` and `PUT` requests of
+/// `<url>/<input hash>.json`, authenticated with the bearer token of
+/// [`REMOTE_CACHE_TOKEN_ENV`] if set. Requests aren't signed, so object stores such as S3 must be
+/// fronted by a server or gateway accepting them.
+#[derive(Clone, Debug)]
+pub struct RemoteCache {
+    client: reqwest::Client,
+    url: String,
+    read_only: bool,
+}
+
+/// The artifacts and the cache file of a compiled project, by their paths relative to the
+/// project root.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Bundle {
+    files: BTreeMap<PathBuf, String>,
+}
+
+impl RemoteCache {
+    /// Creates a client of the configured remote cache, or returns `None` if none is configured.
+    pub fn new(config: &RemoteCacheConfig) -> Result<Option<Self>> {
+        let Some(url) = &config.url else { return Ok(None) };
+        eyre::ensure!(
+            url.starts_with("http://") || url.starts_with("https://"),
+            "Invalid remote cache URL {url}: only HTTP(S) URLs are supported"
+        );
+
+        let mut headers = HeaderMap::new();
+        if let Ok(token) = std::env::var(REMOTE_CACHE_TOKEN_ENV) {
+            let value = HeaderValue::from_str(&format!("Bearer {token}"))
+                .wrap_err_with(|| format!("Invalid {REMOTE_CACHE_TOKEN_ENV}"))?;
+            headers.insert(AUTHORIZATION, value);
+        }
+        let client =
+            reqwest::Client::builder().default_headers(headers).timeout(REQ_TIMEOUT).build()?;
+
+        Ok(Some(Self {
+            client,
+            url: url.trim_end_matches('/').to_string(),
+            read_only: config.read_only,
+        }))
+    }
+
+    /// Returns the hash of the project's compiler input: the contents of its sources, tests,
+    /// scripts and libraries, its remappings, the compiler settings and the compiler version
+    /// resolved for each file.
+    ///
+    /// With auto-detection, the versions are resolved from the files' pragmas, which can install
+    /// the matching compilers.
+    pub fn input_hash(project: &Project) -> Result<B256> {
+        let paths = &project.paths;
+        let mut files = BTreeMap::new();
+        for dir in
+            [&paths.sources, &paths.tests, &paths.scripts].into_iter().chain(&paths.libraries)
+        {
+            for file in source_files_iter(dir) {
+                let hash = keccak256(fs::read(&file)?);
+                files.insert(relative(&file, &paths.root), hash);
+            }
+        }
+        let solc = if project.auto_detect {
+            let (versions, _) = Graph::resolve(paths)?.into_sources_by_version(project.offline)?;
+            versions
+                .into_values()
+                .flat_map(|(version, sources)| {
+                    sources
+                        .into_keys()
+                        .map(move |file| (relative(&file, &paths.root), version.clone()))
+                })
+                .collect::<BTreeMap<_, _>>()
+        } else {
+            let version = project.solc.version()?;
+            files.keys().map(|file| (file.clone(), version.clone())).collect()
+        };
+        let remappings = paths.remappings.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+        let input = serde_json::json!({
+            "files": files,
+            "remappings": remappings,
+            "settings": project.solc_config.settings,
+            "solc": solc,
+        });
+        Ok(keccak256(serde_json::to_vec(&input)?))
+    }
+
+    /// Downloads the artifacts compiled from the input with the hash, and writes them to the
+    /// project.
+    ///
+    /// Returns `false` if the cache doesn't contain them.
+    pub async fn pull(&self, project: &Project, input_hash: B256) -> Result<bool> {
+        let response = self.client.get(self.bundle_url(input_hash)).send().await?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(false)
+        }
+        let bytes = response.error_for_status()?.bytes().await?;
+        let bundle: Bundle =
+            serde_json::from_slice(&bytes).wrap_err("Failed to parse the remote cache bundle")?;
+
+        // only artifacts and the cache file are written, so that a bad cache can't overwrite the
+        // project's sources or config
+        let paths = &project.paths;
+        let mut files = Vec::with_capacity(bundle.files.len());
+        for (path, contents) in bundle.files {
+            let is_relative =
+                path.components().all(|component| matches!(component, Component::Normal(_)));
+            let path = paths.root.join(path);
+            eyre::ensure!(
+                is_relative && (path.starts_with(&paths.artifacts) || path == paths.cache),
+                "Invalid path in the remote cache bundle: {}",
+                path.display()
+            );
+            files.push((path, contents));
+        }
+        for (path, contents) in files {
+            fs::create_dir_all(path.parent().unwrap())?;
+            fs::write(path, contents)?;
+        }
+        Ok(true)
+    }
+
+    /// Uploads the project's artifacts and cache file for the input with the hash, unless the
+    /// cache is read-only or already contains them.
+    ///
+    /// Returns `true` if the artifacts were uploaded.
+    pub async fn push(&self, project: &Project, input_hash: B256) -> Result<bool> {
+        if self.read_only {
+            return Ok(false)
+        }
+        let url = self.bundle_url(input_hash);
+        if self.client.head(&url).send().await?.status().is_success() {
+            return Ok(false)
+        }
+
+        let root = &project.paths.root;
+        let mut bundle = Bundle::default();
+        let artifacts = fs::json_files(&project.paths.artifacts);
+        for path in artifacts.iter().chain([&project.paths.cache]) {
+            bundle
+                .files
+                .insert(relative_path(path, root)?.to_path_buf(), fs::read_to_string(path)?);
+        }
+
+        self.client
+            .put(url)
+            .body(serde_json::to_vec(&bundle)?)
+            .send()
+            .await?
+            .error_for_status()
+            .wrap_err("Failed to upload the artifacts to the remote cache")?;
+        Ok(true)
+    }
+
+    fn bundle_url(&self, input_hash: B256) -> String {
+        format!("{}/{input_hash:x}.json", self.url)
+    }
+}
+
+fn relative(path: &Path, root: &Path) -> PathBuf {
+    path.strip_prefix(root).unwrap_or(path).to_path_buf()
+}
+
+fn relative_path<'a>(path: &'a Path, root: &Path) -> Result<&'a Path> {
+    path.strip_prefix(root).wrap_err_with(|| {
+        format!("{} is outside of the project root and can't be cached", path.display())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_compilers::ProjectPathsConfig;
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+    };
+
+    type Store = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+    /// Serves `GET`, `HEAD` and `PUT` requests of the files in the store, and returns the URL of
+    /// the server.
+    fn serve(store: Store) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream);
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let mut parts = request_line.split_whitespace();
+                let (method, path) = (parts.next().unwrap(), parts.next().unwrap().to_string());
+
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.trim().parse().unwrap();
+                        }
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).unwrap();
+
+                let mut store = store.lock().unwrap();
+                let (status, body) = match method {
+                    "PUT" => {
+                        store.insert(path, body);
+                        ("200 OK", Vec::new())
+                    }
+                    _ => match store.get(&path) {
+                        Some(body) if method == "GET" => ("200 OK", body.clone()),
+                        Some(_) => ("200 OK", Vec::new()),
+                        None => ("404 Not Found", Vec::new()),
+                    },
+                };
+                let mut stream = reader.into_inner();
+                let head = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&body).unwrap();
+            }
+        });
+        format!("http://{addr}/cache")
+    }
+
+    fn temp_project(root: &Path) -> Project {
+        let paths = ProjectPathsConfig::builder().root(root).build().unwrap();
+        Project::builder().paths(paths).build().unwrap()
+    }
+
+    fn config(url: String) -> RemoteCacheConfig {
+        RemoteCacheConfig { url: Some(url), read_only: false }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pushes_and_pulls_artifacts() {
+        let store = Store::default();
+        let cache = RemoteCache::new(&config(serve(store.clone()))).unwrap().unwrap();
+        let hash = B256::repeat_byte(1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let project = temp_project(dir.path());
+        let artifact = project.paths.artifacts.join("Counter.sol/Counter.json");
+        fs::create_dir_all(artifact.parent().unwrap()).unwrap();
+        fs::write(&artifact, r#"{"abi":[]}"#).unwrap();
+        fs::create_dir_all(project.paths.cache.parent().unwrap()).unwrap();
+        fs::write(&project.paths.cache, r#"{"files":{}}"#).unwrap();
+
+        assert!(!cache.pull(&project, hash).await.unwrap());
+        assert!(cache.push(&project, hash).await.unwrap());
+        // the cache already contains the artifacts
+        assert!(!cache.push(&project, hash).await.unwrap());
+
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = temp_project(other_dir.path());
+        assert!(cache.pull(&other, hash).await.unwrap());
+        assert_eq!(
+            fs::read_to_string(other.paths.artifacts.join("Counter.sol/Counter.json")).unwrap(),
+            r#"{"abi":[]}"#
+        );
+        assert_eq!(fs::read_to_string(&other.paths.cache).unwrap(), r#"{"files":{}}"#);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejects_files_outside_of_the_artifacts() {
+        let store = Store::default();
+        let cache = RemoteCache::new(&config(serve(store.clone()))).unwrap().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let project = temp_project(dir.path());
+
+        for (i, path) in
+            ["src/Counter.sol", "foundry.toml", "../Counter.sol"].into_iter().enumerate()
+        {
+            let hash = B256::repeat_byte(i as u8);
+            let bundle = Bundle { files: BTreeMap::from([(PathBuf::from(path), String::new())]) };
+            store
+                .lock()
+                .unwrap()
+                .insert(format!("/cache/{hash:x}.json"), serde_json::to_vec(&bundle).unwrap());
+
+            let err = cache.pull(&project, hash).await.unwrap_err().to_string();
+            assert!(err.contains("Invalid path in the remote cache bundle"), "{err}");
+            assert!(!dir.path().join(path).exists());
+        }
+    }
+
+    #[test]
+    fn rejects_non_http_urls() {
+        let err = RemoteCache::new(&config("s3://artifacts/foundry".to_string())).unwrap_err();
+        assert!(err.to_string().contains("only HTTP(S) URLs are supported"), "{err}");
+        let cache = RemoteCache::new(&config("https://cache.example.com/foundry/".to_string()))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            cache.bundle_url(B256::ZERO),
+            format!("https://cache.example.com/foundry/{:x}.json", B256::ZERO)
+        );
+        assert!(RemoteCache::new(&RemoteCacheConfig::default()).unwrap().is_none());
+    }
+}
@@ -15,6 +15,13 @@ pub trait TestFilter: Send + Sync {
 
     /// Returns a contract with the given path should be included.
     fn matches_path(&self, path: &Path) -> bool;
+
+    /// Returns whether the test with the given signature of the contract with the given
+    /// identifier, `<path>:<name>`, should be included, for filters of specific tests of
+    /// specific contracts.
+    fn matches_contract_test(&self, _contract_id: &str, _test_signature: &str) -> bool {
+        true
+    }
 }
 
 /// Extension trait for `Function`.
@@ -353,3 +353,22 @@ impl ToEthers for Bytes {
         EthersBytes(self.0)
     }
 }
+
+impl ToEthers for AccessList {
+    type To = EthersAccessList;
+
+    fn to_ethers(self) -> Self::To {
+        EthersAccessList(self.0.into_iter().map(ToEthers::to_ethers).collect())
+    }
+}
+
+impl ToEthers for AccessListItem {
+    type To = EthersAccessListItem;
+
+    fn to_ethers(self) -> Self::To {
+        EthersAccessListItem {
+            address: self.address.to_ethers(),
+            storage_keys: self.storage_keys.into_iter().map(ToEthers::to_ethers).collect(),
+        }
+    }
+}
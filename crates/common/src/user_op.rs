@@ -0,0 +1,255 @@
+//! ERC-4337 user operations, and a client of the bundlers submitting them.
+
+use crate::{
+    provider::ethers::{estimate_eip1559_fees, try_get_http_provider, RetryProvider},
+    types::{ToAlloy, ToEthers},
+};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionRequest};
+use ethers_providers::Middleware;
+use ethers_signers::Signer;
+use eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// The address of the v0.6 EntryPoint contract.
+// https://etherscan.io/address/0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789#code
+pub const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+
+/// The signature of the method of smart accounts making a call, which takes the destination,
+/// value and calldata.
+pub const DEFAULT_EXECUTE_SIG: &str = "execute(address,uint256,bytes)";
+
+/// A signature of the right length and shape for the account's validation to run through when
+/// the bundler estimates the gas of a user operation.
+pub const DUMMY_SIGNATURE: &str = "0xfffffffffffffffffffffffffffffff0000000000000000000000000000000007aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa1c";
+
+/// How often the bundler is polled for the receipt of a user operation.
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// An ERC-4337 user operation, as handled by the v0.6 EntryPoint.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The smart account sending the operation
+    pub sender: Address,
+    /// The nonce of the account in the EntryPoint
+    pub nonce: U256,
+    /// The factory address followed by the calldata deploying the account, if it doesn't exist
+    pub init_code: Bytes,
+    /// The calldata of the call to the account
+    pub call_data: Bytes,
+    /// The gas limit of the call to the account
+    pub call_gas_limit: U256,
+    /// The gas limit of the account's validation
+    pub verification_gas_limit: U256,
+    /// The gas paid to the bundler on top of the execution
+    pub pre_verification_gas: U256,
+    /// The maximum fee per gas
+    pub max_fee_per_gas: U256,
+    /// The maximum priority fee per gas
+    pub max_priority_fee_per_gas: U256,
+    /// The paymaster address followed by the data it expects, if the operation is sponsored
+    pub paymaster_and_data: Bytes,
+    /// The signature of the account's owner
+    pub signature: Bytes,
+}
+
+/// The gas limits of a user operation, as estimated by `eth_estimateUserOperationGas`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGas {
+    /// The gas paid to the bundler on top of the execution
+    pub pre_verification_gas: U256,
+    /// The gas limit of the account's validation
+    pub verification_gas_limit: U256,
+    /// The gas limit of the call to the account
+    pub call_gas_limit: U256,
+}
+
+impl UserOperation {
+    /// Creates the unsigned user operation of the account with its next nonce in the EntryPoint
+    /// and the chain's current fees.
+    ///
+    /// Its signature is a dummy one, for the bundler to estimate its gas.
+    pub async fn new(
+        provider: &RetryProvider,
+        entry_point: Address,
+        sender: Address,
+        call_data: Bytes,
+    ) -> Result<Self> {
+        let chain_id = provider.get_chainid().await?.as_u64();
+
+        let get_nonce = Function::parse("getNonce(address,uint192)")?;
+        let tx = TransactionRequest::new()
+            .to(entry_point.to_ethers())
+            .data(get_nonce.abi_encode_input(&[sender.into(), U256::ZERO.into()])?);
+        let nonce = provider
+            .call(&TypedTransaction::Legacy(tx), None)
+            .await
+            .wrap_err("Failed to get the account's nonce from the EntryPoint")?;
+        eyre::ensure!(nonce.len() >= 32, "Invalid nonce returned by the EntryPoint: {nonce}");
+
+        let (max_fee_per_gas, max_priority_fee_per_gas) =
+            estimate_eip1559_fees(provider, Some(chain_id)).await?;
+
+        Ok(Self {
+            sender,
+            nonce: U256::from_be_slice(&nonce[..32]),
+            call_data,
+            max_fee_per_gas: max_fee_per_gas.to_alloy(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.to_alloy(),
+            signature: DUMMY_SIGNATURE.parse()?,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the hash signed by the account's owner, which commits to everything but the
+    /// signature, the EntryPoint and the chain.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = DynSolValue::Tuple(vec![
+            self.sender.into(),
+            self.nonce.into(),
+            keccak256(&self.init_code).into(),
+            keccak256(&self.call_data).into(),
+            self.call_gas_limit.into(),
+            self.verification_gas_limit.into(),
+            self.pre_verification_gas.into(),
+            self.max_fee_per_gas.into(),
+            self.max_priority_fee_per_gas.into(),
+            keccak256(&self.paymaster_and_data).into(),
+        ])
+        .abi_encode_params();
+        let encoded = DynSolValue::Tuple(vec![
+            keccak256(packed).into(),
+            entry_point.into(),
+            U256::from(chain_id).into(),
+        ])
+        .abi_encode_params();
+        keccak256(encoded)
+    }
+}
+
+/// Returns the calldata of the account's `execute_sig` method making a call to `to` with the
+/// value and calldata.
+pub fn encode_execute(execute_sig: &str, to: Address, value: U256, data: Bytes) -> Result<Bytes> {
+    let execute = Function::parse(execute_sig)?;
+    let call_data =
+        execute.abi_encode_input(&[to.into(), value.into(), DynSolValue::Bytes(data.into())])?;
+    Ok(call_data.into())
+}
+
+/// A client of a bundler submitting user operations to an EntryPoint.
+#[derive(Debug)]
+pub struct Bundler {
+    provider: RetryProvider,
+    entry_point: Address,
+}
+
+impl Bundler {
+    /// Creates a client of the bundler with the RPC endpoint, submitting to the EntryPoint.
+    pub fn new(url: &str, entry_point: Address) -> Result<Self> {
+        Ok(Self { provider: try_get_http_provider(url)?, entry_point })
+    }
+
+    /// Estimates the gas limits of the user operation.
+    pub async fn estimate_gas(&self, user_op: &UserOperation) -> Result<UserOperationGas> {
+        self.provider
+            .request("eth_estimateUserOperationGas", (user_op, self.entry_point))
+            .await
+            .wrap_err("Bundler failed to estimate the gas of the user operation")
+    }
+
+    /// Fills the gas limits of the user operation, signs it with the signer and submits it.
+    ///
+    /// Returns the hash of the user operation.
+    pub async fn send<S: Signer>(
+        &self,
+        mut user_op: UserOperation,
+        signer: &S,
+        chain_id: u64,
+    ) -> Result<B256>
+    where
+        S::Error: 'static,
+    {
+        let gas = self.estimate_gas(&user_op).await?;
+        user_op.pre_verification_gas = gas.pre_verification_gas;
+        user_op.verification_gas_limit = gas.verification_gas_limit;
+        user_op.call_gas_limit = gas.call_gas_limit;
+
+        let hash = user_op.hash(self.entry_point, chain_id);
+        user_op.signature = signer.sign_message(hash).await?.to_vec().into();
+
+        self.provider
+            .request("eth_sendUserOperation", (&user_op, self.entry_point))
+            .await
+            .wrap_err("Bundler rejected the user operation")
+    }
+
+    /// Polls the bundler for the receipt of the user operation until it's included or the
+    /// timeout expires.
+    pub async fn wait_for_receipt(
+        &self,
+        hash: B256,
+        timeout: Duration,
+    ) -> Result<serde_json::Value> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let receipt: Option<serde_json::Value> =
+                self.provider.request("eth_getUserOperationReceipt", [hash]).await?;
+            if let Some(receipt) = receipt {
+                return Ok(receipt)
+            }
+            if Instant::now() >= deadline {
+                eyre::bail!(
+                    "User operation {hash} was not included after {} seconds",
+                    timeout.as_secs()
+                )
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hashes_user_operations() {
+        let entry_point: Address = ENTRY_POINT_V06.parse().unwrap();
+        let user_op = UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(1),
+            call_data: Bytes::from_static(&[0x12, 0x34]),
+            signature: DUMMY_SIGNATURE.parse().unwrap(),
+            ..Default::default()
+        };
+        let hash = user_op.hash(entry_point, 1);
+
+        // The signature isn't part of the hash, while the chain and the EntryPoint are
+        let signed = UserOperation { signature: Bytes::from_static(&[1; 65]), ..user_op.clone() };
+        assert_eq!(signed.hash(entry_point, 1), hash);
+        assert_ne!(user_op.hash(entry_point, 10), hash);
+        assert_ne!(user_op.hash(Address::ZERO, 1), hash);
+
+        let json = serde_json::to_value(&user_op).unwrap();
+        assert_eq!(json["callData"], "0x1234");
+        assert_eq!(json["nonce"], "0x1");
+    }
+
+    #[test]
+    fn encodes_execute_calls() {
+        let call_data = encode_execute(
+            DEFAULT_EXECUTE_SIG,
+            Address::repeat_byte(0x22),
+            U256::from(5),
+            Bytes::from_static(&[0xab]),
+        )
+        .unwrap();
+        assert_eq!(call_data[..4], Function::parse(DEFAULT_EXECUTE_SIG).unwrap().selector()[..]);
+        assert_eq!(call_data[16..36], [0x22; 20]);
+    }
+}
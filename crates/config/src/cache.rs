@@ -3,7 +3,14 @@
 use crate::Chain;
 use number_prefix::NumberPrefix;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::{fmt, fmt::Formatter, str::FromStr};
+use std::{
+    fmt,
+    fmt::Formatter,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::SystemTime,
+};
 
 /// Settings to configure caching of remote
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -227,10 +234,117 @@ pub struct ChainCache {
     pub block_explorer: u64,
 }
 
+/// The cached fork database of a single block, stored in `~/.foundry/cache/rpc/<chain>/<block>`
+#[derive(Clone, Debug)]
+pub struct ForkCacheEntry {
+    /// The chain of the block
+    pub chain: Chain,
+    /// The number of the block
+    pub block: u64,
+    /// The directory of the block's cache
+    pub path: PathBuf,
+    /// The size of the cached data in bytes
+    pub size: u64,
+    /// When the cache was last used by a fork
+    pub last_used: SystemTime,
+    /// Whether the cache is protected from eviction and pruning
+    pub pinned: bool,
+}
+
+impl ForkCacheEntry {
+    /// The file in the block's cache directory that holds the cached data
+    pub const STORAGE_FILE: &'static str = "storage.json";
+    /// The marker file of pinned caches
+    const PIN_FILE: &'static str = ".pinned";
+    /// The marker file that is touched whenever the cache is used
+    const LAST_USED_FILE: &'static str = ".last-used";
+
+    /// Reads the entry of the block cache in `path`.
+    ///
+    /// Returns `None` if no data was cached for the block.
+    pub fn read(chain: Chain, block: u64, path: PathBuf) -> io::Result<Option<Self>> {
+        let storage = match fs::metadata(path.join(Self::STORAGE_FILE)) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let last_used = fs::metadata(path.join(Self::LAST_USED_FILE))
+            .and_then(|metadata| metadata.modified())
+            .into_iter()
+            .chain(storage.modified())
+            .max()
+            .unwrap_or(SystemTime::UNIX_EPOCH);
+        let pinned = path.join(Self::PIN_FILE).exists();
+        Ok(Some(Self { chain, block, size: storage.len(), last_used, pinned, path }))
+    }
+
+    /// Marks the block cache in `path` as used now, if it exists.
+    pub fn touch(path: &Path) -> io::Result<()> {
+        if path.is_dir() {
+            fs::write(path.join(Self::LAST_USED_FILE), "")?;
+        }
+        Ok(())
+    }
+
+    /// Protects the cache from eviction and pruning, or removes that protection.
+    pub fn set_pinned(&mut self, pinned: bool) -> io::Result<()> {
+        let pin = self.path.join(Self::PIN_FILE);
+        if pinned {
+            fs::write(pin, "")?;
+        } else if pin.exists() {
+            fs::remove_file(pin)?;
+        }
+        self.pinned = pinned;
+        Ok(())
+    }
+}
+
+impl fmt::Display for ForkCacheEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} block {} (", self.chain, self.block)?;
+        match NumberPrefix::decimal(self.size as f32) {
+            NumberPrefix::Standalone(size) => write!(f, "{size:.1} B")?,
+            NumberPrefix::Prefixed(prefix, size) => write!(f, "{size:.1} {prefix}B")?,
+        }
+        if self.pinned {
+            write!(f, ", pinned")?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Returns the unpinned entries that need to be evicted, least recently used first, so that the
+/// total size of all entries is at most `max_size` bytes.
+///
+/// The entry in `keep`, if any, is never evicted.
+pub fn lru_evictions<'a>(
+    entries: &'a [ForkCacheEntry],
+    max_size: u64,
+    keep: Option<&Path>,
+) -> Vec<&'a ForkCacheEntry> {
+    let mut total = entries.iter().map(|entry| entry.size).sum::<u64>();
+    let mut candidates = entries
+        .iter()
+        .filter(|entry| !entry.pinned && keep != Some(entry.path.as_path()))
+        .collect::<Vec<_>>();
+    candidates.sort_by_key(|entry| entry.last_used);
+
+    let mut evictions = Vec::new();
+    for entry in candidates {
+        if total <= max_size {
+            break
+        }
+        total -= entry.size;
+        evictions.push(entry);
+    }
+    evictions
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use pretty_assertions::assert_str_eq;
+    use std::time::Duration;
 
     #[test]
     fn can_parse_storage_config() {
@@ -309,4 +423,31 @@ mod tests {
                 - Block 2 (2.0 B)\n";
         assert_str_eq!(format!("{cache}"), expected);
     }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let entry = |block: u64, size: u64, used: u64, pinned: bool| ForkCacheEntry {
+            chain: Chain::mainnet(),
+            block,
+            path: PathBuf::from(block.to_string()),
+            size,
+            last_used: SystemTime::UNIX_EPOCH + Duration::from_secs(used),
+            pinned,
+        };
+        let entries = vec![
+            entry(1, 100, 30, false),
+            entry(2, 100, 10, true),
+            entry(3, 100, 20, false),
+            entry(4, 100, 0, false),
+        ];
+        let blocks = |evictions: Vec<&ForkCacheEntry>| {
+            evictions.into_iter().map(|entry| entry.block).collect::<Vec<_>>()
+        };
+
+        assert!(lru_evictions(&entries, 400, None).is_empty());
+        assert_eq!(blocks(lru_evictions(&entries, 250, None)), vec![4, 3]);
+        assert_eq!(blocks(lru_evictions(&entries, 250, Some(Path::new("4")))), vec![3, 1]);
+        // pinned entries are kept even if that exceeds the limit
+        assert_eq!(blocks(lru_evictions(&entries, 0, None)), vec![4, 3, 1]);
+    }
 }
@@ -0,0 +1,11 @@
+//! Configuration specific to the `forge coverage` command
+
+use serde::{Deserialize, Serialize};
+
+/// Contains the config for coverage analysis
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CoverageConfig {
+    /// Globs of source files to exclude from coverage, relative to the project root, e.g.
+    /// `src/vendored/**`.
+    pub exclude: Vec<String>,
+}
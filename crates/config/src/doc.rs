@@ -22,6 +22,17 @@ pub struct DocConfig {
     pub repository: Option<String>,
     /// Globs to ignore
     pub ignore: Vec<String>,
+    /// The version of the documented code.
+    ///
+    /// If set, the docs are written to `<out>/v<version>`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Path to a custom mdbook theme directory, e.g. containing an `index.hbs` template.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<PathBuf>,
+    /// Whether to compile the project to add the selectors and the storage layout of the
+    /// contracts to their docs.
+    pub artifacts: bool,
 }
 
 impl Default for DocConfig {
@@ -33,6 +44,9 @@ impl Default for DocConfig {
             title: String::default(),
             repository: None,
             ignore: Vec::default(),
+            version: None,
+            theme: None,
+            artifacts: false,
         }
     }
 }
@@ -0,0 +1,75 @@
+//! Configuration for checking the NatSpec documentation of the project's sources when building
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Contains the config for checking that the public API of the sources in `src` is documented
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocLintConfig {
+    /// How missing documentation is reported, off by default.
+    pub level: DocLintLevel,
+    /// Overrides of the level for the sources in some directories, e.g. to skip `src/mocks`.
+    pub overrides: Vec<DocLintOverride>,
+}
+
+impl DocLintConfig {
+    /// Returns the level for the source at `path`, relative to the project root.
+    ///
+    /// The override of the most specific directory containing the source applies, if any.
+    pub fn level_for(&self, path: &Path) -> DocLintLevel {
+        self.overrides
+            .iter()
+            .filter(|o| path.starts_with(&o.path))
+            .max_by_key(|o| o.path.components().count())
+            .map_or(self.level, |o| o.level)
+    }
+
+    /// Returns `true` if the documentation of any sources is checked.
+    pub fn is_enabled(&self) -> bool {
+        self.level != DocLintLevel::Off ||
+            self.overrides.iter().any(|o| o.level != DocLintLevel::Off)
+    }
+}
+
+/// How missing documentation is reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocLintLevel {
+    /// The documentation isn't checked.
+    #[default]
+    Off,
+    /// Missing documentation emits warnings.
+    Warn,
+    /// Missing documentation fails the build.
+    Deny,
+}
+
+/// The level of the sources in a directory.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocLintOverride {
+    /// The directory, relative to the project root
+    pub path: PathBuf,
+    pub level: DocLintLevel,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_most_specific_override() {
+        let config = DocLintConfig {
+            level: DocLintLevel::Deny,
+            overrides: vec![
+                DocLintOverride { path: "src/mocks/strict".into(), level: DocLintLevel::Warn },
+                DocLintOverride { path: "src/mocks".into(), level: DocLintLevel::Off },
+            ],
+        };
+        assert!(config.is_enabled());
+        assert_eq!(config.level_for(Path::new("src/Vault.sol")), DocLintLevel::Deny);
+        assert_eq!(config.level_for(Path::new("src/mocks/MockVault.sol")), DocLintLevel::Off);
+        assert_eq!(config.level_for(Path::new("src/mocks/strict/Mock.sol")), DocLintLevel::Warn);
+        assert_eq!(config.level_for(Path::new("src/mocksVault.sol")), DocLintLevel::Deny);
+        assert!(!DocLintConfig::default().is_enabled());
+    }
+}
@@ -33,6 +33,17 @@ pub struct FormatterConfig {
     pub contract_new_lines: bool,
     /// Sort import statements alphabetically in groups (a group is separated by a newline).
     pub sort_imports: bool,
+    /// Group import statements by their origin: `forge-std`, other libraries and local sources.
+    /// The groups are separated by a newline and sorted alphabetically.
+    pub group_imports: bool,
+    /// Maximum line length of named import lists, after which the list is split into one symbol
+    /// per line. Defaults to `line_length`.
+    pub import_line_length: Option<usize>,
+    /// Remove named and aliased imports which aren't used in the file. Files without contracts,
+    /// interfaces or libraries are skipped, since they may only re-export their imports.
+    pub remove_unused_imports: bool,
+    /// Style of inline assembly blocks
+    pub yul_style: YulStyle,
 }
 
 /// Style of uint/int256 types
@@ -149,6 +160,22 @@ pub enum SingleLineBlockStyle {
     Preserve,
 }
 
+/// Style of inline assembly blocks
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum YulStyle {
+    /// Prefer single line blocks when possible
+    #[default]
+    Single,
+    /// Prefer single line blocks when possible, and align the blocks of switch cases if all of
+    /// them fit on a single line
+    Aligned,
+    /// Always write nested blocks on multiple lines
+    Multi,
+    /// Leave inline assembly as it is in the source code
+    Preserve,
+}
+
 /// Style of function header in case it doesn't fit
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -179,6 +206,10 @@ impl Default for FormatterConfig {
             ignore: vec![],
             contract_new_lines: false,
             sort_imports: false,
+            group_imports: false,
+            import_line_length: None,
+            remove_unused_imports: false,
+            yul_style: YulStyle::Single,
         }
     }
 }
@@ -0,0 +1,34 @@
+//! Overrides of the gas schedule of the test EVM
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Overrides of the gas costs of the configured EVM version, e.g. to evaluate a repricing before
+/// it is activated.
+///
+/// ```toml
+/// [profile.default.gas_overrides]
+/// intrinsic = 16000
+/// opcodes = { SLOAD = 800, SSTORE = 5000 }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GasOverrides {
+    /// The base cost of a transaction, 21000 by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intrinsic: Option<u64>,
+    /// The costs of opcodes by mnemonic.
+    ///
+    /// The static cost of opcodes which have one is replaced, and their dynamic costs, such as for
+    /// memory expansion, are still charged. Opcodes which only have a dynamic cost, such as
+    /// `SLOAD` or `SSTORE`, are charged the override instead. The costs of calls and contract
+    /// creations can't be overridden.
+    pub opcodes: BTreeMap<String, u64>,
+}
+
+impl GasOverrides {
+    /// Returns true if no costs are overridden.
+    pub fn is_empty(&self) -> bool {
+        self.intrinsic.is_none() && self.opcodes.is_empty()
+    }
+}
@@ -0,0 +1,82 @@
+//! Support for controlling network access of cheatcodes
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+
+/// Configures which URLs can be requested
+///
+/// E.g. for cheat codes (`vm.httpGet`)
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HttpPermissions {
+    /// URL prefixes that are allowed to be requested
+    pub allowed: Vec<String>,
+}
+
+// === impl HttpPermissions ===
+
+impl HttpPermissions {
+    /// Creates a new instance with the given `allowed` URL prefixes
+    pub fn new(allowed: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self { allowed: allowed.into_iter().map(Into::into).collect() }
+    }
+
+    /// Adds a new allowed URL prefix
+    pub fn add(&mut self, url: impl Into<String>) {
+        self.allowed.push(url.into())
+    }
+
+    /// Returns true if the given `url` may be requested.
+    ///
+    /// A URL is allowed if any of the configured prefixes has the same scheme, host and port, and
+    /// its path is a prefix of the URL's path on a segment boundary, e.g.
+    /// `https://api.example.com/v1` allows `https://api.example.com/v1/users` but not
+    /// `https://api.example.com/v10`.
+    ///
+    /// Nothing is allowed by default.
+    pub fn is_url_allowed(&self, url: &str) -> bool {
+        let Ok(url) = Url::parse(url) else { return false };
+        self.allowed.iter().filter_map(|allowed| Url::parse(allowed).ok()).any(|allowed| {
+            allowed.scheme() == url.scheme() &&
+                allowed.host_str() == url.host_str() &&
+                allowed.port_or_known_default() == url.port_or_known_default() &&
+                is_path_prefix(allowed.path(), url.path())
+        })
+    }
+}
+
+/// Returns true if `prefix` is `path` or one of its parent paths.
+fn is_path_prefix(prefix: &str, path: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+    match path.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with('/'),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denies_by_default() {
+        assert!(!HttpPermissions::default().is_url_allowed("https://example.com"));
+    }
+
+    #[test]
+    fn matches_url_prefixes() {
+        let permissions =
+            HttpPermissions::new(["https://api.example.com/v1", "http://localhost:8545"]);
+
+        assert!(permissions.is_url_allowed("https://api.example.com/v1"));
+        assert!(permissions.is_url_allowed("https://api.example.com/v1/users?id=1"));
+        assert!(permissions.is_url_allowed("https://API.example.com:443/v1/users"));
+        assert!(permissions.is_url_allowed("http://localhost:8545/"));
+        assert!(!permissions.is_url_allowed("https://api.example.com/v10"));
+        assert!(!permissions.is_url_allowed("https://api.example.com/"));
+        assert!(!permissions.is_url_allowed("http://api.example.com/v1"));
+        assert!(!permissions.is_url_allowed("https://api.example.com.evil.com/v1"));
+        assert!(!permissions.is_url_allowed("http://localhost:8546"));
+        assert!(!permissions.is_url_allowed("not a url"));
+    }
+}
@@ -11,6 +11,8 @@ mod natspec;
 
 pub const INLINE_CONFIG_FUZZ_KEY: &str = "fuzz";
 pub const INLINE_CONFIG_INVARIANT_KEY: &str = "invariant";
+pub const INLINE_CONFIG_SYMBOLIC_KEY: &str = "symbolic";
+pub const INLINE_CONFIG_FIXTURES_KEY: &str = "fixtures";
 const INLINE_CONFIG_PREFIX: &str = "forge-config";
 
 static INLINE_CONFIG_PREFIX_SELECTED_PROFILE: Lazy<String> = Lazy::new(|| {
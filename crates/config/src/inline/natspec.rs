@@ -1,4 +1,7 @@
-use super::{remove_whitespaces, INLINE_CONFIG_PREFIX, INLINE_CONFIG_PREFIX_SELECTED_PROFILE};
+use super::{
+    parse_config_bool, remove_whitespaces, InlineConfigParserError, INLINE_CONFIG_FIXTURES_KEY,
+    INLINE_CONFIG_PREFIX, INLINE_CONFIG_PREFIX_SELECTED_PROFILE, INLINE_CONFIG_SYMBOLIC_KEY,
+};
 use foundry_compilers::{
     artifacts::{ast::NodeType, Node},
     ProjectCompileOutput,
@@ -53,6 +56,38 @@ impl NatSpec {
         self.config_lines_with_prefix(INLINE_CONFIG_PREFIX_SELECTED_PROFILE.as_str())
     }
 
+    /// Returns whether the test is marked to be executed symbolically in the current profile,
+    /// i.e. `forge-config: default.symbolic = true`, if it is configured at all.
+    pub fn symbolic(&self) -> Result<Option<bool>, InlineConfigParserError> {
+        let prefix = format!(
+            "{}{INLINE_CONFIG_SYMBOLIC_KEY}=",
+            INLINE_CONFIG_PREFIX_SELECTED_PROFILE.as_str()
+        );
+        let mut symbolic = None;
+        for line in self.current_profile_configs() {
+            if let Some(value) = line.strip_prefix(&prefix) {
+                symbolic =
+                    Some(parse_config_bool(INLINE_CONFIG_SYMBOLIC_KEY.into(), value.into())?);
+            }
+        }
+        Ok(symbolic)
+    }
+
+    /// Returns the fixtures file of a table-driven test in the current profile, relative to the
+    /// project root, i.e. `forge-config: default.fixtures = "test/fixtures/cases.json"`.
+    pub fn fixtures(&self) -> Option<String> {
+        let prefix = format!(
+            "{}{INLINE_CONFIG_FIXTURES_KEY}=",
+            INLINE_CONFIG_PREFIX_SELECTED_PROFILE.as_str()
+        );
+        self.current_profile_configs()
+            .filter_map(|line| {
+                let value = line.strip_prefix(&prefix)?;
+                Some(value.trim_matches(|c| c == '"' || c == '\'').to_string())
+            })
+            .last()
+    }
+
     /// Returns a list of configuration lines that match a specific string prefix
     pub fn config_lines_with_prefix<'a>(
         &'a self,
@@ -190,6 +225,33 @@ mod tests {
         )
     }
 
+    #[test]
+    fn symbolic() {
+        let mut natspec = natspec();
+        assert_eq!(natspec.symbolic(), Ok(None));
+
+        natspec.docs.push_str("\nforge-config: ci.symbolic = true");
+        assert_eq!(natspec.symbolic(), Ok(None));
+
+        natspec.docs.push_str("\nforge-config: default.symbolic = true");
+        assert_eq!(natspec.symbolic(), Ok(Some(true)));
+
+        natspec.docs.push_str("\nforge-config: default.symbolic = yes");
+        assert!(natspec.symbolic().is_err());
+    }
+
+    #[test]
+    fn fixtures() {
+        let mut natspec = natspec();
+        assert_eq!(natspec.fixtures(), None);
+
+        natspec.docs.push_str("\nforge-config: ci.fixtures = \"test/fixtures/ci.json\"");
+        assert_eq!(natspec.fixtures(), None);
+
+        natspec.docs.push_str("\nforge-config: default.fixtures = \"test/fixtures/cases.json\"");
+        assert_eq!(natspec.fixtures(), Some("test/fixtures/cases.json".to_string()));
+    }
+
     #[test]
     fn can_handle_unavailable_src_line_with_fallback() {
         let mut fn_data: BTreeMap<String, Value> = BTreeMap::new();
@@ -8,9 +8,10 @@ use crate::{
     },
 };
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// Contains for invariant testing
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InvariantConfig {
     /// The number of runs that must execute for each invariant test group.
     pub runs: u32,
@@ -28,6 +29,14 @@ pub struct InvariantConfig {
     pub shrink_sequence: bool,
     /// The maximum number of attempts to shrink the sequence
     pub shrink_run_limit: usize,
+    /// Also attempt to shrink the arguments of the calls in the failure case toward zero
+    pub shrink_calldata: bool,
+    /// The directory to persist the call sequences of failing runs, and of runs that found new
+    /// fuzz dictionary values, to. They are replayed before generating new runs.
+    pub corpus_dir: Option<PathBuf>,
+    /// The maximum time, in seconds, to run each invariant campaign for. When set, the number of
+    /// runs is only bounded by it.
+    pub timeout: Option<u32>,
 }
 
 impl Default for InvariantConfig {
@@ -40,6 +49,9 @@ impl Default for InvariantConfig {
             dictionary: FuzzDictionaryConfig { dictionary_weight: 80, ..Default::default() },
             shrink_sequence: true,
             shrink_run_limit: 2usize.pow(18_u32),
+            shrink_calldata: false,
+            corpus_dir: None,
+            timeout: None,
         }
     }
 }
@@ -56,8 +68,7 @@ impl InlineConfigParser for InvariantConfig {
             return Ok(None)
         }
 
-        // self is Copy. We clone it with dereference.
-        let mut conf_clone = *self;
+        let mut conf_clone = self.clone();
 
         for pair in overrides {
             let key = pair.0;
@@ -68,6 +79,8 @@ impl InlineConfigParser for InvariantConfig {
                 "fail-on-revert" => conf_clone.fail_on_revert = parse_config_bool(key, value)?,
                 "call-override" => conf_clone.call_override = parse_config_bool(key, value)?,
                 "shrink-sequence" => conf_clone.shrink_sequence = parse_config_bool(key, value)?,
+                "timeout" => conf_clone.timeout = Some(parse_config_u32(key, value)?),
+                "shrink-calldata" => conf_clone.shrink_calldata = parse_config_bool(key, value)?,
                 _ => Err(InlineConfigParserError::InvalidConfigProperty(key.to_string()))?,
             }
         }
@@ -92,10 +105,14 @@ mod tests {
 
     #[test]
     fn successful_merge() {
-        let configs = &["forge-config: default.invariant.runs = 42424242".to_string()];
+        let configs = &[
+            "forge-config: default.invariant.runs = 42424242".to_string(),
+            "forge-config: default.invariant.shrink-calldata = true".to_string(),
+        ];
         let base_config = InvariantConfig::default();
         let merged: InvariantConfig = base_config.try_merge(configs).expect("No errors").unwrap();
         assert_eq!(merged.runs, 42424242);
+        assert!(merged.shrink_calldata);
     }
 
     #[test]
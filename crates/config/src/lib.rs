@@ -31,7 +31,7 @@ use semver::Version;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
     str::FromStr,
@@ -53,7 +53,7 @@ mod resolve;
 pub use resolve::UnresolvedEnvVarError;
 
 pub mod cache;
-use cache::{Cache, ChainCache};
+use cache::{lru_evictions, Cache, ChainCache, ForkCacheEntry};
 
 pub mod fmt;
 pub use fmt::FormatterConfig;
@@ -61,12 +61,33 @@ pub use fmt::FormatterConfig;
 pub mod fs_permissions;
 pub use crate::fs_permissions::FsPermissions;
 
+pub mod http_permissions;
+pub use crate::http_permissions::HttpPermissions;
+
+pub mod secrets;
+pub use crate::secrets::SecretsBackend;
+
 pub mod error;
 pub use error::SolidityErrorCode;
 
 pub mod doc;
 pub use doc::DocConfig;
 
+pub mod coverage;
+pub use coverage::CoverageConfig;
+
+pub mod lint;
+pub use lint::LintConfig;
+
+pub mod doc_lint;
+pub use doc_lint::{DocLintConfig, DocLintLevel, DocLintOverride};
+
+pub mod remote_cache;
+pub use remote_cache::RemoteCacheConfig;
+
+pub mod gas_overrides;
+pub use gas_overrides::GasOverrides;
+
 mod warning;
 pub use warning::*;
 
@@ -161,6 +182,8 @@ pub struct Config {
     pub cache_path: PathBuf,
     /// where the broadcast logs are stored
     pub broadcast: PathBuf,
+    /// where `forge script --save-deployments` saves the deployed contracts, by chain
+    pub deployments: PathBuf,
     /// additional solc allow paths for `--allow-paths`
     pub allow_paths: Vec<PathBuf>,
     /// additional solc include paths for `--include-path`
@@ -212,6 +235,10 @@ pub struct Config {
     /// Multiple etherscan api configs and their aliases
     #[serde(default, skip_serializing_if = "EtherscanConfigs::is_empty")]
     pub etherscan: EtherscanConfigs,
+    /// The verification providers to fall back to, in order, when verifying with `--verifier`
+    /// fails, by chain name or ID, e.g. `{ mainnet = ["sourcify-v2", "blockscout"] }`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub verifier_fallbacks: BTreeMap<String, Vec<String>>,
     /// list of solidity error codes to always silence in the compiler output
     pub ignored_error_codes: Vec<SolidityErrorCode>,
     /// When true, compiler warnings are treated as errors
@@ -244,6 +271,9 @@ pub struct Config {
     pub sender: Address,
     /// The tx.origin value during EVM execution
     pub tx_origin: Address,
+    /// The CREATE2 factory deploying contracts created with a salt when broadcasting, which must
+    /// take the salt followed by the init code as calldata
+    pub create2_deployer: Address,
     /// the initial balance of each deployed test contract
     pub initial_balance: U256,
     /// the block.number value during EVM execution
@@ -279,6 +309,10 @@ pub struct Config {
     ///
     /// The default is 128MiB.
     pub memory_limit: u64,
+    /// The sets of chain specific precompiles to add to the EVM, e.g. `["arbitrum"]`.
+    pub precompiles: Vec<String>,
+    /// Overrides of the gas costs of the EVM version.
+    pub gas_overrides: GasOverrides,
     /// Additional output selection for all contracts, such as "ir", "devdoc", "storageLayout",
     /// etc.
     ///
@@ -321,6 +355,13 @@ pub struct Config {
     /// Disables storage caching entirely. This overrides any settings made in
     /// `rpc_storage_caching`
     pub no_storage_caching: bool,
+    /// Prefetches the storage of forked contracts when they are first accessed, instead of
+    /// fetching every slot on demand
+    pub fork_prefetch_storage: bool,
+    /// The maximum size of the fork caches of all chains in bytes.
+    ///
+    /// The least recently used caches that aren't pinned are evicted when a fork is created.
+    pub fork_cache_max_size: Option<u64>,
     /// Disables rate limiting entirely. This overrides any settings made in
     /// `compute_units_per_second`
     pub no_rpc_rate_limit: bool,
@@ -359,10 +400,24 @@ pub struct Config {
     pub fmt: FormatterConfig,
     /// Configuration for `forge doc`
     pub doc: DocConfig,
+    /// Configuration for `forge coverage`
+    pub coverage: CoverageConfig,
+    /// Configuration for `forge lint`
+    pub lint: LintConfig,
+    /// Configuration for checking the NatSpec documentation of the sources when building
+    pub doc_lint: DocLintConfig,
+    /// Configuration of the remote cache of compiled artifacts
+    pub remote_cache: RemoteCacheConfig,
     /// Configures the permissions of cheat codes that touch the file system.
     ///
     /// This includes what operations can be executed (read, write)
     pub fs_permissions: FsPermissions,
+    /// Configures which URLs the HTTP cheat codes (`vm.httpGet`, `vm.httpPost`) may request.
+    ///
+    /// Nothing is allowed by default.
+    pub http_permissions: HttpPermissions,
+    /// Configures where `vm.getSecret` resolves secrets from.
+    pub secrets: SecretsBackend,
 
     /// Temporary config to enable [SpecId::CANCUN]
     ///
@@ -415,8 +470,19 @@ impl Config {
     pub const PROFILE_SECTION: &'static str = "profile";
 
     /// Standalone sections in the config which get integrated into the selected profile
-    pub const STANDALONE_SECTIONS: &'static [&'static str] =
-        &["rpc_endpoints", "etherscan", "fmt", "doc", "fuzz", "invariant", "labels"];
+    pub const STANDALONE_SECTIONS: &'static [&'static str] = &[
+        "rpc_endpoints",
+        "etherscan",
+        "fmt",
+        "doc",
+        "coverage",
+        "lint",
+        "doc_lint",
+        "remote_cache",
+        "fuzz",
+        "invariant",
+        "labels",
+    ];
 
     /// File name of config toml file
     pub const FILE_NAME: &'static str = "foundry.toml";
@@ -429,6 +495,12 @@ impl Config {
     /// `0x1804c8AB1F12E6bbf3894d4083f33e07309d1f38`
     pub const DEFAULT_SENDER: Address = address!("1804c8AB1F12E6bbf3894d4083f33e07309d1f38");
 
+    /// Default CREATE2 factory, the deterministic deployment proxy
+    ///
+    /// `0x4e59b44847b379578588920ca78fbf26c0b4956c`
+    pub const DEFAULT_CREATE2_DEPLOYER: Address =
+        address!("4e59b44847b379578588920ca78fbf26c0b4956c");
+
     /// Returns the current `Config`
     ///
     /// See `Config::figment`
@@ -531,12 +603,17 @@ impl Config {
         self.script = p(&root, &self.script);
         self.out = p(&root, &self.out);
         self.broadcast = p(&root, &self.broadcast);
+        self.deployments = p(&root, &self.deployments);
         self.cache_path = p(&root, &self.cache_path);
 
         if let Some(build_info_path) = self.build_info_path {
             self.build_info_path = Some(p(&root, &build_info_path));
         }
 
+        if let Some(corpus_dir) = self.invariant.corpus_dir {
+            self.invariant.corpus_dir = Some(p(&root, &corpus_dir));
+        }
+
         self.libs = self.libs.into_iter().map(|lib| p(&root, &lib)).collect();
 
         self.remappings =
@@ -982,6 +1059,15 @@ impl Config {
         self.get_etherscan_config_with_chain(chain).ok().flatten().map(|c| c.key)
     }
 
+    /// Returns the verification providers to fall back to on the given chain, in order.
+    pub fn get_verifier_fallbacks(&self, chain: Chain) -> &[String] {
+        self.verifier_fallbacks
+            .iter()
+            .find(|(key, _)| key.parse::<Chain>().map_or(false, |key| key == chain))
+            .map(|(_, verifiers)| verifiers.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Returns the remapping for the project's _src_ directory
     ///
     /// **Note:** this will add an additional `<src>/=<src path>` remapping here so imports that
@@ -1494,6 +1580,58 @@ impl Config {
         }
     }
 
+    /// Lists the cached fork databases of `chain`, or of all chains if `None`
+    pub fn list_fork_cache(chain: Option<Chain>) -> eyre::Result<Vec<ForkCacheEntry>> {
+        let Some(rpc_cache_dir) = Config::foundry_rpc_cache_dir() else {
+            eyre::bail!("failed to get foundry_rpc_cache_dir");
+        };
+        let chains = match chain {
+            Some(chain) => vec![chain],
+            None if rpc_cache_dir.is_dir() => rpc_cache_dir
+                .read_dir()?
+                .flatten()
+                .filter_map(|entry| Chain::from_str(&entry.file_name().to_string_lossy()).ok())
+                .collect(),
+            None => vec![],
+        };
+
+        let mut entries = vec![];
+        for chain in chains {
+            let chain_dir = rpc_cache_dir.join(chain.to_string());
+            if !chain_dir.is_dir() {
+                continue
+            }
+            for block_dir in chain_dir.read_dir()?.flatten() {
+                let Ok(block) = block_dir.file_name().to_string_lossy().parse::<u64>() else {
+                    continue
+                };
+                if let Some(entry) = ForkCacheEntry::read(chain, block, block_dir.path())? {
+                    entries.push(entry);
+                }
+            }
+        }
+        entries.sort_by_key(|entry| (entry.chain.id(), entry.block));
+        Ok(entries)
+    }
+
+    /// Evicts the least recently used, unpinned fork caches until the fork caches of all chains
+    /// take up at most `max_size` bytes.
+    ///
+    /// The cache directory `keep` is never evicted. Returns the evicted entries.
+    pub fn evict_fork_cache(
+        max_size: u64,
+        keep: Option<&Path>,
+    ) -> eyre::Result<Vec<ForkCacheEntry>> {
+        let entries = Self::list_fork_cache(None)?;
+        let evictions =
+            lru_evictions(&entries, max_size, keep).into_iter().cloned().collect::<Vec<_>>();
+        for entry in &evictions {
+            trace!(path = ?entry.path, "evicting fork cache");
+            fs::remove_dir_all(&entry.path)?;
+        }
+        Ok(evictions)
+    }
+
     //The path provided to this function should point to a cached chain folder
     fn get_cached_blocks(chain_path: &Path) -> eyre::Result<Vec<(String, u64)>> {
         let mut blocks = vec![];
@@ -1804,6 +1942,8 @@ impl Default for Config {
         Self {
             profile: Self::DEFAULT_PROFILE,
             fs_permissions: FsPermissions::new([PathPermission::read("out")]),
+            http_permissions: Default::default(),
+            secrets: Default::default(),
             cancun: false,
             __root: Default::default(),
             src: "src".into(),
@@ -1814,6 +1954,7 @@ impl Default for Config {
             cache: true,
             cache_path: "cache".into(),
             broadcast: "broadcast".into(),
+            deployments: "deployments".into(),
             allow_paths: vec![],
             include_paths: vec![],
             force: false,
@@ -1842,6 +1983,7 @@ impl Default for Config {
             ffi: false,
             sender: Config::DEFAULT_SENDER,
             tx_origin: Config::DEFAULT_SENDER,
+            create2_deployer: Config::DEFAULT_CREATE2_DEPLOYER,
             initial_balance: U256::from(0xffffffffffffffffffffffffu128),
             block_number: 1,
             fork_block_number: None,
@@ -1856,6 +1998,8 @@ impl Default for Config {
             block_prevrandao: Default::default(),
             block_gas_limit: None,
             memory_limit: 1 << 27, // 2**27 = 128MiB = 134_217_728 bytes
+            precompiles: vec![],
+            gas_overrides: Default::default(),
             eth_rpc_url: None,
             eth_rpc_jwt: None,
             etherscan_api_key: None,
@@ -1873,7 +2017,10 @@ impl Default for Config {
             rpc_storage_caching: Default::default(),
             rpc_endpoints: Default::default(),
             etherscan: Default::default(),
+            verifier_fallbacks: Default::default(),
             no_storage_caching: false,
+            fork_prefetch_storage: false,
+            fork_cache_max_size: None,
             no_rpc_rate_limit: false,
             use_literal_content: false,
             bytecode_hash: BytecodeHash::Ipfs,
@@ -1884,6 +2031,10 @@ impl Default for Config {
             build_info_path: None,
             fmt: Default::default(),
             doc: Default::default(),
+            coverage: Default::default(),
+            lint: Default::default(),
+            doc_lint: Default::default(),
+            remote_cache: Default::default(),
             labels: Default::default(),
             __non_exhaustive: (),
             __warnings: vec![],
@@ -3306,6 +3457,29 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_verifier_fallbacks() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                verifier_fallbacks = { mainnet = ["sourcify-v2", "blockscout"], 10 = ["sourcify"] }
+            "#,
+            )?;
+
+            let config = Config::load();
+            assert_eq!(
+                config.get_verifier_fallbacks(NamedChain::Mainnet.into()),
+                ["sourcify-v2", "blockscout"]
+            );
+            assert_eq!(config.get_verifier_fallbacks(NamedChain::Optimism.into()), ["sourcify"]);
+            assert!(config.get_verifier_fallbacks(NamedChain::Sepolia.into()).is_empty());
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_extract_etherscan_config_by_chain() {
         figment::Jail::expect_with(|jail| {
@@ -4107,6 +4281,76 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_http_permissions() {
+        figment::Jail::expect_with(|jail| {
+            let loaded = Config::load();
+            assert_eq!(loaded.http_permissions, HttpPermissions::default());
+
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                http_permissions = ["https://api.example.com/v1", "http://localhost:8545"]
+            "#,
+            )?;
+            let loaded = Config::load();
+            assert_eq!(
+                loaded.http_permissions,
+                HttpPermissions::new(["https://api.example.com/v1", "http://localhost:8545"])
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_gas_overrides() {
+        figment::Jail::expect_with(|jail| {
+            let loaded = Config::load();
+            assert!(loaded.gas_overrides.is_empty());
+
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default.gas_overrides]
+                intrinsic = 16000
+                opcodes = { SLOAD = 800 }
+            "#,
+            )?;
+            let loaded = Config::load();
+            assert_eq!(
+                loaded.gas_overrides,
+                GasOverrides {
+                    intrinsic: Some(16000),
+                    opcodes: [("SLOAD".to_string(), 800)].into()
+                }
+            );
+
+            Ok(())
+        });
+    }
+
+    #[test]
+    fn test_secrets_backend() {
+        figment::Jail::expect_with(|jail| {
+            let loaded = Config::load();
+            assert_eq!(loaded.secrets, SecretsBackend::Keychain);
+
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [profile.default]
+                secrets = { backend = "keystore", dir = "secrets" }
+            "#,
+            )?;
+            let loaded = Config::load();
+            assert_eq!(loaded.secrets, SecretsBackend::Keystore { dir: "secrets".into() });
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_optimizer_settings_basic() {
         figment::Jail::expect_with(|jail| {
@@ -4289,13 +4533,19 @@ mod tests {
                 [invariant]
                 runs = 512
                 depth = 10
+                corpus_dir = 'corpus'
             ",
             )?;
 
             let loaded = Config::load().sanitized();
             assert_eq!(
                 loaded.invariant,
-                InvariantConfig { runs: 512, depth: 10, ..Default::default() }
+                InvariantConfig {
+                    runs: 512,
+                    depth: 10,
+                    corpus_dir: Some("corpus".into()),
+                    ..Default::default()
+                }
             );
 
             Ok(())
@@ -0,0 +1,13 @@
+//! Configuration specific to the `forge lint` command
+
+use serde::{Deserialize, Serialize};
+
+/// Contains the config for linting the project's sources
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintConfig {
+    /// Globs of source files to exclude from linting, relative to the project root, e.g.
+    /// `src/vendored/**`.
+    pub ignore: Vec<String>,
+    /// The lints which are not checked, e.g. `missing-event`.
+    pub exclude_lints: Vec<String>,
+}
@@ -0,0 +1,20 @@
+//! Configuration for sharing compiled artifacts between machines
+
+use serde::{Deserialize, Serialize};
+
+/// Contains the config of the remote cache the compiled artifacts are downloaded from and
+/// uploaded to
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoteCacheConfig {
+    /// The URL of the cache, e.g. `https://cache.example.com/foundry`.
+    ///
+    /// Only plain HTTP(S) backends are supported: requests carry a bearer token, but aren't signed
+    /// for object stores such as S3.
+    ///
+    /// The remote cache is disabled if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Whether to only download artifacts from the cache and never upload them, e.g. on
+    /// developer machines.
+    pub read_only: bool,
+}
@@ -0,0 +1,33 @@
+//! Configuration of where secrets are resolved from
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// The backend `vm.getSecret` resolves secrets from
+///
+/// E.g. `secrets = { backend = "keystore", dir = "secrets" }`
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "kebab-case")]
+pub enum SecretsBackend {
+    /// The OS keychain, with secrets stored as passwords of the `foundry` service
+    #[default]
+    Keychain,
+    /// A directory of encrypted keystore files named after the secret they hold, decrypted with
+    /// the password in the `FOUNDRY_SECRETS_PASSWORD` environment variable
+    Keystore {
+        /// The directory containing the keystore files
+        dir: PathBuf,
+    },
+}
+
+// === impl SecretsBackend ===
+
+impl SecretsBackend {
+    /// Joins the keystore directory with the given `root`
+    pub fn joined(self, root: impl AsRef<Path>) -> Self {
+        match self {
+            Self::Keystore { dir } => Self::Keystore { dir: root.as_ref().join(dir) },
+            backend => backend,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    document::DocumentContent, helpers::merge_toml_table, AsDoc, BufWriter, Document, ParseItem,
-    ParseSource, Parser, Preprocessor,
+    document::DocumentContent,
+    helpers::{copy_dir, merge_toml_table},
+    AsDoc, BufWriter, Document, ParseItem, ParseSource, Parser, Preprocessor,
 };
 use forge_fmt::{FormatterConfig, Visitable};
 use foundry_common::glob::expand_globs;
@@ -46,6 +47,7 @@ impl DocBuilder {
     const SOL_EXT: &'static str = "sol";
     const README: &'static str = "README.md";
     const SUMMARY: &'static str = "SUMMARY.md";
+    const THEME: &'static str = "theme";
 
     /// Create new instance of builder.
     pub fn new(
@@ -317,6 +319,11 @@ impl DocBuilder {
         // Write css files
         fs::write(out_dir.join("book.css"), include_str!("../static/book.css"))?;
 
+        // Write the custom theme, if any
+        if let Some(theme) = &self.config.theme {
+            copy_dir(&self.root.join(theme), &out_dir.join(Self::THEME))?;
+        }
+
         // Write book config
         fs::write(self.out_dir().join("book.toml"), self.book_config()?)?;
 
@@ -351,6 +358,12 @@ impl DocBuilder {
                 .unwrap()
                 .insert(String::from("git-repository-url"), repo.clone().into());
         }
+        if self.config.theme.is_some() {
+            book["output"].as_table_mut().unwrap()["html"]
+                .as_table_mut()
+                .unwrap()
+                .insert(String::from("theme"), Self::THEME.into());
+        }
 
         // Attempt to find the user provided book path
         let book_path = {
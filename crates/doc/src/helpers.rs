@@ -1,3 +1,4 @@
+use std::{fs, io, path::Path};
 use toml::{value::Table, Value};
 
 /// Merge original toml table with the override.
@@ -26,3 +27,18 @@ pub(crate) fn merge_toml_table(table: &mut Table, override_table: Table) {
         };
     }
 }
+
+/// Recursively copies the contents of the directory `from` to `to`.
+pub(crate) fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
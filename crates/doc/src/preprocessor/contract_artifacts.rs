@@ -0,0 +1,76 @@
+use super::{Preprocessor, PreprocessorId};
+use crate::{Document, ParseSource, PreprocessorOutput};
+use std::{collections::HashMap, path::PathBuf};
+
+/// [ContractArtifacts] preprocessor id.
+pub const CONTRACT_ARTIFACTS_ID: PreprocessorId = PreprocessorId("contract_artifacts");
+
+/// The contract artifacts preprocessor.
+///
+/// This preprocessor writes the selectors and the storage layout of the compiled contracts to
+/// their [Document]'s context.
+#[derive(Debug)]
+pub struct ContractArtifacts {
+    /// The artifacts of the contracts, by the path of their source and their name.
+    pub artifacts: HashMap<(PathBuf, String), ContractArtifact>,
+}
+
+/// The compiled interface and storage layout of a contract.
+#[derive(Clone, Debug, Default)]
+pub struct ContractArtifact {
+    /// The selectors of the contract's functions and errors, and the topics of its events.
+    pub selectors: Vec<Selector>,
+    /// The storage slots of the contract's state variables.
+    pub storage: Vec<StorageSlot>,
+}
+
+/// The selector of a function, error or event.
+#[derive(Clone, Debug)]
+pub struct Selector {
+    /// The kind of the item, i.e. `function`, `error` or `event`
+    pub kind: &'static str,
+    /// The signature of the item, e.g. `transfer(address,uint256)`
+    pub signature: String,
+    /// The hex encoded selector, or the topic of an event
+    pub selector: String,
+}
+
+/// The storage slot of a state variable.
+#[derive(Clone, Debug)]
+pub struct StorageSlot {
+    /// The name of the state variable
+    pub name: String,
+    /// The type of the state variable
+    pub ty: String,
+    /// The slot, as a decimal number
+    pub slot: String,
+    /// The offset within the slot, in bytes
+    pub offset: i64,
+    /// The number of bytes the state variable occupies
+    pub bytes: String,
+}
+
+impl Preprocessor for ContractArtifacts {
+    fn id(&self) -> PreprocessorId {
+        CONTRACT_ARTIFACTS_ID
+    }
+
+    fn preprocess(&self, documents: Vec<Document>) -> Result<Vec<Document>, eyre::Error> {
+        for document in documents.iter() {
+            for item in document.content.iter_items() {
+                if !matches!(item.source, ParseSource::Contract(_)) {
+                    continue
+                }
+                let key = (document.item_path.clone(), item.source.ident());
+                if let Some(artifact) = self.artifacts.get(&key) {
+                    document.add_context(
+                        self.id(),
+                        PreprocessorOutput::ContractArtifacts(artifact.clone()),
+                    );
+                }
+            }
+        }
+
+        Ok(documents)
+    }
+}
@@ -18,6 +18,11 @@ pub use git_source::{GitSource, GIT_SOURCE_ID};
 mod deployments;
 pub use deployments::{Deployment, Deployments, DEPLOYMENTS_ID};
 
+mod contract_artifacts;
+pub use contract_artifacts::{
+    ContractArtifact, ContractArtifacts, Selector, StorageSlot, CONTRACT_ARTIFACTS_ID,
+};
+
 /// The preprocessor id.
 #[derive(Debug, PartialEq, Eq, Hash)]
 pub struct PreprocessorId(&'static str);
@@ -39,6 +44,9 @@ pub enum PreprocessorOutput {
     /// The deployments output.
     /// The deployment address of the item path.
     Deployments(Vec<Deployment>),
+    /// The contract artifacts output.
+    /// The selectors and the storage layout of the compiled contract.
+    ContractArtifacts(ContractArtifact),
 }
 
 /// Trait for preprocessing and/or modifying existing documents
@@ -3,7 +3,7 @@ use crate::{
     parser::ParseSource,
     writer::BufWriter,
     CommentTag, Comments, CommentsRef, Document, Markdown, PreprocessorOutput,
-    CONTRACT_INHERITANCE_ID, DEPLOYMENTS_ID, GIT_SOURCE_ID, INHERITDOC_ID,
+    CONTRACT_ARTIFACTS_ID, CONTRACT_INHERITANCE_ID, DEPLOYMENTS_ID, GIT_SOURCE_ID, INHERITDOC_ID,
 };
 use forge_fmt::solang_ext::SafeUnwrap;
 use itertools::Itertools;
@@ -218,6 +218,13 @@ impl AsDoc for Document {
                                 writer.write_section(comments, code)
                             })?;
                         }
+
+                        if let Some(artifact) =
+                            read_context!(self, CONTRACT_ARTIFACTS_ID, ContractArtifacts)
+                        {
+                            writer.try_write_selectors_table(&artifact.selectors)?;
+                            writer.try_write_storage_layout_table(&artifact.storage)?;
+                        }
                     }
 
                     ParseSource::Function(func) => {
@@ -1,4 +1,7 @@
-use crate::{writer::traits::ParamLike, AsDoc, CommentTag, Comments, Deployment, Markdown};
+use crate::{
+    writer::traits::ParamLike, AsDoc, CommentTag, Comments, Deployment, Markdown, Selector,
+    StorageSlot,
+};
 use itertools::Itertools;
 use once_cell::sync::Lazy;
 use solang_parser::pt::{ErrorParameter, EventParameter, Parameter, VariableDeclaration};
@@ -17,6 +20,16 @@ const DEPLOYMENTS_TABLE_HEADERS: &[&str] = &["Network", "Address"];
 static DEPLOYMENTS_TABLE_SEPARATOR: Lazy<String> =
     Lazy::new(|| DEPLOYMENTS_TABLE_HEADERS.iter().map(|h| "-".repeat(h.len())).join("|"));
 
+/// Headers and separator for rendering the selectors table.
+const SELECTORS_TABLE_HEADERS: &[&str] = &["Kind", "Signature", "Selector"];
+static SELECTORS_TABLE_SEPARATOR: Lazy<String> =
+    Lazy::new(|| SELECTORS_TABLE_HEADERS.iter().map(|h| "-".repeat(h.len())).join("|"));
+
+/// Headers and separator for rendering the storage layout table.
+const STORAGE_LAYOUT_TABLE_HEADERS: &[&str] = &["Name", "Type", "Slot", "Offset", "Bytes"];
+static STORAGE_LAYOUT_TABLE_SEPARATOR: Lazy<String> =
+    Lazy::new(|| STORAGE_LAYOUT_TABLE_HEADERS.iter().map(|h| "-".repeat(h.len())).join("|"));
+
 /// The buffered writer.
 /// Writes various display items into the internal buffer.
 #[derive(Debug, Default)]
@@ -236,6 +249,52 @@ impl BufWriter {
         Ok(())
     }
 
+    /// Writes the selectors table to the buffer, if there are any selectors.
+    pub fn try_write_selectors_table(&mut self, selectors: &[Selector]) -> fmt::Result {
+        if selectors.is_empty() {
+            return Ok(())
+        }
+
+        self.write_subtitle("Selectors")?;
+        self.write_piped(&SELECTORS_TABLE_HEADERS.join("|"))?;
+        self.write_piped(&SELECTORS_TABLE_SEPARATOR)?;
+
+        for selector in selectors {
+            let row = [
+                selector.kind.to_owned(),
+                Markdown::Code(&selector.signature).as_doc()?,
+                Markdown::Code(&selector.selector).as_doc()?,
+            ];
+            self.write_piped(&row.join("|"))?;
+        }
+
+        self.writeln()
+    }
+
+    /// Writes the storage layout table to the buffer, if there are any state variables.
+    pub fn try_write_storage_layout_table(&mut self, storage: &[StorageSlot]) -> fmt::Result {
+        if storage.is_empty() {
+            return Ok(())
+        }
+
+        self.write_subtitle("Storage Layout")?;
+        self.write_piped(&STORAGE_LAYOUT_TABLE_HEADERS.join("|"))?;
+        self.write_piped(&STORAGE_LAYOUT_TABLE_SEPARATOR)?;
+
+        for slot in storage {
+            let row = [
+                Markdown::Code(&slot.name).as_doc()?,
+                Markdown::Code(&slot.ty).as_doc()?,
+                slot.slot.clone(),
+                slot.offset.to_string(),
+                slot.bytes.clone(),
+            ];
+            self.write_piped(&row.join("|"))?;
+        }
+
+        self.writeln()
+    }
+
     /// Write content to the buffer surrounded by pipes.
     pub fn write_piped(&mut self, content: &str) -> fmt::Result {
         self.write_raw("|")?;
@@ -1,10 +1,12 @@
+//! Generation of EIP-2930 access lists.
+
 use alloy_primitives::{Address, B256};
 use alloy_rpc_types::{AccessList, AccessListItem};
-use hashbrown::{HashMap, HashSet};
 use revm::{
     interpreter::{opcode, Interpreter},
     Database, EVMData, Inspector,
 };
+use std::collections::{HashMap, HashSet};
 
 /// An inspector that collects touched accounts and storage slots.
 #[derive(Debug, Default)]
@@ -40,12 +40,12 @@ pub enum DatabaseError {
     #[error("transaction {0} not found")]
     TransactionNotFound(B256),
     #[error(
-        "CREATE2 Deployer (0x4e59b44847b379578588920ca78fbf26c0b4956c) not present on this chain.\n\
+        "CREATE2 Deployer ({0}) not present on this chain.\n\
          For a production environment, you can deploy it using the pre-signed transaction from \
          https://github.com/Arachnid/deterministic-deployment-proxy.\n\
          For a test environment, you can use `etch` to place the required bytecode at that address."
     )]
-    MissingCreate2Deployer,
+    MissingCreate2Deployer(Address),
 }
 
 impl DatabaseError {
@@ -75,7 +75,7 @@ impl DatabaseError {
             Self::Message(_) |
             Self::BlockNotFound(_) |
             Self::TransactionNotFound(_) |
-            Self::MissingCreate2Deployer => None,
+            Self::MissingCreate2Deployer(_) => None,
         }
     }
 
@@ -3,7 +3,7 @@
 use crate::{
     backend::{
         diagnostic::RevertDiagnostic, error::DatabaseError, Backend, DatabaseExt, LocalForkId,
-        RevertSnapshotAction,
+        RevertSnapshotAction, TransactionOverrides,
     },
     fork::{CreateFork, ForkId},
 };
@@ -171,6 +171,24 @@ impl<'a> DatabaseExt for FuzzBackendWrapper<'a> {
         self.backend_mut(env).roll_fork_to_transaction(id, transaction, env, journaled_state)
     }
 
+    fn roll_fork_to_transaction_index(
+        &mut self,
+        id: Option<LocalForkId>,
+        block_hash: B256,
+        index: usize,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()> {
+        trace!(?id, ?block_hash, index, "fuzz: roll fork to transaction index");
+        self.backend_mut(env).roll_fork_to_transaction_index(
+            id,
+            block_hash,
+            index,
+            env,
+            journaled_state,
+        )
+    }
+
     fn transact<I: Inspector<Backend>>(
         &mut self,
         id: Option<LocalForkId>,
@@ -183,6 +201,47 @@ impl<'a> DatabaseExt for FuzzBackendWrapper<'a> {
         self.backend_mut(env).transact(id, transaction, env, journaled_state, inspector)
     }
 
+    fn transact_with_overrides<I: Inspector<Backend>>(
+        &mut self,
+        id: Option<LocalForkId>,
+        transaction: B256,
+        overrides: TransactionOverrides,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<()> {
+        trace!(?id, ?transaction, ?overrides, "fuzz: execute transaction with overrides");
+        self.backend_mut(env).transact_with_overrides(
+            id,
+            transaction,
+            overrides,
+            env,
+            journaled_state,
+            inspector,
+        )
+    }
+
+    fn simulate<I: Inspector<Backend>>(
+        &self,
+        env: &Env,
+        journaled_state: &JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<ResultAndState> {
+        trace!("fuzz: simulate transaction");
+        self.backend.simulate(env, journaled_state, inspector)
+    }
+
+    fn copy_account(
+        &mut self,
+        from: LocalForkId,
+        to: LocalForkId,
+        account: Address,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()> {
+        trace!(?from, ?to, ?account, "fuzz: copy account");
+        self.backend_mut(&Env::default()).copy_account(from, to, account, journaled_state)
+    }
+
     fn active_fork_id(&self) -> Option<LocalForkId> {
         self.backend.active_fork_id()
     }
@@ -1,3 +1,64 @@
+    fn transact<I: Inspector<Backend>>(
+        &mut self,
+        maybe_id: Option<LocalForkId>,
+        transaction: B256,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<()> {
+        self.transact_with_overrides(
+            maybe_id,
+            transaction,
+            TransactionOverrides::default(),
+            env,
+            journaled_state,
+            inspector,
+        )
+    }
+
+    fn transact_with_overrides<I: Inspector<Backend>>(
+        &mut self,
+        maybe_id: Option<LocalForkId>,
+        transaction: B256,
+        overrides: TransactionOverrides,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<()> {
+        trace!(?maybe_id, ?transaction, ?overrides, "execute transaction");
+        let id = self.ensure_fork(maybe_id)?;
+        let fork_id = self.ensure_fork_id(id).cloned()?;
+
+        let mut tx = {
+            let fork = self.inner.get_fork_by_id_mut(id)?;
+            fork.db.db.get_transaction(transaction)?
+        };
+
+        // This is a bit ambiguous because the user wants to transact an arbitrary transaction in the current context, but we're assuming the user wants to transact the transaction as it was mined. Usually this is used in a combination of a fork at the transaction's parent transaction in the block and then the transaction is transacted: <https://github.com/foundry-rs/foundry/issues/6538>
+        // So we modify the env to match the transaction's block
+        let (fork_block, block) =
+            self.get_block_number_and_block_for_transaction(id, transaction)?;
+        let mut env = env.clone();
+        update_env_block(&mut env, fork_block, &block);
+
+        let fork = self.inner.get_fork_by_id_mut(id)?;
+        if let Some(from) = overrides.from {
+            let nonce = match journaled_state.state.get(&from) {
+                Some(account) => account.info.nonce,
+                None => fork.db.basic(from)?.map(|info| info.nonce).unwrap_or_default(),
+            };
+            tx.from = from;
+            tx.nonce = U64::from(nonce);
+        }
+        if let Some(value) = overrides.value {
+            tx.value = value;
+        }
+        if let Some(input) = overrides.input {
+            tx.input = input;
+        }
+        commit_transaction(tx, env, journaled_state, fork, &fork_id, inspector)
+    }
+
 //! Foundry's main executor backend abstraction and implementation.
 
 use crate::{
@@ -7,7 +68,7 @@ use crate::{
     utils::configure_tx_env,
 };
 use alloy_genesis::GenesisAccount;
-use alloy_primitives::{b256, keccak256, Address, B256, U256, U64};
+use alloy_primitives::{b256, keccak256, Address, Bytes, B256, U256, U64};
 use alloy_rpc_types::{Block, BlockNumberOrTag, BlockTransactions, Transaction};
 use foundry_common::{is_known_system_sender, SYSTEM_TRANSACTION_TYPE};
 use revm::{
@@ -185,6 +246,25 @@ pub trait DatabaseExt: Database<Error = DatabaseError> {
         journaled_state: &mut JournaledState,
     ) -> eyre::Result<()>;
 
+    /// Updates the fork to the state before the transaction at `index` in the block with the given
+    /// hash
+    ///
+    /// This will create a new fork at the block's parent and replay the block's first `index`
+    /// transactions.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if not matching fork was found, or if the block has fewer than `index`
+    /// transactions.
+    fn roll_fork_to_transaction_index(
+        &mut self,
+        id: Option<LocalForkId>,
+        block_hash: B256,
+        index: usize,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()>;
+
     /// Fetches the given transaction for the fork and executes it, committing the state in the DB
     fn transact<I: Inspector<Backend>>(
         &mut self,
@@ -195,6 +275,39 @@ pub trait DatabaseExt: Database<Error = DatabaseError> {
         inspector: &mut I,
     ) -> eyre::Result<()>;
 
+    /// Same as [`Self::transact`] but replaces the fields of the transaction set in `overrides`
+    /// before executing it
+    fn transact_with_overrides<I: Inspector<Backend>>(
+        &mut self,
+        id: Option<LocalForkId>,
+        transaction: B256,
+        overrides: TransactionOverrides,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<()>;
+
+    /// Executes the transaction configured in `env` on top of the given journaled state, with an
+    /// inspector, without committing any state changes to the DB or the journaled state
+    fn simulate<I: Inspector<Backend>>(
+        &self,
+        env: &Env,
+        journaled_state: &JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<ResultAndState>;
+
+    /// Copies the code, balance, nonce and all storage slots the `from` fork has touched of the
+    /// given account into the `to` fork.
+    ///
+    /// `journaled_state` is the state of the currently active fork.
+    fn copy_account(
+        &mut self,
+        from: LocalForkId,
+        to: LocalForkId,
+        account: Address,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()>;
+
     /// Returns the `ForkId` that's currently used in the database, if fork mode is on
     fn active_fork_id(&self) -> Option<LocalForkId>;
 
@@ -318,6 +431,18 @@ pub trait DatabaseExt: Database<Error = DatabaseError> {
     }
 }
 
+/// Fields of a transaction to replace when replaying it with
+/// [`DatabaseExt::transact_with_overrides`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionOverrides {
+    /// The sender, which replays the transaction with its own nonce
+    pub from: Option<Address>,
+    /// The value sent with the transaction
+    pub value: Option<U256>,
+    /// The calldata, or the init code if the transaction creates a contract
+    pub input: Option<Bytes>,
+}
+
 /// Provides the underlying `revm::Database` implementation.
 ///
 /// A `Backend` can be initialised in two forms:
@@ -879,40 +1004,47 @@ impl Backend {
     ) -> eyre::Result<Option<Transaction>> {
         trace!(?id, ?tx_hash, "replay until transaction");
 
-        let fork_id = self.ensure_fork_id(id)?.clone();
-
         let fork = self.inner.get_fork_by_id_mut(id)?;
         let full_block = fork.db.db.get_full_block(env.block.number.to::<u64>())?;
 
         if let BlockTransactions::Full(txs) = full_block.transactions {
-            for tx in txs.into_iter() {
-                // System transactions such as on L2s don't contain any pricing info so we skip them
-                // otherwise this would cause reverts
-                if is_known_system_sender(tx.from) ||
-                    tx.transaction_type.map(|ty| ty.to::<u64>()) == Some(SYSTEM_TRANSACTION_TYPE)
-                {
-                    trace!(tx=?tx.hash, "skipping system transaction");
-                    continue;
-                }
+            let index = txs
+                .iter()
+                .position(|tx| tx.hash == tx_hash && !is_system_transaction(tx))
+                .unwrap_or(txs.len());
+            // found the target transaction
+            let target = txs.get(index).cloned();
+            self.replay_transactions(id, env, txs.into_iter().take(index), journaled_state)?;
+            return Ok(target)
+        }
 
-                if tx.hash == tx_hash {
-                    // found the target transaction
-                    return Ok(Some(tx))
-                }
-                trace!(tx=?tx.hash, "committing transaction");
-
-                commit_transaction(
-                    tx,
-                    env.clone(),
-                    journaled_state,
-                    fork,
-                    &fork_id,
-                    NoOpInspector,
-                )?;
+        Ok(None)
+    }
+
+    /// Commits the given transactions of the fork's current block, skipping system transactions
+    fn replay_transactions(
+        &mut self,
+        id: LocalForkId,
+        env: Env,
+        txs: impl IntoIterator<Item = Transaction>,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()> {
+        let fork_id = self.ensure_fork_id(id)?.clone();
+        let fork = self.inner.get_fork_by_id_mut(id)?;
+
+        for tx in txs {
+            // System transactions such as on L2s don't contain any pricing info so we skip them
+            // otherwise this would cause reverts
+            if is_system_transaction(&tx) {
+                trace!(tx=?tx.hash, "skipping system transaction");
+                continue;
             }
+            trace!(tx=?tx.hash, "committing transaction");
+
+            commit_transaction(tx, env.clone(), journaled_state, fork, &fork_id, NoOpInspector)?;
         }
 
-        Ok(None)
+        Ok(())
     }
 }
 
@@ -1213,6 +1345,45 @@ impl DatabaseExt for Backend {
         Ok(())
     }
 
+    fn roll_fork_to_transaction_index(
+        &mut self,
+        id: Option<LocalForkId>,
+        block_hash: B256,
+        index: usize,
+        env: &mut Env,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()> {
+        trace!(?id, ?block_hash, index, "roll fork to transaction index");
+        let id = self.ensure_fork(id)?;
+
+        let block = self.inner.get_fork_by_id(id)?.db.db.get_full_block(block_hash)?;
+        let number = block
+            .header
+            .number
+            .ok_or_else(|| DatabaseError::BlockNotFound(block_hash.into()))?
+            .to::<u64>();
+        let txs = match &block.transactions {
+            BlockTransactions::Full(txs) => txs.clone(),
+            _ => Vec::new(),
+        };
+        if index > txs.len() {
+            eyre::bail!(
+                "transaction index {index} is out of bounds of block {number} with {} transactions",
+                txs.len()
+            )
+        }
+
+        // roll the fork to the state before the block
+        let fork_block = number.saturating_sub(1);
+        self.roll_fork(Some(id), U256::from(fork_block), env, journaled_state)?;
+
+        update_env_block(env, U64::from(fork_block), &block);
+
+        // replay all transactions that came before the index
+        let env = env.clone();
+        self.replay_transactions(id, env, txs.into_iter().take(index), journaled_state)
+    }
+
     fn transact<I: Inspector<Backend>>(
         &mut self,
         maybe_id: Option<LocalForkId>,
@@ -1241,6 +1412,48 @@ impl DatabaseExt for Backend {
         commit_transaction(tx, env, journaled_state, fork, &fork_id, inspector)
     }
 
+    fn simulate<I: Inspector<Backend>>(
+        &self,
+        env: &Env,
+        journaled_state: &JournaledState,
+        inspector: &mut I,
+    ) -> eyre::Result<ResultAndState> {
+        trace!("simulate transaction");
+        // Run on a copy of the backend with the pending changes of the journaled state applied
+        let mut backend = self.clone();
+        backend.commit(journaled_state.state.clone());
+        backend.inspect_ref(&mut env.clone(), inspector)
+    }
+
+    fn copy_account(
+        &mut self,
+        from: LocalForkId,
+        to: LocalForkId,
+        account: Address,
+        journaled_state: &mut JournaledState,
+    ) -> eyre::Result<()> {
+        trace!(?from, ?to, ?account, "copy account");
+        let from_idx = self.inner.ensure_fork_index_by_local_id(from)?;
+        let to_idx = self.inner.ensure_fork_index_by_local_id(to)?;
+        if from_idx == to_idx {
+            return Ok(());
+        }
+
+        // the active fork's changes live in the passed journaled state, not in the fork's own
+        let (info, storage) = {
+            let fork = self.inner.get_fork(from_idx);
+            let source_state =
+                if self.is_active_fork(from) { &*journaled_state } else { &fork.journaled_state };
+            read_account_data(account, &fork.db, source_state)?
+        };
+
+        let is_active = self.is_active_fork(to);
+        let Fork { db, journaled_state: fork_state } = self.inner.get_fork_mut(to_idx);
+        let target_state = if is_active { journaled_state } else { fork_state };
+        write_account_data(account, info, storage, db, target_state)?;
+        Ok(())
+    }
+
     fn active_fork_id(&self) -> Option<LocalForkId> {
         self.active_fork_ids.map(|(id, _)| id)
     }
@@ -1846,6 +2059,61 @@ fn merge_db_account_data<ExtDB: DatabaseRef>(
     fork_db.accounts.insert(addr, acc);
 }
 
+/// Returns the account info, including its code, and all storage slots of the given account that
+/// are known to the `db` or were touched in the `journaled_state`
+fn read_account_data(
+    addr: Address,
+    db: &ForkDB,
+    journaled_state: &JournaledState,
+) -> Result<(AccountInfo, Map<U256, U256>), DatabaseError> {
+    let mut info = db.basic_ref(addr)?.unwrap_or_default();
+    let mut storage = db
+        .accounts
+        .get(&addr)
+        .map(|acc| acc.storage.iter().map(|(slot, value)| (*slot, *value)).collect())
+        .unwrap_or_else(Map::new);
+
+    if let Some(acc) = journaled_state.state.get(&addr) {
+        info = acc.info.clone();
+        storage.extend(acc.storage.iter().map(|(slot, value)| (*slot, value.present_value)));
+    }
+
+    if info.code.is_none() && info.code_hash != KECCAK_EMPTY {
+        info.code = Some(db.code_by_hash_ref(info.code_hash)?);
+    }
+
+    Ok((info, storage))
+}
+
+/// Writes the account info and storage slots into the `db` and, if the account is loaded, into the
+/// `journaled_state`
+fn write_account_data(
+    addr: Address,
+    info: AccountInfo,
+    storage: Map<U256, U256>,
+    db: &mut ForkDB,
+    journaled_state: &mut JournaledState,
+) -> Result<(), DatabaseError> {
+    db.insert_account_info(addr, info.clone());
+    for (slot, value) in storage.iter() {
+        db.insert_account_storage(addr, *slot, *value)?;
+    }
+
+    if let Some(acc) = journaled_state.state.get_mut(&addr) {
+        acc.info = info;
+        for (slot, value) in storage {
+            acc.storage
+                .entry(slot)
+                .and_modify(|s| s.present_value = value)
+                .or_insert_with(|| StorageSlot::new(value));
+        }
+        // ensure the copied state is committed at the end of the call
+        acc.mark_touch();
+    }
+
+    Ok(())
+}
+
 /// Returns true of the address is a contract
 fn is_contract_in_state(journaled_state: &JournaledState, acc: Address) -> bool {
     journaled_state
@@ -1866,6 +2134,13 @@ fn update_env_block(env: &mut Env, fork_block: U64, block: &Block) {
     env.block.number = block.header.number.map(|n| n.to()).unwrap_or(fork_block.to());
 }
 
+/// Returns true if the transaction is a system transaction, e.g. an L2 deposit, which doesn't
+/// contain any pricing info
+fn is_system_transaction(tx: &Transaction) -> bool {
+    is_known_system_sender(tx.from) ||
+        tx.transaction_type.map(|ty| ty.to::<u64>()) == Some(SYSTEM_TRANSACTION_TYPE)
+}
+
 /// Executes the given transaction and commits state changes to the database _and_ the journaled
 /// state, with an optional inspector
 fn commit_transaction<I: Inspector<Backend>>(
@@ -35,3 +35,26 @@ pub const DEFAULT_CREATE2_DEPLOYER: Address = address!("4e59b44847b379578588920c
 pub const DEFAULT_CREATE2_DEPLOYER_CODE: &[u8] = &hex!("604580600e600039806000f350fe7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3");
 /// The runtime code of the default CREATE2 deployer.
 pub const DEFAULT_CREATE2_DEPLOYER_RUNTIME_CODE: &[u8] = &hex!("7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf3");
+/// The signer of the keyless transaction deploying the default CREATE2 deployer.
+pub const DEFAULT_CREATE2_DEPLOYER_SIGNER: Address =
+    address!("3fab184622dc19b6109349b94811493bf2a45362");
+/// The presigned transaction deploying the default CREATE2 deployer on any chain accepting
+/// transactions without replay protection, see <https://github.com/Arachnid/deterministic-deployment-proxy>.
+pub const DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_TX: &[u8] = &hex!("f8a58085174876e800830186a08080b853604580600e600039806000f350fe7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe03601600081602082378035828234f58015156039578182fd5b8082525050506014600cf31ba02222222222222222222222222222222222222222222222222222222222222222a02222222222222222222222222222222222222222222222222222222222222222");
+/// The fee the signer of [`DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_TX`] pays: 100k gas at 100 gwei.
+pub const DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_COST: u128 = 10_000_000_000_000_000;
+
+/// The address of the `ArbSys` precompile of Arbitrum chains.
+pub const ARBSYS_ADDRESS: Address = address!("0000000000000000000000000000000000000064");
+/// The runtime code that stands in for the `ArbSys` precompile in forks of Arbitrum chains.
+///
+/// `arbBlockNumber()` returns the L2 block number stored in slot 0, `arbChainID()` returns the
+/// chain ID and all other calls revert.
+pub const ARBSYS_RUNTIME_CODE: &[u8] = &hex!("60003560e01c8063a3b1b31d14601e578063d127f54a14602a57600080fd5b60005460005260206000f35b4660005260206000f3");
+
+/// The address of the `ArbGasInfo` precompile of Arbitrum chains.
+pub const ARB_GAS_INFO_ADDRESS: Address = address!("000000000000000000000000000000000000006C");
+
+/// The address of the `L1Block` predeploy of OP Stack chains, which holds the values of the latest
+/// L1 block.
+pub const L1_BLOCK_ADDRESS: Address = address!("4200000000000000000000000000000000000015");
@@ -1,6 +1,9 @@
 //! Various utilities to decode test results.
 
-use crate::abi::{Console, Vm};
+use crate::{
+    abi::{Console, Vm},
+    secrets::redact_secrets,
+};
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_json_abi::JsonAbi;
 use alloy_primitives::Log;
@@ -20,7 +23,9 @@ pub fn decode_console_logs(logs: &[Log]) -> Vec<String> {
 /// `console.log`.
 #[instrument(level = "debug", skip_all, fields(topics=?log.data.topics(), data=%log.data.data), ret)]
 pub fn decode_console_log(log: &Log) -> Option<String> {
-    Console::ConsoleEvents::decode_log(log, false).ok().map(|decoded| decoded.to_string())
+    Console::ConsoleEvents::decode_log(log, false)
+        .ok()
+        .map(|decoded| redact_secrets(&decoded.to_string()).into_owned())
 }
 
 /// Tries to decode an error message from the given revert bytes.
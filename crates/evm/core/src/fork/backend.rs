@@ -18,20 +18,26 @@ use revm::{
     db::DatabaseRef,
     primitives::{AccountInfo, Bytecode, KECCAK_EMPTY},
 };
+use serde::Deserialize;
 use std::{
-    collections::{hash_map::Entry, HashMap, VecDeque},
+    cell::Cell,
+    collections::{hash_map::Entry, BTreeMap, HashMap, VecDeque},
     pin::Pin,
     sync::{
         mpsc::{channel as oneshot_channel, Sender as OneshotSender},
         Arc,
     },
+    time::{Duration, Instant},
 };
 
 // Various future/request type aliases
 
 type AccountFuture<Err> =
     Pin<Box<dyn Future<Output = (Result<(U256, U256, Bytes), Err>, Address)> + Send>>;
-type StorageFuture<Err> = Pin<Box<dyn Future<Output = (Result<U256, Err>, Address, U256)> + Send>>;
+type StorageFuture<Err> =
+    Pin<Box<dyn Future<Output = (Result<Vec<U256>, Err>, Address, Vec<U256>)> + Send>>;
+type PrefetchFuture<Err> =
+    Pin<Box<dyn Future<Output = (Result<Vec<(U256, U256)>, Err>, Address)> + Send>>;
 type BlockHashFuture<Err> = Pin<Box<dyn Future<Output = (Result<B256, Err>, u64)> + Send>>;
 type FullBlockFuture<Err> =
     Pin<Box<dyn Future<Output = (FullBlockSender, Result<Option<Block>, Err>, BlockId)> + Send>>;
@@ -48,6 +54,7 @@ type TransactionSender = OneshotSender<DatabaseResult<Transaction>>;
 enum ProviderRequest<Err> {
     Account(AccountFuture<Err>),
     Storage(StorageFuture<Err>),
+    Prefetch(PrefetchFuture<Err>),
     BlockHash(BlockHashFuture<Err>),
     FullBlock(FullBlockFuture<Err>),
     Transaction(TransactionFuture<Err>),
@@ -85,6 +92,11 @@ pub struct BackendHandler<P> {
     account_requests: HashMap<Address, Vec<AccountInfoSender>>,
     /// Listeners that wait for a `get_storage_at` response
     storage_requests: HashMap<(Address, U256), Vec<StorageSender>>,
+    /// Storage slots that were requested but not yet sent to the provider, grouped by account so
+    /// that they can be fetched in a single request
+    queued_storage: HashMap<Address, Vec<U256>>,
+    /// Whether to prefetch the entire storage of a contract when its account is first fetched
+    prefetch_storage: bool,
     /// Listeners that wait for a `get_block` response
     block_requests: HashMap<u64, Vec<BlockHashSender>>,
     /// Incoming commands.
@@ -112,6 +124,8 @@ where
             pending_requests: Default::default(),
             account_requests: Default::default(),
             storage_requests: Default::default(),
+            queued_storage: Default::default(),
+            prefetch_storage: false,
             block_requests: Default::default(),
             queued_requests: Default::default(),
             incoming: rx,
@@ -119,6 +133,16 @@ where
         }
     }
 
+    /// Sets whether the entire storage of a contract should be prefetched when its account is first
+    /// fetched.
+    ///
+    /// This requires the `debug_storageRangeAt` endpoint and a pinned block that is not the chain's
+    /// head, otherwise slots are only fetched on demand.
+    pub fn with_storage_prefetch(mut self, prefetch_storage: bool) -> Self {
+        self.prefetch_storage = prefetch_storage;
+        self
+    }
+
     /// handle the request in queue in the future.
     ///
     /// We always check:
@@ -174,20 +198,39 @@ where
                 entry.get_mut().push(listener);
             }
             Entry::Vacant(entry) => {
-                trace!(target: "backendhandler", %address, %idx, "preparing storage request");
+                trace!(target: "backendhandler", %address, %idx, "queueing storage request");
                 entry.insert(vec![listener]);
-                let provider = self.provider.clone();
-                let block_id = self.block_id;
-                let fut = Box::pin(async move {
-                    let storage =
-                        provider.get_storage_at(address, idx, block_id).await.map_err(Into::into);
-                    (storage, address, idx)
-                });
-                self.pending_requests.push(ProviderRequest::Storage(fut));
+                self.queued_storage.entry(address).or_default().push(idx);
             }
         }
     }
 
+    /// Sends all queued storage requests to the provider, one request per account
+    fn dispatch_storage_requests(&mut self) {
+        for (address, slots) in self.queued_storage.drain() {
+            trace!(target: "backendhandler", %address, slots = slots.len(), "preparing storage request");
+            let provider = self.provider.clone();
+            let block_id = self.block_id;
+            let fut = Box::pin(async move {
+                let storage = fetch_storage(&provider, address, &slots, block_id).await;
+                (storage, address, slots)
+            });
+            self.pending_requests.push(ProviderRequest::Storage(fut));
+        }
+    }
+
+    /// process a request for prefetching the entire storage of a contract
+    fn request_storage_prefetch(&mut self, address: Address) {
+        trace!(target: "backendhandler", %address, "preparing storage prefetch request");
+        let provider = self.provider.clone();
+        let block_id = self.block_id;
+        let fut = Box::pin(async move {
+            let storage = prefetch_storage(&provider, address, block_id).await;
+            (storage, address)
+        });
+        self.pending_requests.push(ProviderRequest::Prefetch(fut));
+    }
+
     /// returns the future that fetches the account data
     fn get_account_req(&self, address: Address) -> ProviderRequest<eyre::Report> {
         trace!(target: "backendhandler", "preparing account request, address={:?}", address);
@@ -296,6 +339,8 @@ where
             while let Some(req) = pin.queued_requests.pop_front() {
                 pin.on_request(req)
             }
+            // storage requests that arrived together are fetched together
+            pin.dispatch_storage_requests();
 
             // receive new requests to delegate to the underlying provider
             loop {
@@ -350,6 +395,10 @@ where
                             };
                             pin.db.accounts().write().insert(addr, acc.clone());
 
+                            if pin.prefetch_storage && code_hash != KECCAK_EMPTY {
+                                pin.request_storage_prefetch(addr);
+                            }
+
                             // notify all listeners
                             if let Some(listeners) = pin.account_requests.remove(&addr) {
                                 listeners.into_iter().for_each(|l| {
@@ -360,35 +409,64 @@ where
                         }
                     }
                     ProviderRequest::Storage(fut) => {
-                        if let Poll::Ready((resp, addr, idx)) = fut.poll_unpin(cx) {
-                            let value = match resp {
-                                Ok(value) => value,
+                        if let Poll::Ready((resp, addr, slots)) = fut.poll_unpin(cx) {
+                            let values = match resp {
+                                Ok(values) => values,
                                 Err(err) => {
                                     // notify all listeners
                                     let err = Arc::new(err);
-                                    if let Some(listeners) =
-                                        pin.storage_requests.remove(&(addr, idx))
-                                    {
-                                        listeners.into_iter().for_each(|l| {
-                                            let _ = l.send(Err(DatabaseError::GetStorage(
-                                                addr,
-                                                idx,
-                                                Arc::clone(&err),
-                                            )));
-                                        })
+                                    for idx in slots {
+                                        if let Some(listeners) =
+                                            pin.storage_requests.remove(&(addr, idx))
+                                        {
+                                            listeners.into_iter().for_each(|l| {
+                                                let _ = l.send(Err(DatabaseError::GetStorage(
+                                                    addr,
+                                                    idx,
+                                                    Arc::clone(&err),
+                                                )));
+                                            })
+                                        }
                                     }
                                     continue;
                                 }
                             };
 
-                            // update the cache
-                            pin.db.storage().write().entry(addr).or_default().insert(idx, value);
-
-                            // notify all listeners
-                            if let Some(listeners) = pin.storage_requests.remove(&(addr, idx)) {
-                                listeners.into_iter().for_each(|l| {
-                                    let _ = l.send(Ok(value));
-                                })
+                            for (idx, value) in slots.into_iter().zip(values) {
+                                // update the cache
+                                pin.db
+                                    .storage()
+                                    .write()
+                                    .entry(addr)
+                                    .or_default()
+                                    .insert(idx, value);
+
+                                // notify all listeners
+                                if let Some(listeners) = pin.storage_requests.remove(&(addr, idx)) {
+                                    listeners.into_iter().for_each(|l| {
+                                        let _ = l.send(Ok(value));
+                                    })
+                                }
+                            }
+                            continue;
+                        }
+                    }
+                    ProviderRequest::Prefetch(fut) => {
+                        if let Poll::Ready((resp, addr)) = fut.poll_unpin(cx) {
+                            match resp {
+                                Ok(slots) => {
+                                    trace!(target: "backendhandler", %addr, slots = slots.len(), "prefetched storage");
+                                    // slots fetched in the meantime are already up to date
+                                    let mut storage = pin.db.storage().write();
+                                    let account = storage.entry(addr).or_default();
+                                    for (idx, value) in slots {
+                                        account.entry(idx).or_insert(value);
+                                    }
+                                }
+                                Err(err) => {
+                                    // slots are still fetched on demand
+                                    debug!(target: "backendhandler", %err, %addr, "failed to prefetch storage");
+                                }
                             }
                             continue;
                         }
@@ -465,6 +543,100 @@ where
     }
 }
 
+/// The maximum number of slots requested per `debug_storageRangeAt` call.
+const PREFETCH_PAGE_SIZE: usize = 1024;
+
+/// The maximum number of slots prefetched per contract, so that huge contracts (e.g. popular
+/// tokens) are not downloaded entirely.
+const MAX_PREFETCH_SLOTS: usize = 8 * PREFETCH_PAGE_SIZE;
+
+/// Fetches the given storage slots of an account.
+///
+/// Multiple slots are fetched with a single `eth_getProof` request, falling back to concurrent
+/// `eth_getStorageAt` requests if the endpoint doesn't support it.
+async fn fetch_storage<P: TempProvider>(
+    provider: &P,
+    address: Address,
+    slots: &[U256],
+    block_id: Option<BlockId>,
+) -> eyre::Result<Vec<U256>> {
+    if let [idx] = slots {
+        return Ok(vec![provider.get_storage_at(address, *idx, block_id).await?]);
+    }
+
+    let keys = slots.iter().map(|idx| B256::from(*idx)).collect();
+    match provider.get_proof(address, keys, block_id).await {
+        Ok(proof)
+            if proof.storage_proof.len() == slots.len() &&
+                proof
+                    .storage_proof
+                    .iter()
+                    .zip(slots)
+                    .all(|(proof, idx)| proof.key.0 == B256::from(*idx)) =>
+        {
+            Ok(proof.storage_proof.into_iter().map(|proof| proof.value).collect())
+        }
+        res => {
+            if let Err(err) = res {
+                trace!(target: "backendhandler", %err, %address, "eth_getProof failed, falling back to eth_getStorageAt");
+            }
+            let requests = slots.iter().map(|idx| provider.get_storage_at(address, *idx, block_id));
+            Ok(futures::future::try_join_all(requests).await?)
+        }
+    }
+}
+
+/// The response of `debug_storageRangeAt`
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageRange {
+    storage: BTreeMap<B256, StorageRangeEntry>,
+    next_key: Option<B256>,
+}
+
+/// A slot returned by `debug_storageRangeAt`, keyed by the hash of the slot
+#[derive(Deserialize)]
+struct StorageRangeEntry {
+    /// The slot, only known if the node stores the preimages of storage keys
+    key: Option<U256>,
+    value: U256,
+}
+
+/// Fetches up to [`MAX_PREFETCH_SLOTS`] storage slots of the account with
+/// `debug_storageRangeAt`.
+///
+/// The state at the end of the pinned block is the state before the first transaction of the next
+/// block, so nothing is fetched if the pinned block is the chain's head.
+async fn prefetch_storage<P: TempProvider>(
+    provider: &P,
+    address: Address,
+    block_id: Option<BlockId>,
+) -> eyre::Result<Vec<(U256, U256)>> {
+    let Some(BlockId::Number(BlockNumberOrTag::Number(number))) = block_id else {
+        return Ok(Vec::new());
+    };
+    let Some(next_block) = provider.get_block_by_number((number + 1).into(), false).await? else {
+        return Ok(Vec::new());
+    };
+    let block_hash = next_block.header.hash.ok_or_else(|| eyre::eyre!("block without hash"))?;
+
+    let mut slots = Vec::new();
+    let mut start = B256::ZERO;
+    loop {
+        let params = serde_json::json!([block_hash, 0, address, start, PREFETCH_PAGE_SIZE]);
+        let range: StorageRange = provider.raw_request("debug_storageRangeAt", params).await?;
+        // slots without a known preimage can't be looked up later and are skipped
+        slots.extend(
+            range.storage.into_values().filter_map(|entry| Some((entry.key?, entry.value))),
+        );
+        match range.next_key {
+            Some(next) if slots.len() < MAX_PREFETCH_SLOTS => start = next,
+            _ => break,
+        }
+    }
+    Ok(slots)
+}
+
 /// A cloneable backend type that shares access to the backend data with all its clones.
 ///
 /// This backend type is connected to the `BackendHandler` via a mpsc channel. The `BackendHandler`
@@ -504,6 +676,25 @@ pub struct SharedBackend {
     cache: Arc<FlushJsonBlockCacheDB>,
 }
 
+thread_local! {
+    /// The time the current thread has spent waiting on requests to a [`SharedBackend`].
+    static REQUEST_TIME: Cell<Duration> = const { Cell::new(Duration::ZERO) };
+}
+
+/// Returns the total time the current thread has spent waiting on requests to a
+/// [`SharedBackend`], which is mostly spent on RPC requests to the forked endpoint.
+pub fn thread_request_time() -> Duration {
+    REQUEST_TIME.with(Cell::get)
+}
+
+/// Blocks on a request to the [`BackendHandler`], recording the time spent waiting on it.
+fn timed_request<T>(f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = tokio::task::block_in_place(f);
+    REQUEST_TIME.with(|time| time.set(time.get() + start.elapsed()));
+    result
+}
+
 impl SharedBackend {
     /// _Spawns_ a new `BackendHandler` on a `tokio::task` that listens for requests from any
     /// `SharedBackend`. Missing values get inserted in the `db`.
@@ -576,7 +767,7 @@ impl SharedBackend {
 
     /// Returns the full block for the given block identifier
     pub fn get_full_block(&self, block: impl Into<BlockId>) -> DatabaseResult<Block> {
-        tokio::task::block_in_place(|| {
+        timed_request(|| {
             let (sender, rx) = oneshot_channel();
             let req = BackendRequest::FullBlock(block.into(), sender);
             self.backend.clone().try_send(req)?;
@@ -586,7 +777,7 @@ impl SharedBackend {
 
     /// Returns the transaction for the hash
     pub fn get_transaction(&self, tx: B256) -> DatabaseResult<Transaction> {
-        tokio::task::block_in_place(|| {
+        timed_request(|| {
             let (sender, rx) = oneshot_channel();
             let req = BackendRequest::Transaction(tx, sender);
             self.backend.clone().try_send(req)?;
@@ -595,7 +786,7 @@ impl SharedBackend {
     }
 
     fn do_get_basic(&self, address: Address) -> DatabaseResult<Option<AccountInfo>> {
-        tokio::task::block_in_place(|| {
+        timed_request(|| {
             let (sender, rx) = oneshot_channel();
             let req = BackendRequest::Basic(address, sender);
             self.backend.clone().try_send(req)?;
@@ -604,7 +795,7 @@ impl SharedBackend {
     }
 
     fn do_get_storage(&self, address: Address, index: U256) -> DatabaseResult<U256> {
-        tokio::task::block_in_place(|| {
+        timed_request(|| {
             let (sender, rx) = oneshot_channel();
             let req = BackendRequest::Storage(address, index, sender);
             self.backend.clone().try_send(req)?;
@@ -613,7 +804,7 @@ impl SharedBackend {
     }
 
     fn do_get_block_hash(&self, number: u64) -> DatabaseResult<B256> {
-        tokio::task::block_in_place(|| {
+        timed_request(|| {
             let (sender, rx) = oneshot_channel();
             let req = BackendRequest::BlockHash(number, sender);
             self.backend.clone().try_send(req)?;
@@ -729,6 +920,44 @@ mod tests {
         assert_eq!(slots.len() as u64, max_slots);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn concurrent_storage_requests() {
+        let provider = get_http_provider(ENDPOINT);
+        let block_num = provider.get_block_number().await.unwrap();
+        let meta = BlockchainDbMeta {
+            cfg_env: Default::default(),
+            block_env: Default::default(),
+            hosts: BTreeSet::from([ENDPOINT.to_string()]),
+        };
+
+        let db = BlockchainDb::new(meta, None);
+        let backend = SharedBackend::spawn_backend(
+            Arc::new(provider.clone()),
+            db.clone(),
+            Some(block_num.into()),
+        )
+        .await;
+
+        // WETH
+        let address: Address = "C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2".parse().unwrap();
+
+        // requests arriving together are fetched with a single request
+        let handles = (0..8u64)
+            .map(|i| {
+                let backend = backend.clone();
+                std::thread::spawn(move || backend.storage_ref(address, U256::from(i)).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let values = handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>();
+
+        for (i, value) in values.into_iter().enumerate() {
+            let expected =
+                provider.get_storage_at(address, U256::from(i), Some(block_num.into())).await;
+            assert_eq!(value, expected.unwrap());
+        }
+        assert_eq!(db.storage().read().get(&address).unwrap().len(), 8);
+    }
+
     #[test]
     fn can_read_cache() {
         let cache_path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/storage.json");
@@ -776,7 +1005,7 @@ mod tests {
 
         let db = BlockchainDb::new(
             meta,
-            Some(Config::foundry_block_cache_dir(NamedChain::Mainnet, block_num).unwrap()),
+            Some(Config::foundry_block_cache_file(NamedChain::Mainnet, block_num).unwrap()),
         );
         assert!(db.accounts().read().contains_key(&address));
         assert!(db.storage().read().contains_key(&address));
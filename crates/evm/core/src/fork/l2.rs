@@ -0,0 +1,86 @@
+//! Chain specific state of forks of L2 chains.
+
+use super::BlockchainDb;
+use crate::constants::{ARBSYS_ADDRESS, ARBSYS_RUNTIME_CODE};
+use alloy_primitives::{keccak256, Bytes, U256};
+use alloy_rpc_types::Block;
+use foundry_config::NamedChain;
+use revm::primitives::{AccountInfo, Bytecode};
+
+/// The kind of L2 chain a fork is of, which determines where its L1 context is found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L2Chain {
+    /// An OP Stack chain, whose latest L1 block is stored in the `L1Block` predeploy
+    OpStack,
+    /// An Arbitrum chain, whose `block.number` is the L1 block number and whose L2 block number is
+    /// returned by the `ArbSys` precompile
+    Arbitrum,
+}
+
+impl L2Chain {
+    /// Returns the kind of the chain with the given ID, if it's a known L2 chain.
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        match NamedChain::try_from(chain_id).ok()? {
+            NamedChain::Optimism |
+            NamedChain::OptimismGoerli |
+            NamedChain::OptimismSepolia |
+            NamedChain::Base |
+            NamedChain::BaseGoerli |
+            NamedChain::BaseSepolia => Some(Self::OpStack),
+            NamedChain::Arbitrum |
+            NamedChain::ArbitrumGoerli |
+            NamedChain::ArbitrumNova |
+            NamedChain::ArbitrumSepolia |
+            NamedChain::ArbitrumTestnet => Some(Self::Arbitrum),
+            _ => None,
+        }
+    }
+}
+
+/// Populates the state of the fork of the given block with the chain specific context that isn't
+/// available over RPC.
+///
+/// The predeploys of OP Stack chains are regular contracts, whose state is forked as is. The
+/// `ArbSys` precompile of Arbitrum chains is stood in for by a contract that returns the block's L2
+/// block number.
+pub fn init_l2_state(db: &BlockchainDb, chain_id: u64, block: &Block) {
+    if L2Chain::from_chain_id(chain_id) != Some(L2Chain::Arbitrum) {
+        return
+    }
+    let Some(number) = block.header.number else { return };
+
+    let code = Bytes::from_static(ARBSYS_RUNTIME_CODE);
+    let info = AccountInfo {
+        balance: U256::ZERO,
+        nonce: 0,
+        code_hash: keccak256(&code),
+        code: Some(Bytecode::new_raw(code).to_checked()),
+    };
+    trace!(target: "fork", %number, "initialising ArbSys");
+    db.accounts().write().insert(ARBSYS_ADDRESS, info);
+    db.storage().write().entry(ARBSYS_ADDRESS).or_default().insert(U256::ZERO, number);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fork::BlockchainDbMeta;
+
+    #[test]
+    fn stands_in_for_arbsys() {
+        let mut block = Block::default();
+        block.header.number = Some(U256::from(100));
+
+        let db = BlockchainDb::new(BlockchainDbMeta::new(Default::default(), String::new()), None);
+        init_l2_state(&db, 42161, &block);
+        assert_eq!(db.accounts().read()[&ARBSYS_ADDRESS].code_hash, keccak256(ARBSYS_RUNTIME_CODE));
+        assert_eq!(db.storage().read()[&ARBSYS_ADDRESS][&U256::ZERO], U256::from(100));
+
+        let db = BlockchainDb::new(BlockchainDbMeta::new(Default::default(), String::new()), None);
+        init_l2_state(&db, 10, &block);
+        assert!(db.accounts().read().is_empty());
+
+        assert_eq!(L2Chain::from_chain_id(10), Some(L2Chain::OpStack));
+        assert_eq!(L2Chain::from_chain_id(1), None);
+    }
+}
@@ -2,11 +2,14 @@ use super::opts::EvmOpts;
 use revm::primitives::Env;
 
 mod backend;
-pub use backend::{BackendHandler, SharedBackend};
+pub use backend::{thread_request_time, BackendHandler, SharedBackend};
 
 mod init;
 pub use init::environment;
 
+mod l2;
+pub use l2::{init_l2_state, L2Chain};
+
 mod cache;
 pub use cache::{BlockchainDb, BlockchainDbMeta, JsonBlockCacheDB, MemDb};
 
@@ -3,11 +3,13 @@
 //! The design is similar to the single `SharedBackend`, `BackendHandler` but supports multiple
 //! concurrently active pairs at once.
 
-use crate::fork::{BackendHandler, BlockchainDb, BlockchainDbMeta, CreateFork, SharedBackend};
+use crate::fork::{
+    init_l2_state, BackendHandler, BlockchainDb, BlockchainDbMeta, CreateFork, SharedBackend,
+};
 use alloy_providers::provider::Provider;
 use alloy_transport::BoxTransport;
 use foundry_common::provider::alloy::ProviderBuilder;
-use foundry_config::Config;
+use foundry_config::{cache::ForkCacheEntry, Config};
 use futures::{
     channel::mpsc::{channel, Receiver, Sender},
     stream::{Fuse, Stream},
@@ -489,6 +491,7 @@ async fn create_fork(mut fork: CreateFork) -> eyre::Result<(CreatedFork, Handler
             .maybe_max_retry(fork.evm_opts.fork_retries)
             .maybe_initial_backoff(fork.evm_opts.fork_retry_backoff)
             .compute_units_per_second(fork.evm_opts.get_compute_units_per_second())
+            .cassette(fork.evm_opts.rpc_cassette.clone())
             .build()?,
     );
 
@@ -503,13 +506,26 @@ async fn create_fork(mut fork: CreateFork) -> eyre::Result<(CreatedFork, Handler
 
     // determine the cache path if caching is enabled
     let cache_path = if fork.enable_caching {
-        Config::foundry_block_cache_dir(meta.cfg_env.chain_id, number.to::<u64>())
+        let chain = meta.cfg_env.chain_id;
+        let block = number.to::<u64>();
+        if let Some(dir) = Config::foundry_block_cache_dir(chain, block) {
+            let _ = ForkCacheEntry::touch(&dir);
+            if let Some(max_size) = fork.evm_opts.fork_cache_max_size {
+                if let Err(err) = Config::evict_fork_cache(max_size, Some(&dir)) {
+                    warn!(target: "fork", %err, "failed to evict fork caches");
+                }
+            }
+        }
+        Config::foundry_block_cache_file(chain, block)
     } else {
         None
     };
 
+    let chain_id = meta.cfg_env.chain_id;
     let db = BlockchainDb::new(meta, cache_path);
+    init_l2_state(&db, chain_id, &block);
     let (backend, handler) = SharedBackend::new(provider, db, Some(number.to::<u64>().into()));
+    let handler = handler.with_storage_prefetch(fork.evm_opts.fork_prefetch_storage);
     let fork = CreatedFork::new(fork, backend);
     Ok((fork, handler))
 }
@@ -10,11 +10,14 @@ extern crate tracing;
 mod ic;
 
 pub mod abi;
+pub mod access_list;
 pub mod backend;
 pub mod constants;
 pub mod debug;
 pub mod decode;
 pub mod fork;
 pub mod opts;
+pub mod precompiles;
+pub mod secrets;
 pub mod snapshot;
 pub mod utils;
@@ -6,11 +6,14 @@ use alloy_rpc_types::Block;
 use eyre::WrapErr;
 use foundry_common::{
     self,
-    provider::alloy::{ProviderBuilder, RpcUrl},
+    provider::{
+        alloy::{ProviderBuilder, RpcUrl},
+        cassette::RpcCassetteConfig,
+    },
     ALCHEMY_FREE_TIER_CUPS,
 };
 use foundry_compilers::utils::RuntimeOrHandle;
-use foundry_config::{Chain, Config};
+use foundry_config::{Chain, Config, GasOverrides};
 use revm::primitives::{BlockEnv, CfgEnv, SpecId, TxEnv};
 use serde::{Deserialize, Deserializer, Serialize};
 
@@ -44,6 +47,16 @@ pub struct EvmOpts {
     /// Disables storage caching entirely.
     pub no_storage_caching: bool,
 
+    /// Prefetches the storage of forked contracts when they are first accessed.
+    pub fork_prefetch_storage: bool,
+
+    /// The maximum size of all fork caches in bytes.
+    pub fork_cache_max_size: Option<u64>,
+
+    /// Records fork RPC responses to, or replays them from, cassettes.
+    #[serde(skip)]
+    pub rpc_cassette: Option<RpcCassetteConfig>,
+
     /// The initial balance of each deployed test contract.
     pub initial_balance: U256,
 
@@ -59,6 +72,12 @@ pub struct EvmOpts {
     /// The memory limit per EVM execution in bytes.
     /// If this limit is exceeded, a `MemoryLimitOOG` result is thrown.
     pub memory_limit: u64,
+
+    /// The sets of chain specific precompiles to add to the EVM.
+    pub precompiles: Vec<String>,
+
+    /// Overrides of the gas costs of the EVM version.
+    pub gas_overrides: GasOverrides,
 }
 
 impl EvmOpts {
@@ -83,6 +102,7 @@ impl EvmOpts {
         let fork_url = fork_url.as_ref();
         let provider = ProviderBuilder::new(fork_url)
             .compute_units_per_second(self.get_compute_units_per_second())
+            .cassette(self.rpc_cassette.clone())
             .build()?;
         environment(
             &provider,
@@ -191,6 +211,7 @@ impl EvmOpts {
             trace!(?url, "retrieving chain via eth_chainId");
             let provider = ProviderBuilder::new(url.as_str())
                 .compute_units_per_second(self.get_compute_units_per_second())
+                .cassette(self.rpc_cassette.clone())
                 .build()
                 .ok()
                 .unwrap_or_else(|| panic!("Failed to establish provider to {url}"));
@@ -0,0 +1,193 @@
+//! Precompiles of specific chains that aren't part of the EVM.
+//!
+//! Registered precompiles are run natively in place of calls to their addresses, so that contracts
+//! calling them can be tested without forking the chain.
+
+use crate::constants::{ARBSYS_ADDRESS, ARB_GAS_INFO_ADDRESS};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::{sol, SolInterface, SolValue};
+use revm::{
+    interpreter::{CallInputs, Gas, InstructionResult},
+    primitives::{Env, KECCAK_EMPTY},
+    Database, EVMData, Inspector,
+};
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// A precompile of a specific chain.
+pub trait ChainPrecompile: fmt::Debug + Send + Sync {
+    /// Returns the address the precompile is called at.
+    fn address(&self) -> Address;
+
+    /// Returns the gas charged for a call with the given input.
+    fn gas_cost(&self, _input: &[u8]) -> u64 {
+        0
+    }
+
+    /// Runs the precompile, returning its output or, if it reverts, its revert data.
+    fn run(&self, input: &[u8], env: &Env) -> Result<Bytes, Bytes>;
+}
+
+/// A set of [`ChainPrecompile`]s, by address.
+///
+/// This is also the inspector that runs them. Accounts with code, such as the stand-ins of forks,
+/// take precedence over the registered precompiles.
+#[derive(Clone, Debug, Default)]
+pub struct ChainPrecompiles {
+    precompiles: HashMap<Address, Arc<dyn ChainPrecompile>>,
+}
+
+impl ChainPrecompiles {
+    /// Creates an empty set of precompiles.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates the union of the prebuilt sets of precompiles with the given names.
+    ///
+    /// The available sets are `arbitrum`.
+    pub fn from_names(names: &[impl AsRef<str>]) -> eyre::Result<Self> {
+        let mut precompiles = Self::new();
+        for name in names {
+            match name.as_ref() {
+                "arbitrum" => {
+                    precompiles.register(ArbSys);
+                    precompiles.register(ArbGasInfo);
+                }
+                name => eyre::bail!("unknown set of precompiles `{name}`, expected `arbitrum`"),
+            }
+        }
+        Ok(precompiles)
+    }
+
+    /// Registers a precompile, replacing the one at the same address, if any.
+    pub fn register(&mut self, precompile: impl ChainPrecompile + 'static) {
+        self.precompiles.insert(precompile.address(), Arc::new(precompile));
+    }
+
+    /// Returns the precompile at the given address, if any.
+    pub fn get(&self, address: &Address) -> Option<&dyn ChainPrecompile> {
+        self.precompiles.get(address).map(|precompile| &**precompile)
+    }
+
+    /// Returns true if no precompiles are registered.
+    pub fn is_empty(&self) -> bool {
+        self.precompiles.is_empty()
+    }
+}
+
+impl<DB: Database> Inspector<DB> for ChainPrecompiles {
+    fn call(
+        &mut self,
+        data: &mut EVMData<'_, DB>,
+        call: &mut CallInputs,
+    ) -> (InstructionResult, Gas, Bytes) {
+        let gas = Gas::new(call.gas_limit);
+        let Some(precompile) = self.get(&call.contract) else {
+            return (InstructionResult::Continue, gas, Bytes::new())
+        };
+        let has_code = data
+            .journaled_state
+            .load_account(call.contract, data.db)
+            .map_or(false, |(account, _)| account.info.code_hash != KECCAK_EMPTY);
+        if has_code {
+            return (InstructionResult::Continue, gas, Bytes::new())
+        }
+
+        let mut gas = gas;
+        if !gas.record_cost(precompile.gas_cost(&call.input)) {
+            return (InstructionResult::PrecompileOOG, gas, Bytes::new())
+        }
+        match precompile.run(&call.input, data.env) {
+            Ok(output) => (InstructionResult::Return, gas, output),
+            Err(revert) => (InstructionResult::Revert, gas, revert),
+        }
+    }
+}
+
+sol! {
+    interface IArbSys {
+        function arbBlockNumber() external view returns (uint256);
+        function arbChainID() external view returns (uint256);
+        function wasMyCallersAddressAliased() external view returns (bool);
+    }
+
+    interface IArbGasInfo {
+        function getPricesInWei() external view returns (uint256, uint256, uint256, uint256, uint256, uint256);
+        function getMinimumGasPrice() external view returns (uint256);
+        function getL1BaseFeeEstimate() external view returns (uint256);
+        function getCurrentTxL1GasFees() external view returns (uint256);
+    }
+}
+
+/// The `ArbSys` precompile of Arbitrum chains.
+///
+/// The L2 block number is `block.number`, and no caller's address is aliased.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArbSys;
+
+impl ChainPrecompile for ArbSys {
+    fn address(&self) -> Address {
+        ARBSYS_ADDRESS
+    }
+
+    fn run(&self, input: &[u8], env: &Env) -> Result<Bytes, Bytes> {
+        let output =
+            match IArbSys::IArbSysCalls::abi_decode(input, false).map_err(|_| Bytes::new())? {
+                IArbSys::IArbSysCalls::arbBlockNumber(_) => env.block.number.abi_encode(),
+                IArbSys::IArbSysCalls::arbChainID(_) => U256::from(env.cfg.chain_id).abi_encode(),
+                IArbSys::IArbSysCalls::wasMyCallersAddressAliased(_) => false.abi_encode(),
+            };
+        Ok(output.into())
+    }
+}
+
+/// The `ArbGasInfo` precompile of Arbitrum chains.
+///
+/// The price of L2 gas is the base fee, and L1 gas is free.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ArbGasInfo;
+
+impl ChainPrecompile for ArbGasInfo {
+    fn address(&self) -> Address {
+        ARB_GAS_INFO_ADDRESS
+    }
+
+    fn run(&self, input: &[u8], env: &Env) -> Result<Bytes, Bytes> {
+        let basefee = env.block.basefee;
+        let output = match IArbGasInfo::IArbGasInfoCalls::abi_decode(input, false)
+            .map_err(|_| Bytes::new())?
+        {
+            IArbGasInfo::IArbGasInfoCalls::getPricesInWei(_) => {
+                (U256::ZERO, U256::ZERO, U256::ZERO, basefee, U256::ZERO, basefee).abi_encode()
+            }
+            IArbGasInfo::IArbGasInfoCalls::getMinimumGasPrice(_) => basefee.abi_encode(),
+            IArbGasInfo::IArbGasInfoCalls::getL1BaseFeeEstimate(_) |
+            IArbGasInfo::IArbGasInfoCalls::getCurrentTxL1GasFees(_) => U256::ZERO.abi_encode(),
+        };
+        Ok(output.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_sol_types::SolCall;
+
+    #[test]
+    fn runs_arbitrum_precompiles() {
+        let precompiles = ChainPrecompiles::from_names(&["arbitrum"]).unwrap();
+        let mut env = Env::default();
+        env.cfg.chain_id = 42161;
+        env.block.number = U256::from(100);
+
+        let arbsys = precompiles.get(&ARBSYS_ADDRESS).unwrap();
+        let output = arbsys.run(&IArbSys::arbChainIDCall {}.abi_encode(), &env).unwrap();
+        assert_eq!(U256::abi_decode(&output, true).unwrap(), U256::from(42161));
+        let output = arbsys.run(&IArbSys::arbBlockNumberCall {}.abi_encode(), &env).unwrap();
+        assert_eq!(U256::abi_decode(&output, true).unwrap(), U256::from(100));
+        assert!(arbsys.run(&[0xde, 0xad, 0xbe, 0xef], &env).is_err());
+
+        assert!(precompiles.get(&ARB_GAS_INFO_ADDRESS).is_some());
+        assert!(ChainPrecompiles::from_names(&["zksync"]).is_err());
+    }
+}
@@ -0,0 +1,73 @@
+//! Redaction of the secrets resolved with `vm.getSecret`.
+//!
+//! Secrets are registered process-wide when they are resolved, since they can end up in outputs
+//! which are rendered long after the executor that resolved them is gone, such as traces, console
+//! logs and broadcast files.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use std::borrow::Cow;
+
+/// The placeholder secrets are replaced with.
+pub const REDACTED_SECRET: &str = "<secret>";
+
+/// The secrets resolved so far.
+static SECRETS: Lazy<RwLock<Vec<String>>> = Lazy::new(Default::default);
+
+/// Registers a resolved secret, so that it is redacted from every output from now on.
+pub fn register_secret(secret: &str) {
+    if secret.is_empty() {
+        return
+    }
+    let mut secrets = SECRETS.write();
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// Replaces every occurrence of a registered secret in `s`, as text or hex encoded, with
+/// [`REDACTED_SECRET`].
+pub fn redact_secrets(s: &str) -> Cow<'_, str> {
+    redact(s, |_| REDACTED_SECRET.to_string())
+}
+
+/// Redacts the registered secrets from serialized JSON, like [`redact_secrets`], but replaces hex
+/// encoded secrets with zeros, so that hex values such as calldata remain valid.
+pub fn redact_secrets_in_json(s: &str) -> Cow<'_, str> {
+    redact(s, |hex| "0".repeat(hex.len()))
+}
+
+fn redact(s: &str, hex_placeholder: impl Fn(&str) -> String) -> Cow<'_, str> {
+    let secrets = SECRETS.read();
+    let mut redacted = Cow::Borrowed(s);
+    for secret in secrets.iter() {
+        // hex encoded secrets are replaced first, since the text of a secret can be valid hex
+        let hex = hex::encode(secret);
+        if redacted.contains(&hex) {
+            redacted = Cow::Owned(redacted.replace(&hex, &hex_placeholder(&hex)));
+        }
+        if redacted.contains(secret.as_str()) {
+            redacted = Cow::Owned(redacted.replace(secret.as_str(), REDACTED_SECRET));
+        }
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_registered_secrets() {
+        assert_eq!(redact_secrets("token: unregistered-token"), "token: unregistered-token");
+
+        register_secret("sup3r-s3cret");
+        let hex = hex::encode("sup3r-s3cret");
+        assert_eq!(redact_secrets("token: sup3r-s3cret"), "token: <secret>");
+        assert_eq!(redact_secrets(&format!("data: 0x{hex}00")), "data: 0x<secret>00");
+        assert_eq!(
+            redact_secrets_in_json(&format!(r#"{{"input":"0x{hex}","args":["sup3r-s3cret"]}}"#)),
+            format!(r#"{{"input":"0x{}","args":["<secret>"]}}"#, "0".repeat(hex.len()))
+        );
+    }
+}
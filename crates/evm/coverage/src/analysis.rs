@@ -1,4 +1,4 @@
-use super::{ContractId, CoverageItem, CoverageItemKind, SourceLocation};
+use super::{BranchAnchor, ContractId, CoverageItem, CoverageItemKind, SourceLocation};
 use foundry_common::TestFunctionExt;
 use foundry_compilers::artifacts::ast::{self, Ast, Node, NodeType};
 use semver::Version;
@@ -15,8 +15,14 @@ pub struct ContractVisitor<'a> {
     /// The name of the contract being walked.
     contract_name: String,
 
+    /// The lines excluded from coverage by `forge-coverage` comments
+    ignored_lines: HashSet<usize>,
+
     /// The current branch ID
     branch_id: usize,
+    /// The branch IDs and source ranges of the `require` and `assert` calls in the statement
+    /// being walked, whose paths are pushed once the statement following it is known
+    pending_requires: Vec<(usize, ast::LowFidelitySourceLocation)>,
     /// Stores the last line we put in the items collection to ensure we don't push duplicate lines
     last_line: usize,
 
@@ -34,7 +40,9 @@ impl<'a> ContractVisitor<'a> {
             source_id,
             source,
             contract_name,
+            ignored_lines: ignored_lines(source),
             branch_id: 0,
+            pending_requires: Vec::new(),
             last_line: 0,
             items: Vec::new(),
             base_contract_node_ids: HashSet::new(),
@@ -92,8 +100,11 @@ impl<'a> ContractVisitor<'a> {
     fn visit_block(&mut self, node: Node) -> eyre::Result<()> {
         let statements: Vec<Node> = node.attribute("statements").unwrap_or_default();
 
-        for statement in statements {
+        let mut statements = statements.into_iter().peekable();
+        while let Some(statement) = statements.next() {
             self.visit_statement(statement)?;
+            let next = statements.peek().map(|next| next.src.clone());
+            self.push_require_branches(next.as_ref());
         }
 
         Ok(())
@@ -196,6 +207,7 @@ impl<'a> ContractVisitor<'a> {
                 let true_body: Node = node
                     .attribute("trueBody")
                     .ok_or_else(|| eyre::eyre!("if statement had no true body"))?;
+                let false_body: Option<Node> = node.attribute("falseBody");
 
                 // We need to store the current branch ID here since visiting the body of either of
                 // the if blocks may increase `self.branch_id` in the case of nested if statements.
@@ -205,28 +217,43 @@ impl<'a> ContractVisitor<'a> {
                 // branch ID as we do
                 self.branch_id += 1;
 
-                // The relevant source range for the branch is the `if(...)` statement itself and
-                // the true body of the if statement. The false body of the statement (if any) is
-                // processed as its own thing. If this source range is not processed like this, it
-                // is virtually impossible to correctly map instructions back to branches that
-                // include more complex logic like conditional logic.
-                self.push_branches(
-                    &foundry_compilers::artifacts::ast::LowFidelitySourceLocation {
-                        start: node.src.start,
-                        length: true_body
-                            .src
-                            .length
-                            .map(|length| true_body.src.start - node.src.start + length),
-                        index: node.src.index,
-                    },
-                    branch_id,
-                );
+                // Each arm with code in it is anchored on the first opcode of its body. An empty
+                // arm has no code of its own, so it is anchored on the `JUMPI` of the condition
+                // instead, for which the relevant source range is the `if(...)` statement itself
+                // and the true body of the if statement.
+                let condition_loc = ast::LowFidelitySourceLocation {
+                    start: node.src.start,
+                    length: true_body
+                        .src
+                        .length
+                        .map(|length| true_body.src.start - node.src.start + length),
+                    index: node.src.index,
+                };
+                if has_statements(Some(&true_body)) || has_statements(false_body.as_ref()) {
+                    for (path_id, body) in
+                        [Some(&true_body), false_body.as_ref()].into_iter().enumerate()
+                    {
+                        match body.filter(|body| has_statements(Some(*body))) {
+                            Some(body) => self.push_branch(
+                                branch_id,
+                                path_id,
+                                BranchAnchor::FirstOpcode,
+                                &body.src,
+                            ),
+                            None => self.push_branch(
+                                branch_id,
+                                path_id,
+                                BranchAnchor::Jump,
+                                &condition_loc,
+                            ),
+                        }
+                    }
+                }
 
                 // Process the true branch
                 self.visit_block_or_statement(true_body)?;
 
                 // Process the false branch
-                let false_body: Option<Node> = node.attribute("falseBody");
                 if let Some(false_body) = false_body {
                     self.visit_block_or_statement(false_body)?;
                 }
@@ -248,11 +275,7 @@ impl<'a> ContractVisitor<'a> {
                 // branch ID as we do
                 self.branch_id += 1;
 
-                self.push_item(CoverageItem {
-                    kind: CoverageItemKind::Branch { branch_id, path_id: 0 },
-                    loc: self.source_location_for(&node.src),
-                    hits: 0,
-                });
+                self.push_branch(branch_id, 0, BranchAnchor::FirstOpcode, &body.src);
                 self.visit_block(*body)?;
 
                 Ok(())
@@ -322,7 +345,7 @@ impl<'a> ContractVisitor<'a> {
                     Some(NodeType::Identifier) => {
                         let name: Option<String> = expr.and_then(|expr| expr.attribute("name"));
                         if let Some("assert" | "require") = name.as_deref() {
-                            self.push_branches(&node.src, self.branch_id);
+                            self.pending_requires.push((self.branch_id, node.src.clone()));
                             self.branch_id += 1;
                         }
                     }
@@ -346,6 +369,23 @@ impl<'a> ContractVisitor<'a> {
                     loc: self.source_location_for(&node.src),
                     hits: 0,
                 });
+
+                if let Some(expr) = node.attribute("condition") {
+                    self.visit_expression(expr)?;
+                }
+
+                // Both arms of a ternary are expressions that compile to code of their own, so
+                // each is anchored on its first opcode
+                let true_expr: Option<Node> = node.attribute("trueExpression");
+                let false_expr: Option<Node> = node.attribute("falseExpression");
+                if let (Some(true_expr), Some(false_expr)) = (true_expr, false_expr) {
+                    let branch_id = self.branch_id;
+                    self.branch_id += 1;
+                    self.push_branch(branch_id, 0, BranchAnchor::FirstOpcode, &true_expr.src);
+                    self.push_branch(branch_id, 1, BranchAnchor::FirstOpcode, &false_expr.src);
+                    self.visit_expression(true_expr)?;
+                    self.visit_expression(false_expr)?;
+                }
                 Ok(())
             }
             // Does not count towards coverage
@@ -379,7 +419,13 @@ impl<'a> ContractVisitor<'a> {
             NodeType::RevertStatement |
             NodeType::TryStatement |
             NodeType::VariableDeclarationStatement |
-            NodeType::WhileStatement => self.visit_statement(node),
+            NodeType::WhileStatement => {
+                // A statement that isn't in a block is the whole body of a branch or loop, so
+                // nothing after it only runs if its `require` calls pass
+                self.visit_statement(node)?;
+                self.push_require_branches(None);
+                Ok(())
+            }
             _ => {
                 warn!("unexpected node type, expected block or statement: {:?}", node.node_type);
                 Ok(())
@@ -388,8 +434,13 @@ impl<'a> ContractVisitor<'a> {
     }
 
     /// Pushes a coverage item to the internal collection, and might push a line item as well.
+    ///
+    /// Items on lines excluded from coverage are dropped.
     fn push_item(&mut self, item: CoverageItem) {
         let source_location = &item.loc;
+        if self.ignored_lines.contains(&source_location.line) {
+            return
+        }
 
         // Push a line item if we haven't already
         if matches!(item.kind, CoverageItemKind::Statement | CoverageItemKind::Branch { .. }) &&
@@ -416,20 +467,76 @@ impl<'a> ContractVisitor<'a> {
         }
     }
 
-    fn push_branches(&mut self, loc: &ast::LowFidelitySourceLocation, branch_id: usize) {
-        self.push_item(CoverageItem {
-            kind: CoverageItemKind::Branch { branch_id, path_id: 0 },
-            loc: self.source_location_for(loc),
-            hits: 0,
-        });
+    /// Pushes the paths of the `require` and `assert` calls of the statement just walked.
+    ///
+    /// The failing path is anchored on the `REVERT` of the call. The passing path is anchored on
+    /// the first opcode of the next statement, which only runs if the condition holds, or on the
+    /// `JUMPI` of the condition if the call is the last statement of its block.
+    fn push_require_branches(&mut self, next: Option<&ast::LowFidelitySourceLocation>) {
+        for (branch_id, loc) in std::mem::take(&mut self.pending_requires) {
+            self.push_branch(branch_id, 0, BranchAnchor::Revert, &loc);
+            match next {
+                Some(next) => self.push_branch(branch_id, 1, BranchAnchor::FirstOpcode, next),
+                None => self.push_branch(branch_id, 1, BranchAnchor::Jump, &loc),
+            }
+        }
+    }
+
+    fn push_branch(
+        &mut self,
+        branch_id: usize,
+        path_id: usize,
+        anchor: BranchAnchor,
+        loc: &ast::LowFidelitySourceLocation,
+    ) {
         self.push_item(CoverageItem {
-            kind: CoverageItemKind::Branch { branch_id, path_id: 1 },
+            kind: CoverageItemKind::Branch { branch_id, path_id, anchor },
             loc: self.source_location_for(loc),
             hits: 0,
         });
     }
 }
 
+/// Returns the lines excluded from coverage by `forge-coverage` comments in the given source.
+///
+/// `// forge-coverage: ignore-next-line` excludes the line following the comment, and all lines
+/// from `// forge-coverage: ignore-start` up to `// forge-coverage: ignore-end` are excluded.
+pub fn ignored_lines(source: &str) -> HashSet<usize> {
+    let mut ignored = HashSet::new();
+    let mut in_ignored_range = false;
+    for (i, line) in source.lines().enumerate() {
+        let line_number = i + 1;
+        let directive = line
+            .split_once("//")
+            .and_then(|(_, comment)| comment.trim().strip_prefix("forge-coverage:"))
+            .map(str::trim);
+        match directive {
+            Some("ignore-next-line") => {
+                ignored.insert(line_number + 1);
+            }
+            Some("ignore-start") => in_ignored_range = true,
+            Some("ignore-end") => in_ignored_range = false,
+            Some(directive) => warn!("unknown forge-coverage directive: {directive}"),
+            None => {}
+        }
+        if in_ignored_range {
+            ignored.insert(line_number);
+        }
+    }
+    ignored
+}
+
+/// Returns true if the given block or statement contains any code.
+fn has_statements(node: Option<&Node>) -> bool {
+    match node {
+        Some(node) if matches!(node.node_type, NodeType::Block | NodeType::UncheckedBlock) => node
+            .attribute::<Vec<Node>>("statements")
+            .map_or(false, |statements| !statements.is_empty()),
+        Some(_) => true,
+        None => false,
+    }
+}
+
 #[derive(Debug)]
 pub struct SourceAnalysis {
     /// A collection of coverage items.
@@ -634,3 +741,26 @@ impl SourceAnalyzer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ignored_lines() {
+        let source = r#"contract A {
+    function f() public {
+        // forge-coverage: ignore-next-line
+        g();
+        h();
+        // forge-coverage: ignore-start
+        i();
+        j();
+        // forge-coverage: ignore-end
+        k();
+    }
+}"#;
+        let ignored = ignored_lines(source);
+        assert_eq!(ignored, HashSet::from([4, 6, 7, 8]));
+    }
+}
@@ -1,4 +1,4 @@
-use super::{CoverageItem, CoverageItemKind, ItemAnchor, SourceLocation};
+use super::{BranchAnchor, CoverageItem, CoverageItemKind, ItemAnchor, SourceLocation};
 use alloy_primitives::Bytes;
 use foundry_compilers::sourcemap::{SourceElement, SourceMap};
 use foundry_evm_core::utils::IcPcMap;
@@ -20,27 +20,33 @@ pub fn find_anchors(
         .filter_map(|item_id| {
             let item = items.get(*item_id)?;
 
-            match item.kind {
-                CoverageItemKind::Branch { path_id, .. } => {
-                    match find_anchor_branch(bytecode, source_map, *item_id, &item.loc) {
-                        Ok(anchors) => match path_id {
-                            0 => Some(anchors.0),
-                            1 => Some(anchors.1),
+            let anchor = match item.kind {
+                CoverageItemKind::Branch { path_id, anchor: BranchAnchor::Jump, .. } => {
+                    find_anchor_branch(bytecode, source_map, *item_id, &item.loc).map(|anchors| {
+                        match path_id {
+                            0 => anchors.0,
+                            1 => anchors.1,
                             _ => panic!("Too many paths for branch"),
-                        },
-                        Err(e) => {
-                            warn!("Could not find anchor for item: {}, error: {e}", item);
-                            None
                         }
-                    }
+                    })
+                }
+                // The code right after the `JUMPI` of the condition reverts if there is no
+                // `REVERT` in the range of the call
+                CoverageItemKind::Branch { anchor: BranchAnchor::Revert, .. } => {
+                    find_anchor_revert(bytecode, source_map, ic_pc_map, *item_id, &item.loc)
+                        .or_else(|_| {
+                            find_anchor_branch(bytecode, source_map, *item_id, &item.loc)
+                                .map(|anchors| anchors.0)
+                        })
+                }
+                _ => find_anchor_simple(source_map, ic_pc_map, *item_id, &item.loc),
+            };
+            match anchor {
+                Ok(anchor) => Some(anchor),
+                Err(e) => {
+                    warn!("Could not find anchor for item: {}, error: {e}", item);
+                    None
                 }
-                _ => match find_anchor_simple(source_map, ic_pc_map, *item_id, &item.loc) {
-                    Ok(anchor) => Some(anchor),
-                    Err(e) => {
-                        warn!("Could not find anchor for item: {}, error: {e}", item);
-                        None
-                    }
-                },
             }
         })
         .collect()
@@ -69,6 +75,24 @@ pub fn find_anchor_simple(
     })
 }
 
+/// Find an anchor representing the first `REVERT` within the given source range.
+pub fn find_anchor_revert(
+    bytecode: &Bytes,
+    source_map: &SourceMap,
+    ic_pc_map: &IcPcMap,
+    item_id: usize,
+    loc: &SourceLocation,
+) -> eyre::Result<ItemAnchor> {
+    source_map
+        .iter()
+        .enumerate()
+        .filter(|(_, element)| is_in_source_range(element, loc))
+        .filter_map(|(ic, _)| ic_pc_map.get(ic))
+        .find(|pc| bytecode.get(*pc) == Some(&opcode::REVERT))
+        .map(|instruction| ItemAnchor { instruction, item_id })
+        .ok_or_else(|| eyre::eyre!("Could not find anchor: No REVERT in range {}", loc))
+}
+
 /// Finds the anchor corresponding to a branch item.
 ///
 /// This finds the relevant anchors for a branch coverage item. These anchors
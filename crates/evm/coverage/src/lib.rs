@@ -277,6 +277,8 @@ pub enum CoverageItemKind {
         ///
         /// The first path has ID 0, the next ID 1, and so on.
         path_id: usize,
+        /// The instruction the path is anchored on.
+        anchor: BranchAnchor,
     },
     /// A function in the code.
     Function {
@@ -285,6 +287,22 @@ pub enum CoverageItemKind {
     },
 }
 
+/// The instruction a path of a branch is anchored on.
+///
+/// Anchoring on the code of the path itself does not depend on the layout of the jumps, which is
+/// not preserved when compiling via IR, so the `JUMPI` of the condition is only used for paths
+/// without code of their own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BranchAnchor {
+    /// The `JUMPI` of the branch condition in the source range: path 0 is the code right after
+    /// it, path 1 the jump destination.
+    Jump,
+    /// The first opcode in the source range, which is the code of the path.
+    FirstOpcode,
+    /// The first `REVERT` in the source range, for the failing path of `require` and `assert`.
+    Revert,
+}
+
 #[derive(Clone, Debug)]
 pub struct CoverageItem {
     /// The coverage item kind.
@@ -304,7 +322,7 @@ impl Display for CoverageItem {
             CoverageItemKind::Statement => {
                 write!(f, "Statement")?;
             }
-            CoverageItemKind::Branch { branch_id, path_id } => {
+            CoverageItemKind::Branch { branch_id, path_id, .. } => {
                 write!(f, "Branch (branch: {branch_id}, path: {path_id})")?;
             }
             CoverageItemKind::Function { name } => {
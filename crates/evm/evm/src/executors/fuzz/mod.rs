@@ -1,7 +1,7 @@
-use crate::executors::{Executor, RawCallResult};
+use crate::executors::{CancellationToken, Executor, FuzzTestTimer, RawCallResult};
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_json_abi::{Function, JsonAbi};
-use alloy_primitives::{Address, Bytes, U256};
+use alloy_primitives::{Address, Bytes, B256, U256};
 use eyre::Result;
 use foundry_config::FuzzConfig;
 use foundry_evm_core::{
@@ -11,16 +11,23 @@ use foundry_evm_core::{
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_fuzz::{
     strategies::{
-        build_initial_state, collect_state_from_call, fuzz_calldata, fuzz_calldata_from_state,
-        EvmFuzzState,
+        build_initial_state, collect_state_from_call, fuzz_calldata, fuzz_calldata_from_corpus,
+        fuzz_calldata_from_state, EvmFuzzState, FuzzCorpus,
     },
     BaseCounterExample, CounterExample, FuzzCase, FuzzError, FuzzTestResult,
 };
 use foundry_evm_traces::CallTraceArena;
+use parking_lot::Mutex;
 use proptest::test_runner::{TestCaseError, TestError, TestRunner};
-use std::cell::RefCell;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use std::{
+    cell::{Cell, RefCell},
+    collections::HashSet,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 mod types;
+use types::FuzzWorkerOutcome;
 pub use types::{CaseOutcome, CounterExampleOutcome, FuzzOutcome};
 
 /// Wrapper around an [`Executor`] which provides fuzzing support using [`proptest`].
@@ -37,17 +44,28 @@ pub struct FuzzedExecutor {
     sender: Address,
     /// The fuzz configuration
     config: FuzzConfig,
+    /// Stops fuzzing once cancelled
+    cancellation: CancellationToken,
 }
 
 impl FuzzedExecutor {
     /// Instantiates a fuzzed executor given a testrunner
     pub fn new(
-        executor: Executor,
+        mut executor: Executor,
         runner: TestRunner,
         sender: Address,
         config: FuzzConfig,
     ) -> Self {
-        Self { executor, runner, sender, config }
+        if config.coverage_guided {
+            executor.set_coverage(true);
+        }
+        Self { executor, runner, sender, config, cancellation: Default::default() }
+    }
+
+    /// Stops fuzzing once the given token is cancelled.
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
     }
 
     /// Fuzzes the provided function, assuming it is available at the contract at `address`
@@ -62,95 +80,201 @@ impl FuzzedExecutor {
         should_fail: bool,
         errors: Option<&JsonAbi>,
     ) -> FuzzTestResult {
-        // Stores the first Fuzzcase
-        let first_case: RefCell<Option<FuzzCase>> = RefCell::default();
+        let state = self.build_fuzz_state();
 
-        // gas usage per case
-        let gas_by_case: RefCell<Vec<(u64, u64)>> = RefCell::default();
+        // Stores the inputs that hit new program counters, when coverage-guided
+        let corpus = FuzzCorpus::default();
 
-        // Stores the result and calldata of the last failed call, if any.
-        let counterexample: RefCell<(Bytes, RawCallResult)> = RefCell::default();
+        // Program counters hit so far, by bytecode hash
+        let seen_pcs: Mutex<HashSet<(B256, usize)>> = Mutex::default();
 
-        // Stores the last successful call trace
-        let traces: RefCell<Option<CallTraceArena>> = RefCell::default();
+        // Set once a worker finds a counterexample, to stop the other workers
+        let stop = AtomicBool::new(false);
 
-        // Stores coverage information for all fuzz cases
-        let coverage: RefCell<Option<HitMaps>> = RefCell::default();
+        let timer = FuzzTestTimer::new(self.config.timeout);
 
-        let state = self.build_fuzz_state();
+        let fuzz_worker = |mut runner: TestRunner| {
+            // Stores the first Fuzzcase
+            let first_case: RefCell<Option<FuzzCase>> = RefCell::default();
 
-        let mut weights = vec![];
-        let dictionary_weight = self.config.dictionary.dictionary_weight.min(100);
-        if self.config.dictionary.dictionary_weight < 100 {
-            weights.push((100 - dictionary_weight, fuzz_calldata(func.clone())));
-        }
-        if dictionary_weight > 0 {
-            weights.push((
-                self.config.dictionary.dictionary_weight,
-                fuzz_calldata_from_state(func.clone(), state.clone()),
-            ));
-        }
+            // gas usage per case
+            let gas_by_case: RefCell<Vec<(u64, u64)>> = RefCell::default();
 
-        let strat = proptest::strategy::Union::new_weighted(weights);
-        debug!(func=?func.name, should_fail, "fuzzing");
-        let run_result = self.runner.clone().run(&strat, |calldata| {
-            let fuzz_res = self.single_fuzz(&state, address, should_fail, calldata)?;
-
-            match fuzz_res {
-                FuzzOutcome::Case(case) => {
-                    let mut first_case = first_case.borrow_mut();
-                    gas_by_case.borrow_mut().push((case.case.gas, case.case.stipend));
-                    if first_case.is_none() {
-                        first_case.replace(case.case);
-                    }
+            // Stores the result and calldata of the last failed call, if any.
+            let counterexample: RefCell<(Bytes, RawCallResult)> = RefCell::default();
 
-                    traces.replace(case.traces);
+            // Stores the last successful call trace
+            let traces: RefCell<Option<CallTraceArena>> = RefCell::default();
 
-                    if let Some(prev) = coverage.take() {
-                        // Safety: If `Option::or` evaluates to `Some`, then `call.coverage` must
-                        // necessarily also be `Some`
-                        coverage.replace(Some(prev.merge(case.coverage.unwrap())));
-                    } else {
-                        coverage.replace(case.coverage);
-                    }
+            // Stores coverage information for all fuzz cases
+            let coverage: RefCell<Option<HitMaps>> = RefCell::default();
+
+            // Whether this worker found a counterexample, and why it was interrupted otherwise
+            let failed = Cell::new(false);
+            let interrupted: Cell<Option<FuzzError>> = Cell::new(None);
+
+            let mut weights = vec![];
+            let dictionary_weight = self.config.dictionary.dictionary_weight.min(100);
+            if self.config.dictionary.dictionary_weight < 100 {
+                weights.push((100 - dictionary_weight, fuzz_calldata(func.clone())));
+            }
+            if dictionary_weight > 0 {
+                weights.push((
+                    self.config.dictionary.dictionary_weight,
+                    fuzz_calldata_from_state(func.clone(), state.clone()),
+                ));
+            }
+            if self.config.coverage_guided {
+                // Mutate inputs from the corpus in a third of the runs
+                weights.push((50, fuzz_calldata_from_corpus(func.clone(), corpus.clone())));
+            }
 
-                    Ok(())
+            let strat = proptest::strategy::Union::new_weighted(weights);
+            let run_result = runner.run(&strat, |calldata| {
+                // Stop fuzzing once the timeout is reached, once the test is cancelled, or once
+                // another worker found a counterexample, unless this worker is shrinking its own
+                let interruption = if failed.get() {
+                    None
+                } else if timer.is_timed_out() {
+                    Some(FuzzError::Timeout)
+                } else if self.cancellation.is_cancelled() {
+                    Some(FuzzError::Cancelled)
+                } else if stop.load(Ordering::Relaxed) {
+                    Some(FuzzError::Stopped)
+                } else {
+                    None
+                };
+                if let Some(error) = interruption {
+                    interrupted.set(Some(error));
+                    return Err(TestCaseError::fail(error))
                 }
-                FuzzOutcome::CounterExample(CounterExampleOutcome {
-                    exit_reason,
-                    counterexample: _counterexample,
-                    ..
-                }) => {
-                    let status = exit_reason;
-                    // We cannot use the calldata returned by the test runner in `TestError::Fail`,
-                    // since that input represents the last run case, which may not correspond with
-                    // our failure - when a fuzz case fails, proptest will try
-                    // to run at least one more case to find a minimal failure
-                    // case.
-                    let call_res = _counterexample.1.result.clone();
-                    *counterexample.borrow_mut() = _counterexample;
-                    // HACK: we have to use an empty string here to denote `None`
-                    let reason = decode::maybe_decode_revert(&call_res, errors, Some(status));
-                    Err(TestCaseError::fail(reason.unwrap_or_default()))
+
+                let fuzz_res =
+                    self.single_fuzz(&state, address, should_fail, calldata).map_err(|err| {
+                        if let TestCaseError::Fail(_) = err {
+                            failed.set(true);
+                        }
+                        err
+                    })?;
+
+                match fuzz_res {
+                    FuzzOutcome::Case(case) => {
+                        // Keep the inputs which hit new program counters in the corpus
+                        if self.config.coverage_guided {
+                            let mut seen_pcs = seen_pcs.lock();
+                            let mut new_coverage = false;
+                            for (code_hash, hit_map) in
+                                case.coverage.iter().flat_map(|maps| &maps.0)
+                            {
+                                for pc in hit_map.hits.keys() {
+                                    new_coverage |= seen_pcs.insert((*code_hash, *pc));
+                                }
+                            }
+                            if new_coverage {
+                                corpus.write().push(case.case.calldata.clone());
+                            }
+                        }
+
+                        let mut first_case = first_case.borrow_mut();
+                        gas_by_case.borrow_mut().push((case.case.gas, case.case.stipend));
+                        if first_case.is_none() {
+                            first_case.replace(case.case);
+                        }
+
+                        traces.replace(case.traces);
+
+                        if let Some(prev) = coverage.take() {
+                            // Safety: If `Option::or` evaluates to `Some`, then `call.coverage`
+                            // must necessarily also be `Some`
+                            coverage.replace(Some(prev.merge(case.coverage.unwrap())));
+                        } else {
+                            coverage.replace(case.coverage);
+                        }
+
+                        Ok(())
+                    }
+                    FuzzOutcome::CounterExample(CounterExampleOutcome {
+                        exit_reason,
+                        counterexample: _counterexample,
+                        ..
+                    }) => {
+                        failed.set(true);
+                        stop.store(true, Ordering::Relaxed);
+
+                        let status = exit_reason;
+                        // We cannot use the calldata returned by the test runner in
+                        // `TestError::Fail`, since that input represents the last run case,
+                        // which may not correspond with our failure - when a fuzz case fails,
+                        // proptest will try to run at least one more case to find a minimal
+                        // failure case.
+                        let call_res = _counterexample.1.result.clone();
+                        *counterexample.borrow_mut() = _counterexample;
+                        // HACK: we have to use an empty string here to denote `None`
+                        let reason = decode::maybe_decode_revert(&call_res, errors, Some(status));
+                        Err(TestCaseError::fail(reason.unwrap_or_default()))
+                    }
                 }
+            });
+
+            FuzzWorkerOutcome {
+                run_result,
+                interrupted: interrupted.get(),
+                first_case: first_case.into_inner(),
+                gas_by_case: gas_by_case.into_inner(),
+                counterexample: counterexample.into_inner(),
+                traces: traces.into_inner(),
+                coverage: coverage.into_inner(),
             }
-        });
+        };
+
+        // Split the runs between the workers, each fuzzing with its own RNG
+        let workers = self.config.workers.max(1);
+        let mut runner = self.runner.clone();
+        let runners = (0..workers)
+            .map(|worker| {
+                let mut config = runner.config().clone();
+                config.cases = config.cases / workers + u32::from(worker < config.cases % workers);
+                // The first worker keeps the RNG of the runner, so a single worker fuzzes the
+                // same inputs for a given seed
+                let rng = if worker == 0 { runner.rng().clone() } else { runner.new_rng() };
+                TestRunner::new_with_rng(config, rng)
+            })
+            .collect::<Vec<_>>();
+
+        debug!(func=?func.name, should_fail, workers, "fuzzing");
+        let mut outcomes: Vec<_> = runners.into_par_iter().map(fuzz_worker).collect();
 
-        let (calldata, call) = counterexample.into_inner();
+        // Aggregate the runs of all the workers, and report the first failure, if any
+        let gas_by_case = outcomes
+            .iter_mut()
+            .flat_map(|outcome| std::mem::take(&mut outcome.gas_by_case))
+            .collect();
+        let first_case = outcomes.iter_mut().find_map(|outcome| outcome.first_case.take());
+        let coverage = outcomes
+            .iter_mut()
+            .filter_map(|outcome| outcome.coverage.take())
+            .reduce(HitMaps::merge);
+        let failure = outcomes.iter().position(FuzzWorkerOutcome::is_failure);
+        let outcome = outcomes.swap_remove(failure.unwrap_or_default());
+        let success = failure.is_none();
+
+        let (calldata, call) = outcome.counterexample;
         let mut result = FuzzTestResult {
-            first_case: first_case.take().unwrap_or_default(),
-            gas_by_case: gas_by_case.take(),
-            success: run_result.is_ok(),
+            first_case: first_case.unwrap_or_default(),
+            gas_by_case,
+            success,
             reason: None,
             counterexample: None,
             decoded_logs: decode_console_logs(&call.logs),
             logs: call.logs,
             labeled_addresses: call.labels,
-            traces: if run_result.is_ok() { traces.into_inner() } else { call.traces.clone() },
-            coverage: coverage.into_inner(),
+            traces: if success { outcome.traces } else { call.traces.clone() },
+            coverage,
         };
 
-        match run_result {
+        match outcome.run_result {
+            // Workers interrupted by the timeout, a cancellation or another worker did not fail
+            _ if success => {}
             // Currently the only operation that can trigger proptest global rejects is the
             // `vm.assume` cheatcode, thus we surface this info to the user when the fuzz test
             // aborts due to too many global rejects, making the error message more actionable.
@@ -246,10 +370,17 @@ impl FuzzedExecutor {
 
     /// Stores fuzz state for use with [fuzz_calldata_from_state]
     pub fn build_fuzz_state(&self) -> EvmFuzzState {
-        if let Some(fork_db) = self.executor.backend.active_fork_db() {
+        let state = if let Some(fork_db) = self.executor.backend.active_fork_db() {
             build_initial_state(fork_db, &self.config.dictionary)
         } else {
             build_initial_state(self.executor.backend.mem_db(), &self.config.dictionary)
+        };
+
+        // Insert the fixtures added with `vm.addFuzzFixture` during setup
+        if let Some(cheatcodes) = &self.executor.inspector.cheatcodes {
+            state.write().values_mut().extend(cheatcodes.fuzz_fixtures.iter().map(|v| v.0));
         }
+
+        state
     }
 }
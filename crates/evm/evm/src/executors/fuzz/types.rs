@@ -3,8 +3,9 @@ use alloy_primitives::Bytes;
 use foundry_common::evm::Breakpoints;
 use foundry_evm_core::debug::DebugArena;
 use foundry_evm_coverage::HitMaps;
-use foundry_evm_fuzz::FuzzCase;
+use foundry_evm_fuzz::{FuzzCase, FuzzError};
 use foundry_evm_traces::CallTraceArena;
+use proptest::test_runner::TestError;
 use revm::interpreter::InstructionResult;
 
 /// Returned by a single fuzz in the case of a successful run
@@ -41,3 +42,35 @@ pub enum FuzzOutcome {
     Case(CaseOutcome),
     CounterExample(CounterExampleOutcome),
 }
+
+/// The outcome of one of the workers fuzzing a test
+#[derive(Debug)]
+pub(crate) struct FuzzWorkerOutcome {
+    /// The result of the proptest run
+    pub run_result: Result<(), TestError<Bytes>>,
+    /// Why the worker stopped before running all its cases, if it was interrupted by the
+    /// timeout, a cancellation or another worker's counterexample rather than failing
+    pub interrupted: Option<FuzzError>,
+    /// The first successful fuzz case
+    pub first_case: Option<FuzzCase>,
+    /// The gas usage of every successful fuzz case
+    pub gas_by_case: Vec<(u64, u64)>,
+    /// The calldata and result of the last failed call, if any
+    pub counterexample: (Bytes, RawCallResult),
+    /// The traces of the last successful call
+    pub traces: Option<CallTraceArena>,
+    /// The coverage info collected during all the successful calls
+    pub coverage: Option<HitMaps>,
+}
+
+impl FuzzWorkerOutcome {
+    /// Returns whether the worker failed the test, rather than passing or being interrupted by
+    /// the timeout or by another worker.
+    pub fn is_failure(&self) -> bool {
+        match self.run_result {
+            Ok(()) => false,
+            Err(TestError::Fail(..)) => self.interrupted.is_none(),
+            Err(TestError::Abort(_)) => true,
+        }
+    }
+}
@@ -1,19 +1,23 @@
 use super::{BasicTxDetails, InvariantContract};
 use crate::executors::{Executor, RawCallResult};
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
 use alloy_json_abi::Function;
-use alloy_primitives::{Address, Bytes, Log};
+use alloy_primitives::{Address, Bytes, Log, B256};
 use eyre::Result;
 use foundry_common::contracts::{ContractsByAddress, ContractsByArtifact};
 use foundry_evm_core::{constants::CALLER, decode::decode_revert};
-use foundry_evm_fuzz::{BaseCounterExample, CounterExample, FuzzedCases, Reason};
+use foundry_evm_fuzz::{
+    strategies::{IntValueTree, UintValueTree},
+    BaseCounterExample, CounterExample, FuzzedCases, Reason,
+};
 use foundry_evm_traces::{load_contracts, CallTraceArena, TraceKind, Traces};
 use itertools::Itertools;
 use parking_lot::RwLock;
-use proptest::test_runner::TestError;
+use proptest::{strategy::ValueTree, test_runner::TestError};
 use rand::{seq, thread_rng, Rng};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use revm::primitives::U256;
-use std::sync::Arc;
+use std::{cell::Cell, sync::Arc};
 
 #[derive(Clone, Default)]
 /// Stores information about failures and reverts of the invariant tests.
@@ -72,9 +76,12 @@ pub struct InvariantFuzzError {
     pub shrink: bool,
     /// Shrink run limit
     pub shrink_run_limit: usize,
+    /// Shrink the arguments of the calls of the failed test case.
+    pub shrink_calldata: bool,
 }
 
 impl InvariantFuzzError {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         invariant_contract: &InvariantContract<'_>,
         error_func: Option<&Function>,
@@ -83,6 +90,7 @@ impl InvariantFuzzError {
         inner_sequence: &[Option<BasicTxDetails>],
         shrink: bool,
         shrink_run_limit: usize,
+        shrink_calldata: bool,
     ) -> Self {
         let (func, origin) = if let Some(f) = error_func {
             (Some(f.selector().to_vec().into()), f.name.as_str())
@@ -109,6 +117,7 @@ impl InvariantFuzzError {
             inner_sequence: inner_sequence.to_vec(),
             shrink,
             shrink_run_limit,
+            shrink_calldata,
         }
     }
 
@@ -134,6 +143,10 @@ impl InvariantFuzzError {
             trace!(target: "forge::test", "Shrinking disabled.");
         }
 
+        if self.shrink_calldata {
+            self.try_shrinking_calldata(&mut calls, &executor, &ided_contracts);
+        }
+
         // We want traces for a failed case.
         executor.set_tracing(true);
 
@@ -265,6 +278,89 @@ impl InvariantFuzzError {
             .collect()
     }
 
+    /// Tries to shrink the arguments of every call of the failure case toward zero, keeping the
+    /// simplest values which still break the invariant.
+    ///
+    /// Integers are shrunk with the value trees of the fuzz strategies, other values are only
+    /// replaced with their zero value.
+    fn try_shrinking_calldata(
+        &self,
+        calls: &mut [BasicTxDetails],
+        executor: &Executor,
+        ided_contracts: &ContractsByAddress,
+    ) {
+        trace!(target: "forge::test", "Shrinking calldata.");
+
+        let runs = Cell::new(0);
+        for index in 0..calls.len() {
+            let (_, (addr, bytes)) = &calls[index];
+            let Some(func) = bytes.get(..4).and_then(|selector| {
+                ided_contracts
+                    .get(addr)?
+                    .1
+                    .functions()
+                    .find(|func| func.selector().as_slice() == selector)
+            }) else {
+                continue
+            };
+            let Ok(mut args) = func.abi_decode_input(&bytes[4..], false) else { continue };
+
+            for arg in 0..args.len() {
+                // Checks whether the invariant still breaks with the given value for the argument
+                let fails = |value: DynSolValue| {
+                    if runs.get() >= self.shrink_run_limit {
+                        return false
+                    }
+                    runs.set(runs.get() + 1);
+
+                    let mut new_args = args.clone();
+                    new_args[arg] = value;
+                    let Ok(new_bytes) = func.abi_encode_input(&new_args) else { return false };
+                    let mut new_calls = calls.to_vec();
+                    new_calls[index].1 .1 = new_bytes.into();
+                    self.breaks_invariant(executor.clone(), &new_calls)
+                };
+
+                let shrunk = match args[arg] {
+                    DynSolValue::Uint(value, size) => {
+                        shrink_value_tree(UintValueTree::new(value, false), |value| {
+                            fails(DynSolValue::Uint(*value, size))
+                        })
+                        .map(|value| DynSolValue::Uint(value, size))
+                    }
+                    DynSolValue::Int(value, size) => {
+                        shrink_value_tree(IntValueTree::new(value, false), |value| {
+                            fails(DynSolValue::Int(*value, size))
+                        })
+                        .map(|value| DynSolValue::Int(value, size))
+                    }
+                    ref value => {
+                        zero_value(value).filter(|zero| zero != value && fails(zero.clone()))
+                    }
+                };
+
+                if let Some(value) = shrunk {
+                    args[arg] = value;
+                    calls[index].1 .1 = func.abi_encode_input(&args).unwrap().into();
+                }
+            }
+        }
+    }
+
+    /// Checks whether replaying the calls breaks the invariant, at any point of the sequence.
+    fn breaks_invariant(&self, mut executor: Executor, calls: &[BasicTxDetails]) -> bool {
+        let Some(func) = &self.func else { return false };
+        calls.iter().any(|(sender, (addr, bytes))| {
+            executor
+                .call_raw_committing(*sender, *addr, bytes.clone(), U256::ZERO)
+                .expect("bad call to evm");
+            executor
+                .call_raw(CALLER, self.addr, func.clone(), U256::ZERO)
+                .expect("bad call to evm")
+                .reverted
+        })
+    }
+
     /// We try to construct a [powerset](https://en.wikipedia.org/wiki/Power_set) of the sequence if
     /// the configuration allows for it and the length of the sequence is small enough. If we
     /// do construct the powerset, we run all of the sequences in parallel, finding the smallest
@@ -417,6 +513,39 @@ impl InvariantFuzzError {
     }
 }
 
+/// Shrinks a value with the given value tree, returning the simplest value for which `fails`
+/// holds, if any simpler than the starting value.
+fn shrink_value_tree<T: ValueTree>(
+    mut tree: T,
+    mut fails: impl FnMut(&T::Value) -> bool,
+) -> Option<T::Value> {
+    let mut simplest = None;
+    let mut more = tree.simplify();
+    while more {
+        let value = tree.current();
+        if fails(&value) {
+            simplest = Some(value);
+            more = tree.simplify();
+        } else {
+            more = tree.complicate();
+        }
+    }
+    simplest
+}
+
+/// Returns the zero value of the type of the given value, for the types which can't be shrunk
+/// with a value tree.
+fn zero_value(value: &DynSolValue) -> Option<DynSolValue> {
+    match value {
+        DynSolValue::Bool(_) => Some(DynSolValue::Bool(false)),
+        DynSolValue::Address(_) => Some(DynSolValue::Address(Address::ZERO)),
+        DynSolValue::FixedBytes(_, size) => Some(DynSolValue::FixedBytes(B256::ZERO, *size)),
+        DynSolValue::Bytes(_) => Some(DynSolValue::Bytes(vec![])),
+        DynSolValue::String(_) => Some(DynSolValue::String(String::new())),
+        _ => None,
+    }
+}
+
 /// Sets up the calls generated by the internal fuzzer, if they exist.
 fn set_up_inner_replay(executor: &mut Executor, inner_sequence: &[Option<BasicTxDetails>]) {
     if let Some(fuzzer) = &mut executor.inspector.fuzzer {
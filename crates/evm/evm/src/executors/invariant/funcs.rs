@@ -20,6 +20,7 @@ pub fn assert_invariants(
     invariant_failures: &mut InvariantFailures,
     shrink_sequence: bool,
     shrink_run_limit: usize,
+    shrink_calldata: bool,
 ) -> Option<RawCallResult> {
     let mut inner_sequence = vec![];
 
@@ -58,6 +59,7 @@ pub fn assert_invariants(
                 &inner_sequence,
                 shrink_sequence,
                 shrink_run_limit,
+                shrink_calldata,
             ));
             return None
         }
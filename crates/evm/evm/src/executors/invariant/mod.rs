@@ -1,10 +1,10 @@
 use crate::{
-    executors::{Executor, RawCallResult},
+    executors::{CancellationToken, Executor, FuzzTestTimer, RawCallResult},
     inspectors::Fuzzer,
 };
 use alloy_dyn_abi::DynSolValue;
 use alloy_json_abi::JsonAbi;
-use alloy_primitives::{Address, FixedBytes, U256};
+use alloy_primitives::{keccak256, Address, FixedBytes, U256};
 use eyre::{eyre, ContextCompat, Result};
 use foundry_common::contracts::{ContractsByAddress, ContractsByArtifact};
 use foundry_config::{FuzzDictionaryConfig, InvariantConfig};
@@ -15,13 +15,13 @@ use foundry_evm_core::{
 use foundry_evm_fuzz::{
     invariant::{
         ArtifactFilters, BasicTxDetails, FuzzRunIdentifiedContracts, InvariantContract,
-        RandomCallGenerator, SenderFilters, TargetedContracts,
+        RandomCallGenerator, SenderFilters, TargetedContracts, TargetedFlows,
     },
     strategies::{
         build_initial_state, collect_created_contracts, collect_state_from_call, invariant_strat,
         override_call_strat, EvmFuzzState,
     },
-    FuzzCase, FuzzedCases,
+    FuzzCase, FuzzError, FuzzedCases,
 };
 use parking_lot::{Mutex, RwLock};
 use proptest::{
@@ -29,7 +29,7 @@ use proptest::{
     test_runner::{TestCaseError, TestRunner},
 };
 use revm::{primitives::HashMap, DatabaseCommit};
-use std::{cell::RefCell, collections::BTreeMap, sync::Arc};
+use std::{cell::RefCell, collections::BTreeMap, path::Path, sync::Arc};
 
 mod error;
 pub use error::{InvariantFailures, InvariantFuzzError, InvariantFuzzTestResult};
@@ -73,6 +73,8 @@ pub struct InvariantExecutor<'a> {
     project_contracts: &'a ContractsByArtifact,
     /// Filters contracts to be fuzzed through their artifact identifiers.
     artifact_filters: ArtifactFilters,
+    /// Stops the campaign once cancelled.
+    cancellation: CancellationToken,
 }
 
 impl<'a> InvariantExecutor<'a> {
@@ -91,9 +93,16 @@ impl<'a> InvariantExecutor<'a> {
             setup_contracts,
             project_contracts,
             artifact_filters: ArtifactFilters::default(),
+            cancellation: CancellationToken::default(),
         }
     }
 
+    /// Stops the campaign once the given token is cancelled.
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = cancellation;
+        self
+    }
+
     /// Fuzzes any deployed contract and checks any broken invariant at `invariant_address`.
     pub fn invariant_fuzz(
         &mut self,
@@ -126,6 +135,7 @@ impl<'a> InvariantExecutor<'a> {
             &mut failures.borrow_mut(),
             self.config.shrink_sequence,
             self.config.shrink_run_limit,
+            self.config.shrink_calldata,
         ));
 
         if last_call_results.borrow().is_none() {
@@ -137,7 +147,16 @@ impl<'a> InvariantExecutor<'a> {
         // during the run. We need another proptest runner to query for random
         // values.
         let branch_runner = RefCell::new(self.runner.clone());
-        let _ = self.runner.run(&strat, |mut inputs| {
+        let timer = FuzzTestTimer::new(self.config.timeout);
+        let run = |mut inputs: Vec<BasicTxDetails>| -> Result<(), TestCaseError> {
+            // Stop the campaign once the timeout is reached, or once the test is cancelled.
+            if timer.is_timed_out() {
+                return Err(TestCaseError::fail(FuzzError::Timeout))
+            }
+            if self.cancellation.is_cancelled() {
+                return Err(TestCaseError::fail(FuzzError::Cancelled))
+            }
+
             // We stop the run immediately if we have reverted, and `fail_on_revert` is set.
             if self.config.fail_on_revert && failures.borrow().reverts > 0 {
                 return Err(TestCaseError::fail("Revert occurred."))
@@ -146,6 +165,11 @@ impl<'a> InvariantExecutor<'a> {
             // Before each run, we must reset the backend state.
             let mut executor = self.executor.clone();
 
+            // Used to persist the calls of the run to the corpus if they found new values.
+            let dictionary_len = fuzz_state.read().values().len();
+            let mut sequence_len = 0;
+            let mut failed = false;
+
             // Used for stat reports (eg. gas usage).
             let mut fuzz_runs = Vec::with_capacity(self.config.depth as usize);
 
@@ -153,8 +177,12 @@ impl<'a> InvariantExecutor<'a> {
             let mut created_contracts = vec![];
 
             for current_run in 0..self.config.depth {
+                // The calls of a flow are generated together, so the next input may be pending
+                // from a previous step.
+                let executed = current_run as usize + 1;
+                sequence_len = executed;
                 let (sender, (address, calldata)) =
-                    inputs.last().expect("to have the next randomly generated input.");
+                    inputs.get(current_run as usize).expect("to have the next generated input.");
 
                 // Executes the call from the randomly generated sequence.
                 let call_result = executor
@@ -198,33 +226,37 @@ impl<'a> InvariantExecutor<'a> {
                         &invariant_contract,
                         call_result,
                         &executor,
-                        &inputs,
+                        &inputs[..executed],
                         &mut failures.borrow_mut(),
                         &targeted_contracts,
                         state_changeset,
                         self.config.fail_on_revert,
                         self.config.shrink_sequence,
                         self.config.shrink_run_limit,
+                        self.config.shrink_calldata,
                     );
 
                 if !can_continue || current_run == self.config.depth - 1 {
-                    *last_run_calldata.borrow_mut() = inputs.clone();
+                    *last_run_calldata.borrow_mut() = inputs[..executed].to_vec();
                 }
 
                 if !can_continue {
+                    failed = true;
                     break
                 }
 
                 *last_call_results.borrow_mut() = call_results;
 
                 // Generates the next call from the run using the recently updated
-                // dictionary.
-                inputs.extend(
-                    strat
-                        .new_tree(&mut branch_runner.borrow_mut())
-                        .map_err(|_| TestCaseError::Fail("Could not generate case".into()))?
-                        .current(),
-                );
+                // dictionary, unless calls of a flow are still pending.
+                if inputs.len() == executed {
+                    inputs.extend(
+                        strat
+                            .new_tree(&mut branch_runner.borrow_mut())
+                            .map_err(|_| TestCaseError::Fail("Could not generate case".into()))?
+                            .current(),
+                    );
+                }
             }
 
             // We clear all the targeted contracts created during this run.
@@ -237,8 +269,22 @@ impl<'a> InvariantExecutor<'a> {
 
             fuzz_cases.borrow_mut().push(FuzzedCases::new(fuzz_runs));
 
+            if let Some(corpus_dir) = &self.config.corpus_dir {
+                if failed || fuzz_state.read().values().len() > dictionary_len {
+                    persist_to_corpus(corpus_dir, &inputs[..sequence_len]);
+                }
+            }
+
             Ok(())
-        });
+        };
+
+        // Replay the call sequences of the corpus before generating new ones.
+        if let Some(corpus_dir) = &self.config.corpus_dir {
+            for sequence in load_corpus(corpus_dir) {
+                let _ = run(sequence);
+            }
+        }
+        let _ = self.runner.run(&strat, run);
 
         trace!(target: "forge::test::invariant::dictionary", "{:?}", fuzz_state.read().values().iter().map(hex::encode).collect::<Vec<_>>());
 
@@ -273,16 +319,24 @@ impl<'a> InvariantExecutor<'a> {
         let fuzz_state: EvmFuzzState =
             build_initial_state(self.executor.backend.mem_db(), &self.config.dictionary);
 
+        // Insert the fixtures added with `vm.addFuzzFixture` during setup
+        if let Some(cheatcodes) = &self.executor.inspector.cheatcodes {
+            fuzz_state.write().values_mut().extend(cheatcodes.fuzz_fixtures.iter().map(|v| v.0));
+        }
+
         // During execution, any newly created contract is added here and used through the rest of
         // the fuzz run.
         let targeted_contracts: FuzzRunIdentifiedContracts =
             Arc::new(Mutex::new(targeted_contracts));
 
+        let targeted_flows = self.select_flows(&targeted_contracts)?;
+
         // Creates the invariant strategy.
         let strat = invariant_strat(
             fuzz_state.clone(),
             targeted_senders,
             targeted_contracts.clone(),
+            targeted_flows,
             self.config.dictionary.dictionary_weight,
         )
         .no_shrink()
@@ -490,6 +544,37 @@ impl<'a> InvariantExecutor<'a> {
         Ok((SenderFilters::new(targeted_senders, excluded_senders), contracts))
     }
 
+    /// Resolves the flows declared with `vm.targetFlow` during `setUp` to the functions of their
+    /// target contracts, which must either be targeted or deployed during `setUp`.
+    pub fn select_flows(
+        &self,
+        targeted_contracts: &FuzzRunIdentifiedContracts,
+    ) -> eyre::Result<TargetedFlows> {
+        let Some(cheatcodes) = &self.executor.inspector.cheatcodes else { return Ok(vec![]) };
+        let targeted_contracts = targeted_contracts.lock();
+        cheatcodes
+            .targeted_flows
+            .iter()
+            .map(|(target, selectors)| {
+                let abi = targeted_contracts
+                    .get(target)
+                    .map(|(_, abi, _)| abi)
+                    .or_else(|| self.setup_contracts.get(target).map(|(_, abi)| abi))
+                    .wrap_err_with(|| format!("flow target {target} is not a known contract"))?;
+                let functions = selectors
+                    .iter()
+                    .map(|selector| {
+                        abi.functions()
+                            .find(|func| func.selector() == *selector)
+                            .cloned()
+                            .wrap_err_with(|| format!("{target} has no function {selector}"))
+                    })
+                    .collect::<eyre::Result<Vec<_>>>()?;
+                Ok((*target, functions))
+            })
+            .collect()
+    }
+
     /// Extends the contracts and selectors to fuzz with the addresses and ABIs specified in
     /// `targetInterfaces() -> (address, string[])[]`. Enables targeting of addresses that are
     /// not deployed during `setUp` such as when fuzzing in a forked environment. Also enables
@@ -687,6 +772,37 @@ impl<'a> InvariantExecutor<'a> {
     }
 }
 
+/// Loads the call sequences persisted in the corpus directory, skipping any unreadable ones.
+fn load_corpus(corpus_dir: &Path) -> Vec<Vec<BasicTxDetails>> {
+    foundry_common::fs::json_files(corpus_dir)
+        .into_iter()
+        .filter_map(|path| match foundry_common::fs::read_json_file(&path) {
+            Ok(sequence) => Some(sequence),
+            Err(err) => {
+                warn!(target: "forge::test", "failed to load corpus entry: {err}");
+                None
+            }
+        })
+        .filter(|sequence: &Vec<BasicTxDetails>| !sequence.is_empty())
+        .collect()
+}
+
+/// Persists a call sequence to the corpus directory, named after its hash to avoid duplicates.
+fn persist_to_corpus(corpus_dir: &Path, sequence: &[BasicTxDetails]) {
+    let mut encoded = Vec::new();
+    for (sender, (target, calldata)) in sequence {
+        encoded.extend_from_slice(sender.as_slice());
+        encoded.extend_from_slice(target.as_slice());
+        encoded.extend_from_slice(keccak256(calldata).as_slice());
+    }
+    let path = corpus_dir.join(format!("{}.json", keccak256(encoded)));
+    let result = foundry_common::fs::create_dir_all(corpus_dir)
+        .and_then(|_| foundry_common::fs::write_json_file(&path, &sequence));
+    if let Err(err) = result {
+        warn!(target: "forge::test", "failed to persist corpus entry: {err}");
+    }
+}
+
 /// Collects data from call for fuzzing. However, it first verifies that the sender is not an EOA
 /// before inserting it into the dictionary. Otherwise, we flood the dictionary with
 /// randomly generated addresses.
@@ -733,6 +849,7 @@ fn can_continue(
     fail_on_revert: bool,
     shrink_sequence: bool,
     shrink_run_limit: usize,
+    shrink_calldata: bool,
 ) -> RichInvariantResults {
     let mut call_results = None;
 
@@ -751,6 +868,7 @@ fn can_continue(
             failures,
             shrink_sequence,
             shrink_run_limit,
+            shrink_calldata,
         );
         if call_results.is_none() {
             return RichInvariantResults::new(false, None)
@@ -768,6 +886,7 @@ fn can_continue(
                 &[],
                 shrink_sequence,
                 shrink_run_limit,
+                shrink_calldata,
             );
 
             failures.revert_reason = Some(error.revert_reason.clone());
@@ -18,9 +18,11 @@ use foundry_evm_core::{
     backend::{Backend, DatabaseError, DatabaseExt, DatabaseResult, FuzzBackendWrapper},
     constants::{
         CALLER, CHEATCODE_ADDRESS, DEFAULT_CREATE2_DEPLOYER, DEFAULT_CREATE2_DEPLOYER_CODE,
+        DEFAULT_CREATE2_DEPLOYER_SIGNER,
     },
     debug::DebugArena,
     decode,
+    fork::thread_request_time,
     utils::{eval_to_instruction_result, halt_to_instruction_result, StateChangeset},
 };
 use foundry_evm_coverage::HitMaps;
@@ -32,7 +34,14 @@ use revm::{
         BlockEnv, Bytecode, Env, ExecutionResult, Output, ResultAndState, SpecId, TransactTo, TxEnv,
     },
 };
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
 
 mod builder;
 pub use builder::ExecutorBuilder;
@@ -98,7 +107,7 @@ impl Executor {
 
         // if the deployer is not currently deployed, deploy the default one
         if create2_deployer_account.code.map_or(true, |code| code.is_empty()) {
-            let creator = "0x3fAB184622Dc19b6109349B94811493BF2a45362".parse().unwrap();
+            let creator = DEFAULT_CREATE2_DEPLOYER_SIGNER;
 
             // Probably 0, but just in case.
             let initial_balance = self.get_balance(creator)?;
@@ -148,6 +157,12 @@ impl Executor {
         self
     }
 
+    #[inline]
+    pub fn set_coverage(&mut self, coverage: bool) -> &mut Self {
+        self.inspector.collect_coverage(coverage);
+        self
+    }
+
     #[inline]
     pub fn set_debugger(&mut self, debugger: bool) -> &mut Self {
         self.inspector.enable_debugger(debugger);
@@ -265,6 +280,23 @@ impl Executor {
         convert_call_result(abi, &func, call_result)
     }
 
+    /// Executes the test function call, persisting the state after the call.
+    pub fn execute_test_committing<T: Into<Vec<DynSolValue>>, F: IntoFunction>(
+        &mut self,
+        from: Address,
+        test_contract: Address,
+        func: F,
+        args: T,
+        value: U256,
+        abi: Option<&JsonAbi>,
+    ) -> Result<CallResult, EvmError> {
+        let func = func.into();
+        let calldata = Bytes::from(func.abi_encode_input(&args.into())?.to_vec());
+        let env = self.build_test_env(from, TransactTo::Call(test_contract), calldata, value);
+        let call_result = self.commit_tx_with_env(env)?;
+        convert_call_result(abi, &func, call_result)
+    }
+
     /// Performs a call to an account on the current state of the VM.
     ///
     /// The state after the call is not persisted.
@@ -301,7 +333,9 @@ impl Executor {
         // Build VM
         let mut env = self.build_test_env(from, TransactTo::Call(to), calldata, value);
         let mut db = FuzzBackendWrapper::new(&self.backend);
-        let result = db.inspect_ref(&mut env, &mut inspector)?;
+        let result = ExecutionProfile::measure(self.inspector.profile.as_deref(), || {
+            db.inspect_ref(&mut env, &mut inspector)
+        })?;
 
         // Persist the snapshot failure recorded on the fuzz backend wrapper.
         let has_snapshot_failure = db.has_snapshot_failure();
@@ -319,7 +353,10 @@ impl Executor {
     pub fn call_raw_with_env(&mut self, mut env: Env) -> eyre::Result<RawCallResult> {
         // execute the call
         let mut inspector = self.inspector.clone();
-        let result = self.backend.inspect_ref(&mut env, &mut inspector)?;
+        let profile = self.inspector.profile.clone();
+        let result = ExecutionProfile::measure(profile.as_deref(), || {
+            self.backend.inspect_ref(&mut env, &mut inspector)
+        })?;
         convert_executed_result(env, inspector, result, self.backend.has_snapshot_failure())
     }
 
@@ -548,13 +585,19 @@ impl Executor {
     ///
     /// If using a backend with cheatcodes, `tx.gas_price` and `block.number` will be overwritten by
     /// the cheatcode state inbetween calls.
-    fn build_test_env(
+    pub fn build_test_env(
         &self,
         caller: Address,
         transact_to: TransactTo,
         data: Bytes,
         value: U256,
     ) -> Env {
+        // The EVM charges the default base cost of transactions, so the limit is adjusted for the
+        // execution to be left with as much gas as with the overridden one
+        let gas_limit = match &self.inspector.gas_schedule {
+            Some(gas_schedule) => gas_schedule.adjust_gas_limit(self.gas_limit.to()),
+            None => self.gas_limit.to(),
+        };
         Env {
             cfg: self.env.cfg.clone(),
             // We always set the gas price to 0 so we can execute the transaction regardless of
@@ -562,7 +605,7 @@ impl Executor {
             // the cheatcode handler if it is enabled
             block: BlockEnv {
                 basefee: U256::ZERO,
-                gas_limit: self.gas_limit,
+                gas_limit: self.gas_limit.max(U256::from(gas_limit)),
                 ..self.env.block.clone()
             },
             tx: TxEnv {
@@ -573,7 +616,7 @@ impl Executor {
                 // As above, we set the gas price to 0.
                 gas_price: U256::ZERO,
                 gas_priority_fee: None,
-                gas_limit: self.gas_limit.to(),
+                gas_limit,
                 ..self.env.tx.clone()
             },
         }
@@ -672,6 +715,8 @@ pub struct CallResult {
     pub env: Env,
     /// breakpoints
     pub breakpoints: Breakpoints,
+    /// The named values recorded with `snapshotValue`
+    pub value_snapshots: BTreeMap<String, Bytes>,
 }
 
 /// The result of a raw call.
@@ -749,6 +794,90 @@ impl Default for RawCallResult {
     }
 }
 
+/// Timer bounding the wall-clock time of a fuzz test or invariant campaign.
+#[derive(Clone, Copy, Debug)]
+pub struct FuzzTestTimer {
+    /// The start time and the duration of the test, if it has a timeout.
+    inner: Option<(Instant, Duration)>,
+}
+
+impl FuzzTestTimer {
+    /// Starts a timer for the given timeout, in seconds.
+    pub fn new(timeout: Option<u32>) -> Self {
+        Self { inner: timeout.map(|timeout| (Instant::now(), Duration::from_secs(timeout.into()))) }
+    }
+
+    /// Returns whether the timeout has been reached, and the test should stop.
+    pub fn is_timed_out(&self) -> bool {
+        self.inner.is_some_and(|(start, duration)| start.elapsed() > duration)
+    }
+}
+
+/// Token to cooperatively cancel the tests of a run, e.g. once one of them fails with
+/// `--fail-fast`.
+///
+/// Clones share the same cancellation state.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Cancels the tests sharing this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the tests have been cancelled, and should stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Profiling counters of the EVM executions of a test, shared by all the executors it runs with.
+#[derive(Debug, Default)]
+pub struct ExecutionProfile {
+    /// Nanoseconds spent executing the EVM.
+    evm_nanos: AtomicU64,
+    /// Nanoseconds spent waiting on fork RPC requests, during EVM executions.
+    rpc_nanos: AtomicU64,
+    /// Number of calls to the cheatcode address.
+    cheatcode_calls: AtomicU64,
+}
+
+impl ExecutionProfile {
+    /// Runs the given EVM execution, recording its duration in the profile, if any.
+    pub fn measure<T>(profile: Option<&Self>, f: impl FnOnce() -> T) -> T {
+        let Some(profile) = profile else { return f() };
+        let start = Instant::now();
+        let rpc_start = thread_request_time();
+        let result = f();
+        profile.evm_nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        profile
+            .rpc_nanos
+            .fetch_add((thread_request_time() - rpc_start).as_nanos() as u64, Ordering::Relaxed);
+        result
+    }
+
+    /// Records a call to the cheatcode address.
+    pub fn record_cheatcode_call(&self) {
+        self.cheatcode_calls.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the time spent executing the EVM.
+    pub fn evm_time(&self) -> Duration {
+        Duration::from_nanos(self.evm_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the time spent waiting on fork RPC requests.
+    pub fn rpc_time(&self) -> Duration {
+        Duration::from_nanos(self.rpc_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the number of calls to the cheatcode address.
+    pub fn cheatcode_calls(&self) -> u64 {
+        self.cheatcode_calls.load(Ordering::Relaxed)
+    }
+}
+
 /// Calculates the initial gas stipend for a transaction
 fn calc_stipend(calldata: &[u8], spec: SpecId) -> u64 {
     let non_zero_data_cost = if SpecId::enabled(spec, SpecId::ISTANBUL) { 16 } else { 68 };
@@ -775,7 +904,12 @@ fn convert_executed_result(
             (halt_to_instruction_result(reason), 0_u64, gas_used, None)
         }
     };
-    let stipend = calc_stipend(&env.tx.data, env.cfg.spec_id);
+    let mut stipend = calc_stipend(&env.tx.data, env.cfg.spec_id);
+    let mut gas_used = gas_used;
+    if let Some(gas_schedule) = &inspector.gas_schedule {
+        stipend = gas_schedule.adjust_intrinsic(stipend);
+        gas_used = gas_schedule.adjust_intrinsic(gas_used);
+    }
 
     let result = match &out {
         Some(Output::Call(data)) => data.clone(),
@@ -847,10 +981,10 @@ fn convert_call_result(
         ..
     } = call_result;
 
-    let breakpoints = if let Some(c) = call_result.cheatcodes {
-        c.breakpoints
+    let (breakpoints, value_snapshots) = if let Some(c) = call_result.cheatcodes {
+        (c.breakpoints, c.value_snapshots)
     } else {
-        std::collections::HashMap::new()
+        Default::default()
     };
 
     match status {
@@ -878,6 +1012,7 @@ fn convert_call_result(
                 script_wallets,
                 env,
                 breakpoints,
+                value_snapshots,
                 skipped: false,
             })
         }
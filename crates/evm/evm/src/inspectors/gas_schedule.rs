@@ -0,0 +1,161 @@
+use foundry_config::GasOverrides;
+use revm::{
+    interpreter::{
+        opcode::{self, spec_opcode_gas},
+        return_ok, InstructionResult, Interpreter,
+    },
+    Database, EVMData, Inspector,
+};
+
+/// The base cost of a transaction.
+const BASE_TX_COST: u64 = 21000;
+
+/// The opcodes whose gas includes the one of the call or creation they make, which can't be
+/// overridden.
+const CALL_OPCODES: [u8; 6] = [
+    opcode::CALL,
+    opcode::CALLCODE,
+    opcode::DELEGATECALL,
+    opcode::STATICCALL,
+    opcode::CREATE,
+    opcode::CREATE2,
+];
+
+/// An inspector that charges the [`GasOverrides`] instead of the gas costs of opcodes.
+///
+/// For opcodes with a static cost, the difference to it is charged before the opcode is executed
+/// if it is more expensive, or given back after it was executed if it is cheaper, so their dynamic
+/// costs are still charged. Opcodes which only have a dynamic cost, such as `SLOAD`, are charged
+/// the override instead of the gas they actually consumed.
+#[derive(Clone, Debug)]
+pub struct GasSchedule {
+    /// The overridden costs, by opcode
+    costs: [Option<u64>; 256],
+    /// The overridden base cost of a transaction
+    intrinsic: Option<u64>,
+    /// The adjustments to make after executing the current opcodes, by call depth
+    pending: Vec<(u64, Adjustment)>,
+}
+
+/// A gas adjustment made once an opcode was executed.
+#[derive(Clone, Copy, Debug)]
+enum Adjustment {
+    /// Gives back the gas
+    Refund(u64),
+    /// Charges the cost instead of the gas consumed since `remaining` gas was left
+    Replace { cost: u64, remaining: u64 },
+}
+
+impl GasSchedule {
+    /// Creates the schedule of the given overrides, failing on unknown opcodes and on the ones
+    /// making calls or creating contracts.
+    pub fn new(overrides: &GasOverrides) -> eyre::Result<Self> {
+        let mut costs = [None; 256];
+        for (name, cost) in &overrides.opcodes {
+            let Some(op) = opcode::OPCODE_JUMPMAP
+                .iter()
+                .position(|mnemonic| mnemonic.map_or(false, |m| m.eq_ignore_ascii_case(name)))
+            else {
+                eyre::bail!("unknown opcode `{name}` in gas overrides")
+            };
+            if CALL_OPCODES.contains(&(op as u8)) {
+                eyre::bail!(
+                    "the cost of `{name}` can't be overridden, since it includes the gas of the \
+                     call it makes"
+                )
+            }
+            costs[op] = Some(*cost);
+        }
+        Ok(Self { costs, intrinsic: overrides.intrinsic, pending: Vec::new() })
+    }
+
+    /// Returns true if no costs are overridden.
+    pub fn is_empty(&self) -> bool {
+        self.intrinsic.is_none() && self.costs.iter().all(Option::is_none)
+    }
+
+    /// Replaces the default base cost of a transaction in `gas` with the overridden one.
+    pub fn adjust_intrinsic(&self, gas: u64) -> u64 {
+        match self.intrinsic {
+            Some(intrinsic) => gas.saturating_sub(BASE_TX_COST).saturating_add(intrinsic),
+            None => gas,
+        }
+    }
+
+    /// Returns the gas limit of a transaction leaving as much gas to its execution as `gas_limit`
+    /// would with the overridden base cost, since the EVM always charges the default one.
+    pub fn adjust_gas_limit(&self, gas_limit: u64) -> u64 {
+        match self.intrinsic {
+            Some(intrinsic) => gas_limit.saturating_sub(intrinsic).saturating_add(BASE_TX_COST),
+            None => gas_limit,
+        }
+    }
+}
+
+impl<DB: Database> Inspector<DB> for GasSchedule {
+    fn step(&mut self, interp: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
+        let op = interp.current_opcode();
+        let Some(cost) = self.costs[op as usize] else { return };
+        let depth = data.journaled_state.depth();
+        let static_cost = u64::from(spec_opcode_gas(data.env.cfg.spec_id)[op as usize].get_gas());
+        if static_cost == 0 {
+            let remaining = interp.gas.remaining();
+            self.pending.push((depth, Adjustment::Replace { cost, remaining }));
+        } else if cost > static_cost {
+            if !interp.gas.record_cost(cost - static_cost) {
+                interp.instruction_result = InstructionResult::OutOfGas;
+            }
+        } else if cost < static_cost {
+            self.pending.push((depth, Adjustment::Refund(static_cost - cost)));
+        }
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter<'_>, data: &mut EVMData<'_, DB>) {
+        let depth = data.journaled_state.depth();
+        if self.pending.last().map_or(false, |(pending_depth, _)| *pending_depth == depth) {
+            let (_, adjustment) = self.pending.pop().unwrap();
+            if !matches!(interp.instruction_result, return_ok!()) {
+                return
+            }
+            match adjustment {
+                Adjustment::Refund(gas) => {
+                    interp.gas.erase_cost(gas);
+                }
+                Adjustment::Replace { cost, remaining } => {
+                    let consumed = remaining.saturating_sub(interp.gas.remaining());
+                    if cost < consumed {
+                        interp.gas.erase_cost(consumed - cost);
+                    } else if !interp.gas.record_cost(cost - consumed) {
+                        interp.instruction_result = InstructionResult::OutOfGas;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_overrides() {
+        let overrides = GasOverrides {
+            intrinsic: Some(16000),
+            opcodes: [("SLOAD".to_string(), 800), ("sstore".to_string(), 5000)].into(),
+        };
+        let schedule = GasSchedule::new(&overrides).unwrap();
+        assert_eq!(schedule.costs[opcode::SLOAD as usize], Some(800));
+        assert_eq!(schedule.costs[opcode::SSTORE as usize], Some(5000));
+        assert_eq!(schedule.adjust_intrinsic(21064), 16064);
+        assert_eq!(schedule.adjust_gas_limit(30_000_000), 30_005_000);
+
+        let overrides =
+            GasOverrides { opcodes: [("SLOWLOAD".to_string(), 1)].into(), ..Default::default() };
+        assert!(GasSchedule::new(&overrides).is_err());
+        let overrides =
+            GasOverrides { opcodes: [("CALL".to_string(), 1)].into(), ..Default::default() };
+        assert!(GasSchedule::new(&overrides).is_err());
+        assert!(GasSchedule::new(&GasOverrides::default()).unwrap().is_empty());
+    }
+}
@@ -1,19 +1,20 @@
 //! EVM inspectors.
 
 pub use foundry_cheatcodes::{self as cheatcodes, Cheatcodes, CheatsConfig};
+pub use foundry_evm_core::access_list::AccessListTracer;
 pub use foundry_evm_coverage::CoverageCollector;
 pub use foundry_evm_fuzz::Fuzzer;
 pub use foundry_evm_traces::{StackSnapshotType, TracingInspector, TracingInspectorConfig};
 
-mod access_list;
-pub use access_list::AccessListTracer;
-
 mod chisel_state;
 pub use chisel_state::ChiselState;
 
 mod debugger;
 pub use debugger::Debugger;
 
+mod gas_schedule;
+pub use gas_schedule::GasSchedule;
+
 mod logs;
 pub use logs::LogCollector;
 
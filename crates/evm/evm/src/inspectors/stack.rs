@@ -1,10 +1,14 @@
 use super::{
-    Cheatcodes, CheatsConfig, ChiselState, CoverageCollector, Debugger, Fuzzer, LogCollector,
-    StackSnapshotType, TracePrinter, TracingInspector, TracingInspectorConfig,
+    Cheatcodes, CheatsConfig, ChiselState, CoverageCollector, Debugger, Fuzzer, GasSchedule,
+    LogCollector, StackSnapshotType, TracePrinter, TracingInspector, TracingInspectorConfig,
 };
+use crate::executors::ExecutionProfile;
 use alloy_primitives::{Address, Bytes, Log, B256, U256};
 use alloy_signer::LocalWallet;
-use foundry_evm_core::{backend::DatabaseExt, debug::DebugArena};
+use foundry_evm_core::{
+    backend::DatabaseExt, constants::CHEATCODE_ADDRESS, debug::DebugArena,
+    precompiles::ChainPrecompiles,
+};
 use foundry_evm_coverage::HitMaps;
 use foundry_evm_traces::CallTraceArena;
 use revm::{
@@ -45,6 +49,10 @@ pub struct InspectorStackBuilder {
     pub print: Option<bool>,
     /// The chisel state inspector.
     pub chisel_state: Option<usize>,
+    /// The chain specific precompiles.
+    pub precompiles: Option<ChainPrecompiles>,
+    /// The overridden gas costs.
+    pub gas_schedule: Option<GasSchedule>,
 }
 
 impl InspectorStackBuilder {
@@ -89,6 +97,20 @@ impl InspectorStackBuilder {
         self
     }
 
+    /// Set the chain specific precompiles.
+    #[inline]
+    pub fn precompiles(mut self, precompiles: ChainPrecompiles) -> Self {
+        self.precompiles = Some(precompiles);
+        self
+    }
+
+    /// Set the overridden gas costs.
+    #[inline]
+    pub fn gas_schedule(mut self, gas_schedule: GasSchedule) -> Self {
+        self.gas_schedule = Some(gas_schedule);
+        self
+    }
+
     /// Set whether to collect logs.
     #[inline]
     pub fn logs(mut self, yes: bool) -> Self {
@@ -139,6 +161,8 @@ impl InspectorStackBuilder {
             coverage,
             print,
             chisel_state,
+            precompiles,
+            gas_schedule,
         } = self;
         let mut stack = InspectorStack::new();
 
@@ -152,6 +176,12 @@ impl InspectorStackBuilder {
         if let Some(chisel_state) = chisel_state {
             stack.set_chisel(chisel_state);
         }
+        if let Some(precompiles) = precompiles.filter(|precompiles| !precompiles.is_empty()) {
+            stack.set_precompiles(precompiles);
+        }
+        if let Some(gas_schedule) = gas_schedule.filter(|gas_schedule| !gas_schedule.is_empty()) {
+            stack.set_gas_schedule(gas_schedule);
+        }
         stack.collect_coverage(coverage.unwrap_or(false));
         stack.collect_logs(logs.unwrap_or(true));
         stack.enable_debugger(debug.unwrap_or(false));
@@ -204,9 +234,13 @@ pub struct InspectorStack {
     pub coverage: Option<CoverageCollector>,
     pub debugger: Option<Debugger>,
     pub fuzzer: Option<Fuzzer>,
+    pub gas_schedule: Option<GasSchedule>,
     pub log_collector: Option<LogCollector>,
+    pub precompiles: Option<ChainPrecompiles>,
     pub printer: Option<TracePrinter>,
     pub tracer: Option<TracingInspector>,
+    /// The profile of the test the stack executes for, if profiling is enabled.
+    pub profile: Option<Arc<ExecutionProfile>>,
 }
 
 impl InspectorStack {
@@ -261,6 +295,18 @@ impl InspectorStack {
         self.chisel_state = Some(ChiselState::new(final_pc));
     }
 
+    /// Set the chain specific precompiles.
+    #[inline]
+    pub fn set_precompiles(&mut self, precompiles: ChainPrecompiles) {
+        self.precompiles = Some(precompiles);
+    }
+
+    /// Set the overridden gas costs.
+    #[inline]
+    pub fn set_gas_schedule(&mut self, gas_schedule: GasSchedule) {
+        self.gas_schedule = Some(gas_schedule);
+    }
+
     /// Set whether to enable the coverage collector.
     #[inline]
     pub fn collect_coverage(&mut self, yes: bool) {
@@ -397,7 +443,8 @@ impl<DB: DatabaseExt> Inspector<DB> for InspectorStack {
                 &mut self.coverage,
                 &mut self.log_collector,
                 &mut self.cheatcodes,
-                &mut self.printer
+                &mut self.printer,
+                &mut self.gas_schedule
             ],
             |inspector| {
                 inspector.step(interpreter, data);
@@ -430,6 +477,7 @@ impl<DB: DatabaseExt> Inspector<DB> for InspectorStack {
         let res = interpreter.instruction_result;
         call_inspectors!(
             [
+                &mut self.gas_schedule,
                 &mut self.debugger,
                 &mut self.tracer,
                 &mut self.log_collector,
@@ -454,6 +502,12 @@ impl<DB: DatabaseExt> Inspector<DB> for InspectorStack {
         data: &mut EVMData<'_, DB>,
         call: &mut CallInputs,
     ) -> (InstructionResult, Gas, Bytes) {
+        if let Some(profile) = &self.profile {
+            if call.contract == CHEATCODE_ADDRESS {
+                profile.record_cheatcode_call();
+            }
+        }
+
         call_inspectors!(
             [
                 &mut self.fuzzer,
@@ -462,6 +516,7 @@ impl<DB: DatabaseExt> Inspector<DB> for InspectorStack {
                 &mut self.coverage,
                 &mut self.log_collector,
                 &mut self.cheatcodes,
+                &mut self.precompiles,
                 &mut self.printer
             ],
             |inspector| {
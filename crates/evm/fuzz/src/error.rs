@@ -2,7 +2,7 @@
 use proptest::test_runner::Reason;
 
 /// Possible errors when running fuzz tests
-#[derive(Debug, thiserror::Error)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
 pub enum FuzzError {
     #[error("Couldn't call unknown contract")]
     UnknownContract,
@@ -14,6 +14,12 @@ pub enum FuzzError {
     AssumeReject,
     #[error("The `vm.assume` cheatcode rejected too many inputs ({0} allowed)")]
     TooManyRejects(u32),
+    #[error("The fuzz test timed out")]
+    Timeout,
+    #[error("Another fuzz worker found a counterexample")]
+    Stopped,
+    #[error("The fuzz test was cancelled")]
+    Cancelled,
 }
 
 impl From<FuzzError> for Reason {
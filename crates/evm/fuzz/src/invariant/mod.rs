@@ -12,6 +12,10 @@ pub use filters::{ArtifactFilters, SenderFilters};
 pub type TargetedContracts = BTreeMap<Address, (String, JsonAbi, Vec<Function>)>;
 pub type FuzzRunIdentifiedContracts = Arc<Mutex<TargetedContracts>>;
 
+/// Ordered sequences of functions of a target contract that are called as a single unit, declared
+/// with `vm.targetFlow`.
+pub type TargetedFlows = Vec<(Address, Vec<Function>)>;
+
 /// (Sender, (TargetContract, Calldata))
 pub type BasicTxDetails = (Address, (Address, Bytes));
 
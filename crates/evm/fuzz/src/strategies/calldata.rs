@@ -2,7 +2,14 @@ use super::fuzz_param;
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_json_abi::Function;
 use alloy_primitives::Bytes;
-use proptest::prelude::{BoxedStrategy, Strategy};
+use parking_lot::RwLock;
+use proptest::prelude::{any, prop, BoxedStrategy, Strategy};
+use std::sync::Arc;
+
+/// Inputs that reached new code during a coverage-guided fuzz run.
+///
+/// Wrapped in a shareable container.
+pub type FuzzCorpus = Arc<RwLock<Vec<Bytes>>>;
 
 /// Given a function, it returns a strategy which generates valid calldata
 /// for that function's input types.
@@ -22,3 +29,32 @@ pub fn fuzz_calldata(func: Function) -> BoxedStrategy<Bytes> {
         })
         .boxed()
 }
+
+/// Given a function and a corpus of previous inputs, it returns a strategy which mutates a random
+/// input of the corpus by replacing one of its arguments with a newly generated value.
+///
+/// Generates entirely new calldata while the corpus is empty.
+pub fn fuzz_calldata_from_corpus(func: Function, corpus: FuzzCorpus) -> BoxedStrategy<Bytes> {
+    let strats = func
+        .inputs
+        .iter()
+        .map(|input| fuzz_param(&input.selector_type().parse().unwrap()))
+        .collect::<Vec<_>>();
+
+    (any::<prop::sample::Index>(), any::<prop::sample::Index>(), strats)
+        .prop_map(move |(entry, arg, tokens)| {
+            let corpus = corpus.read();
+            let mut args = (!corpus.is_empty())
+                .then(|| &corpus[entry.index(corpus.len())])
+                .and_then(|calldata| func.abi_decode_input(&calldata[4..], false).ok())
+                .unwrap_or_else(|| tokens.clone());
+            if !args.is_empty() {
+                let arg = arg.index(args.len());
+                args[arg] = tokens[arg].clone();
+            }
+            trace!(input=?args);
+            func.abi_encode_input(&args).unwrap().into()
+        })
+        .no_shrink()
+        .boxed()
+}
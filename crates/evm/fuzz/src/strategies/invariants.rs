@@ -1,6 +1,6 @@
 use super::fuzz_param_from_state;
 use crate::{
-    invariant::{BasicTxDetails, FuzzRunIdentifiedContracts, SenderFilters},
+    invariant::{BasicTxDetails, FuzzRunIdentifiedContracts, SenderFilters, TargetedFlows},
     strategies::{fuzz_calldata, fuzz_calldata_from_state, fuzz_param, EvmFuzzState},
 };
 use alloy_json_abi::{Function, JsonAbi};
@@ -46,27 +46,38 @@ pub fn override_call_strat(
 /// contract:
 ///
 /// `targetContracts()`, `targetSenders()`, `excludeContracts()`, `targetSelectors()`
+///
+/// If any `flows` are given, the strategy generates either a single call or all the calls of a
+/// flow, made by the same sender.
 pub fn invariant_strat(
     fuzz_state: EvmFuzzState,
     senders: SenderFilters,
     contracts: FuzzRunIdentifiedContracts,
+    flows: TargetedFlows,
     dictionary_weight: u32,
-) -> impl Strategy<Value = Vec<BasicTxDetails>> {
+) -> BoxedStrategy<Vec<BasicTxDetails>> {
+    let senders = Rc::new(senders);
     // We only want to seed the first value, since we want to generate the rest as we mutate the
     // state
-    generate_call(fuzz_state, senders, contracts, dictionary_weight).prop_map(|x| vec![x])
+    let call = generate_call(fuzz_state.clone(), senders.clone(), contracts, dictionary_weight)
+        .prop_map(|x| vec![x])
+        .boxed();
+    if flows.is_empty() {
+        return call
+    }
+    let flow = generate_flow(fuzz_state, senders, flows, dictionary_weight);
+    proptest::strategy::Union::new_weighted(vec![(80, call), (20, flow)]).boxed()
 }
 
 /// Strategy to generate a transaction where the `sender`, `target` and `calldata` are all generated
 /// through specific strategies.
 fn generate_call(
     fuzz_state: EvmFuzzState,
-    senders: SenderFilters,
+    senders: Rc<SenderFilters>,
     contracts: FuzzRunIdentifiedContracts,
     dictionary_weight: u32,
 ) -> BoxedStrategy<BasicTxDetails> {
     let random_contract = select_random_contract(contracts);
-    let senders = Rc::new(senders);
     random_contract
         .prop_flat_map(move |(contract, abi, functions)| {
             let func = select_random_function(abi, functions);
@@ -81,6 +92,29 @@ fn generate_call(
         .boxed()
 }
 
+/// Strategy to generate the transactions of a randomly selected flow, all made by the same sender.
+fn generate_flow(
+    fuzz_state: EvmFuzzState,
+    senders: Rc<SenderFilters>,
+    flows: TargetedFlows,
+    dictionary_weight: u32,
+) -> BoxedStrategy<Vec<BasicTxDetails>> {
+    any::<prop::sample::Selector>()
+        .prop_map(move |selector| selector.select(&flows).clone())
+        .prop_flat_map(move |(target, functions)| {
+            let sender =
+                select_random_sender(fuzz_state.clone(), senders.clone(), dictionary_weight);
+            let calls = functions
+                .into_iter()
+                .map(|func| fuzz_contract_with_calldata(fuzz_state.clone(), target, func))
+                .collect::<Vec<_>>();
+            (sender, calls).prop_map(|(sender, calls)| {
+                calls.into_iter().map(|call| (sender, call)).collect::<Vec<_>>()
+            })
+        })
+        .boxed()
+}
+
 /// Strategy to select a sender address:
 /// * If `senders` is empty, then it's either a random address (10%) or from the dictionary (90%).
 /// * If `senders` is not empty, a random address is chosen from the list of senders.
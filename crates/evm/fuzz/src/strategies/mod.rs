@@ -1,14 +1,14 @@
 mod int;
-pub use int::IntStrategy;
+pub use int::{IntStrategy, IntValueTree};
 
 mod uint;
-pub use uint::UintStrategy;
+pub use uint::{UintStrategy, UintValueTree};
 
 mod param;
 pub use param::{fuzz_param, fuzz_param_from_state};
 
 mod calldata;
-pub use calldata::fuzz_calldata;
+pub use calldata::{fuzz_calldata, fuzz_calldata_from_corpus, FuzzCorpus};
 
 mod state;
 pub use state::{
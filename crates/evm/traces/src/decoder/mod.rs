@@ -476,6 +476,7 @@ impl CallTraceDecoder {
         match func.name.as_str() {
             s if s.starts_with("env") => Some("<env var value>"),
             "createWallet" | "deriveKey" => Some("<pk>"),
+            "getSecret" => Some("<secret>"),
             "parseJson" if self.verbosity < 5 => Some("<encoded JSON value>"),
             "readFile" if self.verbosity < 5 => Some("<file>"),
             _ => None,
@@ -645,6 +646,8 @@ mod tests {
             // Should redact private key on output in all cases:
             ("createWallet(string)", Some("<pk>".to_string())),
             ("deriveKey(string,uint32)", Some("<pk>".to_string())),
+            // Should redact secrets on output:
+            ("getSecret(string)", Some("<secret>".to_string())),
         ];
 
         for (function_signature, data, expected) in cheatcode_input_test_cases {
@@ -9,7 +9,7 @@ extern crate tracing;
 
 use alloy_primitives::LogData;
 use foundry_common::contracts::{ContractsByAddress, ContractsByArtifact};
-use foundry_evm_core::constants::CHEATCODE_ADDRESS;
+use foundry_evm_core::{constants::CHEATCODE_ADDRESS, secrets::redact_secrets};
 use futures::{future::BoxFuture, FutureExt};
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt::Write};
@@ -66,7 +66,8 @@ const RETURN: &str = "← ";
 
 /// Render a collection of call traces.
 ///
-/// The traces will be decoded using the given decoder, if possible.
+/// The traces will be decoded using the given decoder, if possible. The secrets resolved with
+/// `vm.getSecret` are redacted.
 pub async fn render_trace_arena(
     arena: &CallTraceArena,
     decoder: &CallTraceDecoder,
@@ -147,7 +148,7 @@ pub async fn render_trace_arena(
 
     let mut s = String::new();
     inner(arena.nodes(), decoder, &mut s, 0, "  ", "  ").await?;
-    Ok(s)
+    Ok(redact_secrets(&s).into_owned())
 }
 
 /// Render a call trace.
@@ -6,7 +6,7 @@ use crate::{
     comments::{
         CommentPosition, CommentState, CommentStringExt, CommentType, CommentWithMetadata, Comments,
     },
-    helpers::import_path_string,
+    helpers::{import_path_string, ImportGroup},
     macros::*,
     solang_ext::{pt::*, *},
     string::{QuoteState, QuotedStringExt},
@@ -14,10 +14,12 @@ use crate::{
     FormatterConfig, InlineConfig, IntTypes,
 };
 use alloy_primitives::Address;
-use foundry_config::fmt::{HexUnderscore, MultilineFuncHeaderStyle, SingleLineBlockStyle};
+use foundry_config::fmt::{
+    HexUnderscore, MultilineFuncHeaderStyle, SingleLineBlockStyle, YulStyle,
+};
 use itertools::{Either, Itertools};
 use solang_parser::pt::ImportPath;
-use std::{fmt::Write, str::FromStr};
+use std::{collections::HashSet, fmt::Write, str::FromStr};
 use thiserror::Error;
 
 type Result<T, E = FormatterError> = std::result::Result<T, E>;
@@ -78,6 +80,11 @@ struct Context {
     contract: Option<ContractDefinition>,
     function: Option<FunctionDefinition>,
     if_stmt_single_line: Option<bool>,
+    /// The start offsets of the import statements which were regrouped, so the blank lines
+    /// between them are determined by their import groups instead of the source
+    grouped_imports: HashSet<usize>,
+    /// The locations of the removed unused import statements, which don't count as blank lines
+    removed_imports: Vec<Loc>,
 }
 
 impl Context {
@@ -247,7 +254,14 @@ impl<'a, W: Write> Formatter<'a, W> {
         if start > end {
             return 0
         }
-        self.source[start..end].trim_comments().matches('\n').count()
+        // each removed import statement took up a line of its own
+        let removed = self
+            .context
+            .removed_imports
+            .iter()
+            .filter(|loc| start <= loc.start() && loc.end() <= end)
+            .count();
+        self.source[start..end].trim_comments().matches('\n').count().saturating_sub(removed)
     }
 
     /// Get the byte offset of the next line
@@ -330,6 +344,67 @@ impl<'a, W: Write> Formatter<'a, W> {
         }
     }
 
+    /// Returns whether the attempt should be made to write a nested block of inline assembly on a
+    /// single line.
+    fn should_attempt_yul_block_single_line(&self) -> bool {
+        !matches!(self.config.yul_style, YulStyle::Multi)
+    }
+
+    /// Returns the width of the widest case label of the switch statement, if the blocks of its
+    /// cases should be aligned.
+    ///
+    /// The blocks are aligned with [YulStyle::Aligned] if all cases fit on a single line.
+    fn yul_switch_label_width(&mut self, stmt: &mut YulSwitch) -> Result<Option<usize>> {
+        if self.config.yul_style != YulStyle::Aligned {
+            return Ok(None)
+        }
+
+        let mut width = 0;
+        for case in stmt.cases.iter_mut().chain(stmt.default.as_mut()) {
+            let (loc, mut expr, block) = match case {
+                YulSwitchOptions::Case(loc, expr, block) => (*loc, Some(expr), block),
+                YulSwitchOptions::Default(loc, block) => (*loc, None, block),
+            };
+            let mut write_label = |fmt: &mut Self| match &mut expr {
+                Some(expr) => {
+                    write_chunk!(fmt, loc.start(), "case")?;
+                    expr.visit(fmt)
+                }
+                None => write_chunk!(fmt, loc.start(), "default"),
+            };
+            let Some(label) = self.simulate_to_single_line(&mut write_label)? else {
+                return Ok(None)
+            };
+            let fits = self
+                .simulate_to_single_line(|fmt| {
+                    write_label(fmt)?;
+                    fmt.visit_yul_block(block.loc, &mut block.statements, true)
+                })?
+                .is_some();
+            if !fits {
+                return Ok(None)
+            }
+            width = width.max(label.trim().chars().count());
+        }
+        Ok(Some(width))
+    }
+
+    /// Pads the label of a switch case, which starts at `label_start` on the current line, to
+    /// `label_width` so the blocks of the cases are aligned.
+    fn pad_yul_switch_label(
+        &mut self,
+        label_width: Option<usize>,
+        label_start: usize,
+    ) -> Result<()> {
+        let Some(label_width) = label_width else { return Ok(()) };
+        let label_len = self.current_line_len().saturating_sub(label_start);
+        if label_len < label_width {
+            // the padding replaces the space which is written before the block
+            write!(self.buf(), "{}", " ".repeat(label_width - label_len + 1))?;
+        }
+        Ok(())
+    }
+
     /// Create a chunk given a string and the location information
     fn chunk_at(
         &mut self,
@@ -1024,6 +1099,7 @@ impl<'a, W: Write> Formatter<'a, W> {
 
                             if needs_space ||
                                 (!is_last_doc_comment &&
+                                    !self.context.grouped_imports.contains(&loc.start()) &&
                                     self.blank_lines(last_loc.end(), loc.start()) > 1)
                             {
                                 writeln!(self.buf())?;
@@ -1645,9 +1721,14 @@ impl<'a, W: Write> Formatter<'a, W> {
     }
 
     /// Sorts grouped import statement alphabetically.
-    fn sort_imports(&self, source_unit: &mut SourceUnit) {
+    ///
+    /// If `group_imports` is enabled, the import statements are regrouped by their
+    /// [`ImportGroup`] first.
+    fn sort_imports(&mut self, source_unit: &mut SourceUnit) {
         // first we need to find the grouped import statements
-        // A group is defined as a set of import statements that are separated by a blank line
+        // A group is defined as a set of import statements that are separated by a blank line, or
+        // by a comment if the imports are regrouped
+        let group_imports = self.config.group_imports;
         let mut import_groups = Vec::new();
         let mut current_group = Vec::new();
         let mut source_unit_parts = source_unit.0.iter().enumerate().peekable();
@@ -1657,9 +1738,15 @@ impl<'a, W: Write> Formatter<'a, W> {
                 let current_loc = part.loc();
                 if let Some((_, next_part)) = source_unit_parts.peek() {
                     let next_loc = next_part.loc();
-                    // import statements are followed by a new line, so if there are more than one
-                    // we have a group
-                    if self.blank_lines(current_loc.end(), next_loc.start()) > 1 {
+                    let separated = if group_imports {
+                        let between = &self.source[current_loc.end()..next_loc.start()];
+                        !between.trim_matches(|c: char| c == ';' || c.is_whitespace()).is_empty()
+                    } else {
+                        // import statements are followed by a new line, so if there are more than
+                        // one we have a group
+                        self.blank_lines(current_loc.end(), next_loc.start()) > 1
+                    };
+                    if separated {
                         import_groups.push(std::mem::take(&mut current_group));
                     }
                 }
@@ -1695,16 +1782,157 @@ impl<'a, W: Write> Formatter<'a, W> {
             }
 
             import_directives.sort_by_cached_key(|item| match item {
-                SourceUnitPart::ImportDirective(import) => match import {
-                    Import::Plain(path, _) => path.to_string(),
-                    Import::GlobalSymbol(path, _, _) => path.to_string(),
-                    Import::Rename(path, _, _) => path.to_string(),
-                },
+                SourceUnitPart::ImportDirective(import) => {
+                    let group = group_imports.then(|| ImportGroup::of(import));
+                    match import {
+                        Import::Plain(path, _) => (group, path.to_string()),
+                        Import::GlobalSymbol(path, _, _) => (group, path.to_string()),
+                        Import::Rename(path, _, _) => (group, path.to_string()),
+                    }
+                }
                 _ => {
                     unreachable!("import group contains non-import statement")
                 }
             });
+
+            if group_imports {
+                self.context
+                    .grouped_imports
+                    .extend(import_directives.iter().map(|item| item.loc().start()));
+            }
+        }
+    }
+
+    /// Removes the named and aliased imports whose symbols aren't used in the rest of the file.
+    ///
+    /// Plain imports are kept, since the symbols they import are unknown. Files which don't
+    /// define any contract, interface or library are left as is, since they are likely to import
+    /// symbols only to re-export them.
+    fn remove_unused_imports(&mut self, source_unit: &mut SourceUnit) {
+        if !source_unit.0.iter().any(|part| matches!(part, SourceUnitPart::ContractDefinition(_))) {
+            return
+        }
+
+        let source = self.source;
+        let import_locs = source_unit
+            .0
+            .iter()
+            .filter(|part| matches!(part, SourceUnitPart::ImportDirective(_)))
+            .map(|part| part.loc())
+            .collect::<Vec<_>>();
+
+        // collect all identifiers outside of the import statements, including the ones in
+        // comments, since they may be referenced in NatSpec, e.g. `@inheritdoc`
+        let mut used = HashSet::new();
+        let mut start = None;
+        let chars = source.char_indices().chain(std::iter::once((source.len(), ' ')));
+        for (i, ch) in chars {
+            let is_identifier = ch.is_ascii_alphanumeric() || ch == '_' || ch == '$';
+            match (start, is_identifier) {
+                (None, true) => start = Some(i),
+                (Some(start_idx), false) => {
+                    if !import_locs.iter().any(|loc| loc.range().contains(&start_idx)) {
+                        used.insert(&source[start_idx..i]);
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        let mut removed = Vec::new();
+        source_unit.0.retain_mut(|part| {
+            let loc = part.loc();
+            let SourceUnitPart::ImportDirective(import) = part else { return true };
+            if self.inline_config.is_disabled(loc) {
+                return true
+            }
+            let keep = match import {
+                Import::Plain(..) => true,
+                Import::GlobalSymbol(_, symbol, _) => used.contains(symbol.name.as_str()),
+                Import::Rename(_, renames, _) => {
+                    if renames.is_empty() {
+                        return true
+                    }
+                    renames.retain(|(ident, alias)| {
+                        used.contains(alias.as_ref().unwrap_or(ident).name.as_str())
+                    });
+                    !renames.is_empty()
+                }
+            };
+            if !keep {
+                removed.push(loc);
+            }
+            keep
+        });
+        self.context.removed_imports.extend(removed);
+    }
+
+    /// Writes an import statement of a list of named imports.
+    fn write_import_renames(
+        &mut self,
+        loc: Loc,
+        imports: &mut [(Identifier, Option<Identifier>)],
+        from: &mut ImportPath,
+    ) -> Result<()> {
+        if imports.is_empty() {
+            self.grouped(|fmt| {
+                write_chunk!(fmt, loc.start(), "import")?;
+                fmt.write_empty_brackets()?;
+                write_chunk!(fmt, loc.start(), from.loc().start(), "from")?;
+                fmt.write_quoted_str(from.loc(), None, &import_path_string(from))?;
+                fmt.write_semicolon()?;
+                Ok(())
+            })?;
+            return Ok(())
         }
+
+        let imports_start = imports.first().unwrap().0.loc.start();
+
+        write_chunk!(self, loc.start(), imports_start, "import")?;
+
+        self.surrounded(
+            SurroundingChunk::new("{", Some(imports_start), None),
+            SurroundingChunk::new("}", None, Some(from.loc().start())),
+            |fmt, _multiline| {
+                let mut imports = imports.iter_mut().peekable();
+                let mut import_chunks = Vec::new();
+                while let Some((ident, alias)) = imports.next() {
+                    import_chunks.push(fmt.chunked(
+                        ident.loc.start(),
+                        imports.peek().map(|(ident, _)| ident.loc.start()),
+                        |fmt| {
+                            fmt.grouped(|fmt| {
+                                ident.visit(fmt)?;
+                                if let Some(alias) = alias {
+                                    write_chunk!(fmt, ident.loc.end(), alias.loc.start(), "as")?;
+                                    alias.visit(fmt)?;
+                                }
+                                Ok(())
+                            })?;
+                            Ok(())
+                        },
+                    )?);
+                }
+
+                let multiline = fmt.are_chunks_separated_multiline(
+                    &format!("{{}} }} from \"{}\";", import_path_string(from)),
+                    &import_chunks,
+                    ",",
+                )?;
+                fmt.write_chunks_separated(&import_chunks, ",", multiline)?;
+                Ok(())
+            },
+        )?;
+
+        self.grouped(|fmt| {
+            write_chunk!(fmt, imports_start, from.loc().start(), "from")?;
+            fmt.write_quoted_str(from.loc(), None, &import_path_string(from))?;
+            fmt.write_semicolon()?;
+            Ok(())
+        })?;
+
+        Ok(())
     }
 }
 
@@ -1732,9 +1960,13 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
 
     #[instrument(name = "SU", skip_all)]
     fn visit_source_unit(&mut self, source_unit: &mut SourceUnit) -> Result<()> {
-        if self.config.sort_imports {
+        if self.config.remove_unused_imports {
+            self.remove_unused_imports(source_unit);
+        }
+        if self.config.sort_imports || self.config.group_imports {
             self.sort_imports(source_unit);
         }
+        let group_imports = self.config.group_imports;
         // TODO: do we need to put pragma and import directives at the top of the file?
         // source_unit.0.sort_by_key(|item| match item {
         //     SourceUnitPart::PragmaDirective(_, _, _) => 0,
@@ -1758,9 +1990,12 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
                 SourceUnitPart::PragmaDirective(..) => {
                     !matches!(unit, SourceUnitPart::PragmaDirective(..))
                 }
-                SourceUnitPart::ImportDirective(_) => {
-                    !matches!(unit, SourceUnitPart::ImportDirective(_))
-                }
+                SourceUnitPart::ImportDirective(import) => match unit {
+                    SourceUnitPart::ImportDirective(next) => {
+                        group_imports && ImportGroup::of(import) != ImportGroup::of(next)
+                    }
+                    _ => true,
+                },
                 SourceUnitPart::ErrorDefinition(_) => {
                     !matches!(unit, SourceUnitPart::ErrorDefinition(_))
                 }
@@ -1982,64 +2217,14 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     ) -> Result<()> {
         return_source_if_disabled!(self, loc, ';');
 
-        if imports.is_empty() {
-            self.grouped(|fmt| {
-                write_chunk!(fmt, loc.start(), "import")?;
-                fmt.write_empty_brackets()?;
-                write_chunk!(fmt, loc.start(), from.loc().start(), "from")?;
-                fmt.write_quoted_str(from.loc(), None, &import_path_string(from))?;
-                fmt.write_semicolon()?;
-                Ok(())
-            })?;
-            return Ok(())
+        // named import lists are wrapped at their own line length, if configured
+        let line_length = self.config.line_length;
+        if let Some(import_line_length) = self.config.import_line_length {
+            self.config.line_length = import_line_length;
         }
-
-        let imports_start = imports.first().unwrap().0.loc.start();
-
-        write_chunk!(self, loc.start(), imports_start, "import")?;
-
-        self.surrounded(
-            SurroundingChunk::new("{", Some(imports_start), None),
-            SurroundingChunk::new("}", None, Some(from.loc().start())),
-            |fmt, _multiline| {
-                let mut imports = imports.iter_mut().peekable();
-                let mut import_chunks = Vec::new();
-                while let Some((ident, alias)) = imports.next() {
-                    import_chunks.push(fmt.chunked(
-                        ident.loc.start(),
-                        imports.peek().map(|(ident, _)| ident.loc.start()),
-                        |fmt| {
-                            fmt.grouped(|fmt| {
-                                ident.visit(fmt)?;
-                                if let Some(alias) = alias {
-                                    write_chunk!(fmt, ident.loc.end(), alias.loc.start(), "as")?;
-                                    alias.visit(fmt)?;
-                                }
-                                Ok(())
-                            })?;
-                            Ok(())
-                        },
-                    )?);
-                }
-
-                let multiline = fmt.are_chunks_separated_multiline(
-                    &format!("{{}} }} from \"{}\";", import_path_string(from)),
-                    &import_chunks,
-                    ",",
-                )?;
-                fmt.write_chunks_separated(&import_chunks, ",", multiline)?;
-                Ok(())
-            },
-        )?;
-
-        self.grouped(|fmt| {
-            write_chunk!(fmt, imports_start, from.loc().start(), "from")?;
-            fmt.write_quoted_str(from.loc(), None, &import_path_string(from))?;
-            fmt.write_semicolon()?;
-            Ok(())
-        })?;
-
-        Ok(())
+        let res = self.write_import_renames(loc, imports, from);
+        self.config.line_length = line_length;
+        res
     }
 
     #[instrument(name = "enum", skip_all)]
@@ -2084,6 +2269,9 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
         flags: &mut Option<Vec<StringLiteral>>,
     ) -> Result<(), Self::Error> {
         return_source_if_disabled!(self, loc);
+        if self.config.yul_style == YulStyle::Preserve {
+            return self.visit_source(loc)
+        }
 
         write_chunk!(self, loc.start(), "assembly")?;
         if let Some(StringLiteral { loc, string, .. }) = dialect {
@@ -3547,7 +3735,12 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
         self.visit_yul_block(stmt.init_block.loc, &mut stmt.init_block.statements, true)?;
         stmt.condition.visit(self)?;
         self.visit_yul_block(stmt.post_block.loc, &mut stmt.post_block.statements, true)?;
-        self.visit_yul_block(stmt.execution_block.loc, &mut stmt.execution_block.statements, true)?;
+        let attempt_single_line = self.should_attempt_yul_block_single_line();
+        self.visit_yul_block(
+            stmt.execution_block.loc,
+            &mut stmt.execution_block.statements,
+            attempt_single_line,
+        )?;
         Ok(())
     }
 
@@ -3598,7 +3791,8 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
         return_source_if_disabled!(self, loc);
         write_chunk!(self, loc.start(), "if")?;
         expr.visit(self)?;
-        self.visit_yul_block(block.loc, &mut block.statements, true)
+        let attempt_single_line = self.should_attempt_yul_block_single_line();
+        self.visit_yul_block(block.loc, &mut block.statements, attempt_single_line)
     }
 
     #[instrument(name = "yul_leave", skip_all)]
@@ -3611,22 +3805,29 @@ impl<'a, W: Write> Visitor for Formatter<'a, W> {
     fn visit_yul_switch(&mut self, stmt: &mut YulSwitch) -> Result<(), Self::Error> {
         return_source_if_disabled!(self, stmt.loc);
 
+        let label_width = self.yul_switch_label_width(stmt)?;
+        let attempt_single_line = self.should_attempt_yul_block_single_line();
+
         write_chunk!(self, stmt.loc.start(), "switch")?;
         stmt.condition.visit(self)?;
         writeln_chunk!(self)?;
         let mut cases = stmt.cases.iter_mut().peekable();
         while let Some(YulSwitchOptions::Case(loc, expr, block)) = cases.next() {
+            let label_start = self.current_line_len();
             write_chunk!(self, loc.start(), "case")?;
             expr.visit(self)?;
-            self.visit_yul_block(block.loc, &mut block.statements, true)?;
+            self.pad_yul_switch_label(label_width, label_start)?;
+            self.visit_yul_block(block.loc, &mut block.statements, attempt_single_line)?;
             let is_last = cases.peek().is_none();
             if !is_last || stmt.default.is_some() {
                 writeln_chunk!(self)?;
             }
         }
         if let Some(YulSwitchOptions::Default(loc, ref mut block)) = stmt.default {
+            let label_start = self.current_line_len();
             write_chunk!(self, loc.start(), "default")?;
-            self.visit_yul_block(block.loc, &mut block.statements, true)?;
+            self.pad_yul_switch_label(label_width, label_start)?;
+            self.visit_yul_block(block.loc, &mut block.statements, attempt_single_line)?;
         }
         Ok(())
     }
@@ -109,6 +109,36 @@ pub fn import_path_string(path: &ImportPath) -> String {
     }
 }
 
+/// The origin of an imported file, in the order the import groups are written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ImportGroup {
+    /// `forge-std` and `ds-test`
+    Std,
+    /// Any other dependency
+    Lib,
+    /// Relative imports and imports of the project's sources, tests and scripts
+    Local,
+}
+
+impl ImportGroup {
+    pub(crate) fn of(import: &Import) -> Self {
+        let (Import::Plain(path, _) |
+        Import::GlobalSymbol(path, _, _) |
+        Import::Rename(path, _, _)) = import;
+        let path = import_path_string(path);
+        if path.starts_with("forge-std/") || path.starts_with("ds-test/") {
+            ImportGroup::Std
+        } else if ["./", "../", "src/", "test/", "script/"]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            ImportGroup::Local
+        } else {
+            ImportGroup::Lib
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
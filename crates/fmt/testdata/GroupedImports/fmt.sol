@@ -0,0 +1,18 @@
+// config: group_imports = true
+pragma solidity ^0.8.0;
+
+import {Test} from "forge-std/Test.sol";
+import {console2} from "forge-std/console2.sol";
+
+import {Ownable} from "@openzeppelin/contracts/access/Ownable.sol";
+import {ERC20} from "solmate/tokens/ERC20.sol";
+
+import "./Utils.sol";
+import {Vault} from "src/Vault.sol";
+
+// comments separate the imports which are grouped
+import {Math} from "@openzeppelin/contracts/utils/math/Math.sol";
+
+import {Token} from "../Token.sol";
+
+contract VaultTest is Test {}
@@ -0,0 +1,15 @@
+pragma solidity ^0.8.0;
+
+import {Vault} from "src/Vault.sol";
+import {Test} from "forge-std/Test.sol";
+
+import {ERC20} from "solmate/tokens/ERC20.sol";
+import "./Utils.sol";
+import {console2} from "forge-std/console2.sol";
+import {Ownable} from "@openzeppelin/contracts/access/Ownable.sol";
+
+// comments separate the imports which are grouped
+import {Token} from "../Token.sol";
+import {Math} from "@openzeppelin/contracts/utils/math/Math.sol";
+
+contract VaultTest is Test {}
@@ -0,0 +1,11 @@
+// config: import_line_length = 100
+import "SomeFile.sol";
+import "SomeFile.sol";
+import "SomeFile.sol" as SomeOtherFile;
+import "SomeFile.sol" as SomeOtherFile;
+import "AnotherFile.sol" as SomeSymbol;
+import "AnotherFile.sol" as SomeSymbol;
+import {symbol1 as alias, symbol2} from "File.sol";
+import {symbol1 as alias, symbol2} from "File.sol";
+import {symbol1 as alias1, symbol2 as alias2, symbol3 as alias3, symbol4} from "File2.sol";
+import {symbol1 as alias1, symbol2 as alias2, symbol3 as alias3, symbol4} from "File2.sol";
@@ -0,0 +1,12 @@
+// config: remove_unused_imports = true
+import "forge-std/Test.sol";
+import {ERC20, SafeTransferLib as Transfer} from "solmate/Solmate.sol";
+import {IVault} from "src/Vault.sol";
+import "src/Utils.sol" as Utils;
+
+contract VaultTest is Test {
+    /// @inheritdoc IVault
+    function deposit(ERC20 token) external {
+        Transfer.safeTransfer(token, address(0), Utils.amount());
+    }
+}
@@ -0,0 +1,13 @@
+import "forge-std/Test.sol";
+import {console2} from "forge-std/console2.sol";
+import {ERC20, SafeTransferLib as Transfer} from "solmate/Solmate.sol";
+import {IVault, Vault} from "src/Vault.sol";
+import "src/Token.sol" as Token;
+import * as Utils from "src/Utils.sol";
+
+contract VaultTest is Test {
+    /// @inheritdoc IVault
+    function deposit(ERC20 token) external {
+        Transfer.safeTransfer(token, address(0), Utils.amount());
+    }
+}
@@ -0,0 +1,4 @@
+// config: remove_unused_imports = true
+import {ERC20, SafeTransferLib as Transfer} from "solmate/Solmate.sol";
+import {IVault, Vault} from "src/Vault.sol";
+import * as Utils from "src/Utils.sol";
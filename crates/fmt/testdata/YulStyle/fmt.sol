@@ -0,0 +1,12 @@
+contract YulStyle {
+    function run(uint256 x) external returns (uint256 y) {
+        assembly {
+            if iszero(x) { revert(0, 0) }
+            switch x
+            case 1 { y := 10 }
+            case 100 { y := 20 }
+            default { y := 30 }
+            for { let i := 0 } lt(i, 10) { i := add(i, 1) } { mstore(i, 7) }
+        }
+    }
+}
@@ -218,6 +218,7 @@ test_directories! {
     LiteralExpression,
     Yul,
     YulStrings,
+    YulStyle,
     IntTypes,
     InlineDisable,
     NumberLiteralUnderscore,
@@ -235,3 +236,6 @@ test_directories! {
 }
 
 test_dir!(SortedImports, TestConfig::skip_compare_ast_eq());
+test_dir!(GroupedImports, TestConfig::skip_compare_ast_eq());
+test_dir!(UnusedImports, TestConfig::skip_compare_ast_eq());
+test_dir!(UnusedImportsReexport, TestConfig::skip_compare_ast_eq());
@@ -0,0 +1,318 @@
+use super::verify::standard_json_input;
+use alloy_primitives::{keccak256, Address, B256};
+use clap::{Parser, ValueHint};
+use ethers_core::{types::Signature, utils::hash_message};
+use ethers_signers::LocalWallet;
+use eyre::{Context, Result};
+use foundry_cli::{opts::CoreBuildArgs, utils::LoadConfig};
+use foundry_common::{compile::ProjectCompiler, fs, types::ToAlloy};
+use foundry_compilers::{
+    artifacts::ConfigurableContractArtifact, info::ContractInfo, utils::canonicalize, Artifact,
+    ArtifactId, Project, ProjectCompileOutput, Solc,
+};
+use foundry_config::{Config, SolcReq};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use yansi::Paint;
+
+/// The directory the attestations are written to, relative to the project root.
+pub const ATTESTATIONS_DIR: &str = "attestations";
+
+/// The provenance of a compiled artifact, written by `forge build --attest`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Attestation {
+    /// The identifier of the contract in the form `<path>:<contractname>`
+    pub contract: String,
+    pub compiler_version: String,
+    /// The keccak256 hash of the compiler binary
+    pub compiler_hash: B256,
+    /// The keccak256 hash of the standard json input compiling the contract
+    pub input_hash: B256,
+    pub settings: AttestedSettings,
+    /// The keccak256 hash of the init code, unless it has unlinked libraries
+    pub bytecode_hash: Option<B256>,
+    /// The keccak256 hash of the runtime code, unless it has unlinked libraries
+    pub deployed_bytecode_hash: Option<B256>,
+    pub host: HostInfo,
+    /// The UNIX timestamp of the build
+    pub timestamp: u64,
+    /// The address which signed the attestation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<Address>,
+    /// The EIP-191 signature of the attestation without the signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// The settings the contract was compiled with.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestedSettings {
+    pub evm_version: Option<String>,
+    pub optimizer: bool,
+    pub optimizer_runs: Option<usize>,
+    pub via_ir: bool,
+}
+
+/// The machine the contract was compiled on.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostInfo {
+    pub os: String,
+    pub arch: String,
+    /// The version of forge
+    pub forge: String,
+}
+
+impl Attestation {
+    /// Creates the attestation of the artifact, from the standard json input compiling it and the
+    /// compiler binary found for its version.
+    fn new(
+        config: &Config,
+        project: &Project,
+        id: &ArtifactId,
+        artifact: &ConfigurableContractArtifact,
+    ) -> Result<Self> {
+        let input = standard_json_input(project, &id.source, &id.version)?;
+        let settings = AttestedSettings {
+            evm_version: input.settings.evm_version.map(|evm_version| evm_version.to_string()),
+            optimizer: input.settings.optimizer.enabled.unwrap_or_default(),
+            optimizer_runs: input.settings.optimizer.runs,
+            via_ir: input.settings.via_ir.unwrap_or_default(),
+        };
+        let compiler = compiler_path(config, &id.version)?;
+        let path = id.source.strip_prefix(project.root()).unwrap_or(&id.source);
+
+        Ok(Self {
+            contract: format!("{}:{}", path.display(), id.name),
+            compiler_version: id.version.to_string(),
+            compiler_hash: keccak256(fs::read(&compiler)?),
+            input_hash: keccak256(serde_json::to_vec(&input)?),
+            settings,
+            bytecode_hash: artifact.get_bytecode_bytes().map(|code| keccak256(code.as_ref())),
+            deployed_bytecode_hash: artifact
+                .get_deployed_bytecode_bytes()
+                .map(|code| keccak256(code.as_ref())),
+            host: HostInfo {
+                os: std::env::consts::OS.to_string(),
+                arch: std::env::consts::ARCH.to_string(),
+                forge: env!("CARGO_PKG_VERSION").to_string(),
+            },
+            timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            signer: None,
+            signature: None,
+        })
+    }
+
+    /// Returns the message which is signed: the JSON encoded attestation without the signature.
+    fn signing_payload(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(&Self { signature: None, ..self.clone() })?)
+    }
+
+    /// Signs the attestation with the wallet.
+    fn sign(&mut self, wallet: &LocalWallet) -> Result<()> {
+        self.signer = Some(ethers_signers::Signer::address(wallet).to_alloy());
+        let signature = wallet.sign_hash(hash_message(self.signing_payload()?))?;
+        self.signature = Some(format!("0x{signature}"));
+        Ok(())
+    }
+
+    /// Returns the address which signed the attestation, if it's signed.
+    fn recover_signer(&self) -> Result<Option<Address>> {
+        let Some(signature) = &self.signature else { return Ok(None) };
+        let signature = Signature::from_str(signature).wrap_err("Invalid signature")?;
+        let signer = signature.recover(self.signing_payload()?.as_slice())?;
+        Ok(Some(signer.to_alloy()))
+    }
+}
+
+/// Writes the attestations of the artifacts of the sources in `src` to [ATTESTATIONS_DIR],
+/// signed with the private key if one is given, and returns the number of attestations.
+pub fn write_attestations(
+    config: &Config,
+    project: &Project,
+    output: &ProjectCompileOutput,
+    private_key: Option<&str>,
+) -> Result<usize> {
+    let wallet = private_key
+        .map(|key| LocalWallet::from_str(key).wrap_err("Invalid attestation private key"))
+        .transpose()?;
+
+    let mut count = 0;
+    for (id, artifact) in output.artifact_ids() {
+        if !id.source.starts_with(&config.src) {
+            continue
+        }
+        let mut attestation = Attestation::new(config, project, &id, artifact)?;
+        if let Some(wallet) = &wallet {
+            attestation.sign(wallet)?;
+        }
+        let path = attestation_path(config, &id);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write_json_file(&path, &attestation)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// Returns the path of the attestation of the artifact, which mirrors the artifact's path in the
+/// `out` directory.
+fn attestation_path(config: &Config, id: &ArtifactId) -> PathBuf {
+    let mut path = config.__root.0.join(ATTESTATIONS_DIR);
+    match id.path.strip_prefix(&config.out) {
+        Ok(relative) => path.push(relative),
+        Err(_) => {
+            path.push(id.source.file_name().unwrap_or_default());
+            path.push(format!("{}.json", id.name));
+        }
+    }
+    path
+}
+
+/// Returns the path of the compiler binary of the version.
+fn compiler_path(config: &Config, version: &Version) -> Result<PathBuf> {
+    if let Some(SolcReq::Local(solc)) = &config.solc {
+        return Ok(solc.clone())
+    }
+    let version = format!("{}.{}.{}", version.major, version.minor, version.patch);
+    Solc::find_svm_installed_version(&version)?
+        .map(|solc| solc.solc)
+        .ok_or_else(|| eyre::eyre!("Could not find the binary of solc {version}"))
+}
+
+/// CLI arguments for `forge verify-attestation`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyAttestationArgs {
+    /// The identifier of the contract in the form `(<path>:)?<contractname>`.
+    pub contract: ContractInfo,
+
+    /// The address which must have signed the attestation.
+    #[clap(long, value_name = "ADDRESS")]
+    pub signer: Option<Address>,
+
+    /// The path of the attestation.
+    ///
+    /// By default, the attestation written by `forge build --attest` is used.
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub attestation: Option<PathBuf>,
+
+    #[clap(flatten)]
+    build: CoreBuildArgs,
+}
+
+impl VerifyAttestationArgs {
+    pub fn run(mut self) -> Result<()> {
+        let config = self.build.try_load_config_emit_warnings()?;
+        let project = config.project()?;
+        if let Some(path) = &mut self.contract.path {
+            *path = canonicalize(&*path)?.to_string_lossy().to_string();
+        }
+        let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+        let (id, artifact) = output
+            .artifact_ids()
+            .find(|(id, _)| {
+                id.name == self.contract.name &&
+                    self.contract.path.as_ref().map_or(true, |path| id.source == Path::new(path))
+            })
+            .ok_or_else(|| eyre::eyre!("Could not find artifact `{}`", self.contract))?;
+
+        let path = self.attestation.unwrap_or_else(|| attestation_path(&config, &id));
+        let attested: Attestation = fs::read_json_file(&path)
+            .wrap_err_with(|| format!("Failed to read attestation {}", path.display()))?;
+        let rebuilt = Attestation::new(&config, &project, &id, artifact)?;
+
+        let mut mismatches = Vec::new();
+        let checks = [
+            ("contract", attested.contract == rebuilt.contract),
+            ("compiler version", attested.compiler_version == rebuilt.compiler_version),
+            ("compiler binary", attested.compiler_hash == rebuilt.compiler_hash),
+            ("compiler input", attested.input_hash == rebuilt.input_hash),
+            ("settings", attested.settings == rebuilt.settings),
+            ("bytecode", attested.bytecode_hash == rebuilt.bytecode_hash),
+            (
+                "deployed bytecode",
+                attested.deployed_bytecode_hash == rebuilt.deployed_bytecode_hash,
+            ),
+        ];
+        for (name, matches) in checks {
+            if !matches {
+                mismatches.push(name.to_string());
+            }
+        }
+
+        match (attested.recover_signer()?, attested.signer, self.signer) {
+            (Some(recovered), Some(signer), expected) if recovered == signer => {
+                if expected.map_or(false, |expected| expected != signer) {
+                    mismatches.push(format!("signer {signer}"));
+                }
+            }
+            (None, _, None) => {}
+            (None, _, Some(_)) => mismatches.push("missing signature".to_string()),
+            _ => mismatches.push("signature".to_string()),
+        }
+
+        if !mismatches.is_empty() {
+            eyre::bail!(
+                "Artifact `{}` doesn't match its attestation: {}",
+                attested.contract,
+                mismatches.join(", ")
+            );
+        }
+        println!(
+            "{} artifact `{}` matches its attestation{}",
+            Paint::green("Verified"),
+            attested.contract,
+            attested.signer.map(|signer| format!(" signed by {signer}")).unwrap_or_default()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signs_attestations() {
+        let mut attestation = Attestation {
+            contract: "src/Vault.sol:Vault".to_string(),
+            compiler_version: "0.8.23".to_string(),
+            compiler_hash: B256::repeat_byte(1),
+            input_hash: B256::repeat_byte(2),
+            settings: AttestedSettings {
+                evm_version: Some("paris".to_string()),
+                optimizer: true,
+                optimizer_runs: Some(200),
+                via_ir: false,
+            },
+            bytecode_hash: Some(B256::repeat_byte(3)),
+            deployed_bytecode_hash: None,
+            host: HostInfo {
+                os: "linux".to_string(),
+                arch: "x86_64".to_string(),
+                forge: "0.2.0".to_string(),
+            },
+            timestamp: 1700000000,
+            signer: None,
+            signature: None,
+        };
+        assert_eq!(attestation.recover_signer().unwrap(), None);
+
+        let wallet = LocalWallet::from_str(
+            "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+        )
+        .unwrap();
+        attestation.sign(&wallet).unwrap();
+        let signer = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".parse().unwrap();
+        assert_eq!(attestation.signer, Some(signer));
+        assert_eq!(attestation.recover_signer().unwrap(), Some(signer));
+
+        attestation.input_hash = B256::repeat_byte(4);
+        assert_ne!(attestation.recover_signer().unwrap(), Some(signer));
+    }
+}
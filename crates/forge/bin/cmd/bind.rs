@@ -1,10 +1,15 @@
-use clap::{Parser, ValueHint};
-use ethers_contract::{Abigen, ContractFilter, ExcludeContracts, MultiAbigen, SelectContracts};
+use clap::{Parser, ValueEnum, ValueHint};
+use ethers_contract::{Abigen, ContractFilter, ExcludeContracts, SelectContracts};
+use ethers_contract_abigen::multi::{MultiBindings, MultiExpansion};
+use ethers_core::abi::{Abi, ParamType};
 use eyre::{Result, WrapErr};
 use foundry_cli::{opts::CoreBuildArgs, utils::LoadConfig};
 use foundry_common::{compile::ProjectCompiler, fs::json_files};
 use foundry_config::impl_figment_convert;
+use serde_json::Value;
 use std::{
+    collections::BTreeMap,
+    fmt::Write,
     fs,
     path::{Path, PathBuf},
 };
@@ -77,10 +82,26 @@ pub struct BindArgs {
     #[clap(long)]
     skip_build: bool,
 
+    /// The language to generate the bindings in.
+    #[clap(long, value_enum, default_value_t = BindingsLanguage::Rust)]
+    language: BindingsLanguage,
+
     #[clap(flatten)]
     build_args: CoreBuildArgs,
 }
 
+/// The languages bindings can be generated in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum BindingsLanguage {
+    /// A Rust crate or module using `ethers`
+    #[default]
+    Rust,
+    /// TypeScript modules exporting `viem` compatible ABIs, deploy helpers and deployed
+    /// addresses
+    #[clap(alias = "ts")]
+    Typescript,
+}
+
 impl BindArgs {
     pub fn run(self) -> Result<()> {
         if !self.skip_build {
@@ -89,7 +110,12 @@ impl BindArgs {
             let _ = ProjectCompiler::new().compile(&project)?;
         }
 
-        let artifacts = self.try_load_config_emit_warnings()?.out;
+        let config = self.try_load_config_emit_warnings()?;
+        let artifacts = config.out;
+
+        if self.language == BindingsLanguage::Typescript {
+            return self.run_typescript(&artifacts, &config.deployments)
+        }
 
         if !self.overwrite && self.bindings_exist(&artifacts) {
             println!("Bindings found. Checking for consistency.");
@@ -146,41 +172,44 @@ impl BindArgs {
             .into()
     }
 
-    /// Instantiate the multi-abigen
-    fn get_multi(&self, artifacts: impl AsRef<Path>) -> Result<MultiAbigen> {
-        let abigens = json_files(artifacts.as_ref())
-            .into_iter()
-            .filter_map(|path| {
-                // we don't want `.metadata.json files
-                let stem = path.file_stem()?;
-                if stem.to_str()?.ends_with(".metadata") {
-                    None
-                } else {
-                    Some(path)
-                }
-            })
-            .map(|path| {
-                trace!(?path, "parsing Abigen from file");
-                Abigen::from_file(&path)
-                    .wrap_err_with(|| format!("failed to parse Abigen from file: {:?}", path))?
-                    .add_derive("serde::Serialize")?
-                    .add_derive("serde::Deserialize")
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-        let multi = MultiAbigen::from_abigens(abigens).with_filter(self.get_filter());
+    /// Expands the bindings of the filtered contracts, each with its multicall aggregator
+    fn get_bindings(&self, artifacts: impl AsRef<Path>) -> Result<MultiBindings> {
+        let filter = self.get_filter();
+        let mut expansions = Vec::new();
+        for path in json_files(artifacts.as_ref()) {
+            // we don't want `.metadata.json files
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            if name.ends_with(".metadata") || !filter.is_match(name) {
+                continue
+            }
+            trace!(?path, "parsing Abigen from file");
+            let abigen = Abigen::from_file(&path)
+                .wrap_err_with(|| format!("failed to parse Abigen from file: {:?}", path))?
+                .add_derive("serde::Serialize")?
+                .add_derive("serde::Deserialize")?;
+            let artifact: Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            let abi: Abi = match artifact.get("abi") {
+                Some(abi) => serde_json::from_value(abi.clone())?,
+                None => serde_json::from_value(artifact)?,
+            };
+
+            let (mut expanded, context) = abigen.expand()?;
+            expanded.contract.extend(multicall_aggregator(name, &abi)?);
+            expansions.push((expanded, context));
+        }
 
         eyre::ensure!(
-            !multi.is_empty(),
+            !expansions.is_empty(),
             r#"
 No contract artifacts found. Hint: Have you built your contracts yet? `forge bind` does not currently invoke `forge build`, although this is planned for future versions.
             "#
         );
-        Ok(multi)
+        Ok(MultiExpansion::new(expansions).expand().into_bindings())
     }
 
     /// Check that the existing bindings match the expected abigen output
     fn check_existing_bindings(&self, artifacts: impl AsRef<Path>) -> Result<()> {
-        let bindings = self.get_multi(&artifacts)?.build()?;
+        let bindings = self.get_bindings(&artifacts)?;
         println!("Checking bindings for {} contracts.", bindings.len());
         if !self.module {
             bindings
@@ -207,7 +236,7 @@ No contract artifacts found. Hint: Have you built your contracts yet? `forge bin
 
     /// Generate the bindings
     fn generate_bindings(&self, artifacts: impl AsRef<Path>) -> Result<()> {
-        let bindings = self.get_multi(&artifacts)?.build()?;
+        let bindings = self.get_bindings(&artifacts)?;
         println!("Generating bindings for {} contracts", bindings.len());
         if !self.module {
             trace!(single_file = self.single_file, "generating crate");
@@ -222,4 +251,396 @@ No contract artifacts found. Hint: Have you built your contracts yet? `forge bin
             bindings.write_to_module(self.bindings_root(&artifacts), self.single_file)
         }
     }
+
+    /// Generates the TypeScript bindings, or checks the existing ones if `--overwrite` isn't
+    /// passed.
+    fn run_typescript(&self, artifacts: &Path, deployments: &Path) -> Result<()> {
+        let files = self.typescript_bindings(artifacts, deployments)?;
+        let root = self.bindings_root(artifacts);
+
+        if !self.overwrite && root.is_dir() {
+            println!("Bindings found. Checking for consistency.");
+            for (name, contents) in &files {
+                let path = root.join(name);
+                let existing = fs::read_to_string(&path).unwrap_or_default();
+                eyre::ensure!(
+                    existing == *contents,
+                    "{} is not consistent with the contract artifacts, pass --overwrite to regenerate it",
+                    path.display()
+                );
+            }
+            println!("OK.");
+            return Ok(())
+        }
+
+        if root.is_dir() {
+            trace!(?root, "Removing existing bindings");
+            fs::remove_dir_all(&root)?;
+        }
+        fs::create_dir_all(&root)?;
+        println!("Generating bindings for {} contracts", files.len() - 1);
+        for (name, contents) in &files {
+            fs::write(root.join(name), contents)?;
+        }
+        println!("Bindings have been output to {}", root.display());
+        Ok(())
+    }
+
+    /// Returns the TypeScript modules of the contracts' bindings, and an `index.ts` module
+    /// re-exporting them, by file name.
+    fn typescript_bindings(
+        &self,
+        artifacts: &Path,
+        deployments: &Path,
+    ) -> Result<BTreeMap<String, String>> {
+        let filter = self.get_filter();
+        let mut contracts = BTreeMap::new();
+        for path in json_files(artifacts) {
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else { continue };
+            if name.ends_with(".metadata") || contracts.contains_key(name) || !filter.is_match(name)
+            {
+                continue
+            }
+            let artifact: Value = serde_json::from_str(&fs::read_to_string(&path)?)
+                .wrap_err_with(|| format!("failed to parse artifact: {path:?}"))?;
+            let Some(abi) = artifact.get("abi").filter(|abi| abi.is_array()) else { continue };
+            let bytecode = artifact
+                .pointer("/bytecode/object")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            let addresses = deployed_addresses(deployments, name)?;
+            contracts
+                .insert(name.to_string(), typescript_module(name, abi, &bytecode, &addresses)?);
+        }
+
+        eyre::ensure!(
+            !contracts.is_empty(),
+            "No contract artifacts found. Hint: Have you built your contracts yet?"
+        );
+
+        let mut index = String::new();
+        for name in contracts.keys() {
+            writeln!(index, "export * from \"./{name}\";")?;
+        }
+        let mut files = contracts
+            .into_iter()
+            .map(|(name, module)| (format!("{name}.ts"), module))
+            .collect::<BTreeMap<_, _>>();
+        files.insert("index.ts".to_string(), index);
+        Ok(files)
+    }
+}
+
+/// Returns the addresses the contract was deployed at with `forge script --save-deployments`, by
+/// chain ID.
+fn deployed_addresses(deployments: &Path, name: &str) -> Result<BTreeMap<u64, String>> {
+    let mut addresses = BTreeMap::new();
+    let Ok(chains) = fs::read_dir(deployments) else { return Ok(addresses) };
+    for chain in chains {
+        let chain = chain?.path();
+        let Some(chain_id) = chain.file_name().and_then(|id| id.to_str()?.parse::<u64>().ok())
+        else {
+            continue
+        };
+        let Ok(deployment) = fs::read_to_string(chain.join(format!("{name}.json"))) else {
+            continue
+        };
+        let deployment: Value = serde_json::from_str(&deployment)?;
+        if let Some(address) = deployment.get("address").and_then(Value::as_str) {
+            addresses.insert(chain_id, address.to_string());
+        }
+    }
+    Ok(addresses)
+}
+
+/// Returns the TypeScript module of a contract's bindings: its ABI `as const`, and its bytecode
+/// with a typed `viem` deploy helper and its deployed addresses, if any.
+fn typescript_module(
+    name: &str,
+    abi: &Value,
+    bytecode: &str,
+    addresses: &BTreeMap<u64, String>,
+) -> Result<String> {
+    let ident = camel_case(name);
+    // contracts without bytecode, or with unlinked libraries, can't be deployed as is
+    let deployable = bytecode.len() > 2 && !bytecode.contains("__");
+
+    let mut module = String::from("// This file was generated by `forge bind`. Do not edit.\n");
+    if deployable {
+        module.push_str(
+            "\nimport type {\n  Account,\n  Chain,\n  DeployContractParameters,\n  Transport,\n  WalletClient,\n} from \"viem\";\n",
+        );
+    }
+    write!(
+        module,
+        "\nexport const {ident}Abi = {} as const;\n",
+        serde_json::to_string_pretty(abi)?
+    )?;
+
+    if deployable {
+        let ty = format!("DeployContractParameters<typeof {ident}Abi, TChain, TAccount>");
+        write!(
+            module,
+            r#"
+export const {ident}Bytecode = "{bytecode}" as const;
+
+export function deploy{name}<
+  TChain extends Chain | undefined,
+  TAccount extends Account | undefined,
+>(
+  client: WalletClient<Transport, TChain, TAccount>,
+  parameters: Omit<{ty}, "abi" | "bytecode">,
+) {{
+  return client.deployContract({{
+    abi: {ident}Abi,
+    bytecode: {ident}Bytecode,
+    ...parameters,
+  }} as {ty});
+}}
+"#
+        )?;
+    }
+
+    if !addresses.is_empty() {
+        writeln!(module, "\nexport const {ident}Address = {{")?;
+        for (chain_id, address) in addresses {
+            writeln!(module, "  {chain_id}: \"{address}\",")?;
+        }
+        writeln!(module, "}} as const;")?;
+    }
+    Ok(module)
+}
+
+/// Returns the `<Contract>Multicall` aggregator of a contract's bindings, which batches calls to
+/// the contract's functions into a single call to the `Multicall3` contract.
+///
+/// Each function gets an `add_<function>` method, suffixed with the index of the overload for
+/// overloaded functions. Functions with `uint8` array parameters or returns are skipped, since
+/// they can't be told apart from `bytes` when tokenized.
+fn multicall_aggregator(name: &str, abi: &Abi) -> Result<proc_macro2::TokenStream> {
+    let mut methods = String::new();
+    for (function_name, overloads) in &abi.functions {
+        for (i, function) in overloads.iter().enumerate() {
+            let inputs = function
+                .inputs
+                .iter()
+                .map(|param| rust_type(&param.kind))
+                .collect::<Option<Vec<_>>>();
+            let outputs = function
+                .outputs
+                .iter()
+                .map(|param| rust_type(&param.kind))
+                .collect::<Option<Vec<_>>>();
+            let (Some(inputs), Some(outputs)) = (inputs, outputs) else { continue };
+
+            let mut method = format!("add_{}", snake_case(function_name));
+            if overloads.len() > 1 {
+                write!(method, "_{i}")?;
+            }
+            let params = inputs
+                .iter()
+                .enumerate()
+                .map(|(i, ty)| format!("p{i}: {ty}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let args = (0..inputs.len()).map(|i| format!("p{i},")).collect::<String>();
+            let output = match outputs.len() {
+                1 => outputs[0].clone(),
+                _ => format!("({})", outputs.iter().map(|ty| format!("{ty},")).collect::<String>()),
+            };
+            let selector = function.short_signature().map(|byte| format!("{byte}u8")).join(", ");
+            write!(
+                methods,
+                r#"
+    /// Adds a call to `{signature}` to the batch.
+    pub fn {method}(&mut self, {params}) -> &mut Self {{
+        let call = self
+            .contract
+            .method_hash::<_, {output}>([{selector}], ({args}))
+            .expect("method not found (this should never happen)");
+        self.multicall.add_call(call, false);
+        self
+    }}
+"#,
+                signature = function.signature(),
+            )?;
+        }
+    }
+
+    let aggregator = format!(
+        r#"
+/// Batches calls to the `{name}` contract into a single call to the `Multicall3` contract.
+pub struct {name}Multicall<M> {{
+    contract: {name}<M>,
+    multicall: ::ethers::contract::Multicall<M>,
+}}
+
+impl<M: ::ethers::providers::Middleware> {name}Multicall<M> {{
+    /// Creates an empty batch of calls to the contract, aggregated by the `Multicall3` contract
+    /// deployed at its canonical address.
+    pub async fn new(
+        contract: {name}<M>,
+    ) -> ::core::result::Result<Self, ::ethers::contract::MulticallError<M>> {{
+        let multicall = ::ethers::contract::Multicall::new(contract.client(), None).await?;
+        Ok(Self {{ contract, multicall }})
+    }}
+
+    /// Executes the batched calls with `eth_call`, and returns their decoded results.
+    pub async fn call<T: ::ethers::core::abi::Tokenizable>(
+        &self,
+    ) -> ::core::result::Result<T, ::ethers::contract::MulticallError<M>> {{
+        self.multicall.call().await
+    }}
+
+    /// Returns the underlying `Multicall`, e.g. to send the batched calls in a transaction.
+    pub fn into_multicall(self) -> ::ethers::contract::Multicall<M> {{
+        self.multicall
+    }}
+{methods}}}
+"#
+    );
+    aggregator
+        .parse()
+        .map_err(|err| eyre::eyre!("failed to generate the multicall aggregator of {name}: {err}"))
+}
+
+/// Returns the Rust type of an ABI type in the bindings, or `None` if it's a `uint8` array.
+fn rust_type(kind: &ParamType) -> Option<String> {
+    let ty = match kind {
+        ParamType::Address => "::ethers::core::types::Address".to_string(),
+        ParamType::Bytes => "::ethers::core::types::Bytes".to_string(),
+        ParamType::Bool => "bool".to_string(),
+        ParamType::String => "::std::string::String".to_string(),
+        ParamType::Int(size) => match size {
+            ..=8 => "i8",
+            ..=16 => "i16",
+            ..=32 => "i32",
+            ..=64 => "i64",
+            ..=128 => "i128",
+            _ => "::ethers::core::types::I256",
+        }
+        .to_string(),
+        ParamType::Uint(size) => match size {
+            ..=8 => "u8",
+            ..=16 => "u16",
+            ..=32 => "u32",
+            ..=64 => "u64",
+            ..=128 => "u128",
+            _ => "::ethers::core::types::U256",
+        }
+        .to_string(),
+        ParamType::FixedBytes(size) => format!("[u8; {size}]"),
+        ParamType::Array(kind) | ParamType::FixedArray(kind, _)
+            if matches!(**kind, ParamType::Uint(8)) =>
+        {
+            return None
+        }
+        ParamType::Array(kind) => format!("::std::vec::Vec<{}>", rust_type(kind)?),
+        ParamType::FixedArray(kind, size) => format!("[{}; {size}]", rust_type(kind)?),
+        ParamType::Tuple(kinds) => format!(
+            "({})",
+            kinds
+                .iter()
+                .map(|kind| Some(format!("{},", rust_type(kind)?)))
+                .collect::<Option<String>>()?
+        ),
+    };
+    Some(ty)
+}
+
+/// Converts a function name to snake case, e.g. `balanceOf` to `balance_of`.
+fn snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len());
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).map_or(false, |c| c.is_lowercase());
+            if prev.is_lowercase() ||
+                prev.is_ascii_digit() ||
+                (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.push(c.to_ascii_lowercase());
+    }
+    snake
+}
+
+/// Converts a contract name to the camel case prefix of its exports, e.g. `ERC20Token` to
+/// `erc20Token`.
+fn camel_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let upper = chars.iter().take_while(|c| !c.is_lowercase()).count();
+    // keep the first letter of the next word in an acronym followed by one, e.g. `USDCVault`
+    let lower = if upper > 1 && upper < chars.len() && chars[upper - 1].is_uppercase() {
+        upper - 1
+    } else {
+        upper.max(1)
+    };
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| if i < lower { c.to_ascii_lowercase() } else { *c })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_to_camel_case() {
+        assert_eq!(camel_case("Counter"), "counter");
+        assert_eq!(camel_case("ERC20"), "erc20");
+        assert_eq!(camel_case("ERC20Token"), "erc20Token");
+        assert_eq!(camel_case("USDCVault"), "usdcVault");
+        assert_eq!(camel_case("myToken"), "myToken");
+    }
+
+    #[test]
+    fn converts_to_snake_case() {
+        assert_eq!(snake_case("number"), "number");
+        assert_eq!(snake_case("balanceOf"), "balance_of");
+        assert_eq!(snake_case("DOMAIN_SEPARATOR"), "domain_separator");
+        assert_eq!(snake_case("getERC20Balance"), "get_erc20_balance");
+        assert_eq!(snake_case("setURIPrefix"), "set_uri_prefix");
+    }
+
+    #[test]
+    fn generates_multicall_aggregator() {
+        let abi: Abi = serde_json::from_value(serde_json::json!([
+            { "type": "function", "name": "setNumber", "stateMutability": "nonpayable", "inputs": [{ "name": "x", "type": "uint256" }], "outputs": [] },
+            { "type": "function", "name": "number", "stateMutability": "view", "inputs": [], "outputs": [{ "name": "", "type": "uint256" }] },
+            { "type": "function", "name": "get", "stateMutability": "view", "inputs": [], "outputs": [{ "name": "", "type": "address" }, { "name": "", "type": "bool" }] },
+            { "type": "function", "name": "get", "stateMutability": "view", "inputs": [{ "name": "id", "type": "bytes32" }], "outputs": [] },
+            { "type": "function", "name": "packed", "stateMutability": "view", "inputs": [{ "name": "data", "type": "uint8[]" }], "outputs": [] }
+        ]))
+        .unwrap();
+        let aggregator = multicall_aggregator("Counter", &abi).unwrap().to_string();
+        assert!(aggregator.contains("pub struct CounterMulticall"), "{aggregator}");
+        for method in ["add_set_number", "add_number", "add_get_0", "add_get_1"] {
+            assert!(aggregator.contains(&format!("pub fn {method} (")), "{aggregator}");
+        }
+        assert!(!aggregator.contains("add_packed"), "{aggregator}");
+    }
+
+    #[test]
+    fn generates_typescript_module() {
+        let abi = serde_json::json!([{ "type": "constructor", "inputs": [] }]);
+        let addresses = BTreeMap::from([(1, "0x5FbDB2315678afecb367f032d93F642f64180aa3".into())]);
+        let module = typescript_module("Counter", &abi, "0x6080", &addresses).unwrap();
+        assert!(module.contains("export const counterAbi = ["));
+        assert!(module.contains("] as const;"));
+        assert!(module.contains("export const counterBytecode = \"0x6080\" as const;"));
+        assert!(module.contains("export function deployCounter<"));
+        assert!(module.contains("  1: \"0x5FbDB2315678afecb367f032d93F642f64180aa3\","));
+
+        let module = typescript_module("ICounter", &abi, "0x", &BTreeMap::new()).unwrap();
+        assert!(module.contains("export const iCounterAbi"));
+        assert!(!module.contains("deployICounter"));
+        assert!(!module.contains("from \"viem\""));
+    }
 }
@@ -1,8 +1,17 @@
-use super::{install, watch::WatchArgs};
+use super::{attest, install, watch::WatchArgs};
 use clap::Parser;
 use eyre::Result;
-use foundry_cli::{opts::CoreBuildArgs, utils::LoadConfig};
-use foundry_common::compile::{ProjectCompiler, SkipBuildFilter, SkipBuildFilters};
+use forge::doc_lint::find_missing_docs;
+use foundry_cli::{
+    opts::CoreBuildArgs,
+    p_println,
+    utils::{self, LoadConfig},
+};
+use foundry_common::{
+    compile::{ProjectCompiler, SkipBuildFilter, SkipBuildFilters},
+    fs,
+    remote_cache::RemoteCache,
+};
 use foundry_compilers::{Project, ProjectCompileOutput};
 use foundry_config::{
     figment::{
@@ -11,10 +20,12 @@ use foundry_config::{
         value::{Dict, Map, Value},
         Metadata, Profile, Provider,
     },
-    Config,
+    Config, DocLintLevel,
 };
 use serde::Serialize;
+use std::collections::HashSet;
 use watchexec::config::{InitConfig, RuntimeConfig};
+use yansi::Paint;
 
 foundry_config::merge_impl_figment_convert!(BuildArgs, args);
 
@@ -72,6 +83,21 @@ pub struct BuildArgs {
     #[clap(long, conflicts_with = "silent")]
     #[serde(skip)]
     pub format_json: bool,
+
+    /// Write an attestation of the provenance of each artifact of the sources in `src` to the
+    /// `attestations` directory.
+    ///
+    /// The attestations record the hashes of the compiler binary, the compiler input and the
+    /// bytecode, the compiler settings and the host, and can be checked with
+    /// `forge verify-attestation`.
+    #[clap(long)]
+    #[serde(skip)]
+    pub attest: bool,
+
+    /// The private key to sign the attestations with.
+    #[clap(long, env = "FORGE_ATTEST_PRIVATE_KEY", value_name = "PRIVATE_KEY")]
+    #[serde(skip)]
+    pub attest_private_key: Option<String>,
 }
 
 impl BuildArgs {
@@ -87,22 +113,69 @@ impl BuildArgs {
             project = config.project()?;
         }
 
+        let remote_cache = match RemoteCache::new(&config.remote_cache)? {
+            Some(cache) if !config.offline => Some((RemoteCache::input_hash(&project)?, cache)),
+            _ => None,
+        };
+        if let Some((input_hash, cache)) = &remote_cache {
+            // only cold builds are downloaded, a local cache is at least as recent
+            if !project.paths.cache.exists() {
+                match utils::block_on(cache.pull(&project, *input_hash)) {
+                    Ok(true) => {
+                        p_println!(!self.format_json => "Downloaded artifacts from the remote cache")
+                    }
+                    Ok(false) => {}
+                    Err(err) => warn_remote_cache(err),
+                }
+            }
+        }
+
         let mut compiler = ProjectCompiler::new()
             .print_names(self.names)
             .print_sizes(self.sizes)
             .quiet(self.format_json)
             .bail(!self.format_json);
+        let mut filtered = false;
         if let Some(skip) = self.skip {
             if !skip.is_empty() {
                 compiler = compiler.filter(Box::new(SkipBuildFilters::new(skip)?));
+                filtered = true;
             }
         }
         let output = compiler.compile(&project)?;
 
+        // the artifacts of filtered or failed builds are incomplete
+        let complete = !filtered && !output.has_compiler_errors();
+        if let Some((input_hash, cache)) = remote_cache.filter(|_| complete) {
+            match utils::block_on(cache.push(&project, input_hash)) {
+                Ok(true) => {
+                    p_println!(!self.format_json => "Uploaded artifacts to the remote cache")
+                }
+                Ok(false) => {}
+                Err(err) => warn_remote_cache(err),
+            }
+        }
+
         if self.format_json {
             println!("{}", serde_json::to_string_pretty(&output.clone().output())?);
         }
 
+        if config.doc_lint.is_enabled() {
+            check_docs(&config, &output)?;
+        }
+
+        if self.attest {
+            let count = attest::write_attestations(
+                &config,
+                &project,
+                &output,
+                self.attest_private_key.as_deref(),
+            )?;
+            if !self.format_json {
+                println!("Wrote {count} attestations to {}", attest::ATTESTATIONS_DIR);
+            }
+        }
+
         Ok(output)
     }
 
@@ -131,6 +204,55 @@ impl BuildArgs {
     }
 }
 
+/// Warns that the remote cache couldn't be used, without failing the build.
+fn warn_remote_cache(err: eyre::Report) {
+    eprintln!("{}", Paint::yellow(format!("Warning: Failed to use the remote cache: {err:#}")));
+}
+
+/// Checks the NatSpec documentation of the sources in `src`, as configured in `[doc_lint]`.
+///
+/// Missing documentation is printed as warnings, and fails the build if it's in a source whose
+/// level is `deny`.
+fn check_docs(config: &Config, output: &ProjectCompileOutput) -> Result<()> {
+    let root = &config.__root.0;
+    let mut checked = HashSet::new();
+    let mut denied = 0;
+    for (id, artifact) in output.artifact_ids() {
+        let Some(ast) = &artifact.ast else { continue };
+        if !id.source.starts_with(&config.src) || !checked.insert(id.source.clone()) {
+            continue
+        }
+        let path = id.source.strip_prefix(root).unwrap_or(&id.source);
+        let level = config.doc_lint.level_for(path);
+        if level == DocLintLevel::Off {
+            continue
+        }
+
+        let source = fs::read_to_string(&id.source)?;
+        for missing in find_missing_docs(ast, &source) {
+            eprintln!(
+                "{}: {}:{}: `{}` is missing {}",
+                if level == DocLintLevel::Deny {
+                    Paint::red("error[doc-lint]")
+                } else {
+                    Paint::yellow("warning[doc-lint]")
+                },
+                path.display(),
+                missing.line,
+                missing.item,
+                missing.missing.join(", ")
+            );
+            if level == DocLintLevel::Deny {
+                denied += 1;
+            }
+        }
+    }
+    if denied > 0 {
+        eyre::bail!("{denied} items are missing NatSpec documentation");
+    }
+    Ok(())
+}
+
 // Make this args a `figment::Provider` so that it can be merged into the `Config`
 impl Provider for BuildArgs {
     fn metadata(&self) -> Metadata {
@@ -1,10 +1,17 @@
-use cache::Cache;
+use alloy_primitives::{Address, U256};
+use cache::{Cache, ForkCacheEntry};
 use clap::{
     builder::{PossibleValuesParser, TypedValueParser},
     Arg, Command, Parser, Subcommand,
 };
-use eyre::Result;
+use eyre::{Context, Result};
 use foundry_config::{cache, Chain, Config, NamedChain};
+use foundry_evm::{
+    backend::Backend,
+    fork::{CreateFork, JsonBlockCacheDB},
+    opts::EvmOpts,
+    revm::db::DatabaseRef,
+};
 use std::{ffi::OsStr, str::FromStr};
 use strum::VariantNames;
 
@@ -22,6 +29,10 @@ pub enum CacheSubcommands {
 
     /// Shows cached data from the global foundry directory.
     Ls(LsArgs),
+
+    /// Manages the cached state of forks.
+    #[clap(subcommand)]
+    Fork(ForkCacheSubcommands),
 }
 
 /// CLI arguments for `forge clean`.
@@ -107,6 +118,190 @@ impl LsArgs {
     }
 }
 
+#[derive(Debug, Subcommand)]
+pub enum ForkCacheSubcommands {
+    /// Lists the cached blocks.
+    Ls(ForkLsArgs),
+
+    /// Shows what is cached for a block.
+    Inspect(ForkBlockArgs),
+
+    /// Removes cached blocks, except pinned ones.
+    Prune(ForkPruneArgs),
+
+    /// Protects a cached block from pruning and eviction.
+    Pin(ForkBlockArgs),
+
+    /// Removes the protection of a pinned block.
+    Unpin(ForkBlockArgs),
+
+    /// Fetches accounts and storage slots of a block into the cache.
+    Warm(ForkWarmArgs),
+}
+
+impl ForkCacheSubcommands {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            ForkCacheSubcommands::Ls(args) => args.run(),
+            ForkCacheSubcommands::Inspect(args) => args.inspect(),
+            ForkCacheSubcommands::Prune(args) => args.run(),
+            ForkCacheSubcommands::Pin(args) => args.set_pinned(true),
+            ForkCacheSubcommands::Unpin(args) => args.set_pinned(false),
+            ForkCacheSubcommands::Warm(args) => args.run().await,
+        }
+    }
+}
+
+/// CLI arguments for `forge cache fork ls`.
+#[derive(Debug, Parser)]
+pub struct ForkLsArgs {
+    /// The chain to list the cached blocks of, all chains if not set.
+    chain: Option<Chain>,
+}
+
+impl ForkLsArgs {
+    pub fn run(self) -> Result<()> {
+        let now = std::time::SystemTime::now();
+        for entry in Config::list_fork_cache(self.chain)? {
+            let days = now.duration_since(entry.last_used).unwrap_or_default().as_secs() / 86400;
+            println!("- {entry}, last used {days} days ago");
+        }
+        Ok(())
+    }
+}
+
+/// CLI arguments for the `forge cache fork` commands that target a single block.
+#[derive(Debug, Parser)]
+pub struct ForkBlockArgs {
+    /// The chain of the block.
+    chain: Chain,
+
+    /// The block number.
+    block: u64,
+}
+
+impl ForkBlockArgs {
+    fn entry(&self) -> Result<ForkCacheEntry> {
+        let path = Config::foundry_block_cache_dir(self.chain, self.block)
+            .ok_or_else(|| eyre::eyre!("failed to get foundry_block_cache_dir"))?;
+        ForkCacheEntry::read(self.chain, self.block, path)?
+            .ok_or_else(|| eyre::eyre!("block {} of {} is not cached", self.block, self.chain))
+    }
+
+    pub fn inspect(self) -> Result<()> {
+        let entry = self.entry()?;
+        let cache = JsonBlockCacheDB::load(entry.path.join(ForkCacheEntry::STORAGE_FILE))
+            .wrap_err("failed to read the cached data")?;
+        let db = cache.db();
+        let storage = db.storage.read();
+        println!("{entry}");
+        let hosts = cache.meta().read().hosts.iter().cloned().collect::<Vec<_>>();
+        println!("Hosts: {}", hosts.join(", "));
+        println!("Accounts: {}", db.accounts.read().len());
+        println!(
+            "Storage slots: {} in {} accounts",
+            storage.values().map(|slots| slots.len()).sum::<usize>(),
+            storage.len()
+        );
+        println!("Block hashes: {}", db.block_hashes.read().len());
+        Ok(())
+    }
+
+    pub fn set_pinned(self, pinned: bool) -> Result<()> {
+        self.entry()?.set_pinned(pinned)?;
+        Ok(())
+    }
+}
+
+/// CLI arguments for `forge cache fork prune`.
+#[derive(Debug, Parser)]
+pub struct ForkPruneArgs {
+    /// The chain to prune the cached blocks of, all chains if not set.
+    chain: Option<Chain>,
+
+    /// Only prune these blocks.
+    #[clap(long, num_args(1..), use_value_delimiter(true), value_delimiter(','))]
+    blocks: Vec<u64>,
+
+    /// Only prune blocks older than this block.
+    #[clap(long, value_name = "BLOCK")]
+    before: Option<u64>,
+
+    /// Instead of pruning all matching blocks, evict the least recently used blocks until all
+    /// cached blocks take up at most this many bytes.
+    #[clap(long, value_name = "BYTES", conflicts_with_all = ["chain", "blocks", "before"])]
+    max_size: Option<u64>,
+}
+
+impl ForkPruneArgs {
+    pub fn run(self) -> Result<()> {
+        let ForkPruneArgs { chain, blocks, before, max_size } = self;
+        if let Some(max_size) = max_size {
+            for entry in Config::evict_fork_cache(max_size, None)? {
+                println!("Evicted {entry}");
+            }
+            return Ok(())
+        }
+
+        for entry in Config::list_fork_cache(chain)? {
+            if entry.pinned ||
+                (!blocks.is_empty() && !blocks.contains(&entry.block)) ||
+                before.map_or(false, |before| entry.block >= before)
+            {
+                continue
+            }
+            std::fs::remove_dir_all(&entry.path)?;
+            println!("Pruned {entry}");
+        }
+        Ok(())
+    }
+}
+
+/// CLI arguments for `forge cache fork warm`.
+#[derive(Debug, Parser)]
+pub struct ForkWarmArgs {
+    /// The accounts to fetch.
+    #[clap(required = true)]
+    addresses: Vec<Address>,
+
+    /// The storage slots to fetch of every account.
+    #[clap(long, num_args(1..), use_value_delimiter(true), value_delimiter(','))]
+    slots: Vec<U256>,
+
+    /// The RPC endpoint or endpoint alias to fork.
+    #[clap(long, short, visible_alias = "rpc-url", value_name = "URL")]
+    fork_url: String,
+
+    /// The block to fetch the state of, the latest block if not set.
+    #[clap(long, value_name = "BLOCK")]
+    fork_block_number: Option<u64>,
+}
+
+impl ForkWarmArgs {
+    pub async fn run(self) -> Result<()> {
+        let ForkWarmArgs { addresses, slots, fork_url, fork_block_number } = self;
+        let url = match Config::load().get_rpc_url_with_alias(&fork_url) {
+            Some(url) => url?.into_owned(),
+            None => fork_url,
+        };
+
+        let mut evm_opts = Config::figment().extract::<EvmOpts>()?;
+        evm_opts.fork_block_number = fork_block_number;
+        let (env, _) = evm_opts.fork_evm_env(&url).await?;
+        let fork = CreateFork { enable_caching: true, url, env, evm_opts };
+
+        // the cache is written to disk once the backend is dropped
+        let backend = Backend::spawn(Some(fork)).await;
+        for address in addresses {
+            backend.basic_ref(address)?;
+            for slot in &slots {
+                backend.storage_ref(address, *slot)?;
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum ChainOrAll {
     NamedChain(NamedChain),
@@ -186,4 +381,18 @@ mod tests {
         let args: CacheArgs = CacheArgs::parse_from(["cache", "ls"]);
         assert!(matches!(args.sub, CacheSubcommands::Ls(_)));
     }
+
+    #[test]
+    fn can_parse_cache_fork() {
+        let args: CacheArgs =
+            CacheArgs::parse_from(["cache", "fork", "prune", "mainnet", "--blocks", "1,2"]);
+        let CacheSubcommands::Fork(ForkCacheSubcommands::Prune(args)) = args.sub else {
+            panic!("expected `cache fork prune`")
+        };
+        assert_eq!(args.chain, Some(Chain::mainnet()));
+        assert_eq!(args.blocks, vec![1, 2]);
+
+        let args: CacheArgs = CacheArgs::parse_from(["cache", "fork", "pin", "1", "100"]);
+        assert!(matches!(args.sub, CacheSubcommands::Fork(ForkCacheSubcommands::Pin(_))));
+    }
 }
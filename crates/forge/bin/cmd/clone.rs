@@ -0,0 +1,304 @@
+use super::{init::InitArgs, install::DependencyInstallOpts};
+use alloy_primitives::Address;
+use clap::{Parser, ValueHint};
+use eyre::{Context, Result};
+use foundry_block_explorers::{contract::Metadata, errors::EtherscanError, Client};
+use foundry_cli::{opts::EtherscanOpts, p_println, utils::Git};
+use foundry_common::fs;
+use foundry_compilers::remappings::Remapping;
+use foundry_config::{Chain, Config};
+use serde::Serialize;
+use std::{
+    collections::BTreeSet,
+    path::{Component, Path, PathBuf},
+};
+use yansi::Paint;
+
+/// The file the deployment metadata of the cloned contract is written to.
+pub const CLONE_META_FILE: &str = ".clone.meta";
+
+/// CLI arguments for `forge clone`.
+#[derive(Clone, Debug, Parser)]
+pub struct CloneArgs {
+    /// The address of the verified contract to clone.
+    pub address: Address,
+
+    /// The root directory of the cloned project.
+    #[clap(value_hint = ValueHint::DirPath, default_value = ".", value_name = "PATH")]
+    pub root: PathBuf,
+
+    /// Do not install dependencies from the network.
+    #[clap(long, visible_alias = "no-deps")]
+    pub offline: bool,
+
+    #[clap(flatten)]
+    pub etherscan: EtherscanOpts,
+
+    #[clap(flatten)]
+    pub opts: DependencyInstallOpts,
+}
+
+/// The deployment of a cloned contract, written to [`CLONE_META_FILE`].
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneMetadata {
+    /// The path of the source file declaring the contract, relative to the project root
+    pub path: PathBuf,
+    /// The identifier of the contract in the form `<path>:<contractname>`
+    pub target_contract: String,
+    pub address: Address,
+    pub chain_id: u64,
+    pub compiler_version: String,
+    pub constructor_arguments: String,
+    /// The implementation the contract delegates to, if Etherscan detected it as a proxy
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub implementation: Option<Address>,
+}
+
+impl CloneArgs {
+    pub async fn run(self) -> Result<()> {
+        let CloneArgs { address, root, offline, etherscan, opts } = self;
+
+        let config = Config::from(&etherscan);
+        let chain = config.chain.unwrap_or_default();
+        let api_key = config.get_etherscan_api_key(Some(chain)).unwrap_or_default();
+        let meta = fetch_metadata(&Client::new(chain, api_key)?, address, chain).await?;
+
+        // the sources are written to `src`, keeping the layout they were verified with
+        let sources = meta
+            .source_tree()
+            .entries
+            .into_iter()
+            .map(|entry| Ok((source_path(&entry.path, &meta.contract_name)?, entry.contents)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // initialize an empty project, without the template contracts
+        InitArgs {
+            root: root.clone(),
+            template: None,
+            branch: None,
+            offline,
+            force: false,
+            vscode: false,
+            opts: DependencyInstallOpts { no_commit: true, ..opts },
+        }
+        .run()?;
+        let root = dunce::canonicalize(root)?;
+        for template in ["src/Counter.sol", "test/Counter.t.sol", "script/Counter.s.sol"] {
+            fs::remove_file(root.join(template))?;
+        }
+
+        for (path, contents) in &sources {
+            let target = root.join(path);
+            fs::create_dir_all(target.parent().unwrap())?;
+            fs::write(&target, contents)?;
+        }
+
+        let settings = meta.source_code.settings()?.unwrap_or_default();
+        let mut remappings = settings
+            .remappings
+            .iter()
+            .map(|remapping| Remapping {
+                context: remapping.context.clone(),
+                name: remapping.name.clone(),
+                path: format!("src/{}", remapping.path.trim_start_matches('/')),
+            })
+            .collect::<Vec<_>>();
+        remappings.extend(source_dir_remappings(sources.iter().map(|(path, _)| path), &remappings));
+
+        let version = meta.compiler_version()?;
+        let mut profile = toml::map::Map::new();
+        profile.insert(
+            "solc".into(),
+            format!("{}.{}.{}", version.major, version.minor, version.patch).into(),
+        );
+        let optimizer = settings.optimizer.enabled.unwrap_or(meta.optimization_used == 1);
+        profile.insert("optimizer".into(), optimizer.into());
+        if optimizer {
+            let runs = settings.optimizer.runs.map_or(meta.runs, |runs| runs as u64);
+            profile.insert("optimizer_runs".into(), (runs as i64).into());
+        }
+        if let Some(via_ir) = settings.via_ir {
+            profile.insert("via_ir".into(), via_ir.into());
+        }
+        let evm_version = settings
+            .evm_version
+            .map(|evm_version| evm_version.to_string())
+            .or_else(|| Some(meta.evm_version.to_lowercase()).filter(|v| v != "default"));
+        if let Some(evm_version) = evm_version {
+            profile.insert("evm_version".into(), evm_version.into());
+        }
+        if !remappings.is_empty() {
+            let remappings = remappings.iter().map(|r| toml::Value::from(r.to_string())).collect();
+            profile.insert("remappings".into(), toml::Value::Array(remappings));
+        }
+        let libraries = settings
+            .libraries
+            .libs
+            .iter()
+            .flat_map(|(file, libs)| {
+                libs.iter().map(move |(name, address)| {
+                    toml::Value::from(format!("src/{}:{name}:{address}", file.display()))
+                })
+            })
+            .collect::<Vec<_>>();
+        if !libraries.is_empty() {
+            profile.insert("libraries".into(), toml::Value::Array(libraries));
+        }
+        update_profile(&root, profile)?;
+
+        // record where the contract is deployed
+        let path = find_contract_source(&sources, &meta.contract_name)
+            .ok_or_else(|| eyre::eyre!("Could not find the source of `{}`", meta.contract_name))?;
+        let clone_meta = CloneMetadata {
+            target_contract: format!("{}:{}", path.display(), meta.contract_name),
+            path,
+            address,
+            chain_id: chain.id(),
+            compiler_version: meta.compiler_version.clone(),
+            constructor_arguments: meta.constructor_arguments.to_string(),
+            implementation: meta.implementation.filter(|_| meta.proxy == 1),
+        };
+        fs::write_json_file(&root.join(CLONE_META_FILE), &clone_meta)?;
+
+        if !opts.no_git && !opts.no_commit {
+            let git = Git::new(&root).quiet(opts.quiet);
+            git.add(Some("--all"))?;
+            git.commit(&format!("chore: forge clone {address}"))?;
+        }
+
+        p_println!(!opts.quiet => "    {} {} from {chain} into {}", Paint::green("Cloned"), clone_meta.target_contract, root.display());
+        Ok(())
+    }
+}
+
+/// Fetches the verified sources of the contract at `address`.
+async fn fetch_metadata(client: &Client, address: Address, chain: Chain) -> Result<Metadata> {
+    let mut source = match client.contract_source_code(address).await {
+        Ok(source) => source,
+        Err(EtherscanError::InvalidApiKey) => {
+            eyre::bail!("Invalid Etherscan API key. Did you set it correctly? You may be using an API key for another Etherscan API chain (e.g. Etherscan API key for Polygonscan).")
+        }
+        Err(EtherscanError::ContractCodeNotVerified(address)) => {
+            eyre::bail!("Contract source code at {:?} on {} not verified. Maybe you have selected the wrong chain?", address, chain)
+        }
+        Err(err) => eyre::bail!(err),
+    };
+    eyre::ensure!(!source.items.is_empty(), "Etherscan returned no sources for {address}");
+    Ok(source.items.remove(0))
+}
+
+/// Returns the path in `src` of a verified source, rejecting paths which are absolute or
+/// contain `..`, so that the sources can't be written outside of the project.
+fn source_path(path: &Path, contract_name: &str) -> Result<PathBuf> {
+    let path = path.strip_prefix(contract_name).unwrap_or(path);
+    eyre::ensure!(
+        path.components().all(|component| matches!(component, Component::Normal(_))),
+        "Invalid source path in the verified contract: {}",
+        path.display()
+    );
+    Ok(Path::new("src").join(path))
+}
+
+/// Returns the remappings needed to import the sources of the top-level directories under `src`
+/// by their verified paths, e.g. `@openzeppelin/=src/@openzeppelin/`, which aren't already
+/// covered by `existing`.
+fn source_dir_remappings<'a>(
+    sources: impl IntoIterator<Item = &'a PathBuf>,
+    existing: &[Remapping],
+) -> Vec<Remapping> {
+    let dirs = sources
+        .into_iter()
+        .filter_map(|path| {
+            let mut components = path.strip_prefix("src").ok()?.components();
+            let dir = components.next()?;
+            // skip files at the root of `src`
+            components.next()?;
+            Some(dir.as_os_str().to_string_lossy().into_owned())
+        })
+        .collect::<BTreeSet<_>>();
+    dirs.into_iter()
+        .filter(|dir| !existing.iter().any(|remapping| remapping.name.trim_end_matches('/') == dir))
+        .map(|dir| Remapping {
+            context: None,
+            name: format!("{dir}/"),
+            path: format!("src/{dir}/"),
+        })
+        .collect()
+}
+
+/// Returns the path of the source declaring the contract `name`.
+fn find_contract_source(sources: &[(PathBuf, String)], name: &str) -> Option<PathBuf> {
+    let declaration = regex::Regex::new(&format!(r"\b(contract|library|interface)\s+{name}\b"))
+        .expect("contract names are valid identifiers");
+    sources
+        .iter()
+        .find(|(path, contents)| {
+            path.file_stem().map_or(false, |stem| stem == name) && declaration.is_match(contents)
+        })
+        .or_else(|| sources.iter().find(|(_, contents)| declaration.is_match(contents)))
+        .map(|(path, _)| path.clone())
+}
+
+/// Inserts the values into the selected profile of the project's `foundry.toml`.
+fn update_profile(root: &Path, values: toml::map::Map<String, toml::Value>) -> Result<()> {
+    let path = root.join(Config::FILE_NAME);
+    let mut doc: toml::Table = toml::from_str(&fs::read_to_string(&path)?)
+        .wrap_err_with(|| format!("Failed to parse {}", path.display()))?;
+    let profile = doc
+        .entry(Config::PROFILE_SECTION)
+        .or_insert_with(|| toml::Value::Table(Default::default()))
+        .as_table_mut()
+        .and_then(|profiles| {
+            profiles
+                .entry(Config::selected_profile().as_str().as_str())
+                .or_insert_with(|| toml::Value::Table(Default::default()))
+                .as_table_mut()
+        })
+        .ok_or_else(|| eyre::eyre!("Invalid profile section in {}", path.display()))?;
+    profile.extend(values);
+    fs::write(path, toml::to_string_pretty(&doc)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_source_dir_remappings() {
+        let sources = [
+            PathBuf::from("src/Token.sol"),
+            PathBuf::from("src/@openzeppelin/contracts/token/ERC20/ERC20.sol"),
+            PathBuf::from("src/contracts/Vault.sol"),
+            PathBuf::from("src/lib/solmate/src/auth/Owned.sol"),
+        ];
+        let existing = ["lib/=src/lib/".parse::<Remapping>().unwrap()];
+        let remappings = source_dir_remappings(&sources, &existing)
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        assert_eq!(remappings, ["@openzeppelin/=src/@openzeppelin/", "contracts/=src/contracts/"]);
+    }
+
+    #[test]
+    fn rejects_source_paths_outside_of_src() {
+        assert_eq!(
+            source_path(Path::new("Vault/contracts/Vault.sol"), "Vault").unwrap(),
+            PathBuf::from("src/contracts/Vault.sol")
+        );
+        assert!(source_path(Path::new("../.bashrc"), "Vault").is_err());
+        assert!(source_path(Path::new("contracts/../../foundry.toml"), "Vault").is_err());
+        assert!(source_path(Path::new("/etc/passwd"), "Vault").is_err());
+    }
+
+    #[test]
+    fn finds_contract_source() {
+        let sources = [
+            (PathBuf::from("src/IVault.sol"), "interface IVault {}".to_string()),
+            (PathBuf::from("src/Vaults.sol"), "contract Vault is IVault {}".to_string()),
+        ];
+        assert_eq!(find_contract_source(&sources, "Vault"), Some(PathBuf::from("src/Vaults.sol")));
+        assert_eq!(find_contract_source(&sources, "Token"), None);
+    }
+}
@@ -4,8 +4,9 @@ use clap::{Parser, ValueEnum, ValueHint};
 use eyre::{Context, Result};
 use forge::{
     coverage::{
-        analysis::SourceAnalyzer, anchors::find_anchors, BytecodeReporter, ContractId,
-        CoverageReport, CoverageReporter, DebugReporter, ItemAnchor, LcovReporter, SummaryReporter,
+        analysis::SourceAnalyzer, anchors::find_anchors, BytecodeReporter, CoberturaReporter,
+        ContractId, CoverageReport, CoverageReporter, DebugReporter, HtmlReporter, ItemAnchor,
+        LcovReporter, SummaryReporter,
     },
     inspectors::CheatsConfig,
     opts::EvmOpts,
@@ -19,7 +20,7 @@ use foundry_cli::{
     p_println,
     utils::{LoadConfig, STATIC_FUZZ_SEED},
 };
-use foundry_common::{compile::ProjectCompiler, evm::EvmArgs, fs};
+use foundry_common::{compile::ProjectCompiler, evm::EvmArgs, fs, glob::GlobMatcher};
 use foundry_compilers::{
     artifacts::{contract::CompactContractBytecode, Ast, CompactBytecode, CompactDeployedBytecode},
     sourcemap::SourceMap,
@@ -52,7 +53,7 @@ pub struct CoverageArgs {
     #[clap(long)]
     ir_minimum: bool,
 
-    /// The path to output the report.
+    /// The path to output the LCOV report.
     ///
     /// If not specified, the report will be stored in the root of the project.
     #[clap(
@@ -156,6 +157,12 @@ impl CoverageArgs {
         // Extract artifacts
         let (artifacts, sources) = output.into_artifacts_with_sources();
         let mut report = CoverageReport::default();
+        let exclude = config
+            .coverage
+            .exclude
+            .iter()
+            .map(|glob| glob.parse::<GlobMatcher>())
+            .collect::<Result<Vec<_>, _>>()?;
 
         // Collect ASTs and sources
         let mut versioned_asts: HashMap<Version, HashMap<usize, Ast>> = HashMap::new();
@@ -168,6 +175,11 @@ impl CoverageArgs {
                 continue
             }
 
+            // Filter out sources excluded in the config
+            if exclude.iter().any(|glob| glob.is_match(std::path::Path::new(&path))) {
+                continue
+            }
+
             if let Some(ast) = source_file.ast.take() {
                 versioned_asts
                     .entry(version.clone())
@@ -306,7 +318,7 @@ impl CoverageArgs {
             .with_cheats_config(CheatsConfig::new(&config, evm_opts.clone()))
             .with_test_options(TestOptions {
                 fuzz: config.fuzz,
-                invariant: config.invariant,
+                invariant: config.invariant.clone(),
                 ..Default::default()
             })
             .set_coverage(true)
@@ -362,13 +374,18 @@ impl CoverageArgs {
             match report_kind {
                 CoverageReportKind::Summary => SummaryReporter::default().report(&report),
                 CoverageReportKind::Lcov => {
-                    if let Some(report_file) = self.report_file {
-                        return LcovReporter::new(&mut fs::create_file(root.join(report_file))?)
-                            .report(&report)
-                    } else {
-                        return LcovReporter::new(&mut fs::create_file(root.join("lcov.info"))?)
-                            .report(&report)
-                    }
+                    let report_file = self.report_file.as_deref().unwrap_or("lcov.info".as_ref());
+                    LcovReporter::new(&mut fs::create_file(root.join(report_file))?).report(&report)
+                }
+                CoverageReportKind::Cobertura => CoberturaReporter::new(
+                    &mut fs::create_file(root.join("cobertura.xml"))?,
+                    root.clone(),
+                )
+                .report(&report),
+                CoverageReportKind::Html => {
+                    let destdir = root.join("coverage");
+                    fs::create_dir_all(&destdir)?;
+                    HtmlReporter::new(root.clone(), destdir).report(&report)
                 }
                 CoverageReportKind::Bytecode => {
                     let destdir = root.join("bytecode-coverage");
@@ -388,19 +405,26 @@ impl CoverageArgs {
     }
 }
 
-// TODO: HTML
 #[derive(Clone, Debug, ValueEnum)]
 pub enum CoverageReportKind {
+    /// A summary table, printed to stdout.
     Summary,
+    /// LCOV, written to `lcov.info` unless `--report-file` is set.
     Lcov,
+    /// Cobertura XML, written to `cobertura.xml`.
+    Cobertura,
+    /// A self-contained HTML report with annotated sources, written to the `coverage` directory.
+    Html,
+    /// Verbose output for debugging the coverage analysis.
     Debug,
+    /// Bytecode annotated with hit counts, written to the `bytecode-coverage` directory.
     Bytecode,
 }
 
 /// Helper function that will link references in unlinked bytecode to the 0 address.
 ///
 /// This is needed in order to analyze the bytecode for contracts that use libraries.
-fn dummy_link_bytecode(mut obj: CompactBytecode) -> Option<Bytes> {
+pub fn dummy_link_bytecode(mut obj: CompactBytecode) -> Option<Bytes> {
     let link_references = obj.link_references.clone();
     for (file, libraries) in link_references {
         for library in libraries.keys() {
@@ -415,6 +439,6 @@ fn dummy_link_bytecode(mut obj: CompactBytecode) -> Option<Bytes> {
 /// Helper function that will link references in unlinked bytecode to the 0 address.
 ///
 /// This is needed in order to analyze the bytecode for contracts that use libraries.
-fn dummy_link_deployed_bytecode(obj: CompactDeployedBytecode) -> Option<Bytes> {
+pub fn dummy_link_deployed_bytecode(obj: CompactDeployedBytecode) -> Option<Bytes> {
     obj.bytecode.and_then(dummy_link_bytecode)
 }
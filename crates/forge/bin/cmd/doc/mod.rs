@@ -1,11 +1,14 @@
 use clap::{Parser, ValueHint};
 use eyre::Result;
 use forge_doc::{
-    ContractInheritance, Deployments, DocBuilder, GitSource, InferInlineHyperlinks, Inheritdoc,
+    ContractArtifact, ContractArtifacts, ContractInheritance, Deployments, DocBuilder, GitSource,
+    InferInlineHyperlinks, Inheritdoc, Selector, StorageSlot,
 };
 use foundry_cli::opts::GH_REPO_PREFIX_REGEX;
-use foundry_config::{find_project_root_path, load_config_with_root};
-use std::{path::PathBuf, process::Command};
+use foundry_common::compile::ProjectCompiler;
+use foundry_compilers::artifacts::output_selection::ContractOutputSelection;
+use foundry_config::{find_project_root_path, load_config_with_root, Config};
+use std::{collections::HashMap, path::PathBuf, process::Command};
 
 mod server;
 use server::Server;
@@ -58,6 +61,17 @@ pub struct DocArgs {
     /// Whether to create docs for external libraries.
     #[clap(long, short)]
     include_libraries: bool,
+
+    /// The version of the documented code.
+    ///
+    /// The docs are written to `v<VERSION>` in the output directory.
+    #[clap(long, value_name = "VERSION")]
+    doc_version: Option<String>,
+
+    /// Compile the project to add the selectors and the storage layout of the contracts to their
+    /// docs.
+    #[clap(long)]
+    artifacts: bool,
 }
 
 impl DocArgs {
@@ -69,6 +83,17 @@ impl DocArgs {
         if let Some(out) = self.out {
             doc_config.out = out;
         }
+        if let Some(version) = self.doc_version {
+            doc_config.version = Some(version);
+        }
+        if let Some(version) = &doc_config.version {
+            let version = version.trim_start_matches('v');
+            doc_config.out = doc_config.out.join(format!("v{version}"));
+            if !doc_config.title.is_empty() {
+                doc_config.title = format!("{} v{version}", doc_config.title);
+            }
+        }
+        doc_config.artifacts |= self.artifacts;
         if doc_config.repository.is_none() {
             // Attempt to read repo from git
             if let Ok(output) = Command::new("git").args(["remote", "get-url", "origin"]).output() {
@@ -112,6 +137,11 @@ impl DocArgs {
             builder = builder.with_preprocessor(Deployments { root, deployments });
         }
 
+        if doc_config.artifacts {
+            builder = builder
+                .with_preprocessor(ContractArtifacts { artifacts: contract_artifacts(&config)? });
+        }
+
         builder.build()?;
 
         if self.serve {
@@ -125,3 +155,55 @@ impl DocArgs {
         Ok(())
     }
 }
+
+/// Compiles the project and collects the selectors and the storage layouts of its contracts, by
+/// the path of their source and their name.
+fn contract_artifacts(config: &Config) -> Result<HashMap<(PathBuf, String), ContractArtifact>> {
+    let mut config = config.clone();
+    if !config.extra_output.contains(&ContractOutputSelection::StorageLayout) {
+        config.extra_output.push(ContractOutputSelection::StorageLayout);
+    }
+    let output = ProjectCompiler::new().quiet(true).compile(&config.project()?)?;
+
+    let mut artifacts = HashMap::new();
+    for (id, artifact) in output.artifact_ids() {
+        let mut selectors = Vec::new();
+        if let Some(abi) = &artifact.abi {
+            selectors.extend(abi.functions().map(|function| Selector {
+                kind: "function",
+                signature: function.signature(),
+                selector: function.selector().to_string(),
+            }));
+            selectors.extend(abi.errors().map(|error| Selector {
+                kind: "error",
+                signature: error.signature(),
+                selector: error.selector().to_string(),
+            }));
+            selectors.extend(abi.events().map(|event| Selector {
+                kind: "event",
+                signature: event.signature(),
+                selector: event.selector().to_string(),
+            }));
+        }
+
+        let storage = artifact
+            .storage_layout
+            .iter()
+            .flat_map(|layout| {
+                layout.storage.iter().map(|slot| {
+                    let ty = layout.types.get(&slot.storage_type);
+                    StorageSlot {
+                        name: slot.label.clone(),
+                        ty: ty.map_or_else(|| slot.storage_type.clone(), |ty| ty.label.clone()),
+                        slot: slot.slot.clone(),
+                        offset: slot.offset,
+                        bytes: ty.map_or_else(|| "?".to_string(), |ty| ty.number_of_bytes.clone()),
+                    }
+                })
+            })
+            .collect();
+
+        artifacts.insert((id.source, id.name), ContractArtifact { selectors, storage });
+    }
+    Ok(artifacts)
+}
@@ -38,6 +38,15 @@ pub struct FmtArgs {
     /// In 'check' and stdin modes, outputs raw formatted code instead of the diff.
     #[clap(long, short)]
     raw: bool,
+
+    /// Remove unused named and aliased imports.
+    ///
+    /// Files without contracts, interfaces or libraries are skipped, since their imports may be
+    /// there to be re-exported.
+    ///
+    /// Overrides the `remove_unused_imports` formatter setting.
+    #[clap(long)]
+    fix_imports: bool,
 }
 
 impl_figment_convert_basic!(FmtArgs);
@@ -47,6 +56,10 @@ impl_figment_convert_basic!(FmtArgs);
 impl FmtArgs {
     pub fn run(self) -> Result<()> {
         let config = self.try_load_config_emit_warnings()?;
+        let mut fmt_config = config.fmt.clone();
+        if self.fix_imports {
+            fmt_config.remove_unused_imports = true;
+        }
 
         // Expand ignore globs and canonicalize from the get go
         let ignored = expand_globs(&config.__root.0, config.fmt.ignore.iter())?
@@ -115,7 +128,7 @@ impl FmtArgs {
             }
 
             let mut output = String::new();
-            format_to(&mut output, parsed, config.fmt.clone()).unwrap();
+            format_to(&mut output, parsed, fmt_config.clone()).unwrap();
 
             solang_parser::parse(&output, 0).map_err(|diags| {
                 eyre::eyre!(
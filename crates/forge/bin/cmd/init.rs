@@ -13,32 +13,32 @@ use yansi::Paint;
 pub struct InitArgs {
     /// The root directory of the new project.
     #[clap(value_hint = ValueHint::DirPath, default_value = ".", value_name = "PATH")]
-    root: PathBuf,
+    pub root: PathBuf,
 
     /// The template to start from.
     #[clap(long, short)]
-    template: Option<String>,
+    pub template: Option<String>,
 
     /// Branch argument that can only be used with template option.
     /// If not specified, the default branch is used.
     #[clap(long, short, requires = "template")]
-    branch: Option<String>,
+    pub branch: Option<String>,
 
     /// Do not install dependencies from the network.
     #[clap(long, conflicts_with = "template", visible_alias = "no-deps")]
-    offline: bool,
+    pub offline: bool,
 
     /// Create the project even if the specified root directory is not empty.
     #[clap(long, conflicts_with = "template")]
-    force: bool,
+    pub force: bool,
 
     /// Create a .vscode/settings.json file with Solidity settings, and generate a remappings.txt
     /// file.
     #[clap(long, conflicts_with = "template")]
-    vscode: bool,
+    pub vscode: bool,
 
     #[clap(flatten)]
-    opts: DependencyInstallOpts,
+    pub opts: DependencyInstallOpts,
 }
 
 impl InitArgs {
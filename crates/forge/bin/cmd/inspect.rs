@@ -1,20 +1,23 @@
+use super::verify::standard_json_input;
 use clap::Parser;
 use comfy_table::{presets::ASCII_MARKDOWN, Table};
 use eyre::Result;
+use forge::unreachable::{Source, UnreachableCode};
 use foundry_cli::opts::{CompilerArgs, CoreBuildArgs};
-use foundry_common::compile::ProjectCompiler;
+use foundry_common::{compile::ProjectCompiler, fs};
 use foundry_compilers::{
     artifacts::{
         output_selection::{
             BytecodeOutputSelection, ContractOutputSelection, DeployedBytecodeOutputSelection,
             EvmOutputSelection, EwasmOutputSelection,
         },
-        StorageLayout,
+        ConfigurableContractArtifact, StorageLayout,
     },
     info::ContractInfo,
     utils::canonicalize,
+    Artifact, ArtifactId, Project, ProjectCompileOutput,
 };
-use std::fmt;
+use std::{collections::BTreeMap, fmt, path::Path};
 
 /// CLI arguments for `forge inspect`.
 #[derive(Clone, Debug, Parser)]
@@ -77,6 +80,20 @@ impl InspectArgs {
 
         // Match on ContractArtifactFields and pretty-print
         match field {
+            ContractArtifactField::StandardJson => {
+                let id = find_artifact_id(&output, &contract)?;
+                let input = standard_json_input(&project, &id.source, &id.version)?;
+                print_json(&input)?;
+            }
+            ContractArtifactField::Unreachable => {
+                let id = find_artifact_id(&output, &contract)?;
+                let unreachable = find_unreachable(&project, &output, &id, artifact)?;
+                if pretty {
+                    print_unreachable(&unreachable);
+                } else {
+                    print_json(&unreachable)?;
+                }
+            }
             ContractArtifactField::Abi => {
                 let abi = artifact
                     .abi
@@ -164,6 +181,84 @@ impl InspectArgs {
     }
 }
 
+/// Returns the ID of the artifact of the contract.
+fn find_artifact_id(output: &ProjectCompileOutput, contract: &ContractInfo) -> Result<ArtifactId> {
+    output
+        .artifact_ids()
+        .map(|(id, _)| id)
+        .find(|id| {
+            id.name == contract.name &&
+                contract.path.as_ref().map_or(true, |path| id.source == Path::new(path))
+        })
+        .ok_or_else(|| eyre::eyre!("Could not find the source of `{contract}`"))
+}
+
+/// Finds the code of the contract which isn't part of its bytecode, using the ASTs of the sources
+/// it was compiled with.
+fn find_unreachable(
+    project: &Project,
+    output: &ProjectCompileOutput,
+    id: &ArtifactId,
+    artifact: &ConfigurableContractArtifact,
+) -> Result<UnreachableCode> {
+    let (_, versioned_sources) = output.clone().into_artifacts_with_sources();
+    let mut sources = BTreeMap::new();
+    for (path, source_file, version) in versioned_sources.into_sources_with_version() {
+        let Some(ast) = source_file.ast.filter(|_| version == id.version) else { continue };
+        let file = project.root().join(&path);
+        let code = fs::read_to_string(&file)?;
+        let path = file.strip_prefix(project.root()).unwrap_or(&file).display().to_string();
+        sources.insert(source_file.id as usize, Source { path, ast, code });
+    }
+
+    let source_maps = [artifact.get_source_map(), artifact.get_source_map_deployed()]
+        .into_iter()
+        .flatten()
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| eyre::eyre!("Failed to parse the source maps: {err}"))?;
+    let bytecodes = [artifact.get_bytecode_bytes(), artifact.get_deployed_bytecode_bytes()]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    let contract_path =
+        id.source.strip_prefix(project.root()).unwrap_or(&id.source).display().to_string();
+
+    UnreachableCode::find(
+        &id.name,
+        &contract_path,
+        &sources,
+        &source_maps.iter().map(Vec::as_slice).collect::<Vec<_>>(),
+        &bytecodes.iter().map(|code| -> &[u8] { code }).collect::<Vec<_>>(),
+        artifact.abi.as_ref().ok_or_else(|| eyre::eyre!("Failed to fetch the ABI"))?,
+    )
+}
+
+fn print_unreachable(unreachable: &UnreachableCode) {
+    if unreachable.is_empty() {
+        println!("No unreachable code found");
+        return
+    }
+    let sections = [
+        ("Unreachable functions", &unreachable.functions),
+        ("Unreachable statements", &unreachable.statements),
+    ];
+    for (title, items) in sections {
+        if items.is_empty() {
+            continue
+        }
+        println!("{title}:");
+        for item in items {
+            println!("  {}:{} {}.{}", item.path, item.line, item.contract, item.name);
+        }
+    }
+    if !unreachable.errors.is_empty() {
+        println!("Unused errors:");
+        for error in &unreachable.errors {
+            println!("  {error}");
+        }
+    }
+}
+
 pub fn print_storage_layout(storage_layout: Option<&StorageLayout>, pretty: bool) -> Result<()> {
     let Some(storage_layout) = storage_layout else {
         eyre::bail!("Could not get storage layout");
@@ -212,6 +307,8 @@ pub enum ContractArtifactField {
     Ewasm,
     Errors,
     Events,
+    StandardJson,
+    Unreachable,
 }
 
 macro_rules! impl_value_enum {
@@ -298,6 +395,9 @@ impl_value_enum! {
         Ewasm             => "ewasm" | "e-wasm",
         Errors            => "errors" | "er",
         Events            => "events" | "ev",
+        StandardJson      => "standardJson" | "standard-json" | "standard_json"
+                             | "standardjson" | "standard-json-input",
+        Unreachable       => "unreachable" | "dead-code" | "deadcode",
     }
 }
 
@@ -322,6 +422,10 @@ impl From<ContractArtifactField> for ContractOutputSelection {
             Caf::Ewasm => Self::Ewasm(EwasmOutputSelection::All),
             Caf::Errors => Self::Abi,
             Caf::Events => Self::Abi,
+            Caf::StandardJson => Self::Abi,
+            Caf::Unreachable => Self::Evm(EvmOutputSelection::DeployedByteCode(
+                DeployedBytecodeOutputSelection::All,
+            )),
         }
     }
 }
@@ -333,9 +437,9 @@ impl PartialEq<ContractOutputSelection> for ContractArtifactField {
         matches!(
             (self, other),
             (Self::Abi | Self::Events, Cos::Abi) |
-                (Self::Errors, Cos::Abi) |
+                (Self::Errors | Self::StandardJson, Cos::Abi) |
                 (Self::Bytecode, Cos::Evm(Eos::ByteCode(_))) |
-                (Self::DeployedBytecode, Cos::Evm(Eos::DeployedByteCode(_))) |
+                (Self::DeployedBytecode | Self::Unreachable, Cos::Evm(Eos::DeployedByteCode(_))) |
                 (Self::Assembly | Self::AssemblyOptimized, Cos::Evm(Eos::Assembly)) |
                 (Self::MethodIdentifiers, Cos::Evm(Eos::MethodIdentifiers)) |
                 (Self::GasEstimates, Cos::Evm(Eos::GasEstimates)) |
@@ -359,7 +463,7 @@ impl fmt::Display for ContractArtifactField {
 impl ContractArtifactField {
     /// Returns true if this field is generated by default.
     pub const fn is_default(&self) -> bool {
-        matches!(self, Self::Bytecode | Self::DeployedBytecode)
+        matches!(self, Self::Bytecode | Self::DeployedBytecode | Self::Unreachable)
     }
 }
 
@@ -0,0 +1,463 @@
+use clap::ValueEnum;
+use eyre::Result;
+use serde::Serialize;
+use solang_parser::pt::{
+    CodeLocation, ContractDefinition, ContractPart, Expression, FunctionAttribute,
+    FunctionDefinition, FunctionTy, Identifier, Loc, Mutability, SourceUnitPart, Statement,
+    StorageLocation, Visibility,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+};
+
+/// The marker of the comments which disable lints on a line.
+const SUPPRESSION_MARKER: &str = "forge-lint:";
+
+/// The lints the sources are checked for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Lint {
+    /// A local storage pointer which is declared without being initialized, and thus points to
+    /// the first storage slot.
+    UninitializedStoragePointer,
+    /// The success value returned by a low-level call or `send` is discarded.
+    UncheckedCall,
+    /// A parameter or local variable has the same name as a state variable.
+    ShadowedStateVariable,
+    /// A public or external function changes a state variable without emitting an event.
+    MissingEvent,
+}
+
+impl Lint {
+    /// Returns `true` if the lint finds security issues rather than style issues.
+    pub fn is_security(self) -> bool {
+        matches!(self, Self::UninitializedStoragePointer | Self::UncheckedCall)
+    }
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UninitializedStoragePointer => f.write_str("uninitialized-storage-pointer"),
+            Self::UncheckedCall => f.write_str("unchecked-call"),
+            Self::ShadowedStateVariable => f.write_str("shadowed-state-variable"),
+            Self::MissingEvent => f.write_str("missing-event"),
+        }
+    }
+}
+
+/// A violation of a lint.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub lint: Lint,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+/// Checks the source for the given lints, and returns the findings in the order they appear in
+/// the source.
+///
+/// Findings on lines disabled with `// forge-lint: disable-line` or
+/// `// forge-lint: disable-next-line` comments are omitted. The comments can be restricted to
+/// some lints, e.g. `// forge-lint: disable-next-line(unchecked-call, missing-event)`.
+pub fn find_findings(source: &str, lints: &[Lint]) -> Result<Vec<Finding>> {
+    let (source_unit, _) = solang_parser::parse(source, 0)
+        .map_err(|diagnostics| eyre::eyre!("failed to parse source: {diagnostics:?}"))?;
+
+    let contracts = source_unit
+        .0
+        .iter()
+        .filter_map(|part| match part {
+            SourceUnitPart::ContractDefinition(contract) => {
+                Some((contract.name.as_ref()?.name.as_str(), &**contract))
+            }
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut visitor = LintVisitor { source, ..Default::default() };
+    for part in &source_unit.0 {
+        match part {
+            SourceUnitPart::ContractDefinition(contract) => {
+                let mut state_variables = HashSet::new();
+                collect_state_variables(
+                    contract,
+                    &contracts,
+                    &mut HashSet::new(),
+                    &mut state_variables,
+                );
+                visitor.state_variables = state_variables;
+                for part in &contract.parts {
+                    if let ContractPart::FunctionDefinition(function) = part {
+                        visitor.visit_function(function);
+                    }
+                }
+            }
+            SourceUnitPart::FunctionDefinition(function) => {
+                visitor.state_variables.clear();
+                visitor.visit_function(function);
+            }
+            _ => {}
+        }
+    }
+
+    let suppressions = suppressions(source);
+    let mut findings = visitor.findings;
+    findings.retain(|finding| {
+        let suppressed = suppressions.get(&finding.line).map_or(false, |lints| {
+            lints.as_ref().map_or(true, |lints| lints.contains(&finding.lint.to_string()))
+        });
+        lints.contains(&finding.lint) && !suppressed
+    });
+    findings.sort_by_key(|finding| (finding.line, finding.column));
+    Ok(findings)
+}
+
+/// Collects the names of the state variables of the contract and of its base contracts which are
+/// declared in the same source.
+fn collect_state_variables<'a>(
+    contract: &'a ContractDefinition,
+    contracts: &HashMap<&str, &'a ContractDefinition>,
+    visited: &mut HashSet<&'a str>,
+    state_variables: &mut HashSet<String>,
+) {
+    // guard against cyclic inheritance, which doesn't compile
+    if let Some(name) = &contract.name {
+        if !visited.insert(name.name.as_str()) {
+            return
+        }
+    }
+    for part in &contract.parts {
+        if let ContractPart::VariableDefinition(variable) = part {
+            if let Some(name) = &variable.name {
+                state_variables.insert(name.name.clone());
+            }
+        }
+    }
+    for base in &contract.base {
+        let Some(name) = base.name.identifiers.last() else { continue };
+        if let Some(&base) = contracts.get(name.name.as_str()) {
+            collect_state_variables(base, contracts, visited, state_variables);
+        }
+    }
+}
+
+/// Returns the lints disabled by the suppression comments, by line. `None` disables all lints.
+fn suppressions(source: &str) -> HashMap<usize, Option<Vec<String>>> {
+    let mut suppressions = HashMap::new();
+    for (i, line) in source.lines().enumerate() {
+        let Some(idx) = line.find(SUPPRESSION_MARKER) else { continue };
+        if !line[..idx].contains("//") {
+            continue
+        }
+        let directive = line[idx + SUPPRESSION_MARKER.len()..].trim();
+        let (line, rest) = if let Some(rest) = directive.strip_prefix("disable-next-line") {
+            (i + 2, rest)
+        } else if let Some(rest) = directive.strip_prefix("disable-line") {
+            (i + 1, rest)
+        } else {
+            continue
+        };
+        let lints = rest.trim().strip_prefix('(').and_then(|rest| rest.split_once(')')).map(
+            |(lints, _)| lints.split(',').map(|lint| lint.trim().to_string()).collect::<Vec<_>>(),
+        );
+        suppressions.insert(line, lints);
+    }
+    suppressions
+}
+
+/// Walks the statements of functions and collects the findings.
+#[derive(Default)]
+struct LintVisitor<'a> {
+    source: &'a str,
+    /// The state variables of the contract being visited
+    state_variables: HashSet<String>,
+    /// The parameters and local variables of the function being visited
+    locals: HashSet<String>,
+    /// The first state variable the function being visited changes
+    state_change: Option<String>,
+    /// Whether the function being visited emits an event
+    emits: bool,
+    findings: Vec<Finding>,
+}
+
+impl<'a> LintVisitor<'a> {
+    fn visit_function(&mut self, function: &FunctionDefinition) {
+        self.locals.clear();
+        self.state_change = None;
+        self.emits = false;
+
+        for (_, param) in function.params.iter().chain(&function.returns) {
+            if let Some(name) = param.as_ref().and_then(|param| param.name.as_ref()) {
+                self.declare(name);
+            }
+        }
+        if let Some(body) = &function.body {
+            self.visit_statement(body);
+        }
+
+        // events emitted by the internal functions the function calls are not considered
+        let Some(variable) = self.state_change.take() else { return };
+        let changes_state = function.attributes.iter().all(|attr| {
+            !matches!(
+                attr,
+                FunctionAttribute::Mutability(Mutability::View(_) | Mutability::Pure(_))
+            )
+        });
+        let is_public = function.attributes.iter().any(|attr| {
+            matches!(
+                attr,
+                FunctionAttribute::Visibility(Visibility::External(_) | Visibility::Public(_))
+            )
+        });
+        if function.ty == FunctionTy::Function && changes_state && is_public && !self.emits {
+            let name = function.name.as_ref().map_or("", |name| name.name.as_str());
+            self.push(
+                Lint::MissingEvent,
+                function.name.as_ref().map_or(function.loc, |name| name.loc),
+                format!(
+                    "`{name}` changes the state variable `{variable}` without emitting an event"
+                ),
+            );
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block { statements, .. } => {
+                statements.iter().for_each(|statement| self.visit_statement(statement))
+            }
+            Statement::If(_, _, then, otherwise) => {
+                self.visit_statement(then);
+                if let Some(otherwise) = otherwise {
+                    self.visit_statement(otherwise);
+                }
+            }
+            Statement::While(_, _, body) | Statement::DoWhile(_, body, _) => {
+                self.visit_statement(body)
+            }
+            Statement::For(_, init, _, next, body) => {
+                if let Some(init) = init {
+                    self.visit_statement(init);
+                }
+                if let Some(next) = next {
+                    self.visit_expression_statement(next);
+                }
+                if let Some(body) = body {
+                    self.visit_statement(body);
+                }
+            }
+            Statement::VariableDefinition(loc, declaration, initializer) => {
+                if matches!(declaration.storage, Some(StorageLocation::Storage(_))) &&
+                    initializer.is_none()
+                {
+                    let name = declaration.name.as_ref().map_or("", |name| name.name.as_str());
+                    self.push(
+                        Lint::UninitializedStoragePointer,
+                        *loc,
+                        format!("The storage pointer `{name}` is not initialized"),
+                    );
+                }
+                if let Some(name) = &declaration.name {
+                    self.declare(name);
+                }
+            }
+            Statement::Expression(_, expression) => self.visit_expression_statement(expression),
+            Statement::Emit(..) => self.emits = true,
+            _ => {}
+        }
+    }
+
+    fn visit_expression_statement(&mut self, expression: &Expression) {
+        if let Some(function) = low_level_call(expression) {
+            self.push(
+                Lint::UncheckedCall,
+                expression.loc(),
+                format!("The success value returned by `{function}` is not checked"),
+            );
+        }
+
+        if self.state_change.is_none() {
+            if let Some(variable) = changed_variable(expression) {
+                if self.state_variables.contains(&variable.name) &&
+                    !self.locals.contains(&variable.name)
+                {
+                    self.state_change = Some(variable.name.clone());
+                }
+            }
+        }
+    }
+
+    /// Declares a parameter or local variable of the function being visited.
+    fn declare(&mut self, name: &Identifier) {
+        if self.state_variables.contains(&name.name) {
+            self.push(
+                Lint::ShadowedStateVariable,
+                name.loc,
+                format!("`{}` shadows a state variable", name.name),
+            );
+        }
+        self.locals.insert(name.name.clone());
+    }
+
+    fn push(&mut self, lint: Lint, loc: Loc, message: String) {
+        let before = &self.source[..loc.start()];
+        let line = before.matches('\n').count() + 1;
+        let column = loc.start() - before.rfind('\n').map_or(0, |idx| idx + 1) + 1;
+        self.findings.push(Finding { lint, line, column, message });
+    }
+}
+
+/// Returns the name of the low-level function the expression calls, if any.
+fn low_level_call(expression: &Expression) -> Option<&str> {
+    match expression {
+        Expression::Parenthesis(_, inner) => low_level_call(inner),
+        Expression::FunctionCall(_, function, _) => {
+            let mut function = function.as_ref();
+            // `call{value: amount}(data)`
+            while let Expression::FunctionCallBlock(_, inner, _) = function {
+                function = inner;
+            }
+            match function {
+                Expression::MemberAccess(_, _, member)
+                    if matches!(
+                        member.name.as_str(),
+                        "call" | "delegatecall" | "staticcall" | "send"
+                    ) =>
+                {
+                    Some(member.name.as_str())
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Returns the variable the expression assigns to, increments, decrements or deletes, if any.
+///
+/// For assignments to members and elements, this is the variable they belong to.
+fn changed_variable(expression: &Expression) -> Option<&Identifier> {
+    match expression {
+        Expression::Parenthesis(_, inner) => changed_variable(inner),
+        Expression::Assign(_, target, _) |
+        Expression::AssignOr(_, target, _) |
+        Expression::AssignAnd(_, target, _) |
+        Expression::AssignXor(_, target, _) |
+        Expression::AssignShiftLeft(_, target, _) |
+        Expression::AssignShiftRight(_, target, _) |
+        Expression::AssignAdd(_, target, _) |
+        Expression::AssignSubtract(_, target, _) |
+        Expression::AssignMultiply(_, target, _) |
+        Expression::AssignDivide(_, target, _) |
+        Expression::AssignModulo(_, target, _) |
+        Expression::PreIncrement(_, target) |
+        Expression::PostIncrement(_, target) |
+        Expression::PreDecrement(_, target) |
+        Expression::PostDecrement(_, target) |
+        Expression::Delete(_, target) => root_variable(target),
+        _ => None,
+    }
+}
+
+/// Returns the variable the expression accesses a member or element of, or the variable itself.
+fn root_variable(expression: &Expression) -> Option<&Identifier> {
+    match expression {
+        Expression::Variable(variable) => Some(variable),
+        Expression::Parenthesis(_, inner) |
+        Expression::MemberAccess(_, inner, _) |
+        Expression::ArraySubscript(_, inner, _) => root_variable(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+contract Ownable {
+    address public owner;
+}
+
+contract Vault is Ownable {
+    struct Deposit {
+        uint256 amount;
+    }
+
+    uint256 public fee;
+    mapping(address => Deposit) deposits;
+
+    event FeeChanged(uint256 fee);
+
+    function setFee(uint256 newFee) external {
+        fee = newFee;
+        emit FeeChanged(newFee);
+    }
+
+    function setOwner(address owner) external {
+        Deposit storage deposit;
+        deposits[owner].amount = 0;
+    }
+
+    function withdraw(address payable to, uint256 amount) external {
+        to.call{value: amount}("");
+        (bool success,) = to.call{value: amount}("");
+        require(success);
+        to.send(amount); // forge-lint: disable-line(unchecked-call)
+    }
+
+    function fees() external view returns (uint256 fee) {
+        fee = 1;
+    }
+}
+"#;
+
+    fn lints(findings: &[Finding]) -> Vec<(Lint, usize)> {
+        findings.iter().map(|finding| (finding.lint, finding.line)).collect()
+    }
+
+    #[test]
+    fn finds_lints() {
+        let findings = find_findings(SOURCE, Lint::value_variants()).unwrap();
+        assert_eq!(
+            lints(&findings),
+            [
+                (Lint::MissingEvent, 21),
+                (Lint::ShadowedStateVariable, 21),
+                (Lint::UninitializedStoragePointer, 22),
+                (Lint::UncheckedCall, 27),
+                (Lint::ShadowedStateVariable, 33),
+            ]
+        );
+        assert_eq!(
+            findings[0].message,
+            "`setOwner` changes the state variable `deposits` without emitting an event"
+        );
+        assert_eq!(findings[3].column, 9);
+    }
+
+    #[test]
+    fn filters_lints() {
+        let findings = find_findings(SOURCE, &[Lint::UncheckedCall]).unwrap();
+        assert_eq!(lints(&findings), [(Lint::UncheckedCall, 27)]);
+    }
+
+    #[test]
+    fn parses_suppressions() {
+        let source = r"
+// forge-lint: disable-next-line
+uint256 a;
+uint256 b; // forge-lint: disable-line(unchecked-call, missing-event)
+";
+        let suppressions = suppressions(source);
+        assert_eq!(suppressions.get(&3), Some(&None));
+        assert_eq!(
+            suppressions.get(&4),
+            Some(&Some(vec!["unchecked-call".to_string(), "missing-event".to_string()]))
+        );
+        assert_eq!(suppressions.len(), 2);
+    }
+}
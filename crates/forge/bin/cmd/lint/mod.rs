@@ -0,0 +1,125 @@
+use clap::{Parser, ValueEnum, ValueHint};
+use eyre::{Context, Result};
+use foundry_cli::utils::{FoundryPathExt, LoadConfig};
+use foundry_common::{fs, glob::expand_globs};
+use foundry_config::{impl_figment_convert_basic, Config};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use yansi::Paint;
+
+mod linter;
+use linter::{find_findings, Finding, Lint};
+
+/// CLI arguments for `forge lint`.
+#[derive(Clone, Debug, Parser)]
+pub struct LintArgs {
+    /// The source files or directories to lint.
+    ///
+    /// By default, all sources in the `src` directory are linted.
+    #[clap(value_hint = ValueHint::FilePath, value_name = "PATH", num_args(1..))]
+    paths: Vec<PathBuf>,
+
+    /// The project's root path.
+    ///
+    /// By default root of the Git repository, if in one,
+    /// or the current working directory.
+    #[clap(long, value_hint = ValueHint::DirPath, value_name = "PATH")]
+    root: Option<PathBuf>,
+
+    /// Only check the given lints, instead of all lints which aren't excluded in the config.
+    ///
+    /// This flag can be used multiple times.
+    #[clap(long, value_enum, value_name = "LINT")]
+    only: Vec<Lint>,
+
+    /// Print the findings as JSON.
+    #[clap(long)]
+    json: bool,
+}
+
+impl_figment_convert_basic!(LintArgs);
+
+impl LintArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.try_load_config_emit_warnings()?;
+        let root = &config.__root.0;
+        let lints = self.lints(&config)?;
+
+        let ignored = expand_globs(root, config.lint.ignore.iter())?
+            .iter()
+            .flat_map(fs::canonicalize_path)
+            .collect::<Vec<_>>();
+
+        let mut results = Vec::new();
+        for path in self.sources(&config)? {
+            if fs::canonicalize_path(&path).map_or(false, |path| ignored.contains(&path)) {
+                continue
+            }
+            let path = path.strip_prefix(root).unwrap_or(&path).to_path_buf();
+            let source = fs::read_to_string(root.join(&path))?;
+            let findings = find_findings(&source, &lints)
+                .wrap_err_with(|| format!("failed to lint {}", path.display()))?;
+            results.extend(
+                findings.into_iter().map(|finding| PathFinding { path: path.clone(), finding }),
+            );
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&results)?);
+        } else {
+            for PathFinding { path, finding } in &results {
+                let kind = if finding.lint.is_security() {
+                    Paint::red("security")
+                } else {
+                    Paint::yellow("style")
+                };
+                println!("{kind}[{}]: {}", finding.lint, finding.message);
+                println!("  --> {}:{}:{}", path.display(), finding.line, finding.column);
+            }
+        }
+
+        if !results.is_empty() {
+            if !self.json {
+                println!("\n{} lint findings", results.len());
+            }
+            std::process::exit(1);
+        }
+        Ok(())
+    }
+
+    /// Returns the lints passed with `--only`, or all lints which aren't excluded in the config.
+    fn lints(&self, config: &Config) -> Result<Vec<Lint>> {
+        if !self.only.is_empty() {
+            return Ok(self.only.clone())
+        }
+        let excluded = config
+            .lint
+            .exclude_lints
+            .iter()
+            .map(|lint| {
+                Lint::from_str(lint, true)
+                    .map_err(|err| eyre::eyre!("Invalid lint `{lint}` in `exclude_lints`: {err}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Lint::value_variants().iter().copied().filter(|lint| !excluded.contains(lint)).collect())
+    }
+
+    /// Returns the paths of the source files to lint.
+    fn sources(&self, config: &Config) -> Result<Vec<PathBuf>> {
+        if self.paths.is_empty() {
+            let mut sources = fs::files_with_ext(&config.src, "sol");
+            sources.retain(|path| !path.is_sol_test());
+            return Ok(sources)
+        }
+        let cwd = std::env::current_dir()?;
+        Ok(self.paths.iter().flat_map(|path| fs::files_with_ext(cwd.join(path), "sol")).collect())
+    }
+}
+
+/// A finding in a source file.
+#[derive(Debug, Serialize)]
+struct PathFinding {
+    path: PathBuf,
+    #[serde(flatten)]
+    finding: Finding,
+}
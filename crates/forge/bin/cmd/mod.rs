@@ -39,9 +39,11 @@
 //! let config: Config = From::from(&args);
 //! ```
 
+pub mod attest;
 pub mod bind;
 pub mod build;
 pub mod cache;
+pub mod clone;
 pub mod config;
 pub mod coverage;
 pub mod create;
@@ -54,11 +56,14 @@ pub mod generate;
 pub mod init;
 pub mod inspect;
 pub mod install;
+pub mod lint;
+pub mod mutate;
 pub mod remappings;
 pub mod remove;
 pub mod retry;
 pub mod script;
 pub mod selectors;
+pub mod size;
 pub mod snapshot;
 pub mod test;
 pub mod tree;
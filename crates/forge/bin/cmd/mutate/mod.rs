@@ -0,0 +1,350 @@
+use super::{install, test::FilterArgs};
+use alloy_primitives::U256;
+use clap::{Parser, ValueHint};
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Attribute, Cell, CellAlignment, Row, Table};
+use eyre::{Context, Result};
+use forge::{
+    inspectors::CheatsConfig, opts::EvmOpts, MultiContractRunnerBuilder, TestFilter, TestOptions,
+};
+use foundry_cli::{
+    opts::CoreBuildArgs,
+    p_println,
+    utils::{FoundryPathExt, LoadConfig, STATIC_FUZZ_SEED},
+};
+use foundry_common::{compile::ProjectCompiler, evm::EvmArgs, fs};
+use foundry_compilers::{
+    remappings::{RelativeRemapping, Remapping},
+    Graph, Project, ProjectCompileOutput,
+};
+use foundry_config::Config;
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+};
+
+mod mutant;
+use mutant::{find_mutants, Mutant, MutationOperator};
+
+// Loads project's figment and merges the build cli arguments into it
+foundry_config::impl_figment_convert!(MutateArgs, opts, evm_opts);
+
+/// CLI arguments for `forge mutate`.
+#[derive(Clone, Debug, Parser)]
+pub struct MutateArgs {
+    /// The source files or directories to mutate.
+    ///
+    /// By default, all sources in the `src` directory are mutated.
+    #[clap(value_hint = ValueHint::FilePath, value_name = "PATH", num_args(1..))]
+    paths: Vec<PathBuf>,
+
+    /// Only generate mutants of the given kinds.
+    ///
+    /// This flag can be used multiple times.
+    #[clap(long, value_enum, value_name = "OPERATOR")]
+    operator: Vec<MutationOperator>,
+
+    #[clap(flatten)]
+    filter: FilterArgs,
+
+    #[clap(flatten)]
+    evm_opts: EvmArgs,
+
+    #[clap(flatten)]
+    opts: CoreBuildArgs,
+}
+
+impl MutateArgs {
+    pub async fn run(self) -> Result<()> {
+        let (mut config, evm_opts) = self.load_config_and_evm_opts_emit_warnings()?;
+
+        // install missing dependencies
+        if install::install_missing_dependencies(&mut config, self.opts.silent) &&
+            config.auto_detect_remappings
+        {
+            // need to re-configure here to also catch additional remappings
+            config = self.load_config();
+        }
+
+        // Set fuzz seed so mutants are killed deterministically
+        config.fuzz.seed = Some(U256::from_be_bytes(STATIC_FUZZ_SEED));
+
+        let sources = self.sources(&config)?;
+        let filter = self.filter.clone().merge_with_config(&config);
+
+        // Mutants are written to a copy of the project, so that the user's sources are never
+        // modified, even if the process is interrupted
+        let workspace = Workspace::new(&config)?;
+        let config = workspace.config(config);
+        let project = config.ephemeral_no_artifacts_project()?;
+        let graph = Graph::resolve(&project.paths)?;
+
+        p_println!(!self.opts.silent => "Running tests against the unmutated sources...");
+        let output = compile(&project)?;
+        if !run_tests(&config, &evm_opts, output, &filter).await? {
+            eyre::bail!(
+                "Tests must pass before mutation testing, run `forge test` to see failures."
+            )
+        }
+
+        let mut results = Vec::new();
+        for path in sources {
+            let path = fs::canonicalize_path(&path)?;
+            let relative = path
+                .strip_prefix(&workspace.original)
+                .wrap_err_with(|| format!("{} is outside of the project", path.display()))?
+                .to_path_buf();
+            let path = workspace.root.join(&relative);
+            let source = fs::read_to_string(&path)?;
+            let mutants = find_mutants(&source, &self.operator)
+                .wrap_err_with(|| format!("failed to mutate {}", relative.display()))?;
+
+            // Only the tests that import the source can kill its mutants
+            let filter = MutantFilter {
+                filter: &filter,
+                test_files: files_importing(&graph, &path, project.root()),
+            };
+            p_println!(!self.opts.silent => "Testing {} mutants of {} against {} test files...",
+                mutants.len(), relative.display(), filter.test_files.len());
+
+            for mutant in mutants {
+                let status = if filter.test_files.is_empty() {
+                    MutantStatus::Survived
+                } else {
+                    fs::write(&path, mutant.apply(&source))?;
+                    let status = match compile(&project) {
+                        Ok(output) => {
+                            if run_tests(&config, &evm_opts, output, &filter).await? {
+                                MutantStatus::Survived
+                            } else {
+                                MutantStatus::Killed
+                            }
+                        }
+                        Err(_) => MutantStatus::Invalid,
+                    };
+                    fs::write(&path, &source)?;
+                    status
+                };
+                results.push((relative.clone(), mutant, status));
+            }
+        }
+
+        report(&results);
+        Ok(())
+    }
+
+    /// Returns the absolute paths of the source files to mutate.
+    fn sources(&self, config: &Config) -> Result<Vec<PathBuf>> {
+        if self.paths.is_empty() {
+            let mut sources = fs::files_with_ext(&config.src, "sol");
+            sources.retain(|path| !path.is_sol_test());
+            return Ok(sources)
+        }
+        let cwd = std::env::current_dir()?;
+        Ok(self.paths.iter().flat_map(|path| fs::files_with_ext(cwd.join(path), "sol")).collect())
+    }
+}
+
+/// Whether the tests killed a mutant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MutantStatus {
+    /// At least one test failed.
+    Killed,
+    /// All tests passed, or no test imports the mutated source.
+    Survived,
+    /// The mutant does not compile.
+    Invalid,
+}
+
+/// Compiles the project, without printing anything.
+fn compile(project: &Project) -> Result<ProjectCompileOutput> {
+    Ok(ProjectCompiler::new()
+        .quiet(true)
+        .compile(project)?
+        .with_stripped_file_prefixes(project.root()))
+}
+
+/// Runs the tests matching the filter, returning true if they all pass.
+async fn run_tests(
+    config: &Config,
+    evm_opts: &EvmOpts,
+    output: ProjectCompileOutput,
+    filter: &dyn TestFilter,
+) -> Result<bool> {
+    let env = evm_opts.evm_env().await?;
+    let mut runner = MultiContractRunnerBuilder::default()
+        .initial_balance(evm_opts.initial_balance)
+        .evm_spec(config.evm_spec_id())
+        .sender(evm_opts.sender)
+        .with_fork(evm_opts.get_fork(config, env.clone()))
+        .with_cheats_config(CheatsConfig::new(config, evm_opts.clone()))
+        .with_test_options(TestOptions {
+            fuzz: config.fuzz,
+            invariant: config.invariant.clone(),
+            ..Default::default()
+        })
+        .build(config.__root.0.clone(), output, env, evm_opts.clone())?;
+    let results = runner.test_collect(filter, runner.test_options.clone()).await;
+    Ok(results.values().all(|suite| suite.failures().next().is_none()))
+}
+
+/// Returns the files that are or import the given source, relative to the project root.
+fn files_importing(graph: &Graph, source: &Path, root: &Path) -> HashSet<PathBuf> {
+    graph
+        .files()
+        .keys()
+        .filter(|file| {
+            file.as_path() == source ||
+                graph.imports(file).iter().any(|import| import.as_path() == source)
+        })
+        .map(|file| file.strip_prefix(root).unwrap_or(file).to_path_buf())
+        .collect()
+}
+
+/// A [`TestFilter`] that only matches the tests in the given files.
+struct MutantFilter<'a, F> {
+    filter: &'a F,
+    test_files: HashSet<PathBuf>,
+}
+
+impl<'a, F: TestFilter> TestFilter for MutantFilter<'a, F> {
+    fn matches_test(&self, test_name: &str) -> bool {
+        self.filter.matches_test(test_name)
+    }
+
+    fn matches_contract(&self, contract_name: &str) -> bool {
+        self.filter.matches_contract(contract_name)
+    }
+
+    fn matches_path(&self, path: &Path) -> bool {
+        self.filter.matches_path(path) && self.test_files.contains(path)
+    }
+}
+
+/// A copy of the project in a temporary directory, in which the mutants are built and tested.
+///
+/// The dependencies in `libs` are not copied but used from the original project, since they are
+/// never mutated. The directory is removed when dropped.
+struct Workspace {
+    /// The root of the original project.
+    original: PathBuf,
+    /// The root of the copy.
+    root: PathBuf,
+}
+
+impl Workspace {
+    /// Copies the project, without its dependencies, build outputs and git directory.
+    fn new(config: &Config) -> Result<Self> {
+        let original = config.__root.0.clone();
+        let root = std::env::temp_dir().join(format!("forge-mutate-{}", std::process::id()));
+        if root.exists() {
+            fs::remove_dir_all(&root)?;
+        }
+        let workspace = Self { original, root };
+
+        let mut excluded = config.libs.clone();
+        excluded.extend([
+            workspace.original.join(".git"),
+            config.out.clone(),
+            config.broadcast.clone(),
+            config.cache_path.clone(),
+        ]);
+        copy_dir(&workspace.original, &workspace.root, &excluded)
+            .wrap_err("failed to copy the project to a temporary directory")?;
+        Ok(workspace)
+    }
+
+    /// Returns the config of the project rebased onto the copy.
+    fn config(&self, mut config: Config) -> Config {
+        let rebase = |path: &Path| match path.strip_prefix(&self.original) {
+            Ok(relative) if !config.libs.iter().any(|lib| path.starts_with(lib)) => {
+                self.root.join(relative)
+            }
+            _ => path.to_path_buf(),
+        };
+
+        config.src = rebase(&config.src);
+        config.test = rebase(&config.test);
+        config.script = rebase(&config.script);
+        config.out = rebase(&config.out);
+        config.cache_path = rebase(&config.cache_path);
+        config.broadcast = rebase(&config.broadcast);
+        config.remappings = config
+            .remappings
+            .iter()
+            .map(|remapping| {
+                let mut remapping = Remapping::from(remapping.clone());
+                remapping.path = rebase(Path::new(&remapping.path)).to_string_lossy().to_string();
+                RelativeRemapping::new(remapping, &self.root)
+            })
+            .collect();
+        for permission in &mut config.fs_permissions.permissions {
+            permission.path = rebase(&permission.path);
+        }
+        config.__root = self.root.clone().into();
+        config
+    }
+}
+
+impl Drop for Workspace {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Recursively copies the directory `from` to `to`, skipping the excluded paths.
+fn copy_dir(from: &Path, to: &Path, excluded: &[PathBuf]) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        if excluded.contains(&path) {
+            continue
+        }
+        let target = to.join(entry.file_name());
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            copy_dir(&path, &target, excluded)?;
+        } else if file_type.is_file() {
+            std::fs::copy(&path, target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Prints the surviving mutants and the mutation score.
+fn report(results: &[(PathBuf, Mutant, MutantStatus)]) {
+    let count = |status| results.iter().filter(|(_, _, s)| *s == status).count();
+    let (killed, survived, invalid) =
+        (count(MutantStatus::Killed), count(MutantStatus::Survived), count(MutantStatus::Invalid));
+
+    if survived > 0 {
+        let mut table = Table::new();
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(["File", "Line", "Operator", "Original", "Mutant"].map(|header| {
+            Cell::new(header).set_alignment(CellAlignment::Center).add_attribute(Attribute::Bold)
+        }));
+        for (path, mutant, _) in
+            results.iter().filter(|(_, _, status)| *status == MutantStatus::Survived)
+        {
+            table.add_row(Row::from(vec![
+                Cell::new(path.display()),
+                Cell::new(mutant.line).set_alignment(CellAlignment::Right),
+                Cell::new(mutant.operator),
+                Cell::new(&mutant.original),
+                Cell::new(&mutant.replacement),
+            ]));
+        }
+        println!("Surviving mutants:");
+        println!("{table}");
+    }
+
+    let score = if killed + survived == 0 {
+        100.
+    } else {
+        killed as f64 / (killed + survived) as f64 * 100.
+    };
+    println!(
+        "{} mutants: {killed} killed, {survived} survived, {invalid} invalid (mutation score: {score:.2}%)",
+        results.len()
+    );
+}
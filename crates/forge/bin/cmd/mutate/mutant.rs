@@ -0,0 +1,305 @@
+use clap::ValueEnum;
+use eyre::Result;
+use solang_parser::pt::{
+    CodeLocation, ContractPart, Expression, FunctionDefinition, Loc, SourceUnitPart, Statement,
+};
+use std::{fmt, ops::Range};
+
+/// The kinds of changes mutants make to the source.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ValueEnum)]
+pub enum MutationOperator {
+    /// Swaps a binary operator for a different one, e.g. `+` for `-` or `<` for `>=`.
+    OperatorSwap,
+    /// Negates the condition of an `if` statement, a loop or a `require`.
+    ConditionNegation,
+    /// Deletes an expression statement.
+    StatementDeletion,
+}
+
+impl fmt::Display for MutationOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OperatorSwap => f.write_str("operator-swap"),
+            Self::ConditionNegation => f.write_str("condition-negation"),
+            Self::StatementDeletion => f.write_str("statement-deletion"),
+        }
+    }
+}
+
+/// A first-order mutant of a source file, i.e. a single change to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mutant {
+    /// The kind of change.
+    pub operator: MutationOperator,
+    /// The byte range of the source that is replaced.
+    pub span: Range<usize>,
+    /// The line the change is on.
+    pub line: usize,
+    /// The replaced source.
+    pub original: String,
+    /// The source it is replaced with.
+    pub replacement: String,
+}
+
+impl Mutant {
+    /// Returns the mutated source.
+    pub fn apply(&self, source: &str) -> String {
+        let mut mutated = source.to_string();
+        mutated.replace_range(self.span.clone(), &self.replacement);
+        mutated
+    }
+}
+
+/// Generates the mutants of the functions in the given source, restricted to the given operators
+/// if any, in the order they appear in the source.
+pub fn find_mutants(source: &str, operators: &[MutationOperator]) -> Result<Vec<Mutant>> {
+    let (source_unit, _) = solang_parser::parse(source, 0)
+        .map_err(|diagnostics| eyre::eyre!("failed to parse source: {diagnostics:?}"))?;
+
+    let mut visitor = MutantVisitor { source, mutants: Vec::new() };
+    for part in &source_unit.0 {
+        match part {
+            SourceUnitPart::ContractDefinition(contract) => {
+                for part in &contract.parts {
+                    if let ContractPart::FunctionDefinition(function) = part {
+                        visitor.visit_function(function);
+                    }
+                }
+            }
+            SourceUnitPart::FunctionDefinition(function) => visitor.visit_function(function),
+            _ => {}
+        }
+    }
+
+    let mut mutants = visitor.mutants;
+    mutants.retain(|mutant| operators.is_empty() || operators.contains(&mutant.operator));
+    mutants.sort_by_key(|mutant| mutant.span.start);
+    Ok(mutants)
+}
+
+/// Walks the statements and expressions of functions and collects their mutants.
+struct MutantVisitor<'a> {
+    source: &'a str,
+    mutants: Vec<Mutant>,
+}
+
+impl<'a> MutantVisitor<'a> {
+    fn visit_function(&mut self, function: &FunctionDefinition) {
+        if let Some(body) = &function.body {
+            self.visit_statement(body);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Block { statements, .. } => {
+                statements.iter().for_each(|statement| self.visit_statement(statement))
+            }
+            Statement::If(_, condition, then, otherwise) => {
+                self.negate(condition);
+                self.visit_expression(condition);
+                self.visit_statement(then);
+                if let Some(otherwise) = otherwise {
+                    self.visit_statement(otherwise);
+                }
+            }
+            Statement::While(_, condition, body) | Statement::DoWhile(_, body, condition) => {
+                self.negate(condition);
+                self.visit_expression(condition);
+                self.visit_statement(body);
+            }
+            Statement::For(_, init, condition, next, body) => {
+                if let Some(init) = init {
+                    self.visit_statement(init);
+                }
+                if let Some(condition) = condition {
+                    self.negate(condition);
+                    self.visit_expression(condition);
+                }
+                if let Some(next) = next {
+                    self.visit_expression(next);
+                }
+                if let Some(body) = body {
+                    self.visit_statement(body);
+                }
+            }
+            Statement::Expression(loc, expression) => {
+                self.delete(loc);
+                self.visit_expression(expression);
+            }
+            Statement::VariableDefinition(_, _, Some(expression)) |
+            Statement::Return(_, Some(expression)) |
+            Statement::Emit(_, expression) => self.visit_expression(expression),
+            _ => {}
+        }
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let Some((left, right, operator, replacement)) = swappable_operator(expression) {
+            self.swap(left, right, operator, replacement);
+        }
+
+        match expression {
+            Expression::Add(_, left, right) |
+            Expression::Subtract(_, left, right) |
+            Expression::Multiply(_, left, right) |
+            Expression::Divide(_, left, right) |
+            Expression::Modulo(_, left, right) |
+            Expression::Less(_, left, right) |
+            Expression::More(_, left, right) |
+            Expression::LessEqual(_, left, right) |
+            Expression::MoreEqual(_, left, right) |
+            Expression::Equal(_, left, right) |
+            Expression::NotEqual(_, left, right) |
+            Expression::And(_, left, right) |
+            Expression::Or(_, left, right) |
+            Expression::Assign(_, left, right) |
+            Expression::AssignAdd(_, left, right) |
+            Expression::AssignSubtract(_, left, right) |
+            Expression::AssignMultiply(_, left, right) |
+            Expression::AssignDivide(_, left, right) => {
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            Expression::Not(_, inner) |
+            Expression::Parenthesis(_, inner) |
+            Expression::MemberAccess(_, inner, _) => self.visit_expression(inner),
+            Expression::ArraySubscript(_, array, index) => {
+                self.visit_expression(array);
+                if let Some(index) = index {
+                    self.visit_expression(index);
+                }
+            }
+            Expression::ConditionalOperator(_, condition, left, right) => {
+                self.negate(condition);
+                self.visit_expression(condition);
+                self.visit_expression(left);
+                self.visit_expression(right);
+            }
+            Expression::FunctionCall(_, function, args) => {
+                if matches!(function.as_ref(), Expression::Variable(id) if id.name == "require") {
+                    if let Some(condition) = args.first() {
+                        self.negate(condition);
+                    }
+                }
+                self.visit_expression(function);
+                args.iter().for_each(|arg| self.visit_expression(arg));
+            }
+            _ => {}
+        }
+    }
+
+    /// Replaces the operator between the two operands.
+    fn swap(&mut self, left: &Expression, right: &Expression, operator: &str, replacement: &str) {
+        let (start, end) = (left.loc().end(), right.loc().start());
+        let Some(offset) = self.source.get(start..end).and_then(|gap| gap.find(operator)) else {
+            return
+        };
+        let start = start + offset;
+        self.push(MutationOperator::OperatorSwap, start..start + operator.len(), replacement);
+    }
+
+    /// Negates the condition.
+    fn negate(&mut self, condition: &Expression) {
+        let loc = condition.loc();
+        let replacement = format!("!({})", &self.source[loc.start()..loc.end()]);
+        self.push(MutationOperator::ConditionNegation, loc.start()..loc.end(), &replacement);
+    }
+
+    /// Deletes the statement, including its semicolon.
+    fn delete(&mut self, loc: &Loc) {
+        let mut end = loc.end();
+        if self.source[end..].starts_with(';') {
+            end += 1;
+        }
+        self.push(MutationOperator::StatementDeletion, loc.start()..end, "");
+    }
+
+    fn push(&mut self, operator: MutationOperator, span: Range<usize>, replacement: &str) {
+        self.mutants.push(Mutant {
+            operator,
+            line: self.source[..span.start].matches('\n').count() + 1,
+            original: self.source[span.clone()].to_string(),
+            span,
+            replacement: replacement.to_string(),
+        });
+    }
+}
+
+/// Returns the operands, the operator and the operator to swap it with if the expression is a
+/// binary operation that can be swapped.
+fn swappable_operator(
+    expression: &Expression,
+) -> Option<(&Expression, &Expression, &'static str, &'static str)> {
+    let (left, right, operator, replacement) = match expression {
+        Expression::Add(_, left, right) => (left, right, "+", "-"),
+        Expression::Subtract(_, left, right) => (left, right, "-", "+"),
+        Expression::Multiply(_, left, right) => (left, right, "*", "/"),
+        Expression::Divide(_, left, right) => (left, right, "/", "*"),
+        Expression::Modulo(_, left, right) => (left, right, "%", "*"),
+        Expression::Less(_, left, right) => (left, right, "<", ">="),
+        Expression::More(_, left, right) => (left, right, ">", "<="),
+        Expression::LessEqual(_, left, right) => (left, right, "<=", ">"),
+        Expression::MoreEqual(_, left, right) => (left, right, ">=", "<"),
+        Expression::Equal(_, left, right) => (left, right, "==", "!="),
+        Expression::NotEqual(_, left, right) => (left, right, "!=", "=="),
+        Expression::And(_, left, right) => (left, right, "&&", "||"),
+        Expression::Or(_, left, right) => (left, right, "||", "&&"),
+        _ => return None,
+    };
+    Some((left, right, operator, replacement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"
+contract Counter {
+    uint256 public number;
+
+    function increment(uint256 by) public {
+        require(by > 0);
+        if (number + by < 100) {
+            number += by;
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn generates_mutants() {
+        let mutants = find_mutants(SOURCE, &[]).unwrap();
+        let changes = mutants
+            .iter()
+            .map(|m| (m.operator, m.line, m.original.as_str(), m.replacement.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                (MutationOperator::StatementDeletion, 6, "require(by > 0);", ""),
+                (MutationOperator::ConditionNegation, 6, "by > 0", "!(by > 0)"),
+                (MutationOperator::OperatorSwap, 6, ">", "<="),
+                (
+                    MutationOperator::ConditionNegation,
+                    7,
+                    "number + by < 100",
+                    "!(number + by < 100)"
+                ),
+                (MutationOperator::OperatorSwap, 7, "+", "-"),
+                (MutationOperator::OperatorSwap, 7, "<", ">="),
+                (MutationOperator::StatementDeletion, 8, "number += by;", ""),
+            ]
+        );
+
+        let mutated = mutants[4].apply(SOURCE);
+        assert!(mutated.contains("if (number - by < 100) {"));
+    }
+
+    #[test]
+    fn filters_operators() {
+        let mutants = find_mutants(SOURCE, &[MutationOperator::StatementDeletion]).unwrap();
+        assert_eq!(mutants.len(), 2);
+        assert!(mutants.iter().all(|m| m.operator == MutationOperator::StatementDeletion));
+    }
+}
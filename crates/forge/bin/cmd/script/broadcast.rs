@@ -1,6 +1,15 @@
 use super::{
-    multi::MultiChainSequence, providers::ProvidersManager, receipts::clear_pendings,
-    sequence::ScriptSequence, transaction::TransactionWithMetadata, verify::VerifyBundle, *,
+    deployments::save_deployments,
+    multi::MultiChainSequence,
+    proposal::Proposal,
+    providers::ProvidersManager,
+    receipts::{clear_pendings, resolve_fee},
+    report::SimulationReport,
+    safe::SafeBatch,
+    sequence::ScriptSequence,
+    transaction::TransactionWithMetadata,
+    verify::VerifyBundle,
+    *,
 };
 use alloy_primitives::{utils::format_units, TxHash};
 use ethers_core::types::transaction::eip2718::TypedTransaction;
@@ -11,14 +20,22 @@ use foundry_cli::{
     init_progress,
     opts::WalletSigner,
     update_progress,
-    utils::{has_batch_support, has_different_gas_calc},
+    utils::{has_batch_support, has_different_gas_calc, print_receipt},
 };
 use foundry_common::{
     provider::ethers::{estimate_eip1559_fees, try_get_http_provider, RetryProvider},
     shell,
     types::{ToAlloy, ToEthers},
 };
+use foundry_evm::{
+    constants::{
+        DEFAULT_CREATE2_DEPLOYER, DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_COST,
+        DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_TX, DEFAULT_CREATE2_DEPLOYER_SIGNER,
+    },
+    inspectors::cheatcodes::read_artifact_layouts,
+};
 use futures::StreamExt;
+use revm_inspectors::tracing::types::CallKind;
 use std::{cmp::min, collections::HashSet, ops::Mul, sync::Arc};
 
 impl ScriptArgs {
@@ -33,6 +50,8 @@ impl ScriptArgs {
         let already_broadcasted = deployment_sequence.receipts.len();
 
         if already_broadcasted < deployment_sequence.transactions.len() {
+            deploy_missing_create2_deployer(&provider, deployment_sequence).await?;
+
             let required_addresses: HashSet<Address> = deployment_sequence
                 .typed_transactions()
                 .into_iter()
@@ -69,19 +88,24 @@ impl ScriptArgs {
             let sequential_broadcast =
                 send_kind.signers_count() != 1 || self.slow || !has_batch_support(chain);
 
-            // Make a one-time gas price estimation
-            let (gas_price, eip1559_fees) = {
-                match deployment_sequence.transactions.front().unwrap().typed_tx() {
-                    TypedTransaction::Eip1559(_) => {
-                        let fees = estimate_eip1559_fees(&provider, Some(chain))
-                            .await
-                            .wrap_err("Failed to estimate EIP1559 fees. This chain might not support EIP1559, try adding --legacy to your command.")?;
-
-                        (None, Some(fees))
-                    }
-                    _ => (provider.get_gas_price().await.ok(), None),
-                }
+            // Make a one-time gas price estimation for each type of transaction in the sequence,
+            // which can differ if set with `vm.setTxType`
+            let (has_eip1559, has_other) = deployment_sequence.transactions.iter().fold(
+                (false, false),
+                |(has_eip1559, has_other), tx| match tx.typed_tx() {
+                    TypedTransaction::Eip1559(_) => (true, has_other),
+                    _ => (has_eip1559, true),
+                },
+            );
+            let eip1559_fees = if has_eip1559 {
+                let fees = estimate_eip1559_fees(&provider, Some(chain))
+                    .await
+                    .wrap_err("Failed to estimate EIP1559 fees. This chain might not support EIP1559, try adding --legacy to your command.")?;
+                Some(fees)
+            } else {
+                None
             };
+            let gas_price = if has_other { provider.get_gas_price().await.ok() } else { None };
 
             // Iterate through transactions, matching the `from` field with the associated
             // wallet. Then send the transaction. Panics if we find a unknown `from`
@@ -95,6 +119,7 @@ impl ScriptArgs {
 
                     let kind = send_kind.for_sender(&from)?;
                     let is_fixed_gas_limit = tx_with_metadata.is_fixed_gas_limit;
+                    let is_fee_bumped = tx_with_metadata.is_fee_bumped;
 
                     let mut tx = tx.clone();
 
@@ -103,21 +128,31 @@ impl ScriptArgs {
                     if let Some(gas_price) = self.with_gas_price {
                         tx.set_gas_price(gas_price.to_ethers());
                     } else {
-                        // fill gas price
+                        // fill gas price, keeping the fees bumped to replace a pending transaction
+                        // if they're higher
+                        let bumped = |fee: Option<_>| fee.filter(|_| is_fee_bumped);
                         match tx {
                             TypedTransaction::Eip1559(ref mut inner) => {
                                 let eip1559_fees =
                                     eip1559_fees.expect("Could not get eip1559 fee estimation.");
-                                if let Some(priority_gas_price) = self.priority_gas_price {
-                                    inner.max_priority_fee_per_gas =
-                                        Some(priority_gas_price.to_ethers());
-                                } else {
-                                    inner.max_priority_fee_per_gas = Some(eip1559_fees.1);
-                                }
-                                inner.max_fee_per_gas = Some(eip1559_fees.0);
+                                inner.max_priority_fee_per_gas = Some(resolve_fee(
+                                    self.priority_gas_price.map(|price| price.to_ethers()),
+                                    bumped(inner.max_priority_fee_per_gas),
+                                    eip1559_fees.1,
+                                ));
+                                inner.max_fee_per_gas = Some(resolve_fee(
+                                    None,
+                                    bumped(inner.max_fee_per_gas),
+                                    eip1559_fees.0,
+                                ));
                             }
                             _ => {
-                                tx.set_gas_price(gas_price.expect("Could not get gas_price."));
+                                let gas_price = gas_price.expect("Could not get gas_price.");
+                                tx.set_gas_price(resolve_fee(
+                                    None,
+                                    bumped(tx.gas_price()),
+                                    gas_price,
+                                ));
                             }
                         }
                     }
@@ -131,7 +166,7 @@ impl ScriptArgs {
             // We send transactions and wait for receipts in batches of 100, since some networks
             // cannot handle more than that.
             let batch_size = 100;
-            let mut index = 0;
+            let mut index = already_broadcasted;
 
             for (batch_number, batch) in sequence.chunks(batch_size).map(|f| f.to_vec()).enumerate()
             {
@@ -156,7 +191,7 @@ impl ScriptArgs {
                         let tx_hash = tx_hash.await?;
                         deployment_sequence.add_pending(index, tx_hash);
 
-                        update_progress!(pb, (index + already_broadcasted));
+                        update_progress!(pb, index);
                         index += 1;
 
                         clear_pendings(provider.clone(), deployment_sequence, Some(vec![tx_hash]))
@@ -173,7 +208,7 @@ impl ScriptArgs {
                         let tx_hash = tx_hash?;
                         deployment_sequence.add_pending(index, tx_hash);
 
-                        update_progress!(pb, (index + already_broadcasted));
+                        update_progress!(pb, index);
                         index += 1;
                     }
 
@@ -191,9 +226,15 @@ impl ScriptArgs {
             }
         }
 
+        self.send_user_operations(deployment_sequence, fork_url, script_wallets).await?;
+
         shell::println("\n\n==========================")?;
         shell::println("\nONCHAIN EXECUTION COMPLETE & SUCCESSFUL.")?;
 
+        if deployment_sequence.receipts.is_empty() {
+            return Ok(())
+        }
+
         let (total_gas, total_gas_price, total_paid) = deployment_sequence.receipts.iter().fold(
             (U256::ZERO, U256::ZERO, U256::ZERO),
             |acc, receipt| {
@@ -290,6 +331,36 @@ impl ScriptArgs {
                     )
                     .await?;
 
+                if self.report {
+                    self.report_simulation(&deployments, &script_config.config).await?;
+                }
+
+                if let Some(safe) = self.safe {
+                    if script_config.has_multiple_rpcs() {
+                        bail!("Multi chain scripts can't be batched into a Safe transaction.")
+                    }
+                    let sequence = deployments.first().expect("to be set.");
+                    SafeBatch::new(sequence, safe, script_config.target_contract().name.clone())?
+                        .save(sequence)?;
+                    shell::println("\nImport the batch into the Safe Transaction Builder to propose it to the Safe's owners.")?;
+                    return Ok(())
+                }
+
+                if let Some(kind) = self.proposal {
+                    if script_config.has_multiple_rpcs() {
+                        bail!("Multi chain scripts can't be exported as a proposal.")
+                    }
+                    let sequence = deployments.first().expect("to be set.");
+                    Proposal::new(
+                        sequence,
+                        kind,
+                        self.proposal_description.clone(),
+                        self.timelock_delay,
+                    )?
+                    .save(sequence)?;
+                    return Ok(())
+                }
+
                 if script_config.has_multiple_rpcs() {
                     trace!(target: "script", "broadcasting multi chain deployment");
 
@@ -332,6 +403,25 @@ impl ScriptArgs {
         Ok(())
     }
 
+    /// Prints and saves the simulation report of each sequence, with the gas priced at the
+    /// current gas price of its chain unless `--with-gas-price` is passed.
+    async fn report_simulation(&self, sequences: &[ScriptSequence], config: &Config) -> Result<()> {
+        let layouts = read_artifact_layouts(&config.project_paths().artifacts);
+        for sequence in sequences {
+            let gas_price = match (self.with_gas_price, sequence.transactions.front()) {
+                (Some(gas_price), _) => gas_price,
+                (None, Some(TransactionWithMetadata { rpc: Some(rpc), .. })) => {
+                    try_get_http_provider(rpc)?.get_gas_price().await?.to_alloy()
+                }
+                (None, _) => U256::ZERO,
+            };
+            let report = SimulationReport::new(sequence, &layouts, gas_price, self.usd_price);
+            shell::println(format!("\n{report}"))?;
+            report.save(sequence)?;
+        }
+        Ok(())
+    }
+
     /// Broadcasts a single chain script.
     async fn single_deployment(
         &self,
@@ -353,6 +443,14 @@ impl ScriptArgs {
 
         self.send_transactions(deployment_sequence, &rpc, &result.script_wallets).await?;
 
+        if self.save_deployments {
+            save_deployments(
+                deployment_sequence,
+                &verify.known_contracts,
+                &script_config.config.deployments,
+            )?;
+        }
+
         if self.verify {
             return deployment_sequence.verify_contracts(&script_config.config, verify).await;
         }
@@ -372,26 +470,36 @@ impl ScriptArgs {
         decoder: &CallTraceDecoder,
         known_contracts: &ContractsByArtifact,
     ) -> Result<Vec<ScriptSequence>> {
-        if !txs.is_empty() {
+        // User operations aren't simulated, they're sent after the transactions of their chain
+        let (user_ops, txs): (BroadcastableTransactions, BroadcastableTransactions) =
+            txs.into_iter().partition(|tx| tx.user_op_owner.is_some());
+
+        let mut sequences = if !txs.is_empty() {
             let gas_filled_txs = self
                 .fills_transactions_with_gas(txs, script_config, decoder, known_contracts)
                 .await?;
 
             let returns = self.get_returns(&*script_config, &script_result.returned)?;
 
-            return self
-                .bundle_transactions(
-                    gas_filled_txs,
-                    &script_config.target_contract().clone(),
-                    &mut script_config.config,
-                    returns,
-                )
-                .await;
-        } else if self.broadcast {
+            self.bundle_transactions(
+                gas_filled_txs,
+                &script_config.target_contract().clone(),
+                &mut script_config.config,
+                returns,
+            )
+            .await?
+        } else if self.broadcast && user_ops.is_empty() {
             eyre::bail!("No onchain transactions generated in script");
+        } else {
+            vec![]
+        };
+
+        if !user_ops.is_empty() {
+            let returns = self.get_returns(&*script_config, &script_result.returned)?;
+            self.add_user_operations(&mut sequences, user_ops, script_config, returns).await?;
         }
 
-        Ok(vec![])
+        Ok(sequences)
     }
 
     /// Takes the collected transactions and executes them locally before converting them to
@@ -410,6 +518,9 @@ impl ScriptArgs {
                 .map(|btx| {
                     let mut tx = TransactionWithMetadata::from_tx_request(btx.transaction);
                     tx.rpc = btx.rpc;
+                    if let Some(tx_type) = btx.tx_type {
+                        tx.set_tx_type(tx_type);
+                    }
                     tx
                 })
                 .collect()
@@ -667,3 +778,42 @@ impl SendTransactionsKind {
         }
     }
 }
+
+/// Deploys the default CREATE2 deployer with its keyless transaction if transactions of the
+/// sequence create contracts with it, but it's missing on the chain.
+async fn deploy_missing_create2_deployer(
+    provider: &RetryProvider,
+    deployment_sequence: &ScriptSequence,
+) -> Result<()> {
+    let Some(create2_deployer) = deployment_sequence
+        .transactions
+        .iter()
+        .filter(|tx| tx.opcode == CallKind::Create2)
+        .find_map(|tx| tx.typed_tx().to_addr().copied())
+    else {
+        return Ok(())
+    };
+    if !provider.get_code(create2_deployer, None).await?.is_empty() {
+        return Ok(())
+    }
+
+    let chain = deployment_sequence.chain;
+    if create2_deployer.to_alloy() != DEFAULT_CREATE2_DEPLOYER {
+        bail!("The CREATE2 deployer {create2_deployer:?} isn't deployed on chain {chain}. Deploy it before broadcasting.")
+    }
+    let signer = DEFAULT_CREATE2_DEPLOYER_SIGNER.to_ethers();
+    let cost = U256::from(DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_COST);
+    if provider.get_balance(signer, None).await?.to_alloy() < cost {
+        bail!("The CREATE2 deployer {create2_deployer:?} isn't deployed on chain {chain}. Fund the signer of its keyless deployment {signer:?} with {cost} wei to deploy it automatically.")
+    }
+
+    shell::println(format!("##\nDeploying the CREATE2 deployer {create2_deployer:?}."))?;
+    let receipt = provider
+        .send_raw_transaction(DEFAULT_CREATE2_DEPLOYER_DEPLOYMENT_TX.to_vec().into())
+        .await
+        .wrap_err("Failed to deploy the CREATE2 deployer. The chain might only accept replay protected transactions.")?
+        .await?
+        .wrap_err("The deployment of the CREATE2 deployer was dropped from the mempool.")?;
+    print_receipt(chain.into(), &receipt);
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use super::{multi::MultiChainSequence, sequence::ScriptSequence, verify::VerifyBundle, *};
+use super::{
+    deployments::save_deployments, multi::MultiChainSequence, sequence::ScriptSequence,
+    verify::VerifyBundle, *,
+};
 use alloy_primitives::Bytes;
 
 use ethers_providers::Middleware;
@@ -117,7 +120,11 @@ impl ScriptArgs {
         }
 
         verify.known_contracts = flatten_contracts(&highlevel_known_contracts, false);
-        self.check_contract_sizes(&result, &highlevel_known_contracts)?;
+        self.check_contract_sizes(
+            &result,
+            &highlevel_known_contracts,
+            script_config.config.create2_deployer,
+        )?;
 
         self.handle_broadcastable_transactions(result, libraries, &decoder, script_config, verify)
             .await
@@ -172,6 +179,8 @@ impl ScriptArgs {
                 lib_deploy.push_back(BroadcastableTransaction {
                     rpc: tx.rpc.clone(),
                     transaction: tx.transaction.clone(),
+                    tx_type: tx.tx_type,
+                    user_op_owner: tx.user_op_owner,
                 });
             }
             *txs = lib_deploy;
@@ -264,11 +273,20 @@ impl ScriptArgs {
             deployment_sequence.verify_preflight_check(&script_config.config, &verify)?;
         }
 
-        receipts::wait_for_pending(provider, &mut deployment_sequence).await?;
+        receipts::wait_for_pending(provider, &mut deployment_sequence, self.bump_fee_percent)
+            .await?;
 
         if self.resume {
             self.send_transactions(&mut deployment_sequence, fork_url, &result.script_wallets)
                 .await?;
+
+            if self.save_deployments {
+                save_deployments(
+                    &deployment_sequence,
+                    &verify.known_contracts,
+                    &script_config.config.deployments,
+                )?;
+            }
         }
 
         if self.verify {
@@ -337,6 +355,8 @@ impl ScriptArgs {
                 txs.push_back(BroadcastableTransaction {
                     rpc: new_tx.rpc.clone(),
                     transaction: new_tx.transaction.clone(),
+                    tx_type: new_tx.tx_type,
+                    user_op_owner: new_tx.user_op_owner,
                 });
             }
         }
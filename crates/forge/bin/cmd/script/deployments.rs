@@ -0,0 +1,77 @@
+use super::sequence::ScriptSequence;
+use alloy_json_abi::JsonAbi;
+use alloy_primitives::{keccak256, Address, B256};
+use eyre::Result;
+use foundry_common::{fs, shell, types::ToAlloy, ContractsByArtifact};
+use revm_inspectors::tracing::types::CallKind;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A contract deployed by a script, saved to `<deployments>/<chain>/<ContractName>.json` so that
+/// it can be read back with `vm.getDeployment`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Deployment {
+    pub contract_name: String,
+    pub address: Address,
+    pub abi: JsonAbi,
+    pub constructor_args: Vec<String>,
+    pub transaction_hash: B256,
+    pub block_number: u64,
+    /// The keccak256 hash of the contract's creation code, without the constructor arguments
+    pub bytecode_hash: B256,
+}
+
+/// Saves the contracts created by the successful transactions of the sequence to the chain's
+/// directory in `deployments`, replacing earlier deployments of the same contracts.
+///
+/// Only contracts deployed directly by a transaction, with `CREATE` or the CREATE2 deployer, are
+/// saved, since the name of contracts created by other contracts isn't known.
+pub fn save_deployments(
+    sequence: &ScriptSequence,
+    known_contracts: &ContractsByArtifact,
+    deployments: &Path,
+) -> Result<()> {
+    let dir = deployments.join(sequence.chain.to_string());
+    let mut saved = 0;
+    for tx in &sequence.transactions {
+        if !matches!(tx.opcode, CallKind::Create | CallKind::Create2) {
+            continue
+        }
+        let (Some(contract_name), Some(address), Some(transaction_hash)) = (
+            tx.contract_name.as_deref().filter(|name| !name.is_empty()),
+            tx.contract_address,
+            tx.hash,
+        ) else {
+            continue
+        };
+        let Some(receipt) = sequence.receipts.iter().find(|receipt| {
+            receipt.transaction_hash.to_alloy() == transaction_hash &&
+                receipt.status.map_or(false, |status| !status.is_zero())
+        }) else {
+            continue
+        };
+        let Ok(Some((_, (abi, code)))) = known_contracts.find_by_name_or_identifier(contract_name)
+        else {
+            continue
+        };
+
+        let deployment = Deployment {
+            contract_name: contract_name.to_string(),
+            address,
+            abi: abi.clone(),
+            constructor_args: tx.arguments.clone().unwrap_or_default(),
+            transaction_hash,
+            block_number: receipt.block_number.unwrap_or_default().as_u64(),
+            bytecode_hash: keccak256(code),
+        };
+        fs::create_dir_all(&dir)?;
+        fs::write_json_file(&dir.join(format!("{contract_name}.json")), &deployment)?;
+        saved += 1;
+    }
+
+    if saved > 0 {
+        shell::println(format!("\nDeployments saved to: {}\n", dir.display()))?;
+    }
+    Ok(())
+}
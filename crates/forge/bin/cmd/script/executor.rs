@@ -9,7 +9,8 @@ use eyre::Result;
 use forge::{
     backend::Backend,
     executors::ExecutorBuilder,
-    inspectors::{cheatcodes::BroadcastableTransactions, CheatsConfig},
+    inspectors::{cheatcodes::BroadcastableTransactions, CheatsConfig, GasSchedule},
+    precompiles::ChainPrecompiles,
     traces::CallTraceDecoder,
 };
 use foundry_cli::utils::{ensure_clean_constructor, needs_setup};
@@ -138,6 +139,18 @@ impl ScriptArgs {
                 let mut runner = runners.get(rpc).expect("invalid rpc url").write();
 
                 let mut tx = transaction.transaction;
+                let state_diff = if self.report {
+                    let from = tx
+                        .from
+                        .expect("transaction doesn't have a `from` address at execution time");
+                    Some(
+                        runner
+                            .state_diff(from, tx.to, tx.data.clone(), tx.value)
+                            .wrap_err("Internal EVM error during simulation")?,
+                    )
+                } else {
+                    None
+                };
                 let result = runner
                     .simulate(
                         tx.from
@@ -187,7 +200,7 @@ impl ScriptArgs {
                     }
                 }
 
-                let tx = TransactionWithMetadata::new(
+                let mut tx = TransactionWithMetadata::new(
                     tx,
                     transaction.rpc,
                     &result,
@@ -195,7 +208,12 @@ impl ScriptArgs {
                     decoder,
                     created_contracts,
                     is_fixed_gas_limit,
+                    script_config.config.create2_deployer,
                 )?;
+                if let Some(tx_type) = transaction.tx_type {
+                    tx.set_tx_type(tx_type);
+                }
+                tx.state_diff = state_diff;
 
                 eyre::Ok((Some(tx), result.traces))
             })
@@ -299,10 +317,17 @@ impl ScriptArgs {
             .gas_limit(script_config.evm_opts.gas_limit());
 
         if let SimulationStage::Local = stage {
+            let precompiles = ChainPrecompiles::from_names(&script_config.evm_opts.precompiles)?;
+            let gas_schedule = GasSchedule::new(&script_config.evm_opts.gas_overrides)?;
             builder = builder.inspectors(|stack| {
-                stack.debug(self.debug).cheatcodes(
-                    CheatsConfig::new(&script_config.config, script_config.evm_opts.clone()).into(),
-                )
+                stack
+                    .debug(self.debug)
+                    .cheatcodes(
+                        CheatsConfig::new(&script_config.config, script_config.evm_opts.clone())
+                            .into(),
+                    )
+                    .precompiles(precompiles)
+                    .gas_schedule(gas_schedule)
             });
         }
 
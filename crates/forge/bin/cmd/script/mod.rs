@@ -1,4 +1,4 @@
-use self::{build::BuildOutput, runner::ScriptRunner};
+use self::{build::BuildOutput, proposal::ProposalKind, runner::ScriptRunner};
 use super::{build::BuildArgs, retry::RetryArgs};
 use alloy_dyn_abi::FunctionExt;
 use alloy_json_abi::{Function, InternalType, JsonAbi};
@@ -27,7 +27,9 @@ use foundry_common::{
     evm::{Breakpoints, EvmArgs},
     fmt::{format_token, format_token_raw},
     provider::ethers::RpcUrl,
-    shell, ContractsByArtifact, CONTRACT_MAX_SIZE, SELECTOR_LEN,
+    shell,
+    user_op::ENTRY_POINT_V06,
+    ContractsByArtifact, CONTRACT_MAX_SIZE, SELECTOR_LEN,
 };
 use foundry_compilers::{
     artifacts::{ContractBytecodeSome, Libraries},
@@ -43,7 +45,6 @@ use foundry_config::{
     Config, NamedChain,
 };
 use foundry_evm::{
-    constants::DEFAULT_CREATE2_DEPLOYER,
     decode,
     inspectors::cheatcodes::{BroadcastableTransaction, BroadcastableTransactions},
 };
@@ -57,13 +58,18 @@ mod artifacts;
 mod broadcast;
 mod build;
 mod cmd;
+mod deployments;
 mod executor;
 mod multi;
+mod proposal;
 mod providers;
 mod receipts;
+mod report;
 mod runner;
+mod safe;
 mod sequence;
 pub mod transaction;
+mod user_op;
 mod verify;
 
 // Loads project's figment and merges the build cli arguments into it
@@ -109,10 +115,49 @@ pub struct ScriptArgs {
     #[clap(long)]
     pub broadcast: bool,
 
+    /// Batches the transactions into a Safe Transaction Builder file instead of broadcasting
+    /// them, to be proposed to the owners of the Safe at the given address.
+    ///
+    /// All transactions must be calls broadcasted from the Safe.
+    #[clap(long, value_name = "ADDRESS", conflicts_with_all = &["broadcast", "resume"])]
+    pub safe: Option<Address>,
+
+    /// Exports the transactions as the calldata of an OpenZeppelin Governor proposal or
+    /// TimelockController operation instead of broadcasting them.
+    ///
+    /// All transactions must be calls broadcasted from the account executing the proposal.
+    #[clap(
+        long,
+        value_enum,
+        value_name = "KIND",
+        conflicts_with_all = &["broadcast", "resume", "safe"]
+    )]
+    pub proposal: Option<ProposalKind>,
+
+    /// The description of the proposal, whose hash is also the salt of timelock operations.
+    #[clap(long, requires = "proposal", value_name = "DESCRIPTION", default_value = "")]
+    pub proposal_description: String,
+
+    /// The delay of timelock operations, in seconds.
+    #[clap(long, requires = "proposal", value_name = "SECONDS", default_value = "0")]
+    pub timelock_delay: u64,
+
     /// Skips on-chain simulation.
     #[clap(long)]
     pub skip_simulation: bool,
 
+    /// Reports the balance changes, storage changes, contract creations and gas cost of the
+    /// transactions from the on-chain simulation, before broadcasting them.
+    ///
+    /// Storage changes are only decoded for contracts compiled with
+    /// `extra_output = ["storageLayout"]`.
+    #[clap(long, conflicts_with_all = &["skip_simulation", "resume"])]
+    pub report: bool,
+
+    /// The price of the chain's native currency in USD, to report the gas cost in USD.
+    #[clap(long, requires = "report", value_name = "PRICE")]
+    pub usd_price: Option<f64>,
+
     /// Relative percentage to multiply gas estimates by.
     #[clap(long, short, default_value = "130")]
     pub gas_estimate_multiplier: u64,
@@ -134,6 +179,27 @@ pub struct ScriptArgs {
     #[clap(long)]
     pub resume: bool,
 
+    /// Replaces the transactions that are still pending when resuming with ones paying fees
+    /// bumped by the given percentage, instead of waiting for them.
+    ///
+    /// Most nodes only accept replacements paying at least 10% higher fees.
+    #[clap(long, requires = "resume", value_name = "PERCENT")]
+    pub bump_fee_percent: Option<u64>,
+
+    /// The RPC endpoint of the bundler the user operations sent with `vm.broadcastUserOp` are
+    /// submitted to.
+    #[clap(long, env = "BUNDLER_URL", value_name = "URL")]
+    pub bundler_url: Option<String>,
+
+    /// The EntryPoint contract handling the user operations sent with `vm.broadcastUserOp`.
+    #[clap(long, default_value = ENTRY_POINT_V06, value_name = "ADDRESS")]
+    pub entry_point: Address,
+
+    /// Saves the contracts deployed by the broadcasted transactions to
+    /// `<deployments>/<chain>/<ContractName>.json`, to be read back with `vm.getDeployment`.
+    #[clap(long)]
+    pub save_deployments: bool,
+
     /// If present, --resume or --verify will be assumed to be a multi chain deployment.
     #[clap(long)]
     pub multi: bool,
@@ -419,6 +485,8 @@ impl ScriptArgs {
                     nonce: Some(U64::from(nonce + i as u64)),
                     ..Default::default()
                 },
+                tx_type: None,
+                user_op_owner: None,
             })
             .collect()
     }
@@ -466,6 +534,7 @@ impl ScriptArgs {
         &self,
         result: &ScriptResult,
         known_contracts: &BTreeMap<ArtifactId, ContractBytecodeSome>,
+        create2_deployer: Address,
     ) -> Result<()> {
         // (name, &init, &deployed)[]
         let mut bytecodes: Vec<(String, &[u8], &[u8])> = vec![];
@@ -520,7 +589,7 @@ impl ScriptArgs {
 
             // Find if it's a CREATE or CREATE2. Otherwise, skip transaction.
             if let Some(to) = to {
-                if to == DEFAULT_CREATE2_DEPLOYER {
+                if to == create2_deployer {
                     // Size of the salt prefix.
                     offset = 32;
                 }
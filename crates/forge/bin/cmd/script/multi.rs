@@ -1,4 +1,5 @@
 use super::{
+    deployments::save_deployments,
     receipts,
     sequence::{sig_to_file_name, ScriptSequence, SensitiveScriptSequence, DRY_RUN_DIR},
     verify::VerifyBundle,
@@ -6,6 +7,7 @@ use super::{
 };
 use ethers_signers::LocalWallet;
 use eyre::{ContextCompat, Report, Result, WrapErr};
+use forge::secrets::redact_secrets_in_json;
 use foundry_cli::utils::now;
 use foundry_common::{fs, provider::ethers::get_http_provider};
 use foundry_compilers::{artifacts::Libraries, ArtifactId};
@@ -124,9 +126,17 @@ impl MultiChainSequence {
             foundry_compilers::utils::read_json_file(&sensitive_path)
                 .wrap_err("Multi-chain deployment sensitive details not found.")?;
 
-        sequence.deployments.iter_mut().enumerate().for_each(|(i, sequence)| {
-            sequence.fill_sensitive(&sensitive_sequence.deployments[i]);
-        });
+        for (i, deployment) in sequence.deployments.iter_mut().enumerate() {
+            deployment.fill_sensitive(&sensitive_sequence.deployments[i]);
+            (deployment.path, deployment.sensitive_path) = ScriptSequence::get_paths(
+                &config.broadcast,
+                &config.cache_path,
+                sig,
+                target,
+                deployment.chain,
+                true,
+            )?;
+        }
 
         sequence.path = path;
         sequence.sensitive_path = sensitive_path;
@@ -134,17 +144,21 @@ impl MultiChainSequence {
         Ok(sequence)
     }
 
-    /// Saves the transactions as file if it's a standalone deployment.
+    /// Saves the transactions of all chains to the multi chain files, and the transactions of
+    /// each chain to the files of that chain.
     pub fn save(&mut self) -> Result<()> {
         self.timestamp = now().as_secs();
 
+        for deployment in &mut self.deployments {
+            deployment.write()?;
+        }
+
         let sensitive_sequence: SensitiveMultiChainSequence = to_sensitive(self);
 
-        // broadcast writes
+        // broadcast writes, without the secrets which are only kept in the cache folder
         //../Contract-latest/run.json
-        let mut writer = BufWriter::new(fs::create_file(&self.path)?);
-        serde_json::to_writer_pretty(&mut writer, &self)?;
-        writer.flush()?;
+        let json = serde_json::to_string_pretty(&self)?;
+        fs::write(&self.path, redact_secrets_in_json(&json).as_bytes())?;
 
         //../Contract-[timestamp]/run.json
         let path = self.path.to_string_lossy();
@@ -202,7 +216,7 @@ impl ScriptArgs {
                     let provider = Arc::new(get_http_provider(
                         sequence.typed_transactions().first().unwrap().0.clone(),
                     ));
-                    receipts::wait_for_pending(provider, sequence).await
+                    receipts::wait_for_pending(provider, sequence, self.bump_fee_percent).await
                 })
                 .collect::<Vec<_>>();
 
@@ -227,8 +241,16 @@ impl ScriptArgs {
                 )
                 .await
             {
-                Ok(_) if self.verify => sequence.verify_contracts(config, verify.clone()).await,
-                Ok(_) => Ok(()),
+                Ok(_) => {
+                    if self.save_deployments {
+                        save_deployments(sequence, &verify.known_contracts, &config.deployments)?;
+                    }
+                    if self.verify {
+                        sequence.verify_contracts(config, verify.clone()).await
+                    } else {
+                        Ok(())
+                    }
+                }
                 Err(err) => Err(err),
             };
             results.push(result);
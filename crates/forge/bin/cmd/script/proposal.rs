@@ -0,0 +1,174 @@
+use super::sequence::ScriptSequence;
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
+use alloy_json_abi::Function;
+use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
+use clap::ValueEnum;
+use eyre::{ContextCompat, Result};
+use foundry_common::{fs, shell, types::ToAlloy};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// The contract executing the calls of a [`Proposal`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalKind {
+    /// An OpenZeppelin Governor, to which the calls are proposed with `propose`.
+    Governor,
+    /// An OpenZeppelin TimelockController, on which the calls are scheduled with `scheduleBatch`.
+    Timelock,
+}
+
+/// The calls of a script as an OpenZeppelin Governor proposal or TimelockController operation,
+/// along with the calldata to submit and execute it.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proposal {
+    pub kind: ProposalKind,
+    pub chain_id: u64,
+    /// The account sending the calls, i.e. the timelock or governor
+    pub executor: Address,
+    pub targets: Vec<Address>,
+    pub values: Vec<U256>,
+    pub calldatas: Vec<Bytes>,
+    pub description: String,
+    /// The hash of the description, which is also the salt of timelock operations
+    pub description_hash: B256,
+    /// The calldata of `propose` on the governor
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub propose: Option<Bytes>,
+    /// The calldata of `scheduleBatch` on the timelock
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Bytes>,
+    /// The calldata of `execute` on the governor, or of `executeBatch` on the timelock
+    pub execute: Bytes,
+}
+
+impl Proposal {
+    /// Creates the proposal of the sequence's transactions, which must all be calls sent from the
+    /// same account.
+    pub fn new(
+        sequence: &ScriptSequence,
+        kind: ProposalKind,
+        description: String,
+        timelock_delay: u64,
+    ) -> Result<Self> {
+        let executor = sequence
+            .transactions
+            .front()
+            .and_then(|tx| tx.typed_tx().from())
+            .wrap_err("No transactions to propose.")?
+            .to_alloy();
+
+        let (mut targets, mut values, mut calldatas) = (vec![], vec![], vec![]);
+        for (target, value, calldata) in sequence.calls(executor)? {
+            targets.push(target);
+            values.push(value);
+            calldatas.push(calldata);
+        }
+        let description_hash = keccak256(&description);
+
+        let batch = vec![
+            DynSolValue::Array(targets.iter().map(|target| (*target).into()).collect()),
+            DynSolValue::Array(values.iter().map(|value| (*value).into()).collect()),
+            DynSolValue::Array(
+                calldatas.iter().map(|calldata| DynSolValue::Bytes(calldata.to_vec())).collect(),
+            ),
+        ];
+        let encode = |signature: &str, args: &[DynSolValue]| -> Result<Bytes> {
+            let mut inputs = batch.clone();
+            inputs.extend_from_slice(args);
+            Ok(Function::parse(signature)?.abi_encode_input(&inputs)?.into())
+        };
+
+        let (propose, schedule, execute) = match kind {
+            ProposalKind::Governor => (
+                Some(encode(
+                    "propose(address[],uint256[],bytes[],string)",
+                    &[description.clone().into()],
+                )?),
+                None,
+                encode("execute(address[],uint256[],bytes[],bytes32)", &[description_hash.into()])?,
+            ),
+            ProposalKind::Timelock => {
+                let predecessor = DynSolValue::from(B256::ZERO);
+                let salt = DynSolValue::from(description_hash);
+                (
+                    None,
+                    Some(encode(
+                        "scheduleBatch(address[],uint256[],bytes[],bytes32,bytes32,uint256)",
+                        &[predecessor.clone(), salt.clone(), U256::from(timelock_delay).into()],
+                    )?),
+                    encode(
+                        "executeBatch(address[],uint256[],bytes[],bytes32,bytes32)",
+                        &[predecessor, salt],
+                    )?,
+                )
+            }
+        };
+
+        Ok(Self {
+            kind,
+            chain_id: sequence.chain,
+            executor,
+            targets,
+            values,
+            calldatas,
+            description,
+            description_hash,
+            propose,
+            schedule,
+            execute,
+        })
+    }
+
+    /// Writes the proposal next to the sequence's transactions, returning the path of the file.
+    pub fn save(&self, sequence: &ScriptSequence) -> Result<PathBuf> {
+        let file_name = sequence.path.file_name().unwrap_or_default().to_string_lossy();
+        let path =
+            sequence.path.with_file_name(file_name.replace("-latest.json", "-proposal.json"));
+        fs::write_json_file(&path, self)?;
+        shell::println(format!("\nProposal saved to: {}\n", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::script::transaction::TransactionWithMetadata;
+    use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionRequest};
+    use foundry_common::types::ToEthers;
+
+    #[test]
+    fn encodes_proposals() {
+        let timelock = Address::repeat_byte(0x71);
+        let target = Address::repeat_byte(0x11);
+        let call = TransactionRequest::new()
+            .from(timelock.to_ethers())
+            .to(target.to_ethers())
+            .data(vec![0x12, 0x34]);
+        let sequence = ScriptSequence {
+            transactions: [TransactionWithMetadata {
+                transaction: TypedTransaction::Legacy(call),
+                ..Default::default()
+            }]
+            .into(),
+            chain: 1,
+            ..Default::default()
+        };
+
+        let proposal =
+            Proposal::new(&sequence, ProposalKind::Governor, "Upgrade".into(), 0).unwrap();
+        assert_eq!(proposal.executor, timelock);
+        assert_eq!(proposal.targets, vec![target]);
+        assert_eq!(proposal.description_hash, keccak256("Upgrade"));
+        assert_eq!(proposal.propose.unwrap()[..4], [0x7d, 0x5e, 0x81, 0xe2]);
+        assert_eq!(proposal.execute[..4], [0x26, 0x56, 0x22, 0x7d]);
+
+        let proposal =
+            Proposal::new(&sequence, ProposalKind::Timelock, "Upgrade".into(), 86400).unwrap();
+        assert!(proposal.propose.is_none());
+        assert_eq!(proposal.schedule.unwrap()[..4], [0x8f, 0x2a, 0x0b, 0xb0]);
+        assert_eq!(proposal.execute[..4], [0xe3, 0x83, 0x35, 0xe5]);
+    }
+}
@@ -1,6 +1,8 @@
 use super::sequence::ScriptSequence;
 use alloy_primitives::TxHash;
-use ethers_core::types::TransactionReceipt;
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, Transaction, TransactionReceipt, H160, U256,
+};
 use ethers_providers::{Middleware, PendingTransaction};
 use eyre::Result;
 use foundry_cli::{init_progress, update_progress, utils::print_receipt};
@@ -9,7 +11,7 @@ use foundry_common::{
     types::{ToAlloy, ToEthers},
 };
 use futures::StreamExt;
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 /// Convenience enum for internal signalling of transaction status
 enum TxStatus {
@@ -31,17 +33,116 @@ impl From<TransactionReceipt> for TxStatus {
 
 /// Gets the receipts of previously pending transactions, or removes them from
 /// the deploy sequence's pending vector
+///
+/// If `bump_fee_percent` is set, transactions still in the mempool are replaced instead of waited
+/// for, see [`remove_unconfirmed`].
 pub async fn wait_for_pending(
     provider: Arc<RetryProvider>,
     deployment_sequence: &mut ScriptSequence,
+    bump_fee_percent: Option<u64>,
 ) -> Result<()> {
     if deployment_sequence.pending.is_empty() {
         return Ok(());
     }
     println!("##\nChecking previously pending transactions.");
+    remove_unconfirmed(&provider, deployment_sequence, bump_fee_percent).await?;
+    if deployment_sequence.pending.is_empty() {
+        return Ok(());
+    }
     clear_pendings(provider, deployment_sequence, None).await
 }
 
+/// Removes the pending transactions that have to be broadcasted again from the deploy sequence's
+/// pending vector: the ones dropped from the mempool and, if `bump_fee_percent` is set, the ones
+/// still in it, which are replaced by transactions with the same nonce and bumped fees.
+///
+/// A transaction in the mempool can't be included before a dropped transaction of its sender with
+/// a lower nonce is broadcasted again, so this fails on it if it isn't replaced.
+async fn remove_unconfirmed(
+    provider: &RetryProvider,
+    deployment_sequence: &mut ScriptSequence,
+    bump_fee_percent: Option<u64>,
+) -> Result<()> {
+    let mut in_mempool = vec![];
+    let mut dropped_nonces = HashMap::<H160, U256>::new();
+    for tx_hash in deployment_sequence.pending.clone() {
+        if provider.get_transaction_receipt(tx_hash.to_ethers()).await?.is_some() {
+            continue
+        }
+        match provider.get_transaction(tx_hash.to_ethers()).await? {
+            Some(tx) if tx.block_hash.is_none() => in_mempool.push(tx),
+            Some(_) => {}
+            None => {
+                let dropped = deployment_sequence
+                    .transactions
+                    .iter()
+                    .find(|tx| tx.hash == Some(tx_hash))
+                    .map(|tx| tx.typed_tx());
+                if let Some((from, nonce)) =
+                    dropped.and_then(|tx| Some((*tx.from()?, *tx.nonce()?)))
+                {
+                    let lowest = dropped_nonces.entry(from).or_insert(nonce);
+                    *lowest = nonce.min(*lowest);
+                }
+                println!("Transaction {tx_hash:?} was dropped from the mempool and will be rebroadcasted.");
+                deployment_sequence.remove_pending(tx_hash);
+            }
+        }
+    }
+
+    for pending in in_mempool {
+        let tx_hash = pending.hash.to_alloy();
+        let Some(percent) = bump_fee_percent else {
+            if let Some(nonce) = dropped_nonces.get(&pending.from).filter(|n| **n < pending.nonce) {
+                eyre::bail!(
+                    "Pending transaction {tx_hash:?} can't be included before the dropped transaction with nonce {nonce} is rebroadcasted. Add `--bump-fee-percent` to your command to replace it."
+                )
+            }
+            continue
+        };
+        let Some(tx) = deployment_sequence.transactions.iter_mut().find(|tx| {
+            let tx = tx.typed_tx();
+            tx.from() == Some(&pending.from) && tx.nonce() == Some(&pending.nonce)
+        }) else {
+            continue
+        };
+        bump_fees(tx.typed_tx_mut(), &pending, percent);
+        tx.is_fee_bumped = true;
+        println!(
+            "Replacing pending transaction {tx_hash:?} with one paying {percent}% higher fees."
+        );
+        deployment_sequence.remove_pending(tx_hash);
+    }
+    Ok(())
+}
+
+/// Sets the fees of the `pending` transaction, bumped by `percent`, on the transaction replacing
+/// it.
+fn bump_fees(tx: &mut TypedTransaction, pending: &Transaction, percent: u64) {
+    let bump = |fee: U256| fee * (100 + percent) / 100;
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            inner.max_fee_per_gas = pending.max_fee_per_gas.map(bump);
+            inner.max_priority_fee_per_gas = pending.max_priority_fee_per_gas.map(bump);
+        }
+        tx => {
+            if let Some(gas_price) = pending.gas_price {
+                tx.set_gas_price(bump(gas_price));
+            }
+        }
+    }
+}
+
+/// Returns the fee to send a transaction with: the fee set on the command line, else the
+/// estimated fee, or the fee bumped to replace a pending transaction if it's higher.
+pub fn resolve_fee(explicit: Option<U256>, bumped: Option<U256>, estimate: U256) -> U256 {
+    match (explicit, bumped) {
+        (Some(fee), _) => fee,
+        (None, Some(bumped)) => bumped.max(estimate),
+        (None, None) => estimate,
+    }
+}
+
 /// Traverses a set of pendings and either finds receipts, or clears them from
 /// the deployment sequence.
 ///
@@ -153,3 +254,41 @@ async fn check_tx_status(
 
     (hash, result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+
+    #[test]
+    fn bumps_fees() {
+        let pending = Transaction {
+            gas_price: Some(100.into()),
+            max_fee_per_gas: Some(200.into()),
+            max_priority_fee_per_gas: Some(10.into()),
+            ..Default::default()
+        };
+
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new());
+        bump_fees(&mut tx, &pending, 15);
+        assert_eq!(tx.gas_price(), Some(115.into()));
+
+        let mut tx = TypedTransaction::Eip1559(Eip1559TransactionRequest::new());
+        bump_fees(&mut tx, &pending, 15);
+        let TypedTransaction::Eip1559(inner) = tx else { unreachable!() };
+        assert_eq!(inner.max_fee_per_gas, Some(230.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(11.into()));
+    }
+
+    #[test]
+    fn resolves_fees() {
+        let estimate = U256::from(20);
+        assert_eq!(resolve_fee(None, None, estimate), estimate);
+        // bumped fees are kept if they're higher than the estimate
+        assert_eq!(resolve_fee(None, Some(30.into()), estimate), 30.into());
+        assert_eq!(resolve_fee(None, Some(10.into()), estimate), estimate);
+        // explicit fees always win
+        assert_eq!(resolve_fee(Some(5.into()), Some(30.into()), estimate), 5.into());
+        assert_eq!(resolve_fee(Some(5.into()), None, estimate), 5.into());
+    }
+}
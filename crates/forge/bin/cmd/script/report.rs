@@ -0,0 +1,329 @@
+use super::sequence::ScriptSequence;
+use alloy_primitives::{utils::format_units, Address, Bytes, B256, U256};
+use eyre::Result;
+use foundry_common::{fs, shell, types::ToAlloy};
+use foundry_evm::inspectors::cheatcodes::{ArtifactLayout, LayoutDecoder};
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt, path::PathBuf};
+
+/// The changes made to an account by one or more transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccountDiff {
+    /// The balance before and after the transactions
+    pub balance: (U256, U256),
+    /// The storage slots written to, `slot => (previous, new)`
+    pub storage: BTreeMap<U256, (U256, U256)>,
+    /// Whether the account was created by the transactions
+    pub created: bool,
+    /// The runtime bytecode of the account after the transactions, if it was loaded
+    pub code: Option<Bytes>,
+}
+
+/// The changes made to the accounts touched by one or more transactions.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StateDiff(pub BTreeMap<Address, AccountDiff>);
+
+impl StateDiff {
+    /// Merges the changes of a transaction executed after the ones of this diff.
+    pub fn extend(&mut self, later: &StateDiff) {
+        for (address, diff) in &later.0 {
+            let Some(account) = self.0.get_mut(address) else {
+                self.0.insert(*address, diff.clone());
+                continue
+            };
+            account.balance.1 = diff.balance.1;
+            for (slot, (previous, new)) in &diff.storage {
+                account.storage.entry(*slot).or_insert((*previous, *new)).1 = *new;
+            }
+            account.created |= diff.created;
+            if diff.code.is_some() {
+                account.code = diff.code.clone();
+            }
+        }
+    }
+}
+
+/// The outcome of the on-chain simulation of a sequence's transactions, printed with
+/// `forge script --report` before they're broadcasted.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulationReport {
+    pub chain_id: u64,
+    pub balance_changes: Vec<BalanceChange>,
+    pub storage_changes: Vec<StorageChange>,
+    pub creations: Vec<Creation>,
+    /// The sum of the gas limits of the transactions
+    pub gas: U256,
+    pub gas_price: U256,
+    /// The cost of the gas, in wei
+    pub gas_cost: U256,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub account: Address,
+    pub previous_balance: U256,
+    pub new_balance: U256,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageChange {
+    pub account: Address,
+    /// The identifier of the account's contract, if its storage layout is known
+    pub contract_name: Option<String>,
+    pub slot: B256,
+    /// The variable stored in the slot, if it could be decoded
+    pub label: Option<String>,
+    pub type_name: Option<String>,
+    pub previous_value: B256,
+    pub new_value: B256,
+    pub decoded_previous_value: Option<String>,
+    pub decoded_new_value: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Creation {
+    pub address: Address,
+    pub contract_name: Option<String>,
+}
+
+impl SimulationReport {
+    /// Creates the report of the state changes recorded for the sequence's transactions,
+    /// decoding the changed storage slots with the given layouts.
+    pub fn new(
+        sequence: &ScriptSequence,
+        layouts: &[ArtifactLayout],
+        gas_price: U256,
+        usd_price: Option<f64>,
+    ) -> Self {
+        let mut diff = StateDiff::default();
+        let mut gas = U256::ZERO;
+        for tx in &sequence.transactions {
+            if let Some(tx_diff) = &tx.state_diff {
+                diff.extend(tx_diff);
+            }
+            gas += tx.typed_tx().gas().map(|gas| gas.to_alloy()).unwrap_or_default();
+        }
+
+        let mut report = Self { chain_id: sequence.chain, gas, gas_price, ..Default::default() };
+        for (account, account_diff) in diff.0 {
+            let (previous_balance, new_balance) = account_diff.balance;
+            if previous_balance != new_balance {
+                report.balance_changes.push(BalanceChange {
+                    account,
+                    previous_balance,
+                    new_balance,
+                });
+            }
+
+            let artifact = account_diff
+                .code
+                .as_ref()
+                .and_then(|code| layouts.iter().find(|layout| layout.matches(code)));
+            if account_diff.created {
+                let contract_name = sequence
+                    .transactions
+                    .iter()
+                    .find(|tx| tx.contract_address == Some(account))
+                    .and_then(|tx| tx.contract_name.clone())
+                    .filter(|name| !name.is_empty())
+                    .or_else(|| artifact.map(|artifact| artifact.name.clone()));
+                report.creations.push(Creation { address: account, contract_name });
+            }
+
+            for (slot, (previous, new)) in account_diff.storage {
+                if previous == new {
+                    continue
+                }
+                let (slot_word, previous, new) = (
+                    B256::from(slot.to_be_bytes()),
+                    B256::from(previous.to_be_bytes()),
+                    B256::from(new.to_be_bytes()),
+                );
+                let change = |label, type_name, decoded_previous, decoded_new| StorageChange {
+                    account,
+                    contract_name: artifact.map(|artifact| artifact.name.clone()),
+                    slot: slot_word,
+                    label,
+                    type_name,
+                    previous_value: previous,
+                    new_value: new,
+                    decoded_previous_value: decoded_previous,
+                    decoded_new_value: decoded_new,
+                };
+
+                let vars = artifact
+                    .map(|artifact| LayoutDecoder::new(&artifact.layout, None).variables(slot))
+                    .unwrap_or_default();
+                let len = report.storage_changes.len();
+                for var in vars {
+                    let (decoded_previous, decoded_new) = (var.format(previous), var.format(new));
+                    // Only the variables packed into the slot which changed are reported
+                    if decoded_previous != decoded_new {
+                        report.storage_changes.push(change(
+                            Some(var.label),
+                            Some(var.type_name),
+                            Some(decoded_previous),
+                            Some(decoded_new),
+                        ));
+                    }
+                }
+                if report.storage_changes.len() == len {
+                    report.storage_changes.push(change(None, None, None, None));
+                }
+            }
+        }
+
+        report.gas_cost = report.gas * gas_price;
+        report.gas_cost_usd = usd_price.and_then(|price| {
+            Some(format_units(report.gas_cost, 18).ok()?.parse::<f64>().ok()? * price)
+        });
+        report
+    }
+
+    /// Writes the report next to the sequence's transactions, returning the path of the file.
+    pub fn save(&self, sequence: &ScriptSequence) -> Result<PathBuf> {
+        let file_name = sequence.path.file_name().unwrap_or_default().to_string_lossy();
+        let path = sequence.path.with_file_name(file_name.replace("-latest.json", "-report.json"));
+        fs::write_json_file(&path, self)?;
+        shell::println(format!("\nSimulation report saved to: {}\n", path.display()))?;
+        Ok(path)
+    }
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "==========================")?;
+        writeln!(f, "\nSimulation report for chain {}", self.chain_id)?;
+
+        if !self.balance_changes.is_empty() {
+            writeln!(f, "\nBalance changes:")?;
+            for change in &self.balance_changes {
+                writeln!(
+                    f,
+                    "  {}: {} ETH -> {} ETH",
+                    change.account,
+                    format_ether(change.previous_balance),
+                    format_ether(change.new_balance)
+                )?;
+            }
+        }
+
+        if !self.storage_changes.is_empty() {
+            writeln!(f, "\nStorage changes:")?;
+            let mut account = None;
+            for change in &self.storage_changes {
+                if account != Some(change.account) {
+                    account = Some(change.account);
+                    match &change.contract_name {
+                        Some(name) => writeln!(f, "  {} ({name}):", change.account)?,
+                        None => writeln!(f, "  {}:", change.account)?,
+                    }
+                }
+                match (&change.label, &change.decoded_previous_value, &change.decoded_new_value) {
+                    (Some(label), Some(previous), Some(new)) => writeln!(
+                        f,
+                        "    {label} ({}): {previous} -> {new}",
+                        change.type_name.as_deref().unwrap_or_default()
+                    )?,
+                    _ => writeln!(
+                        f,
+                        "    slot {}: {} -> {}",
+                        change.slot, change.previous_value, change.new_value
+                    )?,
+                }
+            }
+        }
+
+        if !self.creations.is_empty() {
+            writeln!(f, "\nContract creations:")?;
+            for creation in &self.creations {
+                match &creation.contract_name {
+                    Some(name) => writeln!(f, "  {}: {name}", creation.address)?,
+                    None => writeln!(f, "  {}", creation.address)?,
+                }
+            }
+        }
+
+        write!(
+            f,
+            "\nEstimated gas cost: {} ETH ({} gas at {} gwei)",
+            format_ether(self.gas_cost),
+            self.gas,
+            format_units(self.gas_price, 9)
+                .unwrap_or_default()
+                .trim_end_matches('0')
+                .trim_end_matches('.')
+        )?;
+        if let Some(usd) = self.gas_cost_usd {
+            write!(f, " ~ ${usd:.2}")?;
+        }
+        writeln!(f)
+    }
+}
+
+fn format_ether(value: U256) -> String {
+    let ether = format_units(value, 18).unwrap_or_default();
+    ether.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::script::transaction::TransactionWithMetadata;
+
+    #[test]
+    fn merges_state_diffs() {
+        let account = Address::repeat_byte(0x11);
+        let diff = |balance: (u64, u64), storage: (u64, u64)| {
+            StateDiff(BTreeMap::from([(
+                account,
+                AccountDiff {
+                    balance: (U256::from(balance.0), U256::from(balance.1)),
+                    storage: BTreeMap::from([(
+                        U256::ZERO,
+                        (U256::from(storage.0), U256::from(storage.1)),
+                    )]),
+                    ..Default::default()
+                },
+            )]))
+        };
+
+        let mut merged = diff((10, 7), (0, 1));
+        merged.extend(&diff((7, 10), (1, 0)));
+        let account_diff = &merged.0[&account];
+        assert_eq!(account_diff.balance, (U256::from(10), U256::from(10)));
+        assert_eq!(account_diff.storage[&U256::ZERO], (U256::ZERO, U256::ZERO));
+
+        let sequence = ScriptSequence {
+            transactions: [TransactionWithMetadata {
+                state_diff: Some(merged),
+                ..Default::default()
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let report = SimulationReport::new(&sequence, &[], U256::from(1), None);
+        assert!(report.balance_changes.is_empty());
+        assert!(report.storage_changes.is_empty());
+
+        let sequence = ScriptSequence {
+            transactions: [TransactionWithMetadata {
+                state_diff: Some(diff((10, 7), (0, 1))),
+                ..Default::default()
+            }]
+            .into(),
+            ..Default::default()
+        };
+        let report = SimulationReport::new(&sequence, &[], U256::from(1), None);
+        assert_eq!(report.balance_changes.len(), 1);
+        assert_eq!(report.storage_changes.len(), 1);
+        assert_eq!(report.storage_changes[0].label, None);
+        assert_eq!(report.storage_changes[0].new_value, B256::with_last_byte(1));
+    }
+}
@@ -1,10 +1,16 @@
-use super::*;
+use super::{
+    report::{AccountDiff, StateDiff},
+    *,
+};
 use alloy_primitives::{Address, Bytes, U256};
 use eyre::Result;
 use forge::{
     constants::CALLER,
     executors::{CallResult, DeployResult, EvmError, ExecutionErr, Executor, RawCallResult},
-    revm::interpreter::{return_ok, InstructionResult},
+    revm::{
+        interpreter::{return_ok, InstructionResult},
+        primitives::{CreateScheme, TransactTo},
+    },
     traces::{TraceKind, Traces},
 };
 use foundry_common::types::ToEthers;
@@ -250,6 +256,48 @@ impl ScriptRunner {
         }
     }
 
+    /// Runs a broadcastable transaction locally without persisting its state, and returns the
+    /// changes it makes to the accounts it touches.
+    pub fn state_diff(
+        &mut self,
+        from: Address,
+        to: Option<Address>,
+        calldata: Option<Bytes>,
+        value: Option<U256>,
+    ) -> Result<StateDiff> {
+        let transact_to = to.map_or(TransactTo::Create(CreateScheme::Create), TransactTo::Call);
+        let env = self.executor.build_test_env(
+            from,
+            transact_to,
+            calldata.unwrap_or_default(),
+            value.unwrap_or(U256::ZERO),
+        );
+        let res = self.executor.call_raw_with_env(env)?;
+
+        let mut diff = StateDiff::default();
+        if res.reverted {
+            return Ok(diff)
+        }
+        for (address, account) in res.state_changeset.unwrap_or_default() {
+            let storage = account
+                .storage
+                .iter()
+                .filter(|(_, slot)| slot.is_changed())
+                .map(|(index, slot)| (*index, (slot.original_value(), slot.present_value())))
+                .collect();
+            diff.0.insert(
+                address,
+                AccountDiff {
+                    balance: (self.executor.get_balance(address)?, account.info.balance),
+                    storage,
+                    created: account.is_created(),
+                    code: account.info.code.as_ref().map(|code| code.original_bytes()),
+                },
+            );
+        }
+        Ok(diff)
+    }
+
     /// Executes the call
     ///
     /// This will commit the changes if `commit` is true.
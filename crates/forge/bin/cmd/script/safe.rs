@@ -0,0 +1,130 @@
+use super::sequence::ScriptSequence;
+use alloy_primitives::{Address, Bytes};
+use eyre::Result;
+use foundry_cli::utils::now;
+use foundry_common::{fs, shell};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// The version of the Safe Transaction Builder file format.
+const BATCH_FILE_VERSION: &str = "1.0";
+
+/// A batch of transactions in the format of the Safe Transaction Builder, which can be imported
+/// into the Safe app to be proposed, signed and executed by a Safe's owners.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatch {
+    pub version: &'static str,
+    pub chain_id: String,
+    /// Milliseconds since the unix epoch
+    pub created_at: u128,
+    pub meta: SafeBatchMeta,
+    pub transactions: Vec<SafeTransaction>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeBatchMeta {
+    pub name: String,
+    pub description: String,
+    pub created_from_safe_address: Address,
+}
+
+/// A single call of a [`SafeBatch`].
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SafeTransaction {
+    pub to: Address,
+    /// The value in wei, as a decimal string
+    pub value: String,
+    pub data: Bytes,
+    pub contract_method: Option<()>,
+    pub contract_inputs_values: Option<()>,
+}
+
+impl SafeBatch {
+    /// Batches the transactions of the sequence, which must all be calls sent from `safe`.
+    pub fn new(sequence: &ScriptSequence, safe: Address, name: String) -> Result<Self> {
+        let transactions = sequence
+            .calls(safe)?
+            .into_iter()
+            .map(|(to, value, data)| SafeTransaction {
+                to,
+                value: value.to_string(),
+                data,
+                contract_method: None,
+                contract_inputs_values: None,
+            })
+            .collect();
+
+        Ok(Self {
+            version: BATCH_FILE_VERSION,
+            chain_id: sequence.chain.to_string(),
+            created_at: now().as_millis(),
+            meta: SafeBatchMeta {
+                name,
+                description: String::new(),
+                created_from_safe_address: safe,
+            },
+            transactions,
+        })
+    }
+
+    /// Writes the batch next to the sequence's transactions, returning the path of the file.
+    pub fn save(&self, sequence: &ScriptSequence) -> Result<PathBuf> {
+        let file_name = sequence.path.file_name().unwrap_or_default().to_string_lossy();
+        let path = sequence.path.with_file_name(file_name.replace("-latest.json", "-safe.json"));
+        fs::write_json_file(&path, self)?;
+        shell::println(format!("\nSafe transaction batch saved to: {}\n", path.display()))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::script::transaction::TransactionWithMetadata;
+    use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionRequest};
+    use foundry_common::types::ToEthers;
+
+    fn sequence(txs: Vec<TransactionRequest>) -> ScriptSequence {
+        ScriptSequence {
+            transactions: txs
+                .into_iter()
+                .map(|tx| TransactionWithMetadata {
+                    transaction: TypedTransaction::Legacy(tx),
+                    ..Default::default()
+                })
+                .collect(),
+            chain: 5,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn batches_safe_calls() {
+        let safe = Address::repeat_byte(0x5a);
+        let target = Address::repeat_byte(0x11);
+        let call = TransactionRequest::new()
+            .from(safe.to_ethers())
+            .to(target.to_ethers())
+            .value(7)
+            .data(vec![0x12, 0x34]);
+
+        let batch = SafeBatch::new(&sequence(vec![call.clone()]), safe, "Deploy".into()).unwrap();
+        assert_eq!(batch.chain_id, "5");
+        assert_eq!(batch.transactions.len(), 1);
+        assert_eq!(batch.transactions[0].to, target);
+        assert_eq!(batch.transactions[0].value, "7");
+        assert_eq!(batch.transactions[0].data, Bytes::from_static(&[0x12, 0x34]));
+
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!(json["meta"]["createdFromSafeAddress"], serde_json::json!(safe));
+        assert_eq!(json["transactions"][0]["contractMethod"], serde_json::Value::Null);
+
+        let other = call.clone().from(target.to_ethers());
+        assert!(SafeBatch::new(&sequence(vec![other]), safe, "Deploy".into()).is_err());
+        let create = TransactionRequest::new().from(safe.to_ethers());
+        assert!(SafeBatch::new(&sequence(vec![create]), safe, "Deploy".into()).is_err());
+    }
+}
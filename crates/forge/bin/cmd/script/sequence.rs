@@ -3,13 +3,17 @@ use crate::cmd::{
     init::get_commit_hash,
     script::{
         transaction::{wrapper, AdditionalContract, TransactionWithMetadata},
+        user_op::ScriptUserOperation,
         verify::VerifyBundle,
     },
     verify::provider::VerificationProviderType,
 };
-use alloy_primitives::{Address, TxHash};
-use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionReceipt};
+use alloy_primitives::{Address, Bytes, TxHash, U256};
+use ethers_core::types::{
+    transaction::eip2718::TypedTransaction, NameOrAddress, TransactionReceipt,
+};
 use eyre::{ContextCompat, Result, WrapErr};
+use forge::secrets::redact_secrets_in_json;
 use foundry_cli::utils::now;
 use foundry_common::{
     fs, shell,
@@ -18,8 +22,10 @@ use foundry_common::{
 };
 use foundry_compilers::{artifacts::Libraries, ArtifactId};
 use foundry_config::Config;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::{
+    borrow::Cow,
     collections::{HashMap, VecDeque},
     io::{BufWriter, Write},
     path::{Path, PathBuf},
@@ -28,6 +34,9 @@ use yansi::Paint;
 
 pub const DRY_RUN_DIR: &str = "dry-run";
 
+/// The number of contracts submitted to Blockscout for verification at once.
+const BLOCKSCOUT_BATCH_SIZE: usize = 10;
+
 /// Helper that saves the transactions sequence and its state on which transactions have been
 /// broadcasted
 #[derive(Clone, Default, Serialize, Deserialize)]
@@ -44,9 +53,14 @@ pub struct ScriptSequence {
     pub returns: HashMap<String, NestedValue>,
     pub timestamp: u64,
     pub chain: u64,
-    /// If `True`, the sequence belongs to a `MultiChainSequence` and won't save to disk as usual.
+    /// If `True`, the sequence belongs to a `MultiChainSequence`, which saves it to disk along
+    /// with the sequences of the other chains.
     pub multi: bool,
     pub commit: Option<String>,
+    /// The user operations sent through smart accounts with `vm.broadcastUserOp`, submitted to
+    /// the bundler once the transactions of the sequence are sent
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_operations: Vec<ScriptUserOperation>,
 }
 
 /// Sensitive values from the transactions in a script sequence
@@ -59,16 +73,25 @@ pub struct SensitiveTransactionMetadata {
 #[derive(Clone, Default, Serialize, Deserialize)]
 pub struct SensitiveScriptSequence {
     pub transactions: VecDeque<SensitiveTransactionMetadata>,
+    /// The sequence before the secrets resolved with `vm.getSecret` were redacted from it, if it
+    /// contains any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unredacted: Option<serde_json::Value>,
 }
 
 impl From<&mut ScriptSequence> for SensitiveScriptSequence {
     fn from(sequence: &mut ScriptSequence) -> Self {
+        let unredacted = serde_json::to_string(&sequence)
+            .ok()
+            .filter(|json| matches!(redact_secrets_in_json(json), Cow::Owned(_)))
+            .and_then(|json| serde_json::from_str(&json).ok());
         SensitiveScriptSequence {
             transactions: sequence
                 .transactions
                 .iter()
                 .map(|tx| SensitiveTransactionMetadata { rpc: tx.rpc.clone() })
                 .collect(),
+            unredacted,
         }
     }
 }
@@ -91,7 +114,7 @@ impl ScriptSequence {
             sig,
             target,
             chain,
-            broadcasted && !is_multi,
+            broadcasted,
         )?;
 
         let commit = get_commit_hash(&config.__root.0);
@@ -108,6 +131,7 @@ impl ScriptSequence {
             chain,
             multi: is_multi,
             commit,
+            user_operations: vec![],
         })
     }
 
@@ -146,7 +170,15 @@ impl ScriptSequence {
 
     /// Saves the transactions as file if it's a standalone deployment.
     pub fn save(&mut self) -> Result<()> {
-        if self.multi || self.transactions.is_empty() {
+        if self.multi {
+            return Ok(())
+        }
+        self.write()
+    }
+
+    /// Writes the transactions to the files of the sequence's chain.
+    pub fn write(&mut self) -> Result<()> {
+        if self.transactions.is_empty() {
             return Ok(())
         }
 
@@ -155,11 +187,10 @@ impl ScriptSequence {
 
         let sensitive_script_sequence: SensitiveScriptSequence = self.into();
 
-        // broadcast folder writes
+        // broadcast folder writes, without the secrets which are only kept in the cache folder
         //../run-latest.json
-        let mut writer = BufWriter::new(fs::create_file(&self.path)?);
-        serde_json::to_writer_pretty(&mut writer, &self)?;
-        writer.flush()?;
+        let json = serde_json::to_string_pretty(&self)?;
+        fs::write(&self.path, redact_secrets_in_json(&json).as_bytes())?;
         //../run-[timestamp].json
         fs::copy(&self.path, self.path.with_file_name(&ts_name))?;
 
@@ -177,6 +208,34 @@ impl ScriptSequence {
         Ok(())
     }
 
+    /// Returns the target, value and calldata of the transactions, which must all be calls sent
+    /// from `sender`, to be batched into a single transaction executed by it.
+    pub fn calls(&self, sender: Address) -> Result<Vec<(Address, U256, Bytes)>> {
+        self.transactions
+            .iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                let tx = tx.typed_tx();
+                let from = tx.from().copied().unwrap_or_default().to_alloy();
+                if from != sender {
+                    eyre::bail!(
+                        "Transaction {i} is sent from {from}, but only transactions of {sender} can be batched. Broadcast them with `vm.startBroadcast({sender})`."
+                    )
+                }
+                let Some(NameOrAddress::Address(to)) = tx.to() else {
+                    eyre::bail!(
+                        "Transaction {i} creates a contract, which can't be batched. Deploy it with a CREATE2 factory instead."
+                    )
+                };
+                Ok((
+                    to.to_alloy(),
+                    tx.value().copied().unwrap_or_default().to_alloy(),
+                    tx.data().cloned().unwrap_or_default().to_alloy(),
+                ))
+            })
+            .collect()
+    }
+
     pub fn add_receipt(&mut self, receipt: TransactionReceipt) {
         self.receipts.push(receipt);
     }
@@ -311,12 +370,23 @@ impl ScriptSequence {
 
             trace!(target: "script", "collected {} verification jobs and {} unverifiable contracts", future_verifications.len(), unverifiable_contracts.len());
 
+            let is_blockscout = verify.verifier.verifier == VerificationProviderType::Blockscout;
             self.check_unverified(unverifiable_contracts, verify);
 
             let num_verifications = future_verifications.len();
             println!("##\nStart verification for ({num_verifications}) contracts",);
-            for verification in future_verifications {
-                verification.await?;
+            if is_blockscout {
+                // Blockscout accepts concurrent verification requests, unlike the rate limited
+                // Etherscan API, so they're submitted in batches
+                let mut verifications = futures::stream::iter(future_verifications)
+                    .buffer_unordered(BLOCKSCOUT_BATCH_SIZE);
+                while let Some(verification) = verifications.next().await {
+                    verification?;
+                }
+            } else {
+                for verification in future_verifications {
+                    verification.await?;
+                }
             }
 
             println!("All ({num_verifications}) contracts were verified!");
@@ -364,6 +434,15 @@ impl ScriptSequence {
     }
 
     pub fn fill_sensitive(&mut self, sensitive: &SensitiveScriptSequence) {
+        // restore the values the secrets were redacted from
+        if let Some(unredacted) = &sensitive.unredacted {
+            if let Ok(mut unredacted) = serde_json::from_value::<Self>(unredacted.clone()) {
+                self.transactions = std::mem::take(&mut unredacted.transactions);
+                self.receipts = std::mem::take(&mut unredacted.receipts);
+                self.returns = std::mem::take(&mut unredacted.returns);
+            }
+        }
+
         self.transactions
             .iter_mut()
             .enumerate()
@@ -1,10 +1,10 @@
-use super::{artifacts::ArtifactInfo, ScriptResult};
+use super::{artifacts::ArtifactInfo, report::StateDiff, ScriptResult};
 use alloy_dyn_abi::JsonAbiExt;
 use alloy_primitives::{Address, Bytes, B256};
 use alloy_rpc_types::request::TransactionRequest;
 use ethers_core::types::{
-    transaction::eip2718::TypedTransaction, NameOrAddress,
-    TransactionRequest as EthersTransactionRequest,
+    transaction::{eip2718::TypedTransaction, eip2930::Eip2930TransactionRequest},
+    NameOrAddress, TransactionRequest as EthersTransactionRequest,
 };
 use eyre::{ContextCompat, Result, WrapErr};
 use foundry_common::{
@@ -13,7 +13,7 @@ use foundry_common::{
     types::{ToAlloy, ToEthers},
     SELECTOR_LEN,
 };
-use foundry_evm::{constants::DEFAULT_CREATE2_DEPLOYER, traces::CallTraceDecoder};
+use foundry_evm::traces::CallTraceDecoder;
 use itertools::Itertools;
 use revm_inspectors::tracing::types::CallKind;
 use serde::{Deserialize, Serialize};
@@ -48,6 +48,17 @@ pub struct TransactionWithMetadata {
     pub transaction: TypedTransaction,
     pub additional_contracts: Vec<AdditionalContract>,
     pub is_fixed_gas_limit: bool,
+    /// Whether the type of the transaction was set with `vm.setTxType`, and must not be changed
+    /// to match the chain.
+    #[serde(default)]
+    pub is_fixed_tx_type: bool,
+    /// Whether the fees of the transaction were bumped to replace a pending one with
+    /// `--bump-fee-percent`, and must not be lowered to the estimated fees.
+    #[serde(default)]
+    pub is_fee_bumped: bool,
+    /// The state changes of the transaction in the on-chain simulation, recorded for `--report`.
+    #[serde(skip)]
+    pub state_diff: Option<StateDiff>,
 }
 
 fn default_string() -> Option<String> {
@@ -64,18 +75,23 @@ fn default_vec_of_strings() -> Option<Vec<String>> {
 
 impl TransactionWithMetadata {
     pub fn from_tx_request(transaction: TransactionRequest) -> Self {
-        Self {
-            transaction: TypedTransaction::Legacy(EthersTransactionRequest {
-                from: transaction.from.map(ToEthers::to_ethers),
-                to: transaction.to.map(ToEthers::to_ethers).map(Into::into),
-                value: transaction.value.map(ToEthers::to_ethers),
-                data: transaction.data.map(ToEthers::to_ethers),
-                nonce: transaction.nonce.map(|n| n.to::<u64>().into()),
-                gas: transaction.gas.map(ToEthers::to_ethers),
-                ..Default::default()
-            }),
+        let tx = EthersTransactionRequest {
+            from: transaction.from.map(ToEthers::to_ethers),
+            to: transaction.to.map(ToEthers::to_ethers).map(Into::into),
+            value: transaction.value.map(ToEthers::to_ethers),
+            data: transaction.data.map(ToEthers::to_ethers),
+            nonce: transaction.nonce.map(|n| n.to::<u64>().into()),
+            gas: transaction.gas.map(ToEthers::to_ethers),
             ..Default::default()
-        }
+        };
+        let transaction = match transaction.access_list {
+            Some(access_list) => TypedTransaction::Eip2930(Eip2930TransactionRequest::new(
+                tx,
+                access_list.to_ethers(),
+            )),
+            None => TypedTransaction::Legacy(tx),
+        };
+        Self { transaction, ..Default::default() }
     }
 
     pub fn new(
@@ -86,6 +102,7 @@ impl TransactionWithMetadata {
         decoder: &CallTraceDecoder,
         additional_contracts: Vec<AdditionalContract>,
         is_fixed_gas_limit: bool,
+        create2_deployer: Address,
     ) -> Result<Self> {
         let mut metadata = Self::from_tx_request(transaction);
         metadata.rpc = rpc;
@@ -93,7 +110,7 @@ impl TransactionWithMetadata {
 
         // Specify if any contract was directly created with this transaction
         if let Some(NameOrAddress::Address(to)) = metadata.transaction.to().cloned() {
-            if to.to_alloy() == DEFAULT_CREATE2_DEPLOYER {
+            if to.to_alloy() == create2_deployer {
                 metadata.set_create(
                     true,
                     Address::from_slice(&result.returned),
@@ -238,9 +255,35 @@ impl TransactionWithMetadata {
         self.transaction = tx;
     }
 
+    /// Converts the transaction to the given EIP-2718 type, which is kept from then on.
+    pub fn set_tx_type(&mut self, tx_type: u8) {
+        let tx = self.transaction.clone();
+        self.transaction = match tx_type {
+            0 => TypedTransaction::Legacy(tx.into()),
+            1 => {
+                let access_list = tx.access_list().cloned().unwrap_or_default();
+                TypedTransaction::Eip2930(Eip2930TransactionRequest::new(tx.into(), access_list))
+            }
+            _ => TypedTransaction::Eip1559(tx.into()),
+        };
+        self.is_fixed_tx_type = true;
+    }
+
     pub fn change_type(&mut self, is_legacy: bool) {
+        if self.is_fixed_tx_type {
+            return
+        }
         self.transaction = if is_legacy {
-            TypedTransaction::Legacy(self.transaction.clone().into())
+            // Access lists can't be attached to legacy transactions
+            match self.transaction.access_list() {
+                Some(access_list) if !access_list.0.is_empty() => {
+                    TypedTransaction::Eip2930(Eip2930TransactionRequest::new(
+                        self.transaction.clone().into(),
+                        access_list.clone(),
+                    ))
+                }
+                _ => TypedTransaction::Legacy(self.transaction.clone().into()),
+            }
         } else {
             TypedTransaction::Eip1559(self.transaction.clone().into())
         };
@@ -0,0 +1,173 @@
+use super::{sequence::ScriptSequence, *};
+use alloy_primitives::B256;
+use eyre::{ContextCompat, Result};
+use foundry_common::{
+    provider::ethers::try_get_http_provider,
+    shell,
+    user_op::{encode_execute, Bundler, UserOperation, DEFAULT_EXECUTE_SIG},
+};
+use serde_json::Value;
+use std::{sync::Arc, time::Duration};
+
+/// How long to wait for a user operation to be included.
+const USER_OP_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A call made by a smart account with `vm.broadcastUserOp`, sent as an ERC-4337 user operation
+/// signed by its owner.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptUserOperation {
+    /// The smart account making the call
+    pub account: Address,
+    /// The owner of the account signing the user operation
+    pub owner: Address,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    /// The hash of the user operation, once it's submitted to the bundler
+    pub hash: Option<B256>,
+    /// The receipt of the user operation, once it's included
+    pub receipt: Option<Value>,
+}
+
+impl ScriptUserOperation {
+    fn new(tx: BroadcastableTransaction) -> Result<Self> {
+        let owner = tx.user_op_owner.wrap_err("No owner for user operation")?;
+        let tx = tx.transaction;
+        Ok(Self {
+            account: tx.from.wrap_err("No smart account for user operation")?,
+            owner,
+            to: tx.to.wrap_err("User operations can't create contracts")?,
+            value: tx.value.unwrap_or_default(),
+            data: tx.data.unwrap_or_default(),
+            hash: None,
+            receipt: None,
+        })
+    }
+}
+
+impl ScriptArgs {
+    /// Adds the user operations recorded by `vm.broadcastUserOp` to the sequences of their
+    /// chains, creating the sequence of a single chain script which sends no transactions.
+    pub async fn add_user_operations(
+        &self,
+        sequences: &mut Vec<ScriptSequence>,
+        user_ops: BroadcastableTransactions,
+        script_config: &mut ScriptConfig,
+        returns: HashMap<String, NestedValue>,
+    ) -> Result<()> {
+        let target = script_config.target_contract().clone();
+        // Config is used to initialize the sequence chain, so it's restored once they're created
+        let original_config_chain = script_config.config.chain;
+
+        for tx in user_ops {
+            let rpc = match tx.rpc.clone() {
+                Some(rpc) => rpc,
+                None => self.evm_opts.ensure_fork_url()?.clone(),
+            };
+
+            // A sequence without transactions is the one of a script only sending user operations
+            let existing = sequences.iter().position(|sequence| {
+                sequence.transactions.front().map_or(true, |tx| tx.rpc.as_ref() == Some(&rpc))
+            });
+            let index = match existing {
+                Some(index) => index,
+                None => {
+                    // The sequences of multi chain scripts are sent to the RPC of their
+                    // transactions, so only single chain ones can be made of user operations
+                    eyre::ensure!(
+                        sequences.is_empty() && !script_config.has_multiple_rpcs(),
+                        "User operations of multi chain scripts must be sent on a chain the script also sends transactions to"
+                    );
+                    let chain = try_get_http_provider(&rpc)?.get_chainid().await?.as_u64();
+                    script_config.config.chain = Some(chain.into());
+                    sequences.push(ScriptSequence::new(
+                        VecDeque::new(),
+                        returns.clone(),
+                        &self.sig,
+                        &target,
+                        &script_config.config,
+                        self.broadcast,
+                        false,
+                    )?);
+                    0
+                }
+            };
+
+            sequences[index].user_operations.push(ScriptUserOperation::new(tx)?);
+        }
+
+        script_config.config.chain = original_config_chain;
+
+        Ok(())
+    }
+
+    /// Sends the user operations of the sequence which haven't been included yet to the bundler,
+    /// signed by the owners of their accounts.
+    pub async fn send_user_operations(
+        &self,
+        sequence: &mut ScriptSequence,
+        fork_url: &str,
+        script_wallets: &[LocalWallet],
+    ) -> Result<()> {
+        let owners: HashSet<Address> = sequence
+            .user_operations
+            .iter()
+            .filter(|user_op| user_op.receipt.is_none())
+            .map(|user_op| user_op.owner)
+            .collect();
+        if owners.is_empty() {
+            return Ok(())
+        }
+
+        let bundler_url = self
+            .bundler_url
+            .as_deref()
+            .wrap_err("The script sends user operations, set the bundler with --bundler-url")?;
+        eyre::ensure!(!self.unlocked, "User operations can't be signed with --unlocked");
+
+        let bundler = Bundler::new(bundler_url, self.entry_point)?;
+        let provider = Arc::new(try_get_http_provider(fork_url)?);
+        let chain = provider.get_chainid().await?.as_u64();
+        let signers = self.wallets.find_all(provider.clone(), owners, script_wallets).await?;
+
+        shell::println("##\nSending user operations.")?;
+        for index in 0..sequence.user_operations.len() {
+            let user_op = sequence.user_operations[index].clone();
+            if user_op.receipt.is_some() {
+                continue
+            }
+
+            let hash = match user_op.hash {
+                Some(hash) => hash,
+                None => {
+                    let call_data = encode_execute(
+                        DEFAULT_EXECUTE_SIG,
+                        user_op.to,
+                        user_op.value,
+                        user_op.data.clone(),
+                    )?;
+                    let op =
+                        UserOperation::new(&provider, self.entry_point, user_op.account, call_data)
+                            .await?;
+                    let hash = bundler.send(op, &signers[&user_op.owner], chain).await?;
+
+                    // Checkpoint save, to wait for the same user operation when resuming
+                    sequence.user_operations[index].hash = Some(hash);
+                    sequence.save()?;
+                    hash
+                }
+            };
+
+            let receipt = bundler.wait_for_receipt(hash, USER_OP_TIMEOUT).await?;
+            let success = receipt.get("success").and_then(Value::as_bool).unwrap_or_default();
+            sequence.user_operations[index].receipt = Some(receipt);
+            sequence.save()?;
+
+            eyre::ensure!(success, "User operation {hash} of {} reverted", user_op.account);
+            shell::println(format!("User operation {hash} of {} included.", user_op.account))?;
+        }
+
+        Ok(())
+    }
+}
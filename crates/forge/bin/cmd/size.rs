@@ -0,0 +1,529 @@
+use super::coverage::{dummy_link_bytecode, dummy_link_deployed_bytecode};
+use clap::{Parser, ValueHint};
+use comfy_table::{presets::ASCII_MARKDOWN, Attribute, Cell, Color, Table};
+use eyre::Result;
+use foundry_cli::{opts::CoreBuildArgs, utils::LoadConfig};
+use foundry_common::{
+    compile::{ProjectCompiler, CONTRACT_INITCODE_SIZE_LIMIT, CONTRACT_SIZE_LIMIT},
+    fs, TestFunctionExt,
+};
+use foundry_compilers::{
+    artifacts::{
+        ast::{Ast, Node, NodeType},
+        contract::CompactContractBytecode,
+    },
+    Artifact,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::PathBuf,
+};
+use yansi::Paint;
+
+foundry_config::impl_figment_convert!(SizeArgs, build);
+
+/// CLI arguments for `forge size`.
+#[derive(Clone, Debug, Parser)]
+pub struct SizeArgs {
+    /// Only show the contracts with these names, with the breakdown of their runtime code.
+    #[clap(value_name = "CONTRACT")]
+    pub contracts: Vec<String>,
+
+    /// Show the breakdown of the runtime code of every contract, by the sources and functions it
+    /// was compiled from.
+    #[clap(long)]
+    pub breakdown: bool,
+
+    /// Print the sizes as JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// The file the sizes are compared against with `--fail-on-growth`.
+    #[clap(
+        long,
+        value_hint = ValueHint::FilePath,
+        value_name = "PATH",
+        default_value = ".size-baseline.json"
+    )]
+    pub baseline: PathBuf,
+
+    /// Save the sizes to the baseline file.
+    #[clap(long, conflicts_with = "fail_on_growth")]
+    pub save_baseline: bool,
+
+    /// Fail if the runtime or init code of a contract grew by more than this many bytes since the
+    /// baseline.
+    #[clap(long, value_name = "BYTES")]
+    pub fail_on_growth: Option<usize>,
+
+    #[clap(flatten)]
+    build: CoreBuildArgs,
+}
+
+impl SizeArgs {
+    pub fn run(self) -> Result<()> {
+        let config = self.try_load_config_emit_warnings()?;
+        let project = config.project()?;
+        let output = ProjectCompiler::new()
+            .quiet(self.json)
+            .compile(&project)?
+            .with_stripped_file_prefixes(project.root());
+
+        let (artifacts, sources) = output.into_artifacts_with_sources();
+        let mut paths = HashMap::new();
+        let mut functions = HashMap::new();
+        for (path, source_file, version) in sources.into_sources_with_version() {
+            if let Some(ast) = &source_file.ast {
+                functions.insert((version.clone(), source_file.id), function_ranges(ast));
+            }
+            paths.insert((version, source_file.id), path);
+        }
+
+        let mut report = SizeReport::default();
+        for (id, artifact) in artifacts {
+            if !self.contracts.is_empty() && !self.contracts.contains(&id.name) {
+                continue
+            }
+            // test and script contracts aren't deployed
+            let is_dev_contract = artifact.abi.as_ref().map_or(false, |abi| {
+                abi.functions().any(|func| {
+                    func.name.is_test() || func.name.eq("IS_TEST") || func.name.eq("IS_SCRIPT")
+                })
+            });
+            if is_dev_contract {
+                continue
+            }
+
+            let artifact = CompactContractBytecode::from(artifact);
+            let Some(deployed) = artifact.get_deployed_bytecode().map(Cow::into_owned) else {
+                continue
+            };
+            // the source and function each instruction was generated from
+            let instructions = deployed
+                .bytecode
+                .as_ref()
+                .and_then(|bytecode| bytecode.source_map())
+                .and_then(Result::ok)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|element| {
+                    element.index.map(|index| {
+                        let function = functions
+                            .get(&(id.version.clone(), index))
+                            .and_then(|ranges| function_at(ranges, element.offset));
+                        (index, function)
+                    })
+                })
+                .collect::<Vec<_>>();
+            let immutables = deployed
+                .immutable_references
+                .values()
+                .flatten()
+                .map(|offsets| offsets.length as usize)
+                .sum();
+            let Some(runtime) = dummy_link_deployed_bytecode(deployed).filter(|c| !c.is_empty())
+            else {
+                continue
+            };
+            let initcode = artifact
+                .get_bytecode()
+                .and_then(|bytecode| dummy_link_bytecode(bytecode.into_owned()))
+                .unwrap_or_default();
+
+            let metadata = metadata_len(&runtime);
+            let breakdown = runtime_breakdown(&runtime, &instructions, metadata)
+                .into_iter()
+                .map(|(part, size)| {
+                    let label = match part {
+                        CodePart::Source(index) => paths
+                            .get(&(id.version.clone(), index))
+                            .cloned()
+                            .unwrap_or_else(|| format!("<source {index}>")),
+                        CodePart::Generated => "<compiler generated>".to_string(),
+                        CodePart::Data => "<data>".to_string(),
+                        CodePart::Metadata => "<metadata>".to_string(),
+                    };
+                    (label, size)
+                })
+                .collect();
+
+            report.contracts.insert(
+                id.name,
+                ContractSize {
+                    runtime: runtime.len(),
+                    initcode: initcode.len(),
+                    immutables,
+                    metadata,
+                    breakdown,
+                },
+            );
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report.contracts)?);
+        } else {
+            println!("{report}");
+            if self.breakdown || !self.contracts.is_empty() {
+                for (name, contract) in &report.contracts {
+                    println!("{}", Breakdown { name, contract });
+                }
+            }
+        }
+
+        if self.save_baseline {
+            fs::write_json_file(&self.baseline, &report.baseline())?;
+            if !self.json {
+                println!("Saved the sizes to {}", self.baseline.display());
+            }
+        }
+
+        if let Some(max_growth) = self.fail_on_growth {
+            let baseline: BTreeMap<String, BaselineSize> = fs::read_json_file(&self.baseline)?;
+            let grown = report.grown(&baseline, max_growth);
+            if !grown.is_empty() {
+                for (name, runtime, initcode) in &grown {
+                    eprintln!(
+                        "{}: runtime code grew by {runtime} bytes, init code by {initcode} bytes",
+                        Paint::red(name)
+                    );
+                }
+                eyre::bail!(
+                    "{} contracts grew by more than {max_growth} bytes since {}",
+                    grown.len(),
+                    self.baseline.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The sizes of the project's deployable contracts.
+#[derive(Debug, Default)]
+pub struct SizeReport {
+    /// `contract name -> sizes`
+    pub contracts: BTreeMap<String, ContractSize>,
+}
+
+/// The size of a contract's code, and what it's made of.
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractSize {
+    /// The size of the runtime code, in bytes
+    pub runtime: usize,
+    /// The size of the init code, including the runtime code it deploys, in bytes
+    pub initcode: usize,
+    /// The bytes of the runtime code filled in with immutables on deployment
+    pub immutables: usize,
+    /// The size of the CBOR encoded metadata appended to the runtime code, in bytes
+    pub metadata: usize,
+    /// The bytes of the runtime code generated from each source, and from its functions
+    pub breakdown: BTreeMap<String, PartSize>,
+}
+
+/// The bytes of runtime code generated for a part of a contract.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartSize {
+    /// The size of the part, in bytes
+    pub size: usize,
+    /// The bytes generated from each function of the source, including the internal and library
+    /// functions inlined into the contract
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub functions: BTreeMap<String, usize>,
+}
+
+/// The sizes of a contract saved with `forge size --save-baseline`.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaselineSize {
+    pub runtime: usize,
+    pub initcode: usize,
+}
+
+impl SizeReport {
+    /// Returns the sizes to save as the baseline.
+    pub fn baseline(&self) -> BTreeMap<&str, BaselineSize> {
+        self.contracts
+            .iter()
+            .map(|(name, size)| {
+                (name.as_str(), BaselineSize { runtime: size.runtime, initcode: size.initcode })
+            })
+            .collect()
+    }
+
+    /// Returns the contracts whose runtime or init code grew by more than `max_growth` bytes
+    /// since the baseline, with how much each grew.
+    pub fn grown(
+        &self,
+        baseline: &BTreeMap<String, BaselineSize>,
+        max_growth: usize,
+    ) -> Vec<(String, usize, usize)> {
+        self.contracts
+            .iter()
+            .filter_map(|(name, size)| {
+                let previous = baseline.get(name)?;
+                let runtime = size.runtime.saturating_sub(previous.runtime);
+                let initcode = size.initcode.saturating_sub(previous.initcode);
+                (runtime > max_growth || initcode > max_growth)
+                    .then(|| (name.clone(), runtime, initcode))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut table = Table::new();
+        table.load_preset(ASCII_MARKDOWN);
+        table.set_header(
+            [
+                "Contract",
+                "Runtime (B)",
+                "Runtime Margin (B)",
+                "Init Code (B)",
+                "Init Code Margin (B)",
+                "Immutables (B)",
+                "Metadata (B)",
+            ]
+            .map(|header| Cell::new(header).add_attribute(Attribute::Bold).fg(Color::Blue)),
+        );
+
+        for (name, contract) in &self.contracts {
+            let color = match contract.runtime {
+                _ if contract.initcode > CONTRACT_INITCODE_SIZE_LIMIT => Color::Red,
+                0..=17999 => Color::Reset,
+                18000..=CONTRACT_SIZE_LIMIT => Color::Yellow,
+                _ => Color::Red,
+            };
+            table.add_row([
+                Cell::new(name).fg(color),
+                Cell::new(contract.runtime).fg(color),
+                Cell::new(CONTRACT_SIZE_LIMIT as isize - contract.runtime as isize).fg(color),
+                Cell::new(contract.initcode).fg(color),
+                Cell::new(CONTRACT_INITCODE_SIZE_LIMIT as isize - contract.initcode as isize)
+                    .fg(color),
+                Cell::new(contract.immutables).fg(color),
+                Cell::new(contract.metadata).fg(color),
+            ]);
+        }
+
+        write!(f, "{table}")
+    }
+}
+
+/// Formats the breakdown of a contract's runtime code as a tree of sources and their functions,
+/// largest parts first.
+struct Breakdown<'a> {
+    name: &'a str,
+    contract: &'a ContractSize,
+}
+
+impl fmt::Display for Breakdown<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "\n{} ({} bytes)", Paint::new(self.name).bold(), self.contract.runtime)?;
+        let share = |size: usize| size as f64 * 100.0 / self.contract.runtime.max(1) as f64;
+        let mut parts = self.contract.breakdown.iter().collect::<Vec<_>>();
+        parts.sort_by(|a, b| b.1.size.cmp(&a.1.size).then_with(|| a.0.cmp(b.0)));
+        for (i, (label, part)) in parts.iter().enumerate() {
+            let is_last = i + 1 == parts.len();
+            let (branch, indent) =
+                if is_last { ("└──", "    ") } else { ("├──", "│   ") };
+            writeln!(f, "{branch} {label}: {} bytes ({:.1}%)", part.size, share(part.size))?;
+
+            let mut functions = part.functions.iter().collect::<Vec<_>>();
+            functions.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+            for (j, (name, size)) in functions.iter().enumerate() {
+                let branch = if j + 1 == functions.len() { "└──" } else { "├──" };
+                writeln!(f, "{indent}{branch} {name}: {size} bytes ({:.1}%)", share(**size))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The source range of a function or modifier definition, and its name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FunctionRange {
+    start: usize,
+    end: usize,
+    name: String,
+}
+
+/// Returns the ranges of the functions and modifiers defined in the source, named after the
+/// contract or library defining them.
+fn function_ranges(ast: &Ast) -> Vec<FunctionRange> {
+    let mut ranges = vec![];
+    for node in &ast.nodes {
+        match node.node_type {
+            NodeType::ContractDefinition => {
+                let contract: String = node.attribute("name").unwrap_or_default();
+                for node in &node.nodes {
+                    ranges.extend(function_range(node, Some(&contract)));
+                }
+            }
+            // free functions
+            _ => ranges.extend(function_range(node, None)),
+        }
+    }
+    ranges
+}
+
+fn function_range(node: &Node, contract: Option<&str>) -> Option<FunctionRange> {
+    if !matches!(node.node_type, NodeType::FunctionDefinition | NodeType::ModifierDefinition) {
+        return None
+    }
+    // constructors, fallback and receive functions are nameless
+    let name: String = node
+        .attribute("name")
+        .filter(|name: &String| !name.is_empty())
+        .or_else(|| node.attribute("kind"))?;
+    Some(FunctionRange {
+        start: node.src.start,
+        end: node.src.start + node.src.length?,
+        name: contract.map_or_else(|| name.clone(), |contract| format!("{contract}.{name}")),
+    })
+}
+
+/// Returns the name of the function whose definition contains the source offset.
+fn function_at(ranges: &[FunctionRange], offset: usize) -> Option<&str> {
+    ranges
+        .iter()
+        .find(|range| (range.start..range.end).contains(&offset))
+        .map(|range| range.name.as_str())
+}
+
+/// What a byte of runtime code was generated for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum CodePart {
+    /// The code of a source, by its ID
+    Source(u32),
+    /// Code the compiler generated without a source location, e.g. the dispatcher
+    Generated,
+    /// Bytes past the instructions covered by the source map, e.g. constants
+    Data,
+    /// The CBOR encoded metadata
+    Metadata,
+}
+
+/// Returns the size of the CBOR encoded metadata at the end of the runtime code, including its
+/// 2 byte length, or 0 if there is none.
+pub fn metadata_len(code: &[u8]) -> usize {
+    let [.., hi, lo] = code else { return 0 };
+    let len = u16::from_be_bytes([*hi, *lo]) as usize + 2;
+    // the metadata is a CBOR map with 1 to 5 entries
+    match code.len().checked_sub(len).map(|start| code[start]) {
+        Some(0xa1..=0xa5) => len,
+        _ => 0,
+    }
+}
+
+/// Attributes the bytes of the runtime code to the sources and functions of its instructions,
+/// given by the source map.
+///
+/// Bytes of a source outside any function, e.g. of state variable initializers, are attributed
+/// to its `<other>` part.
+fn runtime_breakdown(
+    code: &[u8],
+    instructions: &[Option<(u32, Option<&str>)>],
+    metadata: usize,
+) -> BTreeMap<CodePart, PartSize> {
+    let mut breakdown = BTreeMap::new();
+    let end = code.len() - metadata;
+    let (mut pc, mut ic) = (0, 0);
+    while pc < end {
+        let op = code[pc];
+        // PUSH1 to PUSH32 are followed by their immediate bytes
+        let immediates = if (0x60..=0x7f).contains(&op) { (op - 0x5f) as usize } else { 0 };
+        let size = (1 + immediates).min(end - pc);
+        let (part, function) = match instructions.get(ic) {
+            Some(Some((index, function))) => (CodePart::Source(*index), Some(*function)),
+            Some(None) => (CodePart::Generated, None),
+            None => (CodePart::Data, None),
+        };
+        let part: &mut PartSize = breakdown.entry(part).or_default();
+        part.size += size;
+        if let Some(function) = function {
+            *part.functions.entry(function.unwrap_or("<other>").to_string()).or_default() += size;
+        }
+        pc += size;
+        ic += 1;
+    }
+    if metadata > 0 {
+        breakdown.insert(CodePart::Metadata, PartSize { size: metadata, ..Default::default() });
+    }
+    breakdown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaks_down_runtime_code() {
+        // PUSH1 0x80 PUSH1 0x40 MSTORE CALLVALUE INVALID 0x00, then a CBOR map `{0: 0}` and its
+        // length as metadata
+        let code = [0x60, 0x80, 0x60, 0x40, 0x52, 0x34, 0xfe, 0x00, 0xa1, 0x00, 0x00, 0x00, 0x03];
+        let metadata = metadata_len(&code);
+        assert_eq!(metadata, 5);
+
+        let instructions =
+            [Some((0, Some("A.f"))), Some((0, Some("Lib.g"))), Some((1, None)), None];
+        let breakdown = runtime_breakdown(&code, &instructions, metadata);
+        let part = |size, functions: &[(&str, usize)]| PartSize {
+            size,
+            functions: functions.iter().map(|(name, size)| (name.to_string(), *size)).collect(),
+        };
+        assert_eq!(
+            breakdown,
+            BTreeMap::from([
+                (CodePart::Source(0), part(4, &[("A.f", 2), ("Lib.g", 2)])),
+                (CodePart::Source(1), part(1, &[("<other>", 1)])),
+                (CodePart::Generated, part(1, &[])),
+                (CodePart::Data, part(2, &[])),
+                (CodePart::Metadata, part(5, &[])),
+            ])
+        );
+        assert_eq!(breakdown.values().map(|part| part.size).sum::<usize>(), code.len());
+    }
+
+    #[test]
+    fn finds_functions_by_offset() {
+        let ranges = [
+            FunctionRange { start: 10, end: 20, name: "A.f".to_string() },
+            FunctionRange { start: 30, end: 50, name: "A.constructor".to_string() },
+        ];
+        assert_eq!(function_at(&ranges, 10), Some("A.f"));
+        assert_eq!(function_at(&ranges, 20), None);
+        assert_eq!(function_at(&ranges, 49), Some("A.constructor"));
+    }
+
+    #[test]
+    fn finds_grown_contracts() {
+        let report = SizeReport {
+            contracts: BTreeMap::from([
+                (
+                    "A".to_string(),
+                    ContractSize { runtime: 110, initcode: 150, ..Default::default() },
+                ),
+                (
+                    "B".to_string(),
+                    ContractSize { runtime: 100, initcode: 300, ..Default::default() },
+                ),
+                (
+                    "C".to_string(),
+                    ContractSize { runtime: 999, initcode: 999, ..Default::default() },
+                ),
+            ]),
+        };
+        let baseline = BTreeMap::from([
+            ("A".to_string(), BaselineSize { runtime: 100, initcode: 150 }),
+            ("B".to_string(), BaselineSize { runtime: 100, initcode: 100 }),
+        ]);
+        assert_eq!(report.grown(&baseline, 10), [("B".to_string(), 0, 200)]);
+    }
+}
@@ -2,19 +2,24 @@ use super::{
     test,
     test::{Test, TestOutcome},
 };
-use alloy_primitives::U256;
+use alloy_primitives::{Bytes, U256};
 use clap::{builder::RangedU64ValueParser, Parser, ValueHint};
+use comfy_table::{
+    modifiers::UTF8_ROUND_CORNERS, Attribute, Cell, CellAlignment, Color, Row, Table,
+};
 use eyre::{Context, Result};
 use forge::result::TestKindReport;
-use foundry_cli::utils::STATIC_FUZZ_SEED;
+use foundry_cli::utils::{Git, STATIC_FUZZ_SEED};
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
 use std::{
     cmp::Ordering,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     io::{self, BufRead},
     path::{Path, PathBuf},
+    process::Command,
     str::FromStr,
 };
 use watchexec::config::{InitConfig, RuntimeConfig};
@@ -53,6 +58,18 @@ pub struct SnapshotArgs {
     )]
     check: Option<Option<PathBuf>>,
 
+    /// Compare against the snapshot of the given git revision, e.g. `origin/main`, printing the
+    /// gas change of each test.
+    ///
+    /// The snapshot file is read from the revision if it is committed there, otherwise it is
+    /// rebuilt by running `forge snapshot` in a temporary worktree of the revision.
+    #[clap(long, conflicts_with_all = ["diff", "check"], value_name = "REF")]
+    diff_ref: Option<String>,
+
+    /// Write the gas diff against `--diff-ref` to the given file as JSON.
+    #[clap(long, requires = "diff_ref", value_hint = ValueHint::FilePath, value_name = "FILE")]
+    diff_json: Option<PathBuf>,
+
     // Hidden because there is only one option
     /// How to format the output.
     #[clap(long, hide(true))]
@@ -75,6 +92,16 @@ pub struct SnapshotArgs {
     )]
     tolerance: Option<u32>,
 
+    /// Directory of the value snapshots recorded with `vm.snapshotValue`, one JSON file per test
+    /// contract at `<path>/<contract>.json`.
+    #[clap(
+        long,
+        default_value = "snapshots",
+        value_hint = ValueHint::DirPath,
+        value_name = "DIR",
+    )]
+    snapshots: PathBuf,
+
     /// All test arguments are supported
     #[clap(flatten)]
     pub(crate) test: test::TestArgs,
@@ -102,25 +129,89 @@ impl SnapshotArgs {
 
         let outcome = self.test.execute_tests().await?;
         outcome.ensure_ok()?;
+        let value_snapshots = collect_value_snapshots(&outcome);
         let tests = self.config.apply(outcome);
 
-        if let Some(path) = self.diff {
+        if let Some(revision) = &self.diff_ref {
+            let snaps = self.read_ref_snapshot(revision)?;
+            let current = tests
+                .iter()
+                .map(|test| SnapshotEntry {
+                    contract_name: test.contract_name().to_string(),
+                    signature: test.signature.clone(),
+                    gas_used: test.result.kind.report(),
+                })
+                .collect();
+            let report = GasDiffReport::new(revision, current, snaps);
+            println!("{}", report.table());
+            println!(
+                "Overall gas change: {} ({})",
+                fmt_change(report.overall_change.into()),
+                fmt_pct_change(report.overall_change_pct)
+            );
+            if let Some(path) = &self.diff_json {
+                foundry_common::fs::write(path, serde_json::to_string_pretty(&report)?)?;
+            }
+        } else if let Some(path) = self.diff {
             let snap = path.as_ref().unwrap_or(&self.snap);
             let snaps = read_snapshot(snap)?;
             diff(tests, snaps)?;
         } else if let Some(path) = self.check {
             let snap = path.as_ref().unwrap_or(&self.snap);
             let snaps = read_snapshot(snap)?;
-            if check(tests, snaps, self.tolerance) {
+            let gas_ok = check(tests, snaps, self.tolerance);
+            let values_ok = check_value_snapshots(&value_snapshots, &self.snapshots)?;
+            if gas_ok && values_ok {
                 std::process::exit(0)
             } else {
                 std::process::exit(1)
             }
         } else {
             write_to_snapshot_file(&tests, self.snap, self.format)?;
+            write_value_snapshots(&value_snapshots, &self.snapshots)?;
         }
         Ok(())
     }
+
+    /// Returns the snapshot of the given git revision.
+    ///
+    /// Reads the snapshot file committed at the revision, or rebuilds it in a temporary worktree
+    /// if there is none.
+    fn read_ref_snapshot(&self, revision: &str) -> Result<Vec<SnapshotEntry>> {
+        let cwd = std::env::current_dir()?;
+        let git = Git::new(&cwd);
+        if let Ok(content) = git.show_file(revision, &self.snap) {
+            return parse_snapshot(&content)
+        }
+
+        eprintln!("No snapshot file found at `{revision}`, building it...");
+        let root = Git::root_of(&cwd)?;
+        let relative = cwd.strip_prefix(&root).unwrap_or(Path::new(""));
+        let worktree = std::env::temp_dir().join(format!("forge-snapshot-{}", std::process::id()));
+        let forge = std::env::current_exe()?;
+        git.worktree_add(&worktree, revision)?;
+
+        let snap = worktree.join(".gas-snapshot-base");
+        let snaps = Git::new(&worktree)
+            // worktrees don't check out submodules, which usually hold the dependencies in `lib`
+            .submodule_update(false, false, false, true, std::iter::empty::<&Path>())
+            .wrap_err_with(|| format!("failed to check out the submodules of `{revision}`"))
+            .and_then(|()| {
+                let status = Command::new(forge)
+                    .current_dir(worktree.join(relative))
+                    .arg("snapshot")
+                    .arg("--snap")
+                    .arg(&snap)
+                    .status()?;
+                eyre::ensure!(
+                    status.success(),
+                    "failed to build the snapshot of `{revision}`: forge {status}"
+                );
+                read_snapshot(&snap)
+            });
+        git.worktree_remove(&worktree)?;
+        snaps
+    }
 }
 
 // TODO implement pretty tables
@@ -257,6 +348,14 @@ impl FromStr for SnapshotEntry {
     }
 }
 
+/// Parses a list of snapshot entries from the content of a snapshot file
+fn parse_snapshot(content: &str) -> Result<Vec<SnapshotEntry>> {
+    content
+        .lines()
+        .map(|line| SnapshotEntry::from_str(line).map_err(|err| eyre::eyre!("{err}")))
+        .collect()
+}
+
 /// Reads a list of snapshot entries from a snapshot file
 fn read_snapshot(path: impl AsRef<Path>) -> Result<Vec<SnapshotEntry>> {
     let path = path.as_ref();
@@ -292,6 +391,78 @@ fn write_to_snapshot_file(
     Ok(fs::write(path, content)?)
 }
 
+/// Value snapshots recorded with `vm.snapshotValue`, keyed by test contract identifier,
+/// `<path>:<name>`, and then by value name
+type ValueSnapshots = BTreeMap<String, BTreeMap<String, Bytes>>;
+
+/// Collects the value snapshots recorded by the passing tests of the test contracts of the outcome
+fn collect_value_snapshots(outcome: &TestOutcome) -> ValueSnapshots {
+    outcome
+        .results
+        .iter()
+        .map(|(id, suite)| (id.clone(), suite.value_snapshots()))
+        .filter(|(_, values)| !values.is_empty())
+        .collect()
+}
+
+/// Returns the path of the value snapshot file of the given test contract:
+/// `<dir>/<path>/<name>.json`, so that contracts of the same name have their own file
+fn value_snapshot_path(dir: &Path, contract: &str) -> PathBuf {
+    match contract.rsplit_once(':') {
+        Some((path, name)) => dir.join(path).join(format!("{name}.json")),
+        None => dir.join(format!("{contract}.json")),
+    }
+}
+
+/// Writes the value snapshots of each test contract to its own file in the given directory
+fn write_value_snapshots(snapshots: &ValueSnapshots, dir: &Path) -> Result<()> {
+    if snapshots.is_empty() {
+        return Ok(())
+    }
+    for (contract, values) in snapshots {
+        let path = value_snapshot_path(dir, contract);
+        if let Some(parent) = path.parent() {
+            foundry_common::fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(values)?)?;
+    }
+    Ok(())
+}
+
+/// Compares the value snapshots with the ones in the given directory
+///
+/// Returns true if all values match
+fn check_value_snapshots(snapshots: &ValueSnapshots, dir: &Path) -> Result<bool> {
+    let mut has_diff = false;
+    for (contract, values) in snapshots {
+        let path = value_snapshot_path(dir, contract);
+        let expected: BTreeMap<String, Bytes> = if path.exists() {
+            foundry_common::fs::read_json_file(&path)?
+        } else {
+            BTreeMap::new()
+        };
+        for (name, value) in values {
+            match expected.get(name) {
+                Some(expected) if expected == value => {}
+                Some(expected) => {
+                    eprintln!(
+                        "Diff in value snapshot \"{contract}::{name}\": got \"{value}\", expected \"{expected}\""
+                    );
+                    has_diff = true;
+                }
+                None => {
+                    eprintln!(
+                        "No matching value snapshot found for \"{contract}::{name}\" in \"{}\"",
+                        path.display()
+                    );
+                    has_diff = true;
+                }
+            }
+        }
+    }
+    Ok(!has_diff)
+}
+
 /// A Snapshot entry diff
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SnapshotDiff {
@@ -398,6 +569,111 @@ fn diff(tests: Vec<Test>, snaps: Vec<SnapshotEntry>) -> Result<()> {
     Ok(())
 }
 
+/// The gas diff of the tests against the snapshot of a git revision
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct GasDiffReport {
+    /// The git revision the snapshot was compared against
+    base: String,
+    /// The gas diff of each test, sorted by contract and signature
+    tests: Vec<GasDiff>,
+    /// The total gas change of the tests present in both snapshots
+    overall_change: i64,
+    /// The total gas change as a fraction of the base gas usage
+    overall_change_pct: f64,
+}
+
+/// The gas diff of a single test
+#[derive(Clone, Debug, PartialEq, Serialize)]
+struct GasDiff {
+    contract: String,
+    signature: String,
+    /// The gas used at the base revision, if the test exists there
+    base: Option<u64>,
+    current: u64,
+    change: Option<i64>,
+    change_pct: Option<f64>,
+}
+
+impl GasDiffReport {
+    /// Compares the current snapshot entries with the ones of the base revision
+    fn new(base: &str, current: Vec<SnapshotEntry>, snaps: Vec<SnapshotEntry>) -> Self {
+        let snaps = snaps
+            .into_iter()
+            .map(|s| ((s.contract_name, s.signature), s.gas_used.gas()))
+            .collect::<HashMap<_, _>>();
+        let mut tests = current
+            .into_iter()
+            .map(|entry| {
+                let current = entry.gas_used.gas();
+                let base = snaps.get(&(entry.contract_name.clone(), entry.signature.clone()));
+                let change = base.map(|&base| current as i64 - base as i64);
+                let change_pct = base
+                    .zip(change)
+                    .filter(|(base, _)| **base > 0)
+                    .map(|(&base, change)| change as f64 / base as f64);
+                GasDiff {
+                    contract: entry.contract_name,
+                    signature: entry.signature,
+                    base: base.copied(),
+                    current,
+                    change,
+                    change_pct,
+                }
+            })
+            .collect::<Vec<_>>();
+        tests.sort_by(|a, b| (&a.contract, &a.signature).cmp(&(&b.contract, &b.signature)));
+
+        let overall_change = tests.iter().filter_map(|test| test.change).sum();
+        let overall_base = tests.iter().filter_map(|test| test.base).sum::<u64>();
+        let overall_change_pct =
+            if overall_base > 0 { overall_change as f64 / overall_base as f64 } else { 0.0 };
+        Self { base: base.to_string(), tests, overall_change, overall_change_pct }
+    }
+
+    /// Builds a table of the gas change of each test
+    fn table(&self) -> Table {
+        let mut table = Table::new();
+        table.apply_modifier(UTF8_ROUND_CORNERS);
+        table.set_header(["Contract", "Test", self.base.as_str(), "Current", "Δ", "%"].map(
+            |header| {
+                Cell::new(header)
+                    .set_alignment(CellAlignment::Center)
+                    .add_attribute(Attribute::Bold)
+            },
+        ));
+        for test in &self.tests {
+            let color = match test.change.unwrap_or_default().cmp(&0) {
+                Ordering::Less => Some(Color::Green),
+                Ordering::Equal => None,
+                Ordering::Greater => Some(Color::Red),
+            };
+            let colored = |cell: Cell| match color {
+                Some(color) => cell.fg(color),
+                None => cell,
+            };
+            table.add_row(Row::from(vec![
+                Cell::new(&test.contract),
+                Cell::new(&test.signature),
+                Cell::new(test.base.map_or_else(|| "-".to_string(), |base| base.to_string()))
+                    .set_alignment(CellAlignment::Right),
+                Cell::new(test.current).set_alignment(CellAlignment::Right),
+                colored(Cell::new(
+                    test.change.map_or_else(|| "new".to_string(), |change| format!("{change:+}")),
+                ))
+                .set_alignment(CellAlignment::Right),
+                colored(Cell::new(
+                    test.change_pct.map_or_else(
+                        || "-".to_string(),
+                        |change| format!("{:+.3}%", change * 100.0),
+                    ),
+                ))
+                .set_alignment(CellAlignment::Right),
+            ]));
+        }
+        table
+    }
+}
+
 fn fmt_pct_change(change: f64) -> String {
     let change_pct = change * 100.0;
     match change.partial_cmp(&0.0).unwrap_or(Ordering::Equal) {
@@ -440,6 +716,61 @@ fn within_tolerance(source_gas: u64, target_gas: u64, tolerance_pct: Option<u32>
 mod tests {
     use super::*;
 
+    #[test]
+    fn check_value_snapshot_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = BTreeMap::from([("root".to_string(), Bytes::from_static(&[1, 2, 3]))]);
+        let snapshots = ValueSnapshots::from([
+            ("test/A.t.sol:RootTest".to_string(), root.clone()),
+            ("test/B.t.sol:RootTest".to_string(), BTreeMap::new()),
+        ]);
+        write_value_snapshots(&snapshots, dir.path()).unwrap();
+        assert!(dir.path().join("test/A.t.sol/RootTest.json").exists());
+        assert!(check_value_snapshots(&snapshots, dir.path()).unwrap());
+
+        // contracts of the same name don't share their snapshots
+        let other = ValueSnapshots::from([("test/B.t.sol:RootTest".to_string(), root)]);
+        assert!(!check_value_snapshots(&other, dir.path()).unwrap());
+
+        let mut changed = snapshots.clone();
+        let values = changed.get_mut("test/A.t.sol:RootTest").unwrap();
+        values.insert("root".to_string(), Bytes::new());
+        assert!(!check_value_snapshots(&changed, dir.path()).unwrap());
+
+        let values = changed.get_mut("test/A.t.sol:RootTest").unwrap();
+        values.insert("other".to_string(), Bytes::new());
+        assert!(!check_value_snapshots(&changed, dir.path()).unwrap());
+    }
+
+    #[test]
+    fn gas_diff_report() {
+        let entry = |signature: &str, gas| SnapshotEntry {
+            contract_name: "Test".to_string(),
+            signature: signature.to_string(),
+            gas_used: TestKindReport::Standard { gas },
+        };
+        let report = GasDiffReport::new(
+            "origin/main",
+            vec![entry("b()", 90), entry("a()", 150), entry("c()", 10)],
+            vec![entry("a()", 100), entry("b()", 100), entry("d()", 100)],
+        );
+
+        let signatures = report.tests.iter().map(|t| t.signature.as_str()).collect::<Vec<_>>();
+        assert_eq!(signatures, ["a()", "b()", "c()"]);
+        assert_eq!(report.tests[0].change, Some(50));
+        assert_eq!(report.tests[0].change_pct, Some(0.5));
+        assert_eq!(report.tests[1].change, Some(-10));
+        assert_eq!(report.tests[2].base, None);
+        assert_eq!(report.tests[2].change, None);
+        assert_eq!(report.overall_change, 40);
+        assert_eq!(report.overall_change_pct, 0.2);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["base"], "origin/main");
+        assert_eq!(json["tests"][0]["change"], 50);
+        assert_eq!(json["tests"][2]["base"], serde_json::Value::Null);
+    }
+
     #[test]
     fn test_tolerance() {
         assert!(within_tolerance(100, 105, Some(5)));
@@ -1,10 +1,12 @@
+use super::rerun::TestFailures;
+use alloy_primitives::keccak256;
 use clap::Parser;
 use forge::TestFilter;
 use foundry_cli::utils::FoundryPathExt;
 use foundry_common::glob::GlobMatcher;
 use foundry_compilers::{FileFilter, ProjectPathsConfig};
 use foundry_config::Config;
-use std::{fmt, path::Path};
+use std::{fmt, path::Path, str::FromStr};
 
 /// The filter to use during testing.
 ///
@@ -40,6 +42,14 @@ pub struct FilterArgs {
         value_name = "GLOB"
     )]
     pub path_pattern_inverse: Option<GlobMatcher>,
+
+    /// Only run the test contracts of the given shard, to split the test suite across multiple
+    /// machines.
+    ///
+    /// Test contracts are deterministically assigned to one of `M` shards based on the hash of
+    /// their name.
+    #[clap(long, value_name = "N/M")]
+    pub shard: Option<Shard>,
 }
 
 impl FilterArgs {
@@ -50,7 +60,8 @@ impl FilterArgs {
             self.contract_pattern.is_none() &&
             self.contract_pattern_inverse.is_none() &&
             self.path_pattern.is_none() &&
-            self.path_pattern_inverse.is_none()
+            self.path_pattern_inverse.is_none() &&
+            self.shard.is_none()
     }
 
     /// Merges the set filter globs with the config's values
@@ -73,7 +84,7 @@ impl FilterArgs {
         if self.path_pattern_inverse.is_none() {
             self.path_pattern_inverse = config.path_pattern_inverse.clone().map(Into::into);
         }
-        ProjectPathsAwareFilter { args_filter: self, paths: config.project_paths() }
+        ProjectPathsAwareFilter { args_filter: self, paths: config.project_paths(), rerun: None }
     }
 }
 
@@ -86,6 +97,7 @@ impl fmt::Debug for FilterArgs {
             .field("no-match-contract", &self.contract_pattern_inverse.as_ref().map(|r| r.as_str()))
             .field("match-path", &self.path_pattern.as_ref().map(|g| g.as_str()))
             .field("no-match-path", &self.path_pattern_inverse.as_ref().map(|g| g.as_str()))
+            .field("shard", &self.shard)
             .finish_non_exhaustive()
     }
 }
@@ -126,6 +138,9 @@ impl TestFilter for FilterArgs {
         if let Some(re) = &self.contract_pattern_inverse {
             ok = ok && !re.is_match(contract_name);
         }
+        if let Some(shard) = &self.shard {
+            ok = ok && shard.contains(contract_name);
+        }
         ok
     }
 
@@ -161,15 +176,63 @@ impl fmt::Display for FilterArgs {
         if let Some(p) = &self.path_pattern_inverse {
             writeln!(f, "\tno-match-path: `{}`", p.as_str())?;
         }
+        if let Some(shard) = &self.shard {
+            writeln!(f, "\tshard: `{shard}`")?;
+        }
         Ok(())
     }
 }
 
+/// A shard of the test suite, in the form `N/M`, where `N` is the 1-based index of the shard and
+/// `M` the total number of shards.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shard {
+    /// The 1-based index of the shard.
+    pub index: u64,
+    /// The total number of shards.
+    pub count: u64,
+}
+
+impl Shard {
+    /// Returns true if the test contract with the given name belongs to this shard.
+    ///
+    /// The contract is assigned to a shard based on the keccak256 hash of its name, which is
+    /// stable across machines and runs.
+    pub fn contains(&self, contract_name: &str) -> bool {
+        let hash = keccak256(contract_name);
+        let hash = u64::from_be_bytes(hash[..8].try_into().unwrap());
+        hash % self.count == self.index - 1
+    }
+}
+
+impl FromStr for Shard {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) =
+            s.split_once('/').ok_or_else(|| format!("invalid shard `{s}`, expected `N/M`"))?;
+        let index = index.trim().parse::<u64>().map_err(|e| format!("invalid shard index: {e}"))?;
+        let count = count.trim().parse::<u64>().map_err(|e| format!("invalid shard count: {e}"))?;
+        if count == 0 || index == 0 || index > count {
+            return Err(format!("invalid shard `{s}`, expected `N/M` with 1 <= N <= M"))
+        }
+        Ok(Self { index, count })
+    }
+}
+
+impl fmt::Display for Shard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.index, self.count)
+    }
+}
+
 /// A filter that combines all command line arguments and the paths of the current projects
 #[derive(Clone, Debug)]
 pub struct ProjectPathsAwareFilter {
     args_filter: FilterArgs,
     paths: ProjectPathsConfig,
+    /// The failed tests of the last run, if only they are run again
+    rerun: Option<TestFailures>,
 }
 
 // === impl ProjectPathsAwareFilter ===
@@ -189,6 +252,13 @@ impl ProjectPathsAwareFilter {
     pub fn args_mut(&mut self) -> &mut FilterArgs {
         &mut self.args_filter
     }
+
+    /// Restricts the filter to the tests that failed in the last run.
+    pub fn rerun(&mut self, failures: TestFailures) -> eyre::Result<()> {
+        failures.apply(&mut self.args_filter)?;
+        self.rerun = Some(failures);
+        Ok(())
+    }
 }
 
 impl FileFilter for ProjectPathsAwareFilter {
@@ -214,6 +284,10 @@ impl TestFilter for ProjectPathsAwareFilter {
         // we don't want to test files that belong to a library
         self.args_filter.matches_path(path) && !self.paths.has_library_ancestor(path)
     }
+
+    fn matches_contract_test(&self, contract_id: &str, test_signature: &str) -> bool {
+        self.rerun.as_ref().map_or(true, |failures| failures.contains(contract_id, test_signature))
+    }
 }
 
 impl fmt::Display for ProjectPathsAwareFilter {
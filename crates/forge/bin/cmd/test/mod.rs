@@ -4,9 +4,10 @@ use clap::Parser;
 use eyre::Result;
 use forge::{
     decode::decode_console_logs,
-    gas_report::GasReport,
+    gas_report::{GasReport, TestGasReport},
     inspectors::CheatsConfig,
-    result::{SuiteResult, TestResult, TestStatus},
+    result::{FuzzFailure, SuiteResult, TestResult, TestStatus},
+    symbolic::SmtChecker,
     traces::{
         identifier::{EtherscanIdentifier, LocalTraceIdentifier, SignaturesIdentifier},
         CallTraceDecoderBuilder, TraceKind,
@@ -21,7 +22,9 @@ use foundry_common::{
     compact_to_contract,
     compile::{ContractSources, ProjectCompiler},
     evm::EvmArgs,
-    get_contract_name, get_file_name, shell,
+    get_contract_name, get_file_name,
+    provider::cassette::{RpcCassetteConfig, RpcCassetteMode},
+    shell,
 };
 use foundry_config::{
     figment,
@@ -33,16 +36,28 @@ use foundry_config::{
 };
 use foundry_debugger::Debugger;
 use regex::Regex;
-use std::{collections::BTreeMap, fs, sync::mpsc::channel, time::Duration};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    sync::{mpsc::channel, Arc},
+    time::{Duration, Instant},
+};
 use watchexec::config::{InitConfig, RuntimeConfig};
 use yansi::Paint;
 
 mod filter;
+mod profile;
+mod reporter;
+mod rerun;
 mod summary;
 use summary::TestSummaryReporter;
 
 pub use filter::FilterArgs;
 use forge::traces::render_trace_arena;
+use profile::ProfileSort;
+use reporter::{TestLocations, TestReporter};
+use rerun::TestFailures;
 
 // Loads project's figment and merges the build cli arguments into it
 foundry_config::merge_impl_figment_convert!(TestArgs, opts, evm_opts);
@@ -73,6 +88,11 @@ pub struct TestArgs {
     #[clap(long, env = "FORGE_GAS_REPORT")]
     gas_report: bool,
 
+    /// Print a gas report per test, with the gas used at each external call site, grouped by
+    /// call path.
+    #[clap(long)]
+    gas_report_by_test: bool,
+
     /// Exit with code 0 even if a test fails.
     #[clap(long, env = "FORGE_ALLOW_FAILURE")]
     allow_failure: bool,
@@ -81,7 +101,13 @@ pub struct TestArgs {
     #[clap(long, short, help_heading = "Display options")]
     json: bool,
 
+    /// Report test results in a format CI systems can render natively.
+    #[clap(long, value_enum, help_heading = "Display options", conflicts_with = "json")]
+    reporter: Option<TestReporter>,
+
     /// Stop running tests after the first failure.
+    ///
+    /// The tests running in parallel, including fuzz and invariant tests, are cancelled.
     #[clap(long)]
     pub fail_fast: bool,
 
@@ -100,6 +126,56 @@ pub struct TestArgs {
     #[clap(long, env = "FOUNDRY_FUZZ_RUNS", value_name = "RUNS")]
     pub fuzz_runs: Option<u64>,
 
+    /// Fuzz each test for the given number of seconds, instead of a fixed number of runs.
+    #[clap(long, env = "FOUNDRY_FUZZ_TIMEOUT", value_name = "TIMEOUT")]
+    pub fuzz_timeout: Option<u64>,
+
+    /// Fuzz each test concurrently with the given number of workers, with independent seeds.
+    #[clap(long, env = "FOUNDRY_FUZZ_WORKERS", value_name = "WORKERS")]
+    pub fuzz_workers: Option<u64>,
+
+    /// Replay the counterexample of a failed fuzz or invariant test, from the file it was saved
+    /// to, instead of fuzzing the test.
+    #[clap(long, value_name = "FILE")]
+    pub replay: Option<PathBuf>,
+
+    /// Only run the tests that failed in the last run, compiling only their source files.
+    ///
+    /// If no test failed in the last run, all tests are run.
+    #[clap(long, conflicts_with = "replay")]
+    pub rerun: bool,
+
+    /// Run the test contracts and tests one at a time, in a random order, to detect tests that
+    /// depend on the state left by other tests.
+    ///
+    /// The order is printed if a test fails.
+    #[clap(long)]
+    pub shuffle: bool,
+
+    /// The seed of the random order of `--shuffle`.
+    ///
+    /// By default, a random seed is used.
+    #[clap(long, requires = "shuffle", value_name = "SEED")]
+    pub seed: Option<u64>,
+
+    /// Prove the tests marked with `forge-config: <profile>.symbolic = true` with the solc
+    /// SMTChecker, instead of running them.
+    #[clap(long)]
+    pub symbolic: bool,
+
+    /// Record every fork RPC response to a cassette in the given directory, one per endpoint.
+    ///
+    /// The RPC storage cache is disabled, so that every response is recorded.
+    #[clap(long, value_name = "DIR")]
+    pub record_rpc: Option<PathBuf>,
+
+    /// Serve every fork RPC request from the cassettes recorded with `--record-rpc` in the given
+    /// directory, without a network connection.
+    ///
+    /// Requests that weren't recorded fail.
+    #[clap(long, value_name = "DIR", conflicts_with = "record_rpc")]
+    pub replay_rpc: Option<PathBuf>,
+
     #[clap(flatten)]
     filter: FilterArgs,
 
@@ -119,6 +195,23 @@ pub struct TestArgs {
     /// Print detailed test summary table.
     #[clap(long, help_heading = "Display options", requires = "summary")]
     pub detailed: bool,
+
+    /// Profile each test, printing its wall-clock time, EVM execution time, fork RPC time and
+    /// number of cheatcode calls in a table.
+    ///
+    /// With `--json`, the profile of each test is included in its result instead.
+    #[clap(long, help_heading = "Display options")]
+    pub profile_tests: bool,
+
+    /// Sort the test profile table by the given column, in descending order.
+    #[clap(
+        long,
+        value_enum,
+        help_heading = "Display options",
+        requires = "profile_tests",
+        default_value_t = ProfileSort::Duration
+    )]
+    pub profile_sort: ProfileSort,
 }
 
 impl TestArgs {
@@ -129,7 +222,7 @@ impl TestArgs {
 
     pub async fn run(self) -> Result<TestOutcome> {
         trace!(target: "forge::test", "executing test command");
-        shell::set_shell(shell::Shell::from_args(self.opts.silent, self.json))?;
+        shell::set_shell(shell::Shell::from_args(self.opts.silent, self.is_machine_readable()))?;
         self.execute_tests().await
     }
 
@@ -156,10 +249,33 @@ impl TestArgs {
         }
 
         let mut filter = self.filter(&config);
+
+        // Only run the test of the counterexample to replay
+        let replay = self.replay.as_deref().map(FuzzFailure::read).transpose()?;
+        if let Some(failure) = &replay {
+            let contract = failure.contract.rsplit(':').next().unwrap_or(&failure.contract);
+            filter.args_mut().contract_pattern =
+                Some(Regex::new(&format!("^{}$", regex::escape(contract)))?);
+            filter.args_mut().test_pattern =
+                Some(Regex::new(&format!("^{}$", regex::escape(&failure.test)))?);
+        }
+
+        // Only run the tests that failed in the last run
+        let rerun = if self.rerun {
+            Some(TestFailures::read(&TestFailures::path(&config))?).filter(|f| !f.is_empty())
+        } else {
+            None
+        };
+        if let Some(failures) = &rerun {
+            filter.rerun(failures.clone())?;
+        }
         trace!(target: "forge::test", ?filter, "using filter");
 
-        let mut compiler = ProjectCompiler::new().quiet_if(self.json || self.opts.silent);
-        if config.sparse_mode {
+        let mut compiler =
+            ProjectCompiler::new().quiet_if(self.is_machine_readable() || self.opts.silent);
+        if let Some(failures) = &rerun {
+            compiler = compiler.filter(Box::new(failures.sources_filter()));
+        } else if config.sparse_mode {
             compiler = compiler.filter(Box::new(filter.clone()));
         }
         let output = compiler.compile(&project)?;
@@ -169,18 +285,39 @@ impl TestArgs {
         let toml = config.get_config_path();
         let profiles = get_available_profiles(toml)?;
 
-        let test_options: TestOptions = TestOptionsBuilder::default()
+        let mut test_options_builder = TestOptionsBuilder::default()
             .fuzz(config.fuzz)
-            .invariant(config.invariant)
-            .profiles(profiles)
-            .build(&output, project_root)?;
+            .invariant(config.invariant.clone())
+            .profiles(profiles);
+        if let Some(failure) = replay {
+            test_options_builder = test_options_builder.replay(failure);
+        }
+        test_options_builder =
+            test_options_builder.profile(self.profile_tests).fail_fast(self.fail_fast);
+        if self.shuffle {
+            test_options_builder =
+                test_options_builder.shuffle(self.seed.unwrap_or_else(rand::random));
+        }
+        if self.symbolic {
+            test_options_builder =
+                test_options_builder.symbolic(Arc::new(SmtChecker::new(&config, &output)?));
+        }
+        let test_options: TestOptions = test_options_builder.build(&output, project_root)?;
 
         // Determine print verbosity and executor verbosity
         let verbosity = evm_opts.verbosity;
-        if self.gas_report && evm_opts.verbosity < 3 {
+        if (self.gas_report || self.gas_report_by_test) && evm_opts.verbosity < 3 {
             evm_opts.verbosity = 3;
         }
 
+        // Record or replay the fork RPC responses, bypassing the storage cache which would answer
+        // requests without them being recorded
+        evm_opts.rpc_cassette = self.rpc_cassette();
+        if evm_opts.rpc_cassette.is_some() {
+            config.no_storage_caching = true;
+            evm_opts.no_storage_caching = true;
+        }
+
         let env = evm_opts.evm_env().await?;
 
         // Prepare the test builder
@@ -221,6 +358,48 @@ impl TestArgs {
             .run_tests(runner, config.clone(), verbosity, &filter, test_options.clone())
             .await?;
 
+        // Persist the failed tests, to be run again with `--rerun`, and the counterexamples of the
+        // failed fuzz and invariant tests, to be replayed with `--replay`. The counterexamples of
+        // the tests which pass again are removed.
+        if config.cache {
+            let path = TestFailures::path(&config);
+            let mut failures = TestFailures::read(&path)?;
+            failures.update(&outcome);
+            failures.write(&path)?;
+
+            let failures_dir = config.cache_path.join("fuzz").join("failures");
+            for (contract, suite_result) in &outcome.results {
+                for (test, result) in &suite_result.test_results {
+                    if result.status == TestStatus::Success {
+                        let path = FuzzFailure::path_of(&failures_dir, contract, test);
+                        if path.exists() {
+                            fs::remove_file(path)?;
+                        }
+                        continue
+                    }
+                    if result.status != TestStatus::Failure {
+                        continue
+                    }
+                    if let Some(failure) = FuzzFailure::new(contract, test, result) {
+                        let path = failure.write(&failures_dir)?;
+                        if !self.is_machine_readable() {
+                            println!("Saved the counterexample of {test} to {}", path.display());
+                        }
+                    }
+                }
+            }
+        }
+
+        if self.profile_tests && !self.is_machine_readable() {
+            shell::println("\nTest Profile:")?;
+            shell::println(profile::profile_table(&outcome, self.profile_sort))?;
+        }
+
+        if let Some(reporter) = self.reporter {
+            let locations = TestLocations::new(&output, project_root);
+            println!("{}", reporter.report(&outcome, &locations));
+        }
+
         if should_debug {
             let tests = outcome.clone().into_tests();
             // todo(onbjerg): why do we bother decoding everything and having multiple decoders if
@@ -340,9 +519,11 @@ impl TestArgs {
             }
         }
 
-        if self.json {
+        if self.is_machine_readable() {
             let results = runner.test_collect(filter, test_options).await;
-            println!("{}", serde_json::to_string(&results)?);
+            if self.json {
+                println!("{}", serde_json::to_string(&results)?);
+            }
             return Ok(TestOutcome::new(results, self.allow_failure));
         }
 
@@ -356,7 +537,12 @@ impl TestArgs {
         // Set up test reporter channel
         let (tx, rx) = channel::<(String, SuiteResult)>();
 
+        // The order of the shuffled tests, to print on failure
+        let test_order =
+            test_options.shuffle_seed.map(|seed| (seed, runner.test_order(filter, &test_options)));
+
         // Run tests
+        let started = Instant::now();
         let handle = tokio::task::spawn({
             let filter = filter.clone();
             async move { runner.test(&filter, tx, test_options).await }
@@ -364,6 +550,7 @@ impl TestArgs {
 
         let mut results = BTreeMap::new();
         let mut gas_report = GasReport::new(config.gas_reports, config.gas_reports_ignore);
+        let mut test_gas_report = TestGasReport::default();
         let sig_identifier =
             SignaturesIdentifier::new(Config::foundry_cache_dir(), config.offline)?;
 
@@ -451,12 +638,19 @@ impl TestArgs {
                 if self.gas_report {
                     gas_report.analyze(&result.traces, &decoder).await;
                 }
+                if self.gas_report_by_test {
+                    let test = format!("{}::{name}", get_contract_name(&contract_name));
+                    test_gas_report.analyze(&test, &result.traces, &decoder).await;
+                }
 
                 // If the test failed, we want to stop processing the rest of the tests
                 if self.fail_fast && result.status == TestStatus::Failure {
                     break 'outer
                 }
             }
+            if let Some(failure) = &suite_result.failure {
+                shell::println(format!("{} {failure}", Paint::red("[FAIL]")))?;
+            }
             let block_outcome = TestOutcome::new(
                 [(contract_name.clone(), suite_result)].into(),
                 self.allow_failure,
@@ -473,9 +667,22 @@ impl TestArgs {
             }
         }
 
+        if let Some((seed, order)) = test_order.filter(|_| total_failed > 0) {
+            shell::println(format!("\nTests ran in this order (--shuffle --seed {seed}):"))?;
+            for (contract, tests) in order {
+                shell::println(format!("  {contract}"))?;
+                for test in tests {
+                    shell::println(format!("    {test}"))?;
+                }
+            }
+        }
+
         if self.gas_report {
             shell::println(gas_report.finalize())?;
         }
+        if self.gas_report_by_test {
+            shell::println(test_gas_report)?;
+        }
 
         let num_test_suites = results.len();
 
@@ -487,6 +694,14 @@ impl TestArgs {
                 total_skipped,
             ))?;
 
+            // Report the time spent on this shard, to help rebalance shards across machines
+            if let Some(shard) = filter.args().shard {
+                shell::println(format!(
+                    "Shard {shard}: ran {num_test_suites} test suites in {:.2?}",
+                    started.elapsed()
+                ))?;
+            }
+
             if self.summary {
                 let mut summary_table = TestSummaryReporter::new(self.detailed);
                 shell::println("\n\nTest Summary:")?;
@@ -507,11 +722,27 @@ impl TestArgs {
         Ok(TestOutcome::new(results, self.allow_failure))
     }
 
+    /// Returns true if the test results are printed in a machine readable format, instead of the
+    /// regular output.
+    fn is_machine_readable(&self) -> bool {
+        self.json || self.reporter == Some(TestReporter::Junit)
+    }
+
     /// Returns the flattened [`FilterArgs`] arguments merged with [`Config`].
     pub fn filter(&self, config: &Config) -> ProjectPathsAwareFilter {
         self.filter.clone().merge_with_config(config)
     }
 
+    /// Returns the cassette of `--record-rpc` or `--replay-rpc`, if any.
+    fn rpc_cassette(&self) -> Option<RpcCassetteConfig> {
+        let (mode, dir) = match (&self.record_rpc, &self.replay_rpc) {
+            (Some(dir), _) => (RpcCassetteMode::Record, dir),
+            (_, Some(dir)) => (RpcCassetteMode::Replay, dir),
+            (None, None) => return None,
+        };
+        Some(RpcCassetteConfig { mode, dir: dir.clone() })
+    }
+
     /// Returns whether `BuildArgs` was configured with `--watch`
     pub fn is_watch(&self) -> bool {
         self.watch.watch.is_some()
@@ -542,6 +773,12 @@ impl Provider for TestArgs {
         if let Some(fuzz_runs) = self.fuzz_runs {
             fuzz_dict.insert("runs".to_string(), fuzz_runs.into());
         }
+        if let Some(fuzz_timeout) = self.fuzz_timeout {
+            fuzz_dict.insert("timeout".to_string(), fuzz_timeout.into());
+        }
+        if let Some(fuzz_workers) = self.fuzz_workers {
+            fuzz_dict.insert("workers".to_string(), fuzz_workers.into());
+        }
         dict.insert("fuzz".to_string(), fuzz_dict.into());
 
         if let Some(etherscan_api_key) =
@@ -626,10 +863,16 @@ impl TestOutcome {
             .map(|(artifact_id, (signature, result))| Test { artifact_id, signature, result })
     }
 
+    /// Returns an iterator over the suites which failed outside of their tests, and the reason.
+    pub fn suite_failures(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.results.iter().filter_map(|(name, suite)| Some((name, suite.failure.as_ref()?)))
+    }
+
     /// Checks if there are any failures and failures are disallowed
     pub fn ensure_ok(&self) -> Result<()> {
         let failures = self.failures().count();
-        if self.allow_failure || failures == 0 {
+        let suite_failures = self.suite_failures().count();
+        if self.allow_failure || (failures == 0 && suite_failures == 0) {
             return Ok(());
         }
 
@@ -641,6 +884,9 @@ impl TestOutcome {
         shell::println("")?;
         shell::println("Failing tests:")?;
         for (suite_name, suite) in self.results.iter() {
+            if let Some(failure) = &suite.failure {
+                shell::println(format!("Suite {suite_name} failed: {failure}"))?;
+            }
             let failures = suite.failures().count();
             if failures == 0 {
                 continue;
@@ -671,7 +917,11 @@ impl TestOutcome {
 
     pub fn summary(&self) -> String {
         let failed = self.failures().count();
-        let result = if failed == 0 { Paint::green("ok") } else { Paint::red("FAILED") };
+        let result = if failed == 0 && self.suite_failures().next().is_none() {
+            Paint::green("ok")
+        } else {
+            Paint::red("FAILED")
+        };
         format!(
             "Test result: {}. {} passed; {} failed; {} skipped; finished in {:.2?}",
             result,
@@ -744,7 +994,83 @@ mod tests {
         assert!(args.fuzz_seed.is_some());
     }
 
+    #[test]
+    fn fuzz_timeout() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--fuzz-timeout", "300"]);
+        assert_eq!(args.fuzz_timeout, Some(300));
+    }
+
+    #[test]
+    fn replay() {
+        let args: TestArgs =
+            TestArgs::parse_from(["foundry-cli", "--replay", "cache/fuzz/failures/Test.json"]);
+        assert_eq!(args.replay, Some(PathBuf::from("cache/fuzz/failures/Test.json")));
+    }
+
     // <https://github.com/foundry-rs/foundry/issues/5913>
+    #[test]
+    fn rerun() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--rerun"]);
+        assert!(args.rerun);
+    }
+
+    #[test]
+    fn shard() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--shard", "2/8"]);
+        assert_eq!(args.filter.shard, Some(filter::Shard { index: 2, count: 8 }));
+
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--shard", "0/8"]).is_err());
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--shard", "9/8"]).is_err());
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--shard", "2"]).is_err());
+
+        // Every contract belongs to exactly one shard
+        let shards = (1..=8).map(|index| filter::Shard { index, count: 8 }).collect::<Vec<_>>();
+        for name in ["CounterTest", "ERC20Test", "InvariantTest", "ForkTest"] {
+            assert_eq!(shards.iter().filter(|shard| shard.contains(name)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn reporter() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--reporter", "junit"]);
+        assert_eq!(args.reporter, Some(TestReporter::Junit));
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--reporter", "gha"]);
+        assert_eq!(args.reporter, Some(TestReporter::Gha));
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--reporter", "gha", "--json"]).is_err());
+    }
+
+    #[test]
+    fn profile_tests() {
+        let args: TestArgs =
+            TestArgs::parse_from(["foundry-cli", "--profile-tests", "--profile-sort", "rpc"]);
+        assert!(args.profile_tests);
+        assert_eq!(args.profile_sort, ProfileSort::Rpc);
+        assert!(TestArgs::try_parse_from(["foundry-cli", "--profile-sort", "evm"]).is_err());
+    }
+
+    #[test]
+    fn rpc_cassette() {
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--record-rpc", "cassettes"]);
+        assert_eq!(
+            args.rpc_cassette(),
+            Some(RpcCassetteConfig { mode: RpcCassetteMode::Record, dir: "cassettes".into() })
+        );
+        let args: TestArgs = TestArgs::parse_from(["foundry-cli", "--replay-rpc", "cassettes"]);
+        assert_eq!(
+            args.rpc_cassette(),
+            Some(RpcCassetteConfig { mode: RpcCassetteMode::Replay, dir: "cassettes".into() })
+        );
+        assert_eq!(TestArgs::parse_from(["foundry-cli"]).rpc_cassette(), None);
+        assert!(TestArgs::try_parse_from([
+            "foundry-cli",
+            "--record-rpc",
+            "a",
+            "--replay-rpc",
+            "b"
+        ])
+        .is_err());
+    }
+
     #[test]
     fn issue_5913() {
         let args: TestArgs =
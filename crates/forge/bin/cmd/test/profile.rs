@@ -0,0 +1,63 @@
+use crate::cmd::test::TestOutcome;
+use clap::ValueEnum;
+use comfy_table::{modifiers::UTF8_ROUND_CORNERS, Attribute, Cell, CellAlignment, Row, Table};
+use forge::result::TestProfile;
+use std::cmp::Reverse;
+
+/// The column to sort the test profiles by, in descending order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ProfileSort {
+    /// Wall-clock time of the test.
+    #[default]
+    Duration,
+    /// Time spent executing the EVM.
+    Evm,
+    /// Time spent waiting on fork RPC requests.
+    Rpc,
+    /// Number of cheatcode calls.
+    Cheatcodes,
+}
+
+impl ProfileSort {
+    /// Returns the key to sort the given profile by.
+    fn key(&self, profile: &TestProfile) -> u128 {
+        match self {
+            Self::Duration => profile.duration.as_nanos(),
+            Self::Evm => profile.evm_time.as_nanos(),
+            Self::Rpc => profile.rpc_time.as_nanos(),
+            Self::Cheatcodes => profile.cheatcode_calls.into(),
+        }
+    }
+}
+
+/// Builds a table of the execution profiles of the tests of the outcome, slowest first.
+pub fn profile_table(outcome: &TestOutcome, sort: ProfileSort) -> Table {
+    let mut profiles = outcome
+        .results
+        .iter()
+        .flat_map(|(contract, suite)| {
+            let contract = contract.rsplit(':').next().unwrap_or(contract);
+            suite.tests().filter_map(move |(test, result)| Some((contract, test, result.profile?)))
+        })
+        .collect::<Vec<_>>();
+    profiles.sort_by_key(|(_, _, profile)| Reverse(sort.key(profile)));
+
+    let mut table = Table::new();
+    table.apply_modifier(UTF8_ROUND_CORNERS);
+    table.set_header(
+        ["Contract", "Test", "Duration", "EVM Time", "RPC Time", "Cheatcode Calls"].map(|header| {
+            Cell::new(header).set_alignment(CellAlignment::Center).add_attribute(Attribute::Bold)
+        }),
+    );
+    for (contract, test, profile) in profiles {
+        table.add_row(Row::from(vec![
+            Cell::new(contract),
+            Cell::new(test),
+            Cell::new(format!("{:.2?}", profile.duration)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.2?}", profile.evm_time)).set_alignment(CellAlignment::Right),
+            Cell::new(format!("{:.2?}", profile.rpc_time)).set_alignment(CellAlignment::Right),
+            Cell::new(profile.cheatcode_calls).set_alignment(CellAlignment::Right),
+        ]));
+    }
+    table
+}
@@ -0,0 +1,211 @@
+use super::TestOutcome;
+use clap::ValueEnum;
+use forge::{
+    coverage::xml_escape,
+    fuzz::CounterExample,
+    result::{TestResult, TestStatus},
+};
+use foundry_compilers::{
+    artifacts::{ast::NodeType, Node},
+    ProjectCompileOutput,
+};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The format to report test results in, to be rendered natively by CI systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TestReporter {
+    /// JUnit XML, printed instead of the regular output.
+    Junit,
+    /// GitHub Actions `::error` annotations for the failed tests, printed after the regular
+    /// output.
+    Gha,
+}
+
+impl TestReporter {
+    /// Renders the test results of the given outcome.
+    pub fn report(&self, outcome: &TestOutcome, locations: &TestLocations) -> String {
+        match self {
+            Self::Junit => junit(outcome, locations),
+            Self::Gha => gha(outcome, locations),
+        }
+    }
+}
+
+/// The source locations of the test functions, used to map failures back to the source.
+///
+/// Maps the identifier of each test contract, `<source path>:<contract name>`, to the lines its
+/// functions are defined at.
+#[derive(Clone, Debug, Default)]
+pub struct TestLocations(HashMap<String, (PathBuf, HashMap<String, usize>)>);
+
+impl TestLocations {
+    /// Collects the locations of the functions of all contracts from the AST of the artifacts.
+    pub fn new(output: &ProjectCompileOutput, root: &Path) -> Self {
+        let mut locations = HashMap::new();
+        let mut sources = HashMap::new();
+        for (id, artifact) in output.artifact_ids() {
+            let Some(ast) = &artifact.ast else { continue };
+            let path = id.source.as_path();
+            let path = path.strip_prefix(root).unwrap_or(path).to_path_buf();
+            let Some(contract) = ast.nodes.iter().find(|node| {
+                node.node_type == NodeType::ContractDefinition &&
+                    node_name(node) == Some(id.name.as_str())
+            }) else {
+                continue
+            };
+
+            let source = sources
+                .entry(path.clone())
+                .or_insert_with(|| fs::read_to_string(root.join(&path)).unwrap_or_default());
+            let lines = contract
+                .nodes
+                .iter()
+                .filter(|node| node.node_type == NodeType::FunctionDefinition)
+                .filter_map(|node| {
+                    let name = node_name(node)?;
+                    let line = source.get(..node.src.start)?.matches('\n').count() + 1;
+                    Some((name.to_string(), line))
+                })
+                .collect();
+            locations.insert(format!("{}:{}", path.display(), id.name), (path, lines));
+        }
+        Self(locations)
+    }
+
+    /// Returns the source file and line of the given test function of the given contract.
+    pub fn get(&self, contract: &str, signature: &str) -> Option<(&Path, usize)> {
+        let (path, lines) = self.0.get(contract)?;
+        let name = signature.split('(').next().unwrap_or(signature);
+        Some((path.as_path(), *lines.get(name)?))
+    }
+}
+
+/// Returns the name of a definition node.
+fn node_name(node: &Node) -> Option<&str> {
+    match node.other.get("name")? {
+        Value::String(name) => Some(name),
+        _ => None,
+    }
+}
+
+/// Renders the outcome as a JUnit XML report, with a test suite per test contract.
+fn junit(outcome: &TestOutcome, locations: &TestLocations) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"forge\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.3}\">",
+        outcome.tests().count(),
+        outcome.failures().count(),
+        outcome.skips().count(),
+        outcome.duration().as_secs_f64(),
+    );
+    for (contract, suite) in &outcome.results {
+        let failures = suite.failures().count();
+        let skipped = suite.tests().filter(|(_, t)| t.status == TestStatus::Skipped).count();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\" time=\"{:.3}\">",
+            xml_escape(contract),
+            suite.test_results.len(),
+            suite.duration.as_secs_f64(),
+        );
+        for (signature, result) in &suite.test_results {
+            let _ = write!(
+                xml,
+                "    <testcase name=\"{}\" classname=\"{}\"",
+                xml_escape(signature),
+                xml_escape(contract)
+            );
+            if let Some((path, line)) = locations.get(contract, signature) {
+                let _ = write!(
+                    xml,
+                    " file=\"{}\" line=\"{line}\"",
+                    xml_escape(&path.display().to_string())
+                );
+            }
+            match result.status {
+                TestStatus::Success => xml.push_str("/>\n"),
+                TestStatus::Skipped => xml.push_str(">\n      <skipped/>\n    </testcase>\n"),
+                TestStatus::Failure => {
+                    let _ = writeln!(
+                        xml,
+                        ">\n      <failure message=\"{}\">{}</failure>",
+                        xml_escape(&failure_message(result)),
+                        xml_escape(&result.decoded_logs.join("\n"))
+                    );
+                    xml.push_str("    </testcase>\n");
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>");
+    xml
+}
+
+/// Renders the failures of the outcome as GitHub Actions error annotations.
+fn gha(outcome: &TestOutcome, locations: &TestLocations) -> String {
+    let mut annotations = String::new();
+    for (contract, suite) in &outcome.results {
+        for (signature, result) in suite.failures() {
+            let mut properties = String::new();
+            if let Some((path, line)) = locations.get(contract, signature) {
+                let _ = write!(
+                    properties,
+                    " file={},line={line},",
+                    gha_escape_property(&path.display().to_string())
+                );
+            } else {
+                properties.push(' ');
+            }
+            let _ = writeln!(
+                annotations,
+                "::error{properties}title={}::{}",
+                gha_escape_property(&format!("{contract}:{signature}")),
+                gha_escape_data(&failure_message(result))
+            );
+        }
+    }
+    annotations
+}
+
+/// Returns the message describing why the test failed.
+fn failure_message(result: &TestResult) -> String {
+    let mut message = result.reason.clone().unwrap_or_else(|| "test failed".to_string());
+    match &result.counterexample {
+        Some(CounterExample::Single(call)) => {
+            let _ = write!(message, "; counterexample: {call}");
+        }
+        Some(CounterExample::Sequence(sequence)) => {
+            let _ = write!(message, "; counterexample: sequence of {} calls", sequence.len());
+        }
+        None => {}
+    }
+    message
+}
+
+fn gha_escape_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn gha_escape_property(s: &str) -> String {
+    gha_escape_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes() {
+        assert_eq!(xml_escape("a < b && \"c\""), "a &lt; b &amp;&amp; &quot;c&quot;");
+        assert_eq!(gha_escape_data("50%\nfailed"), "50%25%0Afailed");
+        assert_eq!(gha_escape_property("test/A.t.sol:ATest"), "test/A.t.sol%3AATest");
+    }
+}
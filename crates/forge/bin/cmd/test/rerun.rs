@@ -0,0 +1,195 @@
+use super::{filter::FilterArgs, TestOutcome};
+use eyre::Result;
+use forge::result::TestStatus;
+use foundry_compilers::FileFilter;
+use foundry_config::Config;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+};
+
+/// The tests that failed in the last run, to be run again with `forge test --rerun`.
+///
+/// Maps the identifier of each test contract, `<source path>:<contract name>`, to the signatures
+/// of its failed test functions.
+///
+/// The failures of the tests which weren't run are kept, so running a subset of the tests doesn't
+/// forget the other failures.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TestFailures(BTreeMap<String, BTreeSet<String>>);
+
+impl TestFailures {
+    /// Returns the path of the file the failures of the last run are saved to.
+    pub fn path(config: &Config) -> PathBuf {
+        config.cache_path.join("test-failures")
+    }
+
+    /// Updates the failures with the tests run in the given outcome: the failed tests are added,
+    /// and the others removed.
+    pub fn update(&mut self, outcome: &TestOutcome) {
+        for (contract, suite_result) in &outcome.results {
+            let failures = self.0.entry(contract.clone()).or_default();
+            for (signature, result) in &suite_result.test_results {
+                if result.status == TestStatus::Failure {
+                    failures.insert(signature.clone());
+                } else {
+                    failures.remove(signature);
+                }
+            }
+            if failures.is_empty() {
+                self.0.remove(contract);
+            }
+        }
+    }
+
+    /// Returns whether the test with the given signature of the contract with the given
+    /// identifier failed.
+    pub fn contains(&self, contract_id: &str, test_signature: &str) -> bool {
+        self.0.get(contract_id).map_or(false, |tests| tests.contains(test_signature))
+    }
+
+    /// Reads the failures from the given file, if it exists.
+    pub fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default())
+        }
+        Ok(foundry_common::fs::read_json_file(path)?)
+    }
+
+    /// Writes the failures to the given file, or removes it if there are none.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if self.is_empty() {
+            if path.exists() {
+                foundry_common::fs::remove_file(path)?;
+            }
+            return Ok(())
+        }
+        if let Some(parent) = path.parent() {
+            foundry_common::fs::create_dir_all(parent)?;
+        }
+        foundry_common::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Returns true if no test failed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Restricts the given filter to the failed tests and their contracts.
+    pub fn apply(&self, filter: &mut FilterArgs) -> Result<()> {
+        let contracts = self
+            .0
+            .keys()
+            .map(|id| regex::escape(id.rsplit(':').next().unwrap_or(id)))
+            .collect::<BTreeSet<_>>();
+        let tests = self
+            .0
+            .values()
+            .flatten()
+            .map(|signature| regex::escape(signature.split('(').next().unwrap_or(signature)))
+            .collect::<BTreeSet<_>>();
+
+        // Test functions are matched by both their name and their signature, the exact tests of
+        // each contract are matched with `contains`
+        filter.contract_pattern = Some(Regex::new(&format!(
+            "^(?:{})$",
+            contracts.into_iter().collect::<Vec<_>>().join("|")
+        ))?);
+        filter.test_pattern = Some(Regex::new(&format!(
+            r"^(?:{})(?:\(|$)",
+            tests.into_iter().collect::<Vec<_>>().join("|")
+        ))?);
+        Ok(())
+    }
+
+    /// Returns a filter that only compiles the source files of the failed tests.
+    pub fn sources_filter(&self) -> RerunFilter {
+        let sources = self
+            .0
+            .keys()
+            .filter_map(|id| id.rsplit_once(':').map(|(source, _)| PathBuf::from(source)))
+            .collect();
+        RerunFilter { sources }
+    }
+}
+
+/// A [`FileFilter`] matching the source files of the tests to run again.
+#[derive(Clone, Debug)]
+pub struct RerunFilter {
+    sources: BTreeSet<PathBuf>,
+}
+
+impl FileFilter for RerunFilter {
+    fn is_match(&self, file: &Path) -> bool {
+        self.sources.iter().any(|source| file.ends_with(source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use forge::{
+        result::{SuiteResult, TestResult},
+        TestFilter,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn rerun_filter() {
+        let failures = TestFailures(BTreeMap::from([
+            ("test/A.t.sol:ATest".to_string(), BTreeSet::from(["testFoo()".to_string()])),
+            ("test/B.t.sol:BTest".to_string(), BTreeSet::from(["testBar(uint256)".to_string()])),
+        ]));
+
+        let mut filter = FilterArgs::parse_from(["foundry-cli"]);
+        failures.apply(&mut filter).unwrap();
+        assert!(filter.matches_contract("ATest"));
+        assert!(filter.matches_contract("BTest"));
+        assert!(!filter.matches_contract("ATestFoo"));
+        assert!(filter.matches_test("testFoo"));
+        assert!(filter.matches_test("testBar(uint256)"));
+        assert!(!filter.matches_test("testFooBar"));
+
+        // only the failed tests of each contract are run again
+        assert!(failures.contains("test/A.t.sol:ATest", "testFoo()"));
+        assert!(!failures.contains("test/A.t.sol:ATest", "testBar(uint256)"));
+        assert!(!failures.contains("test/B.t.sol:BTest", "testBar(address)"));
+
+        let sources = failures.sources_filter();
+        assert!(sources.is_match(Path::new("/root/project/test/A.t.sol")));
+        assert!(!sources.is_match(Path::new("/root/project/test/C.t.sol")));
+    }
+
+    #[test]
+    fn updates_failures() {
+        let mut failures = TestFailures(BTreeMap::from([
+            ("test/A.t.sol:ATest".to_string(), BTreeSet::from(["testFoo()".to_string()])),
+            ("test/B.t.sol:BTest".to_string(), BTreeSet::from(["testBar()".to_string()])),
+        ]));
+
+        // only `ATest` was run, with `testFoo` passing and `testBaz` failing
+        let result = |status| TestResult { status, ..Default::default() };
+        let suite = SuiteResult::new(
+            Duration::ZERO,
+            BTreeMap::from([
+                ("testFoo()".to_string(), result(TestStatus::Success)),
+                ("testBaz()".to_string(), result(TestStatus::Failure)),
+            ]),
+            vec![],
+        );
+        let outcome = TestOutcome {
+            allow_failure: false,
+            results: BTreeMap::from([("test/A.t.sol:ATest".to_string(), suite)]),
+        };
+        failures.update(&outcome);
+
+        assert!(!failures.contains("test/A.t.sol:ATest", "testFoo()"));
+        assert!(failures.contains("test/A.t.sol:ATest", "testBaz()"));
+        assert!(failures.contains("test/B.t.sol:BTest", "testBar()"));
+    }
+}
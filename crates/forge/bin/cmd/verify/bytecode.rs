@@ -0,0 +1,210 @@
+use crate::cmd::{
+    coverage::dummy_link_deployed_bytecode, script::transaction::TransactionWithMetadata,
+    size::metadata_len,
+};
+use alloy_primitives::Address;
+use clap::{Parser, ValueHint};
+use ethers_providers::Middleware;
+use eyre::{Context, Result};
+use foundry_cli::{
+    opts::{CoreBuildArgs, RpcOpts},
+    utils::{self, LoadConfig},
+};
+use foundry_common::{compile::ProjectCompiler, fs, types::ToEthers};
+use foundry_compilers::{artifacts::CompactDeployedBytecode, info::ContractInfo, ArtifactId};
+use revm_inspectors::tracing::types::CallKind;
+use serde::Deserialize;
+use std::path::PathBuf;
+use yansi::Paint;
+
+/// CLI arguments for `forge verify-bytecode`.
+#[derive(Clone, Debug, Parser)]
+pub struct VerifyBytecodeArgs {
+    /// The address of the contract to verify.
+    #[clap(required_unless_present = "all", conflicts_with = "all")]
+    pub address: Option<Address>,
+
+    /// The contract identifier in the form `(<path>:)?<contractname>`.
+    #[clap(required_unless_present = "all", conflicts_with = "all")]
+    pub contract: Option<ContractInfo>,
+
+    /// Verify every contract deployed by the transactions of the broadcast file, e.g.
+    /// `broadcast/Deploy.s.sol/1/run-latest.json`.
+    #[clap(long, value_hint = ValueHint::FilePath, value_name = "BROADCAST_FILE")]
+    pub all: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub rpc: RpcOpts,
+
+    #[clap(flatten)]
+    pub build: CoreBuildArgs,
+}
+
+/// The transactions of a broadcast file.
+#[derive(Deserialize)]
+struct Broadcast {
+    transactions: Vec<TransactionWithMetadata>,
+}
+
+impl VerifyBytecodeArgs {
+    /// Checks that the runtime code deployed at the addresses was compiled from the local
+    /// artifacts.
+    pub async fn run(self) -> Result<()> {
+        let mut config = self.build.try_load_config_emit_warnings()?;
+        config.eth_rpc_url = self.rpc.url(Some(&config))?.map(Into::into);
+        let provider = utils::get_provider(&config)?;
+
+        let project = config.project()?;
+        let output = ProjectCompiler::new().quiet(true).compile(&project)?;
+        let artifacts = output.artifact_ids().collect::<Vec<_>>();
+
+        // The contracts to verify, with the name of the artifact they were deployed from if known
+        let contracts = match &self.all {
+            Some(path) => {
+                let broadcast: Broadcast = fs::read_json_file(path)
+                    .wrap_err_with(|| format!("Failed to read broadcast {}", path.display()))?;
+                deployed_contracts(broadcast.transactions)
+            }
+            None => vec![(
+                self.address.expect("required by clap"),
+                self.contract.as_ref().map(|contract| contract.to_string()),
+            )],
+        };
+        eyre::ensure!(!contracts.is_empty(), "No contracts were deployed by the broadcast");
+
+        let mut mismatches = 0;
+        for (address, name) in &contracts {
+            let code = provider.get_code(address.to_ethers(), None).await?;
+            let label = name.clone().unwrap_or_else(|| "<unknown>".to_string());
+            if code.is_empty() {
+                println!("{} {label} at {address}: no code deployed", Paint::red("✗"));
+                mismatches += 1;
+                continue
+            }
+
+            let matched = artifacts.iter().find(|(id, artifact)| {
+                name.as_deref().map_or(true, |name| is_named(id, name)) &&
+                    artifact
+                        .deployed_bytecode
+                        .clone()
+                        .map_or(false, |deployed| matches_runtime_code(deployed.into(), &code))
+            });
+            match matched {
+                Some((id, _)) => println!(
+                    "{} {} at {address} matches the local artifact",
+                    Paint::green("✓"),
+                    id.identifier()
+                ),
+                None => {
+                    println!(
+                        "{} {label} at {address} doesn't match any local artifact",
+                        Paint::red("✗")
+                    );
+                    mismatches += 1;
+                }
+            }
+        }
+
+        if mismatches > 0 {
+            eyre::bail!("{mismatches} of {} contracts don't match their artifacts", contracts.len())
+        }
+        Ok(())
+    }
+}
+
+/// Returns the contracts deployed by the transactions, directly or by the contracts they called,
+/// with the name of the contract if it's known.
+fn deployed_contracts(
+    transactions: Vec<TransactionWithMetadata>,
+) -> Vec<(Address, Option<String>)> {
+    let mut contracts = vec![];
+    for tx in transactions {
+        if matches!(tx.opcode, CallKind::Create | CallKind::Create2) {
+            if let Some(address) = tx.contract_address {
+                contracts.push((address, tx.contract_name.filter(|name| !name.is_empty())));
+            }
+        }
+        contracts.extend(tx.additional_contracts.into_iter().map(|c| (c.address, None)));
+    }
+    contracts
+}
+
+/// Returns whether the artifact is the contract with the name or identifier.
+fn is_named(id: &ArtifactId, name: &str) -> bool {
+    match name.rsplit_once(':') {
+        Some((path, name)) => id.name == name && id.source.ends_with(path),
+        None => id.name == name,
+    }
+}
+
+/// Returns whether the runtime code deployed onchain was compiled from the artifact.
+///
+/// The metadata hash appended by solc, the values of the immutables and the addresses of the
+/// linked libraries are ignored, since they depend on the deployment rather than the sources.
+fn matches_runtime_code(deployed: CompactDeployedBytecode, onchain: &[u8]) -> bool {
+    let Some(local) = dummy_link_deployed_bytecode(deployed.clone()).filter(|c| !c.is_empty())
+    else {
+        return false
+    };
+    if local.len() != onchain.len() {
+        return false
+    }
+
+    let mut onchain = onchain.to_vec();
+    let mut local = local.to_vec();
+    let links = deployed
+        .bytecode
+        .iter()
+        .flat_map(|bytecode| bytecode.link_references.values())
+        .flat_map(|libraries| libraries.values())
+        .flatten();
+    let immutables = deployed.immutable_references.values().flatten();
+    for offsets in links.chain(immutables) {
+        let range = offsets.start as usize..(offsets.start + offsets.length) as usize;
+        if let (Some(onchain), Some(local)) = (onchain.get_mut(range.clone()), local.get_mut(range))
+        {
+            onchain.fill(0);
+            local.fill(0);
+        }
+    }
+
+    let onchain = &onchain[..onchain.len() - metadata_len(&onchain)];
+    let local = &local[..local.len() - metadata_len(&local)];
+    onchain == local
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use foundry_compilers::artifacts::{BytecodeObject, CompactBytecode, Offsets};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn matches_runtime_code_ignoring_immutables_and_metadata() {
+        // PUSH32 <immutable> STOP, followed by a metadata map of 3 bytes
+        let mut code = vec![0x7f];
+        code.extend([0; 32]);
+        code.extend([0x00, 0xa1, 0x01, 0x02, 0x00, 0x03]);
+        let deployed = CompactDeployedBytecode {
+            bytecode: Some(CompactBytecode {
+                object: BytecodeObject::Bytecode(code.clone().into()),
+                source_map: None,
+                link_references: BTreeMap::new(),
+            }),
+            immutable_references: BTreeMap::from([(
+                "1".to_string(),
+                vec![Offsets { start: 1, length: 32 }],
+            )]),
+        };
+
+        let mut onchain = code.clone();
+        onchain[1..33].fill(0xaa);
+        let len = onchain.len();
+        onchain[len - 4] = 0xff;
+        assert!(matches_runtime_code(deployed.clone(), &onchain));
+
+        onchain[33] = 0x01;
+        assert!(!matches_runtime_code(deployed.clone(), &onchain));
+        assert!(!matches_runtime_code(deployed, &code[..33]));
+    }
+}
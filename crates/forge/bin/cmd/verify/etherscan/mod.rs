@@ -24,6 +24,7 @@ use std::{
 
 mod flatten;
 mod standard_json;
+pub use standard_json::standard_json_input;
 
 pub static RE_BUILD_COMMIT: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?P<commit>commit\.[0-9,a-f]{8})").unwrap());
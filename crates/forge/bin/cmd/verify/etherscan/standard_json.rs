@@ -15,22 +15,7 @@ impl EtherscanSourceProvider for EtherscanStandardJsonSource {
         target: &Path,
         version: &Version,
     ) -> Result<(String, String, CodeFormat)> {
-        let mut input: StandardJsonCompilerInput = project
-            .standard_json_input(target)
-            .wrap_err("Failed to get standard json input")?
-            .normalize_evm_version(version);
-
-        input.settings.libraries.libs = input
-            .settings
-            .libraries
-            .libs
-            .into_iter()
-            .map(|(f, libs)| (f.strip_prefix(project.root()).unwrap_or(&f).to_path_buf(), libs))
-            .collect();
-
-        // remove all incompatible settings
-        input.settings.sanitize(version);
-
+        let input = standard_json_input(project, target, version)?;
         let source =
             serde_json::to_string(&input).wrap_err("Failed to parse standard json input")?;
 
@@ -44,3 +29,29 @@ impl EtherscanSourceProvider for EtherscanStandardJsonSource {
         Ok((source, name, CodeFormat::StandardJsonInput))
     }
 }
+
+/// Returns the standard json input that compiles the target with the given compiler version,
+/// containing only the sources it depends on, with the project's remappings and settings.
+pub fn standard_json_input(
+    project: &Project,
+    target: &Path,
+    version: &Version,
+) -> Result<StandardJsonCompilerInput> {
+    let mut input: StandardJsonCompilerInput = project
+        .standard_json_input(target)
+        .wrap_err("Failed to get standard json input")?
+        .normalize_evm_version(version);
+
+    input.settings.libraries.libs = input
+        .settings
+        .libraries
+        .libs
+        .into_iter()
+        .map(|(f, libs)| (f.strip_prefix(project.root()).unwrap_or(&f).to_path_buf(), libs))
+        .collect();
+
+    // remove all incompatible settings
+    input.settings.sanitize(version);
+
+    Ok(input)
+}
@@ -7,9 +7,13 @@ use foundry_compilers::info::ContractInfo;
 use foundry_config::{figment, impl_figment_convert, impl_figment_convert_cast, Config};
 use provider::VerificationProviderType;
 use reqwest::Url;
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
+
+mod bytecode;
+pub use bytecode::VerifyBytecodeArgs;
 
 mod etherscan;
+pub use etherscan::standard_json_input;
 use etherscan::EtherscanVerificationProvider;
 
 pub mod provider;
@@ -17,6 +21,8 @@ use provider::VerificationProvider;
 
 mod sourcify;
 
+mod sourcify_v2;
+
 /// Verification provider arguments
 #[derive(Clone, Debug, Parser)]
 pub struct VerifierArgs {
@@ -155,9 +161,18 @@ impl VerifyArgs {
             return Ok(())
         }
 
+        let fallbacks = config
+            .get_verifier_fallbacks(chain)
+            .iter()
+            .map(|verifier| VerificationProviderType::from_str(verifier).map_err(eyre::Error::msg))
+            .filter(|verifier| verifier.as_ref().map_or(true, |v| *v != self.verifier.verifier))
+            .collect::<Result<Vec<_>>>()?;
+        let args = (!fallbacks.is_empty()).then(|| self.clone());
+
         let verifier_url = self.verifier.verifier_url.clone();
         println!("Start verifying contract `{}` deployed on {chain}", self.address);
-        self.verifier.verifier.client(&self.etherscan.key())?.verify(self).await.map_err(|err| {
+        let mut verifier = self.verifier.verifier.clone();
+        let mut result = self.verifier.verifier.client(&self.etherscan.key())?.verify(self).await.map_err(|err| {
             if let Some(verifier_url) = verifier_url {
                  match Url::parse(&verifier_url) {
                     Ok(url) => {
@@ -176,7 +191,21 @@ impl VerifyArgs {
             }
 
             err
-        })
+        });
+
+        // Try the fallback verifiers configured for the chain, in order, until one succeeds
+        for fallback in fallbacks {
+            let Err(err) = &result else { break };
+            println!("Verification with {verifier} failed: {err}. Falling back to {fallback}.");
+            let mut args = args.clone().expect("cloned when there are fallbacks");
+            args.verifier = VerifierArgs { verifier: fallback.clone(), verifier_url: None };
+            result = match args.verification_provider() {
+                Ok(mut provider) => provider.verify(args).await,
+                Err(err) => Err(err),
+            };
+            verifier = fallback;
+        }
+        result
     }
 
     /// Returns the configured verification provider
@@ -193,6 +222,8 @@ pub struct VerifyCheckArgs {
     /// For Etherscan - Submission GUID.
     ///
     /// For Sourcify - Contract Address.
+    ///
+    /// For Sourcify v2 - Contract Address or Verification Job ID.
     id: String,
 
     #[clap(flatten)]
@@ -256,4 +287,20 @@ mod tests {
         ]);
         assert!(args.via_ir);
     }
+
+    #[test]
+    fn can_parse_sourcify_v2_verifier() {
+        let args: VerifyArgs = VerifyArgs::parse_from([
+            "foundry-cli",
+            "0x0000000000000000000000000000000000000000",
+            "src/Domains.sol:Domains",
+            "--verifier",
+            "sourcify-v2",
+        ]);
+        assert_eq!(args.verifier.verifier, VerificationProviderType::SourcifyV2);
+        assert_eq!(
+            VerificationProviderType::from_str("s2").unwrap(),
+            VerificationProviderType::SourcifyV2
+        );
+    }
 }
@@ -1,6 +1,6 @@
 use super::{
-    etherscan::EtherscanVerificationProvider, sourcify::SourcifyVerificationProvider, VerifyArgs,
-    VerifyCheckArgs,
+    etherscan::EtherscanVerificationProvider, sourcify::SourcifyVerificationProvider,
+    sourcify_v2::SourcifyV2VerificationProvider, VerifyArgs, VerifyCheckArgs,
 };
 use async_trait::async_trait;
 use eyre::Result;
@@ -32,6 +32,7 @@ impl FromStr for VerificationProviderType {
         match s {
             "e" | "etherscan" => Ok(VerificationProviderType::Etherscan),
             "s" | "sourcify" => Ok(VerificationProviderType::Sourcify),
+            "s2" | "sourcify-v2" => Ok(VerificationProviderType::SourcifyV2),
             "b" | "blockscout" => Ok(VerificationProviderType::Blockscout),
             _ => Err(format!("Unknown provider: {s}")),
         }
@@ -47,6 +48,9 @@ impl fmt::Display for VerificationProviderType {
             VerificationProviderType::Sourcify => {
                 write!(f, "sourcify")?;
             }
+            VerificationProviderType::SourcifyV2 => {
+                write!(f, "sourcify-v2")?;
+            }
             VerificationProviderType::Blockscout => {
                 write!(f, "blockscout")?;
             }
@@ -60,6 +64,8 @@ pub enum VerificationProviderType {
     #[default]
     Etherscan,
     Sourcify,
+    /// Sourcify's v2 API, which verifies the standard json input of the contract.
+    SourcifyV2,
     Blockscout,
 }
 
@@ -76,6 +82,9 @@ impl VerificationProviderType {
             VerificationProviderType::Sourcify => {
                 Ok(Box::<SourcifyVerificationProvider>::default())
             }
+            VerificationProviderType::SourcifyV2 => {
+                Ok(Box::<SourcifyV2VerificationProvider>::default())
+            }
             VerificationProviderType::Blockscout => {
                 Ok(Box::<EtherscanVerificationProvider>::default())
             }
@@ -0,0 +1,269 @@
+use super::{
+    etherscan::EtherscanVerificationProvider, provider::VerificationProvider, VerifyArgs,
+    VerifyCheckArgs,
+};
+use async_trait::async_trait;
+use eyre::{Context, Result};
+use foundry_common::retry::Retry;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub static SOURCIFY_V2_URL: &str = "https://sourcify.dev/server/";
+
+/// How often the status of a verification job is polled.
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The type that can verify a contract with the v2 API of `sourcify`, which compiles the standard
+/// json input of the contract instead of its metadata.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub struct SourcifyV2VerificationProvider;
+
+#[async_trait]
+impl VerificationProvider for SourcifyV2VerificationProvider {
+    async fn preflight_check(&mut self, args: VerifyArgs) -> Result<()> {
+        let _ = self.prepare_request(&args).await?;
+        Ok(())
+    }
+
+    async fn verify(&mut self, args: VerifyArgs) -> Result<()> {
+        let url = base_url(args.verifier.verifier_url.as_deref());
+        let chain = args.etherscan.chain.unwrap_or_default().id();
+        let client = reqwest::Client::new();
+
+        if !args.skip_is_verified_check {
+            if let Some(contract) = lookup(&client, url, chain, &args.address.to_string()).await? {
+                if let Some(status) = contract.status() {
+                    println!(
+                        "\nContract [{}] {:?} is already verified ({status}). Skipping verification.",
+                        args.contract.name,
+                        args.address.to_checksum(None)
+                    );
+                    return Ok(())
+                }
+            }
+        }
+
+        let body = self.prepare_request(&args).await?;
+        trace!(target: "forge::verify", contract = body.contract_identifier, "submitting sourcify v2 verification request");
+
+        let retry: Retry = args.retry.into();
+        let job = retry
+            .run_async(|| {
+                async {
+                    println!(
+                        "\nSubmitting verification for [{}] {:?}.",
+                        args.contract.name,
+                        args.address.to_string()
+                    );
+                    let response = client
+                        .post(format!("{url}v2/verify/{chain}/{}", args.address))
+                        .json(&body)
+                        .send()
+                        .await?;
+
+                    let status = response.status();
+                    if !status.is_success() {
+                        let error: serde_json::Value = response.json().await?;
+                        eyre::bail!(
+                            "Sourcify verification request for address ({}) failed with status code {status}\nDetails: {error:#}",
+                            args.address,
+                        );
+                    }
+                    Ok(Some(response.json::<SourcifyV2JobResponse>().await?))
+                }
+                .boxed()
+            })
+            .await?
+            .wrap_err("Sourcify didn't return a verification job")?;
+
+        println!(
+            "Submitted contract for verification:\n\tVerification job ID: `{}`",
+            job.verification_id
+        );
+        if !args.watch {
+            return Ok(())
+        }
+
+        loop {
+            let status = job_status(&client, url, &job.verification_id).await?;
+            if status.is_job_completed {
+                return process_job(status)
+            }
+            tokio::time::sleep(JOB_POLL_INTERVAL).await;
+        }
+    }
+
+    async fn check(&self, args: VerifyCheckArgs) -> Result<()> {
+        let url = base_url(args.verifier.verifier_url.as_deref());
+        let client = reqwest::Client::new();
+
+        // The ID is either the address of the contract or the ID of a verification job
+        if args.id.parse::<alloy_primitives::Address>().is_ok() {
+            let chain = args.etherscan.chain.unwrap_or_default().id();
+            match lookup(&client, url, chain, &args.id).await?.and_then(|c| c.status()) {
+                Some(status) => println!("Contract successfully verified ({status})"),
+                None => println!("Contract source code is not verified"),
+            }
+            return Ok(())
+        }
+
+        let retry: Retry = args.retry.into();
+        let status = retry
+            .run_async(|| {
+                async {
+                    let status = job_status(&client, url, &args.id).await?;
+                    if !status.is_job_completed {
+                        eyre::bail!("Verification is still pending...")
+                    }
+                    Ok(status)
+                }
+                .boxed()
+            })
+            .await?;
+        process_job(status)
+    }
+}
+
+impl SourcifyV2VerificationProvider {
+    /// Configures the API request to the sourcify v2 API using the standard json input of the
+    /// contract.
+    async fn prepare_request(&self, args: &VerifyArgs) -> Result<SourcifyV2VerifyRequest> {
+        if args.flatten {
+            eyre::bail!("Sourcify v2 verification only supports the standard json input.")
+        }
+        let verify_args =
+            EtherscanVerificationProvider::default().create_verify_request(args, None).await?;
+        Ok(SourcifyV2VerifyRequest {
+            std_json_input: serde_json::from_str(&verify_args.source)?,
+            compiler_version: verify_args.compiler_version.trim_start_matches('v').to_string(),
+            contract_identifier: verify_args.contract_name,
+            creation_transaction_hash: None,
+        })
+    }
+}
+
+/// Returns the base URL of the API, ending with a slash.
+fn base_url(verifier_url: Option<&str>) -> &str {
+    verifier_url.unwrap_or(SOURCIFY_V2_URL)
+}
+
+/// Returns the verified contract at the address, or `None` if it isn't verified.
+async fn lookup(
+    client: &reqwest::Client,
+    url: &str,
+    chain: u64,
+    address: &str,
+) -> Result<Option<SourcifyV2Contract>> {
+    let response = client.get(format!("{url}v2/contract/{chain}/{address}")).send().await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None)
+    }
+    if !response.status().is_success() {
+        eyre::bail!("Failed to request verification status with status code {}", response.status());
+    }
+    Ok(Some(response.json().await?))
+}
+
+/// Returns the status of the verification job.
+async fn job_status(
+    client: &reqwest::Client,
+    url: &str,
+    verification_id: &str,
+) -> Result<SourcifyV2JobStatus> {
+    let response = client.get(format!("{url}v2/verify/{verification_id}")).send().await?;
+    if !response.status().is_success() {
+        eyre::bail!("Failed to request verification status with status code {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+fn process_job(status: SourcifyV2JobStatus) -> Result<()> {
+    if let Some(error) = status.error {
+        eyre::bail!("Contract failed to verify: {} ({})", error.message, error.custom_code)
+    }
+    match status.contract.as_ref().and_then(SourcifyV2Contract::status) {
+        Some(status) => println!("Contract successfully verified ({status})"),
+        None => eyre::bail!("Contract failed to verify: the recompiled contract doesn't match"),
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyV2VerifyRequest {
+    std_json_input: serde_json::Value,
+    compiler_version: String,
+    contract_identifier: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    creation_transaction_hash: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyV2JobResponse {
+    verification_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyV2JobStatus {
+    is_job_completed: bool,
+    contract: Option<SourcifyV2Contract>,
+    error: Option<SourcifyV2Error>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyV2Contract {
+    /// `exact_match`, `match` or `null`
+    #[serde(rename = "match")]
+    matched: Option<String>,
+}
+
+impl SourcifyV2Contract {
+    /// Returns how the contract matches the deployed bytecode, if it does.
+    fn status(&self) -> Option<&str> {
+        match self.matched.as_deref()? {
+            "exact_match" => Some("exact match"),
+            "match" => Some("partial match"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SourcifyV2Error {
+    custom_code: String,
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_job_status() {
+        let status: SourcifyV2JobStatus = serde_json::from_str(
+            r#"{
+                "isJobCompleted": true,
+                "verificationId": "72a8e1c5-3c3c-4ec0-a8f7-4a0e9f4b6e4f",
+                "contract": { "match": "exact_match", "chainId": "1" }
+            }"#,
+        )
+        .unwrap();
+        assert!(status.is_job_completed);
+        assert_eq!(status.contract.unwrap().status(), Some("exact match"));
+
+        let status: SourcifyV2JobStatus = serde_json::from_str(
+            r#"{
+                "isJobCompleted": true,
+                "error": { "customCode": "no_match", "message": "The onchain and recompiled bytecodes don't match." }
+            }"#,
+        )
+        .unwrap();
+        assert!(process_job(status).is_err());
+    }
+}
@@ -2,8 +2,14 @@ use super::{build::BuildArgs, snapshot::SnapshotArgs, test::TestArgs};
 use clap::Parser;
 use eyre::Result;
 use foundry_cli::utils::{self, FoundryPathExt};
+use foundry_compilers::{Graph, ProjectPathsConfig};
 use foundry_config::Config;
-use std::{collections::HashSet, convert::Infallible, path::PathBuf, sync::Arc};
+use std::{
+    collections::HashSet,
+    convert::Infallible,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use watchexec::{
     action::{Action, Outcome, PreSpawn},
     command::Command,
@@ -35,7 +41,8 @@ pub struct WatchArgs {
 
     /// Explicitly re-run all tests when a change is made.
     ///
-    /// By default, only the tests of the last modified test file are executed.
+    /// By default, only the tests of the test files which are or import the modified files are
+    /// executed.
     #[clap(long)]
     pub run_all: bool,
 
@@ -137,6 +144,7 @@ pub async fn watch_test(args: TestArgs) -> Result<()> {
         args.watch.run_all;
 
     let state = WatchTestState {
+        paths: config.project_paths(),
         project_root: config.__root.0,
         no_reconfigure,
         last_test_files: Default::default(),
@@ -152,20 +160,21 @@ pub async fn watch_test(args: TestArgs) -> Result<()> {
 
 #[derive(Clone, Debug)]
 struct WatchTestState {
+    /// the paths of the project, to resolve the test files affected by a change
+    paths: ProjectPathsConfig,
     /// the root directory of the project
     project_root: PathBuf,
     /// marks whether we can reconfigure the watcher command with the `--match-path` arg
     no_reconfigure: bool,
-    /// Tracks the last changed test files, if any so that if a non-test file was modified we run
-    /// this file instead *Note:* this is a vec, so we can also watch out for changes
-    /// introduced by `forge fmt`
+    /// Tracks the last affected test files, if any so that if a file which no test depends on was
+    /// modified we run these files instead
     last_test_files: HashSet<String>,
 }
 
 /// The `on_action` hook for `forge test --watch`
 fn on_test(action: OnActionState<WatchTestState>) {
     let OnActionState { args, runtime, action, wx, cmd, other } = action;
-    let WatchTestState { project_root, no_reconfigure, last_test_files } = other;
+    let WatchTestState { paths, project_root, no_reconfigure, last_test_files } = other;
 
     if no_reconfigure {
         // nothing to reconfigure
@@ -174,13 +183,12 @@ fn on_test(action: OnActionState<WatchTestState>) {
 
     let mut cmd = cmd.clone();
 
-    let mut changed_sol_test_files: HashSet<_> = action
+    let changed_sol_files: HashSet<_> = action
         .events
         .iter()
         .flat_map(|e| e.paths())
-        .filter(|(path, _)| path.is_sol_test())
-        .filter_map(|(path, _)| path.to_str())
-        .map(str::to_string)
+        .filter(|(path, _)| path.is_sol())
+        .map(|(path, _)| dunce::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()))
         .collect();
 
     // replace `--match-path` | `-mp` argument
@@ -189,12 +197,28 @@ fn on_test(action: OnActionState<WatchTestState>) {
         cmd.drain(pos..=(pos + 1));
     }
 
-    if changed_sol_test_files.len() > 1 ||
-        (changed_sol_test_files.is_empty() && last_test_files.is_empty())
-    {
-        // this could happen if multiple files were changed at once, for example `forge fmt` was
-        // run, or if no test files were changed and no previous test files were modified in which
-        // case we simply run all
+    let mut test_files = match affected_test_files(&paths, &changed_sol_files) {
+        Ok(files) => files
+            .iter()
+            .map(|file| file.strip_prefix(&project_root).unwrap_or(file))
+            .filter_map(Path::to_str)
+            .map(str::to_string)
+            .collect(),
+        Err(err) => {
+            // the graph can't be resolved, for example if an import is being edited, in which case
+            // we simply run all
+            trace!("failed to resolve the test files affected by the change: {err}");
+            HashSet::new()
+        }
+    };
+
+    if test_files.is_empty() {
+        // reuse the old test files if the changed files don't affect any test
+        test_files = last_test_files;
+    }
+
+    if test_files.is_empty() {
+        // no test was affected by this or a previous change, so we simply run all
         let mut config = runtime.clone();
         config.command(watch_command(cmd.clone()));
         // re-register the action
@@ -203,34 +227,16 @@ fn on_test(action: OnActionState<WatchTestState>) {
             config,
             wx,
             cmd,
-            WatchTestState {
-                project_root,
-                no_reconfigure,
-                last_test_files: changed_sol_test_files,
-            },
+            WatchTestState { paths, project_root, no_reconfigure, last_test_files: test_files },
             on_test,
         );
         return
     }
 
-    if changed_sol_test_files.is_empty() {
-        // reuse the old test files if a non-test file was changed
-        changed_sol_test_files = last_test_files;
-    }
-
     // append `--match-path` glob
-    let mut file = changed_sol_test_files.clone().into_iter().next().expect("test file present");
-
-    // remove the project root dir from the detected file
-    if let Some(root) = project_root.as_os_str().to_str() {
-        if let Some(f) = file.strip_prefix(root) {
-            file = f.trim_start_matches('/').to_string();
-        }
-    }
-
     let mut new_cmd = cmd.clone();
     new_cmd.push("--match-path".to_string());
-    new_cmd.push(file);
+    new_cmd.push(match_path_glob(&test_files));
     trace!("reconfigure test command {:?}", new_cmd);
 
     // reconfigure the executor with a new runtime
@@ -243,11 +249,42 @@ fn on_test(action: OnActionState<WatchTestState>) {
         config,
         wx,
         cmd,
-        WatchTestState { project_root, no_reconfigure, last_test_files: changed_sol_test_files },
+        WatchTestState { paths, project_root, no_reconfigure, last_test_files: test_files },
         on_test,
     );
 }
 
+/// Returns the test files which are, or transitively import, any of the changed files
+fn affected_test_files(
+    paths: &ProjectPathsConfig,
+    changed: &HashSet<PathBuf>,
+) -> Result<HashSet<PathBuf>> {
+    if changed.is_empty() {
+        return Ok(HashSet::new())
+    }
+    let graph = Graph::resolve(paths)?;
+    Ok(graph
+        .files()
+        .keys()
+        .filter(|file| file.is_sol_test())
+        .filter(|file| {
+            changed.contains(*file) || graph.imports(file).into_iter().any(|i| changed.contains(i))
+        })
+        .cloned()
+        .collect())
+}
+
+/// Returns the `--match-path` glob matching all the given files
+fn match_path_glob(files: &HashSet<String>) -> String {
+    let mut files: Vec<_> = files.iter().map(String::as_str).collect();
+    files.sort_unstable();
+    if let [file] = files[..] {
+        file.to_string()
+    } else {
+        format!("{{{}}}", files.join(","))
+    }
+}
+
 /// Converts a list of arguments to a `watchexec::Command`
 ///
 /// The first index in `args`, is expected to be the path to the executable, See `cmd_args`
@@ -448,4 +485,44 @@ mod tests {
         let cleaned = clean_cmd_args(0, args);
         assert_eq!(cleaned, vec!["-v".to_string()]);
     }
+
+    #[test]
+    fn finds_affected_test_files() {
+        let root = tempfile::tempdir().unwrap();
+        let root = dunce::canonicalize(root.path()).unwrap();
+        let files = [
+            ("src/Counter.sol", "contract Counter {}"),
+            ("src/Other.sol", "contract Other {}"),
+            ("src/Wrapper.sol", "import \"./Counter.sol\";\ncontract Wrapper {}"),
+            ("test/Counter.t.sol", "import \"../src/Counter.sol\";\ncontract CounterTest {}"),
+            ("test/Wrapper.t.sol", "import \"../src/Wrapper.sol\";\ncontract WrapperTest {}"),
+            ("test/Other.t.sol", "import \"../src/Other.sol\";\ncontract OtherTest {}"),
+        ];
+        for (path, content) in files {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let paths = ProjectPathsConfig::builder().build_with_root(&root);
+
+        let changed = HashSet::from([root.join("src/Counter.sol")]);
+        let affected = affected_test_files(&paths, &changed).unwrap();
+        assert_eq!(
+            affected,
+            HashSet::from([root.join("test/Counter.t.sol"), root.join("test/Wrapper.t.sol")])
+        );
+
+        let changed = HashSet::from([root.join("test/Other.t.sol")]);
+        let affected = affected_test_files(&paths, &changed).unwrap();
+        assert_eq!(affected, HashSet::from([root.join("test/Other.t.sol")]));
+    }
+
+    #[test]
+    fn builds_match_path_glob() {
+        let files = HashSet::from(["test/A.t.sol".to_string()]);
+        assert_eq!(match_path_glob(&files), "test/A.t.sol");
+
+        let files = HashSet::from(["test/B.t.sol".to_string(), "test/A.t.sol".to_string()]);
+        assert_eq!(match_path_glob(&files), "{test/A.t.sol,test/B.t.sol}");
+    }
 }
@@ -37,6 +37,7 @@ fn main() -> Result<()> {
             utils::block_on(cmd.run_script())
         }
         ForgeSubcommand::Coverage(cmd) => utils::block_on(cmd.run()),
+        ForgeSubcommand::Mutate(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::Bind(cmd) => cmd.run(),
         ForgeSubcommand::Build(cmd) => {
             if cmd.is_watch() {
@@ -48,9 +49,12 @@ fn main() -> Result<()> {
         ForgeSubcommand::Debug(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::VerifyContract(args) => utils::block_on(args.run()),
         ForgeSubcommand::VerifyCheck(args) => utils::block_on(args.run()),
+        ForgeSubcommand::VerifyBytecode(args) => utils::block_on(args.run()),
+        ForgeSubcommand::VerifyAttestation(args) => args.run(),
         ForgeSubcommand::Cache(cmd) => match cmd.sub {
             CacheSubcommands::Clean(cmd) => cmd.run(),
             CacheSubcommands::Ls(cmd) => cmd.run(),
+            CacheSubcommands::Fork(cmd) => utils::block_on(cmd.run()),
         },
         ForgeSubcommand::Create(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::Update(cmd) => cmd.run(),
@@ -58,6 +62,7 @@ fn main() -> Result<()> {
         ForgeSubcommand::Remove(cmd) => cmd.run(),
         ForgeSubcommand::Remappings(cmd) => cmd.run(),
         ForgeSubcommand::Init(cmd) => cmd.run(),
+        ForgeSubcommand::Clone(cmd) => utils::block_on(cmd.run()),
         ForgeSubcommand::Completions { shell } => {
             generate(shell, &mut Forge::command(), "forge", &mut std::io::stdout());
             Ok(())
@@ -87,6 +92,7 @@ fn main() -> Result<()> {
         ForgeSubcommand::Config(cmd) => cmd.run(),
         ForgeSubcommand::Flatten(cmd) => cmd.run(),
         ForgeSubcommand::Inspect(cmd) => cmd.run(),
+        ForgeSubcommand::Size(cmd) => cmd.run(),
         ForgeSubcommand::Tree(cmd) => cmd.run(),
         ForgeSubcommand::Geiger(cmd) => {
             let check = cmd.check;
@@ -96,6 +102,7 @@ fn main() -> Result<()> {
             }
             Ok(())
         }
+        ForgeSubcommand::Lint(cmd) => cmd.run(),
         ForgeSubcommand::Doc(cmd) => cmd.run(),
         ForgeSubcommand::Selectors { command } => utils::block_on(command.run()),
         ForgeSubcommand::Generate(cmd) => match cmd.sub {
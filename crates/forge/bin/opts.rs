@@ -1,7 +1,9 @@
 use crate::cmd::{
+    attest::VerifyAttestationArgs,
     bind::BindArgs,
     build::BuildArgs,
     cache::CacheArgs,
+    clone::CloneArgs,
     config, coverage,
     create::CreateArgs,
     debug::DebugArgs,
@@ -12,12 +14,15 @@ use crate::cmd::{
     init::InitArgs,
     inspect,
     install::InstallArgs,
+    lint::LintArgs,
+    mutate::MutateArgs,
     remappings::RemappingArgs,
     remove::RemoveArgs,
     script::ScriptArgs,
     selectors::SelectorsSubcommands,
+    size::SizeArgs,
     snapshot, test, tree, update,
-    verify::{VerifyArgs, VerifyCheckArgs},
+    verify::{VerifyArgs, VerifyBytecodeArgs, VerifyCheckArgs},
 };
 use clap::{Parser, Subcommand, ValueHint};
 use std::path::PathBuf;
@@ -58,6 +63,11 @@ pub enum ForgeSubcommand {
     Coverage(coverage::CoverageArgs),
 
     /// Generate Rust bindings for smart contracts.
+    ///
+    /// Each contract's bindings include typed event filters (`<Event>Filter`) with filter
+    /// builders, an `<Contract>Events` decoder enum, and an `<Contract>Errors` enum of its custom
+    /// errors implementing `Display`, and a `<Contract>Multicall` aggregator batching calls to the
+    /// contract into a single call to `Multicall3`.
     #[clap(alias = "bi")]
     Bind(BindArgs),
 
@@ -97,6 +107,16 @@ pub enum ForgeSubcommand {
     #[clap(visible_alias = "vc")]
     VerifyCheck(VerifyCheckArgs),
 
+    /// Verify that the runtime code deployed at an address, or at every address of a broadcast
+    /// file, was compiled from the local artifacts.
+    #[clap(visible_alias = "vb")]
+    VerifyBytecode(VerifyBytecodeArgs),
+
+    /// Verify that a contract's artifact matches the attestation written by `forge build
+    /// --attest`.
+    #[clap(visible_alias = "va")]
+    VerifyAttestation(VerifyAttestationArgs),
+
     /// Deploy a smart contract.
     #[clap(visible_alias = "c")]
     Create(CreateArgs),
@@ -104,6 +124,9 @@ pub enum ForgeSubcommand {
     /// Create a new Forge project.
     Init(InitArgs),
 
+    /// Clone a verified contract into a new Forge project.
+    Clone(CloneArgs),
+
     /// Generate shell completions script.
     #[clap(visible_alias = "com")]
     Completions {
@@ -129,6 +152,9 @@ pub enum ForgeSubcommand {
     /// Manage the Foundry cache.
     Cache(CacheArgs),
 
+    /// Run the tests against mutants of the sources and report the mutants that survive.
+    Mutate(MutateArgs),
+
     /// Create a snapshot of each test's gas usage.
     #[clap(visible_alias = "s")]
     Snapshot(snapshot::SnapshotArgs),
@@ -148,6 +174,10 @@ pub enum ForgeSubcommand {
     #[clap(visible_alias = "in")]
     Inspect(inspect::InspectArgs),
 
+    /// Show the runtime and init code sizes of the project's contracts, with a breakdown of their
+    /// runtime code by source.
+    Size(SizeArgs),
+
     /// Display a tree visualization of the project's dependency graph.
     #[clap(visible_alias = "tr")]
     Tree(tree::TreeArgs),
@@ -155,6 +185,9 @@ pub enum ForgeSubcommand {
     /// Detects usage of unsafe cheat codes in a project and its dependencies.
     Geiger(geiger::GeigerArgs),
 
+    /// Lint the project's sources for security and style issues.
+    Lint(LintArgs),
+
     /// Generate documentation for the project.
     Doc(DocArgs),
 
@@ -5,9 +5,10 @@ use evm_disassembler::disassemble_bytes;
 use foundry_common::fs;
 pub use foundry_evm::coverage::*;
 use std::{
-    collections::{hash_map, HashMap},
+    collections::{hash_map, BTreeMap, HashMap},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 /// A coverage reporter.
@@ -109,7 +110,7 @@ impl<'a> CoverageReporter for LcovReporter<'a> {
                     CoverageItemKind::Line => {
                         writeln!(self.destination, "DA:{line},{hits}")?;
                     }
-                    CoverageItemKind::Branch { branch_id, path_id } => {
+                    CoverageItemKind::Branch { branch_id, path_id, .. } => {
                         writeln!(
                             self.destination,
                             "BRDA:{line},{branch_id},{path_id},{}",
@@ -142,6 +143,369 @@ impl<'a> CoverageReporter for LcovReporter<'a> {
     }
 }
 
+/// A reporter that writes the coverage results in the Cobertura XML format.
+pub struct CoberturaReporter<'a> {
+    /// Destination buffer
+    destination: &'a mut (dyn Write + 'a),
+    /// The project root, which the source paths are relative to.
+    root: PathBuf,
+}
+
+impl<'a> CoberturaReporter<'a> {
+    pub fn new(destination: &'a mut (dyn Write + 'a), root: PathBuf) -> CoberturaReporter<'a> {
+        Self { destination, root }
+    }
+}
+
+impl<'a> CoverageReporter for CoberturaReporter<'a> {
+    fn report(mut self, report: &CoverageReport) -> eyre::Result<()> {
+        // Cobertura groups classes in packages, which we map to the directories of the sources
+        let mut packages: BTreeMap<String, Vec<(String, Vec<CoverageItem>)>> = BTreeMap::new();
+        let mut total = CoverageSummary::default();
+        for (file, items) in report.items_by_source() {
+            let package = Path::new(&file)
+                .parent()
+                .map(|parent| parent.display().to_string())
+                .unwrap_or_default();
+            packages.entry(package).or_default().push((file, items));
+        }
+        for (_, items) in packages.values().flatten() {
+            for item in items {
+                total += item;
+            }
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        writeln!(self.destination, r#"<?xml version="1.0" ?>"#)?;
+        writeln!(
+            self.destination,
+            r#"<!DOCTYPE coverage SYSTEM "http://cobertura.sourceforge.net/xml/coverage-04.dtd">"#
+        )?;
+        writeln!(
+            self.destination,
+            r#"<coverage line-rate="{}" branch-rate="{}" lines-covered="{}" lines-valid="{}" branches-covered="{}" branches-valid="{}" complexity="0" version="1" timestamp="{timestamp}">"#,
+            rate(total.line_hits, total.line_count),
+            rate(total.branch_hits, total.branch_count),
+            total.line_hits,
+            total.line_count,
+            total.branch_hits,
+            total.branch_count,
+        )?;
+        writeln!(self.destination, "  <sources>")?;
+        writeln!(
+            self.destination,
+            "    <source>{}</source>",
+            xml_escape(&self.root.display().to_string())
+        )?;
+        writeln!(self.destination, "  </sources>")?;
+        writeln!(self.destination, "  <packages>")?;
+        for (package, files) in &packages {
+            let summary = files.iter().flat_map(|(_, items)| items).fold(
+                CoverageSummary::default(),
+                |mut summary, item| {
+                    summary += item;
+                    summary
+                },
+            );
+            writeln!(
+                self.destination,
+                r#"    <package name="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+                xml_escape(package),
+                rate(summary.line_hits, summary.line_count),
+                rate(summary.branch_hits, summary.branch_count),
+            )?;
+            writeln!(self.destination, "      <classes>")?;
+            for (file, items) in files {
+                self.write_class(file, items)?;
+            }
+            writeln!(self.destination, "      </classes>")?;
+            writeln!(self.destination, "    </package>")?;
+        }
+        writeln!(self.destination, "  </packages>")?;
+        writeln!(self.destination, "</coverage>")?;
+
+        println!("Wrote Cobertura report.");
+
+        Ok(())
+    }
+}
+
+impl<'a> CoberturaReporter<'a> {
+    /// Writes a source file as a Cobertura class, with its functions as methods.
+    fn write_class(&mut self, file: &str, items: &[CoverageItem]) -> eyre::Result<()> {
+        let summary = items.iter().fold(CoverageSummary::default(), |mut summary, item| {
+            summary += item;
+            summary
+        });
+        let name = Path::new(file).file_name().map_or(file.into(), |name| name.to_string_lossy());
+        writeln!(
+            self.destination,
+            r#"        <class name="{}" filename="{}" line-rate="{}" branch-rate="{}" complexity="0">"#,
+            xml_escape(&name),
+            xml_escape(file),
+            rate(summary.line_hits, summary.line_count),
+            rate(summary.branch_hits, summary.branch_count),
+        )?;
+
+        writeln!(self.destination, "          <methods>")?;
+        for item in items {
+            if let CoverageItemKind::Function { name } = &item.kind {
+                let name = format!("{}.{name}", item.loc.contract_name);
+                writeln!(
+                    self.destination,
+                    r#"            <method name="{}" signature="" line-rate="{}" branch-rate="0" complexity="0">"#,
+                    xml_escape(&name),
+                    if item.hits > 0 { 1 } else { 0 },
+                )?;
+                writeln!(self.destination, "              <lines>")?;
+                writeln!(
+                    self.destination,
+                    r#"                <line number="{}" hits="{}"/>"#,
+                    item.loc.line, item.hits
+                )?;
+                writeln!(self.destination, "              </lines>")?;
+                writeln!(self.destination, "            </method>")?;
+            }
+        }
+        writeln!(self.destination, "          </methods>")?;
+
+        writeln!(self.destination, "          <lines>")?;
+        for (number, line) in LineCoverage::new(items) {
+            write!(
+                self.destination,
+                r#"            <line number="{number}" hits="{}""#,
+                line.hits
+            )?;
+            if line.branch_count > 0 {
+                write!(
+                    self.destination,
+                    r#" branch="true" condition-coverage="{:.0}% ({}/{})""#,
+                    rate(line.branch_hits, line.branch_count) * 100.,
+                    line.branch_hits,
+                    line.branch_count
+                )?;
+            } else {
+                write!(self.destination, r#" branch="false""#)?;
+            }
+            writeln!(self.destination, "/>")?;
+        }
+        writeln!(self.destination, "          </lines>")?;
+        writeln!(self.destination, "        </class>")?;
+        Ok(())
+    }
+}
+
+/// A reporter that writes a self-contained HTML report, with a summary page and a page per source
+/// file showing its source annotated with hit counts.
+pub struct HtmlReporter {
+    root: PathBuf,
+    destdir: PathBuf,
+}
+
+impl HtmlReporter {
+    pub fn new(root: PathBuf, destdir: PathBuf) -> HtmlReporter {
+        Self { root, destdir }
+    }
+
+    /// Returns the name of the page of the given source file.
+    fn page_name(file: &str) -> String {
+        format!("{}.html", file.replace(['/', '\\'], "_"))
+    }
+}
+
+impl CoverageReporter for HtmlReporter {
+    fn report(self, report: &CoverageReport) -> eyre::Result<()> {
+        use std::fmt::Write;
+
+        let mut index = String::new();
+        let mut total = CoverageSummary::default();
+        for (file, items) in report.items_by_source() {
+            let summary = items.iter().fold(CoverageSummary::default(), |mut summary, item| {
+                summary += item;
+                summary
+            });
+            total += &summary;
+            let page = Self::page_name(&file);
+            writeln!(
+                index,
+                r#"<tr><td><a href="{}">{}</a></td>{}</tr>"#,
+                xml_escape(&page),
+                xml_escape(&file),
+                html_summary_cells(&summary)
+            )?;
+
+            let source = fs::read_to_string(self.root.join(&file)).unwrap_or_default();
+            let lines = LineCoverage::new(&items);
+            let mut rows = String::new();
+            for (i, text) in source.lines().enumerate() {
+                let number = i + 1;
+                let (class, hits, branches) = match lines.get(&number) {
+                    Some(line) => (
+                        if line.hits == 0 {
+                            "miss"
+                        } else if line.branch_hits < line.branch_count {
+                            "partial"
+                        } else {
+                            "hit"
+                        },
+                        line.hits.to_string(),
+                        if line.branch_count > 0 {
+                            format!("{}/{}", line.branch_hits, line.branch_count)
+                        } else {
+                            String::new()
+                        },
+                    ),
+                    None => ("", String::new(), String::new()),
+                };
+                writeln!(
+                    rows,
+                    r#"<tr class="{class}"><td class="num">{number}</td><td class="num">{hits}</td><td class="num">{branches}</td><td><pre>{}</pre></td></tr>"#,
+                    xml_escape(text)
+                )?;
+            }
+
+            let body = format!(
+                r#"<p><a href="index.html">Index</a></p>
+<h1>{}</h1>
+<table class="summary">{HTML_SUMMARY_HEADER}<tr><td></td>{}</tr></table>
+<table class="source">
+<tr><th>Line</th><th>Hits</th><th>Branches</th><th>Source</th></tr>
+{rows}</table>"#,
+                xml_escape(&file),
+                html_summary_cells(&summary)
+            );
+            fs::write(self.destdir.join(page), html_page(&file, &body))?;
+        }
+
+        let body = format!(
+            r#"<h1>Coverage report</h1>
+<table class="summary">
+{HTML_SUMMARY_HEADER}
+{index}<tr class="total"><td>Total</td>{}</tr>
+</table>"#,
+            html_summary_cells(&total)
+        );
+        fs::write(self.destdir.join("index.html"), html_page("Coverage report", &body))?;
+
+        println!("Wrote HTML report to {}.", self.destdir.join("index.html").display());
+
+        Ok(())
+    }
+}
+
+const HTML_SUMMARY_HEADER: &str =
+    "<tr><th>File</th><th>% Lines</th><th>% Statements</th><th>% Branches</th><th>% Funcs</th></tr>";
+
+const HTML_STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0 0.5em; text-align: left; }
+.summary td, .summary th { border: 1px solid #ccc; padding: 0.25em 0.5em; }
+.source td { vertical-align: top; }
+pre { margin: 0; }
+.num { text-align: right; color: #666; }
+tr.hit { background: #dfd; }
+tr.partial { background: #ffd; }
+tr.miss { background: #fdd; }
+td.low { color: #c00; }
+td.medium { color: #b80; }
+td.high { color: #080; }
+tr.total { font-weight: bold; }";
+
+/// Wraps the body in a standalone HTML page.
+fn html_page(title: &str, body: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{}</title>
+<style>
+{HTML_STYLE}
+</style>
+</head>
+<body>
+{body}
+</body>
+</html>
+"#,
+        xml_escape(title)
+    )
+}
+
+/// Renders the coverage percentages of the summary as HTML table cells.
+fn html_summary_cells(summary: &CoverageSummary) -> String {
+    [
+        (summary.line_hits, summary.line_count),
+        (summary.statement_hits, summary.statement_count),
+        (summary.branch_hits, summary.branch_count),
+        (summary.function_hits, summary.function_count),
+    ]
+    .into_iter()
+    .map(|(hits, total)| {
+        let percentage = rate(hits, total);
+        let class = match percentage {
+            _ if percentage < 0.5 => "low",
+            _ if percentage < 0.75 => "medium",
+            _ => "high",
+        };
+        format!(r#"<td class="{class}">{:.2}% ({hits}/{total})</td>"#, percentage * 100.)
+    })
+    .collect()
+}
+
+/// The coverage of a single source line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct LineCoverage {
+    /// The number of times the line was hit.
+    hits: u64,
+    /// The number of branch paths on the line.
+    branch_count: usize,
+    /// The number of branch paths on the line that were hit.
+    branch_hits: usize,
+}
+
+impl LineCoverage {
+    /// Collects the coverage of the executable lines of the given items, keyed by line number.
+    fn new(items: &[CoverageItem]) -> BTreeMap<usize, Self> {
+        let mut lines = BTreeMap::<usize, Self>::new();
+        for item in items {
+            match item.kind {
+                CoverageItemKind::Line => {
+                    let line = lines.entry(item.loc.line).or_default();
+                    line.hits = line.hits.max(item.hits);
+                }
+                CoverageItemKind::Branch { .. } => {
+                    let line = lines.entry(item.loc.line).or_default();
+                    line.branch_count += 1;
+                    if item.hits > 0 {
+                        line.branch_hits += 1;
+                    }
+                }
+                CoverageItemKind::Statement | CoverageItemKind::Function { .. } => {}
+            }
+        }
+        lines
+    }
+}
+
+/// Returns the ratio of hits to the total, or 1 if there is nothing to cover.
+fn rate(hits: usize, total: usize) -> f64 {
+    if total == 0 {
+        1.
+    } else {
+        hits as f64 / total as f64
+    }
+}
+
+/// Escapes the characters with a special meaning in XML text and attributes.
+pub fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
 /// A super verbose reporter for debugging coverage while it is still unstable.
 pub struct DebugReporter;
 
@@ -277,3 +641,93 @@ impl LineNumberCache {
         Ok((lo, pos))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: CoverageItemKind, line: usize, hits: u64) -> CoverageItem {
+        CoverageItem {
+            kind,
+            loc: SourceLocation {
+                source_id: 0,
+                contract_name: "Counter".to_string(),
+                start: 0,
+                length: None,
+                line,
+            },
+            hits,
+        }
+    }
+
+    fn report() -> CoverageReport {
+        let version = semver::Version::new(0, 8, 23);
+        let mut report = CoverageReport::default();
+        report.add_source(version.clone(), 0, "src/Counter.sol".to_string());
+        report.add_items(
+            version,
+            vec![
+                item(CoverageItemKind::Function { name: "increment".to_string() }, 4, 2),
+                item(CoverageItemKind::Line, 5, 2),
+                item(
+                    CoverageItemKind::Branch {
+                        branch_id: 0,
+                        path_id: 0,
+                        anchor: BranchAnchor::FirstOpcode,
+                    },
+                    5,
+                    2,
+                ),
+                item(
+                    CoverageItemKind::Branch {
+                        branch_id: 0,
+                        path_id: 1,
+                        anchor: BranchAnchor::FirstOpcode,
+                    },
+                    5,
+                    0,
+                ),
+                item(CoverageItemKind::Line, 6, 0),
+            ],
+        );
+        report
+    }
+
+    #[test]
+    fn cobertura_report() {
+        let mut xml = Vec::new();
+        CoberturaReporter::new(&mut xml, PathBuf::from("/project")).report(&report()).unwrap();
+        let xml = String::from_utf8(xml).unwrap();
+
+        assert!(xml.contains(r#"lines-covered="1" lines-valid="2""#));
+        assert!(xml.contains(r#"<package name="src" line-rate="0.5" branch-rate="0.5""#));
+        assert!(xml.contains(r#"<class name="Counter.sol" filename="src/Counter.sol""#));
+        assert!(xml.contains(r#"<method name="Counter.increment" signature="" line-rate="1""#));
+        assert!(xml.contains(
+            r#"<line number="5" hits="2" branch="true" condition-coverage="50% (1/2)"/>"#
+        ));
+        assert!(xml.contains(r#"<line number="6" hits="0" branch="false"/>"#));
+    }
+
+    #[test]
+    fn html_report() {
+        let root = tempfile::tempdir().unwrap();
+        fs::create_dir_all(root.path().join("src")).unwrap();
+        fs::write(
+            root.path().join("src/Counter.sol"),
+            "contract Counter {\n    uint256 public number;\n\n    function increment() public {\n        if (number < 10) number++;\n        number += 1;\n    }\n}\n",
+        )
+        .unwrap();
+        let destdir = root.path().join("coverage");
+        fs::create_dir_all(&destdir).unwrap();
+        HtmlReporter::new(root.path().to_path_buf(), destdir.clone()).report(&report()).unwrap();
+
+        let index = fs::read_to_string(destdir.join("index.html")).unwrap();
+        assert!(index.contains(r#"<a href="src_Counter.sol.html">src/Counter.sol</a>"#));
+        assert!(index.contains("50.00% (1/2)"));
+
+        let page = fs::read_to_string(destdir.join("src_Counter.sol.html")).unwrap();
+        assert!(page.contains(r#"<tr class="partial"><td class="num">5</td><td class="num">2</td><td class="num">1/2</td><td><pre>        if (number &lt; 10) number++;</pre></td></tr>"#));
+        assert!(page.contains(r#"<tr class="miss"><td class="num">6</td><td class="num">0</td>"#));
+    }
+}
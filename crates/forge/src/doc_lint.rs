@@ -0,0 +1,140 @@
+//! Checks that the public API of the sources is documented with NatSpec comments.
+
+use foundry_compilers::artifacts::ast::{Ast, Node, NodeType};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// A function, error or event whose NatSpec documentation is incomplete.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MissingDoc {
+    pub line: usize,
+    /// The kind and name of the item, e.g. `function transfer`
+    pub item: String,
+    /// The missing tags, e.g. `@notice` or `@param amount`
+    pub missing: Vec<String>,
+}
+
+/// Finds the public and external functions, errors and events of the source which lack a
+/// `@notice` or a `@param` for any of their named parameters.
+///
+/// `source` is the source code the AST was generated from, used to find the lines of the items.
+/// Items documented with `@inheritdoc` are skipped.
+pub fn find_missing_docs(ast: &Ast, source: &str) -> Vec<MissingDoc> {
+    let mut missing = Vec::new();
+    for node in &ast.nodes {
+        if node.node_type == NodeType::ContractDefinition {
+            node.nodes.iter().for_each(|node| check_item(node, source, &mut missing));
+        } else {
+            check_item(node, source, &mut missing);
+        }
+    }
+    missing
+}
+
+fn check_item(node: &Node, source: &str, missing: &mut Vec<MissingDoc>) {
+    let kind = match node.node_type {
+        NodeType::FunctionDefinition => {
+            let kind: String = node.attribute("kind").unwrap_or_default();
+            let visibility: String = node.attribute("visibility").unwrap_or_default();
+            if kind != "function" || !matches!(visibility.as_str(), "public" | "external") {
+                return
+            }
+            "function"
+        }
+        NodeType::ErrorDefinition => "error",
+        NodeType::EventDefinition => "event",
+        _ => return,
+    };
+
+    let tags = Tags::parse(documentation(node).unwrap_or_default());
+    if tags.inheritdoc {
+        return
+    }
+    let mut missing_tags = Vec::new();
+    if !tags.notice {
+        missing_tags.push("@notice".to_string());
+    }
+    let parameters = node
+        .attribute::<Node>("parameters")
+        .and_then(|parameters| parameters.attribute::<Vec<Node>>("parameters"))
+        .unwrap_or_default();
+    for parameter in parameters {
+        let name: String = parameter.attribute("name").unwrap_or_default();
+        if !name.is_empty() && !tags.params.contains(&name) {
+            missing_tags.push(format!("@param {name}"));
+        }
+    }
+    if missing_tags.is_empty() {
+        return
+    }
+
+    let name: String = node.attribute("name").unwrap_or_default();
+    let line = source.get(..node.src.start).map_or(0, |code| code.matches('\n').count() + 1);
+    missing.push(MissingDoc { line, item: format!("{kind} {name}"), missing: missing_tags });
+}
+
+/// Returns the text of the NatSpec comment of the node.
+fn documentation(node: &Node) -> Option<&str> {
+    match node.other.get("documentation")? {
+        // solc < 0.6.3 emits the text directly
+        Value::String(text) => Some(text),
+        Value::Object(docs) => docs.get("text")?.as_str(),
+        _ => None,
+    }
+}
+
+/// The NatSpec tags of a documentation comment which are checked.
+#[derive(Debug, Default)]
+struct Tags {
+    notice: bool,
+    inheritdoc: bool,
+    params: HashSet<String>,
+}
+
+impl Tags {
+    fn parse(docs: &str) -> Self {
+        let mut tags = Self::default();
+        let mut tagged = false;
+        for line in docs.lines().map(|line| line.trim().trim_start_matches('*').trim()) {
+            let Some(tag) = line.strip_prefix('@') else {
+                // text before the first tag is an implicit `@notice`
+                tags.notice |= !tagged && !line.is_empty();
+                continue
+            };
+            tagged = true;
+            let (tag, text) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            match tag {
+                "notice" => tags.notice = true,
+                "inheritdoc" => tags.inheritdoc = true,
+                "param" => {
+                    if let Some(name) = text.split_whitespace().next() {
+                        tags.params.insert(name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+        tags
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_tags() {
+        let tags = Tags::parse(" @notice Moves tokens.\n @param to The recipient\n @param amount");
+        assert!(tags.notice && !tags.inheritdoc);
+        assert_eq!(tags.params, HashSet::from(["to".to_string(), "amount".to_string()]));
+
+        let tags = Tags::parse("Moves tokens.\n @dev Reverts on overflow.");
+        assert!(tags.notice);
+
+        let tags = Tags::parse(" @dev Reverts on overflow.\n Unless unchecked.");
+        assert!(!tags.notice);
+
+        assert!(Tags::parse(" @inheritdoc IERC20").inheritdoc);
+    }
+}
@@ -0,0 +1,208 @@
+//! Table-driven tests.
+//!
+//! Tests annotated with `forge-config: <profile>.fixtures = "<path>"` are run once for each case
+//! of the JSON or TOML fixtures file at `<path>`, relative to the project root, instead of being
+//! fuzzed. Each case is decoded into the test's arguments and reported as a separate result.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_json_abi::Function;
+use eyre::{Context, Result};
+use foundry_common::fs;
+use serde_json::Value;
+use std::path::Path;
+
+/// A case of a table-driven test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableCase {
+    /// The name of the case, which is its `name` field or its index in the fixtures file.
+    pub name: String,
+    /// The arguments to call the test with.
+    pub args: Vec<DynSolValue>,
+}
+
+impl TableCase {
+    /// Returns the name the result of this case of the given test is reported under, i.e.
+    /// `testSwap(uint256,uint256) [small amounts]`.
+    pub fn test_name(&self, func: &Function) -> String {
+        format!("{} [{}]", func.signature(), self.name)
+    }
+}
+
+/// Reads the cases of a table-driven test from its fixtures file.
+///
+/// The file contains an array of cases, either at the top level or under a `cases` key, which
+/// TOML files must use, i.e. `[[cases]]`. A case is either an array of the test's arguments, or
+/// an object of its arguments by parameter name with an optional `name`. Numbers which don't fit
+/// in 64 bits must be given as strings.
+pub fn load_cases(path: &Path, func: &Function) -> Result<Vec<TableCase>> {
+    let content = fs::read_to_string(path)?;
+    let fixtures: Value = if path.extension().is_some_and(|ext| ext == "toml") {
+        toml::from_str(&content).wrap_err_with(|| format!("invalid TOML in {}", path.display()))?
+    } else {
+        serde_json::from_str(&content)
+            .wrap_err_with(|| format!("invalid JSON in {}", path.display()))?
+    };
+    parse_cases(&fixtures, func)
+}
+
+/// Decodes the cases of a table-driven test.
+fn parse_cases(fixtures: &Value, func: &Function) -> Result<Vec<TableCase>> {
+    let cases = match fixtures {
+        Value::Array(cases) => cases,
+        Value::Object(fixtures) => fixtures
+            .get("cases")
+            .and_then(Value::as_array)
+            .ok_or_else(|| eyre::eyre!("expected an array of cases under `cases`"))?,
+        _ => eyre::bail!("expected an array of cases"),
+    };
+    let types = func
+        .inputs
+        .iter()
+        .map(|param| DynSolType::parse(&param.selector_type()))
+        .collect::<Result<Vec<_>, _>>()?;
+    cases
+        .iter()
+        .enumerate()
+        .map(|(i, case)| parse_case(i, case, func, &types).wrap_err_with(|| format!("case {i}")))
+        .collect()
+}
+
+/// Decodes a case into the arguments of the test.
+fn parse_case(
+    index: usize,
+    case: &Value,
+    func: &Function,
+    types: &[DynSolType],
+) -> Result<TableCase> {
+    let mut name = index.to_string();
+    let values: Vec<&Value> = match case {
+        Value::Array(values) => {
+            eyre::ensure!(
+                values.len() == types.len(),
+                "expected {} arguments, got {}",
+                types.len(),
+                values.len()
+            );
+            values.iter().collect()
+        }
+        Value::Object(args) => {
+            // `name` names the case, unless it is a parameter of the test
+            if !func.inputs.iter().any(|param| param.name == "name") {
+                match args.get("name") {
+                    Some(Value::String(case_name)) => name = case_name.clone(),
+                    Some(case_name) => name = case_name.to_string(),
+                    None => {}
+                }
+            }
+            func.inputs
+                .iter()
+                .map(|param| {
+                    args.get(&param.name)
+                        .ok_or_else(|| eyre::eyre!("missing argument `{}`", param.name))
+                })
+                .collect::<Result<_>>()?
+        }
+        // The only argument of a test can be given as is
+        value if types.len() == 1 => vec![value],
+        _ => eyre::bail!("expected an array or an object of arguments"),
+    };
+    let args = values
+        .into_iter()
+        .zip(types)
+        .map(|(value, ty)| to_sol(value, ty))
+        .collect::<Result<_>>()?;
+    Ok(TableCase { name, args })
+}
+
+/// Converts a JSON value into a Solidity value of the given type.
+fn to_sol(value: &Value, ty: &DynSolType) -> Result<DynSolValue> {
+    let values = |values: &[Value], ty: &DynSolType| {
+        values.iter().map(|value| to_sol(value, ty)).collect::<Result<Vec<_>>>()
+    };
+    match (ty, value) {
+        (DynSolType::Array(ty), Value::Array(items)) => Ok(DynSolValue::Array(values(items, ty)?)),
+        (DynSolType::FixedArray(ty, len), Value::Array(items)) => {
+            eyre::ensure!(items.len() == *len, "expected {len} items for {value}");
+            Ok(DynSolValue::FixedArray(values(items, ty)?))
+        }
+        (DynSolType::Tuple(types), Value::Array(items)) => {
+            eyre::ensure!(
+                items.len() == types.len(),
+                "expected {} fields for {value}",
+                types.len()
+            );
+            let fields = items.iter().zip(types).map(|(item, ty)| to_sol(item, ty));
+            Ok(DynSolValue::Tuple(fields.collect::<Result<_>>()?))
+        }
+        (_, Value::String(s)) => {
+            ty.coerce_str(s).wrap_err_with(|| format!("failed to convert {value} to {ty}"))
+        }
+        (_, Value::Number(_) | Value::Bool(_)) => ty
+            .coerce_str(&value.to_string())
+            .wrap_err_with(|| format!("failed to convert {value} to {ty}")),
+        _ => eyre::bail!("failed to convert {value} to {ty}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, U256};
+    use serde_json::json;
+
+    #[test]
+    fn parses_cases() {
+        let func = Function::parse("testSwap(uint256 amount, address to, bool exact)").unwrap();
+        let fixtures = json!([
+            { "name": "small amounts", "amount": 1, "to": Address::ZERO, "exact": true },
+            ["1000000000000000000000", Address::ZERO, false],
+        ]);
+        let cases = parse_cases(&fixtures, &func).unwrap();
+        assert_eq!(
+            cases,
+            [
+                TableCase {
+                    name: "small amounts".to_string(),
+                    args: vec![
+                        DynSolValue::Uint(U256::from(1), 256),
+                        DynSolValue::Address(Address::ZERO),
+                        DynSolValue::Bool(true),
+                    ],
+                },
+                TableCase {
+                    name: "1".to_string(),
+                    args: vec![
+                        DynSolValue::Uint(U256::from(10).pow(U256::from(21)), 256),
+                        DynSolValue::Address(Address::ZERO),
+                        DynSolValue::Bool(false),
+                    ],
+                },
+            ]
+        );
+        assert_eq!(cases[0].test_name(&func), "testSwap(uint256,address,bool) [small amounts]");
+
+        let toml = toml::from_str(
+            r#"
+[[cases]]
+amount = 2
+to = "0x0000000000000000000000000000000000000000"
+exact = false
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            parse_cases(&toml, &func).unwrap()[0].args[0],
+            DynSolValue::Uint(U256::from(2), 256)
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_cases() {
+        let func = Function::parse("testSwap(uint256 amount, uint256[2] path)").unwrap();
+        assert!(parse_cases(&json!([[1]]), &func).is_err());
+        assert!(parse_cases(&json!([{ "amount": 1 }]), &func).is_err());
+        assert!(parse_cases(&json!([[1, [1, 2, 3]]]), &func).is_err());
+        assert!(parse_cases(&json!([["x", [1, 2]]]), &func).is_err());
+        assert!(parse_cases(&json!({ "amount": 1 }), &func).is_err());
+    }
+}
@@ -171,6 +171,115 @@ impl Display for GasReport {
     }
 }
 
+/// Represents the gas used by the external calls of each test, grouped by call path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TestGasReport {
+    /// The gas used by each call, grouped by test and then by call path, in the form
+    /// `Counter::increment > Token::transfer`
+    tests: BTreeMap<String, BTreeMap<String, Vec<u64>>>,
+}
+
+impl TestGasReport {
+    /// Analyzes the execution traces of the given test, recording the gas used at each external
+    /// call site.
+    pub async fn analyze(
+        &mut self,
+        test: &str,
+        traces: &[(TraceKind, CallTraceArena)],
+        decoder: &CallTraceDecoder,
+    ) {
+        for (kind, arena) in traces {
+            if *kind != TraceKind::Execution {
+                continue
+            }
+
+            // Nodes are ordered by call, so the path of a parent is always known before its
+            // children
+            let mut paths: Vec<Option<String>> = Vec::with_capacity(arena.nodes().len());
+            for node in arena.nodes() {
+                let trace = &node.trace;
+                if trace.address == CHEATCODE_ADDRESS || trace.address == HARDHAT_CONSOLE_ADDRESS {
+                    paths.push(None);
+                    continue
+                }
+
+                // The root node is the test itself, which is not a call site
+                let Some(parent) = node.parent else {
+                    paths.push(Some(String::new()));
+                    continue
+                };
+                let Some(parent_path) = paths[parent].clone() else {
+                    paths.push(None);
+                    continue
+                };
+
+                let label = Self::call_label(node, decoder).await;
+                let path =
+                    if parent_path.is_empty() { label } else { format!("{parent_path} > {label}") };
+                self.tests
+                    .entry(test.to_string())
+                    .or_default()
+                    .entry(path.clone())
+                    .or_default()
+                    .push(trace.gas_used);
+                paths.push(Some(path));
+            }
+        }
+    }
+
+    /// Returns the label of a call in a call path, in the form `Contract::function`.
+    async fn call_label(node: &CallTraceNode, decoder: &CallTraceDecoder) -> String {
+        let decoded = decoder.decode_function(&node.trace).await;
+        let contract = decoded
+            .contract
+            .as_deref()
+            .map(|name| name.rsplit(':').next().unwrap_or(name).to_string())
+            .or(decoded.label)
+            .unwrap_or_else(|| node.trace.address.to_string());
+        if node.trace.kind.is_any_create() {
+            return format!("new {contract}")
+        }
+        let function = decoded
+            .func
+            .map(|func| func.signature.split('(').next().unwrap_or_default().to_string())
+            .unwrap_or_else(|| "fallback".to_string());
+        format!("{contract}::{function}")
+    }
+}
+
+impl Display for TestGasReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+        for (test, paths) in self.tests.iter() {
+            let mut table = Table::new();
+            table.load_preset(ASCII_MARKDOWN);
+            table.set_header([Cell::new(format!("{test} test"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Green)]);
+            table.add_row([
+                Cell::new("Call Path").add_attribute(Attribute::Bold).fg(Color::Magenta),
+                Cell::new("min").add_attribute(Attribute::Bold).fg(Color::Green),
+                Cell::new("max").add_attribute(Attribute::Bold).fg(Color::Red),
+                Cell::new("total").add_attribute(Attribute::Bold).fg(Color::Yellow),
+                Cell::new("# calls").add_attribute(Attribute::Bold),
+            ]);
+            for (path, calls) in paths {
+                table.add_row([
+                    Cell::new(path).add_attribute(Attribute::Bold),
+                    Cell::new(calls.iter().min().copied().unwrap_or_default().to_string())
+                        .fg(Color::Green),
+                    Cell::new(calls.iter().max().copied().unwrap_or_default().to_string())
+                        .fg(Color::Red),
+                    Cell::new(calls.iter().sum::<u64>().to_string()).fg(Color::Yellow),
+                    Cell::new(calls.len().to_string()),
+                ]);
+            }
+            writeln!(f, "{table}")?;
+            writeln!(f, "\n")?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ContractInfo {
     pub gas: U256,
@@ -1,18 +1,29 @@
 #[macro_use]
 extern crate tracing;
 
-use alloy_primitives::B256;
+use alloy_primitives::{keccak256, B256, U256};
 use foundry_compilers::ProjectCompileOutput;
 use foundry_config::{
     validate_profiles, Config, FuzzConfig, InlineConfig, InlineConfigError, InlineConfigParser,
     InvariantConfig, NatSpec,
 };
+use foundry_evm::executors::CancellationToken;
 
 use proptest::test_runner::{RngAlgorithm, TestRng, TestRunner};
-use std::path::Path;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use result::FuzzFailure;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use symbolic::{SymbolicExecutor, SymbolicSource};
 
 pub mod coverage;
 
+pub mod doc_lint;
+
+pub mod fixtures;
+
 pub mod gas_report;
 
 pub mod link;
@@ -25,6 +36,10 @@ pub use runner::ContractRunner;
 
 pub mod result;
 
+pub mod symbolic;
+
+pub mod unreachable;
+
 // TODO: remove
 pub use foundry_common::traits::TestFilter;
 pub use foundry_evm::*;
@@ -42,6 +57,23 @@ pub struct TestOptions {
     pub inline_fuzz: InlineConfig<FuzzConfig>,
     /// Contains per-test specific "invariant" configurations.
     pub inline_invariant: InlineConfig<InvariantConfig>,
+    /// The counterexample to replay instead of fuzzing its test, if any.
+    pub replay: Option<FuzzFailure>,
+    /// Whether to profile the execution of each test.
+    pub profile: bool,
+    /// Contains the sources of the tests marked to be executed symbolically.
+    pub inline_symbolic: InlineConfig<SymbolicSource>,
+    /// The executor to export the tests marked to be executed symbolically to, if any.
+    pub symbolic: Option<Arc<dyn SymbolicExecutor>>,
+    /// Whether to cancel the remaining tests once a test fails.
+    pub fail_fast: bool,
+    /// Cancels the remaining tests, including the in-flight fuzz and invariant tests.
+    pub cancellation: CancellationToken,
+    /// The seed to shuffle the order of the test contracts and tests with, if any. Shuffled tests
+    /// are run one at a time, so that the order is reproducible.
+    pub shuffle_seed: Option<u64>,
+    /// Contains the fixtures files of the table-driven tests.
+    pub inline_fixtures: InlineConfig<PathBuf>,
 }
 
 impl TestOptions {
@@ -57,6 +89,8 @@ impl TestOptions {
         let natspecs: Vec<NatSpec> = NatSpec::parse(output, root);
         let mut inline_invariant = InlineConfig::<InvariantConfig>::default();
         let mut inline_fuzz = InlineConfig::<FuzzConfig>::default();
+        let mut inline_symbolic = InlineConfig::<SymbolicSource>::default();
+        let mut inline_fixtures = InlineConfig::<PathBuf>::default();
 
         for natspec in natspecs {
             // Perform general validation
@@ -81,9 +115,32 @@ impl TestOptions {
                 Ok(None) => { /* No inline config found, do nothing */ }
                 Err(e) => Err(InlineConfigError { line: line.clone(), source: e })?,
             }
+
+            match natspec.symbolic() {
+                Ok(Some(true)) => inline_symbolic.insert(c, f, SymbolicSource::read(root, c, f)),
+                Ok(_) => { /* Not marked as symbolic, do nothing */ }
+                Err(e) => Err(InlineConfigError { line: line.clone(), source: e })?,
+            }
+
+            if let Some(fixtures) = natspec.fixtures() {
+                inline_fixtures.insert(c, f, root.join(fixtures));
+            }
         }
 
-        Ok(Self { fuzz: base_fuzz, invariant: base_invariant, inline_fuzz, inline_invariant })
+        Ok(Self {
+            fuzz: base_fuzz,
+            invariant: base_invariant,
+            inline_fuzz,
+            inline_invariant,
+            replay: None,
+            profile: false,
+            inline_symbolic,
+            symbolic: None,
+            fail_fast: false,
+            cancellation: CancellationToken::default(),
+            shuffle_seed: None,
+            inline_fixtures,
+        })
     }
 
     /// Returns a "fuzz" test runner instance. Parameters are used to select tight scoped fuzz
@@ -93,12 +150,14 @@ impl TestOptions {
     /// - `contract_id` is the id of the test contract, expressed as a relative path from the
     ///   project root.
     /// - `test_fn` is the name of the test function declared inside the test contract.
-    pub fn fuzz_runner<S>(&self, contract_id: S, test_fn: S) -> TestRunner
+    /// - `seed` is the seed of the runner's RNG, see [`TestOptions::fuzz_seed`].
+    pub fn fuzz_runner<S>(&self, contract_id: S, test_fn: S, seed: U256) -> TestRunner
     where
         S: Into<String>,
     {
         let fuzz = self.fuzz_config(contract_id, test_fn);
-        self.fuzzer_with_cases(fuzz.runs)
+        // Tests bounded by a timeout run until it is reached
+        self.fuzzer_with_cases(if fuzz.timeout.is_some() { u32::MAX } else { fuzz.runs }, seed)
     }
 
     /// Returns an "invariant" test runner instance. Parameters are used to select tight scoped fuzz
@@ -108,12 +167,15 @@ impl TestOptions {
     /// - `contract_id` is the id of the test contract, expressed as a relative path from the
     ///   project root.
     /// - `test_fn` is the name of the test function declared inside the test contract.
-    pub fn invariant_runner<S>(&self, contract_id: S, test_fn: S) -> TestRunner
+    /// - `seed` is the seed of the runner's RNG, see [`TestOptions::fuzz_seed`].
+    pub fn invariant_runner<S>(&self, contract_id: S, test_fn: S, seed: U256) -> TestRunner
     where
         S: Into<String>,
     {
         let invariant = self.invariant_config(contract_id, test_fn);
-        self.fuzzer_with_cases(invariant.runs)
+        // Campaigns bounded by a timeout run until it is reached
+        let cases = if invariant.timeout.is_some() { u32::MAX } else { invariant.runs };
+        self.fuzzer_with_cases(cases, seed)
     }
 
     /// Returns a "fuzz" configuration setup. Parameters are used to select tight scoped fuzz
@@ -144,23 +206,84 @@ impl TestOptions {
         self.inline_invariant.get(contract_id, test_fn).unwrap_or(&self.invariant)
     }
 
-    pub fn fuzzer_with_cases(&self, cases: u32) -> TestRunner {
+    /// Returns the symbolic executor and the source of a test, if it is marked to be executed
+    /// symbolically and an executor is set.
+    ///
+    /// - `contract_id` is the id of the test contract, expressed as a relative path from the
+    ///   project root.
+    /// - `test_fn` is the name of the test function declared inside the test contract.
+    pub fn symbolic_test<S>(
+        &self,
+        contract_id: S,
+        test_fn: S,
+    ) -> Option<(&dyn SymbolicExecutor, &SymbolicSource)>
+    where
+        S: Into<String>,
+    {
+        let executor = self.symbolic.as_deref()?;
+        Some((executor, self.inline_symbolic.get(contract_id, test_fn)?))
+    }
+
+    /// Returns the fixtures file of a table-driven test, if any.
+    ///
+    /// - `contract_id` is the id of the test contract, expressed as a relative path from the
+    ///   project root.
+    /// - `test_fn` is the name of the test function declared inside the test contract.
+    pub fn fixtures<S>(&self, contract_id: S, test_fn: S) -> Option<&Path>
+    where
+        S: Into<String>,
+    {
+        self.inline_fixtures.get(contract_id, test_fn).map(PathBuf::as_path)
+    }
+
+    /// Shuffles the test contracts, or the tests of the test contract `contract_id`, into the order
+    /// they are run in, if a shuffle seed is set.
+    pub fn shuffle<T>(&self, items: &mut [T], contract_id: &str) {
+        if let Some(seed) = self.shuffle_seed {
+            let mut rng = StdRng::from_seed(keccak256(format!("{seed}:{contract_id}")).0);
+            items.shuffle(&mut rng);
+        }
+    }
+
+    /// Returns the minimum number of the given test contracts or tests each worker runs, which is
+    /// all of them if they are shuffled, so that they are run one at a time in order.
+    pub fn min_worker_len(&self, len: usize) -> usize {
+        if self.shuffle_seed.is_some() {
+            len.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Returns the seed to run a fuzz or invariant test with: the configured one, or a random one
+    /// which is reported with the test's result so that its run can be reproduced.
+    pub fn fuzz_seed(&self) -> U256 {
+        self.fuzz.seed.unwrap_or_else(|| U256::from_be_bytes(rand::random::<[u8; 32]>()))
+    }
+
+    pub fn fuzzer_with_cases(&self, cases: u32, seed: U256) -> TestRunner {
+        self.fuzzer_with_cases_and_rejects(cases, self.fuzz.max_test_rejects, seed)
+    }
+
+    /// Returns a test runner instance with the given number of cases and maximum number of
+    /// rejected inputs, whose RNG is seeded with the given seed.
+    pub fn fuzzer_with_cases_and_rejects(
+        &self,
+        cases: u32,
+        max_rejects: u32,
+        seed: U256,
+    ) -> TestRunner {
         // TODO: Add Options to modify the persistence
         let cfg = proptest::test_runner::Config {
             failure_persistence: None,
             cases,
-            max_global_rejects: self.fuzz.max_test_rejects,
+            max_global_rejects: max_rejects,
             ..Default::default()
         };
 
-        if let Some(ref fuzz_seed) = self.fuzz.seed {
-            trace!(target: "forge::test", "building deterministic fuzzer with seed {}", fuzz_seed);
-            let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &B256::from(*fuzz_seed).0);
-            TestRunner::new_with_rng(cfg, rng)
-        } else {
-            trace!(target: "forge::test", "building stochastic fuzzer");
-            TestRunner::new(cfg)
-        }
+        trace!(target: "forge::test", "building fuzzer with seed {}", seed);
+        let rng = TestRng::from_seed(RngAlgorithm::ChaCha, &B256::from(seed).0);
+        TestRunner::new_with_rng(cfg, rng)
     }
 }
 
@@ -171,6 +294,11 @@ pub struct TestOptionsBuilder {
     fuzz: Option<FuzzConfig>,
     invariant: Option<InvariantConfig>,
     profiles: Option<Vec<String>>,
+    replay: Option<FuzzFailure>,
+    profile: bool,
+    symbolic: Option<Arc<dyn SymbolicExecutor>>,
+    fail_fast: bool,
+    shuffle_seed: Option<u64>,
 }
 
 impl TestOptionsBuilder {
@@ -193,6 +321,36 @@ impl TestOptionsBuilder {
         self
     }
 
+    /// Sets a [`FuzzFailure`] to replay instead of fuzzing its test.
+    pub fn replay(mut self, failure: FuzzFailure) -> Self {
+        self.replay = Some(failure);
+        self
+    }
+
+    /// Sets whether to profile the execution of each test.
+    pub fn profile(mut self, profile: bool) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// Sets the executor to export the tests marked to be executed symbolically to.
+    pub fn symbolic(mut self, executor: Arc<dyn SymbolicExecutor>) -> Self {
+        self.symbolic = Some(executor);
+        self
+    }
+
+    /// Sets whether to cancel the remaining tests once a test fails.
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
+    /// Sets the seed to shuffle the order of the test contracts and tests with.
+    pub fn shuffle(mut self, seed: u64) -> Self {
+        self.shuffle_seed = Some(seed);
+        self
+    }
+
     /// Creates an instance of [`TestOptions`]. This takes care of creating "fuzz" and
     /// "invariant" fallbacks, and extracting all inline test configs, if available.
     ///
@@ -208,7 +366,13 @@ impl TestOptionsBuilder {
             self.profiles.unwrap_or_else(|| vec![Config::selected_profile().into()]);
         let base_fuzz = self.fuzz.unwrap_or_default();
         let base_invariant = self.invariant.unwrap_or_default();
-        TestOptions::new(output, root, profiles, base_fuzz, base_invariant)
+        let mut options = TestOptions::new(output, root, profiles, base_fuzz, base_invariant)?;
+        options.replay = self.replay;
+        options.profile = self.profile;
+        options.symbolic = self.symbolic;
+        options.fail_fast = self.fail_fast;
+        options.shuffle_seed = self.shuffle_seed;
+        Ok(options)
     }
 }
 
@@ -17,8 +17,9 @@ use foundry_evm::{
     backend::Backend,
     executors::{Executor, ExecutorBuilder},
     fork::CreateFork,
-    inspectors::CheatsConfig,
+    inspectors::{CheatsConfig, GasSchedule},
     opts::EvmOpts,
+    precompiles::ChainPrecompiles,
     revm,
 };
 use rayon::prelude::*;
@@ -56,6 +57,10 @@ pub struct MultiContractRunner {
     pub fork: Option<CreateFork>,
     /// Additional cheatcode inspector related settings derived from the `Config`
     pub cheats_config: Arc<CheatsConfig>,
+    /// The chain specific precompiles to add to the EVM
+    pub precompiles: ChainPrecompiles,
+    /// The overridden gas costs of the EVM
+    pub gas_schedule: GasSchedule,
     /// Whether to collect coverage info
     pub coverage: bool,
     /// Whether to collect debug info
@@ -78,8 +83,12 @@ impl MultiContractRunner {
         self.contracts
             .iter()
             .filter(|(id, _)| filter.matches_path(&id.source) && filter.matches_contract(&id.name))
-            .flat_map(|(_, (abi, _, _))| {
-                abi.functions().filter(|func| filter.matches_test(&func.signature()))
+            .flat_map(|(id, (abi, _, _))| {
+                let identifier = id.identifier();
+                abi.functions().filter(move |func| {
+                    filter.matches_test(&func.signature()) &&
+                        filter.matches_contract_test(&identifier, &func.signature())
+                })
             })
     }
 
@@ -169,15 +178,21 @@ impl MultiContractRunner {
         // the db backend that serves all the data, each contract gets its own instance
         let db = Backend::spawn(self.fork.take()).await;
 
-        self.contracts
-            .par_iter()
-            .filter(|(id, _)| filter.matches_path(&id.source) && filter.matches_contract(&id.name))
-            .filter(|(_, (abi, _, _))| abi.functions().any(|func| filter.matches_test(&func.name)))
-            .for_each_with(stream_result, |stream_result, (id, (abi, deploy_code, libs))| {
+        let contracts = self.contracts_in_order(filter, &test_options);
+        let min_len = test_options.min_worker_len(contracts.len());
+        contracts.into_par_iter().with_min_len(min_len).for_each_with(
+            stream_result,
+            |stream_result, (id, (abi, deploy_code, libs))| {
+                if test_options.cancellation.is_cancelled() {
+                    return
+                }
+
                 let executor = ExecutorBuilder::new()
                     .inspectors(|stack| {
                         stack
                             .cheatcodes(self.cheats_config.clone())
+                            .precompiles(self.precompiles.clone())
+                            .gas_schedule(self.gas_schedule.clone())
                             .trace(self.evm_opts.verbosity >= 3 || self.debug)
                             .debug(self.debug)
                             .coverage(self.coverage)
@@ -199,8 +214,63 @@ impl MultiContractRunner {
                 );
                 trace!(contract=?identifier, "executed all tests in contract");
 
+                // Failures of `setUp` also cancel the remaining tests
+                if test_options.fail_fast && result.failures().next().is_some() {
+                    test_options.cancellation.cancel();
+                }
+                // All the tests of the contract were interrupted by a cancellation
+                if result.is_empty() && test_options.cancellation.is_cancelled() {
+                    return
+                }
+
                 let _ = stream_result.send((identifier, result));
+            },
+        )
+    }
+
+    /// Returns the test contracts matching the filter, in the order they are run in.
+    fn contracts_in_order<'a>(
+        &'a self,
+        filter: &dyn TestFilter,
+        test_options: &TestOptions,
+    ) -> Vec<(&'a ArtifactId, &'a (JsonAbi, Bytes, Vec<Bytes>))> {
+        let mut contracts: Vec<_> = self
+            .contracts
+            .iter()
+            .filter(|(id, _)| filter.matches_path(&id.source) && filter.matches_contract(&id.name))
+            .filter(|(_, (abi, _, _))| abi.functions().any(|func| filter.matches_test(&func.name)))
+            .collect();
+        test_options.shuffle(&mut contracts, "");
+        contracts
+    }
+
+    /// Returns the test contracts and the signatures of their tests matching the filter, in the
+    /// order they are run in.
+    pub fn test_order(
+        &self,
+        filter: &dyn TestFilter,
+        test_options: &TestOptions,
+    ) -> Vec<(String, Vec<String>)> {
+        self.contracts_in_order(filter, test_options)
+            .into_iter()
+            .map(|(id, (abi, _, _))| {
+                let identifier = id.identifier();
+                let mut functions: Vec<_> = abi.functions().collect();
+                test_options.shuffle(&mut functions, &identifier);
+                // Unit and fuzz tests are run before invariant tests
+                let tests = functions
+                    .iter()
+                    .filter(|func| func.is_test())
+                    .chain(functions.iter().filter(|func| func.is_invariant_test()))
+                    .map(|func| func.signature())
+                    .filter(|signature| {
+                        filter.matches_test(signature) &&
+                            filter.matches_contract_test(&identifier, signature)
+                    })
+                    .collect();
+                (identifier, tests)
             })
+            .collect()
     }
 
     #[instrument(skip_all, fields(name = %name))]
@@ -348,6 +418,8 @@ impl MultiContractRunnerBuilder {
         )?;
 
         let execution_info = known_contracts.flatten();
+        let precompiles = ChainPrecompiles::from_names(&evm_opts.precompiles)?;
+        let gas_schedule = GasSchedule::new(&evm_opts.gas_overrides)?;
         Ok(MultiContractRunner {
             contracts: deployable_contracts,
             known_contracts,
@@ -359,6 +431,8 @@ impl MultiContractRunnerBuilder {
             source_paths,
             fork: self.fork,
             cheats_config: self.cheats_config.unwrap_or_default().into(),
+            precompiles,
+            gas_schedule,
             coverage: self.coverage,
             debug: self.debug,
             test_options: self.test_options.unwrap_or_default(),
@@ -1,18 +1,19 @@
 //! Test outcomes.
 
-use alloy_primitives::{Address, Log};
+use alloy_primitives::{Address, Bytes, Log, U256};
 use foundry_common::evm::Breakpoints;
 use foundry_evm::{
     coverage::HitMaps,
     debug::DebugArena,
     executors::EvmError,
-    fuzz::{CounterExample, FuzzCase},
+    fuzz::{BaseCounterExample, CounterExample, FuzzCase},
     traces::{TraceKind, Traces},
 };
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{BTreeMap, HashMap},
     fmt::{self, Write},
+    path::{Path, PathBuf},
     time::Duration,
 };
 use yansi::Paint;
@@ -26,6 +27,9 @@ pub struct SuiteResult {
     pub test_results: BTreeMap<String, TestResult>,
     /// Warnings
     pub warnings: Vec<String>,
+    /// The reason the suite failed outside of its tests, e.g. because its `afterAll` hook failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub failure: Option<String>,
 }
 
 impl SuiteResult {
@@ -34,7 +38,13 @@ impl SuiteResult {
         test_results: BTreeMap<String, TestResult>,
         warnings: Vec<String>,
     ) -> Self {
-        Self { duration, test_results, warnings }
+        Self { duration, test_results, warnings, failure: None }
+    }
+
+    /// Returns the named values recorded with `vm.snapshotValue` by the passing tests of the
+    /// contract.
+    pub fn value_snapshots(&self) -> BTreeMap<String, Bytes> {
+        self.successes().flat_map(|(_, result)| result.value_snapshots.clone()).collect()
     }
 
     /// Iterator over all succeeding tests and their names
@@ -133,6 +143,17 @@ pub struct TestResult {
 
     /// pc breakpoint char map
     pub breakpoints: Breakpoints,
+
+    /// The execution profile of the test, if profiling is enabled
+    pub profile: Option<TestProfile>,
+
+    /// The seed the fuzz or invariant test was run with
+    pub fuzz_seed: Option<U256>,
+
+    /// The named values recorded with `vm.snapshotValue` by the test, if it passed without
+    /// reverting
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub value_snapshots: BTreeMap<String, Bytes>,
 }
 
 impl fmt::Display for TestResult {
@@ -256,6 +277,19 @@ impl TestKind {
     }
 }
 
+/// The execution profile of a test, see `forge test --profile-tests`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestProfile {
+    /// Wall-clock time of the test, excluding compilation and the setup of its contract
+    pub duration: Duration,
+    /// Time spent executing the EVM
+    pub evm_time: Duration,
+    /// Time spent waiting on fork RPC requests during EVM executions
+    pub rpc_time: Duration,
+    /// Number of calls to cheatcodes
+    pub cheatcode_calls: u64,
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TestSetup {
     /// The address at which the test contract was deployed
@@ -326,3 +360,95 @@ impl TestSetup {
         Self { reason: Some(reason), ..Default::default() }
     }
 }
+
+/// The counterexample of a failed fuzz or invariant test, persisted to be shared and replayed with
+/// `forge test --replay`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzFailure {
+    /// The identifier of the test contract, `<path>:<name>`
+    pub contract: String,
+    /// The signature of the test function
+    pub test: String,
+    /// The fuzz seed of the failed run, if any
+    pub seed: Option<U256>,
+    /// The reason of the failure
+    pub reason: Option<String>,
+    /// The calls of the counterexample: the single call of a fuzz test, or the sequence of calls
+    /// of an invariant test
+    pub calls: Vec<FuzzFailureCall>,
+}
+
+/// A call of a [`FuzzFailure`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FuzzFailureCall {
+    /// The sender of the call, if not the test sender
+    pub sender: Option<Address>,
+    /// The target of the call, if not the test contract
+    pub target: Option<Address>,
+    /// The data of the call
+    pub calldata: Bytes,
+    /// The signature of the called function, if known
+    pub signature: Option<String>,
+    /// The decoded arguments of the call
+    pub args: Vec<String>,
+}
+
+impl FuzzFailure {
+    /// Creates the failure of the given test from its result, if it has a counterexample.
+    pub fn new(contract: &str, test: &str, result: &TestResult) -> Option<Self> {
+        let calls = match result.counterexample.as_ref()? {
+            CounterExample::Single(call) => vec![call.into()],
+            CounterExample::Sequence(sequence) => sequence.iter().map(Into::into).collect(),
+        };
+        Some(Self {
+            contract: contract.to_string(),
+            test: test.to_string(),
+            seed: result.fuzz_seed,
+            reason: result.reason.clone(),
+            calls,
+        })
+    }
+
+    /// Returns whether this is the failure of the given test.
+    pub fn matches(&self, contract: &str, test: &str) -> bool {
+        self.contract == contract && self.test == test
+    }
+
+    /// Returns the path of the file of this failure in the given directory, see
+    /// [`FuzzFailure::path_of`].
+    pub fn path(&self, dir: &Path) -> PathBuf {
+        Self::path_of(dir, &self.contract, &self.test)
+    }
+
+    /// Returns the path of the file of the failure of the given test in the given directory:
+    /// `<dir>/<contract name>.<test signature>.json`, so that overloaded tests have their own.
+    pub fn path_of(dir: &Path, contract: &str, test: &str) -> PathBuf {
+        let contract = contract.rsplit(':').next().unwrap_or(contract);
+        dir.join(format!("{contract}.{test}.json"))
+    }
+
+    /// Reads a failure from the given file.
+    pub fn read(path: &Path) -> eyre::Result<Self> {
+        Ok(foundry_common::fs::read_json_file(path)?)
+    }
+
+    /// Writes this failure to its file in the given directory, returning its path.
+    pub fn write(&self, dir: &Path) -> eyre::Result<PathBuf> {
+        let path = self.path(dir);
+        foundry_common::fs::create_dir_all(dir)?;
+        foundry_common::fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+impl From<&BaseCounterExample> for FuzzFailureCall {
+    fn from(call: &BaseCounterExample) -> Self {
+        Self {
+            sender: call.sender,
+            target: call.addr,
+            calldata: call.calldata.clone(),
+            signature: call.signature.clone(),
+            args: foundry_common::fmt::format_tokens(&call.args).collect(),
+        }
+    }
+}
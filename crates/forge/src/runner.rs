@@ -1,9 +1,12 @@
 //! The Forge test runner.
 
 use crate::{
-    result::{SuiteResult, TestKind, TestResult, TestSetup, TestStatus},
+    fixtures::load_cases,
+    result::{FuzzFailure, SuiteResult, TestKind, TestProfile, TestResult, TestSetup, TestStatus},
+    symbolic::{calldata_layout, SymbolicExecutor, SymbolicOutcome, SymbolicSource, SymbolicTest},
     TestFilter, TestOptions,
 };
+use alloy_dyn_abi::{DynSolValue, JsonAbiExt};
 use alloy_json_abi::{Function, JsonAbi};
 use alloy_primitives::{Address, Bytes, U256};
 use eyre::Result;
@@ -15,19 +18,21 @@ use foundry_config::{FuzzConfig, InvariantConfig};
 use foundry_evm::{
     constants::CALLER,
     coverage::HitMaps,
-    decode::decode_console_logs,
+    decode::{decode_console_logs, decode_revert, maybe_decode_revert},
     executors::{
         fuzz::{CaseOutcome, CounterExampleOutcome, FuzzOutcome, FuzzedExecutor},
         invariant::{replay_run, InvariantExecutor, InvariantFuzzError, InvariantFuzzTestResult},
-        CallResult, EvmError, ExecutionErr, Executor,
+        CallResult, CancellationToken, EvmError, ExecutionErr, ExecutionProfile, Executor,
     },
-    fuzz::{invariant::InvariantContract, CounterExample},
-    traces::{load_contracts, TraceKind},
+    fuzz::{invariant::InvariantContract, BaseCounterExample, CounterExample, FuzzCase},
+    traces::{load_contracts, TraceKind, Traces},
 };
 use proptest::test_runner::{TestError, TestRunner};
 use rayon::prelude::*;
 use std::{
     collections::{BTreeMap, HashMap},
+    path::Path,
+    sync::Arc,
     time::Instant,
 };
 
@@ -51,6 +56,8 @@ pub struct ContractRunner<'a> {
     pub sender: Address,
     /// Should generate debug traces
     pub debug: bool,
+    /// Stops the fuzz and invariant tests once cancelled
+    pub cancellation: CancellationToken,
 }
 
 impl<'a> ContractRunner<'a> {
@@ -76,6 +83,7 @@ impl<'a> ContractRunner<'a> {
             errors,
             predeploy_libs,
             debug,
+            cancellation: Default::default(),
         }
     }
 }
@@ -181,6 +189,7 @@ impl<'a> ContractRunner<'a> {
         info!("starting tests");
         let start = Instant::now();
         let mut warnings = Vec::new();
+        self.cancellation = test_options.cancellation.clone();
 
         let setup_fns: Vec<_> =
             self.contract.functions().filter(|func| func.name.is_setup()).collect();
@@ -208,6 +217,7 @@ impl<'a> ContractRunner<'a> {
         }
 
         let has_invariants = self.contract.functions().any(|func| func.is_invariant_test());
+        let hooks = LifecycleHooks::new(self.contract);
 
         // Invariant testing requires tracing to figure out what contracts were created.
         let tmp_tracing = self.executor.inspector.tracer.is_none() && has_invariants && needs_setup;
@@ -243,20 +253,83 @@ impl<'a> ContractRunner<'a> {
             )
         }
 
-        let functions: Vec<_> = self.contract.functions().collect();
+        let mut functions: Vec<_> = self.contract.functions().collect();
+        test_options.shuffle(&mut functions, self.name);
+        let min_len = test_options.min_worker_len(functions.len());
         let mut test_results = functions
             .par_iter()
-            .filter(|&&func| func.is_test() && filter.matches_test(&func.signature()))
-            .map(|&func| {
+            .with_min_len(min_len)
+            .filter(|&&func| {
+                func.is_test() &&
+                    filter.matches_test(&func.signature()) &&
+                    filter.matches_contract_test(self.name, &func.signature())
+            })
+            .filter(|_| !self.cancellation.is_cancelled())
+            .flat_map_iter(|&func| {
                 let should_fail = func.is_test_fail();
-                let res = if func.is_fuzz_test() {
-                    let runner = test_options.fuzz_runner(self.name, &func.name);
-                    let fuzz_config = test_options.fuzz_config(self.name, &func.name);
-                    self.run_fuzz_test(func, should_fail, runner, setup.clone(), *fuzz_config)
-                } else {
-                    self.run_test(func, should_fail, setup.clone())
-                };
-                (func.signature(), res)
+                if let Some(fixtures) = test_options.fixtures(self.name, &func.name) {
+                    return self.run_table_test(
+                        func,
+                        should_fail,
+                        &setup,
+                        fixtures,
+                        hooks,
+                        &test_options,
+                    )
+                }
+                let res = self.profiled(test_options.profile, |this| {
+                    this.with_hooks(hooks, setup.address, |this| {
+                        if let Some(failure) = test_options
+                            .replay
+                            .as_ref()
+                            .filter(|failure| failure.matches(this.name, &func.signature()))
+                        {
+                            (this.replay_fuzz_test(func, should_fail, setup.clone(), failure), None)
+                        } else if let Some((executor, source)) =
+                            test_options.symbolic_test(this.name, &func.name)
+                        {
+                            (this.run_symbolic_test(func, executor, source), None)
+                        } else if func.is_fuzz_test() {
+                            let mut fuzz_config = *test_options.fuzz_config(this.name, &func.name);
+                            // `vm.setFuzzRuns` and `vm.setMaxRejects` in `setUp` take
+                            // precedence over the project and inline configurations
+                            if let Some(cheatcodes) = &this.executor.inspector.cheatcodes {
+                                fuzz_config.runs = cheatcodes.fuzz_runs.unwrap_or(fuzz_config.runs);
+                                fuzz_config.max_test_rejects = cheatcodes
+                                    .fuzz_max_rejects
+                                    .unwrap_or(fuzz_config.max_test_rejects);
+                            }
+                            // Tests bounded by a timeout run until it is reached
+                            let runs = if fuzz_config.timeout.is_some() {
+                                u32::MAX
+                            } else {
+                                fuzz_config.runs
+                            };
+                            let seed = test_options.fuzz_seed();
+                            let runner = test_options.fuzzer_with_cases_and_rejects(
+                                runs,
+                                fuzz_config.max_test_rejects,
+                                seed,
+                            );
+                            let result = this.run_fuzz_test(
+                                func,
+                                should_fail,
+                                runner,
+                                setup.clone(),
+                                fuzz_config,
+                            );
+                            (TestResult { fuzz_seed: Some(seed), ..result }, None)
+                        } else {
+                            let (result, executor) =
+                                this.run_test_with_args(func, vec![], should_fail, setup.clone());
+                            (result, Some(executor))
+                        }
+                    })
+                });
+                fail_fast(&test_options, res)
+                    .map(|res| (func.signature(), res))
+                    .into_iter()
+                    .collect()
             })
             .collect::<BTreeMap<_, _>>();
 
@@ -264,24 +337,69 @@ impl<'a> ContractRunner<'a> {
             let identified_contracts = load_contracts(setup.traces.clone(), known_contracts);
             let results: Vec<_> = functions
                 .par_iter()
-                .filter(|&&func| func.is_invariant_test() && filter.matches_test(&func.signature()))
-                .map(|&func| {
-                    let runner = test_options.invariant_runner(self.name, &func.name);
-                    let invariant_config = test_options.invariant_config(self.name, &func.name);
-                    let res = self.run_invariant_test(
-                        runner,
-                        setup.clone(),
-                        *invariant_config,
-                        func,
-                        known_contracts,
-                        &identified_contracts,
-                    );
-                    (func.signature(), res)
+                .with_min_len(min_len)
+                .filter(|&&func| {
+                    func.is_invariant_test() &&
+                        filter.matches_test(&func.signature()) &&
+                        filter.matches_contract_test(self.name, &func.signature())
+                })
+                .filter(|_| !self.cancellation.is_cancelled())
+                .filter_map(|&func| {
+                    let res = self.profiled(test_options.profile, |this| {
+                        this.with_hooks(hooks, setup.address, |this| {
+                            if let Some(failure) = test_options
+                                .replay
+                                .as_ref()
+                                .filter(|failure| failure.matches(this.name, &func.signature()))
+                            {
+                                let result = this.replay_invariant_test(
+                                    func,
+                                    setup.clone(),
+                                    failure,
+                                    test_options
+                                        .invariant_config(this.name, &func.name)
+                                        .fail_on_revert,
+                                    known_contracts,
+                                    &identified_contracts,
+                                );
+                                return (result, None)
+                            }
+
+                            let seed = test_options.fuzz_seed();
+                            let runner = test_options.invariant_runner(this.name, &func.name, seed);
+                            let mut invariant_config =
+                                test_options.invariant_config(this.name, &func.name).clone();
+                            // Keep a separate corpus for every invariant test
+                            if let Some(corpus_dir) = &mut invariant_config.corpus_dir {
+                                let contract = this.name.rsplit(':').next().unwrap_or(this.name);
+                                *corpus_dir = corpus_dir.join(contract).join(&func.name);
+                            }
+                            let result = this.run_invariant_test(
+                                runner,
+                                setup.clone(),
+                                invariant_config,
+                                func,
+                                known_contracts,
+                                &identified_contracts,
+                            );
+                            (TestResult { fuzz_seed: Some(seed), ..result }, None)
+                        })
+                    });
+                    Some((func.signature(), fail_fast(&test_options, res)?))
                 })
                 .collect();
             test_results.extend(results);
         }
 
+        // Call `afterAll` once all the tests have run, failing the suite if it fails. The state
+        // changes of the tests are isolated from each other, so it's called on the state of `setUp`
+        let mut failure = None;
+        if hooks.after_all && !test_results.is_empty() {
+            if let Err(reason) = self.call_hook("afterAll()", setup.address, &mut Vec::new()) {
+                failure = Some(format!("afterAll() failed: {reason}"));
+            }
+        }
+
         let duration = start.elapsed();
         if !test_results.is_empty() {
             let successful =
@@ -294,7 +412,152 @@ impl<'a> ContractRunner<'a> {
             );
         }
 
-        SuiteResult::new(duration, test_results, warnings)
+        SuiteResult { failure, ..SuiteResult::new(duration, test_results, warnings) }
+    }
+
+    /// Runs a test with the given closure, profiling its execution if `profile` is set.
+    ///
+    /// The test is run with a copy of this runner, so that only the executions of this test are
+    /// recorded in its profile.
+    fn profiled(&self, profile: bool, f: impl FnOnce(&Self) -> TestResult) -> TestResult {
+        if !profile {
+            return f(self)
+        }
+
+        let execution_profile = Arc::new(ExecutionProfile::default());
+        let mut runner = self.clone();
+        runner.executor.inspector.profile = Some(execution_profile.clone());
+        let start = Instant::now();
+        let mut result = f(&runner);
+        result.profile = Some(TestProfile {
+            duration: start.elapsed(),
+            evm_time: execution_profile.evm_time(),
+            rpc_time: execution_profile.rpc_time(),
+            cheatcode_calls: execution_profile.cheatcode_calls(),
+        });
+        result
+    }
+
+    /// Runs a test with the given closure, calling the `beforeEach` and `afterEach` hooks of the
+    /// test contract around it.
+    ///
+    /// The hooks are called with a copy of this runner, so that the state changes of `beforeEach`
+    /// are only seen by this test. `afterEach` is called on the state the test left, if the closure
+    /// returns the executor of the test, and its state changes are discarded with the copy. Fuzz
+    /// and invariant tests have no single final state, so their `afterEach` is called on the state
+    /// left by `beforeEach`.
+    fn with_hooks(
+        &self,
+        hooks: LifecycleHooks,
+        address: Address,
+        f: impl FnOnce(&Self) -> (TestResult, Option<Executor>),
+    ) -> TestResult {
+        if !hooks.before_each && !hooks.after_each {
+            return f(self).0
+        }
+
+        let mut runner = self.clone();
+        let mut traces = Vec::new();
+        if hooks.before_each {
+            if let Err(reason) = runner.call_hook("beforeEach()", address, &mut traces) {
+                return TestResult {
+                    status: TestStatus::Failure,
+                    reason: Some(format!("beforeEach() failed: {reason}")),
+                    traces,
+                    ..Default::default()
+                }
+            }
+        }
+
+        let (mut result, executor) = f(&runner);
+
+        // Show the trace of `beforeEach` before the trace of the test
+        let execution = result
+            .traces
+            .iter()
+            .position(|(kind, _)| *kind == TraceKind::Execution)
+            .unwrap_or(result.traces.len());
+        result.traces.splice(execution..execution, traces.drain(..));
+
+        if hooks.after_each {
+            if let Some(executor) = executor {
+                runner.executor = executor;
+            }
+            // A failed test also fails the `afterEach` called on its state, so its own failure is
+            // kept
+            if let Err(reason) = runner.call_hook("afterEach()", address, &mut traces) {
+                if result.status != TestStatus::Failure {
+                    result.status = TestStatus::Failure;
+                    result.reason = Some(format!("afterEach() failed: {reason}"));
+                }
+            }
+            result.traces.extend(traces);
+        }
+        result
+    }
+
+    /// Calls a lifecycle hook of the test contract, committing its state changes, and returns the
+    /// reason it failed, if it did.
+    fn call_hook(
+        &mut self,
+        hook: &str,
+        address: Address,
+        traces: &mut Traces,
+    ) -> Result<(), String> {
+        match self.executor.call_committing::<_, _>(
+            self.sender,
+            address,
+            hook,
+            vec![],
+            U256::ZERO,
+            self.errors,
+        ) {
+            Ok(CallResult { traces: hook_traces, state_changeset, .. }) => {
+                traces.extend(hook_traces.map(|traces| (TraceKind::Setup, traces)));
+                let state_changeset = state_changeset.unwrap_or_default();
+                if self.executor.is_success(address, false, state_changeset, false) {
+                    Ok(())
+                } else {
+                    Err("assertion failed".to_string())
+                }
+            }
+            Err(EvmError::Execution(err)) => {
+                traces.extend(err.traces.map(|traces| (TraceKind::Setup, traces)));
+                Err(err.reason)
+            }
+            Err(err) => Err(err.to_string()),
+        }
+    }
+
+    /// Exports a test to a symbolic executor instead of running it, and reports the executor's
+    /// verdict as its result.
+    #[instrument(name = "symbolic-test", skip_all, fields(name = %func.signature()))]
+    pub fn run_symbolic_test(
+        &self,
+        func: &Function,
+        executor: &dyn SymbolicExecutor,
+        source: &SymbolicSource,
+    ) -> TestResult {
+        let test = SymbolicTest {
+            contract: self.name.to_string(),
+            function: func.clone(),
+            bytecode: self.code.clone(),
+            calldata: calldata_layout(func),
+            source: source.clone(),
+        };
+        let name = executor.name();
+        let (status, reason) = match executor.execute(&test) {
+            Ok(SymbolicOutcome::Proven) => (TestStatus::Success, None),
+            Ok(SymbolicOutcome::Counterexample(counterexample)) => (
+                TestStatus::Failure,
+                Some(format!("{name} found a counterexample: {counterexample}")),
+            ),
+            Ok(SymbolicOutcome::Unknown(reason)) => {
+                (TestStatus::Failure, Some(format!("{name} could not prove the test: {reason}")))
+            }
+            Err(err) => (TestStatus::Failure, Some(format!("{name} failed: {err}"))),
+        };
+        TestResult { status, reason, kind: TestKind::Standard(0), ..Default::default() }
     }
 
     /// Runs a single test
@@ -303,8 +566,62 @@ impl<'a> ContractRunner<'a> {
     ///
     /// State modifications are not committed to the evm database but discarded after the call,
     /// similar to `eth_call`.
-    #[instrument(name = "test", skip_all, fields(name = %func.signature(), %should_fail))]
     pub fn run_test(&self, func: &Function, should_fail: bool, setup: TestSetup) -> TestResult {
+        self.run_test_with_args(func, vec![], should_fail, setup).0
+    }
+
+    /// Runs a table-driven test once for each case of its fixtures file, returning the result of
+    /// each case under its own name.
+    fn run_table_test(
+        &self,
+        func: &Function,
+        should_fail: bool,
+        setup: &TestSetup,
+        fixtures: &Path,
+        hooks: LifecycleHooks,
+        test_options: &TestOptions,
+    ) -> Vec<(String, TestResult)> {
+        let cases = match load_cases(fixtures, func) {
+            Ok(cases) => cases,
+            Err(err) => {
+                let res = TestResult::fail(format!(
+                    "failed to load fixtures from {}: {err:#}",
+                    fixtures.display()
+                ));
+                return fail_fast(test_options, res)
+                    .map(|res| (func.signature(), res))
+                    .into_iter()
+                    .collect()
+            }
+        };
+        cases
+            .into_iter()
+            .take_while(|_| !self.cancellation.is_cancelled())
+            .filter_map(|case| {
+                let name = case.test_name(func);
+                let res = self.profiled(test_options.profile, |this| {
+                    this.with_hooks(hooks, setup.address, |this| {
+                        let (result, executor) =
+                            this.run_test_with_args(func, case.args, should_fail, setup.clone());
+                        (result, Some(executor))
+                    })
+                });
+                Some((name, fail_fast(test_options, res)?))
+            })
+            .collect()
+    }
+
+    /// Runs a single test with the given arguments.
+    ///
+    /// Returns the result of the test, and the executor in the state the test left it in.
+    #[instrument(name = "test", skip_all, fields(name = %func.signature(), %should_fail))]
+    fn run_test_with_args(
+        &self,
+        func: &Function,
+        args: Vec<DynSolValue>,
+        should_fail: bool,
+        setup: TestSetup,
+    ) -> (TestResult, Executor) {
         let TestSetup {
             address, mut logs, mut traces, mut labeled_addresses, mut coverage, ..
         } = setup;
@@ -313,12 +630,13 @@ impl<'a> ContractRunner<'a> {
         let mut executor = self.executor.clone();
         let start = Instant::now();
         let debug_arena;
+        let mut value_snapshots = BTreeMap::new();
         let (reverted, reason, gas, stipend, coverage, state_changeset, breakpoints) =
-            match executor.execute_test::<_, _>(
+            match executor.execute_test_committing::<_, _>(
                 self.sender,
                 address,
                 func.clone(),
-                vec![],
+                args,
                 U256::ZERO,
                 self.errors,
             ) {
@@ -333,12 +651,14 @@ impl<'a> ContractRunner<'a> {
                     state_changeset,
                     debug,
                     breakpoints,
+                    value_snapshots: new_value_snapshots,
                     ..
                 }) => {
                     traces.extend(execution_trace.map(|traces| (TraceKind::Execution, traces)));
                     labeled_addresses.extend(new_labels);
                     logs.extend(execution_logs);
                     debug_arena = debug;
+                    value_snapshots = new_value_snapshots;
                     coverage = merge_coverages(coverage, execution_coverage);
 
                     (reverted, None, gas, stipend, coverage, state_changeset, breakpoints)
@@ -359,7 +679,7 @@ impl<'a> ContractRunner<'a> {
                     )
                 }
                 Err(EvmError::SkipError) => {
-                    return TestResult {
+                    let result = TestResult {
                         status: TestStatus::Skipped,
                         reason: None,
                         decoded_logs: decode_console_logs(&logs),
@@ -367,10 +687,11 @@ impl<'a> ContractRunner<'a> {
                         labeled_addresses,
                         kind: TestKind::Standard(0),
                         ..Default::default()
-                    }
+                    };
+                    return (result, executor)
                 }
                 Err(err) => {
-                    return TestResult {
+                    let result = TestResult {
                         status: TestStatus::Failure,
                         reason: Some(err.to_string()),
                         decoded_logs: decode_console_logs(&logs),
@@ -378,7 +699,8 @@ impl<'a> ContractRunner<'a> {
                         labeled_addresses,
                         kind: TestKind::Standard(0),
                         ..Default::default()
-                    }
+                    };
+                    return (result, executor)
                 }
             };
 
@@ -398,7 +720,7 @@ impl<'a> ContractRunner<'a> {
             success,
         );
 
-        TestResult {
+        let result = TestResult {
             status: match success {
                 true => TestStatus::Success,
                 false => TestStatus::Failure,
@@ -413,7 +735,12 @@ impl<'a> ContractRunner<'a> {
             labeled_addresses,
             debug: debug_arena,
             breakpoints,
-        }
+            profile: None,
+            fuzz_seed: None,
+            // the values of failed or reverted tests aren't snapshotted
+            value_snapshots: if success && !reverted { value_snapshots } else { BTreeMap::new() },
+        };
+        (result, executor)
     }
 
     #[instrument(name = "invariant-test", skip_all)]
@@ -458,7 +785,8 @@ impl<'a> ContractRunner<'a> {
             invariant_config,
             identified_contracts,
             project_contracts,
-        );
+        )
+        .with_cancellation(self.cancellation.clone());
 
         let invariant_contract =
             InvariantContract { address, invariant_function: func, abi: self.contract };
@@ -561,7 +889,8 @@ impl<'a> ContractRunner<'a> {
         // Run fuzz test
         let start = Instant::now();
         let fuzzed_executor =
-            FuzzedExecutor::new(self.executor.clone(), runner.clone(), self.sender, fuzz_config);
+            FuzzedExecutor::new(self.executor.clone(), runner.clone(), self.sender, fuzz_config)
+                .with_cancellation(self.cancellation.clone());
         let state = fuzzed_executor.build_fuzz_state();
         let result = fuzzed_executor.fuzz(func, address, should_fail, self.errors);
 
@@ -657,7 +986,247 @@ impl<'a> ContractRunner<'a> {
             labeled_addresses,
             debug,
             breakpoints,
+            profile: None,
+            fuzz_seed: None,
+            value_snapshots: BTreeMap::new(),
+        }
+    }
+
+    /// Replays the counterexample of a failed fuzz test, instead of fuzzing the test.
+    #[instrument(name = "replay-fuzz-test", skip_all, fields(name = %func.signature()))]
+    pub fn replay_fuzz_test(
+        &self,
+        func: &Function,
+        should_fail: bool,
+        setup: TestSetup,
+        failure: &FuzzFailure,
+    ) -> TestResult {
+        let TestSetup { address, mut logs, mut traces, mut labeled_addresses, coverage, .. } =
+            setup;
+
+        let Some(calldata) = failure.calls.first().map(|call| call.calldata.clone()) else {
+            return TestResult::fail("the counterexample to replay has no calls".to_string())
+        };
+        let call = match self.executor.call_raw(self.sender, address, calldata.clone(), U256::ZERO)
+        {
+            Ok(call) => call,
+            Err(err) => {
+                return TestResult::fail(format!("failed to replay the counterexample: {err}"))
+            }
+        };
+
+        let success = self.executor.is_raw_call_success(
+            address,
+            call.state_changeset.clone().unwrap_or_default(),
+            &call,
+            should_fail,
+        );
+        let reason = if success {
+            None
+        } else {
+            maybe_decode_revert(&call.result, self.errors, Some(call.exit_reason))
+        };
+        let counterexample = (!success).then(|| {
+            let args = calldata
+                .get(4..)
+                .and_then(|data| func.abi_decode_input(data, false).ok())
+                .unwrap_or_default();
+            CounterExample::Single(BaseCounterExample {
+                sender: None,
+                addr: None,
+                signature: None,
+                contract_name: None,
+                traces: call.traces.clone(),
+                calldata: calldata.clone(),
+                args,
+            })
+        });
+
+        let gas = call.gas_used.saturating_sub(call.stipend);
+        let kind = TestKind::Fuzz {
+            first_case: FuzzCase { calldata, gas: call.gas_used, stipend: call.stipend },
+            runs: 1,
+            mean_gas: gas,
+            median_gas: gas,
+        };
+
+        logs.extend(call.logs);
+        labeled_addresses.extend(call.labels);
+        traces.extend(call.traces.map(|traces| (TraceKind::Execution, traces)));
+
+        TestResult {
+            status: match success {
+                true => TestStatus::Success,
+                false => TestStatus::Failure,
+            },
+            reason,
+            counterexample,
+            decoded_logs: decode_console_logs(&logs),
+            logs,
+            kind,
+            traces,
+            coverage,
+            labeled_addresses,
+            fuzz_seed: failure.seed,
+            ..Default::default()
+        }
+    }
+
+    /// Replays the call sequence of a failed invariant test, checking the invariant after every
+    /// call, instead of fuzzing the test.
+    #[instrument(name = "replay-invariant-test", skip_all, fields(name = %func.signature()))]
+    pub fn replay_invariant_test(
+        &self,
+        func: &Function,
+        setup: TestSetup,
+        failure: &FuzzFailure,
+        fail_on_revert: bool,
+        known_contracts: Option<&ContractsByArtifact>,
+        identified_contracts: &ContractsByAddress,
+    ) -> TestResult {
+        let TestSetup { address, mut logs, mut traces, labeled_addresses, coverage, .. } = setup;
+
+        let mut executor = self.executor.clone();
+        let mut identified_contracts = identified_contracts.clone();
+        let mut sequence = Vec::with_capacity(failure.calls.len());
+        let mut reverts = 0;
+        let mut reason = None;
+        let mut success = true;
+        for call in &failure.calls {
+            let sender = call.sender.unwrap_or(self.sender);
+            let target = call.target.unwrap_or(address);
+            let call_result = match executor.call_raw_committing(
+                sender,
+                target,
+                call.calldata.clone(),
+                U256::ZERO,
+            ) {
+                Ok(call_result) => call_result,
+                Err(err) => {
+                    return TestResult::fail(format!("failed to replay the call sequence: {err}"))
+                }
+            };
+
+            logs.extend(call_result.logs);
+            if let Some(call_traces) = &call_result.traces {
+                identified_contracts.extend(load_contracts(
+                    vec![(TraceKind::Execution, call_traces.clone())],
+                    known_contracts,
+                ));
+                traces.push((TraceKind::Execution, call_traces.clone()));
+            }
+            sequence.push(BaseCounterExample::create(
+                sender,
+                target,
+                &call.calldata,
+                &identified_contracts,
+                call_result.traces,
+            ));
+
+            if call_result.reverted {
+                reverts += 1;
+                if fail_on_revert {
+                    reason = Some(decode_revert(
+                        call_result.result.as_ref(),
+                        self.errors,
+                        Some(call_result.exit_reason),
+                    ));
+                    success = false;
+                    break
+                }
+            }
+
+            // Checks the invariant.
+            let mut invariant_result = match executor.call_raw(
+                CALLER,
+                address,
+                func.selector().to_vec().into(),
+                U256::ZERO,
+            ) {
+                Ok(invariant_result) => invariant_result,
+                Err(err) => {
+                    return TestResult::fail(format!("failed to check the invariant: {err}"))
+                }
+            };
+            traces.extend(
+                invariant_result.traces.clone().map(|traces| (TraceKind::Execution, traces)),
+            );
+            logs.extend(invariant_result.logs.clone());
+
+            let broken = invariant_result.reverted ||
+                !executor.is_raw_call_success(
+                    address,
+                    invariant_result.state_changeset.take().unwrap_or_default(),
+                    &invariant_result,
+                    false,
+                );
+            if broken {
+                reason = Some(decode_revert(
+                    invariant_result.result.as_ref(),
+                    Some(self.contract),
+                    Some(invariant_result.exit_reason),
+                ));
+                success = false;
+                break
+            }
+        }
+
+        TestResult {
+            status: match success {
+                true => TestStatus::Success,
+                false => TestStatus::Failure,
+            },
+            reason,
+            kind: TestKind::Invariant { runs: 1, calls: sequence.len(), reverts },
+            counterexample: (!success).then_some(CounterExample::Sequence(sequence)),
+            decoded_logs: decode_console_logs(&logs),
+            logs,
+            coverage,
+            traces,
+            labeled_addresses,
+            fuzz_seed: failure.seed,
+            ..Default::default()
+        }
+    }
+}
+
+/// The lifecycle hooks of a test contract, called around its tests in addition to `setUp`.
+#[derive(Clone, Copy, Debug, Default)]
+struct LifecycleHooks {
+    /// Whether `beforeEach()` is called before each test.
+    before_each: bool,
+    /// Whether `afterEach()` is called after each test.
+    after_each: bool,
+    /// Whether `afterAll()` is called once all the tests have run.
+    after_all: bool,
+}
+
+impl LifecycleHooks {
+    /// Detects the lifecycle hooks of a test contract.
+    fn new(contract: &JsonAbi) -> Self {
+        let has_hook = |name: &str| {
+            contract.functions().any(|func| func.name == name && func.inputs.is_empty())
+        };
+        Self {
+            before_each: has_hook("beforeEach"),
+            after_each: has_hook("afterEach"),
+            after_all: has_hook("afterAll"),
+        }
+    }
+}
+
+/// Cancels the remaining tests if the test failed and `fail_fast` is set, and discards the results
+/// of the tests that were interrupted by the cancellation.
+fn fail_fast(test_options: &TestOptions, result: TestResult) -> Option<TestResult> {
+    if result.status == TestStatus::Failure {
+        if test_options.fail_fast {
+            test_options.cancellation.cancel();
         }
+        Some(result)
+    } else if test_options.cancellation.is_cancelled() {
+        None
+    } else {
+        Some(result)
     }
 }
 
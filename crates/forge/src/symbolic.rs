@@ -0,0 +1,514 @@
+//! Symbolic execution of tests.
+//!
+//! Tests annotated with `forge-config: <profile>.symbolic = true` are exported as a
+//! [`SymbolicTest`] to a [`SymbolicExecutor`] when running `forge test --symbolic`, instead of
+//! being executed, and the executor's verdict is reported as their result.
+
+use alloy_dyn_abi::DynSolType;
+use alloy_json_abi::Function;
+use alloy_primitives::Bytes;
+use eyre::{Context, Result};
+use foundry_compilers::{
+    artifacts::{ModelCheckerEngine, ModelCheckerSettings, ModelCheckerTarget, Settings},
+    ProjectCompileOutput, Solc,
+};
+use foundry_config::{Config, SolcReq};
+use semver::Version;
+use serde::Serialize;
+use serde_json::Value;
+use solang_parser::pt::{CodeLocation, ContractPart, Expression, Loc, SourceUnitPart, Statement};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+/// A test exported to a [`SymbolicExecutor`].
+#[derive(Clone, Debug, Serialize)]
+pub struct SymbolicTest {
+    /// The identifier of the test contract, i.e. `test/Counter.t.sol:CounterTest`.
+    pub contract: String,
+    /// The test function.
+    pub function: Function,
+    /// The creation bytecode of the test contract.
+    pub bytecode: Bytes,
+    /// The layout of the test function's calldata, after the selector.
+    pub calldata: Vec<CalldataParam>,
+    /// The test function's source and its constraints.
+    pub source: SymbolicSource,
+}
+
+/// A parameter of a test function, as laid out in its calldata.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CalldataParam {
+    /// The name of the parameter.
+    pub name: String,
+    /// The ABI type of the parameter.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The offset of the parameter's head in the calldata, including the selector.
+    pub offset: usize,
+    /// Whether the head holds an offset to the parameter's data instead of the parameter itself.
+    pub dynamic: bool,
+}
+
+/// Returns the calldata layout of the parameters of a function.
+pub fn calldata_layout(function: &Function) -> Vec<CalldataParam> {
+    let mut offset = 4;
+    function
+        .inputs
+        .iter()
+        .map(|input| {
+            let ty = input.selector_type().into_owned();
+            let (words, dynamic) = match DynSolType::parse(&ty) {
+                Ok(sol_type) => (head_words(&sol_type), sol_type.is_dynamic()),
+                Err(_) => (1, false),
+            };
+            let param = CalldataParam { name: input.name.clone(), ty, offset, dynamic };
+            offset += words * 32;
+            param
+        })
+        .collect()
+}
+
+/// Returns the number of words a type takes in the head of an ABI encoding.
+fn head_words(ty: &DynSolType) -> usize {
+    if ty.is_dynamic() {
+        return 1
+    }
+    match ty {
+        DynSolType::FixedArray(inner, len) => len * head_words(inner),
+        DynSolType::Tuple(types) => types.iter().map(head_words).sum(),
+        _ => 1,
+    }
+}
+
+/// The source of a symbolic test.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct SymbolicSource {
+    /// The path of the source file, relative to the project root.
+    pub path: PathBuf,
+    /// The byte range of the test function in the source file.
+    pub span: Range<usize>,
+    /// The constraints the test function places on its inputs and results.
+    pub constraints: Vec<Constraint>,
+}
+
+impl SymbolicSource {
+    /// Reads the source of the test function of the given test contract.
+    ///
+    /// If the source cannot be read or parsed, the returned source has no constraints.
+    pub fn read(root: &Path, contract_id: &str, function: &str) -> Self {
+        let (path, contract) = contract_id.rsplit_once(':').unwrap_or((contract_id, ""));
+        let mut source = Self { path: path.into(), ..Default::default() };
+        if let Ok(content) = std::fs::read_to_string(root.join(path)) {
+            if let Some((span, constraints)) = find_constraints(&content, contract, function) {
+                source.span = span;
+                source.constraints = constraints;
+            }
+        }
+        source
+    }
+}
+
+/// Whether a [`Constraint`] is assumed or asserted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConstraintKind {
+    /// A `vm.assume` precondition on the test's inputs.
+    Assume,
+    /// An `assert` or DSTest assertion that must hold.
+    Assert,
+}
+
+/// A constraint placed by a statement of a test function.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Constraint {
+    /// Whether the constraint is assumed or asserted.
+    pub kind: ConstraintKind,
+    /// The constraint, as a boolean Solidity expression.
+    pub expr: String,
+    /// The byte range of the call that places the constraint in the source file.
+    pub span: Range<usize>,
+}
+
+/// Returns the byte range of the given test function and the constraints placed by the
+/// `vm.assume` calls and assertions in its top-level blocks, in source order.
+pub fn find_constraints(
+    source: &str,
+    contract: &str,
+    function: &str,
+) -> Option<(Range<usize>, Vec<Constraint>)> {
+    let (source_unit, _) = solang_parser::parse(source, 0).ok()?;
+    let definition = source_unit.0.iter().find_map(|part| match part {
+        SourceUnitPart::ContractDefinition(definition)
+            if definition.name.as_ref().is_some_and(|name| name.name == contract) =>
+        {
+            definition.parts.iter().find_map(|part| match part {
+                ContractPart::FunctionDefinition(definition)
+                    if definition.name.as_ref().is_some_and(|name| name.name == function) =>
+                {
+                    Some(definition)
+                }
+                _ => None,
+            })
+        }
+        _ => None,
+    })?;
+
+    let mut constraints = Vec::new();
+    if let Some(body) = &definition.body {
+        collect_constraints(source, body, &mut constraints);
+    }
+    let span = definition.loc.start()..definition.body.as_ref()?.loc().end();
+    Some((span, constraints))
+}
+
+fn collect_constraints(source: &str, statement: &Statement, constraints: &mut Vec<Constraint>) {
+    match statement {
+        Statement::Block { statements, .. } => statements
+            .iter()
+            .for_each(|statement| collect_constraints(source, statement, constraints)),
+        Statement::Expression(_, Expression::FunctionCall(loc, callee, args)) => {
+            let text = |expr: &Expression| source[expr.loc().start()..expr.loc().end()].to_string();
+            let constraint = match (callee.as_ref(), args.as_slice()) {
+                (Expression::MemberAccess(_, _, member), [cond]) if member.name == "assume" => {
+                    Some((ConstraintKind::Assume, text(cond)))
+                }
+                (Expression::Variable(id), [cond, ..]) => match id.name.as_str() {
+                    "assert" | "assertTrue" => Some((ConstraintKind::Assert, text(cond))),
+                    "assertFalse" => Some((ConstraintKind::Assert, format!("!({})", text(cond)))),
+                    name => args.get(1).and_then(|right| {
+                        let operator = match name {
+                            "assertEq" => "==",
+                            "assertNotEq" => "!=",
+                            "assertGt" => ">",
+                            "assertGe" => ">=",
+                            "assertLt" => "<",
+                            "assertLe" => "<=",
+                            _ => return None,
+                        };
+                        let expr = format!("{} {operator} {}", text(cond), text(right));
+                        Some((ConstraintKind::Assert, expr))
+                    }),
+                },
+                _ => None,
+            };
+            if let (Some((kind, expr)), Loc::File(_, start, end)) = (constraint, loc) {
+                constraints.push(Constraint { kind, expr, span: *start..*end });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The verdict of a [`SymbolicExecutor`] on a test.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SymbolicOutcome {
+    /// The test's assertions hold for all inputs satisfying its assumptions.
+    Proven,
+    /// An input violating the test's assertions was found.
+    Counterexample(String),
+    /// The test's assertions could be neither proven nor refuted.
+    Unknown(String),
+}
+
+/// An executor that proves or refutes tests symbolically.
+pub trait SymbolicExecutor: fmt::Debug + Send + Sync {
+    /// Returns the name of the executor, used in test results.
+    fn name(&self) -> &str;
+
+    /// Proves or refutes the assertions of a test.
+    fn execute(&self, test: &SymbolicTest) -> Result<SymbolicOutcome>;
+}
+
+/// A [`SymbolicExecutor`] backed by the solc SMTChecker, using its CHC engine.
+///
+/// The test's assumptions and assertions are rewritten to `require` and `assert` statements, which
+/// the SMTChecker understands, and the test contract is checked with the source it was compiled
+/// from. The state set up by `setUp` is not known to the SMTChecker.
+///
+/// A test is only proven if the SMTChecker reports all of its assertions safe, which requires
+/// solc 0.8.22 or newer.
+#[derive(Clone, Debug)]
+pub struct SmtChecker {
+    /// The project root, which test sources are relative to.
+    root: PathBuf,
+    /// The settings to check the sources with.
+    settings: Settings,
+    /// The solc binary to use for all sources, if configured.
+    solc: Option<PathBuf>,
+    /// The solc version each source was compiled with.
+    versions: HashMap<PathBuf, Version>,
+}
+
+impl SmtChecker {
+    /// Creates a new SMTChecker executor for the sources in the given compile output.
+    pub fn new(config: &Config, output: &ProjectCompileOutput) -> Result<Self> {
+        let root = config.__root.0.clone();
+        let mut settings = config.solc_settings()?;
+        settings.remappings = config.get_all_remappings();
+        settings.output_selection = Default::default();
+
+        let solc = match &config.solc {
+            Some(SolcReq::Local(solc)) => Some(solc.clone()),
+            _ => None,
+        };
+        let versions = output
+            .artifact_ids()
+            .map(|(id, _)| {
+                let path = id.source.strip_prefix(&root).unwrap_or(&id.source).to_path_buf();
+                (path, id.version)
+            })
+            .collect();
+
+        Ok(Self { root, settings, solc, versions })
+    }
+
+    /// Returns the solc binary to check the given source with.
+    fn solc(&self, path: &Path) -> Result<PathBuf> {
+        if let Some(solc) = &self.solc {
+            return Ok(solc.clone())
+        }
+        let version = self
+            .versions
+            .get(path)
+            .ok_or_else(|| eyre::eyre!("no compiler version found for {}", path.display()))?;
+        Solc::find_svm_installed_version(version.to_string())?
+            .map(|solc| solc.solc)
+            .ok_or_else(|| eyre::eyre!("solc {version} is not installed"))
+    }
+}
+
+impl SymbolicExecutor for SmtChecker {
+    fn name(&self) -> &str {
+        "SMTChecker"
+    }
+
+    fn execute(&self, test: &SymbolicTest) -> Result<SymbolicOutcome> {
+        let path = &test.source.path;
+        let contract = test.contract.rsplit(':').next().unwrap_or(&test.contract);
+        let source = std::fs::read_to_string(self.root.join(path))
+            .wrap_err_with(|| format!("failed to read {}", path.display()))?;
+
+        // Rewrite the constraints into statements the SMTChecker understands, back to front so
+        // the spans of the preceding constraints stay valid
+        let mut rewritten = source.clone();
+        for constraint in test.source.constraints.iter().rev() {
+            let statement = match constraint.kind {
+                ConstraintKind::Assume => format!("require({})", constraint.expr),
+                ConstraintKind::Assert => format!("assert({})", constraint.expr),
+            };
+            rewritten.replace_range(constraint.span.clone(), &statement);
+        }
+        // All constraints are inside the test function, so only its end moves
+        let span = test.source.span.start..
+            (test.source.span.end + rewritten.len()).saturating_sub(source.len());
+
+        let mut settings = self.settings.clone();
+        let model_checker = settings.model_checker.take().unwrap_or_default();
+        settings.model_checker = Some(ModelCheckerSettings {
+            contracts: BTreeMap::from([(path.display().to_string(), vec![contract.to_string()])]),
+            engine: Some(ModelCheckerEngine::CHC),
+            targets: Some(vec![ModelCheckerTarget::Assert]),
+            show_unproved: Some(true),
+            show_unsupported: Some(true),
+            show_proved_safe: Some(true),
+            ..model_checker
+        });
+        let input = serde_json::json!({
+            "language": "Solidity",
+            "sources": { path.display().to_string(): { "content": rewritten } },
+            "settings": settings,
+        });
+
+        let mut child = Command::new(self.solc(path)?)
+            .arg("--standard-json")
+            .arg("--base-path")
+            .arg(&self.root)
+            .arg("--allow-paths")
+            .arg(&self.root)
+            .current_dir(&self.root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .wrap_err("failed to run solc")?;
+        child.stdin.take().expect("stdin is piped").write_all(input.to_string().as_bytes())?;
+        let output = child.wait_with_output()?;
+        let output: Value = serde_json::from_slice(&output.stdout)
+            .wrap_err_with(|| String::from_utf8_lossy(&output.stderr).into_owned())?;
+
+        let asserts = test
+            .source
+            .constraints
+            .iter()
+            .filter(|constraint| constraint.kind == ConstraintKind::Assert)
+            .count();
+        checker_outcome(&output, path, &span, asserts)
+    }
+}
+
+/// Returns the outcome of the SMTChecker's output for the test of the given source file and span,
+/// which has `asserts` assertions.
+///
+/// The test is only proven if all of its assertions were reported safe, and no message of the
+/// checker affects it, such as a solver timeout or an unsupported feature.
+fn checker_outcome(
+    output: &Value,
+    path: &Path,
+    span: &Range<usize>,
+    asserts: usize,
+) -> Result<SymbolicOutcome> {
+    let mut proved = 0;
+    let mut unknown = None;
+    for error in output["errors"].as_array().into_iter().flatten() {
+        let message = error["message"].as_str().unwrap_or_default();
+        if error["severity"] == "error" {
+            eyre::bail!("{}", error["formattedMessage"].as_str().unwrap_or(message))
+        }
+        let (summary, details) = message.split_once('\n').unwrap_or((message, ""));
+        let lowercase = summary.to_lowercase();
+        let from_checker = summary.starts_with("CHC") ||
+            lowercase.contains("checker") ||
+            lowercase.contains("solver");
+
+        // Messages without a location, such as a missing solver, apply to all tests, while the
+        // other ones are only reported for this test
+        let location = &error["sourceLocation"];
+        if location.is_null() {
+            if from_checker {
+                unknown.get_or_insert_with(|| summary.to_string());
+            }
+            continue
+        }
+        let in_test = location["file"] == path.display().to_string() &&
+            location["start"].as_u64().is_some_and(|start| span.contains(&(start as usize)));
+        if !in_test {
+            continue
+        }
+
+        if summary.starts_with("CHC: Assertion violation happens here") {
+            return Ok(SymbolicOutcome::Counterexample(details.trim().to_string()))
+        }
+        if summary.starts_with("CHC: Assertion violation check is safe") {
+            proved += 1;
+        } else if from_checker {
+            unknown.get_or_insert_with(|| summary.to_string());
+        }
+    }
+
+    if let Some(unknown) = unknown {
+        return Ok(SymbolicOutcome::Unknown(unknown))
+    }
+    if asserts == 0 {
+        return Ok(SymbolicOutcome::Unknown("the test has no assertions to prove".to_string()))
+    }
+    if proved < asserts {
+        return Ok(SymbolicOutcome::Unknown(format!(
+            "only {proved} of the {asserts} assertions were proved"
+        )))
+    }
+    Ok(SymbolicOutcome::Proven)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_constraints() {
+        let source = r#"
+contract CounterTest {
+    function test_Increment(uint256 x) public {
+        vm.assume(x < 100);
+        counter.set(x);
+        assertEq(counter.number(), x);
+        assert(x != 100);
+    }
+}
+"#;
+        let (span, constraints) =
+            find_constraints(source, "CounterTest", "test_Increment").unwrap();
+        assert!(source[span].starts_with("function test_Increment"));
+        let constraints: Vec<_> = constraints
+            .iter()
+            .map(|c| (c.kind, c.expr.as_str(), &source[c.span.clone()]))
+            .collect();
+        assert_eq!(
+            constraints,
+            [
+                (ConstraintKind::Assume, "x < 100", "vm.assume(x < 100)"),
+                (ConstraintKind::Assert, "counter.number() == x", "assertEq(counter.number(), x)"),
+                (ConstraintKind::Assert, "x != 100", "assert(x != 100)"),
+            ]
+        );
+        assert_eq!(find_constraints(source, "CounterTest", "test_Decrement"), None);
+    }
+
+    #[test]
+    fn lays_out_calldata() {
+        let function =
+            Function::parse("test(uint256 a, bytes b, (uint8,bool) c, uint16[2] d)").unwrap();
+        let layout: Vec<_> =
+            calldata_layout(&function).into_iter().map(|p| (p.offset, p.dynamic)).collect();
+        assert_eq!(layout, [(4, false), (36, true), (68, false), (132, false)]);
+    }
+
+    #[test]
+    fn interprets_checker_output() {
+        let path = Path::new("test/Counter.t.sol");
+        let span = 100..200;
+        let message = |severity: &str, message: &str, file: &str, start: u64| {
+            serde_json::json!({
+                "severity": severity,
+                "message": message,
+                "sourceLocation": { "file": file, "start": start, "end": start + 10 },
+            })
+        };
+        let safe = "CHC: Assertion violation check is safe!";
+        let outcome = |errors: Vec<Value>, asserts| {
+            checker_outcome(&serde_json::json!({ "errors": errors }), path, &span, asserts).unwrap()
+        };
+
+        let proven = vec![
+            message("info", safe, "test/Counter.t.sol", 120),
+            message("info", safe, "test/Counter.t.sol", 150),
+            // the messages of other files and tests don't affect the test
+            message("warning", "CHC: Assertion violation might happen here.", "lib/Test.sol", 120),
+            message("warning", "CHC: Assertion violation happens here.", "test/Counter.t.sol", 20),
+        ];
+        assert_eq!(outcome(proven.clone(), 2), SymbolicOutcome::Proven);
+        // nothing is proven if not all assertions were checked, or if there are none
+        assert!(matches!(outcome(proven[..1].to_vec(), 2), SymbolicOutcome::Unknown(_)));
+        assert!(matches!(outcome(vec![], 0), SymbolicOutcome::Unknown(_)));
+
+        let violation = message(
+            "warning",
+            "CHC: Assertion violation happens here.\nCounterexample:\nx = 100",
+            "test/Counter.t.sol",
+            130,
+        );
+        assert_eq!(
+            outcome(vec![violation], 1),
+            SymbolicOutcome::Counterexample("Counterexample:\nx = 100".to_string())
+        );
+
+        let timeout = serde_json::json!({
+            "severity": "warning",
+            "message": "CHC: 1 verification condition(s) could not be proved. Enable the model checker option \"show unproved\" to see all of them.",
+        });
+        let unsupported = message(
+            "warning",
+            "Assertion checker does not yet support this expression.",
+            "test/Counter.t.sol",
+            140,
+        );
+        for errors in [vec![timeout], vec![unsupported]] {
+            assert!(matches!(outcome(errors, 0), SymbolicOutcome::Unknown(_)));
+        }
+    }
+}
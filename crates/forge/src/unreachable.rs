@@ -0,0 +1,219 @@
+//! Detection of code the compiler didn't generate any instructions for.
+
+use alloy_json_abi::JsonAbi;
+use eyre::Result;
+use foundry_compilers::{
+    artifacts::ast::{Ast, LowFidelitySourceLocation, Node, NodeType},
+    sourcemap::SourceElement,
+};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A source file of the compilation the contract was compiled in.
+#[derive(Clone, Debug)]
+pub struct Source {
+    /// The path of the source, as it is displayed
+    pub path: String,
+    pub ast: Ast,
+    /// The source code, used to find the lines of the unreachable code
+    pub code: String,
+}
+
+/// The code of a contract which is unreachable from any of its entry points, since it isn't
+/// part of its bytecode.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct UnreachableCode {
+    /// The internal and private functions that are never called
+    pub functions: Vec<UnreachableItem>,
+    /// The statements of the called functions that can never be executed
+    pub statements: Vec<UnreachableItem>,
+    /// The signatures of the custom errors that are never raised
+    pub errors: Vec<String>,
+}
+
+/// A function or statement which is unreachable.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct UnreachableItem {
+    /// The contract the code is defined in
+    pub contract: String,
+    /// The name of the function, or the kind of the statement
+    pub name: String,
+    pub path: String,
+    pub line: usize,
+}
+
+impl UnreachableCode {
+    /// Finds the unreachable code of the contract.
+    ///
+    /// `sources` are the sources of the compilation, by source ID. `source_maps` and `bytecodes`
+    /// are the source maps and the bytecodes of the contract's init and runtime code. Code of the
+    /// contract and its base contracts which none of the source maps' instructions is mapped to is
+    /// unreachable, as is a custom error whose selector doesn't appear in any of the bytecodes.
+    pub fn find(
+        contract_name: &str,
+        contract_path: &str,
+        sources: &BTreeMap<usize, Source>,
+        source_maps: &[&[SourceElement]],
+        bytecodes: &[&[u8]],
+        abi: &JsonAbi,
+    ) -> Result<Self> {
+        let mut contracts = HashMap::new();
+        for (source_id, source) in sources {
+            for node in &source.ast.nodes {
+                if node.node_type == NodeType::ContractDefinition {
+                    if let Some(id) = node.id {
+                        contracts.insert(id, (*source_id, node));
+                    }
+                }
+            }
+        }
+        let (_, contract) = contracts
+            .values()
+            .find(|(source_id, node)| {
+                sources.get(source_id).map_or(false, |source| source.path == contract_path) &&
+                    node.attribute::<String>("name").as_deref() == Some(contract_name)
+            })
+            .ok_or_else(|| eyre::eyre!("Could not find the AST of `{contract_name}`"))?;
+        let linearized_base_contracts: Vec<usize> = contract
+            .attribute("linearizedBaseContracts")
+            .ok_or_else(|| {
+            eyre::eyre!("The contract's AST node is missing a list of linearized base contracts")
+        })?;
+
+        let mut visitor = Visitor {
+            sources,
+            covered: covered_ranges(source_maps),
+            code: Self::default(),
+            contract: String::new(),
+            source_id: 0,
+        };
+        for id in linearized_base_contracts {
+            let Some((source_id, node)) = contracts.get(&id) else { continue };
+            visitor.contract = node.attribute("name").unwrap_or_default();
+            visitor.source_id = *source_id;
+            visitor.visit_contract(node);
+        }
+
+        let mut code = visitor.code;
+        for error in abi.errors.values().flatten() {
+            let selector = error.selector();
+            if !bytecodes
+                .iter()
+                .any(|code| code.windows(4).any(|window| window == selector.as_slice()))
+            {
+                code.errors.push(error.signature());
+            }
+        }
+        Ok(code)
+    }
+
+    /// Returns `true` if no unreachable code was found.
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty() && self.statements.is_empty() && self.errors.is_empty()
+    }
+}
+
+/// Returns the source ranges the instructions are mapped to, by source ID.
+fn covered_ranges(source_maps: &[&[SourceElement]]) -> HashMap<usize, Vec<(usize, usize)>> {
+    let mut covered: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    for element in source_maps.iter().flat_map(|source_map| source_map.iter()) {
+        if let Some(index) = element.index {
+            covered
+                .entry(index as usize)
+                .or_default()
+                .push((element.offset, element.offset + element.length));
+        }
+    }
+    covered
+}
+
+struct Visitor<'a> {
+    sources: &'a BTreeMap<usize, Source>,
+    covered: HashMap<usize, Vec<(usize, usize)>>,
+    code: UnreachableCode,
+    /// The name of the contract being walked
+    contract: String,
+    /// The source ID of the contract being walked
+    source_id: usize,
+}
+
+impl Visitor<'_> {
+    fn visit_contract(&mut self, contract: &Node) {
+        for node in &contract.nodes {
+            if node.node_type != NodeType::FunctionDefinition {
+                continue
+            }
+            let Some(body) = &node.body else { continue };
+            let kind: String = node.attribute("kind").unwrap_or_default();
+            let visibility: String = node.attribute("visibility").unwrap_or_default();
+            let name: String = node.attribute("name").unwrap_or_default();
+
+            // external functions are always reachable through their selector
+            let internal =
+                kind == "function" && matches!(visibility.as_str(), "internal" | "private");
+            if internal && !self.is_covered(&node.src) {
+                let item = self.item(name, &node.src);
+                self.code.functions.push(item);
+                continue
+            }
+            self.visit_block(body);
+        }
+    }
+
+    fn visit_block(&mut self, block: &Node) {
+        let statements: Vec<Node> = block.attribute("statements").unwrap_or_default();
+        for statement in &statements {
+            self.visit_statement(statement);
+        }
+    }
+
+    fn visit_statement(&mut self, statement: &Node) {
+        let generates_code = match statement.node_type {
+            NodeType::ExpressionStatement |
+            NodeType::Return |
+            NodeType::EmitStatement |
+            NodeType::RevertStatement |
+            NodeType::IfStatement |
+            NodeType::ForStatement |
+            NodeType::WhileStatement |
+            NodeType::DoWhileStatement => true,
+            NodeType::VariableDeclarationStatement => {
+                statement.attribute::<Node>("initialValue").is_some()
+            }
+            NodeType::Block | NodeType::UncheckedBlock => return self.visit_block(statement),
+            _ => false,
+        };
+        if generates_code && !self.is_covered(&statement.src) {
+            let item = self.item(format!("{:?}", statement.node_type), &statement.src);
+            self.code.statements.push(item);
+            return
+        }
+
+        if let Some(body) = &statement.body {
+            self.visit_statement(body);
+        }
+        for branch in ["trueBody", "falseBody"] {
+            if let Some(body) = statement.attribute::<Node>(branch) {
+                self.visit_statement(&body);
+            }
+        }
+    }
+
+    /// Returns `true` if any instruction is mapped to a range within `src`.
+    fn is_covered(&self, src: &LowFidelitySourceLocation) -> bool {
+        let source_id = src.index.unwrap_or(self.source_id);
+        let (start, end) = (src.start, src.start + src.length.unwrap_or_default());
+        self.covered.get(&source_id).map_or(false, |ranges| {
+            ranges.iter().any(|&(range_start, range_end)| start <= range_start && range_end <= end)
+        })
+    }
+
+    fn item(&self, name: String, src: &LowFidelitySourceLocation) -> UnreachableItem {
+        let source = self.sources.get(&src.index.unwrap_or(self.source_id));
+        let path = source.map(|source| source.path.clone()).unwrap_or_default();
+        let line = source
+            .and_then(|source| source.code.get(..src.start))
+            .map_or(0, |code| code.matches('\n').count() + 1);
+        UnreachableItem { contract: self.contract.clone(), name, path, line }
+    }
+}
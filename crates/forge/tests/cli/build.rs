@@ -45,3 +45,73 @@ Compiler run successful!
         assert_eq!(expected, output, "expected: {}, output: {}", expected, output);
     }
 });
+
+// tests that missing NatSpec documentation is reported when building, as configured
+forgetest!(checks_natspec_docs, |prj, cmd| {
+    prj.add_source(
+        "Vault.sol",
+        r"
+contract Vault {
+    /// @notice Emitted when tokens are deposited.
+    /// @param account The depositor
+    event Deposit(address indexed account, uint256 amount);
+
+    /// @notice Deposits the sent ether.
+    function deposit() external payable {}
+
+    function withdraw(uint256 amount) external {}
+
+    function _burn(uint256 amount) internal {}
+}
+",
+    )
+    .unwrap();
+    prj.add_source("mocks/MockVault.sol", "contract MockVault { function mint() external {} }")
+        .unwrap();
+
+    let mut config = cmd.config();
+    config.doc_lint.level = foundry_config::DocLintLevel::Deny;
+    config.doc_lint.overrides = vec![foundry_config::DocLintOverride {
+        path: "src/mocks".into(),
+        level: foundry_config::DocLintLevel::Off,
+    }];
+    prj.write_config(config);
+
+    cmd.arg("build");
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("`event Deposit` is missing @param amount"), "{stderr}");
+    assert!(stderr.contains("`function withdraw` is missing @notice, @param amount"), "{stderr}");
+    assert!(stderr.contains("2 items are missing NatSpec documentation"), "{stderr}");
+    assert!(!stderr.contains("_burn") && !stderr.contains("mint"), "{stderr}");
+});
+
+// tests that artifacts can be attested and verified against their attestations
+forgetest!(can_attest_and_verify_artifacts, |prj, cmd| {
+    prj.add_source("Counter.sol", "contract Counter { uint256 public number; }").unwrap();
+
+    cmd.args([
+        "build",
+        "--attest",
+        "--attest-private-key",
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    ]);
+    assert!(cmd.stdout_lossy().contains("Wrote 1 attestations to attestations"));
+    let path = prj.root().join("attestations/Counter.sol/Counter.json");
+    assert!(path.exists());
+
+    cmd.forge_fuse().args([
+        "verify-attestation",
+        "src/Counter.sol:Counter",
+        "--signer",
+        "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    ]);
+    assert!(cmd.stdout_lossy().contains("matches its attestation signed by"));
+
+    // a tampered attestation is rejected
+    let mut attestation: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    attestation["settings"]["viaIr"] = true.into();
+    std::fs::write(&path, attestation.to_string()).unwrap();
+    let stderr = cmd.stderr_lossy();
+    assert!(stderr.contains("doesn't match its attestation: settings, signature"), "{stderr}");
+});
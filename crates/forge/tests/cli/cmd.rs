@@ -181,6 +181,31 @@ forgetest!(
     }
 );
 
+// checks that `cache fork prune` keeps pinned blocks
+// this test is not isolated and modifies ~ so it is ignored
+forgetest!(
+    #[ignore]
+    can_cache_fork_prune_pinned,
+    |_prj, cmd| {
+        let chain = Chain::mainnet();
+        for block in [100, 101] {
+            fs::create_dir_all(Config::foundry_block_cache_dir(chain, block).unwrap()).unwrap();
+            fs::write(Config::foundry_block_cache_file(chain, block).unwrap(), "{}").unwrap();
+        }
+
+        cmd.args(["cache", "fork", "pin", "mainnet", "100"]);
+        cmd.assert_empty_stdout();
+
+        cmd.forge_fuse().args(["cache", "fork", "prune", "mainnet"]);
+        cmd.assert_non_empty_stdout();
+
+        assert!(Config::foundry_block_cache_file(chain, 100).unwrap().exists());
+        assert!(!Config::foundry_block_cache_dir(chain, 101).unwrap().exists());
+
+        Config::clean_foundry_cache().unwrap();
+    }
+);
+
 // checks that `cache clean <chain> --etherscan` can be invoked and cleans the etherscan chain cache
 // this test is not isolated and modifies ~ so it is ignored
 forgetest!(
@@ -1287,6 +1312,53 @@ contract ContractThreeTest is DSTest {
     assert!(!third_out.contains("foo") && !third_out.contains("bar") && third_out.contains("baz"));
 });
 
+forgetest!(gas_report_by_test, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Contracts.sol",
+        r#"
+//SPDX-license-identifier: MIT
+
+import "./test.sol";
+
+contract Token {
+    mapping(address => uint256) public balanceOf;
+
+    function mint(address to, uint256 amount) public {
+        balanceOf[to] += amount;
+    }
+}
+
+contract Vault {
+    Token public token = new Token();
+
+    function deposit(uint256 amount) public {
+        token.mint(msg.sender, amount);
+    }
+}
+
+contract VaultTest is DSTest {
+    Vault vault;
+
+    function setUp() public {
+        vault = new Vault();
+    }
+
+    function testDeposit() public {
+        vault.deposit(1);
+        vault.deposit(2);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.forge_fuse();
+    let out = cmd.arg("test").arg("--gas-report-by-test").stdout_lossy();
+    assert!(out.contains("VaultTest::testDeposit() test"));
+    assert!(out.contains("Vault::deposit > Token::mint"));
+});
+
 forgetest!(gas_ignore_some_contracts, |prj, cmd| {
     prj.insert_ds_test();
     prj.add_source(
@@ -1510,10 +1582,99 @@ forgetest_init!(can_inspect_ir_optimized, |_prj, cmd| {
     cmd.assert_success();
 });
 
+// checks `forge inspect <contract> standard-json` only includes the sources the contract imports
+forgetest_init!(can_inspect_standard_json, |_prj, cmd| {
+    cmd.args(["inspect", TEMPLATE_CONTRACT, "standard-json"]);
+    let output = cmd.stdout_lossy();
+    let input: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(input["language"], "Solidity");
+    let sources = input["sources"].as_object().unwrap();
+    assert!(sources.keys().any(|source| source.ends_with("Counter.sol")), "{output}");
+    assert!(!sources.keys().any(|source| source.ends_with("Test.sol")), "{output}");
+});
+
+// checks `forge inspect <contract> unreachable` finds dead internal functions and unused errors
+forgetest!(can_inspect_unreachable, |prj, cmd| {
+    prj.add_source(
+        "Dead.sol",
+        r#"
+contract Dead {
+    error Used();
+    error Unused(uint256 value);
+
+    uint256 public number;
+
+    function set(uint256 value) external {
+        if (value == 0) revert Used();
+        number = _double(value);
+    }
+
+    function _double(uint256 value) internal pure returns (uint256) {
+        return value * 2;
+    }
+
+    function _unused(uint256 value) internal pure returns (uint256) {
+        return value + 1;
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["inspect", "Dead", "unreachable"]);
+    let output = cmd.stdout_lossy();
+    let unreachable: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let functions = unreachable["functions"].as_array().unwrap();
+    assert_eq!(functions.len(), 1, "{output}");
+    assert_eq!(functions[0]["name"], "_unused");
+    assert_eq!(unreachable["errors"], serde_json::json!(["Unused(uint256)"]));
+});
+
 // checks forge bind works correctly on the default project
-forgetest_init!(can_bind, |_prj, cmd| {
+forgetest_init!(can_bind, |prj, cmd| {
+    prj.add_source(
+        "Token.sol",
+        r#"
+contract Token {
+    event Transfer(address indexed from, address indexed to, uint256 amount);
+    error InsufficientBalance(uint256 balance);
+
+    mapping(address => uint256) public balanceOf;
+
+    function transfer(address to, uint256 amount) public {
+        if (balanceOf[msg.sender] < amount) revert InsufficientBalance(balanceOf[msg.sender]);
+        balanceOf[msg.sender] -= amount;
+        balanceOf[to] += amount;
+        emit Transfer(msg.sender, to, amount);
+    }
+}
+   "#,
+    )
+    .unwrap();
+
     cmd.arg("bind");
     cmd.assert_non_empty_stdout();
+
+    let token = fs::read_to_string(prj.root().join("out/bindings/src/token.rs")).unwrap();
+    assert!(token.contains("pub struct TransferFilter"), "{token}");
+    assert!(token.contains("pub fn transfer_filter("), "{token}");
+    assert!(token.contains("pub enum TokenErrors"), "{token}");
+    assert!(token.contains("impl ::core::fmt::Display for TokenErrors"), "{token}");
+    assert!(token.contains("pub struct TokenMulticall<M>"), "{token}");
+    assert!(token.contains("pub fn add_balance_of("), "{token}");
+    assert!(token.contains("pub fn add_transfer("), "{token}");
+});
+
+// checks forge bind generates typescript bindings on the default project
+forgetest_init!(can_bind_typescript, |prj, cmd| {
+    cmd.args(["bind", "--language", "typescript"]);
+    cmd.assert_non_empty_stdout();
+
+    let counter = fs::read_to_string(prj.root().join("out/bindings/Counter.ts")).unwrap();
+    assert!(counter.contains("export const counterAbi = ["), "{counter}");
+    assert!(counter.contains("export function deployCounter<"), "{counter}");
+    let index = fs::read_to_string(prj.root().join("out/bindings/index.ts")).unwrap();
+    assert!(index.contains("export * from \"./Counter\";"), "{index}");
 });
 
 // checks missing dependencies are auto installed
@@ -1596,6 +1757,43 @@ forgetest_init!(can_build_sizes_repeatedly, |prj, cmd| {
     assert!(unchanged.contains(table), "{}", table);
 });
 
+// checks forge size breaks down the sizes by source and function and fails when a contract grew
+// past the baseline
+forgetest_init!(can_check_sizes_against_baseline, |prj, cmd| {
+    cmd.args(["size", "Counter", "--save-baseline"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("src/Counter.sol"), "{stdout}");
+    assert!(stdout.contains("Counter.increment"), "{stdout}");
+    assert!(stdout.contains("<metadata>"), "{stdout}");
+    assert!(prj.root().join(".size-baseline.json").exists());
+
+    cmd.forge_fuse().args(["size", "--fail-on-growth", "0"]);
+    cmd.assert_success();
+
+    prj.add_source(
+        "Counter.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+    uint256 public other;
+
+    function setNumber(uint256 newNumber) public {
+        number = newNumber;
+        other = newNumber * 2;
+    }
+
+    function increment() public {
+        number++;
+        other++;
+    }
+}
+"#,
+    )
+    .unwrap();
+    cmd.forge_fuse().args(["size", "--fail-on-growth", "0"]);
+    cmd.assert_err();
+});
+
 // checks that build --names includes all contracts even if unchanged
 forgetest_init!(can_build_names_repeatedly, |prj, cmd| {
     prj.clear_cache();
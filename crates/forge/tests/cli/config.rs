@@ -30,6 +30,7 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         cache: true,
         cache_path: "test-cache".into(),
         broadcast: "broadcast".into(),
+        deployments: "deployments".into(),
         force: true,
         evm_version: EvmVersion::Byzantium,
         gas_reports: vec!["Contract".to_string()],
@@ -66,6 +67,7 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         ffi: true,
         sender: "00a329c0648769A73afAc7F9381D08FB43dBEA72".parse().unwrap(),
         tx_origin: "00a329c0648769A73afAc7F9F81E08FB43dBEA72".parse().unwrap(),
+        create2_deployer: Config::DEFAULT_CREATE2_DEPLOYER,
         initial_balance: U256::from(0xffffffffffffffffffffffffu128),
         block_number: 10,
         fork_block_number: Some(200),
@@ -80,10 +82,13 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         block_prevrandao: B256::random(),
         block_gas_limit: Some(100u64.into()),
         memory_limit: 1 << 27,
+        precompiles: vec![],
+        gas_overrides: Default::default(),
         eth_rpc_url: Some("localhost".to_string()),
         eth_rpc_jwt: None,
         etherscan_api_key: None,
         etherscan: Default::default(),
+        verifier_fallbacks: Default::default(),
         verbosity: 4,
         remappings: vec![Remapping::from_str("forge-std=lib/forge-std/").unwrap().into()],
         libraries: vec![
@@ -97,6 +102,8 @@ forgetest!(can_extract_config_values, |prj, cmd| {
             endpoints: CachedEndpoints::Remote,
         },
         no_storage_caching: true,
+        fork_prefetch_storage: false,
+        fork_cache_max_size: None,
         no_rpc_rate_limit: true,
         use_literal_content: false,
         bytecode_hash: Default::default(),
@@ -110,7 +117,13 @@ forgetest!(can_extract_config_values, |prj, cmd| {
         build_info_path: None,
         fmt: Default::default(),
         doc: Default::default(),
+        coverage: Default::default(),
+        lint: Default::default(),
+        doc_lint: Default::default(),
+        remote_cache: Default::default(),
         fs_permissions: Default::default(),
+        http_permissions: Default::default(),
+        secrets: Default::default(),
         labels: Default::default(),
         cancun: true,
         __non_exhaustive: (),
@@ -1,3 +1,4 @@
+use foundry_config::{Config, CoverageConfig};
 use regex::Regex;
 
 forgetest!(basic_coverage, |_prj, cmd| {
@@ -15,6 +16,189 @@ forgetest!(report_file_coverage, |prj, cmd| {
     cmd.assert_success();
 });
 
+forgetest!(cobertura_and_html_coverage, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    int public i;
+
+    function foo(bool b) public {
+        if (b) {
+            i = 1;
+        }
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+
+contract AContractTest is DSTest {
+    function testFoo() public {
+        new AContract().foo(true);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.arg("coverage").args(["--report", "cobertura", "--report", "html"]);
+    cmd.assert_success();
+
+    let cobertura = std::fs::read_to_string(prj.root().join("cobertura.xml")).unwrap();
+    assert!(cobertura.contains(r#"filename="src/AContract.sol""#));
+    assert!(cobertura.contains(r#"<method name="AContract.foo""#));
+
+    let html = std::fs::read_to_string(prj.root().join("coverage/src_AContract.sol.html")).unwrap();
+    assert!(html.contains("            i = 1;"));
+    assert!(prj.root().join("coverage/index.html").exists());
+});
+
+// Both arms of if/else statements and ternaries, and both paths of `require`, are covered, with
+// and without the IR pipeline
+forgetest!(branch_coverage, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Branches.sol",
+        r#"
+contract Branches {
+    uint256 public x;
+
+    function pick(bool b) public {
+        if (b) {
+            x = 1;
+        } else {
+            x = 2;
+        }
+    }
+
+    function choose(bool b) public pure returns (uint256) {
+        return b ? 1 : 2;
+    }
+
+    function check(uint256 a) public {
+        require(a > 1, "too small");
+        x = a;
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    prj.add_source(
+        "BranchesTest.sol",
+        r#"
+import "./test.sol";
+import {Branches} from "./Branches.sol";
+
+contract BranchesTest is DSTest {
+    function testBranches() public {
+        Branches branches = new Branches();
+        branches.pick(true);
+        assertEq(branches.choose(false), 2);
+        branches.check(2);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    let lcov_info = prj.root().join("lcov.info");
+    for args in [&[][..], &["--ir-minimum"][..]] {
+        cmd.forge_fuse().arg("coverage").args(["--report", "lcov"]).args(args);
+        cmd.assert_success();
+
+        let lcov_data = std::fs::read_to_string(&lcov_info).unwrap();
+        let branches = lcov_data
+            .lines()
+            .skip_while(|line| !line.ends_with("Branches.sol"))
+            .take_while(|line| *line != "end_of_record")
+            .filter_map(|line| line.strip_prefix("BRDA:"))
+            .map(|line| line.split_once(',').unwrap().1)
+            .collect::<Vec<_>>();
+        assert_eq!(branches, ["0,0,1", "0,1,-", "1,0,-", "1,1,1", "2,0,-", "2,1,1"], "{args:?}");
+    }
+});
+
+// Sources and lines can be excluded from coverage with config globs and comments
+forgetest!(coverage_exclusions, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "AContract.sol",
+        r#"
+contract AContract {
+    uint256 public i;
+
+    function foo() public {
+        i = 1;
+        // forge-coverage: ignore-next-line
+        if (i == 0) revert();
+        // forge-coverage: ignore-start
+        if (i == 2) {
+            i = 3;
+        }
+        // forge-coverage: ignore-end
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "vendored/Vendored.sol",
+        r#"
+contract Vendored {
+    function bar() public pure returns (uint256) {
+        return 1;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "AContractTest.sol",
+        r#"
+import "./test.sol";
+import {AContract} from "./AContract.sol";
+import {Vendored} from "./vendored/Vendored.sol";
+
+contract AContractTest is DSTest {
+    function testFoo() public {
+        new AContract().foo();
+        new Vendored();
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    let config = Config {
+        coverage: CoverageConfig { exclude: vec!["src/vendored/**".to_string()] },
+        ..Default::default()
+    };
+    prj.write_config(config);
+
+    cmd.arg("coverage").args(["--report", "lcov"]);
+    cmd.assert_success();
+
+    let lcov_data = std::fs::read_to_string(prj.root().join("lcov.info")).unwrap();
+    assert!(!lcov_data.contains("Vendored.sol"));
+    let a_contract = lcov_data
+        .lines()
+        .skip_while(|line| !line.ends_with("AContract.sol"))
+        .take_while(|line| *line != "end_of_record")
+        .collect::<Vec<_>>();
+    assert!(a_contract.contains(&"LF:1"), "{a_contract:?}");
+    assert!(a_contract.contains(&"LH:1"), "{a_contract:?}");
+    assert!(a_contract.contains(&"BRF:0"), "{a_contract:?}");
+});
+
 forgetest!(test_setup_coverage, |prj, cmd| {
     prj.insert_ds_test();
     prj.add_source(
@@ -1,4 +1,7 @@
-use foundry_test_utils::util::{setup_forge_remote, RemoteProject};
+use foundry_test_utils::{
+    forgetest,
+    util::{setup_forge_remote, RemoteProject},
+};
 
 #[test]
 fn can_generate_solmate_docs() {
@@ -6,3 +9,42 @@ fn can_generate_solmate_docs() {
         setup_forge_remote(RemoteProject::new("transmissions11/solmate").set_build(false));
     prj.forge_command().args(["doc", "--build"]).assert_success();
 }
+
+forgetest!(can_generate_versioned_docs_with_artifacts, |prj, cmd| {
+    prj.add_source(
+        "Vault.sol",
+        r"
+/// @notice A vault.
+contract Vault {
+    address public owner;
+    mapping(address => uint256) public balances;
+
+    error Unauthorized();
+
+    function deposit() external payable {}
+}
+",
+    )
+    .unwrap();
+    let theme = prj.root().join("theme");
+    std::fs::create_dir_all(&theme).unwrap();
+    std::fs::write(theme.join("index.hbs"), "{{ content }}").unwrap();
+
+    let mut config = cmd.config();
+    config.doc.theme = Some("theme".into());
+    prj.write_config(config);
+
+    cmd.args(["doc", "--doc-version", "1.2.0", "--artifacts"]).assert_success();
+
+    let out = prj.root().join("docs/v1.2.0");
+    let doc = std::fs::read_to_string(out.join("src/src/Vault.sol/contract.Vault.md")).unwrap();
+    assert!(doc.contains("## Selectors"), "{doc}");
+    assert!(doc.contains("|function|`deposit()`|`0xd0e30db0`|"), "{doc}");
+    assert!(doc.contains("|error|`Unauthorized()`|`0x82b42900`|"), "{doc}");
+    assert!(doc.contains("## Storage Layout"), "{doc}");
+    assert!(doc.contains("|`balances`|`mapping(address => uint256)`|1|0|32|"), "{doc}");
+
+    assert!(out.join("theme/index.hbs").exists());
+    let book = std::fs::read_to_string(out.join("book.toml")).unwrap();
+    assert!(book.contains(r#"theme = "theme""#), "{book}");
+});
@@ -0,0 +1,52 @@
+//! Contains various tests for `forge lint`.
+
+const VAULT: &str = r#"
+contract Vault {
+    address public owner;
+    mapping(address => uint256) public balances;
+
+    event Withdrawal(address indexed account, uint256 amount);
+
+    function setOwner(address owner_) external {
+        owner = owner_;
+    }
+
+    function withdraw(uint256 amount) external {
+        balances[msg.sender] -= amount;
+        payable(msg.sender).call{value: amount}("");
+        emit Withdrawal(msg.sender, amount);
+    }
+}
+"#;
+
+forgetest!(reports_lint_findings, |prj, cmd| {
+    prj.add_source("Vault.sol", VAULT).unwrap();
+
+    cmd.args(["lint"]);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(stdout.contains("security[unchecked-call]"), "{stdout}");
+    assert!(stdout.contains("style[missing-event]"), "{stdout}");
+    assert!(stdout.contains("2 lint findings"), "{stdout}");
+});
+
+forgetest!(can_exclude_and_suppress_lints, |prj, cmd| {
+    let source = VAULT.replace(
+        "        payable(msg.sender)",
+        "        // forge-lint: disable-next-line(unchecked-call)\n        payable(msg.sender)",
+    );
+    prj.add_source("Vault.sol", &source).unwrap();
+
+    cmd.args(["lint"]);
+    let (stdout, _) = cmd.unchecked_output_lossy();
+    assert!(!stdout.contains("unchecked-call"), "{stdout}");
+
+    let config = foundry_config::Config {
+        lint: foundry_config::LintConfig {
+            exclude_lints: vec!["missing-event".to_string()],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    prj.write_config(config);
+    cmd.assert_empty_stdout();
+});
@@ -12,7 +12,9 @@ mod coverage;
 mod create;
 mod debug;
 mod doc;
+mod lint;
 mod multi_script;
+mod mutate;
 mod script;
 mod svm;
 mod test_cmd;
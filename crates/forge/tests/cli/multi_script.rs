@@ -20,6 +20,10 @@ forgetest_async!(can_deploy_multi_chain_script_without_lib, |prj, cmd| {
 
     assert_eq!(api2.transaction_count(tester.accounts_pub[0], None).await.unwrap().to::<u32>(), 2);
     assert_eq!(api2.transaction_count(tester.accounts_pub[1], None).await.unwrap().to::<u32>(), 3);
+
+    // The transactions of each chain are also saved to the files of that chain
+    assert!(prj.root().join("broadcast/multi/Broadcast.t.sol-latest/deploy.json").exists());
+    assert!(prj.root().join("broadcast/Broadcast.t.sol/31337/deploy-latest.json").exists());
 });
 
 forgetest_async!(can_not_deploy_multi_chain_script_with_lib, |prj, cmd| {
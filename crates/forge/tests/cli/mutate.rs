@@ -0,0 +1,47 @@
+//! Contains various tests for `forge mutate`.
+
+forgetest!(reports_surviving_mutants, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.add_source(
+        "Counter.sol",
+        r#"
+contract Counter {
+    uint256 public number;
+
+    function increment() public {
+        number = number + 1;
+    }
+
+    function reset() public {
+        number = 0;
+    }
+}
+    "#,
+    )
+    .unwrap();
+    prj.add_source(
+        "Counter.t.sol",
+        r#"
+import "./test.sol";
+import {Counter} from "./Counter.sol";
+
+contract CounterTest is DSTest {
+    function testIncrement() public {
+        Counter counter = new Counter();
+        counter.increment();
+        assertEq(counter.number(), 1);
+    }
+}
+    "#,
+    )
+    .unwrap();
+
+    cmd.args(["mutate", "src/Counter.sol"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("3 mutants: 2 killed, 1 survived, 0 invalid"), "{stdout}");
+    assert!(stdout.contains("number = 0;"), "{stdout}");
+
+    // The mutants are tested in a copy of the project, the source is never modified
+    let source = std::fs::read_to_string(prj.root().join("src/Counter.sol")).unwrap();
+    assert!(source.contains("number = number + 1;"));
+});
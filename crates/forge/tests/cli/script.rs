@@ -1086,3 +1086,121 @@ forgetest_async!(assert_can_resume_with_additional_contracts, |prj, cmd| {
         .await
         .resume(ScriptOutcome::OkBroadcast);
 });
+
+// Tests that calls made with `vm.broadcastUserOp` are recorded as user operations of the account
+forgetest_async!(can_record_user_operations, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let script = prj
+        .add_script(
+            "UserOp.s.sol",
+            r#"
+interface Vm {
+    function broadcastUserOp(address account) external;
+}
+
+contract Counter {
+    address public caller;
+
+    function increment() external {
+        caller = msg.sender;
+    }
+}
+
+contract UserOpScript {
+    Vm constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    function run() external {
+        Counter counter = new Counter();
+        address account = address(0xacc);
+
+        vm.broadcastUserOp(account);
+        counter.increment();
+        require(counter.caller() == account, "not called by the account");
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.arg("script").arg(script).args(["--rpc-url", handle.http_endpoint().as_str()]);
+    assert!(cmd.stdout_lossy().contains("SIMULATION COMPLETE"));
+
+    let run_latest = foundry_common::fs::json_files(prj.root().join("broadcast"))
+        .into_iter()
+        .find(|file| file.ends_with("run-latest.json"))
+        .expect("No broadcast artifacts");
+    let sequence: Value =
+        serde_json::from_str(&foundry_common::fs::read_to_string(run_latest).unwrap()).unwrap();
+
+    assert!(sequence["transactions"].as_array().unwrap().is_empty());
+    let user_ops = sequence["userOperations"].as_array().unwrap();
+    assert_eq!(user_ops.len(), 1);
+    assert_eq!(
+        Address::from_str(user_ops[0]["account"].as_str().unwrap()).unwrap(),
+        Address::left_padding_from(&[0x0a, 0xcc])
+    );
+});
+
+// Tests that `verify-bytecode --all` checks every contract deployed by a broadcast
+forgetest_async!(can_verify_bytecode_of_broadcast, |prj, cmd| {
+    let (_api, handle) = spawn(NodeConfig::test()).await;
+    let script = prj
+        .add_script(
+            "Deploy.s.sol",
+            r#"
+interface Vm {
+    function broadcast() external;
+}
+
+contract Child {}
+
+contract Factory {
+    address public immutable owner;
+    Child public child;
+
+    constructor() {
+        owner = msg.sender;
+        child = new Child();
+    }
+}
+
+contract DeployScript {
+    Vm constant vm = Vm(address(uint160(uint256(keccak256("hevm cheat code")))));
+
+    function run() external {
+        vm.broadcast();
+        new Factory();
+    }
+}
+   "#,
+        )
+        .unwrap();
+
+    cmd.args([
+        "script",
+        script.to_str().unwrap(),
+        "--rpc-url",
+        &handle.http_endpoint(),
+        "--broadcast",
+        "--private-key",
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+    ]);
+    assert!(cmd.stdout_lossy().contains("ONCHAIN EXECUTION COMPLETE & SUCCESSFUL"));
+
+    let run_latest = foundry_common::fs::json_files(prj.root().join("broadcast"))
+        .into_iter()
+        .find(|file| file.ends_with("run-latest.json"))
+        .expect("No broadcast artifacts");
+
+    cmd.forge_fuse().args([
+        "verify-bytecode",
+        "--all",
+        run_latest.to_str().unwrap(),
+        "--rpc-url",
+        &handle.http_endpoint(),
+    ]);
+    let output = cmd.stdout_lossy();
+    assert!(output.contains("Factory at"));
+    assert!(output.contains("Child at"));
+    assert!(!output.contains("doesn't match"));
+});
@@ -1,5 +1,5 @@
 //! Contains various tests for checking `forge test`
-use foundry_common::rpc;
+use foundry_common::{rpc, types::ToAlloy};
 use foundry_config::Config;
 use foundry_test_utils::util::{OutputExt, OTHER_SOLC_VERSION, SOLC_VERSION};
 use std::{path::PathBuf, process::Command, str::FromStr};
@@ -368,6 +368,69 @@ forgetest_init!(exit_code_error_on_fail_fast_with_json, |prj, cmd| {
     cmd.assert_err();
 });
 
+forgetest_init!(fail_fast_cancels_running_fuzz_tests, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_source("failing_test", FAILING_TEST).unwrap();
+    prj.add_source(
+        "fuzz_test",
+        r#"
+import "forge-std/Test.sol";
+
+contract LongFuzzTest is Test {
+    /// forge-config: default.fuzz.timeout = 120
+    function testFuzzForever(uint256 x) public {
+        assertTrue(x == x);
+    }
+}
+"#,
+    )
+    .unwrap();
+
+    cmd.args(["test", "--fail-fast"]);
+    let output = cmd.unchecked_output();
+    assert!(!output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("testShouldFail()"), "{stdout}");
+    assert!(!stdout.contains("testFuzzForever"), "{stdout}");
+});
+
+forgetest_init!(shuffle_prints_order_on_failure, |prj, cmd| {
+    prj.wipe_contracts();
+    prj.add_source("failing_test", FAILING_TEST).unwrap();
+    prj.add_source(
+        "passing_test",
+        r#"
+import "forge-std/Test.sol";
+
+contract PassingTest is Test {
+    function testA() public {}
+    function testB() public {}
+    function testC() public {}
+}
+"#,
+    )
+    .unwrap();
+
+    let mut order = || {
+        cmd.forge_fuse().args(["test", "--shuffle", "--seed", "42"]);
+        let (stdout, _) = cmd.unchecked_output_lossy();
+        let order = stdout
+            .split_once("Tests ran in this order (--shuffle --seed 42):")
+            .unwrap_or_else(|| panic!("{stdout}"))
+            .1
+            .to_string();
+        order.lines().take_while(|line| line.starts_with("  ")).collect::<Vec<_>>().join("\n")
+    };
+
+    let first = order();
+    for test in ["FailingTest", "PassingTest", "testShouldFail()", "testA()", "testB()", "testC()"]
+    {
+        assert!(first.contains(test), "{first}");
+    }
+    assert_eq!(first, order());
+});
+
 // <https://github.com/foundry-rs/foundry/issues/6531>
 forgetest_init!(repro_6531, |prj, cmd| {
     prj.wipe_contracts();
@@ -432,3 +495,124 @@ contract CustomTypesTest is Test {
             .join("tests/fixtures/include_custom_types_in_traces.stdout"),
     );
 });
+
+// tests that fork RPC responses recorded with `--record-rpc` are replayed with `--replay-rpc`
+forgetest_async!(records_and_replays_rpc_responses, |prj, cmd| {
+    let (api, handle) = anvil::spawn(anvil::NodeConfig::test()).await;
+    let account = handle.dev_accounts().next().unwrap().to_alloy();
+    prj.insert_ds_test();
+    prj.add_source(
+        "Fork.t.sol",
+        &format!(
+            r#"
+import "./test.sol";
+
+contract ForkTest is DSTest {{
+    function testBalance() public {{
+        assertEq({account}.balance, {});
+    }}
+}}
+   "#,
+            handle.genesis_balance()
+        ),
+    )
+    .unwrap();
+    let rpc = handle.http_endpoint();
+
+    cmd.args(["test", "--fork-url", rpc.as_str(), "--record-rpc", "cassettes"]);
+    cmd.assert_non_empty_stdout();
+    let cassettes = std::fs::read_dir(prj.root().join("cassettes")).unwrap().count();
+    assert_eq!(cassettes, 1);
+
+    // the replayed balance is the recorded one, not the endpoint's current balance
+    api.anvil_set_balance(account, alloy_primitives::U256::from(1)).await.unwrap();
+    cmd.forge_fuse().args(["test", "--fork-url", rpc.as_str(), "--replay-rpc", "cassettes"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[PASS] testBalance()"), "{stdout}");
+});
+
+// tests that secrets resolved with `vm.getSecret` are redacted from logs and traces
+forgetest!(redacts_secrets, |prj, cmd| {
+    prj.insert_ds_test();
+    prj.insert_console();
+    prj.write_config(Config {
+        secrets: foundry_config::SecretsBackend::Keystore { dir: "secrets".into() },
+        ..Default::default()
+    });
+    eth_keystore::encrypt_key(
+        prj.root().join("secrets"),
+        &mut rand::thread_rng(),
+        "sup3r-s3cret",
+        "hunter2",
+        Some("API_TOKEN"),
+    )
+    .unwrap();
+    prj.add_source(
+        "Secret.t.sol",
+        r#"
+import "./test.sol";
+import "./console.sol";
+
+interface Vm {
+    function getSecret(string calldata key) external returns (string memory);
+}
+
+contract SecretTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testSecret() public {
+        string memory secret = vm.getSecret("API_TOKEN");
+        console.log("token", secret);
+        console.logBytes(bytes(secret));
+    }
+}
+   "#,
+    )
+    .unwrap();
+
+    cmd.env("FOUNDRY_SECRETS_PASSWORD", "hunter2");
+    cmd.args(["test", "-vvvv"]);
+    let stdout = cmd.stdout_lossy();
+    assert!(stdout.contains("[PASS] testSecret()"), "{stdout}");
+    assert!(stdout.contains("token <secret>"), "{stdout}");
+    assert!(!stdout.contains("sup3r-s3cret"), "{stdout}");
+    assert!(!stdout.contains(&alloy_primitives::hex::encode("sup3r-s3cret")), "{stdout}");
+});
+
+// tests that the counterexamples of overloaded fuzz tests are persisted separately, with the seed
+// of the run, and removed once the tests pass again
+forgetest_init!(persists_fuzz_failures_until_fixed, |prj, cmd| {
+    prj.wipe_contracts();
+    let test = |fixed: bool| {
+        format!(
+            r#"
+import "forge-std/Test.sol";
+
+contract OverloadedFuzzTest is Test {{
+    function testFuzz(uint256 x) public {{
+        assertTrue({fixed} || x == 0);
+    }}
+
+    function testFuzz(uint256 x, uint256 y) public {{
+        assertTrue({fixed} || x == y);
+    }}
+}}
+"#
+        )
+    };
+    prj.add_test("OverloadedFuzz.t.sol", &test(false)).unwrap();
+
+    cmd.args(["test"]);
+    cmd.assert_err();
+
+    let failures = prj.root().join("cache").join("fuzz").join("failures");
+    let single = failures.join("OverloadedFuzzTest.testFuzz(uint256).json");
+    let double = failures.join("OverloadedFuzzTest.testFuzz(uint256,uint256).json");
+    assert!(single.exists() && double.exists());
+    let failure: serde_json::Value = foundry_common::fs::read_json_file(&single).unwrap();
+    assert!(!failure["seed"].is_null(), "{failure}");
+
+    prj.add_test("OverloadedFuzz.t.sol", &test(true)).unwrap();
+    cmd.assert_non_empty_stdout();
+    assert!(!single.exists() && !double.exists());
+});
@@ -4,6 +4,12 @@ use crate::{
     config::*,
     test_helpers::{PROJECT, RE_PATH_SEPARATOR},
 };
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{keccak256, Bytes, U256};
+use forge::{
+    fuzz::CounterExample,
+    result::{TestKind, TestStatus},
+};
 use foundry_config::{fs_permissions::PathPermission, Config, FsPermissions};
 use foundry_test_utils::Filter;
 
@@ -13,12 +19,62 @@ async fn test_cheats_local() {
     let mut config = Config::with_root(PROJECT.root());
     config.fs_permissions = FsPermissions::new(vec![PathPermission::read_write("./")]);
     let runner = runner_with_config(config);
-    let filter =
-        Filter::new(".*", ".*", &format!(".*cheats{RE_PATH_SEPARATOR}*")).exclude_paths("Fork");
+    let filter = Filter::new(".*", ".*", &format!(".*cheats{RE_PATH_SEPARATOR}*"))
+        .exclude_tests("testFuzzFixtureNeedle")
+        .exclude_paths("Fork");
 
     // on windows exclude ffi tests since no echo and file test that expect a certain file path
     #[cfg(windows)]
-    let filter = filter.exclude_tests("(Ffi|File|Line|Root)");
+    let filter = filter.exclude_tests("(Ffi|File|Line|Root|testFuzzFixtureNeedle)");
 
     TestConfig::with_filter(runner.await, filter).run().await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cheats_snapshot_value() {
+    let mut runner = runner().await;
+    let filter = Filter::new(".*", "SnapshotValueTest", ".*cheats/SnapshotValue.t.sol");
+
+    let results = runner.test_collect(&filter, test_opts()).await;
+    let suite_result = results.values().next().unwrap();
+    let snapshots = suite_result.value_snapshots();
+    assert_eq!(snapshots.len(), 4);
+    assert_eq!(snapshots["root"][..], keccak256("root")[..]);
+    assert_eq!(snapshots["overwritten"], Bytes::from_static(&[2]));
+    assert_eq!(snapshots["setup"][..], U256::from(1).to_be_bytes::<32>()[..]);
+    // the values of reverted tests aren't snapshotted
+    assert!(!snapshots.contains_key("reverted"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cheats_fuzz_config() {
+    let mut runner = runner().await;
+    let filter = Filter::new("testFuzz", "FuzzConfigTest", ".*cheats/FuzzConfig.t.sol");
+
+    let results = runner.test_collect(&filter, test_opts()).await;
+    let suite_result = results.values().next().unwrap();
+    // `setUp` overrides both the project and the inline number of runs
+    for test in ["testFuzzConfigOverrides(uint16)", "testFuzzConfigOverridesInlineConfig(uint16)"] {
+        let result = &suite_result.test_results[test];
+        assert!(
+            matches!(result.kind, TestKind::Fuzz { runs: 10, .. }),
+            "{test}: {:?}",
+            result.kind
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cheats_fuzz_fixture() {
+    let mut runner = runner().await;
+    let filter = Filter::new(".*", "FuzzFixtureNeedleTest", ".*cheats/FuzzFixture.t.sol");
+
+    let results = runner.test_collect(&filter, test_opts()).await;
+    let result = &results.values().next().unwrap().test_results["testFuzzFixtureNeedle(bytes32)"];
+    // the test can only fail on the value added with `vm.addFuzzFixture`
+    assert_eq!(result.status, TestStatus::Failure, "{:?}", result.reason);
+    let Some(CounterExample::Single(counterexample)) = &result.counterexample else {
+        panic!("no counterexample: {:?}", result.counterexample)
+    };
+    assert_eq!(counterexample.args, vec![DynSolValue::FixedBytes(keccak256("pool"), 32)]);
+}
@@ -117,6 +117,9 @@ pub fn test_opts() -> TestOptions {
             runs: 256,
             max_test_rejects: 65536,
             seed: None,
+            timeout: None,
+            workers: 1,
+            coverage_guided: false,
             dictionary: FuzzDictionaryConfig {
                 include_storage: true,
                 include_push_bytes: true,
@@ -139,6 +142,9 @@ pub fn test_opts() -> TestOptions {
             },
             shrink_sequence: true,
             shrink_run_limit: 2usize.pow(18u32),
+            shrink_calldata: false,
+            corpus_dir: None,
+            timeout: None,
         })
         .build(&COMPILED, &PROJECT.paths.root)
         .expect("Config loaded")
@@ -2,7 +2,8 @@
 
 use crate::config::*;
 use forge::result::SuiteResult;
-use foundry_evm::traces::TraceKind;
+use foundry_config::GasOverrides;
+use foundry_evm::{inspectors::GasSchedule, traces::TraceKind};
 use foundry_test_utils::Filter;
 use std::{collections::BTreeMap, env};
 
@@ -75,6 +76,76 @@ async fn test_core() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_lifecycle_hooks() {
+    let mut runner = runner().await;
+    let results = runner.test_collect(&Filter::new(".*", ".*", ".*lifecycle"), test_opts()).await;
+
+    assert_multiple(
+        &results,
+        BTreeMap::from([
+            (
+                "lifecycle/LifecycleHooks.t.sol:LifecycleHooksTest",
+                vec![
+                    ("testBeforeEachRuns()", true, None, None, None),
+                    ("testBeforeEachResets()", true, None, None, None),
+                ],
+            ),
+            (
+                "lifecycle/LifecycleHooks.t.sol:FailingBeforeEachTest",
+                vec![(
+                    "testNeverRuns()",
+                    false,
+                    Some("beforeEach() failed: revert: beforeEach failed predictably".to_string()),
+                    None,
+                    None,
+                )],
+            ),
+            (
+                "lifecycle/LifecycleHooks.t.sol:FailingAfterEachTest",
+                vec![(
+                    "testPasses()",
+                    false,
+                    Some("afterEach() failed: revert: afterEach failed predictably".to_string()),
+                    None,
+                    None,
+                )],
+            ),
+            (
+                "lifecycle/LifecycleHooks.t.sol:FailingAfterAllTest",
+                vec![("testPasses()", true, None, None, None)],
+            ),
+        ]),
+    );
+
+    // `afterAll` fails the suite rather than one of its tests
+    let failure =
+        |suite: &str| results[&format!("lifecycle/LifecycleHooks.t.sol:{suite}")].failure.clone();
+    assert_eq!(failure("LifecycleHooksTest"), None);
+    assert_eq!(
+        failure("FailingAfterAllTest"),
+        Some("afterAll() failed: assertion failed".to_string())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_gas_overrides() {
+    let mut runner = runner().await;
+    let overrides =
+        GasOverrides { intrinsic: Some(16000), opcodes: [("SLOAD".to_string(), 800)].into() };
+    runner.gas_schedule = GasSchedule::new(&overrides).unwrap();
+    let results =
+        runner.test_collect(&Filter::new(".*", ".*", ".*gas/GasOverrides"), test_opts()).await;
+
+    assert_multiple(
+        &results,
+        BTreeMap::from([(
+            "gas/GasOverrides.t.sol:GasOverridesTest",
+            vec![("testSloadOverride()", true, None, None, None)],
+        )]),
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_linking() {
     let mut runner = runner().await;
@@ -2,9 +2,13 @@
 
 use crate::config::*;
 use alloy_primitives::U256;
-use forge::result::{SuiteResult, TestStatus};
+use forge::result::{FuzzFailure, SuiteResult, TestKind, TestStatus};
 use foundry_test_utils::Filter;
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    time::{Duration, Instant},
+};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_fuzz() {
@@ -13,7 +17,9 @@ async fn test_fuzz() {
     let suite_result = runner
         .test_collect(
             &Filter::new(".*", ".*", ".*fuzz/")
-                .exclude_tests(r"invariantCounter|testIncrement\(address\)|testNeedle\(uint256\)")
+                .exclude_tests(
+                    r"invariantCounter|testIncrement\(address\)|testNeedle\(uint256\)|testStagedBranches",
+                )
                 .exclude_paths("invariant"),
             test_opts(),
         )
@@ -81,3 +87,116 @@ async fn test_fuzz_collection() {
         )]),
     );
 }
+
+/// Test that coverage-guided fuzzing reaches branches nested behind other branches, by mutating
+/// the inputs which reached the outer ones.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuzz_coverage_guided() {
+    let mut runner = runner().await;
+
+    let mut opts = test_opts();
+    opts.fuzz.seed = Some(U256::from(1u32));
+    runner.test_options = opts.clone();
+
+    let results = runner
+        .test_collect(&Filter::new(".*", ".*", ".*fuzz/FuzzCoverageGuided.t.sol"), opts)
+        .await;
+
+    assert_multiple(
+        &results,
+        BTreeMap::from([(
+            "fuzz/FuzzCoverageGuided.t.sol:FuzzCoverageGuidedTest",
+            vec![("testStagedBranches(uint256,uint256)", false, Some("found".into()), None, None)],
+        )]),
+    );
+}
+
+/// Test that the runs of a fuzz test are split between the workers, and that a counterexample
+/// found by any of them fails the test.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuzz_workers() {
+    let mut runner = runner().await;
+
+    let mut opts = test_opts();
+    opts.fuzz.workers = 4;
+    runner.test_options = opts.clone();
+
+    let suite_result =
+        runner.test_collect(&Filter::new(".*", ".*", ".*fuzz/Fuzz.t.sol"), opts).await;
+
+    for (_, SuiteResult { test_results, .. }) in suite_result {
+        for (test_name, result) in test_results {
+            match test_name.as_str() {
+                "testSuccessfulFuzz(uint128,uint128)" => {
+                    assert_eq!(result.status, TestStatus::Success);
+                    match result.kind {
+                        TestKind::Fuzz { runs, .. } => assert_eq!(runs, 256),
+                        _ => unreachable!(),
+                    }
+                }
+                "testFailFuzz(uint8)" => assert_eq!(result.status, TestStatus::Failure),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Test that a fuzz test bounded by a timeout runs until the timeout is reached and passes, while
+/// a counterexample found before it still fails the test.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuzz_timeout() {
+    let mut runner = runner().await;
+
+    let mut opts = test_opts();
+    opts.fuzz.timeout = Some(1);
+    opts.fuzz.workers = 2;
+    runner.test_options = opts.clone();
+
+    let start = Instant::now();
+    let suite_result =
+        runner.test_collect(&Filter::new(".*", ".*", ".*fuzz/Fuzz.t.sol"), opts).await;
+    assert!(start.elapsed() >= Duration::from_secs(1));
+
+    for (_, SuiteResult { test_results, .. }) in suite_result {
+        for (test_name, result) in test_results {
+            match test_name.as_str() {
+                "testSuccessfulFuzz(uint128,uint128)" => {
+                    assert_eq!(result.status, TestStatus::Success, "{:?}", result.reason);
+                    match result.kind {
+                        TestKind::Fuzz { runs, .. } => assert!(runs > 0),
+                        _ => unreachable!(),
+                    }
+                }
+                "testFailFuzz(uint8)" => assert_eq!(result.status, TestStatus::Failure),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Test that the counterexample of a failed fuzz test is replayed instead of fuzzing the test.
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fuzz_replay() {
+    let mut runner = runner().await;
+    let filter = Filter::new("testFailFuzz", ".*", ".*fuzz/Fuzz.t.sol");
+
+    let results = runner.test_collect(&filter, test_opts()).await;
+    let (contract, suite_result) = results.iter().next().unwrap();
+    let result = &suite_result.test_results["testFailFuzz(uint8)"];
+    assert_eq!(result.status, TestStatus::Failure);
+    let failure = FuzzFailure::new(contract, "testFailFuzz(uint8)", result).unwrap();
+    assert_eq!(failure.calls.len(), 1);
+    // the seed of the random run is persisted, and overloaded tests have their own file
+    assert!(failure.seed.is_some());
+    assert!(failure.path(Path::new("failures")).ends_with("FuzzTest.testFailFuzz(uint8).json"));
+
+    let mut opts = test_opts();
+    opts.replay = Some(failure.clone());
+    runner.test_options = opts.clone();
+
+    let results = runner.test_collect(&filter, opts).await;
+    let result = &results[contract].test_results["testFailFuzz(uint8)"];
+    assert_eq!(result.status, TestStatus::Failure);
+    assert!(matches!(result.kind, TestKind::Fuzz { runs: 1, .. }));
+    assert_eq!(FuzzFailure::new(contract, "testFailFuzz(uint8)", result), Some(failure));
+}
@@ -5,11 +5,13 @@ use crate::{
     test_helpers::{COMPILED, PROJECT},
 };
 use forge::{
-    result::{SuiteResult, TestKind, TestResult},
+    result::{SuiteResult, TestKind, TestResult, TestStatus},
+    symbolic::{ConstraintKind, SymbolicExecutor, SymbolicOutcome, SymbolicTest},
     TestOptions, TestOptionsBuilder,
 };
 use foundry_config::{FuzzConfig, InvariantConfig};
 use foundry_test_utils::Filter;
+use std::sync::{Arc, Mutex};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn inline_config_run_fuzz() {
@@ -72,6 +74,96 @@ async fn inline_config_run_invariant() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn inline_config_run_symbolic() {
+    const ROOT: &str = "inline/SymbolicInlineConf.t.sol";
+
+    let mut opts = default_test_options();
+    let executor = Arc::new(RecordingExecutor::default());
+    opts.symbolic = Some(executor.clone() as Arc<dyn SymbolicExecutor>);
+    let filter = Filter::new(".*", ".*", ".*inline/SymbolicInlineConf.t.sol");
+    let mut runner = runner().await;
+    runner.test_options = opts.clone();
+
+    let result = runner.test_collect(&filter, opts).await;
+    let suite_result = result.get(&format!("{ROOT}:SymbolicInlineConf")).expect("Result exists");
+
+    let symbolic = &suite_result.test_results["testInlineConfSymbolic(uint8,bytes)"];
+    assert_eq!(symbolic.status, TestStatus::Failure);
+    assert_eq!(symbolic.reason.as_deref(), Some("recorder found a counterexample: x = 99"));
+    assert_eq!(suite_result.test_results["testNotSymbolic()"].status, TestStatus::Success);
+
+    let tests = executor.tests.lock().unwrap();
+    assert_eq!(tests.len(), 1);
+    let test = &tests[0];
+    assert_eq!(test.contract, format!("{ROOT}:SymbolicInlineConf"));
+    assert_eq!(test.function.name, "testInlineConfSymbolic");
+    assert!(!test.bytecode.is_empty());
+    let calldata: Vec<_> =
+        test.calldata.iter().map(|param| (param.offset, param.dynamic)).collect();
+    assert_eq!(calldata, [(4, false), (36, true)]);
+    let constraints: Vec<_> =
+        test.source.constraints.iter().map(|c| (c.kind, c.expr.as_str())).collect();
+    assert_eq!(
+        constraints,
+        [
+            (ConstraintKind::Assume, "x < 100"),
+            (ConstraintKind::Assert, "data.length + x == x + data.length"),
+            (ConstraintKind::Assert, "x != 100"),
+        ]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn inline_config_run_table() {
+    let opts = default_test_options();
+    let filter = Filter::new(".*", ".*", ".*inline/TableInlineConf.t.sol");
+    let mut runner = runner().await;
+    runner.test_options = opts.clone();
+
+    let result = runner.test_collect(&filter, opts).await;
+    let suite_result =
+        result.get("inline/TableInlineConf.t.sol:TableInlineConf").expect("Result exists");
+    let statuses: Vec<_> = suite_result
+        .test_results
+        .iter()
+        .map(|(name, result)| (name.as_str(), result.status))
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            (
+                "testInlineConfTableJson(uint256,uint256,uint256) [small amounts]",
+                TestStatus::Success
+            ),
+            ("testInlineConfTableJson(uint256,uint256,uint256) [with fee]", TestStatus::Success),
+            (
+                "testInlineConfTableJson(uint256,uint256,uint256) [wrong expectation]",
+                TestStatus::Failure
+            ),
+            ("testInlineConfTableToml(uint256,uint256,uint256) [0]", TestStatus::Success),
+            ("testInlineConfTableToml(uint256,uint256,uint256) [1]", TestStatus::Success),
+        ]
+    );
+}
+
+/// A [SymbolicExecutor] that records the tests exported to it and refutes them.
+#[derive(Debug, Default)]
+struct RecordingExecutor {
+    tests: Mutex<Vec<SymbolicTest>>,
+}
+
+impl SymbolicExecutor for RecordingExecutor {
+    fn name(&self) -> &str {
+        "recorder"
+    }
+
+    fn execute(&self, test: &SymbolicTest) -> eyre::Result<SymbolicOutcome> {
+        self.tests.lock().unwrap().push(test.clone());
+        Ok(SymbolicOutcome::Counterexample("x = 99".to_string()))
+    }
+}
+
 #[test]
 fn build_test_options() {
     let root = &PROJECT.paths.root;
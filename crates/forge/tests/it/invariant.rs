@@ -1,7 +1,8 @@
 //! Invariant tests.
 
 use crate::config::*;
-use alloy_primitives::U256;
+use alloy_dyn_abi::DynSolValue;
+use alloy_primitives::{Address, U256};
 use forge::fuzz::CounterExample;
 use foundry_test_utils::Filter;
 use std::collections::BTreeMap;
@@ -38,6 +39,10 @@ async fn test_invariant() {
                 "fuzz/invariant/common/InvariantReentrancy.t.sol:InvariantReentrancy",
                 vec![("invariantNotStolen()", true, None, None, None)],
             ),
+            (
+                "fuzz/invariant/common/InvariantShrinkCalldata.t.sol:InvariantShrinkCalldata",
+                vec![("invariantNotBroken()", false, Some("revert: broken".into()), None, None)],
+            ),
             (
                 "fuzz/invariant/common/InvariantTest1.t.sol:InvariantTest",
                 vec![
@@ -87,6 +92,16 @@ async fn test_invariant() {
                 "fuzz/invariant/target/TargetSelectors.t.sol:TargetSelectors",
                 vec![("invariantTrueWorld()", true, None, None, None)],
             ),
+            (
+                "fuzz/invariant/target/TargetFlows.t.sol:TargetFlows",
+                vec![(
+                    "invariantNeverWithdrawn()",
+                    false,
+                    Some("revert: withdrawn".into()),
+                    None,
+                    None,
+                )],
+            ),
             (
                 "fuzz/invariant/targetAbi/ExcludeArtifacts.t.sol:ExcludeArtifacts",
                 vec![("invariantShouldPass()", true, None, None, None)],
@@ -244,3 +259,41 @@ async fn test_invariant_shrink() {
         }
     };
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_invariant_shrink_calldata() {
+    let mut runner = runner().await;
+
+    let results = runner
+        .test_collect(
+            &Filter::new(".*", ".*", ".*fuzz/invariant/common/InvariantShrinkCalldata.t.sol"),
+            test_opts(),
+        )
+        .await;
+
+    let results =
+        results.values().last().expect("`InvariantShrinkCalldata.t.sol` should be testable.");
+
+    let result = results
+        .test_results
+        .values()
+        .last()
+        .expect("`InvariantShrinkCalldata` should be testable.");
+
+    let counter = result
+        .counterexample
+        .as_ref()
+        .expect("`InvariantShrinkCalldata` should have failed with a counterexample.");
+
+    match counter {
+        CounterExample::Single(_) => panic!("CounterExample should be a sequence."),
+        // The arguments are shrunk to the smallest values which still break the invariant.
+        CounterExample::Sequence(sequence) => {
+            assert_eq!(sequence.len(), 1);
+            assert_eq!(
+                sequence[0].args,
+                vec![DynSolValue::Uint(U256::from(1001), 256), DynSolValue::Address(Address::ZERO)]
+            );
+        }
+    };
+}
@@ -2,8 +2,8 @@
 
 use alloy_primitives::U256;
 use foundry_compilers::{
-    artifacts::{Libraries, Settings},
-    Project, ProjectCompileOutput, ProjectPathsConfig, SolcConfig,
+    artifacts::{output_selection::ContractOutputSelection, Libraries, Settings},
+    ConfigurableArtifacts, Project, ProjectCompileOutput, ProjectPathsConfig, SolcConfig,
 };
 use foundry_config::Config;
 use foundry_evm::{
@@ -25,10 +25,18 @@ pub static PROJECT: Lazy<Project> = Lazy::new(|| {
 
     let libs =
         ["fork/Fork.t.sol:DssExecLib:0xfD88CeE74f7D78697775aBDAE53f9Da1559728E4".to_string()];
-    let settings = Settings { libraries: Libraries::parse(&libs).unwrap(), ..Default::default() };
+    // Storage layouts are needed by `stopAndReturnDecodedStateDiff`
+    let extra_output = [ContractOutputSelection::StorageLayout];
+    let settings = Settings { libraries: Libraries::parse(&libs).unwrap(), ..Default::default() }
+        .with_extra_output(extra_output.clone());
     let solc_config = SolcConfig::builder().settings(settings).build();
 
-    Project::builder().paths(paths).solc_config(solc_config).build().unwrap()
+    Project::builder()
+        .paths(paths)
+        .solc_config(solc_config)
+        .artifacts(ConfigurableArtifacts::new(extra_output, []))
+        .build()
+        .unwrap()
 });
 
 pub static COMPILED: Lazy<ProjectCompileOutput> = Lazy::new(|| {
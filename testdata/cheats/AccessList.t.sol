@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract Counter {
+    uint256 public count;
+    Counter public next;
+
+    function setNext(Counter counter) public {
+        next = counter;
+    }
+
+    function increment() public {
+        count += 1;
+        if (address(next) != address(0)) {
+            next.count();
+        }
+    }
+}
+
+contract AccessListTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testAccessList() public {
+        Counter first = new Counter();
+        Counter second = new Counter();
+        first.setNext(second);
+
+        Vm.AccessListItem[] memory accessList =
+            vm.accessList(address(first), abi.encodeCall(Counter.increment, ()));
+        assertEq(accessList.length, 2);
+        (uint256 firstIndex, uint256 secondIndex) = address(first) < address(second) ? (0, 1) : (1, 0);
+
+        assertEq(accessList[firstIndex].account, address(first));
+        assertEq(accessList[firstIndex].storageKeys.length, 2);
+        assertEq(accessList[firstIndex].storageKeys[0], bytes32(uint256(0)));
+        assertEq(accessList[firstIndex].storageKeys[1], bytes32(uint256(1)));
+
+        assertEq(accessList[secondIndex].account, address(second));
+        assertEq(accessList[secondIndex].storageKeys.length, 1);
+        assertEq(accessList[secondIndex].storageKeys[0], bytes32(uint256(0)));
+
+        // The call is only simulated
+        assertEq(first.count(), 0);
+    }
+
+    function testApplyAccessList() public {
+        Vm.AccessListItem[] memory accessList = new Vm.AccessListItem[](1);
+        accessList[0] = Vm.AccessListItem(address(this), new bytes32[](0));
+        vm.applyAccessList(accessList);
+        vm.applyAccessList(new Vm.AccessListItem[](0));
+    }
+}
@@ -0,0 +1,47 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract Token {
+    mapping(address => uint256) public balanceOf;
+
+    function transfer(address to, uint256 amount) public returns (bool) {
+        balanceOf[msg.sender] -= amount;
+        balanceOf[to] += amount;
+        return true;
+    }
+
+    function approve(address, uint256) public pure returns (bool) {
+        return true;
+    }
+}
+
+contract ArtifactQueriesTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant ARTIFACT = "ArtifactQueries.t.sol:Token";
+
+    function testGetAbi() public {
+        string memory abiJson = vm.getAbi(ARTIFACT);
+        assertEq(vm.parseJsonString(abiJson, "$[0].name"), "approve");
+        assertEq(vm.parseJsonString(abiJson, "$[0].type"), "function");
+    }
+
+    function testGetMethodIdentifiers() public {
+        (string[] memory signatures, bytes4[] memory selectors) = vm.getMethodIdentifiers(ARTIFACT);
+        assertEq(signatures.length, 3);
+        assertEq(signatures[0], "approve(address,uint256)");
+        assertEq(signatures[1], "balanceOf(address)");
+        assertEq(signatures[2], "transfer(address,uint256)");
+        assertEq(selectors[0], Token.approve.selector);
+        assertEq(selectors[1], Token.balanceOf.selector);
+        assertEq(selectors[2], Token.transfer.selector);
+    }
+
+    function testGetCodeHash() public {
+        Token token = new Token();
+        assertEq(vm.getCodeHash(ARTIFACT), address(token).codehash);
+    }
+}
@@ -11,4 +11,15 @@ contract AssumeTest is DSTest {
         vm.assume(x < 2 ** 7);
         assertTrue(x < 2 ** 7, "did not discard inputs");
     }
+
+    function testAssumeUnique(uint256 x) public {
+        vm.assumeUnique(bytes32(x));
+        // The same run may reuse its own keys
+        vm.assumeUnique(bytes32(x));
+    }
+
+    function testAssumeUniqueFewKeys(uint256 x) public {
+        // Only the first runs using each key are kept, the others are discarded
+        vm.assumeUnique(bytes32(x % 4));
+    }
 }
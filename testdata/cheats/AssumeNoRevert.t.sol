@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ReverterB {
+    error EvenInput(uint256 x);
+
+    function revertIfEven(uint256 x) public pure returns (uint256) {
+        if (x % 2 == 0) {
+            revert("x is even");
+        }
+        return x;
+    }
+
+    function revertIfEvenCustom(uint256 x) public pure returns (uint256) {
+        if (x % 2 == 0) {
+            revert EvenInput(x);
+        }
+        return x;
+    }
+}
+
+contract Router {
+    function forward(ReverterB reverter, uint256 x) public pure returns (uint256) {
+        return reverter.revertIfEven(x);
+    }
+
+    function revertIfOdd(uint256 x) public pure returns (uint256) {
+        if (x % 2 == 1) {
+            revert("x is odd");
+        }
+        return x;
+    }
+}
+
+contract EvenConstructorReverter {
+    uint256 public x;
+
+    constructor(uint256 _x) {
+        require(_x % 2 == 1, "x is even");
+        x = _x;
+    }
+}
+
+contract AssumeNoRevertTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    ReverterB reverter;
+    Router router;
+
+    function setUp() public {
+        reverter = new ReverterB();
+        router = new Router();
+    }
+
+    function testAssumeNoRevert(uint256 x) public {
+        vm.assumeNoRevert();
+        uint256 y = reverter.revertIfEven(x);
+        assertEq(y % 2, 1, "did not discard inputs");
+    }
+
+    function testAssumeNoRevertWithData(uint256 x) public {
+        vm.assumeNoRevert(abi.encodeWithSelector(ReverterB.EvenInput.selector, x));
+        uint256 y = reverter.revertIfEvenCustom(x);
+        assertEq(y % 2, 1, "did not discard inputs");
+    }
+
+    function testAssumeNoRevertWithReason(uint256 x) public {
+        string memory reason = "x is even";
+        vm.assumeNoRevert(reason);
+        uint256 y = reverter.revertIfEven(x);
+        assertEq(y % 2, 1, "did not discard inputs");
+    }
+
+    function testAssumeNoRevertOnlyAppliesToNextCall(uint256 x) public {
+        vm.assume(x % 2 == 1);
+        vm.assumeNoRevert();
+        reverter.revertIfEven(x);
+        assertEq(reverter.revertIfEven(x), x);
+    }
+
+    function testFailAssumeNoRevertWithMismatchedReason(uint256 x) public {
+        vm.assume(x % 2 == 0);
+        string memory reason = "another reason";
+        vm.assumeNoRevert(reason);
+        reverter.revertIfEven(x);
+    }
+
+    function testAssumeNoRevertFromCallee(uint256 x) public {
+        vm.assumeNoRevert(address(reverter));
+        uint256 y = router.forward(reverter, x);
+        assertEq(y % 2, 1, "did not discard inputs");
+    }
+
+    function testFailAssumeNoRevertFromOtherCallee(uint256 x) public {
+        vm.assume(x % 2 == 1);
+        vm.assumeNoRevert(address(reverter));
+        router.revertIfOdd(x);
+    }
+
+    function testAssumeNoRevertConstructor(uint256 x) public {
+        vm.assumeNoRevert();
+        EvenConstructorReverter deployed = new EvenConstructorReverter(x);
+        assertEq(deployed.x() % 2, 1, "did not discard inputs");
+    }
+
+    function testFailAssumeNoRevertOnlyAppliesToNextCreation(uint256 x) public {
+        vm.assume(x % 2 == 1);
+        vm.assumeNoRevert();
+        new EvenConstructorReverter(x);
+        reverter.revertIfEven(x - 1);
+    }
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract CheatStateTarget {
+    function number() public pure returns (uint256) {
+        return 1;
+    }
+
+    function sender() public view returns (address) {
+        return msg.sender;
+    }
+}
+
+contract CheatStateTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testRestoreMockedCalls() public {
+        CheatStateTarget target = new CheatStateTarget();
+        vm.mockCall(address(target), abi.encodeWithSelector(target.number.selector), abi.encode(10));
+        uint256 id = vm.saveCheatState();
+
+        vm.clearMockedCalls();
+        assertEq(target.number(), 1);
+
+        vm.restoreCheatState(id);
+        assertEq(target.number(), 10);
+    }
+
+    function testRestorePrank() public {
+        CheatStateTarget target = new CheatStateTarget();
+        uint256 id = vm.saveCheatState();
+
+        vm.startPrank(address(1337));
+        assertEq(target.sender(), address(1337));
+
+        vm.restoreCheatState(id);
+        assertEq(target.sender(), address(this));
+    }
+
+    function testSaveCheatStateIds() public {
+        assertEq(vm.saveCheatState(), 0);
+        assertEq(vm.saveCheatState(), 1);
+    }
+
+    function testFailRestoreUnknownCheatState() public {
+        vm.restoreCheatState(0);
+    }
+}
@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract CompressTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    bytes constant DATA = "the quick brown fox jumps over the lazy dog, the quick brown fox jumps over the lazy dog";
+
+    function testGzipRoundTrip() public {
+        bytes memory compressed = vm.gzipCompress(DATA);
+        assertEq(uint8(compressed[0]), 0x1f);
+        assertEq(uint8(compressed[1]), 0x8b);
+        assertTrue(compressed.length < DATA.length);
+        assertEq(vm.gzipDecompress(compressed), DATA);
+    }
+
+    function testGzipDecompress() public {
+        bytes memory compressed = hex"1f8b0800000000000203cb48cdc9c95728cf2fca49010085114a0d0b000000";
+        assertEq(vm.gzipDecompress(compressed), "hello world");
+    }
+
+    function testGzipDecompressInvalid() public {
+        vm._expectCheatcodeRevert();
+        vm.gzipDecompress(hex"deadbeef");
+    }
+
+    function testBrotliRoundTrip() public {
+        bytes memory compressed = vm.brotliCompress(DATA);
+        assertTrue(compressed.length < DATA.length);
+        assertEq(vm.brotliDecompress(compressed), DATA);
+        assertEq(vm.brotliDecompress(vm.brotliCompress("")), "");
+    }
+}
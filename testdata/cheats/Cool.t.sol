@@ -57,4 +57,34 @@ contract CoolTest is DSTest {
 
         assertGt(beforeCoolGas, noCoolGas);
     }
+
+    function testWarmSlot_SLOAD() public {
+        uint256 startGas;
+        uint256 endGas;
+        uint256 val;
+        uint256 beforeCoolGas;
+        uint256 afterWarmGas;
+        uint256 noCoolGas;
+
+        startGas = gasleft();
+        val = slot0;
+        endGas = gasleft();
+        beforeCoolGas = startGas - endGas;
+
+        startGas = gasleft();
+        val = slot0;
+        endGas = gasleft();
+        noCoolGas = startGas - endGas;
+
+        vm.cool(address(this));
+        vm.warmSlot(address(this), bytes32(0));
+
+        startGas = gasleft();
+        val = slot0;
+        endGas = gasleft();
+        afterWarmGas = startGas - endGas;
+
+        assertEq(afterWarmGas, noCoolGas);
+        assertGt(beforeCoolGas, afterWarmGas);
+    }
 }
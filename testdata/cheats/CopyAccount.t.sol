@@ -0,0 +1,62 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract CopyAccountTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant DUMP = "fixtures/StateDump/anvil.json";
+    address constant GETTER = address(0xc0de);
+
+    uint256 source;
+    uint256 target;
+
+    function setUp() public {
+        source = vm.createFork(DUMP);
+        target = vm.createFork(DUMP);
+    }
+
+    function read(address account) internal view returns (uint256) {
+        (bool success, bytes memory data) = account.staticcall("");
+        require(success, "getter call failed");
+        return abi.decode(data, (uint256));
+    }
+
+    function testCopyAccount() public {
+        address account = address(0xb0b);
+        vm.selectFork(source);
+        vm.etch(account, hex"60005460005260206000f3");
+        vm.deal(account, 5 ether);
+        vm.setNonce(account, 9);
+        vm.store(account, bytes32(0), bytes32(uint256(1)));
+        vm.store(account, bytes32(uint256(1)), bytes32(uint256(2)));
+
+        vm.copyAccount(source, target, account);
+
+        vm.selectFork(target);
+        assertEq(account.code, hex"60005460005260206000f3");
+        assertEq(account.balance, 5 ether);
+        assertEq(vm.getNonce(account), 9);
+        assertEq(uint256(vm.load(account, bytes32(0))), 1);
+        assertEq(uint256(vm.load(account, bytes32(uint256(1)))), 2);
+        assertEq(read(account), 1);
+    }
+
+    function testCopyIntoActiveFork() public {
+        vm.selectFork(source);
+        vm.store(GETTER, bytes32(0), bytes32(uint256(7)));
+
+        vm.selectFork(target);
+        assertEq(read(GETTER), 42);
+
+        vm.copyAccount(source, target, GETTER);
+        assertEq(read(GETTER), 7);
+
+        // the source fork is left untouched
+        vm.store(GETTER, bytes32(0), bytes32(uint256(8)));
+        vm.selectFork(source);
+        assertEq(read(GETTER), 7);
+    }
+}
@@ -0,0 +1,106 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract SimpleToken {
+    uint256 public totalSupply;
+    mapping(address => uint256) public balanceOf;
+}
+
+contract PackedToken {
+    struct Account {
+        uint64 nonce;
+        uint96 balance;
+        uint96 flags;
+    }
+
+    mapping(address => Account) internal accounts;
+
+    function setAccount(address account, uint64 nonce, uint96 flags) public {
+        accounts[account].nonce = nonce;
+        accounts[account].flags = flags;
+    }
+
+    function nonceOf(address account) public view returns (uint64) {
+        return accounts[account].nonce;
+    }
+
+    function flagsOf(address account) public view returns (uint96) {
+        return accounts[account].flags;
+    }
+
+    function balanceOf(address account) public view returns (uint256) {
+        return accounts[account].balance;
+    }
+}
+
+contract TokenProxy {
+    // Keep the implementation out of the way of the token's storage layout.
+    bytes32 internal constant IMPLEMENTATION_SLOT = keccak256("proxy.implementation");
+
+    constructor(address implementation) {
+        bytes32 slot = IMPLEMENTATION_SLOT;
+        assembly {
+            sstore(slot, implementation)
+        }
+    }
+
+    fallback() external {
+        bytes32 slot = IMPLEMENTATION_SLOT;
+        assembly {
+            let implementation := sload(slot)
+            calldatacopy(0, 0, calldatasize())
+            let success := delegatecall(gas(), implementation, 0, calldatasize(), 0, 0)
+            returndatacopy(0, 0, returndatasize())
+            if iszero(success) { revert(0, returndatasize()) }
+            return(0, returndatasize())
+        }
+    }
+}
+
+contract DealERC20Test is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testDealERC20(uint256 amount) public {
+        SimpleToken token = new SimpleToken();
+        address target = address(10);
+
+        vm.dealERC20(address(token), target, amount / 2);
+        assertEq(token.balanceOf(target), amount / 2);
+
+        // Check that it overwrites the balance and leaves the total supply untouched
+        vm.dealERC20(address(token), target, amount);
+        assertEq(token.balanceOf(target), amount);
+        assertEq(token.totalSupply(), 0);
+    }
+
+    function testDealERC20Packed(uint96 amount, uint64 nonce, uint96 flags) public {
+        PackedToken token = new PackedToken();
+        address target = address(10);
+        token.setAccount(target, nonce, flags);
+
+        vm.dealERC20(address(token), target, amount);
+        assertEq(token.balanceOf(target), amount);
+        assertEq(token.nonceOf(target), nonce);
+        assertEq(token.flagsOf(target), flags);
+    }
+
+    function testDealERC20Proxy(uint256 amount) public {
+        SimpleToken token = SimpleToken(address(new TokenProxy(address(new SimpleToken()))));
+        address target = address(10);
+
+        vm.dealERC20(address(token), target, amount);
+        assertEq(token.balanceOf(target), amount);
+    }
+
+    function testFailDealERC20PackedOverflow() public {
+        PackedToken token = new PackedToken();
+        vm.dealERC20(address(token), address(10), uint256(type(uint96).max) + 1);
+    }
+
+    function testFailDealERC20NotAToken() public {
+        vm.dealERC20(address(this), address(10), 1);
+    }
+}
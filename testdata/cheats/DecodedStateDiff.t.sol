@@ -0,0 +1,116 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract Vault {
+    struct Config {
+        uint256 fee;
+        address admin;
+    }
+
+    address public owner;
+    bool public paused;
+    uint256 public total;
+    Config public config;
+    mapping(address => uint256) public balances;
+    int128[] public deltas;
+
+    constructor() {
+        owner = msg.sender;
+    }
+
+    function setPaused(bool value) public {
+        paused = value;
+    }
+
+    function setFee(uint256 fee) public {
+        config.fee = fee;
+    }
+
+    function deposit(address account, uint256 amount) public {
+        balances[account] += amount;
+        total += amount;
+    }
+
+    function record(int128 delta) public {
+        deltas.push(delta);
+    }
+}
+
+contract DecodedStateDiffTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    Vault vault;
+    address alice = address(0xa11ce);
+
+    function setUp() public {
+        vault = new Vault();
+    }
+
+    function find(Vm.DecodedStorageChange[] memory changes, string memory label)
+        internal
+        pure
+        returns (Vm.DecodedStorageChange memory)
+    {
+        for (uint256 i = 0; i < changes.length; i++) {
+            if (keccak256(bytes(changes[i].label)) == keccak256(bytes(label))) {
+                return changes[i];
+            }
+        }
+        revert(string.concat("no change of ", label));
+    }
+
+    function testDecodesStateDiff() public {
+        vm.startStateDiffRecording();
+        vault.setPaused(true);
+        vault.setFee(30);
+        vault.deposit(alice, 100);
+        vault.record(-5);
+        Vm.DecodedStorageChange[] memory changes = vm.stopAndReturnDecodedStateDiff();
+
+        // `owner` shares its slot with `paused` but is unchanged
+        assertEq(changes.length, 6);
+
+        Vm.DecodedStorageChange memory paused = find(changes, "paused");
+        assertEq(paused.account, address(vault));
+        assertEq(paused.contractName, "DecodedStateDiff.t.sol:Vault");
+        assertEq(paused.slot, bytes32(0));
+        assertEq(paused.typeName, "bool");
+        assertEq(paused.decodedPreviousValue, "false");
+        assertEq(paused.decodedNewValue, "true");
+
+        Vm.DecodedStorageChange memory fee = find(changes, "config.fee");
+        assertEq(fee.slot, bytes32(uint256(2)));
+        assertEq(fee.typeName, "uint256");
+        assertEq(fee.decodedPreviousValue, "0");
+        assertEq(fee.decodedNewValue, "30");
+
+        Vm.DecodedStorageChange memory balance =
+            find(changes, string.concat("balances[", vm.toString(alice), "]"));
+        assertEq(balance.slot, keccak256(abi.encode(alice, uint256(4))));
+        assertEq(balance.decodedNewValue, "100");
+
+        assertEq(find(changes, "total").decodedNewValue, "100");
+        assertEq(find(changes, "deltas").decodedNewValue, "1");
+
+        Vm.DecodedStorageChange memory delta = find(changes, "deltas[0]");
+        assertEq(delta.typeName, "int128");
+        assertEq(delta.decodedNewValue, "-5");
+    }
+
+    function testRevertedWritesAreIgnored() public {
+        vm.startStateDiffRecording();
+        vault.setFee(30);
+        try this.setFeeAndRevert() {} catch {}
+        vault.setFee(0);
+        Vm.DecodedStorageChange[] memory changes = vm.stopAndReturnDecodedStateDiff();
+        assertEq(changes.length, 0);
+    }
+
+    function setFeeAndRevert() public {
+        vault.setFee(50);
+        revert();
+    }
+}
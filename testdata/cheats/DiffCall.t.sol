@@ -0,0 +1,102 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract SumReference {
+    event Summed(uint256 total);
+
+    uint256 public total;
+
+    function sum(uint256 n) public returns (uint256) {
+        uint256 result;
+        for (uint256 i = 1; i <= n; i++) {
+            result += i;
+        }
+        total = result;
+        emit Summed(result);
+        return result;
+    }
+}
+
+contract SumOptimized {
+    event Summed(uint256 total);
+
+    uint256 public total;
+
+    function sum(uint256 n) public returns (uint256) {
+        uint256 result = n * (n + 1) / 2;
+        total = result;
+        emit Summed(result);
+        return result;
+    }
+}
+
+contract SumBroken {
+    uint256 public total;
+
+    function sum(uint256 n) public returns (uint256) {
+        require(n < 10, "too large");
+        total = n;
+        return n;
+    }
+}
+
+contract DiffCallTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    SumReference slow;
+    SumOptimized optimized;
+    SumBroken broken;
+
+    function setUp() public {
+        slow = new SumReference();
+        optimized = new SumOptimized();
+        broken = new SumBroken();
+    }
+
+    function testDiffCallMatches() public {
+        bytes memory data = abi.encodeCall(SumReference.sum, (100));
+        (Vm.DiffCallResult memory a, Vm.DiffCallResult memory b) =
+            vm.diffCall(address(slow), address(optimized), data);
+
+        assertTrue(a.success);
+        assertTrue(b.success);
+        assertEq(a.returnData, b.returnData);
+        assertEq(abi.decode(a.returnData, (uint256)), 5050);
+
+        assertEq(a.logs.length, 1);
+        assertEq(b.logs.length, 1);
+        assertEq(a.logs[0].emitter, address(slow));
+        assertEq(b.logs[0].emitter, address(optimized));
+        assertEq(a.logs[0].topics[0], b.logs[0].topics[0]);
+        assertEq(a.logs[0].data, b.logs[0].data);
+
+        assertEq(a.storageChanges.length, 1);
+        assertEq(b.storageChanges.length, 1);
+        assertEq(a.storageChanges[0].account, address(slow));
+        assertEq(a.storageChanges[0].slot, b.storageChanges[0].slot);
+        assertEq(a.storageChanges[0].previousValue, bytes32(0));
+        assertEq(a.storageChanges[0].newValue, b.storageChanges[0].newValue);
+        assertEq(uint256(a.storageChanges[0].newValue), 5050);
+    }
+
+    function testDiffCallDoesNotCommit() public {
+        vm.diffCall(address(slow), address(optimized), abi.encodeCall(SumReference.sum, (10)));
+        assertEq(slow.total(), 0);
+        assertEq(optimized.total(), 0);
+    }
+
+    function testDiffCallReportsReverts() public {
+        bytes memory data = abi.encodeCall(SumReference.sum, (10));
+        (Vm.DiffCallResult memory a, Vm.DiffCallResult memory b) =
+            vm.diffCall(address(slow), address(broken), data);
+
+        assertTrue(a.success);
+        assertTrue(!b.success);
+        assertEq(b.returnData, abi.encodeWithSignature("Error(string)", "too large"));
+        assertEq(b.logs.length, 0);
+        assertEq(b.storageChanges.length, 0);
+    }
+}
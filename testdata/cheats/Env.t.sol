@@ -1000,4 +1000,9 @@ contract EnvTest is DSTest {
             );
         }
     }
+
+    function testGetSecretEmptyKey() public {
+        vm._expectCheatcodeRevert("secret key can't be empty");
+        vm.getSecret("");
+    }
 }
@@ -242,6 +242,20 @@ contract ExpectCallTest is DSTest {
         this.exposed_addHardGasLimit(target);
     }
 
+    function testExpectCallWithMaxGas() public {
+        Contract inner = new Contract();
+        NestedContract target = new NestedContract(inner);
+        vm.expectCallMaxGas(address(inner), abi.encodeWithSelector(inner.add.selector, 1, 1), 50_000);
+        this.exposed_addHardGasLimit(target);
+    }
+
+    function testFailExpectCallWithWrongMaxGas() public {
+        Contract inner = new Contract();
+        NestedContract target = new NestedContract(inner);
+        vm.expectCallMaxGas(address(inner), abi.encodeWithSelector(inner.add.selector, 1, 1), 49_999);
+        this.exposed_addHardGasLimit(target);
+    }
+
     /// Ensure that you cannot use expectCall with an expectRevert.
     function testFailExpectCallWithRevertDisallowed() public {
         Contract target = new Contract();
@@ -411,6 +425,20 @@ contract ExpectCallCountTest is DSTest {
         vm.expectCallMinGas(address(inner), 0, 50_001, abi.encodeWithSelector(inner.add.selector, 1, 1), 1);
         this.exposed_addHardGasLimit(target, 1);
     }
+
+    function testExpectCallCountWithMaxGas() public {
+        Contract inner = new Contract();
+        NestedContract target = new NestedContract(inner);
+        vm.expectCallMaxGas(address(inner), abi.encodeWithSelector(inner.add.selector, 1, 1), 50_000, 2);
+        this.exposed_addHardGasLimit(target, 2);
+    }
+
+    function testFailExpectCallCountWithWrongMaxGas() public {
+        Contract inner = new Contract();
+        NestedContract target = new NestedContract(inner);
+        vm.expectCallMaxGas(address(inner), abi.encodeWithSelector(inner.add.selector, 1, 1), 49_999, 2);
+        this.exposed_addHardGasLimit(target, 2);
+    }
 }
 
 contract ExpectCallMixedTest is DSTest {
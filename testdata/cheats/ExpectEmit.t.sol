@@ -24,10 +24,18 @@ contract Emitter {
 
     event SomethingNonIndexed(uint256 data);
 
+    event SomethingAnonymous(
+        uint256 indexed topic0, uint256 indexed topic1, uint256 indexed topic2, uint256 indexed topic3, uint256 data
+    ) anonymous;
+
     function emitEvent(uint256 topic1, uint256 topic2, uint256 topic3, uint256 data) public {
         emit Something(topic1, topic2, topic3, data);
     }
 
+    function emitAnonymousEvent(uint256 topic0, uint256 topic1, uint256 topic2, uint256 topic3, uint256 data) public {
+        emit SomethingAnonymous(topic0, topic1, topic2, topic3, data);
+    }
+
     function emitMultiple(
         uint256[2] memory topic1,
         uint256[2] memory topic2,
@@ -119,6 +127,10 @@ contract ExpectEmitTest is DSTest {
 
     event SomethingNonIndexed(uint256 data);
 
+    event SomethingAnonymous(
+        uint256 indexed topic0, uint256 indexed topic1, uint256 indexed topic2, uint256 indexed topic3, uint256 data
+    ) anonymous;
+
     event A(uint256 indexed topic1);
     event B(uint256 indexed topic1);
     event C(uint256 indexed topic1);
@@ -331,6 +343,74 @@ contract ExpectEmitTest is DSTest {
         emitter.emitEvent(1, 2, 3, 4);
     }
 
+    /// The topics that are not checked are altered to be incorrect
+    /// compared to the reference.
+    function testExpectEmitAnonymous(
+        bool checkTopic0,
+        bool checkTopic1,
+        bool checkTopic2,
+        bool checkTopic3,
+        bool checkData,
+        uint128 value
+    ) public {
+        vm.expectEmitAnonymous(checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData);
+
+        emit SomethingAnonymous(value, value, value, value, value);
+        emitter.emitAnonymousEvent(
+            checkTopic0 ? uint256(value) : uint256(value) + 1,
+            checkTopic1 ? uint256(value) : uint256(value) + 1,
+            checkTopic2 ? uint256(value) : uint256(value) + 1,
+            checkTopic3 ? uint256(value) : uint256(value) + 1,
+            checkData ? uint256(value) : uint256(value) + 1
+        );
+    }
+
+    /// The topics that are checked are altered to be incorrect
+    /// compared to the reference.
+    function testFailExpectEmitAnonymous(
+        bool checkTopic0,
+        bool checkTopic1,
+        bool checkTopic2,
+        bool checkTopic3,
+        bool checkData,
+        uint128 value
+    ) public {
+        vm.assume(checkTopic0 || checkTopic1 || checkTopic2 || checkTopic3 || checkData);
+
+        vm.expectEmitAnonymous(checkTopic0, checkTopic1, checkTopic2, checkTopic3, checkData);
+
+        emit SomethingAnonymous(value, value, value, value, value);
+        emitter.emitAnonymousEvent(
+            checkTopic0 ? uint256(value) + 1 : uint256(value),
+            checkTopic1 ? uint256(value) + 1 : uint256(value),
+            checkTopic2 ? uint256(value) + 1 : uint256(value),
+            checkTopic3 ? uint256(value) + 1 : uint256(value),
+            checkData ? uint256(value) + 1 : uint256(value)
+        );
+    }
+
+    function testExpectEmitAnonymousAddress() public {
+        vm.expectEmitAnonymous(address(emitter));
+        emit SomethingAnonymous(1, 2, 3, 4, 5);
+
+        emitter.emitAnonymousEvent(1, 2, 3, 4, 5);
+    }
+
+    function testFailExpectEmitAnonymousAddress() public {
+        vm.expectEmitAnonymous(address(0));
+        emit SomethingAnonymous(1, 2, 3, 4, 5);
+
+        emitter.emitAnonymousEvent(1, 2, 3, 4, 5);
+    }
+
+    function testFailExpectEmitAnonymousWithSignatureCheck() public {
+        // A non-anonymous expectation always matches topic 0 as the event signature.
+        vm.expectEmit(false, false, false, false);
+        emit SomethingAnonymous(1, 2, 3, 4, 5);
+
+        emitter.emitAnonymousEvent(2, 2, 3, 4, 5);
+    }
+
     /// Ref: issue #760
     function testFailLowLevelWithoutEmit() public {
         LowLevelCaller caller = new LowLevelCaller();
@@ -578,6 +658,54 @@ contract ExpectEmitTest is DSTest {
         emitter.emitWindow();
     }
 
+    /// emitWindow() emits events A, B, C, D, E.
+    /// In unordered mode, we should be able to match [E, C, A].
+    function testExpectEmitUnordered() public {
+        vm.expectEmitUnordered();
+        vm.expectEmit(true, false, false, true);
+        emit E(5);
+        vm.expectEmit(true, false, false, true);
+        emit C(3);
+        vm.expectEmit(true, false, false, true);
+        emit A(1);
+
+        emitter.emitWindow();
+    }
+
+    function testFailExpectEmitUnorderedMissingEvent() public {
+        vm.expectEmitUnordered();
+        vm.expectEmit(true, false, false, true);
+        emit E(5);
+        vm.expectEmit(true, false, false, true);
+        emit A(2);
+
+        emitter.emitWindow();
+    }
+
+    /// Each emitted log can only match a single expected log.
+    function testFailExpectEmitUnorderedRepeatedEvent() public {
+        vm.expectEmitUnordered();
+        vm.expectEmit(true, false, false, true);
+        emit A(1);
+        vm.expectEmit(true, false, false, true);
+        emit A(1);
+
+        emitter.emitWindow();
+    }
+
+    function testFailExpectEmitUnorderedOnlyAppliesToNextCall() public {
+        vm.expectEmitUnordered();
+        vm.expectEmit(true, false, false, true);
+        emit A(1);
+        emitter.emitWindow();
+
+        vm.expectEmit(true, false, false, true);
+        emit E(5);
+        vm.expectEmit(true, false, false, true);
+        emit A(1);
+        emitter.emitWindow();
+    }
+
     /// This test will fail if we check that all expected logs were emitted
     /// after every call from the same depth as the call that invoked the cheatcode.
     ///
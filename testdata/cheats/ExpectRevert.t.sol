@@ -6,6 +6,7 @@ import "./Vm.sol";
 
 contract Reverter {
     error CustomError();
+    error CustomErrorWithValue(uint256 value);
 
     function revertWithMessage(string memory message) public pure {
         revert(message);
@@ -21,6 +22,14 @@ contract Reverter {
         revert CustomError();
     }
 
+    function revertWithCustomErrorValue(uint256 value) public pure {
+        revert CustomErrorWithValue(value);
+    }
+
+    function nestedRevertWithCustomErrorValue(Reverter inner, uint256 value) public pure {
+        inner.revertWithCustomErrorValue(value);
+    }
+
     function nestedRevert(Reverter inner, string memory message) public pure {
         inner.revertWithMessage(message);
     }
@@ -113,6 +122,70 @@ contract ExpectRevertTest is DSTest {
         reverter.revertWithCustomError();
     }
 
+    function testExpectRevertCount() public {
+        Reverter reverter = new Reverter();
+        vm.expectRevert(Reverter.CustomError.selector, 3);
+        reverter.revertWithCustomError();
+        reverter.revertWithCustomError();
+        reverter.revertWithCustomError();
+    }
+
+    function testExpectRevertCountWithArguments() public {
+        Reverter reverter = new Reverter();
+        Reverter inner = new Reverter();
+        vm.expectRevert(Reverter.CustomErrorWithValue.selector, 3);
+        reverter.revertWithCustomErrorValue(1);
+        reverter.revertWithCustomErrorValue(2);
+        // the revert of a nested call counts once, as the revert of the call made by the test
+        reverter.nestedRevertWithCustomErrorValue(inner, 3);
+    }
+
+    function testFailExpectRevertCountWrongSelector() public {
+        Reverter reverter = new Reverter();
+        vm.expectRevert(Reverter.CustomErrorWithValue.selector, 2);
+        reverter.revertWithCustomErrorValue(1);
+        reverter.revertWithCustomError();
+    }
+
+    function testFailExpectRevertCountTooFewReverts() public {
+        Reverter reverter = new Reverter();
+        vm.expectRevert(Reverter.CustomError.selector, 2);
+        reverter.revertWithCustomError();
+        reverter.doNotRevert();
+    }
+
+    function testFailExpectRevertCountMissingReverts() public {
+        Reverter reverter = new Reverter();
+        vm.expectRevert(Reverter.CustomError.selector, 2);
+        reverter.revertWithCustomError();
+    }
+
+    function testExpectPartialRevert() public {
+        Reverter reverter = new Reverter();
+        vm.expectPartialRevert(Reverter.CustomErrorWithValue.selector);
+        reverter.revertWithCustomErrorValue(42);
+    }
+
+    function testFailExpectPartialRevertWrongSelector() public {
+        Reverter reverter = new Reverter();
+        vm.expectPartialRevert(Reverter.CustomError.selector);
+        reverter.revertWithCustomErrorValue(42);
+    }
+
+    function testExpectPartialRevertWithReverter() public {
+        Reverter reverter = new Reverter();
+        Reverter inner = new Reverter();
+        vm.expectPartialRevert(Reverter.CustomErrorWithValue.selector, address(inner));
+        reverter.nestedRevertWithCustomErrorValue(inner, 42);
+    }
+
+    function testFailExpectPartialRevertWrongReverter() public {
+        Reverter reverter = new Reverter();
+        Reverter inner = new Reverter();
+        vm.expectPartialRevert(Reverter.CustomErrorWithValue.selector, address(reverter));
+        reverter.nestedRevertWithCustomErrorValue(inner, 42);
+    }
+
     function testExpectRevertNested() public {
         Reverter reverter = new Reverter();
         Reverter inner = new Reverter();
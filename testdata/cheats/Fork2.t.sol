@@ -125,6 +125,24 @@ contract ForkTest is DSTest {
         assert(account.balance < newBalance);
     }
 
+    // test that we can "roll" blocks until a transaction index
+    function testCanRollForkUntilTransactionIndex() public {
+        // block to run transactions from
+        uint256 blockNumber = 16261704;
+
+        // fork the next block to get the hash of the block
+        vm.createSelectFork("rpcAlias", blockNumber + 1);
+        bytes32 hash = blockhash(blockNumber);
+
+        // account that sends ether in 2 transaction of the block
+        address account = 0xAe45a8240147E6179ec7c9f92c5A18F9a97B3fCA;
+
+        // no transactions of the block are replayed
+        vm.rollFork(hash, 0);
+        assertEq(block.number, blockNumber);
+        assertEq(account.balance, 275780074926400862972);
+    }
+
     /// checks that marking as persistent works
     function testMarkPersistent() public {
         assert(vm.isPersistent(address(this)));
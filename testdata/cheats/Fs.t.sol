@@ -176,6 +176,48 @@ contract FsTest is DSTest {
         vm.readDir("/etc");
     }
 
+    function assertFixturePath(string memory path, string memory expected) private {
+        string[] memory parts = vm.split(path, "fixtures/");
+        assertEq(parts[parts.length - 1], expected);
+    }
+
+    function testReadDirRecursive() public {
+        string memory path = "fixtures/Dir";
+
+        string[] memory paths = vm.readDirRecursive(path, 1);
+        assertEq(paths.length, 1);
+        assertFixturePath(paths[0], "Dir/depth1");
+
+        paths = vm.readDirRecursive(path, 0);
+        assertEq(paths.length, 3);
+        assertFixturePath(paths[0], "Dir/depth1");
+        assertFixturePath(paths[1], "Dir/nested/depth2");
+        assertFixturePath(paths[2], "Dir/nested/nested2/depth3");
+
+        vm._expectCheatcodeRevert(FOUNDRY_READ_DIR_ERR);
+        vm.readDirRecursive("/etc", 0);
+    }
+
+    function testGlob() public {
+        string[] memory paths = vm.glob("fixtures/Dir/**/depth*");
+        assertEq(paths.length, 3);
+        assertFixturePath(paths[1], "Dir/nested/depth2");
+
+        paths = vm.glob("fixtures/Dir/*/depth*");
+        assertEq(paths.length, 1);
+        assertFixturePath(paths[0], "Dir/nested/depth2");
+
+        paths = vm.glob("fixtures/Table/*.{json,toml}");
+        assertEq(paths.length, 2);
+        assertFixturePath(paths[0], "Table/swap_cases.json");
+
+        paths = vm.glob("fixtures/Dir/depth1");
+        assertEq(paths.length, 1);
+
+        vm._expectCheatcodeRevert(FOUNDRY_READ_DIR_ERR);
+        vm.glob("/etc/*");
+    }
+
     function testCreateRemoveDir() public {
         string memory path = "fixtures/Dir/remove_dir";
         string memory child = string.concat(path, "/child");
@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract FuzzConfigTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function setUp() public {
+        vm.setFuzzRuns(10);
+        vm.setMaxRejects(1_000_000);
+    }
+
+    function testFuzzConfigOverrides(uint16 x) public {
+        vm.assume(x < 16);
+        assertLt(x, 16);
+    }
+
+    /// forge-config: default.fuzz.runs = 500
+    function testFuzzConfigOverridesInlineConfig(uint16 x) public {
+        assertEq(x, x);
+    }
+
+    function testFailSetFuzzRunsZero() public {
+        vm.setFuzzRuns(0);
+    }
+
+    function testFailSetFuzzRunsTooLarge() public {
+        vm.setFuzzRuns(uint256(type(uint32).max) + 1);
+    }
+}
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract FuzzFixtureTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    bytes32 constant POOL_ID = keccak256("pool");
+    int256 constant MIN_TICK = -887272;
+
+    function setUp() public {
+        vm.addFuzzFixture(POOL_ID);
+        vm.addFuzzFixture(uint256(1e18));
+        vm.addFuzzFixture(MIN_TICK);
+        vm.addFuzzFixture(address(0xdead));
+    }
+
+    function testAddFuzzFixture(bytes32 poolId, int24 tick) public {
+        // The fixtures are only sampled more often, any input is still valid
+        if (poolId == POOL_ID) {
+            assertEq(poolId, keccak256("pool"));
+        }
+        assertGe(tick, type(int24).min);
+    }
+
+    function testAddFuzzFixtureOutsideSetUp() public {
+        vm.addFuzzFixture(bytes32(uint256(1)));
+    }
+}
+
+contract FuzzFixtureNeedleTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    bytes32 constant POOL_ID = keccak256("pool");
+
+    function setUp() public {
+        vm.addFuzzFixture(POOL_ID);
+    }
+
+    // Random inputs can't realistically find the pool ID, so this only fails if the fixture is used
+    function testFuzzFixtureNeedle(bytes32 poolId) public {
+        assertTrue(poolId != POOL_ID);
+    }
+}
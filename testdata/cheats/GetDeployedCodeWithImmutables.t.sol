@@ -0,0 +1,56 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ImmutableHolder {
+    uint256 public immutable amount;
+    address public immutable token;
+    bytes4 public immutable selector;
+
+    constructor(uint256 _amount, address _token, bytes4 _selector) {
+        amount = _amount;
+        token = _token;
+        selector = _selector;
+    }
+
+    function total() public view returns (uint256) {
+        return amount * 2;
+    }
+}
+
+contract GetDeployedCodeWithImmutablesTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant ARTIFACT = "GetDeployedCodeWithImmutables.t.sol:ImmutableHolder";
+
+    function values(uint256 amount, address token, bytes4 selector) internal pure returns (bytes[] memory) {
+        bytes[] memory result = new bytes[](3);
+        result[0] = abi.encode(amount);
+        result[1] = abi.encode(token);
+        result[2] = abi.encode(selector);
+        return result;
+    }
+
+    function testMatchesDeployedCode() public {
+        ImmutableHolder holder = new ImmutableHolder(7, address(0xbeef), 0x12345678);
+        bytes memory code = vm.getDeployedCodeWithImmutables(ARTIFACT, values(7, address(0xbeef), 0x12345678));
+        assertEq(code, address(holder).code);
+    }
+
+    function testEtchWithImmutables() public {
+        address target = address(0x1234);
+        vm.etch(target, vm.getDeployedCodeWithImmutables(ARTIFACT, values(21, address(this), 0xdeadbeef)));
+        ImmutableHolder holder = ImmutableHolder(target);
+        assertEq(holder.amount(), 21);
+        assertEq(holder.total(), 42);
+        assertEq(holder.token(), address(this));
+        assertEq(holder.selector(), bytes4(0xdeadbeef));
+    }
+
+    function testWrongNumberOfValues() public {
+        vm._expectCheatcodeRevert("expected 3 immutable values, got 0");
+        vm.getDeployedCodeWithImmutables(ARTIFACT, new bytes[](0));
+    }
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract GetDeploymentTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant DIR = "deployments/31337";
+
+    function testGetDeployment() public {
+        vm.createDir(DIR, true);
+        vm.writeFile(
+            string.concat(DIR, "/Counter.json"),
+            '{"contractName":"Counter","address":"0x5fbdb2315678afecb367f032d93f642f64180aa3"}'
+        );
+        assertEq(vm.getDeployment("Counter", 31337), 0x5FbDB2315678afecb367f032d93F642f64180aa3);
+        vm.removeDir("deployments", true);
+    }
+
+    function testGetMissingDeployment() public {
+        vm._expectCheatcodeRevert();
+        vm.getDeployment("Missing", 1);
+    }
+}
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract HttpTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testHttpGetNotAllowed() public {
+        vm._expectCheatcodeRevert(
+            "the URL https://example.com/prices is not allowed to be requested; add it to `http_permissions` to allow it"
+        );
+        vm.httpGet("https://example.com/prices");
+    }
+
+    function testHttpPostNotAllowed() public {
+        string[] memory headers = new string[](1);
+        headers[0] = "Content-Type: application/json";
+        vm._expectCheatcodeRevert(
+            "the URL https://example.com/proofs is not allowed to be requested; add it to `http_permissions` to allow it"
+        );
+        vm.httpPost("https://example.com/proofs", "{}", headers);
+    }
+}
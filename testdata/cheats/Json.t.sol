@@ -331,3 +331,48 @@ contract WriteJsonTest is DSTest {
         assertEq(decodedAddress, ex);
     }
 }
+
+contract MergePatchJsonTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant REGISTRY =
+        '{"chainId":1,"contracts":{"token":{"address":"0x0000000000000000000000000000000000000001","version":1},"oracle":{"address":"0x0000000000000000000000000000000000000002"}},"admins":["alice"]}';
+
+    function test_mergeJson() public {
+        string memory merged = vm.mergeJson(
+            REGISTRY, '{"contracts":{"token":{"version":2},"oracle":null,"vault":{"address":"0x0000000000000000000000000000000000000003"}}}'
+        );
+        assertEq(vm.parseJsonUint(merged, ".chainId"), 1);
+        assertEq(vm.parseJsonAddress(merged, ".contracts.token.address"), address(1));
+        assertEq(vm.parseJsonUint(merged, ".contracts.token.version"), 2);
+        assertEq(vm.parseJsonAddress(merged, ".contracts.vault.address"), address(3));
+        assertTrue(!vm.keyExists(merged, ".contracts.oracle"));
+
+        // Values which are not objects replace the document
+        assertEq(vm.mergeJson(REGISTRY, "[1,2]"), "[1,2]");
+    }
+
+    function test_patchJson() public {
+        string memory patched = vm.patchJson(
+            REGISTRY,
+            '[{"op":"test","path":"/chainId","value":1},{"op":"replace","path":"/contracts/token/version","value":2},{"op":"add","path":"/admins/-","value":"bob"},{"op":"move","from":"/contracts/oracle","path":"/contracts/feed"},{"op":"copy","from":"/contracts/token/address","path":"/owner"},{"op":"remove","path":"/admins/0"}]'
+        );
+        assertEq(vm.parseJsonUint(patched, ".contracts.token.version"), 2);
+        assertEq(vm.parseJsonAddress(patched, ".contracts.feed.address"), address(2));
+        assertTrue(!vm.keyExists(patched, ".contracts.oracle"));
+        assertEq(vm.parseJsonAddress(patched, ".owner"), address(1));
+        string[] memory admins = vm.parseJsonStringArray(patched, ".admins");
+        assertEq(admins.length, 1);
+        assertEq(admins[0], "bob");
+    }
+
+    function test_patchJsonFailedTest() public {
+        vm._expectCheatcodeRevert("failed applying JSON patch operation 0: value at \"/chainId\" is not 2");
+        vm.patchJson(REGISTRY, '[{"op":"test","path":"/chainId","value":2}]');
+    }
+
+    function test_patchJsonMissingPath() public {
+        vm._expectCheatcodeRevert("failed applying JSON patch operation 0: path \"/missing\" does not exist");
+        vm.patchJson(REGISTRY, '[{"op":"remove","path":"/missing"}]');
+    }
+}
@@ -0,0 +1,54 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract KzgTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    uint256 constant BYTES_PER_BLOB = 131072;
+
+    function blob() internal pure returns (bytes memory data) {
+        data = new bytes(BYTES_PER_BLOB);
+        // every 32 byte field element must be smaller than the BLS modulus
+        for (uint256 i = 0; i < 16; i++) {
+            data[i * 32 + 31] = bytes1(uint8(i + 1));
+        }
+    }
+
+    function testZeroBlobCommitment() public {
+        bytes memory commitment = vm.blobToKzgCommitment(new bytes(BYTES_PER_BLOB));
+        bytes memory infinity = new bytes(48);
+        infinity[0] = 0xc0;
+        assertEq(commitment, infinity);
+        assertEq(
+            vm.kzgToVersionedHash(commitment), 0x010657f37554c781402a22917dee2f75def7ab966d7b770905398eba3c444014
+        );
+    }
+
+    function testKzgProof() public {
+        bytes memory data = blob();
+        bytes memory commitment = vm.blobToKzgCommitment(data);
+        bytes32 z = bytes32(uint256(42));
+        (bytes memory proof, bytes32 y) = vm.computeKzgProof(data, z);
+
+        assertTrue(vm.verifyKzgProof(commitment, z, y, proof));
+        assertTrue(!vm.verifyKzgProof(commitment, z, bytes32(uint256(y) + 1), proof));
+    }
+
+    function testBlobKzgProof() public {
+        bytes memory data = blob();
+        bytes memory commitment = vm.blobToKzgCommitment(data);
+        bytes memory proof = vm.computeBlobKzgProof(data, commitment);
+
+        assertTrue(vm.verifyBlobKzgProof(data, commitment, proof));
+        data[31] = 0x42;
+        assertTrue(!vm.verifyBlobKzgProof(data, commitment, proof));
+    }
+
+    function testInvalidBlobLength() public {
+        vm._expectCheatcodeRevert("invalid blob length: expected 131072 bytes, got 32");
+        vm.blobToKzgCommitment(new bytes(32));
+    }
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract L1BlockNumberTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testL1BlockNumberNotL2() public {
+        vm._expectCheatcodeRevert("chain 31337 is not a known OP Stack or Arbitrum chain");
+        vm.l1BlockNumber();
+    }
+
+    function testL1BlockNumberOpStack() public {
+        vm.chainId(10);
+        // `L1Block.number` shares its slot with `L1Block.timestamp`
+        vm.store(address(0x4200000000000000000000000000000000000015), 0, bytes32(uint256(1700000000 << 64 | 18000000)));
+        assertEq(vm.l1BlockNumber(), 18000000);
+    }
+
+    function testL1BlockNumberArbitrum() public {
+        vm.chainId(42161);
+        vm.roll(19000000);
+        assertEq(vm.l1BlockNumber(), 19000000);
+    }
+}
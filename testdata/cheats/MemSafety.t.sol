@@ -670,6 +670,101 @@ contract MemSafetyTest is DSTest {
         }
     }
 
+    ////////////////////////////////////////////////////////////////
+    //              `expectSafeMemoryDeep` cheatcode              //
+    ////////////////////////////////////////////////////////////////
+
+    /// @dev Tests that the `expectSafeMemoryDeep` cheatcode allows writes within the range in
+    ///      delegatecalled contexts.
+    function testExpectSafeMemoryDeep() public {
+        // Create a new SubContext contract
+        SubContext sc = new SubContext();
+        // Create a payload to call `doMstore` on the SubContext contract
+        bytes memory payload = abi.encodeWithSelector(SubContext.doMstore.selector, 0x80, 0xc0ffee);
+
+        // Allow memory writes in the range of [0x80, 0xA0) within this context and delegatecalls
+        vm.expectSafeMemoryDeep(0x80, 0xA0);
+
+        // Should not revert- the memory write in the delegatecalled context is within the allowed range.
+        if (!_doDelegateCall(address(sc), payload)) {
+            revert("Expected call to succeed");
+        }
+    }
+
+    /// @dev Tests that the `expectSafeMemoryDeep` cheatcode checks writes in delegatecalled contexts.
+    function testFailExpectSafeMemoryDeep() public {
+        // Create a new SubContext contract
+        SubContext sc = new SubContext();
+        // Create a payload to call `doMstore` on the SubContext contract
+        bytes memory payload = abi.encodeWithSelector(SubContext.doMstore.selector, 0xA0, 0xc0ffee);
+
+        // Allow memory writes in the range of [0x80, 0xA0) within this context and delegatecalls
+        vm.expectSafeMemoryDeep(0x80, 0xA0);
+
+        // Should revert. The memory write in the delegatecalled context is outside of the allowed range.
+        if (!_doDelegateCall(address(sc), payload)) {
+            revert("Expected call to fail");
+        }
+    }
+
+    /// @dev Tests that the `expectSafeMemoryDeep` cheatcode does not apply to regular calls.
+    function testExpectSafeMemoryDeep_respectsCallScheme() public {
+        // Create a new SubContext contract
+        SubContext sc = new SubContext();
+        // Create a payload to call `doMstore` on the SubContext contract
+        bytes memory payload = abi.encodeWithSelector(SubContext.doMstore.selector, 0xA0, 0xc0ffee);
+
+        // Allow memory writes in the range of [0x80, 0xA0) within this context and delegatecalls
+        vm.expectSafeMemoryDeep(0x80, 0xA0);
+
+        // Should not revert- the subcontext is not a delegatecall.
+        if (!_doCall(address(sc), payload)) {
+            revert("Expected call to succeed");
+        }
+    }
+
+    /// @dev Tests that the `expectSafeMemoryDeep` cheatcode keeps the ranges allowed by
+    ///      `expectSafeMemoryCall` in delegatecalled contexts.
+    function testExpectSafeMemoryDeep_withExpectSafeMemoryCall() public {
+        // Create a new SubContext contract
+        SubContext sc = new SubContext();
+        // Create a payload to call `doMstore` on the SubContext contract
+        bytes memory payload = abi.encodeWithSelector(SubContext.doMstore.selector, 0xA0, 0xc0ffee);
+
+        // Allow memory writes in the range of [0xA0, 0xC0) within the next created subcontext, and
+        // in the range of [0x80, 0xA0) within this context and delegatecalls
+        vm.expectSafeMemoryCall(0xA0, 0xC0);
+        vm.expectSafeMemoryDeep(0x80, 0xA0);
+
+        // Should not revert- the memory write is within the range allowed by `expectSafeMemoryCall`.
+        if (!_doDelegateCall(address(sc), payload)) {
+            revert("Expected call to succeed");
+        }
+    }
+
+    /// @dev Tests that the ranges allowed by `expectSafeMemoryCall` are restored after a
+    ///      delegatecall the deep ranges were propagated into.
+    function testFailExpectSafeMemoryDeep_restoresExpectSafeMemoryCall() public {
+        // Create a new SubContext contract
+        SubContext sc = new SubContext();
+        // Create a payload to call `doMstore` on the SubContext contract
+        bytes memory payload = abi.encodeWithSelector(SubContext.doMstore.selector, 0x80, 0xc0ffee);
+
+        // Allow memory writes in the range of [0xA0, 0xC0) within the next created subcontext, and
+        // in the range of [0x80, 0xA0) within this context and delegatecalls
+        vm.expectSafeMemoryCall(0xA0, 0xC0);
+        vm.expectSafeMemoryDeep(0x80, 0xA0);
+
+        // The memory write in the delegatecalled context is within the deep range.
+        _doDelegateCall(address(sc), payload);
+
+        // Should revert. The memory write in this subcontext is outside of the range allowed by
+        // `expectSafeMemoryCall`, which no longer includes the deep range.
+        if (!_doCall(address(sc), payload)) {
+            revert("Expected call to fail");
+        }
+    }
+
     ////////////////////////////////////////////////////////////////
     //              `stopExpectSafeMemory` cheatcode              //
     ////////////////////////////////////////////////////////////////
@@ -722,6 +817,13 @@ contract MemSafetyTest is DSTest {
         }
     }
 
+    /// @dev Performs a delegatecall without copying any returndata.
+    function _doDelegateCall(address _target, bytes memory _payload) internal returns (bool _success) {
+        assembly {
+            _success := delegatecall(gas(), _target, add(_payload, 0x20), mload(_payload), 0x00, 0x00)
+        }
+    }
+
     /// @dev Performs a call and copies returndata to memory.
     function _doCallReturnData(address _target, bytes memory _payload, uint256 returnDataDest, uint256 returnDataSize)
         internal
@@ -768,7 +870,8 @@ contract MemSafetyTest is DSTest {
     }
 }
 
-/// @dev A simple contract for testing the `expectSafeMemory` & `expectSafeMemoryCall` cheatcodes.
+/// @dev A simple contract for testing the `expectSafeMemory`, `expectSafeMemoryCall` & `expectSafeMemoryDeep`
+///      cheatcodes.
 contract SubContext {
     function doMstore(uint256 offset, uint256 val) external {
         assembly {
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract MerkleTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function hashPair(bytes32 a, bytes32 b) internal pure returns (bytes32) {
+        return a < b ? keccak256(abi.encode(a, b)) : keccak256(abi.encode(b, a));
+    }
+
+    // OpenZeppelin's `MerkleProof.processProof`
+    function processProof(bytes32[] memory proof, bytes32 leaf) internal pure returns (bytes32 node) {
+        node = leaf;
+        for (uint256 i = 0; i < proof.length; i++) {
+            node = hashPair(node, proof[i]);
+        }
+    }
+
+    // OpenZeppelin's `MerkleProof.processMultiProof`
+    function processMultiProof(bytes32[] memory leaves, bytes32[] memory proof, bool[] memory proofFlags)
+        internal
+        pure
+        returns (bytes32)
+    {
+        uint256 totalHashes = proofFlags.length;
+        require(leaves.length + proof.length == totalHashes + 1, "invalid multiproof");
+        bytes32[] memory hashes = new bytes32[](totalHashes);
+        uint256 leafPos = 0;
+        uint256 hashPos = 0;
+        uint256 proofPos = 0;
+        for (uint256 i = 0; i < totalHashes; i++) {
+            bytes32 a = leafPos < leaves.length ? leaves[leafPos++] : hashes[hashPos++];
+            bytes32 b = proofFlags[i]
+                ? (leafPos < leaves.length ? leaves[leafPos++] : hashes[hashPos++])
+                : proof[proofPos++];
+            hashes[i] = hashPair(a, b);
+        }
+        if (totalHashes > 0) {
+            return hashes[totalHashes - 1];
+        } else if (leaves.length > 0) {
+            return leaves[0];
+        } else {
+            return proof[0];
+        }
+    }
+
+    function leaves() internal pure returns (bytes32[] memory result) {
+        result = new bytes32[](5);
+        for (uint256 i = 0; i < result.length; i++) {
+            result[i] = keccak256(bytes.concat(keccak256(abi.encode(address(uint160(i + 1)), (i + 1) * 1 ether))));
+        }
+    }
+
+    function testSortedRoot() public {
+        bytes32[] memory data = new bytes32[](3);
+        data[0] = bytes32(uint256(3));
+        data[1] = bytes32(uint256(1));
+        data[2] = bytes32(uint256(2));
+        bytes32 expected = hashPair(hashPair(data[0], data[1]), hashPair(data[2], bytes32(0)));
+        assertEq(vm.merkleRoot(data), expected);
+        assertEq(vm.merkleRoot(data, Vm.MerkleTreeKind.Sorted), expected);
+    }
+
+    function testOpenZeppelinRoot() public {
+        bytes32[] memory data = new bytes32[](3);
+        data[0] = bytes32(uint256(3));
+        data[1] = bytes32(uint256(1));
+        data[2] = bytes32(uint256(2));
+        bytes32 expected = hashPair(hashPair(bytes32(uint256(1)), bytes32(uint256(2))), bytes32(uint256(3)));
+        assertEq(vm.merkleRoot(data, Vm.MerkleTreeKind.OpenZeppelin), expected);
+    }
+
+    function testProofs() public {
+        bytes32[] memory data = leaves();
+        for (uint8 kind = 0; kind < 2; kind++) {
+            Vm.MerkleTreeKind treeKind = Vm.MerkleTreeKind(kind);
+            bytes32 root = vm.merkleRoot(data, treeKind);
+            for (uint256 i = 0; i < data.length; i++) {
+                assertEq(processProof(vm.merkleProof(data, i, treeKind), data[i]), root);
+            }
+        }
+    }
+
+    function testMultiProofs() public {
+        bytes32[] memory data = leaves();
+        uint256[] memory indices = new uint256[](3);
+        indices[0] = 4;
+        indices[1] = 0;
+        indices[2] = 1;
+        for (uint8 kind = 0; kind < 2; kind++) {
+            Vm.MerkleTreeKind treeKind = Vm.MerkleTreeKind(kind);
+            (bytes32[] memory proofLeaves, bytes32[] memory proof, bool[] memory proofFlags) =
+                vm.merkleMultiProof(data, indices, treeKind);
+            assertEq(proofLeaves.length, 3);
+            assertEq(processMultiProof(proofLeaves, proof, proofFlags), vm.merkleRoot(data, treeKind));
+        }
+    }
+
+    function testProofIndexOutOfBounds() public {
+        vm._expectCheatcodeRevert("leaf index 5 out of bounds of 5 leaves");
+        vm.merkleProof(leaves(), 5);
+    }
+
+    function testDuplicateMultiProofIndices() public {
+        uint256[] memory indices = new uint256[](2);
+        indices[0] = 1;
+        indices[1] = 1;
+        vm._expectCheatcodeRevert("duplicate leaf indices");
+        vm.merkleMultiProof(leaves(), indices);
+    }
+
+    function testNoLeaves() public {
+        vm._expectCheatcodeRevert("cannot build a Merkle tree without leaves");
+        vm.merkleRoot(new bytes32[](0));
+    }
+}
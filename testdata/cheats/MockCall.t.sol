@@ -155,6 +155,58 @@ contract MockCallTest is DSTest {
         assertEq(mock.add(1, 2), 10);
         mock.noReturnValue();
     }
+
+    function testMockCallMasked() public {
+        Mock mock = new Mock();
+
+        // Match any first argument, but only `2` as the second argument.
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(0), bytes32(type(uint256).max));
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 0, 2), mask, abi.encode(10));
+
+        assertEq(mock.add(1, 2), 10);
+        assertEq(mock.add(5, 2), 10);
+        assertEq(mock.add(1, 3), 4);
+    }
+
+    function testMockCallMaskedPrecedence() public {
+        Mock mock = new Mock();
+
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(0), bytes32(type(uint256).max));
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 0, 2), mask, abi.encode(10));
+        vm.mockCall(address(mock), abi.encodeWithSelector(mock.add.selector, 1, 2), abi.encode(20));
+
+        assertEq(mock.add(1, 2), 20);
+        assertEq(mock.add(5, 2), 10);
+    }
+
+    function testMockCallMaskedPrecedenceIgnoresMaskedBytes() public {
+        Mock mock = new Mock();
+
+        // The masked out first argument is larger than the one of the exact mock
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(0), bytes32(type(uint256).max));
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 9, 2), mask, abi.encode(10));
+        vm.mockCall(address(mock), abi.encodeWithSelector(mock.add.selector, 1, 2), abi.encode(20));
+
+        assertEq(mock.add(1, 2), 20);
+        assertEq(mock.add(5, 2), 10);
+    }
+
+    function testMockCallMaskedOverridesSameMask() public {
+        Mock mock = new Mock();
+
+        // Mocks differing only in masked out bytes are the same mock
+        bytes memory mask = abi.encodePacked(bytes4(0xffffffff), bytes32(0), bytes32(type(uint256).max));
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 1, 2), mask, abi.encode(10));
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 9, 2), mask, abi.encode(30));
+
+        assertEq(mock.add(1, 2), 30);
+        assertEq(mock.add(5, 2), 30);
+    }
+
+    function testFailMockCallMaskedWrongMaskLength() public {
+        Mock mock = new Mock();
+        vm.mockCallMasked(address(mock), abi.encodeWithSelector(mock.add.selector, 0, 2), hex"ffffffff", abi.encode(10));
+    }
 }
 
 contract MockCallRevertTest is DSTest {
@@ -295,3 +347,68 @@ contract MockCallRevertTest is DSTest {
         mock.add(1, 2);
     }
 }
+
+contract MockFunctionTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    uint256 payCalls;
+
+    function pay(uint256 a) public payable returns (uint256) {
+        payCalls++;
+        return a * payCalls;
+    }
+
+    function add(uint256 a, uint256 b) public pure returns (uint256) {
+        return a * b;
+    }
+
+    function testMockFunction() public {
+        Mock mock = new Mock();
+
+        vm.mockFunction(address(mock), address(this), abi.encodeWithSelector(Mock.pay.selector));
+
+        assertEq(mock.pay(10), 10);
+        assertEq(mock.pay(10), 20);
+        assertEq(payCalls, 2);
+    }
+
+    function testMockFunctionExactCalldata() public {
+        Mock mock = new Mock();
+
+        vm.mockFunction(address(mock), address(this), abi.encodeWithSelector(Mock.pay.selector, 10));
+
+        assertEq(mock.pay(10), 10);
+        assertEq(mock.pay(5), 5);
+        assertEq(mock.pay(10), 20);
+        assertEq(payCalls, 2);
+    }
+
+    function testMockFunctionValue() public {
+        Mock mock = new Mock();
+        vm.deal(address(this), 1 ether);
+
+        vm.mockFunction(address(mock), address(this), abi.encodeWithSelector(Mock.pay.selector));
+
+        assertEq(mock.pay{value: 1 ether}(10), 10);
+        assertEq(address(mock).balance, 0);
+        assertEq(address(this).balance, 1 ether);
+    }
+
+    function testMockFunctionEmptyAccount() public {
+        Mock mock = Mock(address(100));
+
+        vm.mockFunction(address(mock), address(this), abi.encodeWithSelector(Mock.add.selector));
+
+        assertEq(mock.add(2, 3), 6);
+    }
+
+    function testClearMockedFunctions() public {
+        Mock mock = new Mock();
+
+        vm.mockFunction(address(mock), address(this), abi.encodeWithSelector(Mock.add.selector));
+        assertEq(mock.add(2, 3), 6);
+
+        vm.clearMockedCalls();
+        assertEq(mock.add(2, 3), 5);
+    }
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract MockPrecompileTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testMockEcrecover() public {
+        bytes32 hash = keccak256("hello");
+        bytes32 r = bytes32(uint256(1));
+        bytes32 s = bytes32(uint256(2));
+        vm.mockPrecompile(address(1), abi.encode(hash, uint8(27), r, s), abi.encode(address(0xbeef)));
+        assertEq(ecrecover(hash, 27, r, s), address(0xbeef));
+    }
+
+    function testMockOnlyMatchingInput() public {
+        vm.mockPrecompile(address(2), "abc", abi.encode(bytes32(uint256(1))));
+        assertEq(sha256("abc"), bytes32(uint256(1)));
+        assertEq(sha256("abd"), 0xa52d159f262b2c6ddb724a61840befc36eb30c88877a4030b65cbe86298449c9);
+
+        vm.clearMockedCalls();
+        assertEq(sha256("abc"), 0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad);
+    }
+
+    function testDisablePrecompile() public {
+        vm.disablePrecompile(address(2));
+        (bool success, bytes memory output) = address(2).staticcall("abc");
+        assertTrue(success);
+        assertEq(output.length, 0);
+
+        // mocked outputs take precedence
+        vm.mockPrecompile(address(2), "abc", abi.encode(bytes32(uint256(1))));
+        assertEq(sha256("abc"), bytes32(uint256(1)));
+    }
+
+    function testMockNotPrecompile() public {
+        vm._expectCheatcodeRevert("0x000000000000000000000000000000000000bEEF is not a precompile");
+        vm.mockPrecompile(address(0xbeef), "", "");
+    }
+}
@@ -52,6 +52,18 @@ contract NestedVictim {
     }
 }
 
+contract VictimForwarder {
+    function forwardAssertCallerAndOrigin(
+        Victim victim,
+        address expectedSender,
+        string memory senderMessage,
+        address expectedOrigin,
+        string memory originMessage
+    ) public view {
+        victim.assertCallerAndOrigin(expectedSender, senderMessage, expectedOrigin, originMessage);
+    }
+}
+
 contract NestedPranker {
     Vm constant vm = Vm(address(bytes20(uint160(uint256(keccak256("hevm cheat code"))))));
 
@@ -414,6 +426,65 @@ contract PrankTest is DSTest {
         pranker.completePrank(victim);
     }
 
+    function testStartPrankOrigin(address origin) public {
+        address oldOrigin = tx.origin;
+
+        // Perform the prank
+        Victim victim = new Victim();
+        Victim innerVictim = new Victim();
+        NestedVictim nestedVictim = new NestedVictim(innerVictim);
+        vm.startPrankOrigin(origin);
+        victim.assertCallerAndOrigin(
+            address(this), "msg.sender was altered during prank", origin, "tx.origin was not set during prank"
+        );
+        nestedVictim.assertCallerAndOrigin(
+            address(this), "msg.sender was altered during prank", origin, "tx.origin was not set during prank"
+        );
+        vm.stopPrank();
+
+        // Ensure we cleaned up correctly
+        victim.assertCallerAndOrigin(
+            address(this), "msg.sender was not cleaned up", oldOrigin, "tx.origin was not cleaned up"
+        );
+    }
+
+    function testPrankDepth(address sender) public {
+        Victim victim = new Victim();
+        VictimForwarder forwarder = new VictimForwarder();
+
+        // Perform the prank on the call made by the forwarder
+        vm.prankDepth(sender, 1);
+        forwarder.forwardAssertCallerAndOrigin(
+            victim, sender, "msg.sender was not set during prank", tx.origin, "tx.origin invariant failed"
+        );
+
+        // Ensure we cleaned up correctly
+        forwarder.forwardAssertCallerAndOrigin(
+            victim, address(forwarder), "msg.sender was not cleaned up", tx.origin, "tx.origin invariant failed"
+        );
+    }
+
+    function testPrankDepthZero(address sender) public {
+        Victim victim = new Victim();
+
+        vm.prankDepth(sender, 0);
+        victim.assertCallerAndOrigin(
+            sender, "msg.sender was not set during prank", tx.origin, "tx.origin invariant failed"
+        );
+    }
+
+    function testFailPrankDepthWrongDepth(address sender) public {
+        Victim victim = new Victim();
+        VictimForwarder forwarder = new VictimForwarder();
+        vm.assume(sender != address(forwarder));
+
+        // The prank applies to the call made by the victim, which makes no calls
+        vm.prankDepth(sender, 2);
+        forwarder.forwardAssertCallerAndOrigin(
+            victim, sender, "msg.sender was not set during prank", tx.origin, "tx.origin invariant failed"
+        );
+    }
+
     /// Checks that `tx.origin` is set for all subcalls of a `prank`.
     ///
     /// Ref: issue #1210
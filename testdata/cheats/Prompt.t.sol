@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract PromptTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function setUp() public {
+        vm.setEnv("FOUNDRY_PROMPT_ANSWERS", "fixtures/Prompt/answers.json");
+    }
+
+    function options() internal pure returns (string[] memory result) {
+        result = new string[](3);
+        result[0] = "mainnet";
+        result[1] = "sepolia";
+        result[2] = "holesky";
+    }
+
+    function testPromptConfirm() public {
+        assertTrue(!vm.promptConfirm("Deploy to mainnet?"));
+        assertTrue(vm.promptConfirm("Verify contracts?"));
+    }
+
+    function testPromptSelect() public {
+        assertEq(vm.promptSelect("Select network", options()), 1);
+        assertEq(vm.promptSelect("Select deployer", options()), 1);
+    }
+
+    function testPromptMissingAnswer() public {
+        vm._expectCheatcodeRevert();
+        vm.promptConfirm("Unknown prompt");
+    }
+
+    function testPromptSelectInvalidAnswer() public {
+        vm._expectCheatcodeRevert("answer true to prompt \"Verify contracts?\" must be an option or its index");
+        vm.promptSelect("Verify contracts?", options());
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract RandomTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testRandomUint() public {
+        uint256 a = vm.randomUint();
+        uint256 b = vm.randomUint();
+        assertTrue(a != b);
+    }
+
+    function testRandomUintBetween(uint256 min, uint256 max) public {
+        vm.assume(min <= max);
+        uint256 value = vm.randomUintBetween(min, max);
+        assertGe(value, min);
+        assertLe(value, max);
+    }
+
+    function testRandomUintBetweenSingleValue(uint256 value) public {
+        assertEq(vm.randomUintBetween(value, value), value);
+    }
+
+    function testFailRandomUintBetweenInvalidRange() public {
+        vm.randomUintBetween(2, 1);
+    }
+
+    function testRandomAddress(uint256 seed) public {
+        assertEq(vm.randomAddress(seed), vm.randomAddress(seed));
+        assertTrue(vm.randomAddress(seed) != vm.randomAddress(~seed));
+    }
+
+    function testRandomBytesLen(uint8 len) public {
+        assertEq(vm.randomBytesLen(len).length, len);
+    }
+
+    function testRandomBytesLenTooLarge() public {
+        vm.expectRevert(bytes("length 4194305 exceeds the maximum of 4194304"));
+        this.randomBytesLen(4194305);
+    }
+
+    function randomBytesLen(uint256 len) public returns (bytes memory) {
+        return vm.randomBytesLen(len);
+    }
+
+    function testRandomBytesLenDifferent() public {
+        assertTrue(keccak256(vm.randomBytesLen(32)) != keccak256(vm.randomBytesLen(32)));
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract SetTxTypeTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testSetTxType() public {
+        vm.setTxType(0);
+        vm.setTxType(1);
+        vm.setTxType(2);
+    }
+
+    function testSetUnsupportedTxType() public {
+        vm._expectCheatcodeRevert("transactions of type 3 can't be broadcasted, expected 0, 1 or 2");
+        vm.setTxType(3);
+    }
+}
@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract SignBlsTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    uint256 constant BLS_ORDER = 0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001;
+
+    function testBlsPublicKeyGenerator() public {
+        // The public key of `1` is the generator of G1.
+        bytes memory expected = abi.encodePacked(
+            bytes16(0),
+            hex"17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb",
+            bytes16(0),
+            hex"08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"
+        );
+        assertEq(vm.blsPublicKey(1), expected);
+    }
+
+    function testSignBls(uint248 pk, bytes memory message) public {
+        vm.assume(pk != 0);
+        bytes memory signature = vm.signBls(pk, message);
+        assertEq(signature.length, 256);
+        assertEq(keccak256(vm.signBls(pk, message)), keccak256(signature));
+
+        bytes memory other = vm.signBls(pk, abi.encodePacked(message, uint8(1)));
+        assertTrue(keccak256(other) != keccak256(signature));
+    }
+
+    function testDeriveBlsKey(bytes32 a, bytes32 b) public {
+        uint256 pk = vm.deriveBlsKey(abi.encodePacked(a, b));
+        assertTrue(pk != 0);
+        assertLt(pk, BLS_ORDER);
+        assertEq(vm.deriveBlsKey(abi.encodePacked(a, b)), pk);
+        assertEq(vm.blsPublicKey(pk).length, 128);
+    }
+
+    function testFailDeriveBlsKeyShortIkm() public {
+        vm.deriveBlsKey(hex"01");
+    }
+
+    function testFailSignBlsZeroKey() public {
+        vm.signBls(0, hex"01");
+    }
+
+    function testFailSignBlsKeyTooLarge() public {
+        vm.signBls(BLS_ORDER, hex"01");
+    }
+}
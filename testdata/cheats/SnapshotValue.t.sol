@@ -0,0 +1,28 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract SnapshotValueTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function setUp() public {
+        vm.snapshotValue("setup", abi.encode(uint256(1)));
+    }
+
+    function testSnapshotValue() public {
+        vm.snapshotValue("root", abi.encodePacked(keccak256("root")));
+        vm.snapshotValue("calldata", abi.encodeWithSignature("transfer(address,uint256)", address(1), 2));
+    }
+
+    function testSnapshotValueOverwrite() public {
+        vm.snapshotValue("overwritten", hex"01");
+        vm.snapshotValue("overwritten", hex"02");
+    }
+
+    function testFailSnapshotValueReverted() public {
+        vm.snapshotValue("reverted", hex"01");
+        revert();
+    }
+}
@@ -0,0 +1,64 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract StateDumpTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    address constant EOA = address(0xbeef);
+    address constant GETTER = address(0xc0de);
+
+    function read(address target) internal view returns (uint256) {
+        (bool success, bytes memory data) = target.staticcall("");
+        require(success, "getter call failed");
+        return abi.decode(data, (uint256));
+    }
+
+    function testForkAnvilStateDump() public {
+        vm.createSelectFork("fixtures/StateDump/anvil.json");
+
+        assertEq(block.number, 100);
+        assertEq(block.timestamp, 0x65f0a000);
+        assertEq(EOA.balance, 1 ether);
+        assertEq(vm.getNonce(EOA), 3);
+        assertEq(GETTER.code, hex"60005460005260206000f3");
+        assertEq(uint256(vm.load(GETTER, bytes32(0))), 42);
+        assertEq(read(GETTER), 42);
+
+        // accounts missing from the dump are empty
+        assertEq(address(0x1234).balance, 0);
+        assertEq(address(0x1234).code.length, 0);
+    }
+
+    function testForkStateDumpIsolated() public {
+        uint256 fork = vm.createSelectFork("fixtures/StateDump/anvil.json");
+        vm.store(GETTER, bytes32(0), bytes32(uint256(7)));
+        assertEq(read(GETTER), 7);
+
+        vm.createSelectFork("fixtures/StateDump/anvil.json");
+        assertEq(read(GETTER), 42);
+
+        vm.selectFork(fork);
+        assertEq(read(GETTER), 7);
+    }
+
+    function testForkDumpedState() public {
+        string memory path = "fixtures/StateDump/dumped.json";
+        vm.deal(EOA, 2 ether);
+        vm.etch(GETTER, hex"60005460005260206000f3");
+        vm.store(GETTER, bytes32(0), bytes32(uint256(1337)));
+        vm.dumpState(path);
+
+        vm.createSelectFork(path);
+        vm.removeFile(path);
+        assertEq(EOA.balance, 2 ether);
+        assertEq(read(GETTER), 1337);
+    }
+
+    function testForkMissingStateDump() public {
+        vm._expectCheatcodeRevert("state dump fixtures/StateDump/missing.json does not exist");
+        vm.createFork("fixtures/StateDump/missing.json");
+    }
+}
@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract Registry {
+    struct Order {
+        address maker;
+        uint96 amount;
+        uint256 price;
+    }
+
+    address public owner;
+    mapping(address => mapping(uint256 => uint256)) public allowances;
+    Order[] public orders;
+    uint256[3] public limits;
+    mapping(string => Order) public named;
+
+    function setAllowance(address account, uint256 id, uint256 value) public {
+        allowances[account][id] = value;
+    }
+
+    function addOrder(address maker, uint96 amount, uint256 price) public {
+        orders.push(Order(maker, amount, price));
+    }
+
+    function setLimit(uint256 index, uint256 value) public {
+        limits[index] = value;
+    }
+
+    function setNamed(string memory name, uint256 price) public {
+        named[name].price = price;
+    }
+}
+
+contract StorageLayoutTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    string constant ARTIFACT = "StorageLayout.t.sol:Registry";
+
+    Registry registry;
+
+    function setUp() public {
+        registry = new Registry();
+    }
+
+    function testGetStorageLayout() public {
+        string memory layout = vm.getStorageLayout(ARTIFACT);
+        assertEq(vm.parseJsonString(layout, ".storage[0].label"), "owner");
+        assertEq(vm.parseJsonString(layout, ".storage[1].label"), "allowances");
+    }
+
+    function testSlotOf() public {
+        assertEq(vm.slotOf(ARTIFACT, "owner"), bytes32(0));
+        assertEq(vm.slotOf(ARTIFACT, "limits[2]"), bytes32(uint256(5)));
+
+        registry.setAllowance(address(0xbeef), 7, 42);
+        bytes32 slot = vm.slotOf(ARTIFACT, "allowances[0x000000000000000000000000000000000000bEEF][7]");
+        assertEq(uint256(vm.load(address(registry), slot)), 42);
+
+        registry.addOrder(address(this), 1, 2);
+        registry.addOrder(address(this), 3, 4);
+        slot = vm.slotOf(ARTIFACT, "orders[1].price");
+        assertEq(uint256(vm.load(address(registry), slot)), 4);
+
+        registry.setNamed("alice", 5);
+        slot = vm.slotOf(ARTIFACT, "named[alice].price");
+        assertEq(uint256(vm.load(address(registry), slot)), 5);
+    }
+
+    function testSlotOfInvalidPath() public {
+        vm._expectCheatcodeRevert("variable `missing` not found in the storage layout");
+        vm.slotOf(ARTIFACT, "missing");
+    }
+
+    function testSlotOfOutOfBounds() public {
+        vm._expectCheatcodeRevert("index 3 out of bounds of `uint256[3]`");
+        vm.slotOf(ARTIFACT, "limits[3]");
+    }
+}
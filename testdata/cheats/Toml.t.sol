@@ -0,0 +1,83 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ParseTomlTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    string toml;
+
+    function setUp() public {
+        toml = vm.readFile("fixtures/Toml/test.toml");
+    }
+
+    function test_keyExists() public {
+        assertTrue(vm.keyExistsToml(toml, ".nested.deep.key"));
+        assertTrue(!vm.keyExistsToml(toml, ".nested.missing"));
+    }
+
+    function test_parse() public {
+        string[] memory strArray = abi.decode(vm.parseToml(toml, ".strArray"), (string[]));
+        assertEq(strArray[0], "hai");
+        assertEq(strArray[1], "there");
+
+        uint256[] memory uintArray = abi.decode(vm.parseToml(toml, ".uintArray"), (uint256[]));
+        assertEq(uintArray[0], 42);
+        assertEq(uintArray[1], 43);
+    }
+
+    function test_coerce() public {
+        assertEq(vm.parseTomlString(toml, ".str"), "hai");
+        assertEq(vm.parseTomlStringArray(toml, ".strArray")[1], "there");
+        assertTrue(vm.parseTomlBool(toml, ".bool"));
+        assertTrue(!vm.parseTomlBoolArray(toml, ".boolArray")[1]);
+        assertEq(vm.parseTomlUint(toml, ".uint"), 42);
+        assertEq(vm.parseTomlUintArray(toml, ".uintArray")[1], 43);
+        assertEq(vm.parseTomlUint(toml, ".largeUint"), type(uint256).max);
+        assertEq(vm.parseTomlInt(toml, ".int"), -42);
+        assertEq(vm.parseTomlIntArray(toml, ".intArray")[0], -42);
+        assertEq(vm.parseTomlAddress(toml, ".address"), 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266);
+        assertEq(vm.parseTomlAddressArray(toml, ".addressArray")[1], 0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);
+        assertEq(vm.parseTomlBytes(toml, ".bytes"), hex"7109709ECfa91a80626fF3989D68f67F5b1DD12D");
+        assertEq(
+            vm.parseTomlBytes32(toml, ".bytes32"), 0x8e4e4e0bdc3e6d80ab4caf4d35f6c4ea0a9d1a1ae9fbcc1c4a4fe0c18f2f5a9d
+        );
+        assertEq(vm.parseTomlUint(toml, ".nested.number"), 9);
+    }
+
+    function test_keys() public {
+        string[] memory keys = vm.parseTomlKeys(toml, ".nested");
+        assertEq(keys.length, 3);
+        assertEq(keys[0], "number");
+        assertEq(keys[1], "name");
+        assertEq(keys[2], "deep");
+    }
+
+    function test_invalidToml() public {
+        vm._expectCheatcodeRevert();
+        vm.parseTomlUint("key = [unclosed", ".key");
+    }
+}
+
+contract WriteTomlTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function test_writeToml() public {
+        string memory path = "fixtures/Toml/write_test.toml";
+        string memory json = vm.serializeUint("toml", "count", 7);
+        json = vm.serializeString("toml", "name", "written");
+        vm.writeToml(json, path);
+
+        string memory toml = vm.readFile(path);
+        assertEq(vm.parseTomlUint(toml, ".count"), 7);
+        assertEq(vm.parseTomlString(toml, ".name"), "written");
+
+        vm.writeToml("8", path, ".count");
+        toml = vm.readFile(path);
+        assertEq(vm.parseTomlUint(toml, ".count"), 8);
+        assertEq(vm.parseTomlString(toml, ".name"), "written");
+
+        vm.removeFile(path);
+    }
+}
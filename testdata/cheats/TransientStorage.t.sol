@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract TransientStorageTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testTloadEmpty(bytes32 slot) public {
+        assertEq(vm.tload(address(this), slot), bytes32(0));
+    }
+
+    function testTstoreTload(bytes32 slot, bytes32 value) public {
+        address target = address(0x1234);
+        vm.tstore(target, slot, value);
+        assertEq(vm.tload(target, slot), value);
+
+        // Transient storage is not persistent storage
+        assertEq(vm.load(target, slot), bytes32(0));
+    }
+
+    function testTstoreIsPerAccount(bytes32 slot, bytes32 value) public {
+        vm.assume(value != bytes32(0));
+        vm.tstore(address(0x1234), slot, value);
+        assertEq(vm.tload(address(0x1235), slot), bytes32(0));
+    }
+
+    function testTloadNotAvailableOnPrecompiles() public {
+        vm.expectRevert(bytes("cannot call `tload` on precompile 0x0000000000000000000000000000000000000001"));
+        this.tload(address(1), bytes32(0));
+    }
+
+    function tload(address target, bytes32 slot) public view returns (bytes32) {
+        return vm.tload(target, slot);
+    }
+}
@@ -8,6 +8,7 @@ pragma experimental ABIEncoderV2;
 interface Vm {
     enum CallerMode { None, Broadcast, RecurrentBroadcast, Prank, RecurrentPrank }
     enum AccountAccessKind { Call, DelegateCall, CallCode, StaticCall, Create, SelfDestruct, Resume, Balance, Extcodesize, Extcodehash, Extcodecopy }
+    enum MerkleTreeKind { Sorted, OpenZeppelin }
     struct Log { bytes32[] topics; bytes data; address emitter; }
     struct Rpc { string key; string url; }
     struct EthGetLogs { address emitter; bytes32[] topics; bytes data; bytes32 blockHash; uint64 blockNumber; bytes32 transactionHash; uint64 transactionIndex; uint256 logIndex; bool removed; }
@@ -15,16 +16,26 @@ interface Vm {
     struct FsMetadata { bool isDir; bool isSymlink; uint256 length; bool readOnly; uint256 modified; uint256 accessed; uint256 created; }
     struct Wallet { address addr; uint256 publicKeyX; uint256 publicKeyY; uint256 privateKey; }
     struct FfiResult { int32 exitCode; bytes stdout; bytes stderr; }
+    struct HttpResponse { uint256 status; bytes body; }
     struct ChainInfo { uint256 forkId; uint256 chainId; }
     struct AccountAccess { ChainInfo chainInfo; AccountAccessKind kind; address account; address accessor; bool initialized; uint256 oldBalance; uint256 newBalance; bytes deployedCode; uint256 value; bytes data; bool reverted; StorageAccess[] storageAccesses; }
     struct StorageAccess { address account; bytes32 slot; bool isWrite; bytes32 previousValue; bytes32 newValue; bool reverted; }
+    struct DiffCallResult { bool success; bytes returnData; Log[] logs; StorageAccess[] storageChanges; }
+    struct AccessListItem { address account; bytes32[] storageKeys; }
+    struct DecodedStorageChange { address account; string contractName; bytes32 slot; string label; string typeName; bytes32 previousValue; bytes32 newValue; string decodedPreviousValue; string decodedNewValue; }
     function _expectCheatcodeRevert() external;
     function _expectCheatcodeRevert(bytes4 revertData) external;
     function _expectCheatcodeRevert(bytes calldata revertData) external;
+    function accessList(address to, bytes calldata data) external returns (AccessListItem[] memory accessList);
     function accesses(address target) external returns (bytes32[] memory readSlots, bytes32[] memory writeSlots);
     function activeFork() external view returns (uint256 forkId);
+    function addFuzzFixture(bytes32 value) external;
+    function addFuzzFixture(uint256 value) external;
+    function addFuzzFixture(int256 value) external;
+    function addFuzzFixture(address value) external;
     function addr(uint256 privateKey) external pure returns (address keyAddr);
     function allowCheatcodes(address account) external;
+    function applyAccessList(AccessListItem[] calldata accessList) external;
     function assertApproxEqAbsDecimal(uint256 left, uint256 right, uint256 maxDelta, uint256 decimals) external pure;
     function assertApproxEqAbsDecimal(uint256 left, uint256 right, uint256 maxDelta, uint256 decimals, string calldata error) external pure;
     function assertApproxEqAbsDecimal(int256 left, int256 right, uint256 maxDelta, uint256 decimals) external pure;
@@ -142,19 +153,32 @@ interface Vm {
     function assertTrue(bool condition) external pure;
     function assertTrue(bool condition, string calldata error) external pure;
     function assume(bool condition) external pure;
+    function assumeNoRevert() external;
+    function assumeNoRevert(bytes calldata revertData) external;
+    function assumeNoRevert(string calldata reason) external;
+    function assumeNoRevert(address callee) external;
+    function assumeUnique(bytes32 key) external;
+    function blobToKzgCommitment(bytes calldata blob) external pure returns (bytes memory commitment);
+    function blsPublicKey(uint256 privateKey) external pure returns (bytes memory publicKey);
     function breakpoint(string calldata char) external;
     function breakpoint(string calldata char, bool value) external;
+    function broadcastUserOp(address account) external;
     function broadcast() external;
     function broadcast(address signer) external;
     function broadcast(uint256 privateKey) external;
+    function brotliCompress(bytes calldata data) external pure returns (bytes memory compressed);
+    function brotliDecompress(bytes calldata data) external pure returns (bytes memory decompressed);
     function chainId(uint256 newChainId) external;
     function clearMockedCalls() external;
     function closeFile(string calldata path) external;
     function coinbase(address newCoinbase) external;
+    function computeBlobKzgProof(bytes calldata blob, bytes calldata commitment) external pure returns (bytes memory proof);
     function computeCreate2Address(bytes32 salt, bytes32 initCodeHash, address deployer) external pure returns (address);
     function computeCreate2Address(bytes32 salt, bytes32 initCodeHash) external pure returns (address);
     function computeCreateAddress(address deployer, uint256 nonce) external pure returns (address);
+    function computeKzgProof(bytes calldata blob, bytes32 z) external pure returns (bytes memory proof, bytes32 y);
     function cool(address target) external;
+    function copyAccount(uint256 fromForkId, uint256 toForkId, address account) external;
     function copyFile(string calldata from, string calldata to) external returns (uint64 copied);
     function createDir(string calldata path, bool recursive) external;
     function createFork(string calldata urlOrAlias) external returns (uint256 forkId);
@@ -167,13 +191,17 @@ interface Vm {
     function createWallet(uint256 privateKey) external returns (Wallet memory wallet);
     function createWallet(uint256 privateKey, string calldata walletLabel) external returns (Wallet memory wallet);
     function deal(address account, uint256 newBalance) external;
+    function dealERC20(address token, address to, uint256 amount) external;
     function deleteSnapshot(uint256 snapshotId) external returns (bool success);
     function deleteSnapshots() external;
+    function deriveBlsKey(bytes calldata ikm) external pure returns (uint256 privateKey);
     function deriveKey(string calldata mnemonic, uint32 index) external pure returns (uint256 privateKey);
     function deriveKey(string calldata mnemonic, string calldata derivationPath, uint32 index) external pure returns (uint256 privateKey);
     function deriveKey(string calldata mnemonic, uint32 index, string calldata language) external pure returns (uint256 privateKey);
     function deriveKey(string calldata mnemonic, string calldata derivationPath, uint32 index, string calldata language) external pure returns (uint256 privateKey);
+    function diffCall(address a, address b, bytes calldata data) external returns (DiffCallResult memory resultA, DiffCallResult memory resultB);
     function difficulty(uint256 newDifficulty) external;
+    function disablePrecompile(address precompile) external;
     function dumpState(string calldata pathToStateJson) external;
     function envAddress(string calldata name) external view returns (address value);
     function envAddress(string calldata name, string calldata delim) external view returns (address[] memory value);
@@ -206,6 +234,8 @@ interface Vm {
     function etch(address target, bytes calldata newRuntimeBytecode) external;
     function eth_getLogs(uint256 fromBlock, uint256 toBlock, address target, bytes32[] memory topics) external returns (EthGetLogs[] memory logs);
     function exists(string calldata path) external returns (bool result);
+    function expectCallMaxGas(address callee, bytes calldata data, uint64 maxGas) external;
+    function expectCallMaxGas(address callee, bytes calldata data, uint64 maxGas, uint64 count) external;
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data) external;
     function expectCallMinGas(address callee, uint256 msgValue, uint64 minGas, bytes calldata data, uint64 count) external;
     function expectCall(address callee, bytes calldata data) external;
@@ -214,33 +244,58 @@ interface Vm {
     function expectCall(address callee, uint256 msgValue, bytes calldata data, uint64 count) external;
     function expectCall(address callee, uint256 msgValue, uint64 gas, bytes calldata data) external;
     function expectCall(address callee, uint256 msgValue, uint64 gas, bytes calldata data, uint64 count) external;
+    function expectEmitAnonymous(bool checkTopic0, bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData) external;
+    function expectEmitAnonymous(bool checkTopic0, bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter) external;
+    function expectEmitAnonymous() external;
+    function expectEmitAnonymous(address emitter) external;
+    function expectEmitUnordered() external;
     function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData) external;
     function expectEmit(bool checkTopic1, bool checkTopic2, bool checkTopic3, bool checkData, address emitter) external;
     function expectEmit() external;
     function expectEmit(address emitter) external;
+    function expectPartialRevert(bytes4 revertData) external;
+    function expectPartialRevert(bytes4 revertData, address reverter) external;
     function expectRevert() external;
     function expectRevert(bytes4 revertData) external;
     function expectRevert(bytes calldata revertData) external;
+    function expectRevert(bytes4 revertData, uint64 count) external;
     function expectSafeMemory(uint64 min, uint64 max) external;
     function expectSafeMemoryCall(uint64 min, uint64 max) external;
+    function expectSafeMemoryDeep(uint64 min, uint64 max) external;
     function fee(uint256 newBasefee) external;
     function ffi(string[] calldata commandInput) external returns (bytes memory result);
     function fsMetadata(string calldata path) external view returns (FsMetadata memory metadata);
+    function getAbi(string calldata artifactPath) external view returns (string memory abiJson);
     function getBlockNumber() external view returns (uint256 height);
     function getBlockTimestamp() external view returns (uint256 timestamp);
     function getCode(string calldata artifactPath) external view returns (bytes memory creationBytecode);
+    function getCodeHash(string calldata artifactPath) external view returns (bytes32 codeHash);
     function getDeployedCode(string calldata artifactPath) external view returns (bytes memory runtimeBytecode);
+    function getDeployedCodeWithImmutables(string calldata artifactPath, bytes[] calldata values) external view returns (bytes memory runtimeBytecode);
+    function getDeployment(string calldata contractName, uint256 chainId) external view returns (address deployedAddress);
     function getLabel(address account) external view returns (string memory currentLabel);
     function getMappingKeyAndParentOf(address target, bytes32 elementSlot) external returns (bool found, bytes32 key, bytes32 parent);
     function getMappingLength(address target, bytes32 mappingSlot) external returns (uint256 length);
     function getMappingSlotAt(address target, bytes32 mappingSlot, uint256 idx) external returns (bytes32 value);
+    function getMethodIdentifiers(string calldata artifactPath) external view returns (string[] memory signatures, bytes4[] memory selectors);
     function getNonce(address account) external view returns (uint64 nonce);
     function getNonce(Wallet calldata wallet) external returns (uint64 nonce);
     function getRecordedLogs() external returns (Log[] memory logs);
+    function getSecret(string calldata key) external view returns (string memory value);
+    function getStorageLayout(string calldata artifactPath) external view returns (string memory layout);
+    function glob(string calldata pattern) external view returns (string[] memory paths);
+    function gzipCompress(bytes calldata data) external pure returns (bytes memory compressed);
+    function gzipDecompress(bytes calldata data) external pure returns (bytes memory decompressed);
+    function httpGet(string calldata url) external returns (HttpResponse memory response);
+    function httpPost(string calldata url, string calldata body, string[] calldata headers) external returns (HttpResponse memory response);
     function isDir(string calldata path) external returns (bool result);
     function isFile(string calldata path) external returns (bool result);
     function isPersistent(address account) external view returns (bool persistent);
     function keyExists(string calldata json, string calldata key) external view returns (bool);
+    function keyExistsToml(string calldata toml, string calldata key) external view returns (bool);
+    function keyExistsYaml(string calldata yaml, string calldata key) external view returns (bool);
+    function kzgToVersionedHash(bytes calldata commitment) external pure returns (bytes32 versionedHash);
+    function l1BlockNumber() external view returns (uint256 blockNumber);
     function label(address account, string calldata newLabel) external;
     function load(address target, bytes32 slot) external view returns (bytes32 data);
     function loadAllocs(string calldata pathToAllocsJson) external;
@@ -248,10 +303,20 @@ interface Vm {
     function makePersistent(address account0, address account1) external;
     function makePersistent(address account0, address account1, address account2) external;
     function makePersistent(address[] calldata accounts) external;
+    function mergeJson(string calldata json, string calldata other) external pure returns (string memory merged);
+    function merkleMultiProof(bytes32[] calldata leaves, uint256[] calldata indices) external pure returns (bytes32[] memory proofLeaves, bytes32[] memory proof, bool[] memory proofFlags);
+    function merkleMultiProof(bytes32[] calldata leaves, uint256[] calldata indices, MerkleTreeKind kind) external pure returns (bytes32[] memory proofLeaves, bytes32[] memory proof, bool[] memory proofFlags);
+    function merkleProof(bytes32[] calldata leaves, uint256 index) external pure returns (bytes32[] memory proof);
+    function merkleProof(bytes32[] calldata leaves, uint256 index, MerkleTreeKind kind) external pure returns (bytes32[] memory proof);
+    function merkleRoot(bytes32[] calldata leaves) external pure returns (bytes32 root);
+    function merkleRoot(bytes32[] calldata leaves, MerkleTreeKind kind) external pure returns (bytes32 root);
+    function mockCallMasked(address callee, bytes calldata data, bytes calldata mask, bytes calldata returnData) external;
     function mockCallRevert(address callee, bytes calldata data, bytes calldata revertData) external;
     function mockCallRevert(address callee, uint256 msgValue, bytes calldata data, bytes calldata revertData) external;
     function mockCall(address callee, bytes calldata data, bytes calldata returnData) external;
     function mockCall(address callee, uint256 msgValue, bytes calldata data, bytes calldata returnData) external;
+    function mockFunction(address callee, address target, bytes calldata data) external;
+    function mockPrecompile(address precompile, bytes calldata input, bytes calldata output) external;
     function parseAddress(string calldata stringifiedValue) external pure returns (address parsedValue);
     function parseBool(string calldata stringifiedValue) external pure returns (bool parsedValue);
     function parseBytes(string calldata stringifiedValue) external pure returns (bytes memory parsedValue);
@@ -274,13 +339,56 @@ interface Vm {
     function parseJsonUintArray(string calldata json, string calldata key) external pure returns (uint256[] memory);
     function parseJson(string calldata json) external pure returns (bytes memory abiEncodedData);
     function parseJson(string calldata json, string calldata key) external pure returns (bytes memory abiEncodedData);
+    function parseTomlAddress(string calldata toml, string calldata key) external pure returns (address);
+    function parseTomlAddressArray(string calldata toml, string calldata key) external pure returns (address[] memory);
+    function parseTomlBool(string calldata toml, string calldata key) external pure returns (bool);
+    function parseTomlBoolArray(string calldata toml, string calldata key) external pure returns (bool[] memory);
+    function parseTomlBytes(string calldata toml, string calldata key) external pure returns (bytes memory);
+    function parseTomlBytes32(string calldata toml, string calldata key) external pure returns (bytes32);
+    function parseTomlBytes32Array(string calldata toml, string calldata key) external pure returns (bytes32[] memory);
+    function parseTomlBytesArray(string calldata toml, string calldata key) external pure returns (bytes[] memory);
+    function parseTomlInt(string calldata toml, string calldata key) external pure returns (int256);
+    function parseTomlIntArray(string calldata toml, string calldata key) external pure returns (int256[] memory);
+    function parseTomlKeys(string calldata toml, string calldata key) external pure returns (string[] memory keys);
+    function parseTomlString(string calldata toml, string calldata key) external pure returns (string memory);
+    function parseTomlStringArray(string calldata toml, string calldata key) external pure returns (string[] memory);
+    function parseTomlUint(string calldata toml, string calldata key) external pure returns (uint256);
+    function parseTomlUintArray(string calldata toml, string calldata key) external pure returns (uint256[] memory);
+    function parseToml(string calldata toml) external pure returns (bytes memory abiEncodedData);
+    function parseToml(string calldata toml, string calldata key) external pure returns (bytes memory abiEncodedData);
     function parseUint(string calldata stringifiedValue) external pure returns (uint256 parsedValue);
+    function parseYamlAddress(string calldata yaml, string calldata key) external pure returns (address);
+    function parseYamlAddressArray(string calldata yaml, string calldata key) external pure returns (address[] memory);
+    function parseYamlBool(string calldata yaml, string calldata key) external pure returns (bool);
+    function parseYamlBoolArray(string calldata yaml, string calldata key) external pure returns (bool[] memory);
+    function parseYamlBytes(string calldata yaml, string calldata key) external pure returns (bytes memory);
+    function parseYamlBytes32(string calldata yaml, string calldata key) external pure returns (bytes32);
+    function parseYamlBytes32Array(string calldata yaml, string calldata key) external pure returns (bytes32[] memory);
+    function parseYamlBytesArray(string calldata yaml, string calldata key) external pure returns (bytes[] memory);
+    function parseYamlInt(string calldata yaml, string calldata key) external pure returns (int256);
+    function parseYamlIntArray(string calldata yaml, string calldata key) external pure returns (int256[] memory);
+    function parseYamlKeys(string calldata yaml, string calldata key) external pure returns (string[] memory keys);
+    function parseYamlString(string calldata yaml, string calldata key) external pure returns (string memory);
+    function parseYamlStringArray(string calldata yaml, string calldata key) external pure returns (string[] memory);
+    function parseYamlUint(string calldata yaml, string calldata key) external pure returns (uint256);
+    function parseYamlUintArray(string calldata yaml, string calldata key) external pure returns (uint256[] memory);
+    function parseYaml(string calldata yaml) external pure returns (bytes memory abiEncodedData);
+    function parseYaml(string calldata yaml, string calldata key) external pure returns (bytes memory abiEncodedData);
+    function patchJson(string calldata json, string calldata patch) external pure returns (string memory patched);
     function pauseGasMetering() external;
+    function prankDepth(address msgSender, uint64 depth) external;
     function prank(address msgSender) external;
     function prank(address msgSender, address txOrigin) external;
     function prevrandao(bytes32 newPrevrandao) external;
     function projectRoot() external view returns (string memory path);
+    function promptConfirm(string calldata label) external returns (bool confirmed);
+    function promptSelect(string calldata label, string[] calldata options) external returns (uint256 index);
+    function randomAddress(uint256 seed) external view returns (address);
+    function randomBytesLen(uint256 len) external returns (bytes memory);
+    function randomUint() external returns (uint256);
+    function randomUintBetween(uint256 min, uint256 max) external returns (uint256);
     function readCallers() external returns (CallerMode callerMode, address msgSender, address txOrigin);
+    function readDirRecursive(string calldata path, uint64 maxDepth) external view returns (string[] memory paths);
     function readDir(string calldata path) external view returns (DirEntry[] memory entries);
     function readDir(string calldata path, uint64 maxDepth) external view returns (DirEntry[] memory entries);
     function readDir(string calldata path, uint64 maxDepth, bool followLinks) external view returns (DirEntry[] memory entries);
@@ -295,6 +403,7 @@ interface Vm {
     function removeFile(string calldata path) external;
     function replace(string calldata input, string calldata from, string calldata to) external pure returns (string memory output);
     function resetNonce(address account) external;
+    function restoreCheatState(uint256 id) external;
     function resumeGasMetering() external;
     function revertTo(uint256 snapshotId) external returns (bool success);
     function revertToAndDelete(uint256 snapshotId) external returns (bool success);
@@ -305,10 +414,12 @@ interface Vm {
     function rollFork(bytes32 txHash) external;
     function rollFork(uint256 forkId, uint256 blockNumber) external;
     function rollFork(uint256 forkId, bytes32 txHash) external;
+    function rollFork(bytes32 blockHash, uint256 txIndex) external;
     function rpc(string calldata method, string calldata params) external returns (bytes memory data);
     function rpcUrl(string calldata rpcAlias) external view returns (string memory json);
     function rpcUrlStructs() external view returns (Rpc[] memory urls);
     function rpcUrls() external view returns (string[2][] memory urls);
+    function saveCheatState() external returns (uint256 id);
     function selectFork(uint256 forkId) external;
     function serializeAddress(string calldata objectKey, string calldata valueKey, address value) external returns (string memory json);
     function serializeAddress(string calldata objectKey, string calldata valueKey, address[] calldata values) external returns (string memory json);
@@ -326,28 +437,38 @@ interface Vm {
     function serializeUint(string calldata objectKey, string calldata valueKey, uint256 value) external returns (string memory json);
     function serializeUint(string calldata objectKey, string calldata valueKey, uint256[] calldata values) external returns (string memory json);
     function setEnv(string calldata name, string calldata value) external;
+    function setFuzzRuns(uint256 runs) external;
+    function setMaxRejects(uint256 maxRejects) external;
     function setNonce(address account, uint64 newNonce) external;
     function setNonceUnsafe(address account, uint64 newNonce) external;
+    function setTxType(uint8 txType) external;
+    function signBls(uint256 privateKey, bytes calldata message) external pure returns (bytes memory signature);
     function signP256(uint256 privateKey, bytes32 digest) external pure returns (bytes32 r, bytes32 s);
     function sign(uint256 privateKey, bytes32 digest) external pure returns (uint8 v, bytes32 r, bytes32 s);
     function sign(Wallet calldata wallet, bytes32 digest) external returns (uint8 v, bytes32 r, bytes32 s);
     function skip(bool skipTest) external;
     function sleep(uint256 duration) external;
+    function slotOf(string calldata artifactPath, string calldata variablePath) external view returns (bytes32 slot);
     function snapshot() external returns (uint256 snapshotId);
+    function snapshotValue(string calldata name, bytes calldata value) external;
     function split(string calldata input, string calldata delimiter) external pure returns (string[] memory outputs);
     function startBroadcast() external;
     function startBroadcast(address signer) external;
     function startBroadcast(uint256 privateKey) external;
     function startMappingRecording() external;
+    function startPrankOrigin(address txOrigin) external;
     function startPrank(address msgSender) external;
     function startPrank(address msgSender, address txOrigin) external;
     function startStateDiffRecording() external;
+    function stopAndReturnDecodedStateDiff() external returns (DecodedStorageChange[] memory changes);
     function stopAndReturnStateDiff() external returns (AccountAccess[] memory accountAccesses);
     function stopBroadcast() external;
     function stopExpectSafeMemory() external;
     function stopMappingRecording() external;
     function stopPrank() external;
     function store(address target, bytes32 slot, bytes32 value) external;
+    function targetFlow(address target, bytes4[] calldata selectors) external;
+    function tload(address account, bytes32 slot) external view returns (bytes32 data);
     function toBase64URL(bytes calldata data) external pure returns (string memory);
     function toBase64URL(string calldata data) external pure returns (string memory);
     function toBase64(bytes calldata data) external pure returns (string memory);
@@ -360,16 +481,23 @@ interface Vm {
     function toString(uint256 value) external pure returns (string memory stringifiedValue);
     function toString(int256 value) external pure returns (string memory stringifiedValue);
     function toUppercase(string calldata input) external pure returns (string memory output);
+    function transactWithOverrides(bytes32 txHash, address newSender, uint256 newValue, bytes calldata newData) external;
     function transact(bytes32 txHash) external;
     function transact(uint256 forkId, bytes32 txHash) external;
     function trim(string calldata input) external pure returns (string memory output);
     function tryFfi(string[] calldata commandInput) external returns (FfiResult memory result);
+    function tstore(address account, bytes32 slot, bytes32 value) external;
     function txGasPrice(uint256 newGasPrice) external;
     function unixTime() external returns (uint256 milliseconds);
+    function verifyBlobKzgProof(bytes calldata blob, bytes calldata commitment, bytes calldata proof) external pure returns (bool valid);
+    function verifyKzgProof(bytes calldata commitment, bytes32 z, bytes32 y, bytes calldata proof) external pure returns (bool valid);
+    function warmSlot(address target, bytes32 slot) external;
     function warp(uint256 newTimestamp) external;
     function writeFile(string calldata path, string calldata data) external;
     function writeFileBinary(string calldata path, bytes calldata data) external;
     function writeJson(string calldata json, string calldata path) external;
     function writeJson(string calldata json, string calldata path, string calldata valueKey) external;
     function writeLine(string calldata path, string calldata data) external;
+    function writeToml(string calldata json, string calldata path) external;
+    function writeToml(string calldata json, string calldata path, string calldata valueKey) external;
 }
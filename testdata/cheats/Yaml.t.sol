@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ParseYamlTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+    string yaml;
+
+    function setUp() public {
+        yaml = vm.readFile("fixtures/Yaml/test.yaml");
+    }
+
+    function test_keyExists() public {
+        assertTrue(vm.keyExistsYaml(yaml, ".nested.deep.key"));
+        assertTrue(!vm.keyExistsYaml(yaml, ".nested.missing"));
+    }
+
+    function test_parse() public {
+        string[] memory strArray = abi.decode(vm.parseYaml(yaml, ".strArray"), (string[]));
+        assertEq(strArray[0], "hai");
+        assertEq(strArray[1], "there");
+
+        uint256[] memory uintArray = abi.decode(vm.parseYaml(yaml, ".uintArray"), (uint256[]));
+        assertEq(uintArray[0], 42);
+        assertEq(uintArray[1], 43);
+    }
+
+    function test_coerce() public {
+        assertEq(vm.parseYamlString(yaml, ".str"), "hai");
+        assertEq(vm.parseYamlStringArray(yaml, ".strArray")[1], "there");
+        assertTrue(vm.parseYamlBool(yaml, ".bool"));
+        assertTrue(!vm.parseYamlBoolArray(yaml, ".boolArray")[1]);
+        assertEq(vm.parseYamlUint(yaml, ".uint"), 42);
+        assertEq(vm.parseYamlUintArray(yaml, ".uintArray")[1], 43);
+        assertEq(vm.parseYamlInt(yaml, ".int"), -42);
+        assertEq(vm.parseYamlIntArray(yaml, ".intArray")[0], -42);
+        assertEq(vm.parseYamlAddress(yaml, ".address"), 0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266);
+        assertEq(vm.parseYamlAddressArray(yaml, ".addressArray")[1], 0x7109709ECfa91a80626fF3989D68f67F5b1DD12D);
+        assertEq(vm.parseYamlBytes(yaml, ".bytes"), hex"7109709ECfa91a80626fF3989D68f67F5b1DD12D");
+        assertEq(
+            vm.parseYamlBytes32(yaml, ".bytes32"), 0x8e4e4e0bdc3e6d80ab4caf4d35f6c4ea0a9d1a1ae9fbcc1c4a4fe0c18f2f5a9d
+        );
+        assertEq(vm.parseYamlUint(yaml, ".nested.number"), 9);
+    }
+
+    function test_keys() public {
+        string[] memory keys = vm.parseYamlKeys(yaml, ".nested");
+        assertEq(keys.length, 3);
+        assertEq(keys[0], "number");
+        assertEq(keys[1], "name");
+        assertEq(keys[2], "deep");
+    }
+
+    function test_invalidYaml() public {
+        vm._expectCheatcodeRevert();
+        vm.parseYamlUint("key: [unclosed", ".key");
+    }
+}
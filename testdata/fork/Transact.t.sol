@@ -46,6 +46,33 @@ contract TransactOnForkTest is DSTest {
         assert(sender.balance < expectedSenderBalance);
     }
 
+    function testTransactWithOverrides() public {
+        // A random block https://etherscan.io/block/17134913
+        uint256 fork = vm.createFork("rpcAlias", 17134913);
+        vm.selectFork(fork);
+        // a random transfer transaction in the next block: https://etherscan.io/tx/0xaf6201d435b216a858c580e20512a16136916d894aa33260650e164e3238c771
+        bytes32 tx = 0xaf6201d435b216a858c580e20512a16136916d894aa33260650e164e3238c771;
+
+        address sender = address(0x9B315A70FEe05a70A9F2c832E93a7095FEb32Bfe);
+        address recipient = address(0xDB358B93157Df9b3B1eE9Ea5CDB7D0aE9a1D8110);
+        address newSender = address(0xbeef);
+        vm.deal(newSender, 1 ether);
+
+        uint256 senderBalance = sender.balance;
+        uint256 expectedRecipientBalance = recipient.balance + 0.5 ether;
+
+        // execute the transaction from another sender, with another value and the same calldata
+        vm.transactWithOverrides(tx, newSender, 0.5 ether, "");
+
+        // recipient received the overridden value
+        assertEq(recipient.balance, expectedRecipientBalance);
+
+        // the new sender paid for the transaction instead of the original one
+        assertEq(sender.balance, senderBalance);
+        assert(newSender.balance < 0.5 ether);
+        assertEq(vm.getNonce(newSender), 1);
+    }
+
     function testTransactCooperatesWithCheatcodes() public {
         // A random block https://etherscan.io/block/16260609
         uint256 fork = vm.createFork("rpcAlias", 16260609);
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+
+contract FuzzCoverageGuidedTest is DSTest {
+    /**
+     * forge-config: default.fuzz.runs = 20000
+     * forge-config: default.fuzz.coverage-guided = true
+     */
+    function testStagedBranches(uint256 a, uint256 b) public {
+        // The branches depend on the hashes of the inputs, so the dictionary can't supply the values
+        // reaching them, and random inputs only reach the inner one once in 65536 runs.
+        if (uint8(uint256(keccak256(abi.encode(a)))) == 0) {
+            if (uint8(uint256(keccak256(abi.encode(b)))) == 0) {
+                revert("found");
+            }
+        }
+    }
+}
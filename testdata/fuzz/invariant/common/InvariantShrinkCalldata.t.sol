@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+
+contract ThresholdBreaker {
+    bool public broken;
+
+    function setValue(uint256 value, address account) public {
+        if (value > 1000 && account != address(this)) {
+            broken = true;
+        }
+    }
+}
+
+contract InvariantShrinkCalldata is DSTest {
+    ThresholdBreaker breaker;
+
+    function setUp() public {
+        breaker = new ThresholdBreaker();
+    }
+
+    /**
+     * forge-config: default.invariant.shrink-calldata = true
+     */
+    function invariantNotBroken() public {
+        require(!breaker.broken(), "broken");
+    }
+}
@@ -0,0 +1,50 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "../../../cheats/Vm.sol";
+
+contract Vault {
+    address public approved;
+    address public depositor;
+    bool public withdrawn;
+
+    function approve() public {
+        approved = msg.sender;
+        depositor = address(0);
+    }
+
+    function deposit() public {
+        if (approved == msg.sender) {
+            depositor = msg.sender;
+        }
+        approved = address(0);
+    }
+
+    function withdraw() public {
+        if (depositor != address(0) && depositor == msg.sender) {
+            withdrawn = true;
+        }
+        depositor = address(0);
+    }
+}
+
+contract TargetFlows is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    Vault vault;
+
+    function setUp() public {
+        vault = new Vault();
+
+        bytes4[] memory selectors = new bytes4[](3);
+        selectors[0] = Vault.approve.selector;
+        selectors[1] = Vault.deposit.selector;
+        selectors[2] = Vault.withdraw.selector;
+        vm.targetFlow(address(vault), selectors);
+    }
+
+    function invariantNeverWithdrawn() public {
+        require(!vault.withdrawn(), "withdrawn");
+    }
+}
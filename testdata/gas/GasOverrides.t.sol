@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+
+// Run with `SLOAD = 800` in the gas overrides.
+contract GasOverridesTest is DSTest {
+    uint256 value = 1;
+
+    function sloadGas() internal view returns (uint256 gasUsed) {
+        assembly {
+            let start := gas()
+            pop(sload(value.slot))
+            gasUsed := sub(start, gas())
+        }
+    }
+
+    function mloadGas() internal pure returns (uint256 gasUsed) {
+        assembly {
+            let start := gas()
+            pop(mload(0))
+            gasUsed := sub(start, gas())
+        }
+    }
+
+    function testSloadOverride() public {
+        // `MLOAD` costs 3, and both cold and warm `SLOAD`s cost the override
+        assertEq(sloadGas() - mloadGas(), 797);
+        assertEq(sloadGas() - mloadGas(), 797);
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+import "../cheats/Vm.sol";
+
+contract SymbolicInlineConf is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    /// forge-config: default.symbolic = true
+    function testInlineConfSymbolic(uint8 x, bytes memory data) public {
+        vm.assume(x < 100);
+        assertEq(data.length + x, x + data.length);
+        assertTrue(x != 100);
+    }
+
+    function testNotSymbolic() public {
+        assertTrue(true);
+    }
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity >=0.8.0;
+
+import "ds-test/test.sol";
+
+contract TableInlineConf is DSTest {
+    function swap(uint256 amountIn, uint256 fee) internal pure returns (uint256) {
+        return amountIn * (10_000 - fee) / 10_000;
+    }
+
+    /// forge-config: default.fixtures = "fixtures/Table/swap_cases.json"
+    function testInlineConfTableJson(uint256 amountIn, uint256 fee, uint256 expected) public {
+        assertEq(swap(amountIn, fee), expected);
+    }
+
+    /// forge-config: default.fixtures = "fixtures/Table/swap_cases.toml"
+    function testInlineConfTableToml(uint256 amountIn, uint256 fee, uint256 expected) public {
+        assertEq(swap(amountIn, fee), expected);
+    }
+}
@@ -0,0 +1,60 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+
+contract LifecycleHooksTest is DSTest {
+    uint256 counter;
+
+    function setUp() public {
+        counter = 1;
+    }
+
+    function beforeEach() public {
+        counter += 1;
+    }
+
+    function afterEach() public {
+        // `afterEach` runs on the state left by the test
+        assertTrue(counter == 10 || counter == 20);
+    }
+
+    function afterAll() public {
+        // `afterAll` runs on the state left by `setUp`
+        assertEq(counter, 1);
+    }
+
+    function testBeforeEachRuns() public {
+        assertEq(counter, 2);
+        counter = 10;
+    }
+
+    function testBeforeEachResets() public {
+        assertEq(counter, 2);
+        counter = 20;
+    }
+}
+
+contract FailingBeforeEachTest is DSTest {
+    function beforeEach() public {
+        require(false, "beforeEach failed predictably");
+    }
+
+    function testNeverRuns() public {}
+}
+
+contract FailingAfterEachTest is DSTest {
+    function afterEach() public {
+        require(false, "afterEach failed predictably");
+    }
+
+    function testPasses() public {}
+}
+
+contract FailingAfterAllTest is DSTest {
+    function afterAll() public {
+        assertTrue(false);
+    }
+
+    function testPasses() public {}
+}